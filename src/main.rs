@@ -6,16 +6,31 @@
 #[macro_use]
 extern crate clap;
 
-use clap::{App, AppSettings, ArgMatches, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, Shell, SubCommand};
 use dotenv::dotenv;
 use env_logger::Builder;
-use log::Record;
+use log::{error, warn, Record};
+use serde_json::json;
 use std::env;
-use std::io::Write;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+use t_rex_core::cache::{sync, S3Cache};
+use t_rex_core::core::config_upgrade;
+use t_rex_core::core::{parse_config, read_config, ApplicationCfg};
+use t_rex_core::mvt::tile::Tile;
+use t_rex_core::mvt::validator;
+use t_rex_service::seed_coordination::{WorkPartition, WorkQueue};
+use t_rex_service::tune;
 use t_rex_webserver as webserver;
 use tile_grid::Extent;
 use time;
 
+/// Print result for consumption by scripts
+fn print_json(value: &serde_json::Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
 fn init_logger(args: &ArgMatches<'_>) {
     let mut builder = Builder::new();
     builder.format(|buf, record: &Record<'_>| {
@@ -44,12 +59,59 @@ fn init_logger(args: &ArgMatches<'_>) {
     builder.init();
 }
 
+fn package(args: &ArgMatches<'_>) {
+    let config = webserver::config_from_args(&args);
+    let mut service = webserver::service_from_args(&config, &args);
+    let tileset = args.value_of("tileset").unwrap();
+    let minzoom = args.value_of("minzoom").map_or(0, |s| {
+        s.parse::<u8>()
+            .expect("Error parsing 'minzoom' as integer value")
+    });
+    let maxzoom = args.value_of("maxzoom").map_or(14, |s| {
+        s.parse::<u8>()
+            .expect("Error parsing 'maxzoom' as integer value")
+    });
+    let arr: Vec<f64> = args
+        .value_of("extent")
+        .unwrap()
+        .split(",")
+        .map(|v| {
+            v.parse()
+                .expect("Error parsing 'extent' as list of float values")
+        })
+        .collect();
+    if arr.len() != 4 {
+        error!("Expected extent minx,miny,maxx,maxy");
+        process::exit(1);
+    }
+    let extent = Extent {
+        minx: arr[0],
+        miny: arr[1],
+        maxx: arr[2],
+        maxy: arr[3],
+    };
+    service.prepare_feature_queries();
+    match service.offline_package(tileset, &extent, minzoom, maxzoom) {
+        Ok(package) => {
+            let output = args.value_of("output").unwrap();
+            if let Err(e) = fs::write(output, package) {
+                error!("Error writing {}: {}", output, e);
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            error!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
 fn generate(args: &ArgMatches<'_>) {
     let config = webserver::config_from_args(&args);
     let mut service = webserver::service_from_args(&config, &args);
-    config
-        .cache
-        .expect("Missing configuration entry base in [cache.file]");
+    if config.cache.is_none() && config.tilesets.iter().all(|ts| ts.cache.is_none()) {
+        panic!("Missing configuration entry base in [cache.file]");
+    }
     let tileset = args.value_of("tileset");
     let minzoom = args.value_of("minzoom").map(|s| {
         s.parse::<u8>()
@@ -96,6 +158,16 @@ fn generate(args: &ArgMatches<'_>) {
         s.parse::<u8>()
             .expect("Error parsing 'nodeno' as integer value")
     });
+    let partition = if let Some(dir) = args.value_of("work-queue") {
+        WorkPartition::Queue(WorkQueue::new(dir))
+    } else if let Some(shard) = args.value_of("shard") {
+        WorkPartition::from_shard(shard).unwrap_or_else(|e| {
+            error!("{}", e);
+            process::exit(1)
+        })
+    } else {
+        WorkPartition::from_nodes(nodes, nodeno)
+    };
     let progress = args.value_of("progress").map_or(true, |s| {
         s.parse::<bool>()
             .expect("Error parsing 'progress' as boolean value")
@@ -104,17 +176,28 @@ fn generate(args: &ArgMatches<'_>) {
         s.parse::<bool>()
             .expect("Error parsing 'overwrite' as boolean value")
     });
+    let attr_stats = args.value_of("attr-stats").map_or(false, |s| {
+        s.parse::<bool>()
+            .expect("Error parsing 'attr-stats' as boolean value")
+    });
     service.prepare_feature_queries();
+    if let Some(addr) = args.value_of("metrics") {
+        if let Err(e) = webserver::serve_metrics(service.clone(), addr) {
+            error!("Can not serve metrics on {}: {}", addr, e);
+            process::exit(1);
+        }
+    }
     service.generate(
         tileset,
         minzoom,
         maxzoom,
         extent,
-        nodes,
-        nodeno,
+        &partition,
         progress,
         overwrite,
         extent_srid,
+        attr_stats,
+        args.value_of("since"),
     );
 }
 
@@ -146,9 +229,238 @@ fn drilldown(args: &ArgMatches<'_>) {
         s.parse::<bool>()
             .expect("Error parsing 'progress' as boolean value")
     });
+    let count_only = args.is_present("count-only");
+    service.prepare_feature_queries();
+    let stats = service.drilldown(tileset, minzoom, maxzoom, points, progress, count_only);
+    if args.is_present("json") {
+        print_json(&stats.as_json().unwrap());
+    } else {
+        print!("{}", stats.as_csv());
+    }
+}
+
+fn tune(args: &ArgMatches<'_>) {
+    let config = webserver::config_from_args(&args);
+    let mut service = webserver::service_from_args(&config, &args);
+    let path = args.value_of("config").unwrap();
+    let config_text = fs::read_to_string(path).unwrap_or_else(|e| {
+        error!("Could not read config file {}: {}", path, e);
+        process::exit(1)
+    });
+    let defaults = tune::TuneOptions::default();
+    let parse_num = |name: &str| {
+        args.value_of(name).map(|s| {
+            s.parse::<u64>()
+                .unwrap_or_else(|_| panic!("Error parsing '{}' as integer value", name))
+        })
+    };
+    let options = tune::TuneOptions {
+        tileset: args.value_of("tileset").map(|s| s.to_string()),
+        minzoom: parse_num("minzoom").map(|z| z as u8),
+        maxzoom: parse_num("maxzoom").map(|z| z as u8).or(defaults.maxzoom),
+        samples: parse_num("samples").map_or(defaults.samples, |n| n as u32),
+        max_layer_size: parse_num("max-layer-size").map_or(defaults.max_layer_size, |kb| kb * 1024),
+        max_features: parse_num("max-features").unwrap_or(defaults.max_features),
+    };
     service.prepare_feature_queries();
-    let stats = service.drilldown(tileset, minzoom, maxzoom, points, progress);
-    print!("{}", stats.as_csv());
+    let (samples, attr_stats) = tune::sample_layers(&service, &options);
+    for line in tune::report(&samples) {
+        eprintln!("{}", line);
+    }
+    let suggestions = tune::suggestions(&samples, &attr_stats, &options);
+    let json = args.is_present("json");
+    if json {
+        let suggestions: Vec<_> = suggestions
+            .iter()
+            .map(|s| {
+                json!({
+                    "tileset": s.tileset,
+                    "layer": s.layer,
+                    "key": s.key,
+                    "value": s.value,
+                    "reason": s.reason,
+                })
+            })
+            .collect();
+        print_json(&json!(suggestions));
+    }
+    for s in &suggestions {
+        match s.value {
+            Some(ref value) => eprintln!(
+                "Layer '{}/{}': {} = {} - {}",
+                s.tileset, s.layer, s.key, value, s.reason
+            ),
+            None => eprintln!("Layer '{}/{}': {}", s.tileset, s.layer, s.reason),
+        }
+    }
+    let patched = tune::patch_config(&config_text, &suggestions);
+    match args.value_of("output") {
+        Some(output) => {
+            if let Err(e) = fs::write(output, patched) {
+                error!("Error writing {}: {}", output, e);
+                process::exit(1);
+            }
+        }
+        None if !json => print!("{}", patched),
+        None => {}
+    }
+}
+
+fn inspect(args: &ArgMatches<'_>) {
+    let path = args.value_of("FILE").unwrap();
+    let data = fs::read(path).unwrap_or_else(|e| {
+        error!("Could not read tile {}: {}", path, e);
+        process::exit(1)
+    });
+    let tile = if data.starts_with(&[0x1f, 0x8b]) {
+        Tile::read_gz_from(&mut &data[..])
+    } else {
+        Tile::read_from(&mut &data[..])
+    }
+    .unwrap_or_else(|e| {
+        error!("Could not decode tile {}: {}", path, e);
+        process::exit(1)
+    });
+    let violations = if args.is_present("validate") {
+        Some(validator::validate(&tile))
+    } else {
+        None
+    };
+    if args.is_present("json") {
+        let layers: Vec<_> = tile
+            .get_layers()
+            .iter()
+            .map(|layer| {
+                json!({
+                    "name": layer.get_name(),
+                    "version": layer.get_version(),
+                    "extent": layer.get_extent(),
+                    "features": layer.get_features().len(),
+                    "keys": layer.get_keys().len(),
+                    "values": layer.get_values().len(),
+                })
+            })
+            .collect();
+        let mut result = json!({ "layers": layers });
+        if let Some(ref violations) = violations {
+            let violations: Vec<_> = violations
+                .iter()
+                .map(|v| json!({"layer": v.layer, "feature": v.feature, "message": v.message}))
+                .collect();
+            result["valid"] = json!(violations.is_empty());
+            result["violations"] = json!(violations);
+        }
+        print_json(&result);
+        if violations.map_or(false, |v| !v.is_empty()) {
+            process::exit(1);
+        }
+        return;
+    }
+    for layer in tile.get_layers() {
+        println!(
+            "Layer '{}': version {}, extent {}, {} features, {} keys, {} values",
+            layer.get_name(),
+            layer.get_version(),
+            layer.get_extent(),
+            layer.get_features().len(),
+            layer.get_keys().len(),
+            layer.get_values().len()
+        );
+    }
+    if let Some(violations) = violations {
+        for violation in &violations {
+            println!("{}", violation);
+        }
+        if !violations.is_empty() {
+            error!("{} specification violations", violations.len());
+            process::exit(1);
+        }
+        println!("Tile conforms to vector tile specification 2.1");
+    }
+}
+
+fn sync(args: &ArgMatches<'_>) {
+    let source = args.value_of("SOURCE").unwrap();
+    let destination = args.value_of("DEST").unwrap();
+    let (bucket, key_prefix) = sync::parse_s3_url(destination).unwrap_or_else(|e| {
+        error!("{}", e);
+        process::exit(1)
+    });
+    // S3 settings from [cache.s3] or from the command line and AWS environment variables
+    let s3_cfg = args.value_of("config").and_then(|path| {
+        let config: ApplicationCfg = read_config(path).unwrap_or_else(|e| {
+            error!("{}", e);
+            process::exit(1)
+        });
+        config.cache.and_then(|cache| cache.s3)
+    });
+    let env_var = |name: &str| env::var(name).unwrap_or_default();
+    let cache = match s3_cfg {
+        Some(cfg) => S3Cache::new(
+            &cfg.endpoint,
+            &bucket,
+            &cfg.access_key,
+            &cfg.secret_key,
+            &cfg.region,
+            None,
+            key_prefix,
+            cfg.gzip_header_enabled,
+        ),
+        None => S3Cache::new(
+            args.value_of("endpoint")
+                .unwrap_or("https://s3.amazonaws.com"),
+            &bucket,
+            &env_var("AWS_ACCESS_KEY_ID"),
+            &env_var("AWS_SECRET_ACCESS_KEY"),
+            args.value_of("region").unwrap_or("us-east-1"),
+            None,
+            key_prefix,
+            None,
+        ),
+    };
+    let options = sync::SyncOptions {
+        threads: args.value_of("threads").map_or(8, |s| {
+            s.parse::<usize>()
+                .expect("Error parsing 'threads' as integer value")
+        }),
+        force: args.is_present("force"),
+    };
+    match sync::sync_cache(source, &cache, destination, &options) {
+        Ok(stats) => {
+            if args.is_present("json") {
+                print_json(&json!({
+                    "uploaded": stats.uploaded,
+                    "unchanged": stats.unchanged,
+                    "failed": stats.failed,
+                }));
+            } else {
+                println!(
+                    "{} files uploaded, {} unchanged, {} failed",
+                    stats.uploaded, stats.unchanged, stats.failed
+                );
+            }
+            if stats.failed > 0 {
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            error!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn upgrade_config(args: &ArgMatches<'_>) {
+    let path = args.value_of("config").unwrap();
+    let config = fs::read_to_string(path).unwrap_or_else(|e| {
+        error!("Could not read config file {}: {}", path, e);
+        process::exit(1)
+    });
+    let upgraded = config_upgrade::upgrade_config(&config);
+    if let Err(e) = parse_config::<ApplicationCfg>(upgraded.clone(), path) {
+        warn!("Upgraded configuration is not valid: {}", e);
+    }
+    print!("{}", upgraded);
 }
 
 #[cfg(feature = "with-gdal")]
@@ -163,7 +475,11 @@ fn version_info() -> String {
     );
     #[cfg(not(feature = "with-gdal"))]
     let version = crate_version!().to_string();
-    version
+    if cfg!(feature = "pure-rust") {
+        format!("{} (pure Rust file datasources)", version)
+    } else {
+        version
+    }
 }
 
 fn main() {
@@ -175,7 +491,7 @@ fn main() {
         .author("Pirmin Kalberer <pka@sourcepole.ch>")
         .about("vector tile server specialized on publishing MVT tiles from your own data")
         .subcommand(SubCommand::with_name("serve")
-                        .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME'
+                        .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME, MySQL connection mysql://USER@HOST/DBNAME, SQL Server connection mssql://USER@HOST/DBNAME, Oracle ORDS connection ords://USER@HOST/ords/SCHEMA, MongoDB connection mongodb://USER@HOST/DBNAME or Elasticsearch connection elasticsearch://USER@HOST'
                                               --datasource=[FILE_OR_GDAL_DS] 'GDAL datasource specification'
                                               --detect-geometry-types=[true|false] 'Detect geometry types when undefined'
                                               --qgs=[FILE] 'QGIS project file'
@@ -184,13 +500,16 @@ fn main() {
                                               --clip=[true|false] 'Clip geometries'
                                               --no-transform=[true|false] 'Do not transform to grid SRS'
                                               --cache=[DIR] 'Use tile cache in DIR'
+                                              --record=[DIR] 'Record retrieved features in DIR for replay datasources'
                                               -c, --config=[FILE] 'Load from custom config file'
+                                              --strict-config 'Reject unknown configuration keys'
                                               --bind=[IPADDRESS] 'Bind web server to this address (0.0.0.0 for all)'
                                               --port=[PORT] 'Bind web server to this port'
+                                              --hardened 'Safe defaults for public internet exposure (rate limits, no admin and debug endpoints)'
                                               --openbrowser=[true|false] 'Open backend URL in browser'")
                         .about("Start web server and serve MVT vector tiles"))
         .subcommand(SubCommand::with_name("genconfig")
-                        .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME'
+                        .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME, MySQL connection mysql://USER@HOST/DBNAME, SQL Server connection mssql://USER@HOST/DBNAME, Oracle ORDS connection ords://USER@HOST/ords/SCHEMA, MongoDB connection mongodb://USER@HOST/DBNAME or Elasticsearch connection elasticsearch://USER@HOST'
                                               --datasource=[FILE_OR_GDAL_DS] 'GDAL datasource specification'
                                               --detect-geometry-types=[true|false] 'Detect geometry types when undefined'
                                               --qgs=[FILE] 'QGIS project file'
@@ -202,6 +521,7 @@ fn main() {
         .subcommand(SubCommand::with_name("generate")
                         .setting(AppSettings::AllowLeadingHyphen)
                         .args_from_usage("-c, --config=<FILE> 'Load from custom config file'
+                                              --strict-config 'Reject unknown configuration keys'
                                               --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'
                                               --tileset=[NAME] 'Tileset name'
                                               --minzoom=[LEVEL] 'Minimum zoom level'
@@ -209,19 +529,77 @@ fn main() {
                                               --extent=[minx,miny,maxx,maxy[,srid]] 'Extent of tiles'
                                               --nodes=[NUM] 'Number of generator nodes'
                                               --nodeno=[NUM] 'Number of this nodes (0 <= n < nodes)'
+                                              --shard=[n/count] 'Generate shard n of count (1 <= n <= count)'
+                                              --work-queue=[DIR] 'Claim tiles from work queue directory shared between instances (cleared when starting after a completed run)'
                                               --progress=[true|false] 'Show progress bar'
-                                              --overwrite=[false|true] 'Overwrite previously cached tiles'")
+                                              --overwrite=[false|true] 'Overwrite previously cached tiles'
+                                              --since=[TIMESTAMP] 'Only re-render tiles of features changed since timestamp (layer changes_sql)'
+                                              --attr-stats=[false|true] 'Report attribute statistics of generated tiles'
+                                              --record=[DIR] 'Record retrieved features in DIR for replay datasources'
+                                              --metrics=[ADDR] 'Serve Prometheus metrics while seeding (e.g. 127.0.0.1:9100)'")
                         .about("Generate tiles for cache"))
+        .subcommand(SubCommand::with_name("package")
+                        .setting(AppSettings::AllowLeadingHyphen)
+                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file'
+                                              --strict-config 'Reject unknown configuration keys'
+                                              --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'
+                                              --tileset=<NAME> 'Tileset name'
+                                              --minzoom=[LEVEL] 'Minimum zoom level (Default: 0)'
+                                              --maxzoom=[LEVEL] 'Maximum zoom level (Default: 14)'
+                                              --extent=<minx,miny,maxx,maxy> 'Extent of package in WGS84'
+                                              --output=<FILE> 'PMTiles output file'")
+                        .about("Create PMTiles package for offline use"))
         .subcommand(SubCommand::with_name("drilldown")
                         .setting(AppSettings::AllowLeadingHyphen)
                         .args_from_usage("-c, --config=<FILE> 'Load from custom config file'
+                                              --strict-config 'Reject unknown configuration keys'
                                               --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'
                                               --tileset=[NAME] 'Tileset name'
                                               --minzoom=[LEVEL] 'Minimum zoom level'
                                               --maxzoom=[LEVEL] 'Maximum zoom level'
                                               --points=[x1,y1,x2,y2,..] 'Drilldown points'
-                                              --progress=[true|false] 'Show progress bar'")
-                        .about("Tile layer statistics"));
+                                              --progress=[true|false] 'Show progress bar'
+                                              --count-only 'Count features without generating tiles'
+                                              --json 'Print statistics as JSON instead of CSV'")
+                        .about("Tile layer statistics"))
+        .subcommand(SubCommand::with_name("tune")
+                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file'
+                                              --strict-config 'Reject unknown configuration keys'
+                                              --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'
+                                              --tileset=[NAME] 'Tileset name'
+                                              --minzoom=[LEVEL] 'Minimum zoom level'
+                                              --maxzoom=[LEVEL] 'Maximum zoom level (Default: 14)'
+                                              --samples=[NUM] 'Sampled tiles per zoom level (Default: 16)'
+                                              --max-layer-size=[KB] 'Layer size budget per tile (Default: 250)'
+                                              --max-features=[NUM] 'Features per tile and layer (Default: 5000)'
+                                              --output=[FILE] 'Write patched config file (Default: stdout)'
+                                              --json 'Print suggestions as JSON instead of patched config'")
+                        .about("Sample tiles and suggest layer minzoom, simplification and attribute settings"))
+        .subcommand(SubCommand::with_name("inspect")
+                        .args_from_usage("<FILE> 'Vector tile file (optionally gzip compressed)'
+                                              --validate 'Check conformance with vector tile specification 2.1'
+                                              --json 'Print layers and violations as JSON'
+                                              --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'")
+                        .about("Show content of vector tile file"))
+        .subcommand(SubCommand::with_name("sync")
+                        .args_from_usage("<SOURCE> 'Local tile cache directory'
+                                              <DEST> 'Destination (s3://bucket/prefix)'
+                                              -c, --config=[FILE] 'Config file with [cache.s3] settings'
+                                              --endpoint=[URL] 'S3 endpoint (Default: https://s3.amazonaws.com)'
+                                              --region=[REGION] 'S3 region (Default: us-east-1)'
+                                              --threads=[NUM] 'Number of parallel uploads (Default: 8)'
+                                              --force 'Upload all files, not only new and changed ones'
+                                              --json 'Print upload statistics as JSON'
+                                              --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'")
+                        .about("Upload new and changed tiles of a local cache to S3"))
+        .subcommand(SubCommand::with_name("upgrade-config")
+                        .args_from_usage("-c, --config=<FILE> 'Config file of an older t-rex version'
+                                              --loglevel=[error|warn|info|debug|trace] 'Log level (Default: info)'")
+                        .about("Convert configuration to current format"))
+        .subcommand(SubCommand::with_name("completions")
+                        .arg(Arg::from_usage("<SHELL> 'Target shell'")
+                                 .possible_values(&Shell::variants()))
+                        .about("Generate shell completion script"));
 
     match app.get_matches_from_safe_borrow(env::args()) {
         //app.get_matches() prohibits later call of app.print_help()
@@ -241,10 +619,34 @@ fn main() {
                 init_logger(sub_m);
                 generate(sub_m);
             }
+            ("package", Some(sub_m)) => {
+                init_logger(sub_m);
+                package(sub_m);
+            }
             ("drilldown", Some(sub_m)) => {
                 init_logger(sub_m);
                 drilldown(sub_m);
             }
+            ("tune", Some(sub_m)) => {
+                init_logger(sub_m);
+                tune(sub_m);
+            }
+            ("inspect", Some(sub_m)) => {
+                init_logger(sub_m);
+                inspect(sub_m);
+            }
+            ("sync", Some(sub_m)) => {
+                init_logger(sub_m);
+                sync(sub_m);
+            }
+            ("upgrade-config", Some(sub_m)) => {
+                init_logger(sub_m);
+                upgrade_config(sub_m);
+            }
+            ("completions", Some(sub_m)) => {
+                let shell = value_t!(sub_m, "SHELL", Shell).unwrap_or_else(|e| e.exit());
+                app.gen_completions_to("t_rex", shell, &mut io::stdout());
+            }
             _ => {
                 let _ = app.print_help();
                 println!("");
@@ -5,6 +5,7 @@
 
 use std::io;
 use std::io::Read;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub trait Cache {
     fn info(&self) -> String;
@@ -15,6 +16,53 @@ pub trait Cache {
         F: FnMut(&mut dyn Read);
     fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error>;
     fn exists(&self, path: &str) -> bool;
+    /// Remove cached object. Removing a missing object is not an error.
+    fn remove(&self, _path: &str) -> Result<(), io::Error> {
+        Ok(())
+    }
+    /// Local file path of a cached object for streaming it directly
+    fn local_path(&self, _path: &str) -> Option<String> {
+        None
+    }
+    /// Time since a cached object was written, if known
+    fn age(&self, _path: &str) -> Option<Duration> {
+        None
+    }
+    /// Acquire a render lease on path for `ttl`, unless another owner holds an unexpired lease.
+    /// Caches not shared between instances always grant the lease.
+    fn try_lease(&self, _path: &str, _owner: &str, _ttl: Duration) -> bool {
+        true
+    }
+    /// Release render lease of owner
+    fn release_lease(&self, _path: &str, _owner: &str) {}
+}
+
+/// Path of the lease object for a cached object
+pub fn lease_path(path: &str) -> String {
+    format!("{}.lease", path)
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Lease object content with owner and expiration time
+pub fn lease_content(owner: &str, ttl: Duration) -> String {
+    format!("{} {}", owner, unix_time() + ttl.as_secs())
+}
+
+/// Owner of an unexpired lease
+pub fn lease_owner(content: &str) -> Option<&str> {
+    let (owner, expires) = content.trim().rsplit_once(' ')?;
+    let expires: u64 = expires.parse().ok()?;
+    if expires > unix_time() {
+        Some(owner)
+    } else {
+        None
+    }
 }
 
 #[derive(Clone)]
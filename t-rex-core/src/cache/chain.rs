@@ -0,0 +1,102 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::cache::cache::Cache;
+use crate::cache::Tilecache;
+use std::io::{self, Read};
+use std::time::Duration;
+
+/// Caches queried in order, e.g. local file cache before S3
+#[derive(Clone)]
+pub struct CacheChain {
+    pub caches: Vec<Tilecache>,
+    /// Write tiles found in a later cache into the preceding caches
+    pub backfill: bool,
+}
+
+impl CacheChain {
+    // Not generic, to avoid endless instantiation of nested generic calls
+    fn read_chain(&self, path: &str, read: &mut dyn FnMut(&mut dyn Read)) -> bool {
+        for (no, cache) in self.caches.iter().enumerate() {
+            if no == 0 || !self.backfill {
+                if cache.read(path, &mut *read) {
+                    return true;
+                }
+                continue;
+            }
+            let mut data = Vec::new();
+            if cache.read(path, |f| {
+                let _ = f.read_to_end(&mut data);
+            }) {
+                for faster in &self.caches[..no] {
+                    if let Err(e) = faster.write(path, &data) {
+                        warn!("Backfilling {} into {} failed: {}", path, faster.info(), e);
+                    }
+                }
+                read(&mut data.as_slice());
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Cache for CacheChain {
+    fn info(&self) -> String {
+        let infos: Vec<String> = self.caches.iter().map(|c| c.info()).collect();
+        format!("Tile cache chain: {}", infos.join(" -> "))
+    }
+    fn baseurl(&self) -> String {
+        self.caches
+            .first()
+            .map(|c| c.baseurl())
+            .unwrap_or("http://localhost:6767".to_string())
+    }
+    fn read<F>(&self, path: &str, mut read: F) -> bool
+    where
+        F: FnMut(&mut dyn Read),
+    {
+        self.read_chain(path, &mut read)
+    }
+    /// Write into first cache of chain
+    fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
+        match self.caches.first() {
+            Some(cache) => cache.write(path, obj),
+            None => Ok(()),
+        }
+    }
+    fn exists(&self, path: &str) -> bool {
+        self.caches.iter().any(|c| c.exists(path))
+    }
+    /// Remove from all caches of chain
+    fn remove(&self, path: &str) -> Result<(), io::Error> {
+        for cache in &self.caches {
+            cache.remove(path)?;
+        }
+        Ok(())
+    }
+    fn local_path(&self, path: &str) -> Option<String> {
+        self.caches.first().and_then(|c| c.local_path(path))
+    }
+    /// Lease in the last cache of chain, usually the one shared between instances
+    fn try_lease(&self, path: &str, owner: &str, ttl: Duration) -> bool {
+        match self.caches.last() {
+            Some(cache) => cache.try_lease(path, owner, ttl),
+            None => true,
+        }
+    }
+    fn release_lease(&self, path: &str, owner: &str) {
+        if let Some(cache) = self.caches.last() {
+            cache.release_lease(path, owner);
+        }
+    }
+    /// Age of the object in the first cache containing it
+    fn age(&self, path: &str) -> Option<Duration> {
+        self.caches
+            .iter()
+            .find(|c| c.exists(path))
+            .and_then(|c| c.age(path))
+    }
+}
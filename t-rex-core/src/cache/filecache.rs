@@ -3,10 +3,11 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::cache::cache::Cache;
-use std::fs::{self, File};
+use crate::cache::cache::{lease_content, lease_owner, lease_path, Cache};
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, Read, Write};
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Clone)]
 pub struct Filecache {
@@ -50,4 +51,70 @@ impl Cache for Filecache {
         let fullpath = format!("{}/{}", self.basepath, path);
         Path::new(&fullpath).exists()
     }
+    fn remove(&self, path: &str) -> Result<(), io::Error> {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        debug!("Filecache.remove {}", fullpath);
+        match fs::remove_file(&fullpath) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+    fn local_path(&self, path: &str) -> Option<String> {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        if Path::new(&fullpath).is_file() {
+            Some(fullpath)
+        } else {
+            None
+        }
+    }
+    fn try_lease(&self, path: &str, owner: &str, ttl: Duration) -> bool {
+        let fullpath = format!("{}/{}", self.basepath, lease_path(path));
+        if let Some(parent) = Path::new(&fullpath).parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        // Retry once after removing an expired lease
+        for _ in 0..2 {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&fullpath)
+            {
+                Ok(mut f) => {
+                    let _ = f.write_all(lease_content(owner, ttl).as_bytes());
+                    return true;
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    let content = fs::read_to_string(&fullpath).unwrap_or_default();
+                    match lease_owner(&content) {
+                        Some(holder) => return holder == owner,
+                        None => {
+                            debug!("Filecache: removing expired lease {}", fullpath);
+                            let _ = fs::remove_file(&fullpath);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Filecache: lease {} failed: {}", fullpath, e);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    fn release_lease(&self, path: &str, owner: &str) {
+        let fullpath = format!("{}/{}", self.basepath, lease_path(path));
+        let content = fs::read_to_string(&fullpath).unwrap_or_default();
+        if lease_owner(&content) == Some(owner) {
+            let _ = fs::remove_file(&fullpath);
+        }
+    }
+    fn age(&self, path: &str) -> Option<Duration> {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        fs::metadata(&fullpath)
+            .ok()?
+            .modified()
+            .ok()?
+            .elapsed()
+            .ok()
+    }
 }
@@ -27,6 +27,7 @@ fn test_dircache() {
 
     // Cache miss
     assert_eq!(cache.read(path, |_| {}), false);
+    assert_eq!(cache.local_path(path), None);
 
     // Write into cache
     let _ = cache.write(path, obj.as_bytes());
@@ -34,6 +35,7 @@ fn test_dircache() {
 
     // Cache hit
     assert_eq!(cache.read(path, |_| {}), true);
+    assert_eq!(cache.local_path(path), Some(fullpath.clone()));
 
     // Read from cache
     let mut s = String::new();
@@ -41,4 +43,38 @@ fn test_dircache() {
         let _ = f.read_to_string(&mut s);
     });
     assert_eq!(&s, "0123456789");
+
+    // Remove from cache
+    assert!(cache.remove(path).is_ok());
+    assert!(!cache.exists(path));
+    assert!(cache.remove(path).is_ok());
+}
+
+#[test]
+fn test_lease() {
+    use std::env;
+    use std::time::Duration;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_lease");
+    let basepath = format!("{}", &dir.display());
+    let _ = fs::remove_dir_all(&basepath);
+
+    let cache = Filecache {
+        basepath: basepath,
+        baseurl: None,
+    };
+    let path = "tileset/0/1/2.pbf";
+    let ttl = Duration::from_secs(30);
+    assert!(cache.try_lease(path, "a", ttl));
+    assert!(cache.try_lease(path, "a", ttl));
+    assert!(!cache.try_lease(path, "b", ttl));
+    // Only the owner releases a lease
+    cache.release_lease(path, "b");
+    assert!(!cache.try_lease(path, "b", ttl));
+    cache.release_lease(path, "a");
+    assert!(cache.try_lease(path, "b", Duration::from_secs(0)));
+    // Expired lease
+    assert!(cache.try_lease(path, "a", ttl));
+    assert!(!cache.exists(path));
 }
@@ -4,28 +4,38 @@
 //
 
 pub mod cache;
+pub mod chain;
 pub mod filecache;
 pub mod s3cache;
+pub mod sync;
 
 #[cfg(test)]
-mod filecache_test;
+mod chain_test;
 #[cfg(test)]
+mod filecache_test;
+#[cfg(all(test, feature = "http"))]
 mod s3cache_test;
+#[cfg(test)]
+mod sync_test;
 
 pub use self::cache::Cache;
 pub use self::cache::Nocache;
+pub use self::chain::CacheChain;
 pub use self::filecache::Filecache;
 pub use self::s3cache::S3Cache;
+use crate::core::config::CacheCfg;
 use crate::core::ApplicationCfg;
 use crate::core::Config;
 use std::io;
 use std::io::Read;
+use std::time::Duration;
 
 #[derive(Clone)]
 pub enum Tilecache {
     Nocache(Nocache),
     Filecache(Filecache),
     S3Cache(S3Cache),
+    Chain(CacheChain),
 }
 
 impl Cache for Tilecache {
@@ -34,6 +44,7 @@ impl Cache for Tilecache {
             &Tilecache::Nocache(ref cache) => cache.info(),
             &Tilecache::Filecache(ref cache) => cache.info(),
             &Tilecache::S3Cache(ref cache) => cache.info(),
+            &Tilecache::Chain(ref cache) => cache.info(),
         }
     }
     fn baseurl(&self) -> String {
@@ -41,6 +52,7 @@ impl Cache for Tilecache {
             &Tilecache::Nocache(ref cache) => cache.baseurl(),
             &Tilecache::Filecache(ref cache) => cache.baseurl(),
             &Tilecache::S3Cache(ref cache) => cache.baseurl(),
+            &Tilecache::Chain(ref cache) => cache.baseurl(),
         }
     }
     fn read<F>(&self, path: &str, read: F) -> bool
@@ -51,6 +63,7 @@ impl Cache for Tilecache {
             &Tilecache::Nocache(ref cache) => cache.read(path, read),
             &Tilecache::Filecache(ref cache) => cache.read(path, read),
             &Tilecache::S3Cache(ref cache) => cache.read(path, read),
+            &Tilecache::Chain(ref cache) => cache.read(path, read),
         }
     }
     fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
@@ -58,6 +71,7 @@ impl Cache for Tilecache {
             &Tilecache::Nocache(ref cache) => cache.write(path, obj),
             &Tilecache::Filecache(ref cache) => cache.write(path, obj),
             &Tilecache::S3Cache(ref cache) => cache.write(path, obj),
+            &Tilecache::Chain(ref cache) => cache.write(path, obj),
         }
     }
     fn exists(&self, path: &str) -> bool {
@@ -65,42 +79,112 @@ impl Cache for Tilecache {
             &Tilecache::Nocache(ref cache) => cache.exists(path),
             &Tilecache::Filecache(ref cache) => cache.exists(path),
             &Tilecache::S3Cache(ref cache) => cache.exists(path),
+            &Tilecache::Chain(ref cache) => cache.exists(path),
+        }
+    }
+    fn remove(&self, path: &str) -> Result<(), io::Error> {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.remove(path),
+            &Tilecache::Filecache(ref cache) => cache.remove(path),
+            &Tilecache::S3Cache(ref cache) => cache.remove(path),
+            &Tilecache::Chain(ref cache) => cache.remove(path),
+        }
+    }
+    fn local_path(&self, path: &str) -> Option<String> {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.local_path(path),
+            &Tilecache::Filecache(ref cache) => cache.local_path(path),
+            &Tilecache::S3Cache(ref cache) => cache.local_path(path),
+            &Tilecache::Chain(ref cache) => cache.local_path(path),
+        }
+    }
+    fn age(&self, path: &str) -> Option<Duration> {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.age(path),
+            &Tilecache::Filecache(ref cache) => cache.age(path),
+            &Tilecache::S3Cache(ref cache) => cache.age(path),
+            &Tilecache::Chain(ref cache) => cache.age(path),
+        }
+    }
+    fn try_lease(&self, path: &str, owner: &str, ttl: Duration) -> bool {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.try_lease(path, owner, ttl),
+            &Tilecache::Filecache(ref cache) => cache.try_lease(path, owner, ttl),
+            &Tilecache::S3Cache(ref cache) => cache.try_lease(path, owner, ttl),
+            &Tilecache::Chain(ref cache) => cache.try_lease(path, owner, ttl),
+        }
+    }
+    fn release_lease(&self, path: &str, owner: &str) {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.release_lease(path, owner),
+            &Tilecache::Filecache(ref cache) => cache.release_lease(path, owner),
+            &Tilecache::S3Cache(ref cache) => cache.release_lease(path, owner),
+            &Tilecache::Chain(ref cache) => cache.release_lease(path, owner),
         }
     }
 }
 
-impl<'a> Config<'a, ApplicationCfg> for Tilecache {
-    fn from_config(config: &ApplicationCfg) -> Result<Self, String> {
-        if config.cache.as_ref().is_none() {
-            return Ok(Tilecache::Nocache(Nocache));
+impl Tilecache {
+    /// Cache of global or tileset cache configuration
+    pub fn from_cache_cfg(cache: &CacheCfg) -> Result<Tilecache, String> {
+        if let Some(ref tiers) = cache.tiers {
+            let caches = tiers
+                .iter()
+                .map(|kind| Tilecache::single_cache(cache, kind))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Tilecache::Chain(CacheChain {
+                caches,
+                backfill: cache.backfill.unwrap_or(true),
+            }))
+        } else if cache.file.is_some() {
+            Tilecache::single_cache(cache, "file")
+        } else if cache.s3.is_some() {
+            Tilecache::single_cache(cache, "s3")
         } else {
-            config
-                .cache
+            Ok(Tilecache::Nocache(Nocache))
+        }
+    }
+    fn single_cache(cache: &CacheCfg, kind: &str) -> Result<Tilecache, String> {
+        match kind {
+            "file" => cache
+                .file
                 .as_ref()
-                .map(|cache| {
-                    if let Some(file_cache_cfg) = cache.file.as_ref() {
-                        let fc = Filecache {
-                            basepath: file_cache_cfg.base.clone(),
-                            baseurl: file_cache_cfg.baseurl.clone(),
-                        };
-                        Tilecache::Filecache(fc)
-                    } else if let Some(s3_cache_cfg) = cache.s3.as_ref() {
-                        let s3c = S3Cache::new(
-                            &s3_cache_cfg.endpoint.clone(),
-                            &s3_cache_cfg.bucket.clone(),
-                            &s3_cache_cfg.access_key.clone(),
-                            &s3_cache_cfg.secret_key.clone(),
-                            &s3_cache_cfg.region.clone(),
-                            s3_cache_cfg.baseurl.clone(),
-                            s3_cache_cfg.key_prefix.clone(),
-                            s3_cache_cfg.gzip_header_enabled.clone(),
-                        );
-                        Tilecache::S3Cache(s3c)
-                    } else {
-                        Tilecache::Nocache(Nocache)
-                    }
+                .map(|file_cache_cfg| {
+                    Tilecache::Filecache(Filecache {
+                        basepath: file_cache_cfg.base.clone(),
+                        baseurl: file_cache_cfg.baseurl.clone(),
+                    })
                 })
-                .ok_or("No Application Config found".to_string())
+                .ok_or("Cache tier 'file' requires [cache.file]".to_string()),
+            "s3" => cache
+                .s3
+                .as_ref()
+                .map(|s3_cache_cfg| {
+                    Tilecache::S3Cache(S3Cache::new(
+                        &s3_cache_cfg.endpoint.clone(),
+                        &s3_cache_cfg.bucket.clone(),
+                        &s3_cache_cfg.access_key.clone(),
+                        &s3_cache_cfg.secret_key.clone(),
+                        &s3_cache_cfg.region.clone(),
+                        s3_cache_cfg.baseurl.clone(),
+                        s3_cache_cfg.key_prefix.clone(),
+                        s3_cache_cfg.gzip_header_enabled.clone(),
+                    ))
+                })
+                .ok_or("Cache tier 's3' requires [cache.s3]".to_string()),
+            _ => Err(format!(
+                "Unknown cache tier '{}' (expected 'file' or 's3')",
+                kind
+            )),
+        }
+    }
+}
+
+impl<'a> Config<'a, ApplicationCfg> for Tilecache {
+    fn from_config(config: &ApplicationCfg) -> Result<Self, String> {
+        match config.cache.as_ref() {
+            Some(cache) => Tilecache::from_cache_cfg(cache),
+            None => Ok(Tilecache::Nocache(Nocache)),
         }
     }
     fn gen_config() -> String {
@@ -108,6 +192,23 @@ impl<'a> Config<'a, ApplicationCfg> for Tilecache {
 #[cache.file]
 #base = "/tmp/mvtcache"
 #baseurl = "http://example.com/tiles"
+# Coordinate rendering of missing tiles between instances sharing a cache
+#[cache.lease]
+#ttl = 30 # Lease duration in seconds
+#wait = 5 # Seconds waiting for a tile rendered by another instance
+#fallback = "render" # After waiting: "render" the tile or serve an "empty" placeholder
+# Render tiles around cache misses of interactive requests in the background
+#[cache.prerender]
+#radius = 1 # Neighboring tiles in each direction
+#zoom_depth = 1 # Zoom levels of child tiles
+#queue_size = 1000
+#threads = 1
+# Invalidate cached tiles on PostgreSQL notifications, e.g.
+# NOTIFY t_rex_invalidate, '{"layer": "roads", "bbox": [7.4, 46.9, 7.5, 47.0], "minzoom": 10}'
+#[cache.notify]
+#channel = "t_rex_invalidate"
+#datasource = "pg"
+#max_tiles = 100000 # Maximal number of tiles removed per notification
 "#;
         toml.to_string()
     }
@@ -3,17 +3,26 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::cache::cache::Cache;
+use crate::cache::cache::{lease_content, lease_owner, lease_path, Cache};
+use futures01::Future;
+use rusoto_core::signature::SignedRequest;
 use rusoto_core::{Client, HttpClient, Region};
 use rusoto_credential::StaticProvider;
-use rusoto_s3::{GetObjectRequest, HeadObjectRequest, PutObjectRequest, S3Client, S3};
+use rusoto_s3::{
+    DeleteObjectRequest, GetObjectRequest, HeadObjectRequest, PutObjectError, PutObjectRequest,
+    S3Client, S3,
+};
 use std::io::{self, Read};
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Clone)]
 pub struct S3Cache {
     baseurl: Option<String>,
     client: S3Client,
+    /// Client for requests not supported by S3Client (conditional PUT)
+    raw_client: Client,
+    region: Region,
     endpoint: String,
     bucket_name: String,
     key_prefix: Option<String>,
@@ -35,15 +44,15 @@ impl S3Cache {
             name: region.to_string(),
             endpoint: endpoint.to_string(),
         };
-        let client = S3Client::new_with_client(
-            Client::new_with(
-                StaticProvider::new(access_key.to_string(), secret_key.to_string(), None, None),
-                HttpClient::new().expect("Could not instantiate a new http client??"),
-            ),
-            region_object.clone(),
+        let raw_client = Client::new_with(
+            StaticProvider::new(access_key.to_string(), secret_key.to_string(), None, None),
+            HttpClient::new().expect("Could not instantiate a new http client??"),
         );
+        let client = S3Client::new_with_client(raw_client.clone(), region_object.clone());
         S3Cache {
             client: client,
+            raw_client,
+            region: region_object,
             baseurl: baseurl,
             endpoint: endpoint.to_string(),
             bucket_name: bucket_name.to_string(),
@@ -157,4 +166,157 @@ impl Cache for S3Cache {
             Err(_) => false,
         }
     }
+    fn remove(&self, path: &str) -> Result<(), io::Error> {
+        let key = self.full_path(path);
+        if key.is_empty() {
+            return Ok(());
+        }
+        let request = DeleteObjectRequest {
+            bucket: self.bucket_name.to_owned(),
+            key,
+            ..Default::default()
+        };
+        match self.client.delete_object(request).sync() {
+            Ok(_) => Ok(()),
+            Err(err) => Err(io::Error::new(io::ErrorKind::Other, err.to_string())),
+        }
+    }
+    /// The lease is created with a conditional PUT (`If-None-Match: *`) and an expired lease is
+    /// replaced with `If-Match` on its ETag, so only one instance acquires it.
+    /// S3 compatible servers without conditional writes fall back to writing and reading back
+    /// the lease. The last writer wins, but concurrent instances may both render the tile.
+    fn try_lease(&self, path: &str, owner: &str, ttl: Duration) -> bool {
+        let lease = lease_path(path);
+        let content = lease_content(owner, ttl);
+        match self.put_if(&lease, content.as_bytes(), ("If-None-Match", "*")) {
+            Ok(ConditionalPut::Written) => {
+                // Servers ignoring the precondition header
+                return self
+                    .lease_holder(&lease)
+                    .is_none_or(|holder| holder == owner);
+            }
+            Ok(ConditionalPut::PreconditionFailed) => {}
+            Ok(ConditionalPut::Unsupported) => {
+                return self.try_lease_unconditional(&lease, owner, &content)
+            }
+            Err(e) => {
+                warn!("S3 lease {} failed: {}", lease, e);
+                return true;
+            }
+        }
+        let (existing, etag) = match self.lease_object(&lease) {
+            Some(object) => object,
+            // Released in the meantime
+            None => return false,
+        };
+        if let Some(holder) = lease_owner(&existing) {
+            return holder == owner;
+        }
+        // Take over expired lease, unless another instance replaced it first
+        match self.put_if(&lease, content.as_bytes(), ("If-Match", &etag)) {
+            Ok(ConditionalPut::Written) => true,
+            Ok(ConditionalPut::PreconditionFailed) => false,
+            Ok(ConditionalPut::Unsupported) => {
+                self.try_lease_unconditional(&lease, owner, &content)
+            }
+            Err(e) => {
+                warn!("S3 lease {} failed: {}", lease, e);
+                true
+            }
+        }
+    }
+    fn release_lease(&self, path: &str, owner: &str) {
+        let lease = lease_path(path);
+        if self.lease_holder(&lease).as_deref() != Some(owner) {
+            return;
+        }
+        let request = DeleteObjectRequest {
+            bucket: self.bucket_name.to_owned(),
+            key: self.full_path(&lease),
+            ..Default::default()
+        };
+        if let Err(e) = self.client.delete_object(request).sync() {
+            warn!("S3 lease {} not released: {}", lease, e);
+        }
+    }
+}
+
+/// Result of a conditional PUT request
+enum ConditionalPut {
+    Written,
+    PreconditionFailed,
+    /// Precondition header not supported by server
+    Unsupported,
+}
+
+impl S3Cache {
+    /// PUT object with a precondition header (`If-None-Match: *` or `If-Match: <etag>`),
+    /// which is not supported by rusoto's PutObjectRequest
+    fn put_if(
+        &self,
+        path: &str,
+        obj: &[u8],
+        (header, value): (&str, &str),
+    ) -> Result<ConditionalPut, io::Error> {
+        let uri = format!("/{}/{}", self.bucket_name, self.full_path(path));
+        let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+        request.add_header(header, value);
+        request.add_header("Content-Type", "text/plain");
+        request.set_payload(Some(obj.to_vec()));
+        let status = self
+            .raw_client
+            .sign_and_dispatch::<u16, PutObjectError>(request, |response| {
+                Box::new(
+                    response
+                        .buffer()
+                        .from_err()
+                        .map(|response| response.status.as_u16()),
+                )
+            })
+            .sync()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        Ok(match status {
+            200..=299 => ConditionalPut::Written,
+            // 409: concurrent conditional write of the same object
+            409 | 412 => ConditionalPut::PreconditionFailed,
+            _ => ConditionalPut::Unsupported,
+        })
+    }
+    /// Best-effort lease: write and read back, the last writer wins
+    fn try_lease_unconditional(&self, lease: &str, owner: &str, content: &str) -> bool {
+        if let Some(holder) = self.lease_holder(lease) {
+            return holder == owner;
+        }
+        if let Err(e) = self.write(lease, content.as_bytes()) {
+            warn!("S3 lease {} failed: {}", lease, e);
+            return true;
+        }
+        self.lease_holder(lease)
+            .is_none_or(|holder| holder == owner)
+    }
+    /// Content and ETag of a lease object
+    fn lease_object(&self, lease: &str) -> Option<(String, String)> {
+        let request = GetObjectRequest {
+            bucket: self.bucket_name.to_owned(),
+            key: self.full_path(lease),
+            ..Default::default()
+        };
+        let mut result = self.client.get_object(request).sync().ok()?;
+        let mut content = String::new();
+        result
+            .body
+            .take()?
+            .into_blocking_read()
+            .read_to_string(&mut content)
+            .ok()?;
+        Some((content, result.e_tag?))
+    }
+    /// Owner of an unexpired lease object
+    fn lease_holder(&self, lease: &str) -> Option<String> {
+        let mut content = String::new();
+        self.read(lease, |f| {
+            let _ = f.read_to_string(&mut content);
+        });
+        lease_owner(&content).map(|owner| owner.to_string())
+    }
 }
@@ -128,3 +128,42 @@ fn test_s3cache() {
     }
     assert!(!headers.contains(&"Content-Encoding: gzip\r\n".to_string()));
 }
+
+#[test]
+#[ignore]
+fn test_s3cache_lease() {
+    use std::time::Duration;
+
+    if env::var("S3TEST").is_err() {
+        return;
+    }
+
+    let cache = S3Cache::new(
+        "http://localhost:9000",
+        "trex",
+        "miniostorage",
+        "miniostorage",
+        "my-region",
+        None,
+        Some("lease-test".to_string()),
+        None,
+    );
+    let path = "tileset/3/1/2.pbf";
+    let ttl = Duration::from_secs(30);
+    cache.release_lease(path, "a");
+    cache.release_lease(path, "b");
+
+    assert!(cache.try_lease(path, "a", ttl));
+    assert!(cache.try_lease(path, "a", ttl));
+    assert!(!cache.try_lease(path, "b", ttl));
+    cache.release_lease(path, "b");
+    assert!(!cache.try_lease(path, "b", ttl));
+    cache.release_lease(path, "a");
+    assert!(cache.try_lease(path, "b", ttl));
+
+    // Expired lease is taken over
+    cache.release_lease(path, "b");
+    assert!(cache.try_lease(path, "a", Duration::from_secs(0)));
+    assert!(cache.try_lease(path, "b", ttl));
+    cache.release_lease(path, "b");
+}
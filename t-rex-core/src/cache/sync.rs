@@ -0,0 +1,214 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Upload of a local tile cache into another cache (e.g. S3)
+
+use crate::cache::cache::Cache;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Manifest of uploaded files within the local cache directory, one per destination
+const MANIFEST_PREFIX: &str = ".t_rex_sync_";
+/// Number of upload attempts per file
+const MAX_ATTEMPTS: u32 = 3;
+
+pub struct SyncOptions {
+    /// Number of parallel uploads
+    pub threads: usize,
+    /// Upload all files, ignoring the manifest of a previous sync
+    pub force: bool,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct SyncStats {
+    pub uploaded: u64,
+    pub unchanged: u64,
+    pub failed: u64,
+}
+
+/// (size, mtime in seconds) of a local file
+type FileState = (u64, u64);
+
+/// Split `s3://bucket/prefix` into bucket and optional key prefix
+pub fn parse_s3_url(url: &str) -> Result<(String, Option<String>), String> {
+    let path = url.strip_prefix("s3://").ok_or_else(|| {
+        format!(
+            "Invalid destination '{}' (expected s3://bucket/prefix)",
+            url
+        )
+    })?;
+    let mut parts = path.splitn(2, '/');
+    let bucket = parts.next().unwrap_or("").to_string();
+    if bucket.is_empty() {
+        return Err(format!("Missing bucket name in '{}'", url));
+    }
+    let prefix = parts
+        .next()
+        .map(|p| p.trim_matches('/').to_string())
+        .filter(|p| !p.is_empty());
+    Ok((bucket, prefix))
+}
+
+fn manifest_path(basedir: &Path, destination: &str) -> std::path::PathBuf {
+    let name: String = destination
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    basedir.join(format!("{}{}", MANIFEST_PREFIX, name))
+}
+
+fn read_manifest(path: &Path) -> BTreeMap<String, FileState> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut cols = line.split('\t');
+            let path = cols.next()?.to_string();
+            let size = cols.next()?.parse().ok()?;
+            let mtime = cols.next()?.parse().ok()?;
+            Some((path, (size, mtime)))
+        })
+        .collect()
+}
+
+fn write_manifest(path: &Path, manifest: &BTreeMap<String, FileState>) -> io::Result<()> {
+    let lines: Vec<String> = manifest
+        .iter()
+        .map(|(path, (size, mtime))| format!("{}\t{}\t{}", path, size, mtime))
+        .collect();
+    fs::write(path, lines.join("\n") + "\n")
+}
+
+/// Relative paths and state of all files below `dir`
+fn collect_files(
+    basedir: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, FileState)>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            collect_files(basedir, &path, files)?;
+        } else if meta.is_file() {
+            let relpath = path
+                .strip_prefix(basedir)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/");
+            if relpath.starts_with(MANIFEST_PREFIX) {
+                continue;
+            }
+            let mtime = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs());
+            files.push((relpath, (meta.len(), mtime)));
+        }
+    }
+    Ok(())
+}
+
+/// Upload new and changed files of local cache directory into `cache`.
+/// Files are compared by size and modification time with the previous sync to `destination`.
+pub fn sync_cache<C: Cache + Clone + Send + 'static>(
+    local_dir: &str,
+    cache: &C,
+    destination: &str,
+    options: &SyncOptions,
+) -> Result<SyncStats, String> {
+    let basedir = Path::new(local_dir);
+    let mut files = Vec::new();
+    collect_files(basedir, basedir, &mut files)
+        .map_err(|e| format!("Error reading {}: {}", local_dir, e))?;
+    let manifest_file = manifest_path(basedir, destination);
+    let previous = if options.force {
+        BTreeMap::new()
+    } else {
+        read_manifest(&manifest_file)
+    };
+    let unchanged = files
+        .iter()
+        .filter(|(path, state)| previous.get(path) == Some(state))
+        .count() as u64;
+    let pending: Vec<(String, FileState)> = files
+        .into_iter()
+        .filter(|(path, state)| previous.get(path) != Some(state))
+        .collect();
+    info!(
+        "Uploading {} files to {} ({} unchanged)",
+        pending.len(),
+        destination,
+        unchanged
+    );
+
+    let queue = Arc::new(Mutex::new(pending));
+    let manifest = Arc::new(Mutex::new(previous));
+    let failed = Arc::new(AtomicU64::new(0));
+    let uploaded = Arc::new(AtomicU64::new(0));
+    let workers: Vec<_> = (0..options.threads.max(1))
+        .map(|_| {
+            let queue = queue.clone();
+            let manifest = manifest.clone();
+            let failed = failed.clone();
+            let uploaded = uploaded.clone();
+            let cache = cache.clone();
+            let basedir = basedir.to_path_buf();
+            thread::spawn(move || loop {
+                let next = queue.lock().unwrap().pop();
+                let (path, state) = match next {
+                    Some(entry) => entry,
+                    None => break,
+                };
+                let mut result = Err("not attempted".to_string());
+                for attempt in 1..=MAX_ATTEMPTS {
+                    result = fs::read(basedir.join(&path))
+                        .and_then(|data| cache.write(&path, &data))
+                        .map_err(|e| e.to_string());
+                    if result.is_ok() {
+                        break;
+                    }
+                    warn!(
+                        "Upload of {} failed (attempt {}): {:?}",
+                        path, attempt, result
+                    );
+                    if attempt < MAX_ATTEMPTS {
+                        thread::sleep(Duration::from_millis(200 * 2u64.pow(attempt)));
+                    }
+                }
+                match result {
+                    Ok(_) => {
+                        uploaded.fetch_add(1, Ordering::Relaxed);
+                        manifest.lock().unwrap().insert(path, state);
+                    }
+                    Err(e) => {
+                        error!("Upload of {} failed: {}", path, e);
+                        failed.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker
+            .join()
+            .map_err(|_| "Upload thread panicked".to_string())?;
+    }
+    write_manifest(&manifest_file, &manifest.lock().unwrap())
+        .map_err(|e| format!("Error writing {:?}: {}", manifest_file, e))?;
+    Ok(SyncStats {
+        uploaded: uploaded.load(Ordering::Relaxed),
+        unchanged,
+        failed: failed.load(Ordering::Relaxed),
+    })
+}
@@ -0,0 +1,63 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::cache::filecache::Filecache;
+use crate::cache::sync::{parse_s3_url, sync_cache, SyncOptions, SyncStats};
+use std::env;
+use std::fs;
+
+#[test]
+fn test_parse_s3_url() {
+    assert_eq!(
+        parse_s3_url("s3://tiles/osm/v2/"),
+        Ok(("tiles".to_string(), Some("osm/v2".to_string())))
+    );
+    assert_eq!(parse_s3_url("s3://tiles"), Ok(("tiles".to_string(), None)));
+    assert!(parse_s3_url("s3://").is_err());
+    assert!(parse_s3_url("/tmp/tiles").is_err());
+}
+
+#[test]
+fn test_sync_cache() {
+    let basedir = env::temp_dir().join(format!("t_rex_sync_{}", std::process::id()));
+    let local = basedir.join("local");
+    let remote = basedir.join("remote");
+    fs::create_dir_all(local.join("osm/0/0")).unwrap();
+    fs::write(local.join("osm/0/0/0.pbf"), b"tile").unwrap();
+    fs::write(local.join("osm/metadata.json"), b"{}").unwrap();
+    let cache = Filecache {
+        basepath: remote.to_str().unwrap().to_string(),
+        baseurl: None,
+    };
+    let options = SyncOptions {
+        threads: 2,
+        force: false,
+    };
+    let local_dir = local.to_str().unwrap();
+
+    let stats = sync_cache(local_dir, &cache, "s3://tiles", &options).unwrap();
+    assert_eq!(
+        stats,
+        SyncStats {
+            uploaded: 2,
+            unchanged: 0,
+            failed: 0
+        }
+    );
+    assert_eq!(fs::read(remote.join("osm/0/0/0.pbf")).unwrap(), b"tile");
+
+    let stats = sync_cache(local_dir, &cache, "s3://tiles", &options).unwrap();
+    assert_eq!((stats.uploaded, stats.unchanged), (0, 2));
+
+    fs::write(local.join("osm/0/0/0.pbf"), b"changed tile").unwrap();
+    let stats = sync_cache(local_dir, &cache, "s3://tiles", &options).unwrap();
+    assert_eq!((stats.uploaded, stats.unchanged), (1, 1));
+
+    // Other destinations have their own manifest
+    let stats = sync_cache(local_dir, &cache, "s3://other", &options).unwrap();
+    assert_eq!(stats.uploaded, 2);
+
+    let _ = fs::remove_dir_all(&basedir);
+}
@@ -0,0 +1,204 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Attribute statistics collector
+
+use crate::mvt::vector_tile;
+use std::collections::{BTreeMap, HashSet};
+
+/// Maximal number of distinct values tracked per attribute
+const MAX_DISTINCT_VALUES: usize = 10_000;
+/// Distinct string values at which an attribute is flagged as high cardinality
+const HIGH_CARDINALITY: usize = 1_000;
+
+#[derive(Default)]
+struct AttributeCollector {
+    count: u64,
+    distinct: HashSet<String>,
+    distinct_overflow: bool,
+    string_count: u64,
+    string_bytes: u64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl AttributeCollector {
+    fn add(&mut self, value: &vector_tile::Tile_Value) {
+        self.count += 1;
+        let strval = if value.has_string_value() {
+            let s = value.get_string_value();
+            self.string_count += 1;
+            self.string_bytes += s.len() as u64;
+            s.to_string()
+        } else {
+            let num = if value.has_double_value() {
+                Some(value.get_double_value())
+            } else if value.has_float_value() {
+                Some(value.get_float_value() as f64)
+            } else if value.has_int_value() {
+                Some(value.get_int_value() as f64)
+            } else if value.has_uint_value() {
+                Some(value.get_uint_value() as f64)
+            } else if value.has_sint_value() {
+                Some(value.get_sint_value() as f64)
+            } else {
+                None
+            };
+            if let Some(num) = num {
+                self.min = Some(self.min.map_or(num, |min| min.min(num)));
+                self.max = Some(self.max.map_or(num, |max| max.max(num)));
+                num.to_string()
+            } else {
+                value.get_bool_value().to_string()
+            }
+        };
+        if self.distinct.len() < MAX_DISTINCT_VALUES {
+            self.distinct.insert(strval);
+        } else if !self.distinct.contains(&strval) {
+            self.distinct_overflow = true;
+        }
+    }
+    fn is_high_cardinality(&self) -> bool {
+        self.string_count > 0 && self.distinct.len() >= HIGH_CARDINALITY
+    }
+}
+
+/// Attribute cardinality and value ranges per layer
+#[derive(Default)]
+pub struct AttributeStatistics(BTreeMap<(String, String), AttributeCollector>);
+
+pub struct AttributeResults {
+    pub count: u64,
+    /// Number of distinct values (lower bound, if `distinct_overflow`)
+    pub distinct: usize,
+    pub distinct_overflow: bool,
+    /// Average length of string values
+    pub mean_string_len: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub high_cardinality: bool,
+}
+
+impl AttributeStatistics {
+    pub fn new() -> AttributeStatistics {
+        AttributeStatistics(BTreeMap::new())
+    }
+    /// Collect attribute values of all features in tile
+    pub fn add_tile(&mut self, mvt_tile: &vector_tile::Tile) {
+        for layer in mvt_tile.get_layers() {
+            let keys = layer.get_keys();
+            let values = layer.get_values();
+            for feature in layer.get_features() {
+                for tag in feature.get_tags().chunks(2) {
+                    if let (Some(key), Some(value)) = (
+                        keys.get(tag[0] as usize),
+                        tag.get(1).and_then(|idx| values.get(*idx as usize)),
+                    ) {
+                        self.0
+                            .entry((layer.get_name().to_string(), key.clone()))
+                            .or_default()
+                            .add(value);
+                    }
+                }
+            }
+        }
+    }
+    pub fn results(&self, layer: &str, attribute: &str) -> Option<AttributeResults> {
+        self.0
+            .get(&(layer.to_string(), attribute.to_string()))
+            .map(|c| AttributeResults {
+                count: c.count,
+                distinct: c.distinct.len(),
+                distinct_overflow: c.distinct_overflow,
+                mean_string_len: if c.string_count > 0 {
+                    c.string_bytes as f64 / c.string_count as f64
+                } else {
+                    0.0
+                },
+                min: c.min,
+                max: c.max,
+                high_cardinality: c.is_high_cardinality(),
+            })
+    }
+    /// Names of collected attributes of layer
+    pub fn attributes(&self, layer: &str) -> Vec<String> {
+        self.0
+            .keys()
+            .filter(|(l, _)| l == layer)
+            .map(|(_, attr)| attr.clone())
+            .collect()
+    }
+    /// Report lines with one attribute per line
+    pub fn report(&self) -> Vec<String> {
+        self.0
+            .keys()
+            .map(|(layer, attr)| {
+                let res = self.results(layer, attr).unwrap();
+                let mut line = format!(
+                    "Layer '{}' attribute '{}': {} values, {}{} distinct",
+                    layer,
+                    attr,
+                    res.count,
+                    if res.distinct_overflow { ">" } else { "" },
+                    res.distinct
+                );
+                if let (Some(min), Some(max)) = (res.min, res.max) {
+                    line.push_str(&format!(", range {} - {}", min, max));
+                }
+                if res.mean_string_len > 0.0 {
+                    line.push_str(&format!(", mean length {:.1}", res.mean_string_len));
+                }
+                line
+            })
+            .collect()
+    }
+    /// (layer, attribute) of string attributes with high cardinality
+    pub fn high_cardinality_attributes(&self) -> Vec<(String, String)> {
+        self.0
+            .iter()
+            .filter(|(_, c)| c.is_high_cardinality())
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+#[test]
+fn test_attribute_stats() {
+    let mut layer = vector_tile::Tile_Layer::new();
+    layer.set_name("points".to_string());
+    layer.mut_keys().push("name".to_string());
+    layer.mut_keys().push("pop".to_string());
+    for (i, name) in ["a", "bb", "a"].iter().enumerate() {
+        let mut strval = vector_tile::Tile_Value::new();
+        strval.set_string_value(name.to_string());
+        layer.mut_values().push(strval);
+        let mut intval = vector_tile::Tile_Value::new();
+        intval.set_int_value(i as i64 * 10);
+        layer.mut_values().push(intval);
+        let mut feature = vector_tile::Tile_Feature::new();
+        feature.set_tags(vec![0, 2 * i as u32, 1, 2 * i as u32 + 1]);
+        layer.mut_features().push(feature);
+    }
+    let mut tile = vector_tile::Tile::new();
+    tile.mut_layers().push(layer);
+
+    let mut stats = AttributeStatistics::new();
+    stats.add_tile(&tile);
+    let res = stats.results("points", "name").unwrap();
+    assert_eq!(res.count, 3);
+    assert_eq!(res.distinct, 2);
+    assert_eq!(res.mean_string_len, 4.0 / 3.0);
+    assert_eq!(res.min, None);
+    let res = stats.results("points", "pop").unwrap();
+    assert_eq!((res.min, res.max), (Some(0.0), Some(20.0)));
+    assert_eq!(
+        stats.report(),
+        vec![
+            "Layer 'points' attribute 'name': 3 values, 2 distinct, mean length 1.3",
+            "Layer 'points' attribute 'pop': 3 values, 3 distinct, range 0 - 20"
+        ]
+    );
+    assert!(stats.high_cardinality_attributes().is_empty());
+}
@@ -6,6 +6,7 @@
 use crate::core::gridcfg::ExtentCfg;
 use regex::Regex;
 use serde::Deserialize;
+use serde_ignored::Path;
 use std;
 use std::collections::HashMap;
 use std::env;
@@ -36,8 +37,13 @@ pub struct ApplicationCfg {
     pub grid: GridCfg,
     #[serde(rename = "tileset")]
     pub tilesets: Vec<TilesetCfg>,
+    #[serde(rename = "raster", default)]
+    pub rasters: Vec<RasterTilesetCfg>,
     pub cache: Option<CacheCfg>,
     pub webserver: WebserverCfg,
+    /// Reusable SQL snippets, referenced in layer queries as `!snippet.NAME!`
+    #[serde(default)]
+    pub sql_snippets: HashMap<String, String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -48,6 +54,27 @@ pub struct ServiceCfg {
 #[derive(Deserialize, Clone, Debug)]
 pub struct ServiceMvtCfg {
     pub viewer: bool,
+    /// Gzip compression level (0-9, default 6)
+    pub gzip_level: Option<u32>,
+    /// Store gzip compressed tiles in cache (default true)
+    pub cache_compressed: Option<bool>,
+    /// Log warning for tiles larger than this size in KB
+    pub tile_size_warning: Option<u32>,
+    /// Cap of approximate memory in MB used by all tiles in rendering
+    pub memory_limit: Option<u32>,
+    /// Consecutive failed queries of a layer before skipping it (default 5, 0: never skip)
+    pub layer_failure_threshold: Option<u32>,
+    /// Seconds a failing layer is skipped before it is queried again (default 60)
+    pub layer_failure_cooldown: Option<u32>,
+    /// Add Content-Digest (SHA-256) header to tile responses
+    pub content_digest: Option<bool>,
+    /// Byte-identical tiles for identical data (sorted features, keys and values)
+    pub deterministic: Option<bool>,
+    /// Refuse to start with invalid tilesets or layers instead of disabling them (default false)
+    pub fail_on_invalid: Option<bool>,
+    /// Tileset aliases (alias name -> tileset name), switchable with the admin API
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -57,8 +84,60 @@ pub struct DatasourceCfg {
     // Postgis
     pub dbconn: Option<String>,
     pub pool: Option<u16>,
+    /// Number of idle connections kept open (default: pool size)
+    pub pool_min: Option<u16>,
+    /// Seconds after which idle connections are closed (default: 600)
+    pub pool_idle_timeout: Option<u64>,
+    /// Seconds waiting for a free connection (default: 30)
+    pub pool_timeout: Option<u64>,
+    /// Maximum number of concurrent feature queries (default: unlimited)
+    pub max_queries: Option<u16>,
+    /// Session application_name with `{version}` and `{tileset}` placeholders
+    pub application_name: Option<String>,
+    /// Read-only sessions (default_transaction_read_only)
+    pub read_only: Option<bool>,
     // GDAL
     pub path: Option<String>,
+    // GeoPackage
+    pub gpkg: Option<String>,
+    // GeoJSON
+    pub geojson: Option<String>,
+    // FlatGeobuf
+    pub flatgeobuf: Option<String>,
+    // ESRI Shapefile
+    pub shapefile: Option<String>,
+    // OpenStreetMap PBF extract
+    pub osm_pbf: Option<String>,
+    // SpatiaLite
+    pub spatialite: Option<String>,
+    // DuckDB
+    pub duckdb: Option<String>,
+    // WFS 2.0 service URL
+    pub wfs: Option<String>,
+    // CSV file with point coordinate columns
+    pub csv: Option<String>,
+    /// Field delimiter of CSV files (Default: ',')
+    pub delimiter: Option<String>,
+    // Upstream vector tile URL with {z}, {x} and {y} placeholders
+    pub upstream: Option<String>,
+    /// Directory with features recorded with `--record`, replayed instead of querying the datasource
+    pub replay: Option<String>,
+    /// Layers derived from OSM tags
+    #[serde(default)]
+    pub osm_layer: Vec<OsmLayerCfg>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct OsmLayerCfg {
+    pub name: String,
+    /// "point" (nodes), "line" (ways) or "polygon" (closed ways and multipolygon relations)
+    pub geometry_type: String,
+    /// Required tags. Values are "*" (any value) or alternatives separated by "|"
+    #[serde(default)]
+    pub filter: HashMap<String, String>,
+    /// Tags published as attributes (default: all)
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -90,6 +169,11 @@ pub struct UserGridCfg {
     /// pixel for most grids used in webmapping).
     #[serde(default)]
     pub resolutions: Vec<f64>,
+    /// Optional number of tiles in x direction for each zoom level (TileMatrix MatrixWidth).
+    /// Default: calculated from extent and resolution.
+    pub matrix_width: Option<Vec<u32>>,
+    /// Optional number of tiles in y direction for each zoom level (TileMatrix MatrixHeight).
+    pub matrix_height: Option<Vec<u32>>,
     /// Grid origin
     pub origin: String,
 }
@@ -103,11 +187,26 @@ pub struct TilesetCfg {
     pub center: Option<(f64, f64)>,
     pub start_zoom: Option<u8>,
     pub attribution: Option<String>,
-    #[serde(rename = "layer")]
+    /// Tags for catalog discovery
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Serve pre-rendered tiles of an MBTiles file instead of layers
+    pub mbtiles: Option<String>,
+    /// Serve pre-rendered tiles of a PMTiles archive (file or http(s) URL) instead of layers
+    pub pmtiles: Option<String>,
+    #[serde(rename = "layer", default)]
     pub layers: Vec<LayerCfg>,
     // Inline style
     pub style: Option<Value>,
     pub cache_limits: Option<TilesetCacheCfg>,
+    /// Cache storage of this tileset instead of the global cache (lease, prerender and notify
+    /// settings are global). An empty table disables caching.
+    pub cache: Option<CacheCfg>,
+    pub access: Option<TilesetAccessCfg>,
+    pub time: Option<TimeDimensionCfg>,
+    /// Content type of vector tiles, e.g. `application/vnd.mapbox-vector-tile`
+    pub content_type: Option<String>,
+    pub crawl_protection: Option<TilesetCrawlCfg>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -128,15 +227,64 @@ pub struct LayerCfg {
     pub datasource: Option<String>,
     pub geometry_field: Option<String>,
     pub geometry_type: Option<String>,
+    /// Coordinate columns of point rows (CSV), e.g. "lon" and "lat"
+    pub x_field: Option<String>,
+    pub y_field: Option<String>,
+    /// Attribute columns (CSV, default: all except coordinate columns)
+    #[serde(default)]
+    pub fields: Vec<String>,
     /// Spatial reference system (PostGIS SRID)
     pub srid: Option<i32>,
+    /// Use `srid` instead of the SRS reported by the datasource
+    #[serde(default)]
+    pub force_srid: bool,
+    /// Axis order of source coordinates ("xy" or "yx")
+    pub axis_order: Option<String>,
     /// Handle geometry like one in grid SRS
     #[serde(default)]
     pub no_transform: bool,
+    /// Feature id column, or comma separated columns of a composite key
     pub fid_field: Option<String>,
+    /// Skip features with an already encoded fid
+    #[serde(default)]
+    pub dedup_fid: bool,
+    /// Check uniqueness of fids within tile layer ("warn" or "renumber")
+    pub fid_check: Option<String>,
     // Input for derived queries
     pub table_name: Option<String>,
     pub query_limit: Option<u32>,
+    /// Size of a dedicated connection pool for this layer (PostGIS)
+    pub pool: Option<u16>,
+    /// Feature order within layer (SQL ORDER BY expression)
+    pub order_by: Option<String>,
+    /// Truncate string attribute values to this number of characters
+    pub max_string_length: Option<usize>,
+    /// Array columns as attributes `key.0`, `key.1`, ... instead of JSON text (PostGIS)
+    #[serde(default)]
+    pub explode_arrays: bool,
+    /// hstore or JSON object columns expanded into one attribute per key (PostGIS)
+    #[serde(default)]
+    pub expand_fields: Vec<String>,
+    /// Keys of expanded columns to publish (empty for all)
+    #[serde(default)]
+    pub expand_include: Vec<String>,
+    /// Keys of expanded columns to skip
+    #[serde(default)]
+    pub expand_exclude: Vec<String>,
+    /// Layer encoding: "st_asmvt" for encoding by PostGIS ST_AsMVT (Default: t-rex)
+    pub encoding: Option<String>,
+    /// Row-level filter condition, e.g. `public = true`. Layers of datasources without
+    /// filter support (non-SQLite files, WFS and upstream tiles) are disabled.
+    pub filter: Option<String>,
+    /// Query returning geometries of features changed since `!since!` (for incremental seeding)
+    pub changes_sql: Option<String>,
+    /// Query returning the time of the last data update, e.g. `SELECT max(updated_at) FROM mytable` (PostGIS)
+    pub updated_sql: Option<String>,
+    /// File whose modification time is the time of the last data update
+    pub updated_file: Option<String>,
+    /// Attributes only published for requests with a valid access token
+    #[serde(default)]
+    pub protected_fields: Vec<String>,
     // Explicit queries
     #[serde(default)]
     pub query: Vec<LayerQueryCfg>,
@@ -145,24 +293,52 @@ pub struct LayerCfg {
     /// Width and height of the tile (Default: 4096. Grid default size is 256)
     #[serde(default = "default_tile_size")]
     pub tile_size: u32,
-    /// Simplify geometry (lines and polygons)
+    /// Simplify geometry (lines and polygons), `"auto"` derives all thresholds from the grid resolution
     #[serde(default)]
-    pub simplify: bool,
+    pub simplify: SimplifyCfg,
     /// Simplification tolerance (default to !pixel_width!/2)
     #[serde(default = "default_tolerance")]
     pub tolerance: String,
+    /// Simplification tolerance in pixels (overrides `tolerance`)
+    pub tolerance_px: Option<f64>,
+    /// Simplification algorithm ("preserve_topology", "douglas_peucker", "visvalingam" or "snap_to_grid")
+    pub simplify_method: Option<String>,
     /// Tile buffer size in pixels (None: no clipping)
     pub buffer_size: Option<u32>,
     /// Fix invalid geometries before clipping (lines and polygons)
     #[serde(default)]
     pub make_valid: bool,
+    /// Remove collinear intermediate points when encoding lines and polygons
+    #[serde(default)]
+    pub remove_collinear: bool,
+    /// Drop polygon holes with a smaller area (in tile coordinate units, see tile_size)
+    pub min_hole_area: Option<f64>,
     /// Apply ST_Shift_Longitude to (transformed) bbox
     #[serde(default)]
     pub shift_longitude: bool,
+    /// Tags for catalog discovery
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Data volatility hint ("static", "daily" or "live") for Cache-Control headers and cache lifetime
+    pub volatility: Option<String>,
     // Inline style
     pub style: Option<Value>,
 }
 
+/// Simplification setting (`true`, `false` or `"auto"`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum SimplifyCfg {
+    Enabled(bool),
+    Mode(String),
+}
+
+impl Default for SimplifyCfg {
+    fn default() -> Self {
+        SimplifyCfg::Enabled(false)
+    }
+}
+
 pub fn default_tile_size() -> u32 {
     4096
 }
@@ -173,6 +349,33 @@ pub fn default_tolerance() -> String {
     DEFAULT_TOLERANCE.to_string()
 }
 
+/// Pre-rendered raster tiles
+#[derive(Deserialize, Clone, Debug)]
+pub struct RasterTilesetCfg {
+    pub name: String,
+    /// Directory with tiles in {z}/{x}/{y}.{format} layout
+    pub path: String,
+    /// Image format (png, jpg or webp). Default: png
+    pub format: Option<String>,
+    /// Additional formats stored in the same directory, selected by extension or Accept header
+    #[serde(default)]
+    pub formats: Vec<String>,
+    pub minzoom: Option<u8>,
+    pub maxzoom: Option<u8>,
+    /// Render hillshade PNG tiles from GDAL elevation model in `path`
+    pub hillshade: Option<HillshadeCfg>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct HillshadeCfg {
+    /// Direction of light source in degrees (default 315)
+    pub azimuth: Option<f64>,
+    /// Altitude of light source in degrees (default 45)
+    pub altitude: Option<f64>,
+    /// Vertical exaggeration (default 1.0)
+    pub z_factor: Option<f64>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct TilesetCacheCfg {
     #[serde(default)]
@@ -182,10 +385,88 @@ pub struct TilesetCacheCfg {
     pub no_cache: bool,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct TilesetAccessCfg {
+    /// Allowed Origin/Referer patterns `scheme://host[:port][/path]`, e.g. `https://*.example.org`
+    /// (any subdomain and page) or `https://example.com/maps/*`. The path may contain `*` wildcards,
+    /// patterns without path allow any page. Origin headers are matched against scheme, host and port.
+    pub allowed_referrers: Vec<String>,
+    /// Allow requests without Origin and Referer header
+    #[serde(default)]
+    pub allow_missing: bool,
+    /// Token for bypassing referrer check with `?token=...`
+    pub token: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TilesetCrawlCfg {
+    /// Exclude tileset in generated robots.txt (default: true)
+    pub disallow: Option<bool>,
+    /// Maximal number of sequentially enumerated tiles per client within `window` (default: 200)
+    pub max_sequential: Option<u32>,
+    /// Time window for detecting tile enumeration in seconds (default: 60)
+    pub window: Option<u64>,
+    /// Token allowing bulk requests with `X-Crawl-Token` header or `?crawl_token=...`
+    pub token: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TimeDimensionCfg {
+    /// Valid time values
+    #[serde(default)]
+    pub values: Vec<String>,
+    /// Range of valid ISO 8601 time values (inclusive)
+    pub range: Option<(String, String)>,
+    /// Time value of requests without time
+    pub default: Option<String>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct CacheCfg {
+    /// Cache chain queried in order (e.g. `["file", "s3"]`)
+    pub tiers: Option<Vec<String>>,
+    /// Write tiles found in a later tier into the preceding tiers (default true)
+    pub backfill: Option<bool>,
     pub file: Option<CacheFileCfg>,
     pub s3: Option<S3CacheFileCfg>,
+    /// Render leases coordinating instances sharing a cache
+    pub lease: Option<CacheLeaseCfg>,
+    /// Background rendering of tiles around cache misses of interactive requests
+    pub prerender: Option<CachePrerenderCfg>,
+    /// Invalidation of cached tiles by PostgreSQL notifications
+    pub notify: Option<CacheNotifyCfg>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CacheNotifyCfg {
+    /// Notification channel (`LISTEN` name)
+    pub channel: String,
+    /// PostGIS datasource receiving notifications (default datasource if undefined)
+    pub datasource: Option<String>,
+    /// Maximal number of tiles removed per notification (default 100000)
+    pub max_tiles: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CachePrerenderCfg {
+    /// Neighboring tiles in each direction rendered at the zoom level of a missed tile (default 1, maximum 4)
+    pub radius: Option<u8>,
+    /// Zoom levels of child tiles rendered below a missed tile (default 1, maximum 4)
+    pub zoom_depth: Option<u8>,
+    /// Maximal number of queued tiles, oldest tiles are dropped first (default 1000)
+    pub queue_size: Option<usize>,
+    /// Number of rendering threads (default 1)
+    pub threads: Option<u8>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CacheLeaseCfg {
+    /// Lease duration in seconds (default 30)
+    pub ttl: Option<u64>,
+    /// Maximal time in seconds waiting for a tile rendered by another instance (default 5)
+    pub wait: Option<u64>,
+    /// Action after waiting: "render" the tile anyway or serve an "empty" placeholder (default "render")
+    pub fallback: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -210,12 +491,65 @@ pub struct S3CacheFileCfg {
 pub struct WebserverCfg {
     pub bind: Option<String>,
     pub port: Option<u16>,
+    /// Number of HTTP workers
     pub threads: Option<u8>,
+    /// Size of the thread pool for tile rendering and encoding
+    pub render_threads: Option<u16>,
     // Cache-Control headers set by web server
     // https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cache-Control#Expiration
     pub cache_control_max_age: Option<u32>,
+    /// Bearer token enabling the admin API
+    pub admin_token: Option<String>,
+    /// IP addresses of reverse proxies whose `Forwarded`/`X-Forwarded-For` headers identify
+    /// clients for rate limits and crawl detection. Other requests are identified by peer address.
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    /// Additional tile routes like `/services/{tileset}/tiles/{z}/{x}/{y}.mvt`
+    #[serde(default)]
+    pub tile_paths: Vec<String>,
+    /// Content of `/robots.txt` (default: generated from crawl protected tilesets)
+    pub robots_txt: Option<String>,
+    pub viewer: Option<ViewerCfg>,
+    /// Directory served under `/static/` (custom viewers, landing pages, sprites)
+    pub static_dir: Option<String>,
     #[serde(rename = "static", default)]
     pub static_: Vec<WebserverStaticCfg>,
+    /// Safe defaults for public internet exposure (also enabled with `t_rex serve --hardened`)
+    pub hardened: Option<HardenedCfg>,
+}
+
+/// Limits of the hardened serve profile with explicit opt-outs
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct HardenedCfg {
+    /// Tile requests per second and client (default 50, 0: unlimited)
+    pub rate_limit: Option<u32>,
+    /// Tiles rendered concurrently (default: 4 x number of cores, 0: unlimited)
+    pub max_concurrency: Option<u32>,
+    /// Maximal size of rendered tiles in KB (default 2048, 0: unlimited)
+    pub max_tile_size: Option<u32>,
+    /// Keep admin API enabled
+    #[serde(default)]
+    pub admin_api: bool,
+    /// Keep viewer, drilldown, layer samples and metrics enabled
+    #[serde(default)]
+    pub debug_endpoints: bool,
+    /// Serve tilesets without configured extent
+    #[serde(default)]
+    pub unbounded_tilesets: bool,
+}
+
+/// Map settings of the built-in viewer
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ViewerCfg {
+    /// Raster XYZ tile URL of background map, e.g. `https://tile.openstreetmap.org/{z}/{x}/{y}.png`
+    pub background_tiles: Option<String>,
+    /// Attribution of background tiles
+    pub background_attribution: Option<String>,
+    /// URL of a Mapbox GL style used as background map
+    pub background_style: Option<String>,
+    /// Tilesets displayed on top of the selected tileset
+    #[serde(default)]
+    pub overlays: Vec<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -227,6 +561,17 @@ pub struct WebserverStaticCfg {
 pub const DEFAULT_CONFIG: &'static str = r#"
 [service.mvt]
 viewer = true
+#gzip_level = 6 # Gzip compression level (0-9)
+#cache_compressed = true # Store gzip compressed tiles in cache
+#tile_size_warning = 500 # Warn about tiles larger than 500 KB
+#memory_limit = 1024 # Cap of memory (MB) used by tiles in rendering
+#layer_failure_threshold = 5 # Skip layers after 5 consecutive failed queries
+#layer_failure_cooldown = 60 # Seconds until a skipped layer is queried again
+#content_digest = true # Add Content-Digest (SHA-256) header to tiles
+#deterministic = true # Byte-identical tiles for identical data
+#fail_on_invalid = true # Refuse to start with invalid tilesets or layers
+#[service.mvt.aliases]
+#osm = "osm_v42" # Tileset alias, switchable with the admin API
 
 [[datasource]]
 dbconn = ""
@@ -236,8 +581,9 @@ predefined = "web_mercator"
 
 [[tileset]]
 name = ""
-minzoom = 0 # Optional override of zoom limits broadcasted to tilejson descriptor
+minzoom = 0 # Optional limitation of layer zoom ranges broadcasted to tilejson descriptor
 maxzoom = 22
+#tags = ["basemap"] # Optional tags for filtering the /catalog endpoint
 
 [[tileset.layer]]
 name = ""
@@ -249,6 +595,11 @@ port = 6767
 
 /// Load and parse the config file into an config struct.
 pub fn read_config<'a, T: Deserialize<'a>>(path: &str) -> Result<T, String> {
+    read_config_checked(path, false)
+}
+
+/// Load and parse the config file. In strict mode, unknown keys are rejected instead of logged.
+pub fn read_config_checked<'a, T: Deserialize<'a>>(path: &str, strict: bool) -> Result<T, String> {
     let mut file = match File::open(path) {
         Ok(file) => file,
         Err(_) => {
@@ -260,11 +611,112 @@ pub fn read_config<'a, T: Deserialize<'a>>(path: &str) -> Result<T, String> {
         return Err(format!("Error while reading config: [{}]", err));
     };
 
-    parse_config(config_toml, path)
+    parse_config_checked(config_toml, path, strict)
+}
+
+/// Maximal nesting depth of SQL snippets
+const MAX_SNIPPET_DEPTH: usize = 10;
+
+/// Replace `!snippet.NAME!` references with SQL snippets (which may reference other snippets)
+pub fn expand_sql_snippets(
+    sql: &str,
+    snippets: &HashMap<String, String>,
+) -> Result<String, String> {
+    let re = Regex::new(r"!snippet\.([[:word:]]+)!").unwrap();
+    let mut sql = sql.to_string();
+    for _ in 0..=MAX_SNIPPET_DEPTH {
+        if !re.is_match(&sql) {
+            return Ok(sql);
+        }
+        if let Some(unknown) = re
+            .captures_iter(&sql)
+            .map(|cap| cap[1].to_string())
+            .find(|name| !snippets.contains_key(name))
+        {
+            return Err(format!("Unknown SQL snippet '{}'", unknown));
+        }
+        sql = re
+            .replace_all(&sql, |cap: &regex::Captures| snippets[&cap[1]].clone())
+            .to_string();
+    }
+    Err(format!(
+        "SQL snippets nested deeper than {} levels",
+        MAX_SNIPPET_DEPTH
+    ))
+}
+
+impl TilesetCfg {
+    /// Tileset configuration with SQL snippets expanded in layer queries and filters
+    pub fn with_sql_snippets(&self, snippets: &HashMap<String, String>) -> Result<Self, String> {
+        let mut cfg = self.clone();
+        for layer in cfg.layers.iter_mut() {
+            let name = layer.name.clone();
+            let context = |e| format!("Layer '{}': {}", name, e);
+            for query in layer.query.iter_mut() {
+                if let Some(ref sql) = query.sql {
+                    query.sql = Some(expand_sql_snippets(sql, snippets).map_err(context)?);
+                }
+            }
+            if let Some(ref filter) = layer.filter {
+                layer.filter = Some(expand_sql_snippets(filter, snippets).map_err(context)?);
+            }
+            if let Some(ref sql) = layer.changes_sql {
+                layer.changes_sql = Some(expand_sql_snippets(sql, snippets).map_err(context)?);
+            }
+            if let Some(ref sql) = layer.updated_sql {
+                layer.updated_sql = Some(expand_sql_snippets(sql, snippets).map_err(context)?);
+            }
+        }
+        Ok(cfg)
+    }
 }
 
 /// Parse the configuration into an config struct.
 pub fn parse_config<'a, T: Deserialize<'a>>(config_toml: String, path: &str) -> Result<T, String> {
+    parse_config_checked(config_toml, path, false)
+}
+
+/// Line number of the first assignment or table header of `key`
+fn key_line(toml: &str, key: &str) -> Option<usize> {
+    toml.lines()
+        .position(|line| {
+            let line = line.trim();
+            let assignment = line
+                .strip_prefix(key)
+                .filter(|rest| rest.trim_start().starts_with('='))
+                .is_some();
+            let header = line.starts_with('[')
+                && line
+                    .trim_matches(|c| c == '[' || c == ']')
+                    .split('.')
+                    .any(|part| part.trim() == key);
+            assignment || header
+        })
+        .map(|idx| idx + 1)
+}
+
+/// Location of a configuration key, e.g. `tileset[0].layer[1].bufer_size`
+fn key_path(path: &Path<'_>) -> String {
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{}]", key_path(parent), index),
+        Path::Map { parent, key } => match key_path(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => key_path(parent),
+    }
+}
+
+/// Parse the configuration into an config struct and check for unknown keys.
+/// In strict mode, unknown keys are an error, otherwise they are logged as warning.
+pub fn parse_config_checked<'a, T: Deserialize<'a>>(
+    config_toml: String,
+    path: &str,
+    strict: bool,
+) -> Result<T, String> {
     // Check for old ${var} expressions
     let re = Regex::new(r"\$\{([[:alnum:]]+)\}").unwrap();
     if re.is_match(&config_toml) {
@@ -287,7 +739,26 @@ pub fn parse_config<'a, T: Deserialize<'a>>(config_toml: String, path: &str) ->
         .render(path, &context)
         .map_err(|e| format!("Template error: {}", e.source().unwrap()))?;
 
-    toml.parse::<Value>()
-        .and_then(|cfg| cfg.try_into::<T>())
-        .map_err(|err| format!("{} - {}", path, err))
+    let mut unknown = Vec::new();
+    let cfg = toml
+        .parse::<Value>()
+        .and_then(|value| serde_ignored::deserialize(value, |key| unknown.push(key_path(&key))))
+        .map_err(|err| format!("{} - {}", path, err))?;
+    let messages: Vec<String> = unknown
+        .iter()
+        .map(|key| {
+            let name = key.rsplit('.').next().unwrap_or("");
+            let location = key_line(&toml, name)
+                .map(|line| format!("{}:{}", path, line))
+                .unwrap_or_else(|| path.to_string());
+            format!("{} - Unknown configuration key `{}`", location, key)
+        })
+        .collect();
+    if strict && !messages.is_empty() {
+        return Err(messages.join("\n"));
+    }
+    for message in messages {
+        warn!("{}", message);
+    }
+    Ok(cfg)
 }
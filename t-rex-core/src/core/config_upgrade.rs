@@ -0,0 +1,125 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Conversion of configurations written for older t-rex versions
+
+use regex::Regex;
+
+const NOTE: &str = "# t_rex upgrade-config:";
+
+/// Keys of a user defined grid, moved into `[grid.user]` in 0.8
+const USER_GRID_KEYS: [&str; 7] = [
+    "width",
+    "height",
+    "extent",
+    "srid",
+    "units",
+    "resolutions",
+    "origin",
+];
+
+/// Name of a TOML section header line like `[[tileset.layer]]`
+pub fn section_name(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with('[') {
+        Some(line.trim_matches(|c| c == '[' || c == ']').trim())
+    } else {
+        None
+    }
+}
+
+/// Key of a TOML key/value line
+pub fn key_name(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with('#') || line.starts_with('[') {
+        return None;
+    }
+    line.find('=').map(|pos| line[..pos].trim())
+}
+
+/// Rewrite configuration to the current schema with comments about applied changes
+pub fn upgrade_config(config: &str) -> String {
+    let env_re = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+    let lines: Vec<&str> = config.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut section = String::new();
+    for (no, line) in lines.iter().enumerate() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        if let Some(name) = section_name(line) {
+            section = name.to_string();
+            if line.trim() == "[datasource]" {
+                out.push(format!(
+                    "{} datasource configuration is a table array since 0.8",
+                    NOTE
+                ));
+                out.push(format!("{}[[datasource]]", indent));
+                continue;
+            }
+            if line.trim() == "[grid]" {
+                let user_grid = lines[no + 1..]
+                    .iter()
+                    .take_while(|l| section_name(l).is_none())
+                    .filter_map(|l| key_name(l))
+                    .any(|key| USER_GRID_KEYS.contains(&key));
+                if user_grid {
+                    out.push(format!(
+                        "{} user defined grid is configured in [grid.user] since 0.8",
+                        NOTE
+                    ));
+                    out.push(format!("{}[grid.user]", indent));
+                    section = "grid.user".to_string();
+                    continue;
+                }
+            }
+            out.push(line.to_string());
+            continue;
+        }
+
+        let mut line = line.to_string();
+        if env_re.is_match(&line) {
+            out.push(format!(
+                "{} environment variables are expanded with {{{{ env.VARNAME }}}} since 0.10",
+                NOTE
+            ));
+            line = env_re.replace_all(&line, "{{ env.$1 }}").to_string();
+        }
+        match (section.as_str(), key_name(&line)) {
+            ("datasource", Some("type")) => {
+                out.push(format!(
+                    "{} datasource type is derived from connection parameters since 0.8",
+                    NOTE
+                ));
+                out.push(format!("#{}", line));
+            }
+            ("datasource", Some("url")) => {
+                out.push(format!("{} `url` renamed to `dbconn` in 0.8", NOTE));
+                out.push(line.replacen("url", "dbconn", 1));
+            }
+            ("grid.user", Some("units")) => {
+                let lower = line.to_lowercase();
+                if lower != line {
+                    out.push(format!("{} grid units are lower case since 0.8", NOTE));
+                }
+                out.push(lower);
+            }
+            (_, Some("buffer-size")) => {
+                out.push(format!(
+                    "{} `buffer-size` renamed to `buffer_size` in 0.7.3",
+                    NOTE
+                ));
+                out.push(line.replacen("buffer-size", "buffer_size", 1));
+            }
+            ("tileset", Some("minzoom")) | ("tileset", Some("maxzoom")) => {
+                out.push(format!(
+                    "{} tileset zoom levels limit the zoom range of its layers since 0.14",
+                    NOTE
+                ));
+                out.push(line);
+            }
+            _ => out.push(line),
+        }
+    }
+    out.join("\n") + "\n"
+}
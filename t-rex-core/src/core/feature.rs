@@ -18,10 +18,99 @@ pub enum FeatureAttrValType {
     VarcharArray(Vec<String>),
 }
 
+/// Largest integer exactly representable in JavaScript numbers (2^53 - 1)
+pub const MAX_SAFE_FID: u64 = (1 << 53) - 1;
+
+impl FeatureAttrValType {
+    /// Value as text, used for deriving feature ids
+    pub fn fid_text(&self) -> String {
+        match self {
+            FeatureAttrValType::String(v) => v.clone(),
+            FeatureAttrValType::Float(v) => v.to_string(),
+            FeatureAttrValType::Double(v) => v.to_string(),
+            FeatureAttrValType::Int(v) | FeatureAttrValType::SInt(v) => v.to_string(),
+            FeatureAttrValType::UInt(v) => v.to_string(),
+            FeatureAttrValType::Bool(v) => v.to_string(),
+            FeatureAttrValType::VarcharArray(v) => v.join(","),
+        }
+    }
+}
+
+/// Civil date (year, month, day) of days since the Unix epoch
+/// (http://howardhinnant.github.io/date_algorithms.html)
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// ISO 8601 UTC timestamp of seconds since the Unix epoch, e.g. `2021-01-01T12:10:00Z`
+pub fn utc_timestamp(secs: i64) -> String {
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let time = secs.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Stable feature id for composite or non-integer keys.
+/// 64 bit FNV-1a hash reduced to 53 bits for JavaScript clients.
+pub fn hashed_fid(parts: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            // Unit separator, so that ("ab", "c") and ("a", "bc") differ
+            hash ^= 0x1f;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        for byte in part.as_bytes() {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash & MAX_SAFE_FID
+}
+
+/// Feature id from key column values (NULL values as None).
+/// Non-negative integers of a single key up to `MAX_SAFE_FID` are used directly,
+/// other keys (including larger integers) are hashed.
+pub fn fid_from_values(values: &[Option<FeatureAttrValType>]) -> Option<u64> {
+    match values {
+        [] | [None] => None,
+        [Some(FeatureAttrValType::Int(v))] if *v >= 0 && *v as u64 <= MAX_SAFE_FID => {
+            Some(*v as u64)
+        }
+        [Some(FeatureAttrValType::UInt(v))] if *v <= MAX_SAFE_FID => Some(*v),
+        _ => {
+            let parts: Vec<String> = values
+                .iter()
+                .map(|v| v.as_ref().map(|v| v.fid_text()).unwrap_or_default())
+                .collect();
+            Some(hashed_fid(&parts))
+        }
+    }
+}
+
 pub trait Feature {
     fn fid(&self) -> Option<u64>;
     fn attributes(&self) -> Vec<FeatureAttr>; //TODO: return tuples
     fn geometry(&self) -> Result<GeometryType, String>;
+    /// Raw (E)WKB geometry for encoding without intermediate geometry structs
+    fn geometry_wkb(&self) -> Option<&[u8]> {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -50,3 +139,59 @@ impl Feature for FeatureStruct {
         Ok(GeometryType::Point(Point::new(0.0, 0.0, None)))
     }
 }
+
+#[test]
+fn test_fid_from_values() {
+    use FeatureAttrValType::*;
+
+    assert_eq!(fid_from_values(&[]), None);
+    assert_eq!(fid_from_values(&[None]), None);
+    assert_eq!(fid_from_values(&[Some(Int(42))]), Some(42));
+    assert_eq!(
+        fid_from_values(&[Some(UInt(MAX_SAFE_FID))]),
+        Some(MAX_SAFE_FID)
+    );
+    // Integers above 2^53 are hashed like text keys
+    assert_eq!(
+        fid_from_values(&[Some(UInt(u64::MAX))]),
+        Some(hashed_fid(&[u64::MAX.to_string()]))
+    );
+    assert_eq!(
+        fid_from_values(&[Some(Int(1 << 53))]),
+        Some(hashed_fid(&[(1u64 << 53).to_string()]))
+    );
+    // Hashed ids are stable and JavaScript safe
+    let fid = fid_from_values(&[Some(String("CH".to_string())), Some(Int(1))]).unwrap();
+    assert_eq!(
+        fid_from_values(&[Some(String("CH".to_string())), Some(Int(1))]),
+        Some(fid)
+    );
+    assert!(fid <= MAX_SAFE_FID);
+    assert_ne!(
+        fid_from_values(&[Some(String("CH".to_string())), Some(Int(2))]),
+        Some(fid)
+    );
+    assert_ne!(
+        fid_from_values(&[
+            Some(String("ab".to_string())),
+            Some(String("c".to_string()))
+        ]),
+        fid_from_values(&[
+            Some(String("a".to_string())),
+            Some(String("bc".to_string()))
+        ])
+    );
+    // Negative and text keys are hashed
+    assert_eq!(
+        fid_from_values(&[Some(Int(-1))]),
+        Some(hashed_fid(&["-1".to_string()]))
+    );
+    assert_eq!(
+        fid_from_values(&[Some(String("a3f9".to_string()))]),
+        Some(hashed_fid(&["a3f9".to_string()]))
+    );
+    assert_eq!(
+        hashed_fid(&["".to_string()]),
+        0xcbf2_9ce4_8422_2325 & MAX_SAFE_FID
+    );
+}
@@ -26,12 +26,21 @@ impl From<&ExtentCfg> for Extent {
     }
 }
 
+impl GridCfg {
+    /// Name of predefined grid or "user"
+    pub fn name(&self) -> &str {
+        self.predefined.as_deref().unwrap_or("user")
+    }
+}
+
 impl<'a> Config<'a, GridCfg> for Grid {
     fn from_config(grid_cfg: &GridCfg) -> Result<Self, String> {
         if let Some(ref gridname) = grid_cfg.predefined {
             match gridname.as_str() {
                 "wgs84" => Ok(Grid::wgs84()),
                 "web_mercator" => Ok(Grid::web_mercator()),
+                "world_crs84_quad" => Ok(Grid::world_crs84_quad()),
+                "lv95" => Ok(Grid::lv95()),
                 _ => Err(format!("Unkown grid '{}'", gridname)),
             }
         } else if let Some(ref usergrid) = grid_cfg.user {
@@ -46,7 +55,18 @@ impl<'a> Config<'a, GridCfg> for Grid {
                 "BottomLeft" => Ok(Origin::BottomLeft),
                 _ => Err(format!("Unexpected enum value '{}'", usergrid.origin)),
             };
-            let grid = Grid::new(
+            if usergrid.resolutions.is_empty() {
+                return Err("Grid resolutions undefined".to_string());
+            }
+            if usergrid.resolutions.iter().any(|res| *res <= 0.0)
+                || usergrid.resolutions.windows(2).any(|res| res[1] >= res[0])
+            {
+                return Err(
+                    "Grid resolutions must be positive and ordered from largest to smallest"
+                        .to_string(),
+                );
+            }
+            let mut grid = Grid::new(
                 usergrid.width,
                 usergrid.height,
                 Extent::from(&usergrid.extent),
@@ -55,6 +75,30 @@ impl<'a> Config<'a, GridCfg> for Grid {
                 usergrid.resolutions.clone(),
                 origin?,
             );
+            match (&usergrid.matrix_width, &usergrid.matrix_height) {
+                (Some(widths), Some(heights)) => {
+                    let nlevels = usergrid.resolutions.len();
+                    if widths.len() != nlevels || heights.len() != nlevels {
+                        return Err(format!(
+                            "Grid matrix_width and matrix_height must have {} entries (one per resolution)",
+                            nlevels
+                        ));
+                    }
+                    grid.set_matrix_sizes(
+                        widths
+                            .iter()
+                            .cloned()
+                            .zip(heights.iter().cloned())
+                            .collect(),
+                    );
+                }
+                (None, None) => {}
+                _ => {
+                    return Err(
+                        "Grid matrix_width and matrix_height must be defined together".to_string(),
+                    )
+                }
+            }
             Ok(grid)
         } else {
             Err("Invalid grid definition".to_string())
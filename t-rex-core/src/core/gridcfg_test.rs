@@ -28,6 +28,24 @@ fn test_grid_from_config() {
         }
     );
 
+    let toml = r#"
+        #[grid]
+        predefined = "lv95"
+        "#;
+    let config: GridCfg = parse_config(toml.to_string(), "").unwrap();
+    let grid = Grid::from_config(&config).unwrap();
+    assert_eq!(grid.srid, 2056);
+    assert_eq!(grid.origin, Origin::TopLeft);
+
+    let toml = r#"
+        #[grid]
+        predefined = "world_crs84_quad"
+        "#;
+    let config: GridCfg = parse_config(toml.to_string(), "").unwrap();
+    let grid = Grid::from_config(&config).unwrap();
+    assert_eq!(grid.srid, 4326);
+    assert_eq!(grid.matrix_size(0), (2, 1));
+
     let toml = r#"
         #[grid.user]
         [user]
@@ -74,3 +92,75 @@ fn test_grid_from_config() {
         }
     );
 }
+
+#[test]
+fn test_custom_matrix_grid() {
+    use crate::core::parse_config;
+
+    // Dutch RD grid (EPSG:28992) with non-square tiles and explicit matrix sizes
+    let toml = r#"
+        [user]
+        width = 256
+        height = 128
+        extent = { minx = -285401.92, miny = 22598.08, maxx = 595401.92, maxy = 903401.92 }
+        srid = 28992
+        units = "m"
+        resolutions = [3440.64, 1720.32, 860.16, 300.0]
+        matrix_width = [1, 2, 4, 12]
+        matrix_height = [2, 4, 8, 23]
+        origin = "TopLeft"
+        "#;
+    let config: GridCfg = parse_config(toml.to_string(), "").unwrap();
+    let grid = Grid::from_config(&config).unwrap();
+    assert_eq!(grid.nlevels(), 4);
+    assert_eq!(grid.matrix_size(0), (1, 2));
+    assert_eq!(grid.matrix_size(3), (12, 23));
+    assert_eq!(
+        grid.tile_extent(0, 0, 0),
+        Extent {
+            minx: -285401.92,
+            miny: 463000.00000000006,
+            maxx: 595401.9199999999,
+            maxy: 903401.92,
+        }
+    );
+    // XYZ addressing uses configured matrix height
+    assert_eq!(grid.ytile_from_xyz(0, 0), 1);
+
+    let toml = r#"
+        [user]
+        width = 256
+        height = 256
+        extent = { minx = -285401.92, miny = 22598.08, maxx = 595401.92, maxy = 903401.92 }
+        srid = 28992
+        units = "m"
+        resolutions = [3440.64, 1720.32]
+        matrix_width = [1, 2, 4]
+        matrix_height = [1, 2, 4]
+        origin = "TopLeft"
+        "#;
+    let config: GridCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        Grid::from_config(&config).err(),
+        Some(
+            "Grid matrix_width and matrix_height must have 2 entries (one per resolution)"
+                .to_string()
+        )
+    );
+
+    let toml = r#"
+        [user]
+        width = 256
+        height = 256
+        extent = { minx = -285401.92, miny = 22598.08, maxx = 595401.92, maxy = 903401.92 }
+        srid = 28992
+        units = "m"
+        resolutions = [1720.32, 3440.64]
+        origin = "TopLeft"
+        "#;
+    let config: GridCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        Grid::from_config(&config).err(),
+        Some("Grid resolutions must be positive and ordered from largest to smallest".to_string())
+    );
+}
@@ -0,0 +1,117 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Hillshading of elevation models
+
+use crate::core::config::HillshadeCfg;
+use crate::core::Config;
+use std::f64::consts::PI;
+
+/// Hillshade parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hillshade {
+    /// Direction of light source in degrees clockwise from north
+    pub azimuth: f64,
+    /// Altitude of light source in degrees above horizon
+    pub altitude: f64,
+    /// Vertical exaggeration (or conversion factor between elevation and ground units)
+    pub z_factor: f64,
+}
+
+impl Default for Hillshade {
+    fn default() -> Self {
+        Hillshade {
+            azimuth: 315.0,
+            altitude: 45.0,
+            z_factor: 1.0,
+        }
+    }
+}
+
+impl Hillshade {
+    /// Shade elevation values (row by row, north up) with Horn's method.
+    /// `dem` has a buffer of one pixel around the `width` x `height` output, so that
+    /// border pixels are shaded with the elevation of neighbouring tiles.
+    /// `cell_size` is the pixel size in ground units.
+    pub fn render(&self, dem: &[f32], width: usize, height: usize, cell_size: f64) -> Vec<u8> {
+        assert_eq!(dem.len(), (width + 2) * (height + 2));
+        let zenith = (90.0 - self.altitude) * PI / 180.0;
+        let azimuth = (360.0 - self.azimuth + 90.0) * PI / 180.0;
+        let elevation = |col: isize, row: isize| -> f64 {
+            dem[(row + 1) as usize * (width + 2) + (col + 1) as usize] as f64
+        };
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in 0..height as isize {
+            for col in 0..width as isize {
+                let (a, b, c) = (
+                    elevation(col - 1, row - 1),
+                    elevation(col, row - 1),
+                    elevation(col + 1, row - 1),
+                );
+                let (d, f) = (elevation(col - 1, row), elevation(col + 1, row));
+                let (g, h, i) = (
+                    elevation(col - 1, row + 1),
+                    elevation(col, row + 1),
+                    elevation(col + 1, row + 1),
+                );
+                let dzdx = ((c + 2.0 * f + i) - (a + 2.0 * d + g)) / (8.0 * cell_size);
+                let dzdy = ((g + 2.0 * h + i) - (a + 2.0 * b + c)) / (8.0 * cell_size);
+                let slope = (self.z_factor * (dzdx * dzdx + dzdy * dzdy).sqrt()).atan();
+                let aspect = dzdy.atan2(-dzdx);
+                let shade = zenith.cos() * slope.cos()
+                    + zenith.sin() * slope.sin() * (azimuth - aspect).cos();
+                pixels.push((255.0 * shade).clamp(0.0, 255.0).round() as u8);
+            }
+        }
+        pixels
+    }
+}
+
+impl<'a> Config<'a, HillshadeCfg> for Hillshade {
+    fn from_config(cfg: &HillshadeCfg) -> Result<Self, String> {
+        let default = Hillshade::default();
+        let hillshade = Hillshade {
+            azimuth: cfg.azimuth.unwrap_or(default.azimuth),
+            altitude: cfg.altitude.unwrap_or(default.altitude),
+            z_factor: cfg.z_factor.unwrap_or(default.z_factor),
+        };
+        if hillshade.altitude < 0.0 || hillshade.altitude > 90.0 {
+            return Err(format!(
+                "Invalid hillshade altitude {} (expected 0-90)",
+                hillshade.altitude
+            ));
+        }
+        Ok(hillshade)
+    }
+    fn gen_config() -> String {
+        "#hillshade = { azimuth = 315, altitude = 45, z_factor = 1.0 }\n".to_string()
+    }
+}
+
+#[test]
+fn test_hillshade() {
+    let hillshade = Hillshade::default();
+    // Flat terrain is lit by cos(zenith)
+    let flat = vec![100.0; 25];
+    assert_eq!(hillshade.render(&flat, 3, 3, 10.0), vec![180; 9]);
+
+    // Slope facing north-west (towards the light source) is brighter than the flat case
+    let rising_east = vec![0.0, 10.0, 20.0, 0.0, 10.0, 20.0, 0.0, 10.0, 20.0];
+    let lit = hillshade.render(&rising_east, 1, 1, 10.0);
+    let falling_east: Vec<f32> = rising_east.iter().map(|z| 20.0 - z).collect();
+    let shaded = hillshade.render(&falling_east, 1, 1, 10.0);
+    assert_eq!(lit, vec![218]);
+    assert!(shaded[0] < 180);
+
+    // Border pixels are shaded with the buffer values
+    let rising_east: Vec<f32> = (0..16).map(|i| (i % 4) as f32 * 10.0).collect();
+    assert_eq!(hillshade.render(&rising_east, 2, 2, 10.0), vec![218; 4]);
+
+    let hillshade = Hillshade {
+        azimuth: 90.0,
+        ..Default::default()
+    };
+    assert!(hillshade.render(&rising_east, 2, 2, 10.0)[0] < 100);
+}
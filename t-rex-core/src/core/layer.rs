@@ -3,10 +3,13 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::core::config::{self, LayerCfg};
+use crate::core::config::{self, LayerCfg, SimplifyCfg};
+use crate::core::feature::utc_timestamp;
 use crate::core::Config;
 use crate::service::glstyle_converter::toml_style_to_gljson;
 use std::collections::HashMap;
+use std::fs;
+use std::time::UNIX_EPOCH;
 
 #[derive(Clone, Debug)]
 pub struct LayerQuery {
@@ -17,20 +20,103 @@ pub struct LayerQuery {
     pub sql: Option<String>,
 }
 
+/// Change frequency of layer data, ordered from least to most volatile
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Volatility {
+    Static,
+    Daily,
+    Live,
+}
+
+impl Volatility {
+    pub fn parse(value: &str) -> Option<Volatility> {
+        match value {
+            "static" => Some(Volatility::Static),
+            "daily" => Some(Volatility::Daily),
+            "live" => Some(Volatility::Live),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Volatility::Static => "static",
+            Volatility::Daily => "daily",
+            Volatility::Live => "live",
+        }
+    }
+    /// Cache-Control max-age in seconds
+    pub fn max_age(&self) -> u32 {
+        match self {
+            Volatility::Static => 86400,
+            Volatility::Daily => 3600,
+            Volatility::Live => 0,
+        }
+    }
+    /// Lifetime of cached tiles in seconds (None: unlimited)
+    pub fn cache_ttl(&self) -> Option<u64> {
+        match self {
+            Volatility::Static => None,
+            Volatility::Daily => Some(86400),
+            Volatility::Live => Some(0),
+        }
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct Layer {
     pub name: String,
     pub datasource: Option<String>,
     pub geometry_field: Option<String>,
     pub geometry_type: Option<String>,
+    /// Coordinate columns of point rows (CSV)
+    pub x_field: Option<String>,
+    pub y_field: Option<String>,
+    /// Attribute columns (CSV, empty for all)
+    pub fields: Vec<String>,
     /// Spatial reference system (PostGIS SRID)
     pub srid: Option<i32>,
+    /// Use `srid` instead of the SRS reported by the datasource
+    pub force_srid: bool,
+    /// Axis order of source coordinates ("xy" or "yx")
+    pub axis_order: Option<String>,
     /// Handle geometry like one in grid SRS
     pub no_transform: bool,
+    /// Feature id column, or comma separated columns of a composite key
     pub fid_field: Option<String>,
+    /// Skip features with an already encoded fid
+    pub dedup_fid: bool,
+    /// Check uniqueness of fids within tile layer ("warn" or "renumber")
+    pub fid_check: Option<String>,
     // Input for derived queries
     pub table_name: Option<String>,
     pub query_limit: Option<u32>,
+    /// Size of a dedicated connection pool for this layer (PostGIS)
+    pub pool: Option<u16>,
+    /// Feature order within layer (SQL ORDER BY expression)
+    pub order_by: Option<String>,
+    /// Truncate string attribute values to this number of characters
+    pub max_string_length: Option<usize>,
+    /// Array columns as attributes `key.0`, `key.1`, ... instead of JSON text (PostGIS)
+    pub explode_arrays: bool,
+    /// hstore or JSON object columns expanded into one attribute per key (PostGIS)
+    pub expand_fields: Vec<String>,
+    /// Keys of expanded columns to publish (empty for all)
+    pub expand_include: Vec<String>,
+    /// Keys of expanded columns to skip
+    pub expand_exclude: Vec<String>,
+    /// Layer encoding: "st_asmvt" for encoding by PostGIS ST_AsMVT (Default: t-rex)
+    pub encoding: Option<String>,
+    /// Row-level filter condition, e.g. `public = true`. Layers of datasources without
+    /// filter support (non-SQLite files, WFS and upstream tiles) are disabled.
+    pub filter: Option<String>,
+    /// Query returning geometries of features changed since `!since!` (for incremental seeding)
+    pub changes_sql: Option<String>,
+    /// Query returning the time of the last data update, e.g. `SELECT max(updated_at) FROM mytable` (PostGIS)
+    pub updated_sql: Option<String>,
+    /// File whose modification time is the time of the last data update
+    pub updated_file: Option<String>,
+    /// Attributes only published for requests with a valid access token
+    pub protected_fields: Vec<String>,
     // Explicit queries
     pub query: Vec<LayerQuery>,
     pub minzoom: Option<u8>,
@@ -39,14 +125,28 @@ pub struct Layer {
     pub tile_size: u32,
     /// Simplify geometry (lines and polygons)
     pub simplify: bool,
+    /// Derive tolerance, snap grid and minimal areas from grid resolution
+    pub simplify_auto: bool,
     /// Simplification tolerance (default to !pixel_width!/2)
     pub tolerance: String,
+    /// Simplification tolerance in pixels (overrides `tolerance`)
+    pub tolerance_px: Option<f64>,
+    /// Simplification algorithm ("preserve_topology", "douglas_peucker", "visvalingam" or "snap_to_grid")
+    pub simplify_method: Option<String>,
     /// Tile buffer size in pixels (None: no clipping)
     pub buffer_size: Option<u32>,
     /// Fix invalid geometries before clipping (lines and polygons)
     pub make_valid: bool,
+    /// Remove collinear intermediate points when encoding lines and polygons
+    pub remove_collinear: bool,
+    /// Drop polygon holes with a smaller area (in tile coordinate units, see tile_size)
+    pub min_hole_area: Option<f64>,
     /// Apply ST_Shift_Longitude to (transformed) bbox
     pub shift_longitude: bool,
+    /// Tags for catalog discovery
+    pub tags: Vec<String>,
+    /// Data volatility hint for Cache-Control headers and cache lifetime
+    pub volatility: Option<Volatility>,
     // Inline style
     pub style: Option<String>,
 }
@@ -59,6 +159,10 @@ impl Layer {
             ..Default::default()
         }
     }
+    /// Source coordinates have to be swapped into x/y order
+    pub fn swap_axes(&self) -> bool {
+        self.axis_order.as_deref() == Some("yx")
+    }
     pub fn minzoom(&self) -> u8 {
         self.minzoom
             .unwrap_or(self.query.iter().map(|q| q.minzoom).min().unwrap_or(0))
@@ -72,6 +176,17 @@ impl Layer {
                 .unwrap_or(default),
         )
     }
+    /// Key columns of fid_field (comma separated for composite keys)
+    pub fn fid_fields(&self) -> Vec<&str> {
+        match self.fid_field {
+            Some(ref fid_field) => fid_field
+                .split(',')
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
     /// Query config for zoom level
     fn query_cfg<F>(&self, level: u8, check: F) -> Option<&LayerQuery>
     where
@@ -102,11 +217,55 @@ impl Layer {
     }
     /// tolerance config for zoom level
     pub fn tolerance(&self, level: u8) -> &String {
+        if self.simplify_auto {
+            return &self.tolerance;
+        }
         let query_cfg = self.query_cfg(level, |q| q.tolerance.is_some());
         query_cfg
             .and_then(|q| q.tolerance.as_ref())
             .unwrap_or(&self.tolerance)
     }
+    /// Key of an expanded column is published as attribute
+    pub fn expands_key(&self, key: &str) -> bool {
+        (self.expand_include.is_empty() || self.expand_include.iter().any(|k| k == key))
+            && !self.expand_exclude.iter().any(|k| k == key)
+    }
+    /// Layer blob is encoded by the datasource (PostGIS ST_AsMVT)
+    pub fn st_asmvt(&self) -> bool {
+        self.encoding.as_deref() == Some("st_asmvt")
+    }
+    /// Modification time of `updated_file` as UTC timestamp
+    pub fn file_updated(&self) -> Option<String> {
+        let path = self.updated_file.as_ref()?;
+        let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+        let secs = modified.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        Some(utc_timestamp(secs as i64))
+    }
+    /// Check for invalid or ambiguous zoom ranges of queries
+    fn check_query_ranges(&self) -> Result<(), String> {
+        for (i, q) in self.query.iter().enumerate() {
+            if let Some(maxzoom) = q.maxzoom {
+                if maxzoom < q.minzoom {
+                    return Err(format!(
+                        "Layer '{}': query minzoom {} is greater than maxzoom {}",
+                        self.name, q.minzoom, maxzoom
+                    ));
+                }
+            }
+            // Overlapping ranges are resolved by the highest minzoom,
+            // queries starting at the same zoom level are ambiguous
+            if let Some(other) = self.query[i + 1..]
+                .iter()
+                .find(|other| other.minzoom == q.minzoom)
+            {
+                return Err(format!(
+                    "Layer '{}': queries with same minzoom {} (maxzoom {:?} and {:?})",
+                    self.name, q.minzoom, q.maxzoom, other.maxzoom
+                ));
+            }
+        }
+        Ok(())
+    }
     /// Layer properties needed e.g. for metadata.json
     pub fn metadata(&self) -> HashMap<&str, String> {
         //TODO: return Zoom-Level Array
@@ -142,27 +301,135 @@ impl<'a> Config<'a, LayerCfg> for Layer {
             }
             None => None,
         };
-        Ok(Layer {
+        let (simplify, simplify_auto) = match layer_cfg.simplify {
+            SimplifyCfg::Enabled(enabled) => (enabled, false),
+            SimplifyCfg::Mode(ref mode) if mode == "auto" => (true, true),
+            SimplifyCfg::Mode(ref mode) => {
+                return Err(format!(
+                    "Layer '{}': invalid simplify mode '{}' (expected true, false or \"auto\")",
+                    layer_cfg.name, mode
+                ))
+            }
+        };
+        let tolerance = match layer_cfg.tolerance_px {
+            // Pixel width is set per zoom level
+            Some(px) => format!("!pixel_width!*{:?}", px),
+            None if simplify_auto => config::DEFAULT_TOLERANCE.to_string(),
+            None => layer_cfg.tolerance.clone(),
+        };
+        let min_hole_area = if simplify_auto {
+            // One pixel in tile coordinate units
+            let px = layer_cfg.tile_size as f64 / 256.0;
+            Some(layer_cfg.min_hole_area.unwrap_or(px * px))
+        } else {
+            layer_cfg.min_hole_area
+        };
+        let volatility = match layer_cfg.volatility {
+            Some(ref value) => Some(Volatility::parse(value).ok_or(format!(
+                "Layer '{}': invalid volatility '{}' (expected 'static', 'daily' or 'live')",
+                layer_cfg.name, value
+            ))?),
+            None => None,
+        };
+        match layer_cfg.encoding.as_deref() {
+            None | Some("t-rex") | Some("st_asmvt") => {}
+            Some(encoding) => {
+                return Err(format!(
+                    "Layer '{}': invalid encoding '{}' (expected 't-rex' or 'st_asmvt')",
+                    layer_cfg.name, encoding
+                ))
+            }
+        }
+        let layer = Layer {
             name: layer_cfg.name.clone(),
             datasource: layer_cfg.datasource.clone(), //TODO: inherit from parents if None?
             geometry_field: layer_cfg.geometry_field.clone(),
             geometry_type: layer_cfg.geometry_type.clone(),
+            x_field: layer_cfg.x_field.clone(),
+            y_field: layer_cfg.y_field.clone(),
+            fields: layer_cfg.fields.clone(),
             srid: layer_cfg.srid,
+            force_srid: layer_cfg.force_srid,
+            axis_order: layer_cfg.axis_order.clone(),
             no_transform: layer_cfg.no_transform,
             fid_field: layer_cfg.fid_field.clone(),
+            dedup_fid: layer_cfg.dedup_fid,
+            fid_check: layer_cfg.fid_check.clone(),
             table_name: layer_cfg.table_name.clone(),
             query_limit: layer_cfg.query_limit,
+            pool: layer_cfg.pool,
+            order_by: layer_cfg.order_by.clone(),
+            max_string_length: layer_cfg.max_string_length,
+            explode_arrays: layer_cfg.explode_arrays,
+            expand_fields: layer_cfg.expand_fields.clone(),
+            expand_include: layer_cfg.expand_include.clone(),
+            expand_exclude: layer_cfg.expand_exclude.clone(),
+            encoding: layer_cfg.encoding.clone(),
+            filter: layer_cfg.filter.clone(),
+            changes_sql: layer_cfg.changes_sql.clone(),
+            updated_sql: layer_cfg.updated_sql.clone(),
+            updated_file: layer_cfg.updated_file.clone(),
+            protected_fields: layer_cfg.protected_fields.clone(),
             query: queries,
             minzoom: layer_cfg.minzoom,
             maxzoom: layer_cfg.maxzoom,
             tile_size: layer_cfg.tile_size,
-            simplify: layer_cfg.simplify,
-            tolerance: layer_cfg.tolerance.clone(),
+            simplify,
+            simplify_auto,
+            tolerance,
+            tolerance_px: layer_cfg.tolerance_px,
+            simplify_method: layer_cfg.simplify_method.clone(),
             buffer_size: layer_cfg.buffer_size,
             make_valid: layer_cfg.make_valid,
+            remove_collinear: layer_cfg.remove_collinear,
+            min_hole_area,
             shift_longitude: layer_cfg.shift_longitude,
+            tags: layer_cfg.tags.clone(),
+            volatility,
             style: style,
-        })
+        };
+        layer.check_query_ranges()?;
+        match layer.fid_check.as_deref() {
+            None | Some("warn") | Some("renumber") => {}
+            Some(check) => {
+                return Err(format!(
+                    "Layer '{}': invalid fid_check '{}' (expected 'warn' or 'renumber')",
+                    layer.name, check
+                ))
+            }
+        }
+        match layer.simplify_method.as_deref() {
+            None
+            | Some("preserve_topology")
+            | Some("douglas_peucker")
+            | Some("visvalingam")
+            | Some("snap_to_grid") => {}
+            Some(method) => {
+                return Err(format!(
+                    "Layer '{}': invalid simplify_method '{}' (expected 'preserve_topology', 'douglas_peucker', 'visvalingam' or 'snap_to_grid')",
+                    layer.name, method
+                ))
+            }
+        }
+        match layer.axis_order.as_deref() {
+            None | Some("xy") | Some("yx") => {}
+            Some(order) => {
+                return Err(format!(
+                    "Layer '{}': invalid axis_order '{}' (expected 'xy' or 'yx')",
+                    layer.name, order
+                ))
+            }
+        }
+        if layer.force_srid && layer.srid.is_none() {
+            return Err(format!("Layer '{}': force_srid requires srid", layer.name));
+        }
+        if (layer.dedup_fid || layer.fid_check.is_some()) && layer.fid_field.is_none() {
+            warn!(
+                "Layer '{}': dedup_fid or fid_check without fid_field has no effect",
+                layer.name
+            );
+        }
+        Ok(layer)
     }
 
     fn gen_config() -> String {
@@ -173,16 +440,38 @@ name = "points"
 #maxzoom = 22
 #attribution = "© Contributeurs de OpenStreetMap" # Acknowledgment of ownership, authorship or copyright.
 #cache_limits = {minzoom = 0, maxzoom = 22, no_cache = false}
+# Cache of this tileset instead of [cache] (same storage options, empty table disables caching)
+#[tileset.cache.file]
+#base = "/tmp/mvtcache-points"
 
 [[tileset.layer]]
 name = "points"
 table_name = "mytable"
 geometry_field = "wkb_geometry"
 geometry_type = "POINT"
+#force_srid = true # Use srid instead of the SRS reported by the datasource
+#axis_order = "yx" # Source coordinates in lat/lon (northing/easting) order
 #simplify = true
 #tolerance = "!pixel_width!/2"
+#tolerance_px = 0.5 # Tolerance in pixels (instead of tolerance)
+#simplify_method = "douglas_peucker" # preserve_topology, douglas_peucker, visvalingam or snap_to_grid
 #buffer_size = 10
 #make_valid = true
+#remove_collinear = true
+#min_hole_area = 256.0
+#order_by = "ST_Area(wkb_geometry) DESC"
+#max_string_length = 1000
+#explode_arrays = true # Array columns as attributes key.0, key.1, ... instead of JSON text
+#expand_fields = ["tags"] # hstore or JSON columns as one attribute per key
+#expand_include = ["name", "ref"]
+#expand_exclude = ["note"]
+#encoding = "st_asmvt" # Encode layer in PostGIS with ST_AsMVT
+#filter = "public = true"
+#changes_sql = "SELECT wkb_geometry FROM mytable WHERE updated_at > !since!"
+#updated_sql = "SELECT max(updated_at) FROM mytable" # Data update time in TileJSON and X-Data-Updated header
+#updated_file = "/data/import.log" # Alternative: modification time of file
+#protected_fields = ["owner"]
+#volatility = "daily" # static, daily or live: Cache-Control max-age and cache lifetime
 #[[tileset.layer.query]]
 #minzoom = 0
 #maxzoom = 22
@@ -214,16 +503,37 @@ geometry_type = "POINT"
             Some(ref geometry_type) => lines.push(format!("geometry_type = \"{}\"", geometry_type)),
             _ => lines.push("#geometry_type = \"POINT\"".to_string()),
         }
+        if let Some(ref x_field) = self.x_field {
+            lines.push(format!("x_field = \"{}\"", x_field));
+        }
+        if let Some(ref y_field) = self.y_field {
+            lines.push(format!("y_field = \"{}\"", y_field));
+        }
+        if !self.fields.is_empty() {
+            lines.push(format!("fields = {:?}", self.fields));
+        }
         match self.srid {
             Some(ref srid) => lines.push(format!("srid = {}", srid)),
             _ => lines.push("#srid = 3857".to_string()),
         }
+        if self.force_srid {
+            lines.push("force_srid = true".to_string());
+        }
+        if let Some(ref axis_order) = self.axis_order {
+            lines.push(format!("axis_order = \"{}\"", axis_order));
+        }
         if self.no_transform {
             lines.push(format!("no_transform = true"));
         }
         if let Some(ref fid_field) = self.fid_field {
             lines.push(format!("fid_field = \"{}\"", fid_field));
         }
+        if self.dedup_fid {
+            lines.push("dedup_fid = true".to_string());
+        }
+        if let Some(ref fid_check) = self.fid_check {
+            lines.push(format!("fid_check = \"{}\"", fid_check));
+        }
         if self.tile_size != 4096 {
             lines.push(format!(r#"tile_size = "{}""#, self.tile_size));
         }
@@ -235,20 +545,85 @@ geometry_type = "POINT"
             true => lines.push(format!("make_valid = true")),
             _ => lines.push(format!("#make_valid = true")),
         }
+        if self.remove_collinear {
+            lines.push("remove_collinear = true".to_string());
+        }
+        if let Some(min_hole_area) = self.min_hole_area {
+            lines.push(format!("min_hole_area = {:?}", min_hole_area));
+        }
         if self.shift_longitude {
             lines.push(format!("shift_longitude = true"));
         }
+        if !self.tags.is_empty() {
+            lines.push(format!("tags = {:?}", self.tags));
+        }
+        if let Some(volatility) = self.volatility {
+            lines.push(format!("volatility = \"{}\"", volatility.as_str()));
+        }
         if self.geometry_type != Some("POINT".to_string()) {
             // simplify is ignored for points
-            lines.push(format!("simplify = {}", self.simplify));
-            if self.simplify && self.tolerance != config::DEFAULT_TOLERANCE {
+            if self.simplify_auto {
+                lines.push("simplify = \"auto\"".to_string());
+            } else {
+                lines.push(format!("simplify = {}", self.simplify));
+            }
+            if let Some(ref method) = self.simplify_method {
+                lines.push(format!("simplify_method = \"{}\"", method));
+            }
+            if let Some(px) = self.tolerance_px {
+                lines.push(format!("tolerance_px = {:?}", px));
+            } else if self.simplify
+                && !self.simplify_auto
+                && self.tolerance != config::DEFAULT_TOLERANCE
+            {
                 lines.push(format!("tolerance = \"{}\"", self.tolerance));
+            } else {
+                lines.push(format!("#tolerance = \"{}\"", config::DEFAULT_TOLERANCE));
             }
         }
         match self.query_limit {
             Some(ref query_limit) => lines.push(format!("query_limit = {}", query_limit)),
             _ => lines.push("#query_limit = 1000".to_string()),
         }
+        if let Some(pool) = self.pool {
+            lines.push(format!("pool = {}", pool));
+        }
+        if let Some(ref order_by) = self.order_by {
+            lines.push(format!("order_by = \"{}\"", order_by));
+        }
+        if let Some(max_string_length) = self.max_string_length {
+            lines.push(format!("max_string_length = {}", max_string_length));
+        }
+        if self.explode_arrays {
+            lines.push("explode_arrays = true".to_string());
+        }
+        if !self.expand_fields.is_empty() {
+            lines.push(format!("expand_fields = {:?}", self.expand_fields));
+        }
+        if !self.expand_include.is_empty() {
+            lines.push(format!("expand_include = {:?}", self.expand_include));
+        }
+        if !self.expand_exclude.is_empty() {
+            lines.push(format!("expand_exclude = {:?}", self.expand_exclude));
+        }
+        if let Some(ref encoding) = self.encoding {
+            lines.push(format!("encoding = \"{}\"", encoding));
+        }
+        if let Some(ref filter) = self.filter {
+            lines.push(format!("filter = \"{}\"", filter));
+        }
+        if let Some(ref changes_sql) = self.changes_sql {
+            lines.push(format!("changes_sql = \"{}\"", changes_sql));
+        }
+        if let Some(ref updated_sql) = self.updated_sql {
+            lines.push(format!("updated_sql = \"{}\"", updated_sql));
+        }
+        if let Some(ref updated_file) = self.updated_file {
+            lines.push(format!("updated_file = \"{}\"", updated_file));
+        }
+        if !self.protected_fields.is_empty() {
+            lines.push(format!("protected_fields = {:?}", self.protected_fields));
+        }
         match self.query(0) {
             Some(ref query) => {
                 lines.push("[[tileset.layer.query]]".to_string());
@@ -343,3 +343,287 @@ fn test_layers_from_config() {
         Some(" - missing field `name`".to_string())
     );
 }
+
+#[test]
+fn test_query_range_validation() {
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "points"
+        geometry_field = "wkb_geometry"
+        [[query]]
+        minzoom = 10
+        maxzoom = 8
+        sql = "SELECT name,wkb_geometry FROM places_z10"
+        "#;
+    assert_eq!(
+        layer_from_config(toml).err(),
+        Some("Layer 'points': query minzoom 10 is greater than maxzoom 8".to_string())
+    );
+
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "points"
+        geometry_field = "wkb_geometry"
+        [[query]]
+        minzoom = 10
+        maxzoom = 11
+        sql = "SELECT name,wkb_geometry FROM places_z10"
+        [[query]]
+        minzoom = 10
+        sql = "SELECT name,wkb_geometry FROM places_z11"
+        "#;
+    assert_eq!(
+        layer_from_config(toml).err(),
+        Some(
+            "Layer 'points': queries with same minzoom 10 (maxzoom Some(11) and None)".to_string()
+        )
+    );
+
+    // Overlapping ranges with different minzoom are valid
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "points"
+        geometry_field = "wkb_geometry"
+        fid_field = "id"
+        dedup_fid = true
+        [[query]]
+        sql = "SELECT name,wkb_geometry FROM places_z2"
+        [[query]]
+        minzoom = 10
+        maxzoom = 14
+        sql = "SELECT name,wkb_geometry FROM places_z10"
+        "#;
+    let cfg = layer_from_config(toml).unwrap();
+    assert!(cfg.dedup_fid);
+}
+
+#[test]
+fn test_fid_check() {
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "points"
+        table_name = "places"
+        fid_field = "id"
+        fid_check = "renumber"
+        "#;
+    let cfg = layer_from_config(toml).unwrap();
+    assert_eq!(cfg.fid_check, Some("renumber".to_string()));
+
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "points"
+        table_name = "places"
+        fid_field = "id"
+        fid_check = "drop"
+        "#;
+    assert_eq!(
+        layer_from_config(toml).err(),
+        Some(
+            "Layer 'points': invalid fid_check 'drop' (expected 'warn' or 'renumber')".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_srid_override() {
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "stations"
+        table_name = "stations"
+        srid = 4326
+        force_srid = true
+        axis_order = "yx"
+        "#;
+    let layer = layer_from_config(toml).unwrap();
+    assert!(layer.force_srid);
+    assert!(layer.swap_axes());
+    assert!(layer
+        .gen_runtime_config()
+        .contains("force_srid = true\naxis_order = \"yx\""));
+
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "stations"
+        axis_order = "lat/lon"
+        "#;
+    assert_eq!(
+        layer_from_config(toml).err(),
+        Some("Layer 'stations': invalid axis_order 'lat/lon' (expected 'xy' or 'yx')".to_string())
+    );
+
+    let toml = r#"
+        #[[tileset.layer]]
+        name = "stations"
+        force_srid = true
+        "#;
+    assert_eq!(
+        layer_from_config(toml).err(),
+        Some("Layer 'stations': force_srid requires srid".to_string())
+    );
+}
+
+#[test]
+fn test_fid_fields() {
+    let mut layer = Layer::new("parcels");
+    assert!(layer.fid_fields().is_empty());
+    layer.fid_field = Some("id".to_string());
+    assert_eq!(layer.fid_fields(), vec!["id"]);
+    layer.fid_field = Some("canton, parcel_no".to_string());
+    assert_eq!(layer.fid_fields(), vec!["canton", "parcel_no"]);
+}
+
+#[test]
+fn test_simplify_method() {
+    use crate::core::config::LayerCfg;
+    use crate::core::parse_config;
+
+    let toml = r#"
+        name = "roads"
+        simplify = true
+        tolerance_px = 1.5
+        simplify_method = "visvalingam"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    let layer = Layer::from_config(&cfg).unwrap();
+    assert_eq!(layer.tolerance(10), "!pixel_width!*1.5");
+    assert!(layer
+        .gen_runtime_config()
+        .contains("simplify_method = \"visvalingam\"\ntolerance_px = 1.5\n"));
+
+    let toml = r#"
+        name = "roads"
+        simplify_method = "fast"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        Layer::from_config(&cfg).err(),
+        Some("Layer 'roads': invalid simplify_method 'fast' (expected 'preserve_topology', 'douglas_peucker', 'visvalingam' or 'snap_to_grid')".to_string())
+    );
+}
+
+#[test]
+fn test_simplify_auto() {
+    use crate::core::config::LayerCfg;
+    use crate::core::parse_config;
+
+    let toml = r#"
+        name = "landuse"
+        geometry_type = "POLYGON"
+        simplify = "auto"
+        tolerance = "10"
+        [[query]]
+        minzoom = 10
+        tolerance = "!pixel_width!/6"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    let layer = Layer::from_config(&cfg).unwrap();
+    assert!(layer.simplify && layer.simplify_auto);
+    assert_eq!(layer.tolerance(12), "!pixel_width!/2");
+    assert_eq!(layer.min_hole_area, Some(256.0));
+    assert!(layer.gen_runtime_config().contains("simplify = \"auto\"\n"));
+
+    let toml = r#"
+        name = "landuse"
+        simplify = "always"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        Layer::from_config(&cfg).err(),
+        Some(
+            "Layer 'landuse': invalid simplify mode 'always' (expected true, false or \"auto\")"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_volatility() {
+    use crate::core::config::LayerCfg;
+    use crate::core::layer::Volatility;
+    use crate::core::parse_config;
+
+    let toml = r#"
+        name = "traffic"
+        volatility = "live"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    let layer = Layer::from_config(&cfg).unwrap();
+    assert_eq!(layer.volatility, Some(Volatility::Live));
+    assert!(layer
+        .gen_runtime_config()
+        .contains("volatility = \"live\"\n"));
+
+    let toml = r#"
+        name = "traffic"
+        volatility = "hourly"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        Layer::from_config(&cfg).err(),
+        Some(
+            "Layer 'traffic': invalid volatility 'hourly' (expected 'static', 'daily' or 'live')"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_file_updated() {
+    use crate::core::feature::utc_timestamp;
+
+    assert_eq!(utc_timestamp(0), "1970-01-01T00:00:00Z");
+    assert_eq!(utc_timestamp(1_609_503_000), "2021-01-01T12:10:00Z");
+    assert_eq!(utc_timestamp(-1), "1969-12-31T23:59:59Z");
+
+    let mut layer = Layer::new("points");
+    assert_eq!(layer.file_updated(), None);
+    layer.updated_file = Some("Cargo.toml".to_string());
+    let updated = layer.file_updated().unwrap();
+    assert_eq!(updated.len(), 20);
+    assert!(updated.ends_with('Z'));
+    layer.updated_file = Some("missing.txt".to_string());
+    assert_eq!(layer.file_updated(), None);
+}
+
+#[test]
+fn test_expands_key() {
+    let mut layer = Layer::new("roads");
+    layer.expand_fields = vec!["tags".to_string()];
+    assert!(layer.expands_key("name"));
+    layer.expand_exclude = vec!["note".to_string()];
+    assert!(layer.expands_key("name"));
+    assert!(!layer.expands_key("note"));
+    layer.expand_include = vec!["name".to_string(), "ref".to_string()];
+    assert!(layer.expands_key("ref"));
+    assert!(!layer.expands_key("highway"));
+}
+
+#[test]
+fn test_encoding() {
+    use crate::core::config::LayerCfg;
+    use crate::core::parse_config;
+
+    assert!(!Layer::new("roads").st_asmvt());
+    let toml = r#"
+        name = "roads"
+        encoding = "st_asmvt"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    let layer = Layer::from_config(&cfg).unwrap();
+    assert!(layer.st_asmvt());
+    assert!(layer
+        .gen_runtime_config()
+        .contains("encoding = \"st_asmvt\"\n"));
+
+    let toml = r#"
+        name = "roads"
+        encoding = "geobuf"
+        "#;
+    let cfg: LayerCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        Layer::from_config(&cfg).err(),
+        Some(
+            "Layer 'roads': invalid encoding 'geobuf' (expected 't-rex' or 'st_asmvt')".to_string()
+        )
+    );
+}
@@ -3,20 +3,28 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+pub mod attr_stats;
 #[macro_use]
 pub mod config;
+pub mod config_upgrade;
 pub mod feature;
 pub mod geom;
 mod gridcfg;
+pub mod hillshade;
 pub mod layer;
+pub mod png;
 pub mod screen;
 pub mod stats;
 
-pub use self::config::{parse_config, read_config, ApplicationCfg, Config};
+pub use self::config::{
+    parse_config, parse_config_checked, read_config, read_config_checked, ApplicationCfg, Config,
+};
 
 #[cfg(test)]
 mod config_test;
 #[cfg(test)]
+mod config_upgrade_test;
+#[cfg(test)]
 mod geom_test;
 #[cfg(test)]
 mod gridcfg_test;
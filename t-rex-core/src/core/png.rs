@@ -0,0 +1,65 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Minimal PNG encoder for greyscale images
+
+use flate2::{write::ZlibEncoder, Compression, Crc};
+use std::io::Write;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+fn write_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    let mut crc = Crc::new();
+    crc.update(&png[start..]);
+    png.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+/// Encode 8 bit greyscale pixels (row by row) as PNG
+pub fn encode_grey_png(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    assert_eq!(pixels.len(), (width * height) as usize);
+    let mut png = PNG_SIGNATURE.to_vec();
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // Bit depth 8, color type 0 (greyscale), deflate, adaptive filtering, no interlace
+    ihdr.extend_from_slice(&[8, 0, 0, 0, 0]);
+    write_chunk(&mut png, b"IHDR", &ihdr);
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks(width as usize) {
+        // Filter type None
+        encoder.write_all(&[0]).unwrap();
+        encoder.write_all(row).unwrap();
+    }
+    let idat = encoder.finish().unwrap();
+    write_chunk(&mut png, b"IDAT", &idat);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+#[test]
+fn test_grey_png() {
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    let png = encode_grey_png(&[0, 64, 128, 255], 2, 2);
+    assert_eq!(png[0..8], PNG_SIGNATURE);
+    assert_eq!(&png[12..16], b"IHDR");
+    assert_eq!(png[16..24], [0, 0, 0, 2, 0, 0, 0, 2]);
+    assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    assert_eq!(png[png.len() - 4..], 0xAE42_6082u32.to_be_bytes());
+    // IDAT starts after signature (8) and IHDR chunk (25)
+    let idat_len = u32::from_be_bytes([png[33], png[34], png[35], png[36]]) as usize;
+    assert_eq!(&png[37..41], b"IDAT");
+    let mut raw = Vec::new();
+    ZlibDecoder::new(&png[41..41 + idat_len])
+        .read_to_end(&mut raw)
+        .unwrap();
+    assert_eq!(raw, vec![0, 0, 64, 0, 128, 255]);
+}
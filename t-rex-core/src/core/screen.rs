@@ -43,3 +43,81 @@ pub struct Polygon {
 pub struct MultiPolygon {
     pub polygons: Vec<Polygon>,
 }
+
+impl LineString {
+    /// Remove intermediate points lying on a straight segment between their neighbours.
+    /// First and last point are kept, so closed rings stay closed.
+    pub fn remove_collinear(&mut self) {
+        if self.points.len() < 3 {
+            return;
+        }
+        let last = self.points.len() - 1;
+        let mut keep = vec![true; self.points.len()];
+        let mut prev = 0;
+        for (i, keep_point) in keep.iter_mut().enumerate().take(last).skip(1) {
+            let (p, c, n) = (&self.points[prev], &self.points[i], &self.points[i + 1]);
+            let (dx1, dy1) = ((c.x - p.x) as i64, (c.y - p.y) as i64);
+            let (dx2, dy2) = ((n.x - c.x) as i64, (n.y - c.y) as i64);
+            // Same direction, no spikes
+            if dx1 * dy2 == dy1 * dx2 && dx1 * dx2 + dy1 * dy2 > 0 {
+                *keep_point = false;
+            } else {
+                prev = i;
+            }
+        }
+        let mut idx = 0;
+        self.points.retain(|_| {
+            idx += 1;
+            keep[idx - 1]
+        });
+    }
+    /// Area of closed ring (absolute value)
+    pub fn ring_area(&self) -> f64 {
+        let sum: i64 = self
+            .points
+            .windows(2)
+            .map(|w| w[0].x as i64 * w[1].y as i64 - w[1].x as i64 * w[0].y as i64)
+            .sum();
+        (sum as f64 / 2.0).abs()
+    }
+}
+
+impl MultiLineString {
+    pub fn remove_collinear(&mut self) {
+        for line in &mut self.lines {
+            line.remove_collinear();
+        }
+    }
+}
+
+impl Polygon {
+    pub fn remove_collinear(&mut self) {
+        for ring in &mut self.rings {
+            ring.remove_collinear();
+        }
+    }
+    /// Remove interior rings with an area below `min_area`
+    pub fn drop_holes(&mut self, min_area: f64) {
+        if self.rings.len() > 1 {
+            let holes = self.rings.split_off(1);
+            self.rings.extend(
+                holes
+                    .into_iter()
+                    .filter(|ring| ring.ring_area() >= min_area),
+            );
+        }
+    }
+}
+
+impl MultiPolygon {
+    pub fn remove_collinear(&mut self) {
+        for polygon in &mut self.polygons {
+            polygon.remove_collinear();
+        }
+    }
+    pub fn drop_holes(&mut self, min_area: f64) {
+        for polygon in &mut self.polygons {
+            polygon.drop_holes(min_area);
+        }
+    }
+}
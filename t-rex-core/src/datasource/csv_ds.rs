@@ -0,0 +1,603 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! CSV file datasource with point coordinate columns. Rows of each layer are indexed
+//! in memory when preparing the layer queries.
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::{self, GeometryType};
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::rtree::RTree;
+use crate::datasource::DatasourceType;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tile_grid::{Extent, Grid};
+
+/// Column names detected as x (longitude) coordinates
+const X_COLUMNS: &[&str] = &["x", "lon", "lng", "long", "longitude", "easting"];
+/// Column names detected as y (latitude) coordinates
+const Y_COLUMNS: &[&str] = &["y", "lat", "latitude", "northing"];
+
+/// Split CSV text into records (RFC 4180 quoting)
+pub fn parse_records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+        } else if c == '"' && field.is_empty() {
+            quoted = true;
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            // Skip empty lines
+            if record.len() > 1 || !record[0].is_empty() {
+                records.push(std::mem::take(&mut record));
+            } else {
+                record.clear();
+            }
+            line += 1;
+        } else {
+            field.push(c);
+        }
+    }
+    if quoted {
+        return Err(format!("line {}: unterminated quoted field", line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Attribute value of a text field (`None` for empty fields)
+pub(crate) fn text_value(text: &str) -> Option<FeatureAttrValType> {
+    if text.is_empty() {
+        return None;
+    }
+    let digits = text.trim_start_matches('-');
+    // Keep codes like zip numbers as string
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if !leading_zero {
+        if let Ok(v) = text.parse::<i64>() {
+            return Some(FeatureAttrValType::Int(v));
+        }
+        if let Ok(v) = text.parse::<f64>() {
+            if v.is_finite() {
+                return Some(FeatureAttrValType::Double(v));
+            }
+        }
+    }
+    let value = match text {
+        "true" => FeatureAttrValType::Bool(true),
+        "false" => FeatureAttrValType::Bool(false),
+        _ => FeatureAttrValType::String(text.to_string()),
+    };
+    Some(value)
+}
+
+/// Coordinate columns detected by name (case insensitive)
+pub fn coord_columns(header: &[String]) -> Option<(String, String)> {
+    let find = |names: &[&str]| {
+        names.iter().find_map(|name| {
+            header
+                .iter()
+                .find(|col| col.trim().eq_ignore_ascii_case(name))
+                .cloned()
+        })
+    };
+    Some((find(X_COLUMNS)?, find(Y_COLUMNS)?))
+}
+
+struct CsvPoint {
+    fid: Option<u64>,
+    x: f64,
+    y: f64,
+    properties: Vec<(String, FeatureAttrValType)>,
+}
+
+/// Points of a layer with spatial index
+pub struct CsvData {
+    points: Vec<CsvPoint>,
+    index: RTree,
+}
+
+impl CsvData {
+    /// Read points of `layer` from records with header
+    pub fn from_records(records: &[Vec<String>], layer: &Layer) -> Result<CsvData, String> {
+        let header = records.first().ok_or("Empty CSV file")?;
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|col| col == name)
+                .ok_or(format!("Column '{}' not found", name))
+        };
+        let (x_field, y_field) = match (&layer.x_field, &layer.y_field) {
+            (Some(x_field), Some(y_field)) => (x_field.clone(), y_field.clone()),
+            _ => coord_columns(header)
+                .ok_or("Coordinate columns not found (x_field and y_field undefined)")?,
+        };
+        let x_col = column(&x_field)?;
+        let y_col = column(&y_field)?;
+        let fields: Vec<(String, usize)> = if layer.fields.is_empty() {
+            header
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != x_col && *idx != y_col)
+                .map(|(idx, name)| (name.clone(), idx))
+                .collect()
+        } else {
+            layer
+                .fields
+                .iter()
+                .map(|name| column(name).map(|idx| (name.clone(), idx)))
+                .collect::<Result<_, _>>()?
+        };
+        let fid_cols = layer
+            .fid_fields()
+            .iter()
+            .map(|name| column(name))
+            .collect::<Result<Vec<usize>, _>>()?;
+        let mut points = Vec::with_capacity(records.len());
+        let mut skipped = 0;
+        for record in &records[1..] {
+            let field = |idx: usize| record.get(idx).map(|v| v.trim()).unwrap_or("");
+            let (x, y) = match (field(x_col).parse::<f64>(), field(y_col).parse::<f64>()) {
+                (Ok(x), Ok(y)) if x.is_finite() && y.is_finite() => (x, y),
+                _ => {
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let fid = if fid_cols.is_empty() {
+                None
+            } else {
+                let values: Vec<Option<FeatureAttrValType>> =
+                    fid_cols.iter().map(|idx| text_value(field(*idx))).collect();
+                fid_from_values(&values)
+            };
+            let properties = fields
+                .iter()
+                .filter_map(|(name, idx)| text_value(field(*idx)).map(|v| (name.clone(), v)))
+                .collect();
+            points.push(CsvPoint {
+                fid,
+                x,
+                y,
+                properties,
+            });
+        }
+        if skipped > 0 {
+            warn!(
+                "Layer '{}': {} rows without valid coordinates skipped",
+                layer.name, skipped
+            );
+        }
+        let extents: Vec<Extent> = points
+            .iter()
+            .map(|p| Extent {
+                minx: p.x,
+                miny: p.y,
+                maxx: p.x,
+                maxy: p.y,
+            })
+            .collect();
+        Ok(CsvData {
+            index: RTree::new(&extents),
+            points,
+        })
+    }
+    /// Number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+    /// Extent of all points
+    pub fn extent(&self) -> Option<Extent> {
+        self.index.extent()
+    }
+}
+
+pub struct CsvFeature<'a> {
+    point: &'a CsvPoint,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> Feature for CsvFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        self.point.fid
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.point
+            .properties
+            .iter()
+            .map(|(key, value)| FeatureAttr {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        let (x, y) = (self.transform)(self.point.x, self.point.y);
+        Ok(GeometryType::Point(geom::Point::new(x, y, self.srid)))
+    }
+}
+
+#[derive(Clone)]
+pub struct CsvDatasource {
+    pub path: String,
+    pub delimiter: char,
+    /// Records of the file including header
+    records: Option<Arc<Vec<Vec<String>>>>,
+    /// Indexed points of prepared layers
+    layer_data: BTreeMap<String, Arc<CsvData>>,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+impl CsvDatasource {
+    pub fn new(path: &str, delimiter: char) -> CsvDatasource {
+        CsvDatasource {
+            path: path.to_string(),
+            delimiter,
+            records: None,
+            layer_data: BTreeMap::new(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    /// Records of the file (file is read if not connected)
+    fn records(&self) -> Result<Arc<Vec<Vec<String>>>, String> {
+        match self.records {
+            Some(ref records) => Ok(records.clone()),
+            None => {
+                let text =
+                    fs::read_to_string(&self.path).map_err(|e| format!("{}: {}", self.path, e))?;
+                parse_records(&text, self.delimiter)
+                    .map(Arc::new)
+                    .map_err(|e| format!("{}: {}", self.path, e))
+            }
+        }
+    }
+    /// Indexed points of layer (indexed on the fly if not prepared)
+    fn data(&self, layer: &Layer) -> Result<Arc<CsvData>, String> {
+        match self.layer_data.get(&layer.name) {
+            Some(data) => Ok(data.clone()),
+            None => CsvData::from_records(&self.records()?, layer).map(Arc::new),
+        }
+    }
+    /// Layer name derived from file name
+    fn layer_name(&self) -> String {
+        Path::new(&self.path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "csv".to_string())
+    }
+    /// Spatial filter in layer SRS and axis order
+    fn bbox(&self, layer: &Layer, extent: &Extent, zoom: u8, grid: &Grid) -> Extent {
+        let mut bbox = if let Some(pixels) = layer.buffer_size {
+            let buf = f64::from(pixels) * grid.pixel_width(zoom);
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        if self.geom_transform.contains_key(&layer.name) {
+            let srid = layer.srid.unwrap_or(4326);
+            if let Some(Some(inverse)) = transformation(grid.srid, srid) {
+                bbox = transform_extent(&bbox, inverse);
+            }
+        }
+        if layer.swap_axes() {
+            bbox = swap_extent_axes(&bbox);
+        }
+        bbox
+    }
+}
+
+impl DatasourceType for CsvDatasource {
+    /// New instance with loaded records
+    fn connected(&self) -> CsvDatasource {
+        let records = match self.records() {
+            Ok(records) => {
+                info!(
+                    "Loaded {} rows from '{}'",
+                    records.len().saturating_sub(1),
+                    self.path
+                );
+                Some(records)
+            }
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        };
+        CsvDatasource {
+            path: self.path.clone(),
+            delimiter: self.delimiter,
+            records,
+            layer_data: BTreeMap::new(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let records = match self.records() {
+            Ok(records) => records,
+            Err(e) => {
+                error!("{}", e);
+                return Vec::new();
+            }
+        };
+        let header = records.first().cloned().unwrap_or_default();
+        let mut layer = Layer::new(&self.layer_name());
+        layer.geometry_type = Some("POINT".to_string());
+        match coord_columns(&header) {
+            Some((x_field, y_field)) => {
+                if !x_field.eq_ignore_ascii_case("x") {
+                    // Longitude/latitude columns
+                    layer.srid = Some(4326);
+                }
+                layer.x_field = Some(x_field);
+                layer.y_field = Some(y_field);
+            }
+            None => warn!(
+                "{}: coordinate columns not detected, set x_field and y_field",
+                self.path
+            ),
+        }
+        vec![layer]
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        if !layer.fields.is_empty() {
+            return layer
+                .fields
+                .iter()
+                .map(|col| (col.clone(), String::new()))
+                .collect();
+        }
+        match self.records() {
+            Ok(records) => {
+                let header = records.first().cloned().unwrap_or_default();
+                let coords = match (&layer.x_field, &layer.y_field) {
+                    (Some(x_field), Some(y_field)) => Some((x_field.clone(), y_field.clone())),
+                    _ => coord_columns(&header),
+                };
+                header
+                    .into_iter()
+                    .filter(|col| coords.as_ref().is_none_or(|(x, y)| col != x && col != y))
+                    .map(|col| (col, String::new()))
+                    .collect()
+            }
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let extent = self
+            .data(layer)
+            .map_err(|e| error!("Layer '{}': {}", layer.name, e))
+            .ok()?
+            .extent()?;
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(4326);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if !layer.query.is_empty() || layer.filter.is_some() {
+            warn!(
+                "Layer '{}': filter and SQL queries not supported for CSV layers",
+                layer.name
+            );
+        }
+        if layer.geometry_type.as_deref().is_some_and(|t| t != "POINT") {
+            warn!("Layer '{}': CSV layers have POINT geometries", layer.name);
+        }
+        if !layer.no_transform {
+            let srid = layer.srid.unwrap_or(4326);
+            match transformation(srid, grid_srid) {
+                Some(Some(transform)) => {
+                    info!(
+                        "Layer '{}': Reprojecting geometry to SRID {}",
+                        layer.name, grid_srid
+                    );
+                    self.geom_transform.insert(layer.name.clone(), transform);
+                }
+                Some(None) => {}
+                None => error!(
+                    "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                    layer.name, srid, grid_srid
+                ),
+            }
+        }
+        match self.data(layer) {
+            Ok(data) => {
+                info!("Layer '{}': {} points indexed", layer.name, data.len());
+                self.layer_data.insert(layer.name.clone(), data);
+            }
+            Err(e) => error!("Layer '{}': {}", layer.name, e),
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let data = match self.data(layer) {
+            Ok(data) => data,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return 0;
+            }
+        };
+        let bbox = self.bbox(layer, extent, zoom, grid);
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        let swap_axes = layer.swap_axes();
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        for idx in data.index.query(&bbox) {
+            let feat = CsvFeature {
+                point: &data.points[idx],
+                srid: Some(grid.srid),
+                transform: &transform,
+            };
+            read(&feat);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+                break;
+            }
+        }
+        cnt
+    }
+    fn estimated_row_count(&self, layer: &Layer) -> Option<u64> {
+        self.data(layer).ok().map(|data| data.len() as u64)
+    }
+    fn count_features(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+    ) -> u64 {
+        match self.data(layer) {
+            Ok(data) => data
+                .index
+                .query(&self.bbox(layer, extent, zoom, grid))
+                .len() as u64,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                0
+            }
+        }
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for CsvDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        let delimiter = match ds_cfg.delimiter.as_deref() {
+            None => ',',
+            Some(delimiter) => {
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c != '"' => c,
+                    _ => {
+                        return Err(format!(
+                            "Invalid CSV delimiter '{}' (expected single character)",
+                            delimiter
+                        ))
+                    }
+                }
+            }
+        };
+        Ok(CsvDatasource::new(ds_cfg.csv.as_ref().unwrap(), delimiter))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "csv"
+# CSV file with header. Layers declare coordinate columns with x_field/y_field
+# (detected for lon/lat or x/y) and attribute columns with fields (default: all).
+csv = "<filename>.csv"
+#delimiter = ";"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        let delimiter = if self.delimiter == ',' {
+            String::new()
+        } else {
+            format!("delimiter = {:?}\n", self.delimiter.to_string())
+        };
+        format!(
+            r#"
+[[datasource]]
+csv = "{}"
+{}"#,
+            self.path, delimiter
+        )
+    }
+}
@@ -0,0 +1,181 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::layer::Layer;
+use crate::datasource::csv_ds::{coord_columns, parse_records, text_value, CsvData, CsvDatasource};
+use crate::datasource::test_helpers::bern_extent;
+use crate::datasource::DatasourceType;
+use tile_grid::Extent;
+use tile_grid::Grid;
+
+const CSV: &str = "../data/places.csv";
+
+#[test]
+fn test_parse_records() {
+    let text = "a;b;c\r\n1;\"x;y\";\"say \"\"hi\"\"\"\n\n2;\"multi\nline\";\n3";
+    let records = parse_records(text, ';').unwrap();
+    assert_eq!(
+        records,
+        vec![
+            vec!["a", "b", "c"],
+            vec!["1", "x;y", "say \"hi\""],
+            vec!["2", "multi\nline", ""],
+            vec!["3"],
+        ]
+    );
+    assert_eq!(
+        parse_records("\u{feff}x,y\n", ',').unwrap(),
+        vec![vec!["x", "y"]]
+    );
+    assert_eq!(
+        parse_records("a\n\"open", ',').err(),
+        Some("line 2: unterminated quoted field".to_string())
+    );
+}
+
+#[test]
+fn test_text_value() {
+    assert_eq!(text_value(""), None);
+    assert_eq!(text_value("42"), Some(FeatureAttrValType::Int(42)));
+    assert_eq!(text_value("-7"), Some(FeatureAttrValType::Int(-7)));
+    assert_eq!(text_value("0.5"), Some(FeatureAttrValType::Double(0.5)));
+    assert_eq!(text_value("true"), Some(FeatureAttrValType::Bool(true)));
+    assert_eq!(
+        text_value("03000"),
+        Some(FeatureAttrValType::String("03000".to_string()))
+    );
+    assert_eq!(
+        text_value("NaN"),
+        Some(FeatureAttrValType::String("NaN".to_string()))
+    );
+}
+
+#[test]
+fn test_coord_columns() {
+    let header = |cols: &[&str]| cols.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        coord_columns(&header(&["name", "Latitude", "Longitude"])),
+        Some(("Longitude".to_string(), "Latitude".to_string()))
+    );
+    assert_eq!(
+        coord_columns(&header(&["x", "y"])),
+        Some(("x".to_string(), "y".to_string()))
+    );
+    assert_eq!(coord_columns(&header(&["name", "lat"])), None);
+}
+
+#[test]
+fn test_csv_data() {
+    let records = parse_records(
+        "e,n,name,code\n2600000,1200000,A,1\n2600100,1200100,B,2\n",
+        ',',
+    )
+    .unwrap();
+    let mut layer = Layer::new("points");
+    assert!(CsvData::from_records(&records, &layer).is_err());
+
+    layer.x_field = Some("e".to_string());
+    layer.y_field = Some("n".to_string());
+    let data = CsvData::from_records(&records, &layer).unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(
+        data.extent(),
+        Some(Extent {
+            minx: 2600000.0,
+            miny: 1200000.0,
+            maxx: 2600100.0,
+            maxy: 1200100.0
+        })
+    );
+
+    layer.fields = vec!["missing".to_string()];
+    assert_eq!(
+        CsvData::from_records(&records, &layer).err(),
+        Some("Column 'missing' not found".to_string())
+    );
+}
+
+#[test]
+fn test_detect_layers() {
+    let ds = CsvDatasource::new(CSV, ',');
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+    assert_eq!(layers[0].geometry_type, Some("POINT".to_string()));
+    assert_eq!(layers[0].x_field, Some("lon".to_string()));
+    assert_eq!(layers[0].y_field, Some("lat".to_string()));
+    assert_eq!(layers[0].srid, Some(4326));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        cols.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(),
+        vec!["id", "name", "population", "capital"]
+    );
+
+    let extent = ds.layer_extent(&layers[0], 3857);
+    assert_eq!(
+        format!("{:.4?}", extent),
+        "Some(Extent { minx: 6.1432, miny: 46.2044, maxx: 8.5417, maxy: 47.3769 })"
+    );
+    assert_eq!(ds.estimated_row_count(&layers[0]), Some(4));
+
+    assert!(CsvDatasource::new("../data/missing.csv", ',')
+        .detect_layers(true)
+        .is_empty());
+}
+
+#[test]
+fn test_retrieve_features() {
+    let mut layer = Layer::new("places");
+    layer.srid = Some(4326);
+    layer.fid_field = Some("id".to_string());
+    layer.fields = vec!["name".to_string(), "capital".to_string()];
+    let grid = Grid::web_mercator();
+
+    let mut ds = CsvDatasource::new(CSV, ',').connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut features = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        features.push((
+            feat.fid(),
+            feat.attributes(),
+            format!("{:.0?}", feat.geometry()),
+        ));
+    });
+    assert_eq!(features.len(), 1);
+    let (fid, attrs, geom) = &features[0];
+    assert_eq!(*fid, Some(1));
+    assert_eq!(attrs.len(), 2);
+    assert_eq!(
+        attrs[0].value,
+        FeatureAttrValType::String("Bern".to_string())
+    );
+    assert_eq!(attrs[1].value, FeatureAttrValType::Bool(true));
+    assert_eq!(
+        *geom,
+        "Ok(Point(Point { x: 829041, y: 5933590, srid: Some(3857) }))"
+    );
+    assert_eq!(
+        ds.count_features("ts", &layer, &bern_extent(), 10, &grid),
+        1
+    );
+
+    // Quoted field with delimiter
+    let world = Extent {
+        minx: -20037508.3,
+        miny: -20037508.3,
+        maxx: 20037508.3,
+        maxy: 20037508.3,
+    };
+    let mut names = Vec::new();
+    ds.retrieve_features("ts", &layer, &world, 0, &grid, |feat| {
+        names.push(feat.attributes()[0].value.clone());
+    });
+    assert_eq!(names.len(), 4);
+    assert!(names.contains(&FeatureAttrValType::String(
+        "Biel/Bienne, \"Seeland\"".to_string()
+    )));
+}
@@ -7,8 +7,9 @@ use crate::core::config::DatasourceCfg;
 use crate::core::feature::Feature;
 use crate::core::layer::Layer;
 use crate::core::Config;
+use async_trait::async_trait;
 use tile_grid::Extent;
-use tile_grid::Grid;
+use tile_grid::{Grid, Origin};
 
 pub trait DatasourceType {
     /// New instance with connected pool
@@ -37,6 +38,121 @@ pub trait DatasourceType {
     ) -> u64
     where
         F: FnMut(&dyn Feature);
+    /// Retrieve features of one layer at time value of a time dimension (`!time!` query variable).
+    /// Datasources without time support ignore the time.
+    #[allow(clippy::too_many_arguments)]
+    fn retrieve_features_at<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        _time: Option<&str>,
+        read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        self.retrieve_features(tileset, layer, extent, zoom, grid, read)
+    }
+    /// Retrieve features like `retrieve_features_at`, returning query errors instead of
+    /// logging them. Datasources without error reporting never fail.
+    #[allow(clippy::too_many_arguments)]
+    fn try_retrieve_features_at<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        time: Option<&str>,
+        read: F,
+    ) -> Result<u64, String>
+    where
+        F: FnMut(&dyn Feature),
+    {
+        Ok(self.retrieve_features_at(tileset, layer, extent, zoom, grid, time, read))
+    }
+    /// Retrieve layer encoded by the datasource as vector tile (layer `encoding = "st_asmvt"`)
+    #[allow(clippy::too_many_arguments)]
+    fn retrieve_mvt_layer(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        _extent: &Extent,
+        _zoom: u8,
+        _grid: &Grid,
+        _time: Option<&str>,
+    ) -> Result<Vec<u8>, String> {
+        Err(format!(
+            "Layer '{}': datasource does not support ST_AsMVT encoding",
+            layer.name
+        ))
+    }
+    /// Layer `filter` conditions are applied when retrieving features
+    fn supports_filter(&self) -> bool {
+        false
+    }
+    /// Prepared query of layer at zoom level (for introspection)
+    fn query_sql(&self, _tileset: &str, _layer: &Layer, _zoom: u8) -> Option<String> {
+        None
+    }
+    /// Approximate number of rows of layer table (e.g. from database statistics)
+    fn estimated_row_count(&self, _layer: &Layer) -> Option<u64> {
+        None
+    }
+    /// Time of the last data update as UTC timestamp (see `updated_sql`)
+    fn data_updated(&self, _layer: &Layer) -> Option<String> {
+        None
+    }
+    /// Extents in grid SRS of features changed since timestamp (see `changes_sql`)
+    fn changed_extents(
+        &self,
+        layer: &Layer,
+        _since: &str,
+        _grid_srid: i32,
+    ) -> Result<Vec<Extent>, String> {
+        Err(format!(
+            "Layer '{}': datasource does not support incremental seeding",
+            layer.name
+        ))
+    }
+    /// Number of features of one layer in extent, without encoding geometries.
+    /// Datasources without a native count query iterate over all features.
+    fn count_features(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+    ) -> u64 {
+        self.retrieve_features(tileset, layer, extent, zoom, grid, |_| {})
+    }
+}
+
+/// Datasources retrieving features without blocking a thread per query.
+/// Feature retrieval is split into fetching rows asynchronously and reading them afterwards.
+#[async_trait]
+pub trait DatasourceInput {
+    /// Rows of one layer query
+    type Features: Send;
+    /// Fetch features of one layer at an optional time value
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_features(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        time: Option<&str>,
+    ) -> Result<Self::Features, String>;
+    /// Read fetched features. Return feature count.
+    fn read_features<F>(&self, layer: &Layer, zoom: u8, features: &Self::Features, read: F) -> u64
+    where
+        F: FnMut(&dyn Feature);
 }
 
 #[derive(Clone)]
@@ -97,3 +213,13 @@ impl<'a> Config<'a, DatasourceCfg> for DummyDatasource {
         "".to_string()
     }
 }
+
+/// Tile coordinates in XYZ scheme of a tile extent
+pub fn xyz_tile(extent: &Extent, zoom: u8, grid: &Grid) -> (u32, u32) {
+    let limits = &grid.tile_limits(extent.clone(), 0)[zoom as usize];
+    let y = match grid.origin {
+        Origin::TopLeft => limits.miny,
+        Origin::BottomLeft => grid.ytile_from_xyz(limits.miny, zoom),
+    };
+    (limits.minx, y)
+}
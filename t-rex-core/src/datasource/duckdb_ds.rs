@@ -0,0 +1,722 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! DuckDB datasource with the spatial extension (https://duckdb.org/docs/extensions/spatial).
+//! Geometries are read as WKB (`ST_AsWKB`).
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{
+    civil_from_days, fid_from_values, Feature, FeatureAttr, FeatureAttrValType,
+};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::pool::PoolSettings;
+use crate::datasource::query_params::{self, quote_opt, quote_str};
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::wkb_reader::read_wkb;
+use crate::datasource::DatasourceType;
+use duckdb::types::{TimeUnit, Value};
+use duckdb::{AccessMode, DuckdbConnectionManager};
+use std::collections::BTreeMap;
+use tile_grid::{Extent, Grid};
+
+/// In-memory database for queries on Parquet/CSV/remote files only
+pub const MEMORY_DB: &str = ":memory:";
+
+/// Name of the WKB geometry column in feature queries
+const WKB_COLUMN: &str = "_wkb";
+
+/// Query result row (column names and values)
+pub type DuckdbRow = Vec<(String, Value)>;
+
+#[derive(Clone)]
+pub struct DuckdbDatasource {
+    pub path: String,
+    pool_size: Option<u16>,
+    pool_settings: PoolSettings,
+    conn_pool: Option<r2d2::Pool<DuckdbConnectionManager>>,
+    // Query templates for all tileset/layers and zoom levels
+    queries: BTreeMap<String, BTreeMap<String, BTreeMap<u8, String>>>,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+/// Quote identifier with double quotes
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Replace variables (!bbox!, !zoom!, etc.) in query with values of tile.
+/// `bbox` is in layer SRS and axis order.
+pub fn replace_params(
+    sql: &str,
+    bbox: &Extent,
+    zoom: u8,
+    grid: &Grid,
+    time: Option<&str>,
+) -> String {
+    let bbox_expr = format!(
+        "ST_MakeEnvelope({},{},{},{})",
+        bbox.minx, bbox.miny, bbox.maxx, bbox.maxy
+    );
+    query_params::replace_params(sql, &bbox_expr, zoom, grid, &quote_opt(time, quote_str))
+}
+
+/// Value of a column in a result row
+pub fn column<'a>(row: &'a DuckdbRow, name: &str) -> Option<&'a Value> {
+    row.iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+}
+
+fn int_value(value: &Value) -> Option<i64> {
+    match attr_value(value)? {
+        FeatureAttrValType::Int(v) => Some(v),
+        FeatureAttrValType::UInt(v) => Some(v as i64),
+        _ => None,
+    }
+}
+
+fn float_value(value: &Value) -> Option<f64> {
+    match attr_value(value)? {
+        FeatureAttrValType::Double(v) => Some(v),
+        FeatureAttrValType::Float(v) => Some(f64::from(v)),
+        FeatureAttrValType::Int(v) => Some(v as f64),
+        _ => None,
+    }
+}
+
+fn text_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Text(v) | Value::Enum(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Timestamp in DuckDB text format
+fn timestamp(unit: &TimeUnit, value: i64) -> String {
+    let micros = unit.to_micros(value);
+    let secs = micros.div_euclid(1_000_000);
+    let daysecs = secs.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let mut text = format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        daysecs / 3600,
+        daysecs / 60 % 60,
+        daysecs % 60
+    );
+    if micros.rem_euclid(1_000_000) != 0 {
+        text.push_str(&format!(".{:06}", micros.rem_euclid(1_000_000)));
+    }
+    text
+}
+
+/// Attribute value of a column value (`None` for NULL and unsupported types)
+pub fn attr_value(value: &Value) -> Option<FeatureAttrValType> {
+    let value = match value {
+        Value::Boolean(v) => FeatureAttrValType::Bool(*v),
+        Value::TinyInt(v) => FeatureAttrValType::Int(i64::from(*v)),
+        Value::SmallInt(v) => FeatureAttrValType::Int(i64::from(*v)),
+        Value::Int(v) => FeatureAttrValType::Int(i64::from(*v)),
+        Value::BigInt(v) => FeatureAttrValType::Int(*v),
+        Value::UTinyInt(v) => FeatureAttrValType::Int(i64::from(*v)),
+        Value::USmallInt(v) => FeatureAttrValType::Int(i64::from(*v)),
+        Value::UInt(v) => FeatureAttrValType::Int(i64::from(*v)),
+        Value::UBigInt(v) => FeatureAttrValType::UInt(*v),
+        Value::HugeInt(v) => FeatureAttrValType::Double(*v as f64),
+        Value::UHugeInt(v) => FeatureAttrValType::Double(*v as f64),
+        Value::Float(v) => FeatureAttrValType::Float(*v),
+        Value::Double(v) => FeatureAttrValType::Double(*v),
+        Value::Decimal(v) => FeatureAttrValType::Double(v.to_string().parse().ok()?),
+        Value::Text(v) | Value::Enum(v) => FeatureAttrValType::String(v.clone()),
+        Value::Date32(days) => {
+            let (year, month, day) = civil_from_days(i64::from(*days));
+            FeatureAttrValType::String(format!("{:04}-{:02}-{:02}", year, month, day))
+        }
+        Value::Timestamp(unit, v) => FeatureAttrValType::String(timestamp(unit, *v)),
+        Value::List(values) | Value::Array(values) => {
+            FeatureAttrValType::VarcharArray(values.iter().filter_map(text_value).collect())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+impl DuckdbDatasource {
+    pub fn new(path: &str) -> DuckdbDatasource {
+        DuckdbDatasource {
+            path: path.to_string(),
+            pool_size: None,
+            pool_settings: PoolSettings::default(),
+            conn_pool: None,
+            queries: BTreeMap::new(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn conn(&self) -> Result<r2d2::PooledConnection<DuckdbConnectionManager>, String> {
+        let pool = self
+            .conn_pool
+            .as_ref()
+            .ok_or("DuckDB datasource not connected".to_string())?;
+        pool.get().map_err(|e| e.to_string())
+    }
+    /// Pool of connections to the database. Database files are opened read-only.
+    fn connect(&self, pool_size: u16) -> Result<r2d2::Pool<DuckdbConnectionManager>, String> {
+        let err = |e: duckdb::Error| format!("DuckDB database {}: {}", self.path, e);
+        let manager = if self.path == MEMORY_DB {
+            DuckdbConnectionManager::memory()
+        } else {
+            duckdb::Config::default()
+                .access_mode(AccessMode::ReadOnly)
+                .and_then(|config| DuckdbConnectionManager::file_with_flags(&self.path, config))
+        }
+        .map_err(err)?;
+        let pool = self
+            .pool_settings
+            .r2d2_builder(pool_size)
+            .build(manager)
+            .map_err(|e| format!("DuckDB database {}: {}", self.path, e))?;
+        // Extensions are loaded into the database instance shared by all pool connections
+        pool.get()
+            .map_err(|e| e.to_string())?
+            .execute_batch("INSTALL spatial; LOAD spatial;")
+            .map_err(err)?;
+        Ok(pool)
+    }
+    /// Execute query and call `read` for each result row
+    pub fn query_each<F>(&self, sql: &str, mut read: F) -> Result<(), String>
+    where
+        F: FnMut(&DuckdbRow),
+    {
+        trace!("Query: {}", sql);
+        let err = |e: duckdb::Error| format!("DuckDB error: {}", e);
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(sql).map_err(err)?;
+        let mut rows = stmt.query([]).map_err(err)?;
+        let columns = rows
+            .as_ref()
+            .map(|stmt| stmt.column_names())
+            .unwrap_or_default();
+        while let Some(row) = rows.next().map_err(err)? {
+            let values = columns
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| Ok((name.clone(), row.get::<_, Value>(idx)?)))
+                .collect::<Result<DuckdbRow, duckdb::Error>>()
+                .map_err(err)?;
+            read(&values);
+        }
+        Ok(())
+    }
+    /// Execute query and return all result rows
+    pub fn query(&self, sql: &str) -> Result<Vec<DuckdbRow>, String> {
+        let mut rows = Vec::new();
+        self.query_each(sql, |row| rows.push(row.clone()))?;
+        Ok(rows)
+    }
+    /// Single value query
+    fn query_value(&self, sql: &str) -> Result<Option<Value>, String> {
+        let rows = self.query(sql)?;
+        Ok(rows
+            .into_iter()
+            .next()
+            .and_then(|row| row.into_iter().next())
+            .map(|(_, value)| value))
+    }
+    /// Build feature query template (with variables like !bbox!)
+    pub fn build_query(&self, layer: &Layer, sql: Option<&String>) -> Option<String> {
+        let geom_col = quote_ident(layer.geometry_field.as_ref()?);
+        let from = match sql {
+            Some(userquery) => format!("({}) AS _q", userquery),
+            None => format!("{} AS _q", layer.table_name.as_ref()?),
+        };
+        let mut sqlquery = format!(
+            "SELECT ST_AsWKB({}) AS {}, _q.* EXCLUDE ({}) FROM {}",
+            geom_col,
+            quote_ident(WKB_COLUMN),
+            geom_col,
+            from
+        );
+        let mut conditions = Vec::new();
+        if !sql.is_some_and(|userquery| userquery.contains("!bbox!")) {
+            conditions.push(format!("ST_Intersects_Extent({},!bbox!)", geom_col));
+        }
+        if let Some(ref filter) = layer.filter {
+            conditions.push(format!("({})", filter));
+        }
+        if !conditions.is_empty() {
+            sqlquery.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        if let Some(ref order_by) = layer.order_by {
+            sqlquery.push_str(&format!(" ORDER BY {}", order_by));
+        }
+        if let Some(limit) = layer.query_limit {
+            sqlquery.push_str(&format!(" LIMIT {}", limit));
+        }
+        Some(sqlquery)
+    }
+    fn prepared_query(&self, tileset: &str, layer: &Layer, zoom: u8) -> Option<&String> {
+        self.queries
+            .get(tileset)
+            .and_then(|layers| layers.get(&layer.name))
+            .and_then(|queries| queries.get(&zoom))
+    }
+    /// Feature query with tile values
+    fn tile_query(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        time: Option<&str>,
+    ) -> Option<String> {
+        let sql = self.prepared_query(tileset, layer, zoom)?;
+        let mut bbox = if let Some(pixels) = layer.buffer_size {
+            let buf = f64::from(pixels) * grid.pixel_width(zoom);
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        if self.geom_transform.contains_key(&layer.name) {
+            // Spatial filter must be in layer SRS
+            let inverse = layer
+                .srid
+                .and_then(|srid| transformation(grid.srid, srid))
+                .and_then(|tr| tr);
+            if let Some(inverse) = inverse {
+                bbox = transform_extent(&bbox, inverse);
+            }
+        }
+        if layer.swap_axes() {
+            bbox = swap_extent_axes(&bbox);
+        }
+        Some(replace_params(sql, &bbox, zoom, grid, time))
+    }
+}
+
+pub struct DuckdbFeature<'a> {
+    pub layer: &'a Layer,
+    pub row: &'a DuckdbRow,
+    pub srid: Option<i32>,
+    pub transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> Feature for DuckdbFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let values: Vec<_> = self
+            .layer
+            .fid_fields()
+            .iter()
+            .map(|field| column(self.row, field).and_then(attr_value))
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.row
+            .iter()
+            .filter(|(key, _)| *key != WKB_COLUMN)
+            .filter_map(|(key, value)| {
+                attr_value(value).map(|value| FeatureAttr {
+                    key: key.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        match column(self.row, WKB_COLUMN) {
+            Some(Value::Blob(wkb)) | Some(Value::Geometry(wkb)) => {
+                read_wkb(wkb, self.srid, Some(self.transform))
+            }
+            _ => Err("Geometry is NULL".to_string()),
+        }
+    }
+}
+
+impl DatasourceType for DuckdbDatasource {
+    /// New instance with connected pool
+    fn connected(&self) -> DuckdbDatasource {
+        debug!("Opening DuckDB database {}", &self.path);
+        let pool_size = self.pool_size.unwrap_or(8);
+        let conn_pool = match self.connect(pool_size) {
+            Ok(pool) => Some(pool),
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        };
+        DuckdbDatasource {
+            path: self.path.clone(),
+            pool_size: Some(pool_size),
+            pool_settings: self.pool_settings.clone(),
+            conn_pool,
+            queries: BTreeMap::new(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, detect_geometry_types: bool) -> Vec<Layer> {
+        info!("Detecting layers from duckdb_columns()");
+        let sql = "SELECT table_name, column_name FROM duckdb_columns() \
+                   WHERE data_type = 'GEOMETRY' AND NOT internal \
+                   ORDER BY table_name, column_index";
+        let rows = match self.query(sql) {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!("{}", e);
+                return Vec::new();
+            }
+        };
+        let columns: Vec<(String, String)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    column(row, "table_name")
+                        .and_then(text_value)
+                        .unwrap_or_default(),
+                    column(row, "column_name")
+                        .and_then(text_value)
+                        .unwrap_or_default(),
+                )
+            })
+            .collect();
+        let ntables = |table: &str| columns.iter().filter(|c| c.0 == table).count();
+        let mut layers: Vec<Layer> = Vec::new();
+        for (table_name, geometry_column) in &columns {
+            // Name layers of tables with multiple geometry columns like GDAL
+            let name = if ntables(table_name) > 1 {
+                format!("{}_{}", table_name, geometry_column)
+            } else {
+                table_name.clone()
+            };
+            let mut layer = Layer::new(&name);
+            layer.table_name = Some(quote_ident(table_name));
+            layer.geometry_field = Some(geometry_column.clone());
+            if detect_geometry_types {
+                // DuckDB geometries have no SRID, only the type can be detected
+                let col = quote_ident(geometry_column);
+                let sql = format!(
+                    "SELECT DISTINCT ST_GeometryType({}) AS geometry_type FROM {} \
+                     WHERE {} IS NOT NULL LIMIT 2",
+                    col,
+                    quote_ident(table_name),
+                    col
+                );
+                match self.query(&sql) {
+                    Ok(rows) if rows.len() == 1 => {
+                        layer.geometry_type = column(&rows[0], "geometry_type")
+                            .and_then(text_value)
+                            .map(|t| t.to_uppercase());
+                    }
+                    Ok(_) => layer.geometry_type = Some("GEOMETRY".to_string()),
+                    Err(e) => warn!("Layer '{}': {}", name, e),
+                }
+            }
+            layers.push(layer);
+        }
+        layers
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, sql: Option<&String>) -> Vec<(String, String)> {
+        let from = match (sql, layer.table_name.as_ref()) {
+            (Some(userquery), _) => format!("({}) AS _q", userquery),
+            (None, Some(table_name)) => table_name.clone(),
+            (None, None) => return Vec::new(),
+        };
+        let empty = Extent {
+            minx: 0.0,
+            miny: 0.0,
+            maxx: 0.0,
+            maxy: 0.0,
+        };
+        let query = replace_params(
+            &format!("DESCRIBE SELECT * FROM {}", from),
+            &empty,
+            0,
+            &Grid::web_mercator(),
+            None,
+        );
+        match self.query(&query) {
+            Ok(rows) => rows
+                .iter()
+                .filter_map(|row| {
+                    let name = text_value(column(row, "column_name")?)?;
+                    let column_type = text_value(column(row, "column_type")?)?;
+                    if column_type == "GEOMETRY" || Some(&name) == layer.geometry_field.as_ref() {
+                        None
+                    } else {
+                        Some((name, String::new()))
+                    }
+                })
+                .collect(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84) from the bounding boxes of all geometries
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let geom_col = quote_ident(layer.geometry_field.as_ref()?);
+        if !layer.query.is_empty() || layer.table_name.is_none() {
+            info!(
+                "Couldn't detect extent of layer {}, because of custom queries",
+                layer.name
+            );
+            return None;
+        }
+        let sql = format!(
+            "SELECT min(ST_XMin({col})) AS minx, min(ST_YMin({col})) AS miny, \
+             max(ST_XMax({col})) AS maxx, max(ST_YMax({col})) AS maxy FROM {table}",
+            col = geom_col,
+            table = layer.table_name.as_ref()?
+        );
+        let row = match self.query(&sql) {
+            Ok(rows) => rows.into_iter().next()?,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return None;
+            }
+        };
+        let coord = |key: &str| column(&row, key).and_then(float_value);
+        let extent = match (coord("minx"), coord("miny"), coord("maxx"), coord("maxy")) {
+            (Some(minx), Some(miny), Some(maxx), Some(maxy)) => Extent {
+                minx,
+                miny,
+                maxx,
+                maxy,
+            },
+            _ => {
+                warn!("Layer '{}': Unable to get extent", layer.name);
+                return None;
+            }
+        };
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(0);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, tileset: &str, layer: &Layer, grid_srid: i32) {
+        let mut queries = BTreeMap::new();
+
+        if layer.geometry_field.is_none() {
+            error!("Layer '{}': geometry_field undefined", layer.name);
+        }
+        if layer.query.is_empty() && layer.table_name.is_none() {
+            error!("Layer '{}': table_name undefined", layer.name);
+        }
+        if !layer.no_transform {
+            match layer.srid {
+                Some(srid) => match transformation(srid, grid_srid) {
+                    Some(Some(transform)) => {
+                        info!(
+                            "Layer '{}': Reprojecting geometry to SRID {}",
+                            layer.name, grid_srid
+                        );
+                        self.geom_transform.insert(layer.name.clone(), transform);
+                    }
+                    Some(None) => {}
+                    None => error!(
+                        "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                        layer.name, srid, grid_srid
+                    ),
+                },
+                None => warn!(
+                    "Layer '{}': srid undefined, assuming SRID {}",
+                    layer.name, grid_srid
+                ),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for DuckDB layers",
+                layer.name
+            );
+        }
+
+        for zoom in layer.minzoom()..=layer.maxzoom(22) {
+            if let Some(query) = self.build_query(layer, layer.query(zoom)) {
+                debug!("Query for layer '{}': {}", layer.name, query);
+                queries.insert(zoom, query);
+            }
+        }
+        self.queries
+            .entry(tileset.to_string())
+            .or_default()
+            .insert(layer.name.clone(), queries);
+    }
+    fn supports_filter(&self) -> bool {
+        true
+    }
+    fn retrieve_features<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        self.retrieve_features_at(tileset, layer, extent, zoom, grid, None, read)
+    }
+    fn retrieve_features_at<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        time: Option<&str>,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let sql = match self.tile_query(tileset, layer, extent, zoom, grid, time) {
+            Some(sql) => sql,
+            None => return 0,
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        let swap_axes = layer.swap_axes();
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+        debug!("Reading features in layer {}", layer.name);
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        let result = self.query_each(&sql, |row| {
+            let feature = DuckdbFeature {
+                layer,
+                row,
+                srid: Some(grid.srid),
+                transform: &transform,
+            };
+            read(&feature);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+            }
+        });
+        if let Err(e) = result {
+            error!("Layer '{}': {}", layer.name, e);
+            error!("Query: {}", sql);
+        }
+        cnt
+    }
+    fn query_sql(&self, tileset: &str, layer: &Layer, zoom: u8) -> Option<String> {
+        self.prepared_query(tileset, layer, zoom).cloned()
+    }
+    fn estimated_row_count(&self, layer: &Layer) -> Option<u64> {
+        // Row estimate of table statistics (not available for file scans)
+        let table_name = layer.table_name.as_ref()?.replace('"', "");
+        let sql = format!(
+            "SELECT estimated_size FROM duckdb_tables() WHERE table_name = {}",
+            quote_str(&table_name)
+        );
+        match self.query_value(&sql) {
+            Ok(value) => value.as_ref().and_then(int_value).map(|v| v as u64),
+            Err(e) => {
+                warn!("Layer '{}': {}", layer.name, e);
+                None
+            }
+        }
+    }
+    fn count_features(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+    ) -> u64 {
+        let sql = match self.tile_query(tileset, layer, extent, zoom, grid, None) {
+            Some(sql) => format!("SELECT COUNT(*) FROM ({}) AS _count", sql),
+            None => return 0,
+        };
+        match self.query_value(&sql) {
+            Ok(value) => value.as_ref().and_then(int_value).unwrap_or(0) as u64,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                error!("Query: {}", sql);
+                0
+            }
+        }
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for DuckdbDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        let mut ds = DuckdbDatasource::new(ds_cfg.duckdb.as_ref().unwrap());
+        ds.pool_size = ds_cfg.pool;
+        ds.pool_settings = PoolSettings::from_config(ds_cfg);
+        Ok(ds)
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "duckdb"
+# DuckDB database with spatial extension (":memory:" for queries on Parquet/CSV files only)
+duckdb = "<filename>.duckdb"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+duckdb = "{}"
+"#,
+            self.path
+        )
+    }
+}
@@ -0,0 +1,206 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::{Feature, FeatureAttrValType};
+use crate::core::layer::{Layer, LayerQuery};
+use crate::datasource::duckdb_ds::{
+    attr_value, replace_params, DuckdbDatasource, DuckdbFeature, DuckdbRow,
+};
+use duckdb::types::{TimeUnit, Value};
+use tile_grid::{Extent, Grid};
+
+#[test]
+fn test_replace_params() {
+    let extent = Extent {
+        minx: 958826.08,
+        miny: 5987771.04,
+        maxx: 978393.96,
+        maxy: 6007338.92,
+    };
+    let grid = Grid::web_mercator();
+    assert_eq!(
+        replace_params(
+            "SELECT * FROM 'places.parquet' WHERE ST_Intersects(geom, !bbox!) AND !zoom! > 5",
+            &extent,
+            10,
+            &grid,
+            None
+        ),
+        "SELECT * FROM 'places.parquet' WHERE ST_Intersects(geom, ST_MakeEnvelope(958826.08,5987771.04,978393.96,6007338.92)) AND 10 > 5"
+    );
+    assert_eq!(
+        replace_params(
+            "ST_Simplify(geom, !pixel_width!) AS geom, !time! AS t",
+            &extent,
+            0,
+            &grid,
+            Some("2020-01-01")
+        ),
+        "ST_Simplify(geom, 156543.033928041) AS geom, '2020-01-01' AS t"
+    );
+}
+
+#[test]
+fn test_build_query() {
+    let ds = DuckdbDatasource::new(":memory:");
+    let mut layer = Layer::new("places");
+    layer.table_name = Some("read_parquet('places.parquet')".to_string());
+    layer.geometry_field = Some("geom".to_string());
+    assert_eq!(
+        ds.build_query(&layer, None),
+        Some(
+            r#"SELECT ST_AsWKB("geom") AS "_wkb", _q.* EXCLUDE ("geom") FROM read_parquet('places.parquet') AS _q WHERE ST_Intersects_Extent("geom",!bbox!)"#
+                .to_string()
+        )
+    );
+
+    layer.filter = Some("population > 10000".to_string());
+    layer.order_by = Some("population DESC".to_string());
+    layer.query_limit = Some(100);
+    assert_eq!(
+        ds.build_query(&layer, None),
+        Some(
+            r#"SELECT ST_AsWKB("geom") AS "_wkb", _q.* EXCLUDE ("geom") FROM read_parquet('places.parquet') AS _q WHERE ST_Intersects_Extent("geom",!bbox!) AND (population > 10000) ORDER BY population DESC LIMIT 100"#
+                .to_string()
+        )
+    );
+
+    layer.filter = None;
+    layer.order_by = None;
+    layer.query_limit = None;
+    layer.query = vec![LayerQuery {
+        minzoom: 0,
+        maxzoom: None,
+        simplify: None,
+        tolerance: None,
+        sql: Some(
+            "SELECT * FROM 'places.csv' WHERE ST_Within(geom, !bbox!) AND !zoom! >= 8".to_string(),
+        ),
+    }];
+    assert_eq!(
+        ds.build_query(&layer, layer.query(10)),
+        Some(
+            r#"SELECT ST_AsWKB("geom") AS "_wkb", _q.* EXCLUDE ("geom") FROM (SELECT * FROM 'places.csv' WHERE ST_Within(geom, !bbox!) AND !zoom! >= 8) AS _q"#
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_attr_value() {
+    assert_eq!(attr_value(&Value::Null), None);
+    assert_eq!(attr_value(&Value::Blob(vec![1])), None);
+    assert_eq!(
+        attr_value(&Value::SmallInt(-3)),
+        Some(FeatureAttrValType::Int(-3))
+    );
+    assert_eq!(
+        attr_value(&Value::UBigInt(7)),
+        Some(FeatureAttrValType::UInt(7))
+    );
+    assert_eq!(
+        attr_value(&Value::Date32(18262)),
+        Some(FeatureAttrValType::String("2020-01-01".to_string()))
+    );
+    assert_eq!(
+        attr_value(&Value::Timestamp(
+            TimeUnit::Microsecond,
+            1_577_881_800_500_000
+        )),
+        Some(FeatureAttrValType::String(
+            "2020-01-01 12:30:00.500000".to_string()
+        ))
+    );
+    assert_eq!(
+        attr_value(&Value::List(vec![
+            Value::Text("a".to_string()),
+            Value::Text("b".to_string())
+        ])),
+        Some(FeatureAttrValType::VarcharArray(vec![
+            "a".to_string(),
+            "b".to_string()
+        ]))
+    );
+}
+
+#[test]
+fn test_feature() {
+    let wkb = vec![
+        1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xf0, 0x3f, 0, 0, 0, 0, 0, 0, 0, 0x40,
+    ];
+    let row: DuckdbRow = vec![
+        ("_wkb".to_string(), Value::Blob(wkb)),
+        ("fid".to_string(), Value::Int(7)),
+        ("name".to_string(), Value::Text("Bern".to_string())),
+        ("pop".to_string(), Value::Double(133.8)),
+        ("capital".to_string(), Value::Boolean(true)),
+        ("note".to_string(), Value::Null),
+    ];
+
+    let mut layer = Layer::new("places");
+    layer.geometry_field = Some("geom".to_string());
+    layer.fid_field = Some("fid".to_string());
+    let transform = |x, y| (x, y);
+    let feature = DuckdbFeature {
+        layer: &layer,
+        row: &row,
+        srid: Some(3857),
+        transform: &transform,
+    };
+    assert_eq!(feature.fid(), Some(7));
+    let attrs: Vec<(String, FeatureAttrValType)> = feature
+        .attributes()
+        .into_iter()
+        .map(|attr| (attr.key, attr.value))
+        .collect();
+    assert_eq!(
+        attrs,
+        vec![
+            ("fid".to_string(), FeatureAttrValType::Int(7)),
+            (
+                "name".to_string(),
+                FeatureAttrValType::String("Bern".to_string())
+            ),
+            ("pop".to_string(), FeatureAttrValType::Double(133.8)),
+            ("capital".to_string(), FeatureAttrValType::Bool(true)),
+        ]
+    );
+    assert_eq!(
+        format!("{:?}", feature.geometry()),
+        "Ok(Point(Point { x: 1.0, y: 2.0, srid: Some(3857) }))"
+    );
+
+    let row: DuckdbRow = vec![("_wkb".to_string(), Value::Null)];
+    let feature = DuckdbFeature {
+        layer: &layer,
+        row: &row,
+        srid: Some(3857),
+        transform: &transform,
+    };
+    assert!(feature.geometry().is_err());
+}
+
+#[test]
+fn test_not_connected() {
+    let ds = DuckdbDatasource::new(":memory:");
+    assert_eq!(
+        ds.query("SELECT 1").err(),
+        Some("DuckDB datasource not connected".to_string())
+    );
+}
+
+#[test]
+#[ignore]
+fn test_duckdb_query() {
+    use crate::datasource::DatasourceType;
+
+    let ds = DuckdbDatasource::new(":memory:").connected();
+    let rows = ds
+        .query("SELECT ST_AsWKB(ST_Point(1, 2)) AS _wkb, 42 AS answer")
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0][0].0, "_wkb");
+    assert_eq!(rows[0][1], ("answer".to_string(), Value::Int(42)));
+}
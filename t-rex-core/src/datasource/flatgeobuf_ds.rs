@@ -0,0 +1,402 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Native FlatGeobuf datasource, without GDAL
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::wkb_reader::read_wkb;
+use crate::datasource::DatasourceType;
+use flatgeobuf::{FallibleStreamingIterator, FeatureProperties, FgbFeature, FgbReader};
+use geozero::{ColumnValue, CoordDimensions, PropertyProcessor, ToWkb};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use tile_grid::{Extent, Grid};
+
+#[derive(Clone)]
+pub struct FlatGeobufDatasource {
+    pub path: String,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+struct FlatGeobufFeature<'a> {
+    layer: &'a Layer,
+    feature: &'a FgbFeature,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+/// Collects feature properties as attributes
+#[derive(Default)]
+struct AttributeReader {
+    attributes: Vec<FeatureAttr>,
+}
+
+impl PropertyProcessor for AttributeReader {
+    fn property(
+        &mut self,
+        _idx: usize,
+        name: &str,
+        value: &ColumnValue,
+    ) -> geozero::error::Result<bool> {
+        let value = match *value {
+            ColumnValue::Byte(v) => FeatureAttrValType::Int(i64::from(v)),
+            ColumnValue::Short(v) => FeatureAttrValType::Int(i64::from(v)),
+            ColumnValue::Int(v) => FeatureAttrValType::Int(i64::from(v)),
+            ColumnValue::Long(v) => FeatureAttrValType::Int(v),
+            ColumnValue::UByte(v) => FeatureAttrValType::UInt(u64::from(v)),
+            ColumnValue::UShort(v) => FeatureAttrValType::UInt(u64::from(v)),
+            ColumnValue::UInt(v) => FeatureAttrValType::UInt(u64::from(v)),
+            ColumnValue::ULong(v) => FeatureAttrValType::UInt(v),
+            ColumnValue::Bool(v) => FeatureAttrValType::Bool(v),
+            ColumnValue::Float(v) => FeatureAttrValType::Float(v),
+            ColumnValue::Double(v) => FeatureAttrValType::Double(v),
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                FeatureAttrValType::String(v.to_string())
+            }
+            // Not representable in vector tiles
+            ColumnValue::Binary(_) => return Ok(false),
+        };
+        self.attributes.push(FeatureAttr {
+            key: name.to_string(),
+            value,
+        });
+        Ok(false)
+    }
+}
+
+impl<'a> Feature for FlatGeobufFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            return None;
+        }
+        let attributes = self.attributes();
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                attributes
+                    .iter()
+                    .find(|attr| &attr.key == field)
+                    .map(|attr| attr.value.clone())
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        let mut reader = AttributeReader::default();
+        if let Err(e) = self.feature.process_properties(&mut reader) {
+            warn!("Layer '{}': {}", self.layer.name, e);
+        }
+        reader.attributes
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        let wkb = self
+            .feature
+            .to_wkb(CoordDimensions::xy())
+            .map_err(|e| e.to_string())?;
+        read_wkb(&wkb, self.srid, Some(self.transform))
+    }
+}
+
+impl FlatGeobufDatasource {
+    pub fn new(path: &str) -> FlatGeobufDatasource {
+        FlatGeobufDatasource {
+            path: path.to_string(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn open(&self) -> Result<FgbReader<BufReader<File>>, String> {
+        let file = File::open(&self.path).map_err(|e| format!("{}: {}", self.path, e))?;
+        FgbReader::open(BufReader::new(file)).map_err(|e| format!("{}: {}", self.path, e))
+    }
+    /// Layer name derived from file name
+    fn layer_name(&self) -> String {
+        Path::new(&self.path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "flatgeobuf".to_string())
+    }
+}
+
+impl DatasourceType for FlatGeobufDatasource {
+    /// Features are read from file with the spatial index of the FlatGeobuf file
+    fn connected(&self) -> FlatGeobufDatasource {
+        if let Err(e) = self.open() {
+            error!("{}", e);
+        }
+        self.clone()
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let reader = match self.open() {
+            Ok(reader) => reader,
+            Err(e) => {
+                error!("{}", e);
+                return Vec::new();
+            }
+        };
+        let header = reader.header();
+        let mut layer = Layer::new(&self.layer_name());
+        layer.table_name = Some(self.layer_name());
+        layer.geometry_type = header
+            .geometry_type()
+            .variant_name()
+            .filter(|name| *name != "Unknown")
+            .map(|name| name.to_uppercase());
+        layer.srid = header.crs().map(|crs| crs.code()).filter(|code| *code > 0);
+        vec![layer]
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        match self.open() {
+            Ok(reader) => reader
+                .header()
+                .columns()
+                .map(|columns| {
+                    columns
+                        .iter()
+                        .map(|col| (col.name().to_string(), String::new()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let reader = self
+            .open()
+            .map_err(|e| error!("Layer '{}': {}", layer.name, e))
+            .ok()?;
+        let envelope = reader.header().envelope()?;
+        if envelope.len() < 4 {
+            return None;
+        }
+        let extent = Extent {
+            minx: envelope.get(0),
+            miny: envelope.get(1),
+            maxx: envelope.get(2),
+            maxy: envelope.get(3),
+        };
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(4326);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        match self.open() {
+            Ok(reader) if reader.header().index_node_size() == 0 => warn!(
+                "Layer '{}': FlatGeobuf file without spatial index, reading all features for each tile",
+                layer.name
+            ),
+            Ok(_) => {}
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return;
+            }
+        }
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for FlatGeobuf layers",
+                layer.name
+            );
+        }
+        if !layer.no_transform {
+            match layer.srid {
+                Some(srid) => match transformation(srid, grid_srid) {
+                    Some(Some(transform)) => {
+                        info!(
+                            "Layer '{}': Reprojecting geometry to SRID {}",
+                            layer.name, grid_srid
+                        );
+                        self.geom_transform.insert(layer.name.clone(), transform);
+                    }
+                    Some(None) => {}
+                    None => error!(
+                        "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                        layer.name, srid, grid_srid
+                    ),
+                },
+                None => warn!("Layer '{}': Couldn't detect spatialref", layer.name),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for FlatGeobuf layers",
+                layer.name
+            );
+        }
+        if layer.buffer_size.is_some() && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Clipping with buffer_size not supported for FlatGeobuf layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let mut bbox_extent = if let Some(pixels) = layer.buffer_size {
+            let pixel_width = grid.pixel_width(zoom);
+            let buf = f64::from(pixels) * pixel_width;
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        if layer_transform.is_some() {
+            // Spatial filter must be in layer SRS
+            let inverse = layer
+                .srid
+                .and_then(|srid| transformation(grid.srid, srid))
+                .and_then(|tr| tr);
+            if let Some(inverse) = inverse {
+                bbox_extent = transform_extent(&bbox_extent, inverse);
+            }
+        }
+        let swap_axes = layer.swap_axes();
+        if swap_axes {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let selected = self.open().and_then(|reader| {
+            let indexed =
+                reader.header().index_node_size() > 0 && reader.header().features_count() > 0;
+            let selected = if indexed {
+                reader.select_bbox(
+                    bbox_extent.minx,
+                    bbox_extent.miny,
+                    bbox_extent.maxx,
+                    bbox_extent.maxy,
+                )
+            } else {
+                reader.select_all()
+            };
+            selected.map_err(|e| format!("{}: {}", self.path, e))
+        });
+        let mut features = match selected {
+            Ok(features) => features,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return 0;
+            }
+        };
+
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        loop {
+            let feature = match features.next() {
+                Ok(Some(feature)) => feature,
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Layer '{}': {}", layer.name, e);
+                    break;
+                }
+            };
+            let feat = FlatGeobufFeature {
+                layer,
+                feature,
+                srid: Some(grid.srid),
+                transform: &transform,
+            };
+            read(&feat);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+                break;
+            }
+        }
+        cnt
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for FlatGeobufDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        Ok(FlatGeobufDatasource::new(
+            ds_cfg.flatgeobuf.as_ref().unwrap(),
+        ))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "flatgeobuf"
+# FlatGeobuf file (read without GDAL)
+flatgeobuf = "<filename>.fgb"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+flatgeobuf = "{}"
+"#,
+            self.path
+        )
+    }
+}
@@ -0,0 +1,88 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::flatgeobuf_ds::FlatGeobufDatasource;
+use crate::datasource::test_helpers::{assert_point, bern_extent, BERN};
+use crate::datasource::DatasourceType;
+use tile_grid::Grid;
+
+const FGB: &str = "../data/places.fgb";
+
+#[test]
+fn test_detect_layers() {
+    let ds = FlatGeobufDatasource::new(FGB);
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+    assert_eq!(layers[0].geometry_type, Some("POINT".to_string()));
+    assert_eq!(layers[0].srid, Some(4326));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        cols.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(),
+        vec!["fid", "name", "population", "capital"]
+    );
+
+    let extent = ds.layer_extent(&layers[0], 3857);
+    assert_eq!(
+        format!("{:.4?}", extent),
+        "Some(Extent { minx: 6.1432, miny: 46.2044, maxx: 8.5417, maxy: 47.3769 })"
+    );
+
+    assert!(FlatGeobufDatasource::new("../data/missing.fgb")
+        .detect_layers(true)
+        .is_empty());
+}
+
+#[test]
+fn test_retrieve_features() {
+    let mut layer = Layer::new("places");
+    layer.srid = Some(4326);
+    let grid = Grid::web_mercator();
+
+    let mut ds = FlatGeobufDatasource::new(FGB).connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut features = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        assert_point(feat.geometry(), BERN);
+        features.push((feat.fid(), feat.attributes()));
+    });
+    assert_eq!(features.len(), 1);
+    let (fid, attrs) = &features[0];
+    assert_eq!(*fid, None);
+    assert_eq!(attrs.len(), 4);
+    assert_eq!(attrs[1].key, "name");
+    assert_eq!(
+        attrs[1].value,
+        FeatureAttrValType::String("Bern".to_string())
+    );
+    assert_eq!(attrs[2].value, FeatureAttrValType::Int(133883));
+    assert_eq!(attrs[3].value, FeatureAttrValType::Bool(true));
+
+    layer.fid_field = Some("fid".to_string());
+    let world = grid.tile_extent(0, 0, 0);
+    let mut fids = Vec::new();
+    ds.retrieve_features("ts", &layer, &world, 0, &grid, |feat| {
+        fids.push(feat.fid());
+    });
+    fids.sort();
+    assert_eq!(fids, vec![Some(1), Some(2), Some(3)]);
+
+    layer.query_limit = Some(2);
+    let cnt = ds.retrieve_features("ts", &layer, &world, 0, &grid, |_| {});
+    assert_eq!(cnt, 2);
+}
+
+#[test]
+fn test_gen_runtime_config() {
+    let ds = FlatGeobufDatasource::new(FGB);
+    assert_eq!(
+        ds.gen_runtime_config(),
+        "\n[[datasource]]\nflatgeobuf = \"../data/places.fgb\"\n"
+    );
+}
@@ -0,0 +1,664 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! GeoJSON and GeoJSONSeq file datasource, loaded into memory
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::{self, GeometryType};
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::rtree::RTree;
+use crate::datasource::DatasourceType;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tile_grid::{Extent, Grid};
+
+/// Geometry coordinates
+#[derive(Debug)]
+enum Geom {
+    Point((f64, f64)),
+    LineString(Vec<(f64, f64)>),
+    Polygon(Vec<Vec<(f64, f64)>>),
+    MultiPoint(Vec<(f64, f64)>),
+    MultiLineString(Vec<Vec<(f64, f64)>>),
+    MultiPolygon(Vec<Vec<Vec<(f64, f64)>>>),
+    GeometryCollection(Vec<Geom>),
+}
+
+struct JsonFeature {
+    /// Feature `id` member
+    id: Option<FeatureAttrValType>,
+    properties: Vec<(String, FeatureAttrValType)>,
+    geometry: Geom,
+}
+
+/// Features of a GeoJSON file with spatial index
+pub struct GeoJsonData {
+    features: Vec<JsonFeature>,
+    index: RTree,
+    /// Property names in order of appearance
+    columns: Vec<String>,
+    /// Geometry type of all features or "GEOMETRY"
+    geometry_type: String,
+}
+
+#[derive(Clone)]
+pub struct GeoJsonDatasource {
+    pub path: String,
+    data: Option<Arc<GeoJsonData>>,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+fn parse_position(value: &Value) -> Result<(f64, f64), String> {
+    match value.as_array().map(|a| a.as_slice()) {
+        Some([x, y, ..]) => match (x.as_f64(), y.as_f64()) {
+            (Some(x), Some(y)) => Ok((x, y)),
+            _ => Err(format!("Invalid position {}", value)),
+        },
+        _ => Err(format!("Invalid position {}", value)),
+    }
+}
+
+fn parse_array<T, F>(value: &Value, parse: F) -> Result<Vec<T>, String>
+where
+    F: Fn(&Value) -> Result<T, String>,
+{
+    value
+        .as_array()
+        .ok_or(format!("Invalid coordinates {}", value))?
+        .iter()
+        .map(parse)
+        .collect()
+}
+
+fn parse_line(value: &Value) -> Result<Vec<(f64, f64)>, String> {
+    parse_array(value, parse_position)
+}
+
+fn parse_polygon(value: &Value) -> Result<Vec<Vec<(f64, f64)>>, String> {
+    parse_array(value, parse_line)
+}
+
+fn parse_geometry(value: &Value) -> Result<Geom, String> {
+    let coords = &value["coordinates"];
+    let geom = match value["type"].as_str() {
+        Some("Point") => Geom::Point(parse_position(coords)?),
+        Some("LineString") => Geom::LineString(parse_line(coords)?),
+        Some("Polygon") => Geom::Polygon(parse_polygon(coords)?),
+        Some("MultiPoint") => Geom::MultiPoint(parse_line(coords)?),
+        Some("MultiLineString") => Geom::MultiLineString(parse_polygon(coords)?),
+        Some("MultiPolygon") => Geom::MultiPolygon(parse_array(coords, parse_polygon)?),
+        Some("GeometryCollection") => {
+            Geom::GeometryCollection(parse_array(&value["geometries"], parse_geometry)?)
+        }
+        _ => return Err(format!("Unsupported geometry type {}", value["type"])),
+    };
+    Ok(geom)
+}
+
+fn geometry_type(geometry: geom::Geometry) -> GeometryType {
+    match geometry {
+        geom::Geometry::Point(g) => GeometryType::Point(g),
+        geom::Geometry::LineString(g) => GeometryType::LineString(g),
+        geom::Geometry::Polygon(g) => GeometryType::Polygon(g),
+        geom::Geometry::MultiPoint(g) => GeometryType::MultiPoint(g),
+        geom::Geometry::MultiLineString(g) => GeometryType::MultiLineString(g),
+        geom::Geometry::MultiPolygon(g) => GeometryType::MultiPolygon(g),
+        geom::Geometry::GeometryCollection(g) => GeometryType::GeometryCollection(g),
+    }
+}
+
+/// Convert GeoJSON geometry object with coordinate transformation
+#[cfg(any(feature = "http", feature = "mongodb"))]
+pub(crate) fn read_geojson_geometry(
+    value: &Value,
+    srid: Option<i32>,
+    transform: &dyn Fn(f64, f64) -> (f64, f64),
+) -> Result<GeometryType, String> {
+    Ok(geometry_type(
+        parse_geometry(value)?.to_geometry(srid, transform),
+    ))
+}
+
+pub(crate) fn attr_value(value: &Value) -> Option<FeatureAttrValType> {
+    match value {
+        Value::Null => None,
+        Value::Bool(v) => Some(FeatureAttrValType::Bool(*v)),
+        Value::Number(n) => {
+            if let Some(v) = n.as_i64() {
+                Some(FeatureAttrValType::Int(v))
+            } else if let Some(v) = n.as_u64() {
+                Some(FeatureAttrValType::UInt(v))
+            } else {
+                n.as_f64().map(FeatureAttrValType::Double)
+            }
+        }
+        Value::String(v) => Some(FeatureAttrValType::String(v.clone())),
+        Value::Array(values) if values.iter().all(|v| v.is_string()) => {
+            Some(FeatureAttrValType::VarcharArray(
+                values
+                    .iter()
+                    .map(|v| v.as_str().unwrap_or("").to_string())
+                    .collect(),
+            ))
+        }
+        // Nested objects and mixed arrays as JSON text
+        _ => Some(FeatureAttrValType::String(value.to_string())),
+    }
+}
+
+impl Geom {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Geom::Point(_) => "POINT",
+            Geom::LineString(_) => "LINESTRING",
+            Geom::Polygon(_) => "POLYGON",
+            Geom::MultiPoint(_) => "MULTIPOINT",
+            Geom::MultiLineString(_) => "MULTILINESTRING",
+            Geom::MultiPolygon(_) => "MULTIPOLYGON",
+            Geom::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+        }
+    }
+    fn positions(&self, visit: &mut dyn FnMut(&(f64, f64))) {
+        match self {
+            Geom::Point(p) => visit(p),
+            Geom::LineString(line) | Geom::MultiPoint(line) => line.iter().for_each(visit),
+            Geom::Polygon(lines) | Geom::MultiLineString(lines) => {
+                lines.iter().flatten().for_each(visit)
+            }
+            Geom::MultiPolygon(polygons) => polygons.iter().flatten().flatten().for_each(visit),
+            Geom::GeometryCollection(geoms) => geoms.iter().for_each(|g| g.positions(visit)),
+        }
+    }
+    fn extent(&self) -> Option<Extent> {
+        let mut extent: Option<Extent> = None;
+        self.positions(&mut |&(x, y)| match extent {
+            Some(ref mut e) => {
+                e.minx = e.minx.min(x);
+                e.miny = e.miny.min(y);
+                e.maxx = e.maxx.max(x);
+                e.maxy = e.maxy.max(y);
+            }
+            None => {
+                extent = Some(Extent {
+                    minx: x,
+                    miny: y,
+                    maxx: x,
+                    maxy: y,
+                })
+            }
+        });
+        extent
+    }
+    fn to_geometry(
+        &self,
+        srid: Option<i32>,
+        transform: &dyn Fn(f64, f64) -> (f64, f64),
+    ) -> geom::Geometry {
+        let point = |p: &(f64, f64)| {
+            let (x, y) = transform(p.0, p.1);
+            geom::Point::new(x, y, srid)
+        };
+        let line = |coords: &Vec<(f64, f64)>| geom::LineString {
+            points: coords.iter().map(point).collect(),
+            srid,
+        };
+        let polygon = |rings: &Vec<Vec<(f64, f64)>>| geom::Polygon {
+            rings: rings.iter().map(line).collect(),
+            srid,
+        };
+        match self {
+            Geom::Point(p) => geom::Geometry::Point(point(p)),
+            Geom::LineString(coords) => geom::Geometry::LineString(line(coords)),
+            Geom::Polygon(rings) => geom::Geometry::Polygon(polygon(rings)),
+            Geom::MultiPoint(coords) => geom::Geometry::MultiPoint(geom::MultiPoint {
+                points: coords.iter().map(point).collect(),
+                srid,
+            }),
+            Geom::MultiLineString(lines) => {
+                geom::Geometry::MultiLineString(geom::MultiLineString {
+                    lines: lines.iter().map(line).collect(),
+                    srid,
+                })
+            }
+            Geom::MultiPolygon(polygons) => geom::Geometry::MultiPolygon(geom::MultiPolygon {
+                polygons: polygons.iter().map(polygon).collect(),
+                srid,
+            }),
+            Geom::GeometryCollection(geoms) => {
+                geom::Geometry::GeometryCollection(geom::GeometryCollection {
+                    geometries: geoms
+                        .iter()
+                        .map(|g| g.to_geometry(srid, transform))
+                        .collect(),
+                    srid,
+                })
+            }
+        }
+    }
+}
+
+impl GeoJsonData {
+    /// Parse GeoJSON (FeatureCollection, Feature or Geometry) or GeoJSONSeq (one object per line)
+    pub fn parse(text: &str) -> Result<GeoJsonData, String> {
+        let objects: Vec<Value> = match serde_json::from_str(text) {
+            Ok(value) => vec![value],
+            Err(e) => {
+                let lines = text
+                    .lines()
+                    // RFC 8142 record separator
+                    .map(|line| line.trim_start_matches('\u{1e}').trim())
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>();
+                if lines.len() < 2 {
+                    return Err(e.to_string());
+                }
+                lines
+                    .iter()
+                    .enumerate()
+                    .map(|(no, line)| {
+                        serde_json::from_str(line).map_err(|e| format!("line {}: {}", no + 1, e))
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        let mut data = GeoJsonData {
+            features: Vec::new(),
+            index: RTree::new(&[]),
+            columns: Vec::new(),
+            geometry_type: String::new(),
+        };
+        for object in objects {
+            match object["type"].as_str() {
+                Some("FeatureCollection") => {
+                    if let Some(features) = object["features"].as_array() {
+                        for feature in features {
+                            data.add_feature(feature)?;
+                        }
+                    }
+                }
+                Some("Feature") => data.add_feature(&object)?,
+                _ => {
+                    let geometry = parse_geometry(&object)?;
+                    data.features.push(JsonFeature {
+                        id: None,
+                        properties: Vec::new(),
+                        geometry,
+                    });
+                }
+            }
+        }
+        let mut extents = Vec::with_capacity(data.features.len());
+        let mut geometry_types = Vec::new();
+        for feature in &data.features {
+            // Empty geometries get an extent outside of valid coordinates
+            extents.push(feature.geometry.extent().unwrap_or(Extent {
+                minx: f64::MAX,
+                miny: f64::MAX,
+                maxx: f64::MAX,
+                maxy: f64::MAX,
+            }));
+            let type_name = feature.geometry.type_name();
+            if !geometry_types.contains(&type_name) {
+                geometry_types.push(type_name);
+            }
+        }
+        data.geometry_type = match geometry_types.as_slice() {
+            [single] => single.to_string(),
+            _ => "GEOMETRY".to_string(),
+        };
+        data.index = RTree::new(&extents);
+        Ok(data)
+    }
+    fn add_feature(&mut self, feature: &Value) -> Result<(), String> {
+        let geometry = match feature["geometry"] {
+            // Features without geometry are not displayable
+            Value::Null => return Ok(()),
+            ref geometry => parse_geometry(geometry)?,
+        };
+        let mut properties = Vec::new();
+        if let Some(props) = feature["properties"].as_object() {
+            for (key, value) in props {
+                if !self.columns.contains(key) {
+                    self.columns.push(key.clone());
+                }
+                if let Some(value) = attr_value(value) {
+                    properties.push((key.clone(), value));
+                }
+            }
+        }
+        self.features.push(JsonFeature {
+            id: attr_value(&feature["id"]),
+            properties,
+            geometry,
+        });
+        Ok(())
+    }
+    /// Extent of all features
+    pub fn extent(&self) -> Option<Extent> {
+        self.index.extent().filter(|extent| extent.minx != f64::MAX)
+    }
+    /// Read all features without coordinate transformation. Return feature count.
+    pub(crate) fn read_all<F>(&self, layer: &Layer, srid: Option<i32>, mut read: F) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let transform = |x: f64, y: f64| (x, y);
+        for feature in &self.features {
+            read(&GeoJsonFeature {
+                layer,
+                feature,
+                srid,
+                transform: &transform,
+            });
+        }
+        self.features.len() as u64
+    }
+}
+
+struct GeoJsonFeature<'a> {
+    layer: &'a Layer,
+    feature: &'a JsonFeature,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> Feature for GeoJsonFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            return fid_from_values(std::slice::from_ref(&self.feature.id));
+        }
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                self.feature
+                    .properties
+                    .iter()
+                    .find(|(key, _)| key == field)
+                    .map(|(_, value)| value.clone())
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.feature
+            .properties
+            .iter()
+            .map(|(key, value)| FeatureAttr {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        Ok(geometry_type(
+            self.feature.geometry.to_geometry(self.srid, self.transform),
+        ))
+    }
+}
+
+impl GeoJsonDatasource {
+    pub fn new(path: &str) -> GeoJsonDatasource {
+        GeoJsonDatasource {
+            path: path.to_string(),
+            data: None,
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn load(path: &str) -> Result<GeoJsonData, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        GeoJsonData::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+    /// Loaded features (file is read if not connected)
+    fn data(&self) -> Result<Arc<GeoJsonData>, String> {
+        match self.data {
+            Some(ref data) => Ok(data.clone()),
+            None => Self::load(&self.path).map(Arc::new),
+        }
+    }
+    /// Layer name derived from file name
+    fn layer_name(&self) -> String {
+        Path::new(&self.path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "geojson".to_string())
+    }
+}
+
+impl DatasourceType for GeoJsonDatasource {
+    /// New instance with loaded features
+    fn connected(&self) -> GeoJsonDatasource {
+        let data = match self.data() {
+            Ok(data) => {
+                info!(
+                    "Loaded {} features from '{}'",
+                    data.features.len(),
+                    self.path
+                );
+                Some(data)
+            }
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        };
+        GeoJsonDatasource {
+            path: self.path.clone(),
+            data,
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        match self.data() {
+            Ok(data) => {
+                let mut layer = Layer::new(&self.layer_name());
+                layer.geometry_field = Some("geometry".to_string());
+                layer.geometry_type = Some(data.geometry_type.clone());
+                // RFC 7946: coordinates are WGS84 longitude/latitude
+                layer.srid = Some(4326);
+                vec![layer]
+            }
+            Err(e) => {
+                error!("{}", e);
+                Vec::new()
+            }
+        }
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        match self.data() {
+            Ok(data) => data
+                .columns
+                .iter()
+                .map(|col| (col.clone(), String::new()))
+                .collect(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let extent = self
+            .data()
+            .map_err(|e| error!("Layer '{}': {}", layer.name, e))
+            .ok()?
+            .extent()?;
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(4326);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for GeoJSON layers",
+                layer.name
+            );
+        }
+        if !layer.no_transform {
+            let srid = layer.srid.unwrap_or(4326);
+            match transformation(srid, grid_srid) {
+                Some(Some(transform)) => {
+                    info!(
+                        "Layer '{}': Reprojecting geometry to SRID {}",
+                        layer.name, grid_srid
+                    );
+                    self.geom_transform.insert(layer.name.clone(), transform);
+                }
+                Some(None) => {}
+                None => error!(
+                    "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                    layer.name, srid, grid_srid
+                ),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for GeoJSON layers",
+                layer.name
+            );
+        }
+        if layer.buffer_size.is_some() && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Clipping with buffer_size not supported for GeoJSON layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let data = match self.data() {
+            Ok(data) => data,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return 0;
+            }
+        };
+        let mut bbox_extent = if let Some(pixels) = layer.buffer_size {
+            let pixel_width = grid.pixel_width(zoom);
+            let buf = f64::from(pixels) * pixel_width;
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        if layer_transform.is_some() {
+            // Spatial filter must be in layer SRS
+            let srid = layer.srid.unwrap_or(4326);
+            if let Some(Some(inverse)) = transformation(grid.srid, srid) {
+                bbox_extent = transform_extent(&bbox_extent, inverse);
+            }
+        }
+        let swap_axes = layer.swap_axes();
+        if swap_axes {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        for idx in data.index.query(&bbox_extent) {
+            let feat = GeoJsonFeature {
+                layer,
+                feature: &data.features[idx],
+                srid: Some(grid.srid),
+                transform: &transform,
+            };
+            read(&feat);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+                break;
+            }
+        }
+        cnt
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for GeoJsonDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        Ok(GeoJsonDatasource::new(ds_cfg.geojson.as_ref().unwrap()))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "geojson"
+# GeoJSON or GeoJSONSeq file (loaded into memory)
+geojson = "<filename>.geojson"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+geojson = "{}"
+"#,
+            self.path
+        )
+    }
+}
@@ -0,0 +1,111 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::geojson_ds::{GeoJsonData, GeoJsonDatasource};
+use crate::datasource::test_helpers::{assert_point, bern_extent, BERN};
+use crate::datasource::DatasourceType;
+use tile_grid::Grid;
+
+const GEOJSON: &str = "../data/places.geojson";
+
+#[test]
+fn test_detect_layers() {
+    let ds = GeoJsonDatasource::new(GEOJSON);
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+    assert_eq!(layers[0].geometry_field, Some("geometry".to_string()));
+    assert_eq!(layers[0].geometry_type, Some("GEOMETRY".to_string()));
+    assert_eq!(layers[0].srid, Some(4326));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        cols.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(),
+        vec!["capital", "name", "population", "tags", "info", "area"]
+    );
+
+    let extent = ds.layer_extent(&layers[0], 3857);
+    assert_eq!(
+        format!("{:.4?}", extent),
+        "Some(Extent { minx: 6.1432, miny: 46.2044, maxx: 8.5417, maxy: 47.3769 })"
+    );
+
+    assert!(GeoJsonDatasource::new("../data/missing.geojson")
+        .detect_layers(true)
+        .is_empty());
+}
+
+#[test]
+fn test_retrieve_features() {
+    let mut layer = Layer::new("places");
+    layer.srid = Some(4326);
+    let grid = Grid::web_mercator();
+
+    let mut ds = GeoJsonDatasource::new(GEOJSON).connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut features = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        assert!(feat.geometry().is_ok());
+        features.push((feat.fid(), feat.attributes()));
+    });
+    assert_eq!(features.len(), 3);
+    let (fid, attrs) = &features[0];
+    assert_eq!(*fid, Some(1));
+    assert_eq!(attrs.len(), 3);
+    assert_eq!(attrs[0].key, "capital");
+    assert_eq!(attrs[0].value, FeatureAttrValType::Bool(true));
+    assert_eq!(
+        attrs[1].value,
+        FeatureAttrValType::String("Bern".to_string())
+    );
+    assert_eq!(attrs[2].value, FeatureAttrValType::Int(133883));
+    let (fid, attrs) = &features[1];
+    assert!(fid.is_some());
+    assert_eq!(
+        attrs[0].value,
+        FeatureAttrValType::String("{\"length\":288}".to_string())
+    );
+    assert_eq!(features[2].0, None);
+    assert_eq!(features[2].1[0].value, FeatureAttrValType::Double(8.7));
+
+    layer.fid_field = Some("population".to_string());
+    let mut fids = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        if feat.fid().is_some() {
+            assert_point(feat.geometry(), BERN);
+        }
+        fids.push(feat.fid());
+    });
+    assert_eq!(fids, vec![Some(133883), None, None]);
+
+    layer.query_limit = Some(2);
+    let cnt = ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |_| {});
+    assert_eq!(cnt, 2);
+}
+
+#[test]
+fn test_geojson_seq() {
+    let text = "\u{1e}{\"type\":\"Feature\",\"properties\":{\"tags\":[\"a\",\"b\"]},\"geometry\":{\"type\":\"Point\",\"coordinates\":[7.0,46.0]}}\n\
+                \u{1e}{\"type\":\"MultiPoint\",\"coordinates\":[[8.0,47.0],[8.5,47.5]]}\n";
+    let data = GeoJsonData::parse(text).unwrap();
+    assert_eq!(
+        format!("{:?}", data.extent()),
+        "Some(Extent { minx: 7.0, miny: 46.0, maxx: 8.5, maxy: 47.5 })"
+    );
+    assert!(GeoJsonData::parse("{\"type\":\"Point\",\"coordinates\":[7.0]}").is_err());
+    assert!(GeoJsonData::parse("not json").is_err());
+}
+
+#[test]
+fn test_gen_runtime_config() {
+    let ds = GeoJsonDatasource::new(GEOJSON);
+    assert_eq!(
+        ds.gen_runtime_config(),
+        "\n[[datasource]]\ngeojson = \"../data/places.geojson\"\n"
+    );
+}
@@ -0,0 +1,535 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Native GeoPackage datasource (http://www.geopackage.org/spec/), without GDAL
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::sqlite_db::{
+    read_rows, table_info, SpatialIndex, SqliteDb, TableColumn, TableInfo,
+};
+use crate::datasource::wkb_reader::read_wkb;
+use crate::datasource::DatasourceType;
+use rusqlite::types::Value as SqliteValue;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use tile_grid::{Extent, Grid};
+
+#[derive(Clone)]
+pub struct GpkgDatasource {
+    pub path: String,
+    db: SqliteDb,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+/// Entry of `gpkg_geometry_columns`
+struct GeomColumn {
+    table_name: String,
+    column_name: String,
+    geometry_type: String,
+    /// EPSG code of `srs_id`
+    srid: Option<i32>,
+}
+
+impl GpkgDatasource {
+    pub fn new(path: &str) -> GpkgDatasource {
+        GpkgDatasource {
+            path: path.to_string(),
+            db: SqliteDb::new(path),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn geometry_columns(conn: &Connection) -> Result<Vec<GeomColumn>, String> {
+        // EPSG codes of GeoPackage SRS ids
+        let sql = "SELECT g.table_name, g.column_name, g.geometry_type_name, \
+                          CASE WHEN upper(s.organization) = 'EPSG' THEN s.organization_coordsys_id END \
+                   FROM gpkg_geometry_columns g \
+                   LEFT JOIN gpkg_spatial_ref_sys s ON s.srs_id = g.srs_id";
+        let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+        let columns = stmt
+            .query_map([], |row| {
+                Ok(GeomColumn {
+                    table_name: row.get(0)?,
+                    column_name: row.get(1)?,
+                    geometry_type: row
+                        .get::<_, Option<String>>(2)?
+                        .unwrap_or("GEOMETRY".to_string())
+                        .to_uppercase(),
+                    srid: row.get(3)?,
+                })
+            })
+            .and_then(|rows| rows.collect())
+            .map_err(|e| e.to_string())?;
+        Ok(columns)
+    }
+    /// Table and geometry column of layer
+    fn layer_table(conn: &Connection, layer: &Layer) -> Result<(TableInfo, usize), String> {
+        let table_name = layer.table_name.as_ref().unwrap_or(&layer.name);
+        let table = table_info(conn, table_name)?;
+        let geom_name = match layer.geometry_field {
+            Some(ref field) => field.clone(),
+            None => Self::geometry_columns(conn)?
+                .into_iter()
+                .find(|col| col.table_name.eq_ignore_ascii_case(table_name))
+                .map(|col| col.column_name)
+                .ok_or(format!(
+                    "No geometry column found in table '{}'",
+                    table_name
+                ))?,
+        };
+        let geom_idx = table
+            .column_index(&geom_name)
+            .ok_or(format!("Geometry column '{}' not found", geom_name))?;
+        Ok((table, geom_idx))
+    }
+    /// Layer extent stored in `gpkg_contents` (in layer SRS)
+    fn contents_extent(conn: &Connection, table_name: &str) -> Result<Option<Extent>, String> {
+        conn.query_row(
+            "SELECT min_x, min_y, max_x, max_y FROM gpkg_contents \
+             WHERE table_name = ?1 COLLATE NOCASE \
+               AND min_x IS NOT NULL AND min_y IS NOT NULL \
+               AND max_x IS NOT NULL AND max_y IS NOT NULL",
+            params![table_name],
+            |row| {
+                Ok(Extent {
+                    minx: row.get(0)?,
+                    miny: row.get(1)?,
+                    maxx: row.get(2)?,
+                    maxy: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Decoded GeoPackage geometry blob header
+struct GpkgBlob<'a> {
+    /// Bounding box (minx, maxx, miny, maxy)
+    envelope: Option<[f64; 4]>,
+    empty: bool,
+    wkb: &'a [u8],
+}
+
+impl<'a> GpkgBlob<'a> {
+    fn parse(blob: &'a [u8]) -> Result<GpkgBlob<'a>, String> {
+        if blob.len() < 8 || &blob[0..2] != b"GP" {
+            return Err("Invalid GeoPackage geometry".to_string());
+        }
+        let flags = blob[3];
+        let little_endian = flags & 0x01 != 0;
+        let envelope_len = match (flags >> 1) & 0x07 {
+            0 => 0,
+            1 => 4,
+            2 | 3 => 6,
+            4 => 8,
+            n => return Err(format!("Invalid GeoPackage envelope type {}", n)),
+        };
+        let wkb_start = 8 + 8 * envelope_len;
+        let wkb = blob
+            .get(wkb_start..)
+            .ok_or("GeoPackage geometry truncated")?;
+        let envelope = if envelope_len > 0 {
+            let mut env = [0f64; 4];
+            for (i, v) in env.iter_mut().enumerate() {
+                let mut b = [0u8; 8];
+                b.copy_from_slice(&blob[8 + 8 * i..16 + 8 * i]);
+                *v = if little_endian {
+                    f64::from_le_bytes(b)
+                } else {
+                    f64::from_be_bytes(b)
+                };
+            }
+            Some(env)
+        } else {
+            None
+        };
+        Ok(GpkgBlob {
+            envelope,
+            empty: flags & 0x10 != 0,
+            wkb,
+        })
+    }
+    fn intersects(&self, extent: &Extent) -> bool {
+        match self.envelope {
+            Some([minx, maxx, miny, maxy]) => {
+                minx <= extent.maxx
+                    && maxx >= extent.minx
+                    && miny <= extent.maxy
+                    && maxy >= extent.miny
+            }
+            // Envelope is optional
+            None => !self.empty,
+        }
+    }
+}
+
+struct GpkgFeature<'a> {
+    layer: &'a Layer,
+    columns: &'a [TableColumn],
+    geom_idx: usize,
+    rowid: i64,
+    values: Vec<SqliteValue>,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> GpkgFeature<'a> {
+    fn attr_value(&self, idx: usize) -> Option<FeatureAttrValType> {
+        match self.values[idx] {
+            SqliteValue::Null | SqliteValue::Blob(_) => None,
+            SqliteValue::Integer(v) if self.columns[idx].decl_type == "BOOLEAN" => {
+                Some(FeatureAttrValType::Bool(v != 0))
+            }
+            SqliteValue::Integer(v) => Some(FeatureAttrValType::Int(v)),
+            SqliteValue::Real(v) => Some(FeatureAttrValType::Double(v)),
+            SqliteValue::Text(ref v) => Some(FeatureAttrValType::String(v.clone())),
+        }
+    }
+}
+
+impl<'a> Feature for GpkgFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            // Feature id of GeoPackage tables
+            return if self.rowid >= 0 {
+                Some(self.rowid as u64)
+            } else {
+                None
+            };
+        }
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                self.columns
+                    .iter()
+                    .position(|col| col.name == *field)
+                    .and_then(|idx| self.attr_value(idx))
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        let mut attrs = Vec::new();
+        for (idx, col) in self.columns.iter().enumerate() {
+            // The INTEGER PRIMARY KEY is the feature id
+            if idx == self.geom_idx || col.rowid_alias {
+                continue;
+            }
+            match self.attr_value(idx) {
+                Some(value) => attrs.push(FeatureAttr {
+                    key: col.name.clone(),
+                    value,
+                }),
+                None => {
+                    if let SqliteValue::Blob(_) = self.values[idx] {
+                        warn!(
+                            "Layer '{}' - skipping unsupported blob field '{}'",
+                            self.layer.name, col.name
+                        );
+                    }
+                }
+            }
+        }
+        attrs
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        match self.values[self.geom_idx] {
+            SqliteValue::Blob(ref blob) => {
+                let blob = GpkgBlob::parse(blob)?;
+                read_wkb(blob.wkb, self.srid, Some(self.transform))
+            }
+            _ => Err("Geometry is NULL".to_string()),
+        }
+    }
+}
+
+impl DatasourceType for GpkgDatasource {
+    /// New instance with connected pool
+    fn connected(&self) -> GpkgDatasource {
+        GpkgDatasource {
+            path: self.path.clone(),
+            db: self.db.connected(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let columns = self.db.with_conn(Self::geometry_columns);
+        let columns = match columns {
+            Ok(columns) => columns,
+            Err(e) => {
+                error!("GeoPackage '{}': {}", self.path, e);
+                return Vec::new();
+            }
+        };
+        let mut layers: Vec<Layer> = Vec::new();
+        let ntables = |table: &str| columns.iter().filter(|c| c.table_name == table).count();
+        for col in &columns {
+            // Name layers of tables with multiple geometry columns like GDAL
+            let name = if ntables(&col.table_name) > 1 {
+                format!("{}_{}", col.table_name, col.column_name)
+            } else {
+                col.table_name.clone()
+            };
+            let mut layer = Layer::new(&name);
+            layer.table_name = Some(col.table_name.clone());
+            layer.geometry_field = Some(col.column_name.clone());
+            layer.geometry_type = Some(col.geometry_type.clone());
+            layer.srid = col.srid;
+            layers.push(layer);
+        }
+        layers
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        let table = self.db.with_conn(|conn| Self::layer_table(conn, layer));
+        match table {
+            Ok((table, geom_idx)) => table
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|&(idx, col)| idx != geom_idx && !col.rowid_alias)
+                .map(|(_, col)| (col.name.clone(), String::new()))
+                .collect(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let table_name = layer.table_name.as_ref().unwrap_or(&layer.name);
+        let extent = self
+            .db
+            .with_conn(|conn| Self::contents_extent(conn, table_name));
+        let extent = match extent {
+            Ok(Some(extent)) => extent,
+            Ok(None) => {
+                warn!("Layer '{}': Unable to get extent", layer.name);
+                return None;
+            }
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return None;
+            }
+        };
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(0);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if let Err(e) = self.db.with_conn(|conn| Self::layer_table(conn, layer)) {
+            error!("Layer '{}': {}", layer.name, e);
+            return;
+        }
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for GeoPackage layers",
+                layer.name
+            );
+        }
+        if !layer.no_transform {
+            match layer.srid {
+                Some(srid) => match transformation(srid, grid_srid) {
+                    Some(Some(transform)) => {
+                        info!(
+                            "Layer '{}': Reprojecting geometry to SRID {}",
+                            layer.name, grid_srid
+                        );
+                        self.geom_transform.insert(layer.name.clone(), transform);
+                    }
+                    Some(None) => {}
+                    None => error!(
+                        "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                        layer.name, srid, grid_srid
+                    ),
+                },
+                None => warn!("Layer '{}': Couldn't detect spatialref", layer.name),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for GeoPackage layers",
+                layer.name
+            );
+        }
+        if layer.buffer_size.is_some() && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Clipping with buffer_size not supported for GeoPackage layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let mut bbox_extent = if let Some(pixels) = layer.buffer_size {
+            let pixel_width = grid.pixel_width(zoom);
+            let buf = f64::from(pixels) * pixel_width;
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        if layer_transform.is_some() {
+            // Spatial filter must be in layer SRS
+            let inverse = layer
+                .srid
+                .and_then(|srid| transformation(grid.srid, srid))
+                .and_then(|tr| tr);
+            if let Some(inverse) = inverse {
+                bbox_extent = transform_extent(&bbox_extent, inverse);
+            }
+        }
+        let swap_axes = layer.swap_axes();
+        if swap_axes {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let result = self.db.with_conn(|conn| {
+            let (table, geom_idx) = Self::layer_table(conn, layer)?;
+            debug!("retrieve_features layer: {}", table.name);
+            let mut cnt = 0;
+            let query_limit = layer.query_limit.unwrap_or(0);
+            let read_row = |rowid: i64, values: Vec<SqliteValue>| {
+                let intersects = match values[geom_idx] {
+                    SqliteValue::Blob(ref blob) => GpkgBlob::parse(blob)
+                        .map(|blob| blob.intersects(&bbox_extent))
+                        .unwrap_or(true),
+                    _ => false,
+                };
+                if !intersects {
+                    return true;
+                }
+                let feat = GpkgFeature {
+                    layer,
+                    columns: &table.columns,
+                    geom_idx,
+                    rowid,
+                    values,
+                    srid: Some(grid.srid),
+                    transform: &transform,
+                };
+                read(&feat);
+                cnt += 1;
+                if cnt == query_limit as u64 {
+                    info!(
+                        "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                        layer.name, cnt, zoom
+                    );
+                    return false;
+                }
+                true
+            };
+
+            let index = SpatialIndex {
+                table: format!("rtree_{}_{}", table.name, table.columns[geom_idx].name),
+                columns: ["id", "minx", "maxx", "miny", "maxy"],
+            };
+            read_rows(
+                conn,
+                &table,
+                Some(&index),
+                &bbox_extent,
+                layer.filter.as_deref(),
+                read_row,
+            )?;
+            Ok(cnt)
+        });
+        match result {
+            Ok(cnt) => cnt,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                0
+            }
+        }
+    }
+    fn supports_filter(&self) -> bool {
+        true
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for GpkgDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        Ok(GpkgDatasource::new(ds_cfg.gpkg.as_ref().unwrap()))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "gpkg"
+# GeoPackage file (read without GDAL)
+gpkg = "<filename>.gpkg"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+gpkg = "{}"
+"#,
+            self.path
+        )
+    }
+}
@@ -0,0 +1,158 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::gpkg_ds::GpkgDatasource;
+use crate::datasource::sqlite_db::{table_info, SqliteDb};
+use crate::datasource::test_helpers::{assert_point, bern_extent};
+use crate::datasource::DatasourceType;
+use tile_grid::Extent;
+use tile_grid::Grid;
+
+const GPKG: &str = "../data/natural_earth.gpkg";
+
+#[test]
+fn test_table_info() {
+    let conn = SqliteDb::open(GPKG).unwrap();
+    let table = table_info(&conn, "NE_110m_admin_0_countries").unwrap();
+    assert_eq!(table.name, "ne_110m_admin_0_countries");
+    let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["fid", "geom", "name", "iso_a3"]);
+    assert!(table.columns[0].rowid_alias);
+    assert_eq!(table.column_index("NAME"), Some(2));
+    assert_eq!(
+        table_info(&conn, "missing").err(),
+        Some("Table 'missing' not found".to_string())
+    );
+
+    assert!(SqliteDb::open("../data/natural_earth.gpkg.missing").is_err());
+    assert!(SqliteDb::open("../README.md").is_err());
+}
+
+#[test]
+fn test_detect_layers() {
+    let ds = GpkgDatasource::new(GPKG);
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 3);
+    assert_eq!(layers[0].name, "ne_10m_populated_places");
+    assert_eq!(layers[0].geometry_field, Some("geom".to_string()));
+    assert_eq!(layers[0].geometry_type, Some("POINT".to_string()));
+    assert_eq!(layers[0].srid, Some(3857));
+    assert_eq!(layers[2].geometry_type, Some("MULTIPOLYGON".to_string()));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        cols.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(),
+        vec!["SCALERANK", "NAME", "POP_MAX"]
+    );
+
+    let extent = ds.layer_extent(&layers[1], 3857);
+    assert_eq!(
+        format!("{:.5?}", extent),
+        "Some(Extent { minx: -164.90374, miny: -52.15776, maxx: 177.21155, maxy: 75.79355 })"
+    );
+}
+
+#[test]
+fn test_retrieve_points() {
+    let mut layer = Layer::new("points");
+    layer.table_name = Some(String::from("ne_10m_populated_places"));
+    layer.geometry_field = Some(String::from("geom"));
+    layer.srid = Some(3857);
+    layer.fid_field = Some(String::from("SCALERANK"));
+    let grid = Grid::web_mercator();
+
+    let mut ds = GpkgDatasource::new(GPKG);
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut reccnt = 0;
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        assert_point(feat.geometry(), (831219.91, 5928485.17));
+        let attrs = feat.attributes();
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(attrs[0].key, "SCALERANK");
+        assert_eq!(attrs[0].value, FeatureAttrValType::Int(4));
+        assert_eq!(
+            attrs[1].value,
+            FeatureAttrValType::String("Bern".to_string())
+        );
+        assert_eq!(attrs[2].value, FeatureAttrValType::Int(275329));
+        assert_eq!(feat.fid(), Some(4));
+        reccnt += 1;
+    });
+    assert_eq!(reccnt, 1);
+}
+
+#[test]
+fn test_retrieve_polygons() {
+    let mut layer = Layer::new("countries");
+    layer.table_name = Some(String::from("ne_110m_admin_0_countries"));
+    layer.srid = Some(3857);
+    let grid = Grid::web_mercator();
+
+    let mut ds = GpkgDatasource::new(GPKG);
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut names = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        assert!(feat.geometry().is_ok());
+        names.push((feat.fid(), feat.attributes()[0].value.clone()));
+    });
+    assert_eq!(names.len(), 4);
+    assert_eq!(
+        names[0],
+        (
+            Some(29),
+            FeatureAttrValType::String("Switzerland".to_string())
+        )
+    );
+
+    layer.query_limit = Some(2);
+    let cnt = ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |_| {});
+    assert_eq!(cnt, 2);
+
+    layer.query_limit = None;
+    layer.filter = Some("iso_a3 IN ('CHE', 'ITA')".to_string());
+    let mut ds = ds.connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let cnt = ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |_| {});
+    assert_eq!(cnt, 2);
+}
+
+#[test]
+fn test_coord_transformation() {
+    let mut layer = Layer::new("points");
+    layer.table_name = Some(String::from("ne_10m_populated_places"));
+    layer.geometry_field = Some(String::from("geom"));
+    layer.srid = Some(3857);
+    let grid = Grid::wgs84();
+
+    let mut ds = GpkgDatasource::new(GPKG);
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let extent = Extent {
+        minx: 7.3828,
+        miny: 46.8000,
+        maxx: 7.7343,
+        maxy: 47.0401,
+    };
+    let mut reccnt = 0;
+    ds.retrieve_features("ts", &layer, &extent, 10, &grid, |feat| {
+        assert_eq!(
+            "Ok(Point(Point { x: 7.46698, y: 46.91668, srid: Some(4326) }))",
+            &*format!("{:.5?}", feat.geometry())
+        );
+        reccnt += 1;
+    });
+    assert_eq!(reccnt, 1);
+}
+
+#[test]
+fn test_gen_runtime_config() {
+    let ds = GpkgDatasource::new(GPKG);
+    assert_eq!(
+        ds.gen_runtime_config(),
+        "\n[[datasource]]\ngpkg = \"../data/natural_earth.gpkg\"\n"
+    );
+}
@@ -0,0 +1,119 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! HTTP client shared by datasources of web services (WFS, Elasticsearch, ORDS, upstream tiles
+//! and remote PMTiles archives). Based on libcurl, which handles TLS, chunked transfer encoding,
+//! redirects and proxies configured with `http_proxy`, `https_proxy` and `no_proxy`.
+
+use crate::datasource::db_url::ConnectParams;
+use curl::easy::{Easy, List};
+use std::time::Duration;
+
+/// Maximal number of followed redirects
+const MAX_REDIRECTS: u32 = 5;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client of a web service with optional basic authentication
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    /// Service name for error messages
+    service: &'static str,
+    /// `http(s)://host:port`
+    base_url: String,
+    user: String,
+    password: String,
+    /// Abort requests without received data within this time
+    timeout: Duration,
+}
+
+/// Status code and body of an HTTP response
+#[derive(Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl HttpClient {
+    pub fn new(
+        service: &'static str,
+        params: &ConnectParams,
+        https: bool,
+        timeout: Duration,
+    ) -> HttpClient {
+        let host = if params.host.contains(':') {
+            // IPv6 address
+            format!("[{}]", params.host)
+        } else {
+            params.host.clone()
+        };
+        HttpClient {
+            service,
+            base_url: format!(
+                "{}://{}:{}",
+                if https { "https" } else { "http" },
+                host,
+                params.port
+            ),
+            user: params.user.clone(),
+            password: params.password.clone(),
+            timeout,
+        }
+    }
+    /// URL of an absolute request path (with query string)
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+    /// Send request and return the final response after redirects
+    pub fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, String)],
+        body: Option<&[u8]>,
+    ) -> Result<HttpResponse, String> {
+        trace!("{} {}", method, url);
+        let err = |e: curl::Error| format!("{} request {}: {}", self.service, url, e);
+        let mut easy = Easy::new();
+        easy.url(url).map_err(err)?;
+        easy.custom_request(method).map_err(err)?;
+        if !self.user.is_empty() {
+            easy.username(&self.user).map_err(err)?;
+            easy.password(&self.password).map_err(err)?;
+        }
+        let mut list = List::new();
+        for (name, value) in headers {
+            list.append(&format!("{}: {}", name, value)).map_err(err)?;
+        }
+        // Send body without waiting for `100 Continue`
+        list.append("Expect:").map_err(err)?;
+        easy.http_headers(list).map_err(err)?;
+        if let Some(body) = body {
+            easy.post_fields_copy(body).map_err(err)?;
+        }
+        easy.follow_location(true).map_err(err)?;
+        easy.max_redirections(MAX_REDIRECTS).map_err(err)?;
+        easy.connect_timeout(CONNECT_TIMEOUT).map_err(err)?;
+        easy.low_speed_limit(1).map_err(err)?;
+        easy.low_speed_time(self.timeout).map_err(err)?;
+        let mut data = Vec::new();
+        {
+            let mut transfer = easy.transfer();
+            transfer
+                .write_function(|chunk| {
+                    data.extend_from_slice(chunk);
+                    Ok(chunk.len())
+                })
+                .map_err(err)?;
+            transfer.perform().map_err(err)?;
+        }
+        let status = easy.response_code().map_err(err)? as u16;
+        Ok(HttpResponse { status, body: data })
+    }
+    /// GET request
+    pub fn get(&self, url: &str, headers: &[(&str, String)]) -> Result<HttpResponse, String> {
+        self.request("GET", url, headers, None)
+    }
+}
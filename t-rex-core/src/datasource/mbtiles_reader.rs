@@ -0,0 +1,149 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Stored tiles of MBTiles files (https://github.com/mapbox/mbtiles-spec/blob/master/1.3/spec.md)
+
+use crate::datasource::sqlite_db::{find_table, SqliteDb};
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::fmt;
+use tile_grid::Extent;
+
+/// Read-only access to the tiles of an MBTiles file
+pub struct MbtilesReader {
+    path: String,
+    db: SqliteDb,
+    /// Number of stored tiles
+    count: usize,
+    metadata: BTreeMap<String, String>,
+}
+
+impl fmt::Debug for MbtilesReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MbtilesReader")
+            .field("path", &self.path)
+            .field("tiles", &self.count)
+            .finish()
+    }
+}
+
+fn read_metadata(conn: &Connection) -> Result<BTreeMap<String, String>, String> {
+    for table in &["metadata", "tiles"] {
+        if find_table(conn, table)?.is_none() {
+            return Err(format!("Table '{}' not found", table));
+        }
+    }
+    let mut stmt = conn
+        .prepare("SELECT name, value FROM metadata")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .and_then(|rows| rows.collect::<Result<Vec<(Value, Value)>, _>>())
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match row {
+            (Value::Text(name), Value::Text(value)) => Some((name, value)),
+            _ => None,
+        })
+        .collect())
+}
+
+impl MbtilesReader {
+    pub fn open(path: &str) -> Result<MbtilesReader, String> {
+        let conn = SqliteDb::open(path)?;
+        let err = |e: String| format!("{}: {}", path, e);
+        let metadata = read_metadata(&conn).map_err(err)?;
+        if let Some(format) = metadata.get("format") {
+            if format != "pbf" {
+                return Err(format!(
+                    "{}: unsupported tile format '{}' (only 'pbf' vector tiles are supported)",
+                    path, format
+                ));
+            }
+        }
+        // `tiles` is a view joining `map` and `images` in files with deduplicated tiles
+        let count: i64 = conn
+            .query_row("SELECT count(*) FROM tiles", [], |row| row.get(0))
+            .map_err(|e| err(e.to_string()))?;
+        info!("{}: {} tiles", path, count);
+        Ok(MbtilesReader {
+            path: path.to_string(),
+            db: SqliteDb::new(path).connected(),
+            count: count as usize,
+            metadata,
+        })
+    }
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Number of stored tiles
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Entries of the `metadata` table
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+    fn metadata_value<T: std::str::FromStr>(&self, name: &str) -> Option<T> {
+        self.metadata.get(name)?.trim().parse().ok()
+    }
+    fn metadata_numbers(&self, name: &str) -> Option<Vec<f64>> {
+        self.metadata
+            .get(name)?
+            .split(',')
+            .map(|v| v.trim().parse().ok())
+            .collect()
+    }
+    pub fn minzoom(&self) -> Option<u8> {
+        self.metadata_value("minzoom")
+    }
+    pub fn maxzoom(&self) -> Option<u8> {
+        self.metadata_value("maxzoom")
+    }
+    /// Bounds in WGS84
+    pub fn bounds(&self) -> Option<Extent> {
+        match self.metadata_numbers("bounds")?[..] {
+            [minx, miny, maxx, maxy] => Some(Extent {
+                minx,
+                miny,
+                maxx,
+                maxy,
+            }),
+            _ => None,
+        }
+    }
+    /// Center longitude, latitude and zoom level
+    pub fn center(&self) -> Option<(f64, f64, u8)> {
+        match self.metadata_numbers("center")?[..] {
+            [lon, lat, zoom] => Some((lon, lat, zoom as u8)),
+            _ => None,
+        }
+    }
+    /// `vector_layers` of the `json` metadata entry
+    pub fn vector_layers(&self) -> Option<serde_json::Value> {
+        let json: serde_json::Value = serde_json::from_str(self.metadata.get("json")?).ok()?;
+        json.get("vector_layers").cloned()
+    }
+    /// Stored tile data at zoom level, column and row in TMS scheme
+    pub fn tile(&self, zoom: u8, xtile: u32, ytile: u32) -> Result<Option<Vec<u8>>, String> {
+        self.db
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT tile_data FROM tiles \
+                     WHERE zoom_level = ?1 AND tile_column = ?2 AND tile_row = ?3",
+                    params![zoom, xtile, ytile],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())
+            })
+            .map_err(|e| format!("{}: {}", self.path, e))
+    }
+}
@@ -0,0 +1,56 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::datasource::mbtiles_reader::MbtilesReader;
+use crate::mvt::tile::TileEncoding;
+use tile_grid::Extent;
+
+#[test]
+fn test_metadata() {
+    let mbtiles = MbtilesReader::open("../data/places.mbtiles").unwrap();
+    assert_eq!(mbtiles.len(), 2);
+    assert_eq!(mbtiles.minzoom(), Some(0));
+    assert_eq!(mbtiles.maxzoom(), Some(1));
+    assert_eq!(
+        mbtiles.bounds(),
+        Some(Extent {
+            minx: 5.9,
+            miny: 45.8,
+            maxx: 10.5,
+            maxy: 47.8
+        })
+    );
+    assert_eq!(mbtiles.center(), Some((7.44, 46.95, 1)));
+    assert_eq!(
+        mbtiles.metadata().get("attribution"),
+        Some(&"Test data".to_string())
+    );
+    assert_eq!(
+        mbtiles.vector_layers(),
+        Some(json!([{"id": "places", "fields": {"name": "String"}, "minzoom": 0, "maxzoom": 1}]))
+    );
+}
+
+#[test]
+fn test_tiles() {
+    for path in &["../data/places.mbtiles", "../data/places_dedup.mbtiles"] {
+        let mbtiles = MbtilesReader::open(path).unwrap();
+        let tile = mbtiles.tile(0, 0, 0).unwrap().unwrap();
+        assert_eq!(TileEncoding::detect(&tile), TileEncoding::Gzip);
+        let tile = mbtiles.tile(1, 1, 1).unwrap().unwrap();
+        assert_eq!(TileEncoding::detect(&tile), TileEncoding::Identity);
+        assert_eq!(tile.len(), 46);
+        assert_eq!(mbtiles.tile(1, 0, 0).unwrap(), None);
+    }
+}
+
+#[test]
+fn test_open_errors() {
+    assert_eq!(
+        MbtilesReader::open("../data/places.sqlite").err(),
+        Some("../data/places.sqlite: Table 'metadata' not found".to_string())
+    );
+    assert!(MbtilesReader::open("../data/missing.mbtiles").is_err());
+}
@@ -3,11 +3,144 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+mod csv_ds;
+#[cfg(test)]
+mod csv_test;
 mod datasource;
+mod db_url;
+#[cfg(feature = "duckdb")]
+mod duckdb_ds;
+#[cfg(all(test, feature = "duckdb"))]
+mod duckdb_test;
+#[cfg(feature = "http")]
+mod elastic_client;
+#[cfg(feature = "http")]
+mod elastic_ds;
+#[cfg(all(test, feature = "http"))]
+mod elastic_test;
+mod flatgeobuf_ds;
+#[cfg(test)]
+mod flatgeobuf_test;
+mod geojson_ds;
+#[cfg(test)]
+mod geojson_test;
+#[cfg(feature = "sqlite")]
+mod gpkg_ds;
+#[cfg(all(test, feature = "sqlite"))]
+mod gpkg_test;
+#[cfg(feature = "http")]
+mod http_client;
+#[cfg(all(test, feature = "http"))]
+mod http_client_test;
+#[cfg(feature = "sqlite")]
+mod mbtiles_reader;
+#[cfg(all(test, feature = "sqlite"))]
+mod mbtiles_test;
+#[cfg(feature = "mongodb")]
+mod mongo_ds;
+#[cfg(all(test, feature = "mongodb"))]
+mod mongo_test;
+#[cfg(feature = "mssql")]
+mod mssql_client;
+#[cfg(feature = "mssql")]
+mod mssql_ds;
+#[cfg(all(test, feature = "mssql"))]
+mod mssql_test;
+#[cfg(feature = "mysql")]
+mod mysql_client;
+#[cfg(feature = "mysql")]
+mod mysql_ds;
+#[cfg(all(test, feature = "mysql"))]
+mod mysql_test;
+#[cfg(feature = "http")]
+mod oracle_ds;
+#[cfg(all(test, feature = "http"))]
+mod oracle_test;
+#[cfg(feature = "http")]
+mod ords_client;
+mod osm_pbf_ds;
+mod osm_pbf_reader;
+#[cfg(test)]
+mod osm_pbf_test;
+mod pg_pool;
+mod pg_tls;
+mod pmtiles_reader;
+mod pool;
 mod postgis_ds;
 mod postgis_fields;
 #[cfg(test)]
 mod postgis_test;
+#[cfg(any(
+    feature = "duckdb",
+    feature = "http",
+    feature = "mongodb",
+    feature = "mssql",
+    feature = "mysql"
+))]
+mod query_params;
+mod replay_ds;
+#[cfg(test)]
+mod replay_test;
+mod reproject;
+mod rtree;
+#[cfg(feature = "http")]
+mod sdo_geometry;
+mod shapefile_ds;
+mod shapefile_reader;
+#[cfg(test)]
+mod shapefile_test;
+#[cfg(feature = "sqlite")]
+mod spatialite_ds;
+#[cfg(all(test, feature = "sqlite"))]
+mod spatialite_test;
+#[cfg(feature = "sqlite")]
+mod sqlite_db;
+#[cfg(test)]
+mod test_helpers;
+#[cfg(feature = "http")]
+mod upstream_ds;
+#[cfg(all(test, feature = "http"))]
+mod upstream_test;
+#[cfg(feature = "http")]
+mod wfs_client;
+#[cfg(feature = "http")]
+mod wfs_ds;
+#[cfg(all(test, feature = "http"))]
+mod wfs_test;
+pub(crate) mod wkb_reader;
 
-pub use self::datasource::{DatasourceType, DummyDatasource};
-pub use self::postgis_ds::PostgisDatasource;
+pub use self::csv_ds::CsvDatasource;
+pub use self::datasource::{DatasourceInput, DatasourceType, DummyDatasource};
+pub use self::db_url::DbKind;
+#[cfg(feature = "duckdb")]
+pub use self::duckdb_ds::DuckdbDatasource;
+#[cfg(feature = "http")]
+pub use self::elastic_ds::ElasticDatasource;
+pub use self::flatgeobuf_ds::FlatGeobufDatasource;
+pub use self::geojson_ds::GeoJsonDatasource;
+#[cfg(feature = "sqlite")]
+pub use self::gpkg_ds::GpkgDatasource;
+#[cfg(feature = "sqlite")]
+pub use self::mbtiles_reader::MbtilesReader;
+#[cfg(feature = "mongodb")]
+pub use self::mongo_ds::MongoDatasource;
+#[cfg(feature = "mssql")]
+pub use self::mssql_ds::MssqlDatasource;
+#[cfg(feature = "mysql")]
+pub use self::mysql_ds::MysqlDatasource;
+#[cfg(feature = "http")]
+pub use self::oracle_ds::OracleDatasource;
+pub use self::osm_pbf_ds::OsmPbfDatasource;
+pub use self::pg_pool::{PgPool, PooledClient};
+pub use self::pg_tls::{TlsMode, TlsSettings};
+pub use self::pmtiles_reader::{zxy_to_tile_id, PmtilesReader};
+pub use self::pool::PoolSettings;
+pub use self::postgis_ds::{PostgisDatasource, PostgisFeatures};
+pub use self::replay_ds::ReplayDatasource;
+pub use self::shapefile_ds::ShapefileDatasource;
+#[cfg(feature = "sqlite")]
+pub use self::spatialite_ds::SpatialiteDatasource;
+#[cfg(feature = "http")]
+pub use self::upstream_ds::UpstreamDatasource;
+#[cfg(feature = "http")]
+pub use self::wfs_ds::WfsDatasource;
@@ -0,0 +1,147 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::layer::{Layer, LayerQuery};
+use crate::datasource::mssql_client::{client_config, MssqlValue};
+use crate::datasource::mssql_ds::{replace_params, MssqlDatasource};
+use tiberius::numeric::Numeric;
+use tiberius::{ColumnData, Uuid};
+use tile_grid::{Extent, Grid};
+
+#[test]
+fn test_client_config() {
+    let config = client_config("mssql://user:ab@localhost/gis").unwrap();
+    assert_eq!(config.get_addr(), "localhost:1433");
+    let config =
+        client_config("sqlserver://db.example.com:1434/gis?trustServerCertificate=true").unwrap();
+    assert_eq!(config.get_addr(), "db.example.com:1434");
+    assert_eq!(
+        client_config("postgresql://localhost/gis").err(),
+        Some("Invalid connection URL 'postgresql://localhost/gis'".to_string())
+    );
+}
+
+#[test]
+fn test_values() {
+    assert_eq!(
+        MssqlValue::from(ColumnData::I32(Some(-5))),
+        MssqlValue::Int(-5)
+    );
+    assert_eq!(
+        MssqlValue::from(ColumnData::U8(Some(200))),
+        MssqlValue::Int(200)
+    );
+    assert_eq!(MssqlValue::from(ColumnData::I64(None)), MssqlValue::Null);
+    assert_eq!(
+        MssqlValue::from(ColumnData::Numeric(Some(Numeric::new_with_scale(
+            -12345, 2
+        )))),
+        MssqlValue::Float(-123.45)
+    );
+    assert_eq!(
+        MssqlValue::from(ColumnData::String(Some("Bern".into()))),
+        MssqlValue::String("Bern".to_string())
+    );
+    assert_eq!(
+        MssqlValue::from(ColumnData::Binary(Some(vec![1, 2, 3].into()))),
+        MssqlValue::Binary(vec![1, 2, 3])
+    );
+    assert_eq!(
+        MssqlValue::from(ColumnData::Guid(Some(Uuid::nil()))),
+        MssqlValue::Unsupported
+    );
+}
+
+#[test]
+fn test_build_query() {
+    let ds = MssqlDatasource::new("mssql://localhost/gis", None);
+    let mut layer = Layer::new("places");
+    layer.table_name = Some("[places]".to_string());
+    layer.geometry_field = Some("geom".to_string());
+    let columns = vec!["name".to_string(), "population".to_string()];
+    assert_eq!(
+        ds.build_query(&layer, None, &columns),
+        Some(
+            "SELECT _q.[geom].STAsBinary() AS [_wkb], _q.[name], _q.[population] FROM [places] AS _q WHERE _q.[geom].Filter(!bbox!) = 1"
+                .to_string()
+        )
+    );
+
+    layer.filter = Some("population > 10000".to_string());
+    layer.order_by = Some("population DESC".to_string());
+    layer.query_limit = Some(100);
+    assert_eq!(
+        ds.build_query(&layer, None, &columns),
+        Some(
+            "SELECT TOP 100 _q.[geom].STAsBinary() AS [_wkb], _q.[name], _q.[population] FROM [places] AS _q WHERE _q.[geom].Filter(!bbox!) = 1 AND (population > 10000) ORDER BY population DESC"
+                .to_string()
+        )
+    );
+
+    layer.filter = None;
+    layer.order_by = None;
+    layer.query_limit = None;
+    layer.query = vec![LayerQuery {
+        minzoom: 0,
+        maxzoom: None,
+        simplify: None,
+        tolerance: None,
+        sql: Some("SELECT * FROM places WHERE geom.STIntersects(!bbox!) = 1".to_string()),
+    }];
+    assert_eq!(
+        ds.build_query(&layer, layer.query(10), &[]),
+        Some(
+            "SELECT _q.[geom].STAsBinary() AS [_wkb] FROM (SELECT * FROM places WHERE geom.STIntersects(!bbox!) = 1) AS _q"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_replace_params() {
+    let extent = Extent {
+        minx: 821850.9,
+        miny: 5909499.5,
+        maxx: 860986.7,
+        maxy: 5948635.3,
+    };
+    let sql = "SELECT * FROM t WHERE geom.Filter(!bbox!) = 1 AND !zoom! > 8 AND !pixel_width! < 100 AND t = !time!";
+    assert_eq!(
+        replace_params(sql, &extent, 3857, false, 10, &Grid::web_mercator(), None),
+        "SELECT * FROM t WHERE geom.Filter(geometry::STGeomFromText('POLYGON((821850.9 5909499.5,860986.7 5909499.5,860986.7 5948635.3,821850.9 5948635.3,821850.9 5909499.5))',3857)) = 1 AND 10 > 8 AND 152.87405657035254 < 100 AND t = NULL"
+    );
+    assert!(replace_params(
+        sql,
+        &extent,
+        3857,
+        false,
+        10,
+        &Grid::web_mercator(),
+        Some("2021's")
+    )
+    .ends_with("t = N'2021''s'"));
+
+    let sql = "!bbox!";
+    let extent = Extent {
+        minx: 7.0,
+        miny: 46.0,
+        maxx: 8.0,
+        maxy: 47.0,
+    };
+    assert_eq!(
+        replace_params(sql, &extent, 4326, true, 10, &Grid::web_mercator(), None),
+        "geography::STGeomFromText('POLYGON((7 46,8 46,8 47,7 47,7 46))',4326)"
+    );
+    let world = Extent {
+        minx: -180.0,
+        miny: -85.0,
+        maxx: 180.0,
+        maxy: 85.0,
+    };
+    assert_eq!(
+        replace_params(sql, &world, 4326, true, 0, &Grid::web_mercator(), None),
+        "geography::STGeomFromText('FULLGLOBE',4326)"
+    );
+}
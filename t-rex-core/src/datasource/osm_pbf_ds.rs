@@ -0,0 +1,743 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! OpenStreetMap PBF datasource with tag-to-layer mapping, loaded into memory
+
+use crate::core::config::{DatasourceCfg, OsmLayerCfg};
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::{self, GeometryType};
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::osm_pbf_reader::{read_pbf, Element, ElementFilter, MemberType};
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::rtree::RTree;
+use crate::datasource::DatasourceType;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tile_grid::{Extent, Grid};
+
+/// Offset of feature ids of areas from multipolygon relations (Overpass area id convention)
+const RELATION_AREA_ID_OFFSET: u64 = 3_600_000_000;
+
+type Ring = Vec<(f64, f64)>;
+
+enum OsmGeom {
+    Point((f64, f64)),
+    LineString(Vec<(f64, f64)>),
+    /// Polygons with exterior ring followed by holes
+    Polygons(Vec<Vec<Ring>>),
+}
+
+struct OsmFeature {
+    fid: u64,
+    tags: Vec<(String, String)>,
+    geometry: OsmGeom,
+}
+
+struct OsmLayerData {
+    features: Vec<OsmFeature>,
+    index: RTree,
+    /// Tag keys in order of appearance
+    columns: Vec<String>,
+}
+
+/// Features of all configured layers
+pub struct OsmData {
+    layers: BTreeMap<String, OsmLayerData>,
+}
+
+#[derive(Clone)]
+pub struct OsmPbfDatasource {
+    pub path: String,
+    pub layers: Vec<OsmLayerCfg>,
+    data: Option<Arc<OsmData>>,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+/// Tags match all filter conditions. An empty filter matches all tagged elements.
+fn tags_match(filter: &HashMap<String, String>, tags: &[(String, String)]) -> bool {
+    if filter.is_empty() {
+        return !tags.is_empty();
+    }
+    filter.iter().all(|(key, values)| {
+        tags.iter()
+            .any(|(k, v)| k == key && (values == "*" || values.split('|').any(|val| val == v)))
+    })
+}
+
+/// Twice the signed ring area (positive for counter-clockwise rings)
+fn ring_area(ring: &[(f64, f64)]) -> f64 {
+    ring.windows(2)
+        .map(|w| w[0].0 * w[1].1 - w[1].0 * w[0].1)
+        .sum()
+}
+
+/// Orient exterior rings counter-clockwise and holes clockwise (right-hand rule)
+fn orient_ring(mut ring: Ring, exterior: bool) -> Ring {
+    if (ring_area(&ring) > 0.0) != exterior {
+        ring.reverse();
+    }
+    ring
+}
+
+fn is_closed(ring: &[(f64, f64)]) -> bool {
+    ring.len() >= 4 && ring.first() == ring.last()
+}
+
+fn point_in_ring(point: (f64, f64), ring: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    for w in ring.windows(2) {
+        let ((x1, y1), (x2, y2)) = (w[0], w[1]);
+        if (y1 > point.1) != (y2 > point.1) && point.0 < (x2 - x1) * (point.1 - y1) / (y2 - y1) + x1
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Join way segments into closed rings. Unclosable segments are dropped.
+fn assemble_rings(mut segments: Vec<Ring>) -> Vec<Ring> {
+    let mut rings = Vec::new();
+    while let Some(mut ring) = segments.pop() {
+        while !is_closed(&ring) {
+            let end = match ring.last() {
+                Some(end) => *end,
+                None => break,
+            };
+            let next = segments
+                .iter()
+                .position(|s| s.first() == Some(&end) || s.last() == Some(&end));
+            match next {
+                Some(idx) => {
+                    let mut segment = segments.swap_remove(idx);
+                    if segment.first() != Some(&end) {
+                        segment.reverse();
+                    }
+                    ring.extend(segment.into_iter().skip(1));
+                }
+                None => break,
+            }
+        }
+        if is_closed(&ring) {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+/// Polygons from outer and inner rings of a multipolygon relation
+fn assemble_polygons(outer: Vec<Ring>, inner: Vec<Ring>) -> Vec<Vec<Ring>> {
+    let mut polygons: Vec<Vec<Ring>> = assemble_rings(outer)
+        .into_iter()
+        .map(|ring| vec![orient_ring(ring, true)])
+        .collect();
+    for ring in assemble_rings(inner) {
+        if let Some(polygon) = polygons
+            .iter_mut()
+            .find(|polygon| point_in_ring(ring[0], &polygon[0]))
+        {
+            polygon.push(orient_ring(ring, false));
+        }
+    }
+    polygons
+}
+
+impl OsmGeom {
+    fn positions(&self) -> Box<dyn Iterator<Item = &(f64, f64)> + '_> {
+        match self {
+            OsmGeom::Point(p) => Box::new(std::iter::once(p)),
+            OsmGeom::LineString(line) => Box::new(line.iter()),
+            OsmGeom::Polygons(polygons) => Box::new(polygons.iter().flatten().flatten()),
+        }
+    }
+    fn extent(&self) -> Extent {
+        let mut extent = Extent {
+            minx: f64::MAX,
+            miny: f64::MAX,
+            maxx: f64::MIN,
+            maxy: f64::MIN,
+        };
+        for &(x, y) in self.positions() {
+            extent.minx = extent.minx.min(x);
+            extent.miny = extent.miny.min(y);
+            extent.maxx = extent.maxx.max(x);
+            extent.maxy = extent.maxy.max(y);
+        }
+        extent
+    }
+    fn to_geometry(
+        &self,
+        srid: Option<i32>,
+        transform: &dyn Fn(f64, f64) -> (f64, f64),
+    ) -> GeometryType {
+        let point = |p: &(f64, f64)| {
+            let (x, y) = transform(p.0, p.1);
+            geom::Point::new(x, y, srid)
+        };
+        let line = |coords: &Ring| geom::LineString {
+            points: coords.iter().map(point).collect(),
+            srid,
+        };
+        let polygon = |rings: &Vec<Ring>| geom::Polygon {
+            rings: rings.iter().map(line).collect(),
+            srid,
+        };
+        match self {
+            OsmGeom::Point(p) => GeometryType::Point(point(p)),
+            OsmGeom::LineString(coords) => GeometryType::LineString(line(coords)),
+            OsmGeom::Polygons(polygons) if polygons.len() == 1 => {
+                GeometryType::Polygon(polygon(&polygons[0]))
+            }
+            OsmGeom::Polygons(polygons) => GeometryType::MultiPolygon(geom::MultiPolygon {
+                polygons: polygons.iter().map(polygon).collect(),
+                srid,
+            }),
+        }
+    }
+}
+
+struct LayerBuilder<'a> {
+    cfg: &'a OsmLayerCfg,
+    features: Vec<OsmFeature>,
+    columns: Vec<String>,
+}
+
+impl<'a> LayerBuilder<'a> {
+    fn add(&mut self, fid: u64, tags: &[(String, String)], geometry: OsmGeom) {
+        let tags: Vec<(String, String)> = tags
+            .iter()
+            .filter(|(k, _)| self.cfg.tags.is_empty() || self.cfg.tags.contains(k))
+            .cloned()
+            .collect();
+        for (key, _) in &tags {
+            if !self.columns.contains(key) {
+                self.columns.push(key.clone());
+            }
+        }
+        self.features.push(OsmFeature {
+            fid,
+            tags,
+            geometry,
+        });
+    }
+    fn build(self) -> (String, OsmLayerData) {
+        let extents: Vec<Extent> = self.features.iter().map(|f| f.geometry.extent()).collect();
+        let columns = if self.cfg.tags.is_empty() {
+            self.columns
+        } else {
+            self.cfg.tags.clone()
+        };
+        (
+            self.cfg.name.clone(),
+            OsmLayerData {
+                features: self.features,
+                index: RTree::new(&extents),
+                columns,
+            },
+        )
+    }
+}
+
+struct MultipolygonRelation {
+    id: i64,
+    tags: Vec<(String, String)>,
+    outer: Vec<i64>,
+    inner: Vec<i64>,
+}
+
+impl OsmData {
+    /// Read PBF file and build features of all layers
+    pub fn load(path: &str, layers: &[OsmLayerCfg]) -> Result<OsmData, String> {
+        let mut builders: Vec<LayerBuilder> = layers
+            .iter()
+            .map(|cfg| LayerBuilder {
+                cfg,
+                features: Vec::new(),
+                columns: Vec::new(),
+            })
+            .collect();
+        let has_type = |t: &str| layers.iter().any(|l| l.geometry_type == t);
+
+        // Multipolygon relations are stored after ways, collect them first
+        let mut relations = Vec::new();
+        if has_type("polygon") {
+            let filter = ElementFilter {
+                nodes: false,
+                ways: false,
+                relations: true,
+            };
+            read_pbf(path, filter, |element| {
+                if let Element::Relation { id, members, tags } = element {
+                    let multipolygon = tags.iter().any(|(k, v)| k == "type" && v == "multipolygon");
+                    let matching = layers
+                        .iter()
+                        .any(|l| l.geometry_type == "polygon" && tags_match(&l.filter, &tags));
+                    if multipolygon && matching {
+                        let ways = |inner: bool| {
+                            members
+                                .iter()
+                                .filter(|m| m.member_type == MemberType::Way)
+                                .filter(|m| (m.role == "inner") == inner)
+                                .map(|m| m.id)
+                                .collect()
+                        };
+                        relations.push(MultipolygonRelation {
+                            id,
+                            outer: ways(false),
+                            inner: ways(true),
+                            tags,
+                        });
+                    }
+                }
+            })?;
+        }
+        let member_ways: HashSet<i64> = relations
+            .iter()
+            .flat_map(|r| r.outer.iter().chain(r.inner.iter()).cloned())
+            .collect();
+
+        let mut nodes: HashMap<i64, (f64, f64)> = HashMap::new();
+        let mut way_coords: HashMap<i64, Ring> = HashMap::new();
+        let filter = ElementFilter {
+            nodes: true,
+            ways: has_type("line") || has_type("polygon"),
+            relations: false,
+        };
+        read_pbf(path, filter, |element| match element {
+            Element::Node { id, lon, lat, tags } => {
+                nodes.insert(id, (lon, lat));
+                for builder in builders.iter_mut() {
+                    if builder.cfg.geometry_type == "point"
+                        && tags_match(&builder.cfg.filter, &tags)
+                    {
+                        builder.add(id as u64, &tags, OsmGeom::Point((lon, lat)));
+                    }
+                }
+            }
+            Element::Way { id, refs, tags } => {
+                let coords: Ring = refs.iter().filter_map(|r| nodes.get(r).cloned()).collect();
+                if coords.len() < 2 {
+                    return;
+                }
+                for builder in builders.iter_mut() {
+                    if !tags_match(&builder.cfg.filter, &tags) {
+                        continue;
+                    }
+                    match builder.cfg.geometry_type.as_str() {
+                        "line" => {
+                            builder.add(id as u64, &tags, OsmGeom::LineString(coords.clone()))
+                        }
+                        "polygon" if is_closed(&coords) => builder.add(
+                            id as u64,
+                            &tags,
+                            OsmGeom::Polygons(vec![vec![orient_ring(coords.clone(), true)]]),
+                        ),
+                        _ => {}
+                    }
+                }
+                if member_ways.contains(&id) {
+                    way_coords.insert(id, coords);
+                }
+            }
+            Element::Relation { .. } => {}
+        })?;
+
+        for relation in relations {
+            let rings = |ids: &[i64]| -> Vec<Ring> {
+                ids.iter()
+                    .filter_map(|id| way_coords.get(id).cloned())
+                    .collect()
+            };
+            let polygons = assemble_polygons(rings(&relation.outer), rings(&relation.inner));
+            if polygons.is_empty() {
+                debug!("Skipping invalid multipolygon relation {}", relation.id);
+                continue;
+            }
+            let tags: Vec<(String, String)> = relation
+                .tags
+                .into_iter()
+                .filter(|(k, _)| k != "type")
+                .collect();
+            for builder in builders.iter_mut() {
+                if builder.cfg.geometry_type == "polygon" && tags_match(&builder.cfg.filter, &tags)
+                {
+                    builder.add(
+                        RELATION_AREA_ID_OFFSET + relation.id as u64,
+                        &tags,
+                        OsmGeom::Polygons(polygons.clone()),
+                    );
+                }
+            }
+        }
+
+        Ok(OsmData {
+            layers: builders.into_iter().map(|b| b.build()).collect(),
+        })
+    }
+    /// Extent of layer features
+    fn layer_extent(&self, name: &str) -> Option<Extent> {
+        self.layers.get(name)?.index.extent()
+    }
+}
+
+struct OsmPbfFeature<'a> {
+    layer: &'a Layer,
+    feature: &'a OsmFeature,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> Feature for OsmPbfFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            return Some(self.feature.fid);
+        }
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                self.feature
+                    .tags
+                    .iter()
+                    .find(|(key, _)| key == field)
+                    .map(|(_, value)| FeatureAttrValType::String(value.clone()))
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.feature
+            .tags
+            .iter()
+            .map(|(key, value)| FeatureAttr {
+                key: key.clone(),
+                value: FeatureAttrValType::String(value.clone()),
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        Ok(self.feature.geometry.to_geometry(self.srid, self.transform))
+    }
+}
+
+impl OsmPbfDatasource {
+    pub fn new(path: &str, layers: Vec<OsmLayerCfg>) -> OsmPbfDatasource {
+        OsmPbfDatasource {
+            path: path.to_string(),
+            layers,
+            data: None,
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    /// Loaded features (file is read if not connected)
+    fn data(&self) -> Result<Arc<OsmData>, String> {
+        match self.data {
+            Some(ref data) => Ok(data.clone()),
+            None => OsmData::load(&self.path, &self.layers).map(Arc::new),
+        }
+    }
+    fn layer_cfg(&self, layer: &Layer) -> Result<&OsmLayerCfg, String> {
+        let name = layer.table_name.as_ref().unwrap_or(&layer.name);
+        self.layers
+            .iter()
+            .find(|l| &l.name == name)
+            .ok_or_else(|| format!("OSM layer '{}' not configured", name))
+    }
+}
+
+impl DatasourceType for OsmPbfDatasource {
+    /// New instance with loaded features
+    fn connected(&self) -> OsmPbfDatasource {
+        let data = match self.data() {
+            Ok(data) => {
+                for (name, layer) in &data.layers {
+                    info!(
+                        "Loaded {} features of layer '{}' from '{}'",
+                        layer.features.len(),
+                        name,
+                        self.path
+                    );
+                }
+                Some(data)
+            }
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        };
+        OsmPbfDatasource {
+            path: self.path.clone(),
+            layers: self.layers.clone(),
+            data,
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        self.layers
+            .iter()
+            .map(|cfg| {
+                let mut layer = Layer::new(&cfg.name);
+                layer.table_name = Some(cfg.name.clone());
+                layer.geometry_type = Some(
+                    match cfg.geometry_type.as_str() {
+                        "point" => "POINT",
+                        "line" => "LINESTRING",
+                        _ => "POLYGON",
+                    }
+                    .to_string(),
+                );
+                layer.srid = Some(4326);
+                layer
+            })
+            .collect()
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        let columns = self.layer_cfg(layer).and_then(|cfg| {
+            if cfg.tags.is_empty() {
+                let data = self.data()?;
+                Ok(data
+                    .layers
+                    .get(&cfg.name)
+                    .map(|l| l.columns.clone())
+                    .unwrap_or_default())
+            } else {
+                Ok(cfg.tags.clone())
+            }
+        });
+        match columns {
+            Ok(columns) => columns
+                .into_iter()
+                .map(|col| (col, String::new()))
+                .collect(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let cfg = self
+            .layer_cfg(layer)
+            .map_err(|e| error!("Layer '{}': {}", layer.name, e))
+            .ok()?;
+        let extent = self
+            .data()
+            .map_err(|e| error!("Layer '{}': {}", layer.name, e))
+            .ok()?
+            .layer_extent(&cfg.name)?;
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(4326);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if let Err(e) = self.layer_cfg(layer) {
+            error!("Layer '{}': {}", layer.name, e);
+            return;
+        }
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for OSM PBF layers",
+                layer.name
+            );
+        }
+        if !layer.no_transform {
+            let srid = layer.srid.unwrap_or(4326);
+            match transformation(srid, grid_srid) {
+                Some(Some(transform)) => {
+                    info!(
+                        "Layer '{}': Reprojecting geometry to SRID {}",
+                        layer.name, grid_srid
+                    );
+                    self.geom_transform.insert(layer.name.clone(), transform);
+                }
+                Some(None) => {}
+                None => error!(
+                    "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                    layer.name, srid, grid_srid
+                ),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for OSM PBF layers",
+                layer.name
+            );
+        }
+        if layer.buffer_size.is_some() && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Clipping with buffer_size not supported for OSM PBF layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let features = self.layer_cfg(layer).and_then(|cfg| {
+            let data = self.data()?;
+            if data.layers.contains_key(&cfg.name) {
+                Ok((data, cfg.name.clone()))
+            } else {
+                Err(format!("OSM layer '{}' not loaded", cfg.name))
+            }
+        });
+        let (data, name) = match features {
+            Ok(features) => features,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return 0;
+            }
+        };
+        let layer_data = &data.layers[&name];
+        let mut bbox_extent = if let Some(pixels) = layer.buffer_size {
+            let pixel_width = grid.pixel_width(zoom);
+            let buf = f64::from(pixels) * pixel_width;
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        if layer_transform.is_some() {
+            // Spatial filter must be in layer SRS
+            let srid = layer.srid.unwrap_or(4326);
+            if let Some(Some(inverse)) = transformation(grid.srid, srid) {
+                bbox_extent = transform_extent(&bbox_extent, inverse);
+            }
+        }
+        let swap_axes = layer.swap_axes();
+        if swap_axes {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        for idx in layer_data.index.query(&bbox_extent) {
+            let feat = OsmPbfFeature {
+                layer,
+                feature: &layer_data.features[idx],
+                srid: Some(grid.srid),
+                transform: &transform,
+            };
+            read(&feat);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+                break;
+            }
+        }
+        cnt
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for OsmPbfDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        let path = ds_cfg.osm_pbf.as_ref().unwrap();
+        if ds_cfg.osm_layer.is_empty() {
+            return Err(format!("Datasource '{}': osm_layer missing", path));
+        }
+        for layer in &ds_cfg.osm_layer {
+            if !["point", "line", "polygon"].contains(&layer.geometry_type.as_str()) {
+                return Err(format!(
+                    "OSM layer '{}': geometry_type must be 'point', 'line' or 'polygon'",
+                    layer.name
+                ));
+            }
+        }
+        Ok(OsmPbfDatasource::new(path, ds_cfg.osm_layer.clone()))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "osm"
+# OpenStreetMap extract (loaded into memory)
+osm_pbf = "<filename>.osm.pbf"
+[[datasource.osm_layer]]
+name = "roads"
+# point, line or polygon
+geometry_type = "line"
+# Required tags ("*" for any value, alternatives separated by "|")
+filter = { highway = "motorway|primary|secondary" }
+# Published tags (default: all)
+tags = ["highway", "name"]
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        let mut config = format!("\n[[datasource]]\nosm_pbf = \"{}\"\n", self.path);
+        for layer in &self.layers {
+            let filter: BTreeMap<&String, &String> = layer.filter.iter().collect();
+            config.push_str(&format!(
+                "[[datasource.osm_layer]]\nname = \"{}\"\ngeometry_type = \"{}\"\nfilter = {{ {} }}\ntags = {:?}\n",
+                layer.name,
+                layer.geometry_type,
+                filter
+                    .iter()
+                    .map(|(k, v)| format!("\"{}\" = \"{}\"", k, v))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                layer.tags
+            ));
+        }
+        config
+    }
+}
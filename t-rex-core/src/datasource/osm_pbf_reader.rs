@@ -0,0 +1,457 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Reader for OpenStreetMap PBF files (https://wiki.openstreetmap.org/wiki/PBF_Format)
+
+use flate2::read::ZlibDecoder;
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+/// Maximal size of a blob header and of an uncompressed blob
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+const MAX_BLOB_SIZE: usize = 32 * 1024 * 1024;
+
+/// Supported features of OSMHeader blocks
+const SUPPORTED_FEATURES: &[&str] = &["OsmSchema-V0.6", "DenseNodes"];
+
+/// Minimal protobuf message decoder
+struct Message<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+enum FieldValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    /// Fixed 32 or 64 bit values (not used by OSM PBF)
+    Fixed,
+}
+
+impl<'a> Message<'a> {
+    fn new(data: &'a [u8]) -> Message<'a> {
+        Message { data, pos: 0 }
+    }
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or("Unexpected end of protobuf message")?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Invalid protobuf varint".to_string())
+    }
+    fn skip(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.pos + len > self.data.len() {
+            return Err("Unexpected end of protobuf message".to_string());
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+    /// Next field number and value
+    fn field(&mut self) -> Result<Option<(u64, FieldValue<'a>)>, String> {
+        if self.pos >= self.data.len() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let value = match key & 7 {
+            0 => FieldValue::Varint(self.varint()?),
+            1 => {
+                self.skip(8)?;
+                FieldValue::Fixed
+            }
+            2 => {
+                let len = self.varint()? as usize;
+                FieldValue::Bytes(self.skip(len)?)
+            }
+            5 => {
+                self.skip(4)?;
+                FieldValue::Fixed
+            }
+            wire_type => return Err(format!("Unsupported protobuf wire type {}", wire_type)),
+        };
+        Ok(Some((key >> 3, value)))
+    }
+}
+
+fn zigzag(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+/// Append values of a packed or unpacked repeated varint field
+fn push_varints(value: &FieldValue, values: &mut Vec<u64>) -> Result<(), String> {
+    match value {
+        FieldValue::Varint(v) => values.push(*v),
+        FieldValue::Bytes(bytes) => {
+            let mut packed = Message::new(bytes);
+            while packed.pos < bytes.len() {
+                values.push(packed.varint()?);
+            }
+        }
+        FieldValue::Fixed => return Err("Invalid repeated field".to_string()),
+    }
+    Ok(())
+}
+
+/// Delta decoded signed values
+fn delta_decode(values: &[u64]) -> Vec<i64> {
+    let mut acc = 0i64;
+    values
+        .iter()
+        .map(|v| {
+            acc += zigzag(*v);
+            acc
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MemberType {
+    Node,
+    Way,
+    Relation,
+}
+
+#[derive(Debug)]
+pub struct Member {
+    pub id: i64,
+    pub member_type: MemberType,
+    pub role: String,
+}
+
+/// OSM element with resolved tags
+#[derive(Debug)]
+pub enum Element {
+    Node {
+        id: i64,
+        lon: f64,
+        lat: f64,
+        tags: Vec<(String, String)>,
+    },
+    Way {
+        id: i64,
+        refs: Vec<i64>,
+        tags: Vec<(String, String)>,
+    },
+    Relation {
+        id: i64,
+        members: Vec<Member>,
+        tags: Vec<(String, String)>,
+    },
+}
+
+/// Element types to decode
+#[derive(Clone, Copy)]
+pub struct ElementFilter {
+    pub nodes: bool,
+    pub ways: bool,
+    pub relations: bool,
+}
+
+struct Block<'a> {
+    strings: Vec<&'a str>,
+    granularity: i64,
+    lat_offset: i64,
+    lon_offset: i64,
+}
+
+impl<'a> Block<'a> {
+    fn string(&self, idx: u64) -> Result<String, String> {
+        self.strings
+            .get(idx as usize)
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("Invalid string table index {}", idx))
+    }
+    fn tags(&self, keys: &[u64], vals: &[u64]) -> Result<Vec<(String, String)>, String> {
+        keys.iter()
+            .zip(vals.iter())
+            .map(|(k, v)| Ok((self.string(*k)?, self.string(*v)?)))
+            .collect()
+    }
+    fn coord(&self, offset: i64, value: i64) -> f64 {
+        1e-9 * (offset + self.granularity * value) as f64
+    }
+}
+
+fn parse_node(block: &Block, data: &[u8]) -> Result<Element, String> {
+    let (mut id, mut lat, mut lon) = (0, 0, 0);
+    let (mut keys, mut vals) = (Vec::new(), Vec::new());
+    let mut msg = Message::new(data);
+    while let Some((field, value)) = msg.field()? {
+        match (field, &value) {
+            (1, FieldValue::Varint(v)) => id = zigzag(*v),
+            (2, _) => push_varints(&value, &mut keys)?,
+            (3, _) => push_varints(&value, &mut vals)?,
+            (8, FieldValue::Varint(v)) => lat = zigzag(*v),
+            (9, FieldValue::Varint(v)) => lon = zigzag(*v),
+            _ => {}
+        }
+    }
+    Ok(Element::Node {
+        id,
+        lon: block.coord(block.lon_offset, lon),
+        lat: block.coord(block.lat_offset, lat),
+        tags: block.tags(&keys, &vals)?,
+    })
+}
+
+fn parse_dense_nodes<F>(block: &Block, data: &[u8], visit: &mut F) -> Result<(), String>
+where
+    F: FnMut(Element),
+{
+    let (mut ids, mut lats, mut lons, mut keys_vals) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    let mut msg = Message::new(data);
+    while let Some((field, value)) = msg.field()? {
+        match field {
+            1 => push_varints(&value, &mut ids)?,
+            8 => push_varints(&value, &mut lats)?,
+            9 => push_varints(&value, &mut lons)?,
+            10 => push_varints(&value, &mut keys_vals)?,
+            _ => {}
+        }
+    }
+    if lats.len() != ids.len() || lons.len() != ids.len() {
+        return Err("Invalid DenseNodes block".to_string());
+    }
+    let (ids, lats, lons) = (delta_decode(&ids), delta_decode(&lats), delta_decode(&lons));
+    // Tags of all nodes as key/value string indices, each node terminated by 0
+    let mut kv = keys_vals.iter();
+    for i in 0..ids.len() {
+        let mut tags = Vec::new();
+        while let Some(&key) = kv.next() {
+            if key == 0 {
+                break;
+            }
+            let val = kv.next().ok_or("Invalid DenseNodes tags")?;
+            tags.push((block.string(key)?, block.string(*val)?));
+        }
+        visit(Element::Node {
+            id: ids[i],
+            lon: block.coord(block.lon_offset, lons[i]),
+            lat: block.coord(block.lat_offset, lats[i]),
+            tags,
+        });
+    }
+    Ok(())
+}
+
+fn parse_way(block: &Block, data: &[u8]) -> Result<Element, String> {
+    let mut id = 0;
+    let (mut keys, mut vals, mut refs) = (Vec::new(), Vec::new(), Vec::new());
+    let mut msg = Message::new(data);
+    while let Some((field, value)) = msg.field()? {
+        match (field, &value) {
+            (1, FieldValue::Varint(v)) => id = *v as i64,
+            (2, _) => push_varints(&value, &mut keys)?,
+            (3, _) => push_varints(&value, &mut vals)?,
+            (8, _) => push_varints(&value, &mut refs)?,
+            _ => {}
+        }
+    }
+    Ok(Element::Way {
+        id,
+        refs: delta_decode(&refs),
+        tags: block.tags(&keys, &vals)?,
+    })
+}
+
+fn parse_relation(block: &Block, data: &[u8]) -> Result<Element, String> {
+    let mut id = 0;
+    let (mut keys, mut vals, mut roles, mut memids, mut types) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    let mut msg = Message::new(data);
+    while let Some((field, value)) = msg.field()? {
+        match (field, &value) {
+            (1, FieldValue::Varint(v)) => id = *v as i64,
+            (2, _) => push_varints(&value, &mut keys)?,
+            (3, _) => push_varints(&value, &mut vals)?,
+            (8, _) => push_varints(&value, &mut roles)?,
+            (9, _) => push_varints(&value, &mut memids)?,
+            (10, _) => push_varints(&value, &mut types)?,
+            _ => {}
+        }
+    }
+    let members = delta_decode(&memids)
+        .into_iter()
+        .zip(roles.iter().zip(types.iter()))
+        .map(|(id, (role, member_type))| {
+            let member_type = match member_type {
+                0 => MemberType::Node,
+                1 => MemberType::Way,
+                _ => MemberType::Relation,
+            };
+            Ok(Member {
+                id,
+                member_type,
+                role: block.string(*role)?,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(Element::Relation {
+        id,
+        members,
+        tags: block.tags(&keys, &vals)?,
+    })
+}
+
+fn parse_primitive_block<F>(data: &[u8], filter: ElementFilter, visit: &mut F) -> Result<(), String>
+where
+    F: FnMut(Element),
+{
+    let mut block = Block {
+        strings: Vec::new(),
+        granularity: 100,
+        lat_offset: 0,
+        lon_offset: 0,
+    };
+    let mut groups = Vec::new();
+    let mut msg = Message::new(data);
+    while let Some((field, value)) = msg.field()? {
+        match (field, value) {
+            (1, FieldValue::Bytes(bytes)) => {
+                let mut table = Message::new(bytes);
+                while let Some((field, value)) = table.field()? {
+                    if let (1, FieldValue::Bytes(s)) = (field, value) {
+                        block.strings.push(std::str::from_utf8(s).unwrap_or(""));
+                    }
+                }
+            }
+            (2, FieldValue::Bytes(bytes)) => groups.push(bytes),
+            (17, FieldValue::Varint(v)) => block.granularity = v as i64,
+            (19, FieldValue::Varint(v)) => block.lat_offset = v as i64,
+            (20, FieldValue::Varint(v)) => block.lon_offset = v as i64,
+            _ => {}
+        }
+    }
+    for group in groups {
+        let mut msg = Message::new(group);
+        while let Some((field, value)) = msg.field()? {
+            let bytes = match value {
+                FieldValue::Bytes(bytes) => bytes,
+                _ => continue,
+            };
+            match field {
+                1 if filter.nodes => visit(parse_node(&block, bytes)?),
+                2 if filter.nodes => parse_dense_nodes(&block, bytes, visit)?,
+                3 if filter.ways => visit(parse_way(&block, bytes)?),
+                4 if filter.relations => visit(parse_relation(&block, bytes)?),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_header_block(data: &[u8]) -> Result<(), String> {
+    let mut msg = Message::new(data);
+    while let Some((field, value)) = msg.field()? {
+        if let (4, FieldValue::Bytes(feature)) = (field, value) {
+            let feature = String::from_utf8_lossy(feature);
+            if !SUPPORTED_FEATURES.contains(&feature.as_ref()) {
+                return Err(format!("Unsupported PBF feature '{}'", feature));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Uncompressed content of a Blob message
+fn blob_data(blob: &[u8]) -> Result<Vec<u8>, String> {
+    let mut raw_size = 0;
+    let mut msg = Message::new(blob);
+    while let Some((field, value)) = msg.field()? {
+        match (field, value) {
+            (1, FieldValue::Bytes(raw)) => return Ok(raw.to_vec()),
+            (2, FieldValue::Varint(v)) => raw_size = v as usize,
+            (3, FieldValue::Bytes(zlib)) => {
+                if raw_size > MAX_BLOB_SIZE {
+                    return Err("PBF blob too large".to_string());
+                }
+                let mut data = Vec::with_capacity(raw_size);
+                ZlibDecoder::new(zlib)
+                    .take(MAX_BLOB_SIZE as u64)
+                    .read_to_end(&mut data)
+                    .map_err(|e| format!("Invalid zlib data: {}", e))?;
+                return Ok(data);
+            }
+            (4, _) | (5, _) | (6, _) | (7, _) => {
+                return Err("Unsupported PBF blob compression".to_string())
+            }
+            _ => {}
+        }
+    }
+    Err("Empty PBF blob".to_string())
+}
+
+/// Read all elements of an OSM PBF file
+pub fn read_pbf<F>(path: &str, filter: ElementFilter, mut visit: F) -> Result<(), String>
+where
+    F: FnMut(Element),
+{
+    let mut file = BufReader::new(File::open(path).map_err(|e| format!("{}: {}", path, e))?);
+    loop {
+        let mut len = [0; 4];
+        match file.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(ref e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(format!("{}: {}", path, e)),
+        }
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_HEADER_SIZE {
+            return Err(format!("{}: Invalid PBF blob header", path));
+        }
+        let mut header = vec![0; len];
+        file.read_exact(&mut header)
+            .map_err(|e| format!("{}: {}", path, e))?;
+        let (mut blob_type, mut blob_size) = (String::new(), 0);
+        let mut msg = Message::new(&header);
+        while let Some((field, value)) = msg.field()? {
+            match (field, value) {
+                (1, FieldValue::Bytes(t)) => blob_type = String::from_utf8_lossy(t).to_string(),
+                (3, FieldValue::Varint(v)) => blob_size = v as usize,
+                _ => {}
+            }
+        }
+        if blob_size > MAX_BLOB_SIZE {
+            return Err(format!("{}: PBF blob too large", path));
+        }
+        let mut blob = vec![0; blob_size];
+        file.read_exact(&mut blob)
+            .map_err(|e| format!("{}: {}", path, e))?;
+        match blob_type.as_str() {
+            "OSMHeader" => check_header_block(&blob_data(&blob)?)?,
+            "OSMData" => parse_primitive_block(&blob_data(&blob)?, filter, &mut visit)?,
+            _ => debug!("{}: Skipping unknown blob type '{}'", path, blob_type),
+        }
+    }
+}
+
+#[test]
+fn test_protobuf_decoding() {
+    assert_eq!(zigzag(0), 0);
+    assert_eq!(zigzag(1), -1);
+    assert_eq!(zigzag(4), 2);
+    assert_eq!(delta_decode(&[20, 1, 4]), vec![10, 9, 11]);
+    let mut msg = Message::new(&[0x08, 0x96, 0x01, 0x12, 0x02, 0x05, 0x07]);
+    match msg.field() {
+        Ok(Some((1, FieldValue::Varint(150)))) => {}
+        _ => panic!("Invalid varint field"),
+    }
+    let mut values = Vec::new();
+    match msg.field() {
+        Ok(Some((2, value))) => push_varints(&value, &mut values).unwrap(),
+        _ => panic!("Invalid packed field"),
+    }
+    assert_eq!(values, vec![5, 7]);
+    assert!(matches!(msg.field(), Ok(None)));
+    assert!(Message::new(&[0x08, 0x96]).field().is_err());
+}
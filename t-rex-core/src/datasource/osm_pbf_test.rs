@@ -0,0 +1,412 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::config::{DatasourceCfg, OsmLayerCfg};
+use crate::core::feature::FeatureAttrValType;
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::osm_pbf_ds::OsmPbfDatasource;
+use crate::datasource::test_helpers::{assert_point, bern_extent};
+use crate::datasource::DatasourceType;
+use flate2::{write::ZlibEncoder, Compression};
+use std::collections::HashMap;
+use std::io::Write;
+use tile_grid::Extent;
+use tile_grid::Grid;
+
+// Minimal protobuf encoder for test data
+
+fn varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn zz(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+fn varint_field(buf: &mut Vec<u8>, field: u64, v: u64) {
+    varint(buf, field << 3);
+    varint(buf, v);
+}
+
+fn bytes_field(buf: &mut Vec<u8>, field: u64, data: &[u8]) {
+    varint(buf, field << 3 | 2);
+    varint(buf, data.len() as u64);
+    buf.extend_from_slice(data);
+}
+
+fn packed_field(buf: &mut Vec<u8>, field: u64, values: &[u64]) {
+    let mut data = Vec::new();
+    for v in values {
+        varint(&mut data, *v);
+    }
+    bytes_field(buf, field, &data);
+}
+
+fn delta(values: &[i64]) -> Vec<u64> {
+    let mut prev = 0;
+    values
+        .iter()
+        .map(|v| {
+            let d = zz(v - prev);
+            prev = *v;
+            d
+        })
+        .collect()
+}
+
+fn blob(buf: &mut Vec<u8>, blob_type: &str, data: &[u8], compress: bool) {
+    let mut blob = Vec::new();
+    if compress {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        varint_field(&mut blob, 2, data.len() as u64);
+        bytes_field(&mut blob, 3, &encoder.finish().unwrap());
+    } else {
+        bytes_field(&mut blob, 1, data);
+    }
+    let mut header = Vec::new();
+    bytes_field(&mut header, 1, blob_type.as_bytes());
+    varint_field(&mut header, 3, blob.len() as u64);
+    buf.extend_from_slice(&(header.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&header);
+    buf.extend_from_slice(&blob);
+}
+
+const STRINGS: &[&str] = &[
+    "",
+    "highway",
+    "primary",
+    "name",
+    "Bundesgasse",
+    "amenity",
+    "cafe",
+    "Café Fédéral",
+    "building",
+    "yes",
+    "type",
+    "multipolygon",
+    "leisure",
+    "park",
+    "outer",
+    "inner",
+];
+
+fn sid(s: &str) -> u64 {
+    STRINGS.iter().position(|e| *e == s).unwrap() as u64
+}
+
+/// Node coordinates (lon, lat)
+const NODES: &[(i64, f64, f64)] = &[
+    (1, 7.4440, 46.9470),
+    (2, 7.4400, 46.9460),
+    (3, 7.4480, 46.9480),
+    // clockwise building
+    (4, 7.450, 46.950),
+    (5, 7.450, 46.951),
+    (6, 7.451, 46.951),
+    (7, 7.451, 46.950),
+    // park
+    (8, 7.46, 46.94),
+    (9, 7.47, 46.94),
+    (10, 7.47, 46.95),
+    (11, 7.46, 46.95),
+    (12, 7.463, 46.943),
+    (13, 7.467, 46.943),
+    (14, 7.467, 46.947),
+    (15, 7.463, 46.947),
+];
+
+fn way(id: u64, refs: &[i64], tags: &[(&str, &str)]) -> Vec<u8> {
+    let mut way = Vec::new();
+    varint_field(&mut way, 1, id);
+    packed_field(
+        &mut way,
+        2,
+        &tags.iter().map(|t| sid(t.0)).collect::<Vec<_>>(),
+    );
+    packed_field(
+        &mut way,
+        3,
+        &tags.iter().map(|t| sid(t.1)).collect::<Vec<_>>(),
+    );
+    packed_field(&mut way, 8, &delta(refs));
+    way
+}
+
+/// Test extract written to temp file `name`
+fn pbf_file(name: &str) -> String {
+    let mut header = Vec::new();
+    bytes_field(&mut header, 4, b"OsmSchema-V0.6");
+    bytes_field(&mut header, 4, b"DenseNodes");
+
+    let mut strings = Vec::new();
+    for s in STRINGS {
+        bytes_field(&mut strings, 1, s.as_bytes());
+    }
+    let mut dense = Vec::new();
+    let ids: Vec<i64> = NODES.iter().map(|n| n.0).collect();
+    let lons: Vec<i64> = NODES.iter().map(|n| (n.1 * 1e7).round() as i64).collect();
+    let lats: Vec<i64> = NODES.iter().map(|n| (n.2 * 1e7).round() as i64).collect();
+    packed_field(&mut dense, 1, &delta(&ids));
+    packed_field(&mut dense, 8, &delta(&lats));
+    packed_field(&mut dense, 9, &delta(&lons));
+    let mut keys_vals = vec![
+        sid("amenity"),
+        sid("cafe"),
+        sid("name"),
+        sid("Café Fédéral"),
+        0,
+    ];
+    keys_vals.extend(vec![0; NODES.len() - 1]);
+    packed_field(&mut dense, 10, &keys_vals);
+    let mut nodes_group = Vec::new();
+    bytes_field(&mut nodes_group, 2, &dense);
+
+    let mut ways_group = Vec::new();
+    let ways = vec![
+        way(
+            10,
+            &[2, 1, 3],
+            &[("highway", "primary"), ("name", "Bundesgasse")],
+        ),
+        way(11, &[4, 5, 6, 7, 4], &[("building", "yes")]),
+        way(12, &[8, 9, 10], &[]),
+        way(13, &[10, 11, 8], &[]),
+        way(14, &[12, 13, 14, 15, 12], &[]),
+    ];
+    for way in ways {
+        bytes_field(&mut ways_group, 3, &way);
+    }
+
+    let mut relation = Vec::new();
+    varint_field(&mut relation, 1, 20);
+    packed_field(&mut relation, 2, &[sid("type"), sid("leisure")]);
+    packed_field(&mut relation, 3, &[sid("multipolygon"), sid("park")]);
+    packed_field(
+        &mut relation,
+        8,
+        &[sid("outer"), sid("inner"), sid("outer")],
+    );
+    packed_field(&mut relation, 9, &delta(&[12, 14, 13]));
+    packed_field(&mut relation, 10, &[1, 1, 1]);
+    let mut relations_group = Vec::new();
+    bytes_field(&mut relations_group, 4, &relation);
+
+    let mut block = Vec::new();
+    bytes_field(&mut block, 1, &strings);
+    bytes_field(&mut block, 2, &nodes_group);
+    bytes_field(&mut block, 2, &ways_group);
+    bytes_field(&mut block, 2, &relations_group);
+
+    let mut pbf = Vec::new();
+    blob(&mut pbf, "OSMHeader", &header, false);
+    blob(&mut pbf, "OSMData", &block, true);
+    let path = std::env::temp_dir().join(format!("t_rex_{}.osm.pbf", name));
+    std::fs::write(&path, pbf).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn osm_layer(
+    name: &str,
+    geometry_type: &str,
+    filter: &[(&str, &str)],
+    tags: &[&str],
+) -> OsmLayerCfg {
+    OsmLayerCfg {
+        name: name.to_string(),
+        geometry_type: geometry_type.to_string(),
+        filter: filter
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+fn datasource(name: &str) -> OsmPbfDatasource {
+    OsmPbfDatasource::new(
+        &pbf_file(name),
+        vec![
+            osm_layer("pois", "point", &[("amenity", "*")], &[]),
+            osm_layer(
+                "roads",
+                "line",
+                &[("highway", "primary|secondary")],
+                &["highway", "name"],
+            ),
+            osm_layer("buildings", "polygon", &[("building", "*")], &[]),
+            osm_layer("parks", "polygon", &[("leisure", "park")], &[]),
+        ],
+    )
+}
+
+/// Feature fid, attributes and geometry
+type TestFeature = (Option<u64>, Vec<(String, FeatureAttrValType)>, GeometryType);
+
+fn layer_features(ds: &OsmPbfDatasource, name: &str) -> Vec<TestFeature> {
+    let mut layer = Layer::new(name);
+    layer.srid = Some(4326);
+    let grid = Grid::wgs84();
+    let extent = Extent {
+        minx: 7.3828,
+        miny: 46.8000,
+        maxx: 7.7343,
+        maxy: 47.0401,
+    };
+    let mut features = Vec::new();
+    ds.retrieve_features("ts", &layer, &extent, 10, &grid, |feat| {
+        let attrs = feat
+            .attributes()
+            .into_iter()
+            .map(|a| (a.key, a.value))
+            .collect();
+        features.push((feat.fid(), attrs, feat.geometry().unwrap()));
+    });
+    features
+}
+
+#[test]
+fn test_detect_layers() {
+    let ds = datasource("detect_layers");
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 4);
+    assert_eq!(layers[0].name, "pois");
+    assert_eq!(layers[0].geometry_type, Some("POINT".to_string()));
+    assert_eq!(layers[1].geometry_type, Some("LINESTRING".to_string()));
+    assert_eq!(layers[3].geometry_type, Some("POLYGON".to_string()));
+    assert_eq!(layers[3].srid, Some(4326));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        cols.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(),
+        vec!["amenity", "name"]
+    );
+
+    let extent = ds.layer_extent(&layers[3], 3857);
+    assert_eq!(
+        format!("{:.5?}", extent),
+        "Some(Extent { minx: 7.46000, miny: 46.94000, maxx: 7.47000, maxy: 46.95000 })"
+    );
+}
+
+#[test]
+fn test_retrieve_features() {
+    let ds = datasource("retrieve_features").connected();
+    let pois = layer_features(&ds, "pois");
+    assert_eq!(pois.len(), 1);
+    assert_eq!(pois[0].0, Some(1));
+    assert_eq!(
+        pois[0].1[1],
+        (
+            "name".to_string(),
+            FeatureAttrValType::String("Café Fédéral".to_string())
+        )
+    );
+    assert_eq!(
+        format!("{:.4?}", pois[0].2),
+        "Point(Point { x: 7.4440, y: 46.9470, srid: Some(4326) })"
+    );
+
+    let roads = layer_features(&ds, "roads");
+    assert_eq!(roads.len(), 1);
+    assert_eq!(roads[0].0, Some(10));
+    match roads[0].2 {
+        GeometryType::LineString(ref line) => assert_eq!(line.points.len(), 3),
+        ref geom => panic!("Unexpected geometry {:?}", geom),
+    }
+
+    // Exterior ring oriented counter-clockwise
+    let buildings = layer_features(&ds, "buildings");
+    assert_eq!(buildings.len(), 1);
+    match buildings[0].2 {
+        GeometryType::Polygon(ref polygon) => {
+            let p = &polygon.rings[0].points[1];
+            assert_eq!(format!("{:.5} {:.5}", p.x, p.y), "7.45100 46.95000");
+        }
+        ref geom => panic!("Unexpected geometry {:?}", geom),
+    }
+
+    // Multipolygon relation with hole, assembled from two outer ways
+    let parks = layer_features(&ds, "parks");
+    assert_eq!(parks.len(), 1);
+    assert_eq!(parks[0].0, Some(3_600_000_020));
+    assert_eq!(
+        parks[0].1,
+        vec![(
+            "leisure".to_string(),
+            FeatureAttrValType::String("park".to_string())
+        )]
+    );
+    match parks[0].2 {
+        GeometryType::Polygon(ref polygon) => {
+            assert_eq!(polygon.rings.len(), 2);
+            assert_eq!(polygon.rings[0].points.len(), 5);
+        }
+        ref geom => panic!("Unexpected geometry {:?}", geom),
+    }
+}
+
+#[test]
+fn test_reprojection() {
+    let mut layer = Layer::new("pois");
+    layer.srid = Some(4326);
+    layer.fid_field = Some("name".to_string());
+    let grid = Grid::web_mercator();
+    let mut ds = datasource("reprojection").connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut reccnt = 0;
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        assert_point(feat.geometry(), (828662.29, 5933427.41));
+        assert!(feat.fid().is_some());
+        reccnt += 1;
+    });
+    assert_eq!(reccnt, 1);
+}
+
+#[test]
+fn test_from_config() {
+    let cfg: DatasourceCfg = toml::from_str(
+        r#"
+        osm_pbf = "extract.osm.pbf"
+        [[osm_layer]]
+        name = "roads"
+        geometry_type = "line"
+        filter = { highway = "*" }
+        "#,
+    )
+    .unwrap();
+    let ds = OsmPbfDatasource::from_config(&cfg).unwrap();
+    assert_eq!(
+        ds.gen_runtime_config(),
+        "\n[[datasource]]\nosm_pbf = \"extract.osm.pbf\"\n[[datasource.osm_layer]]\nname = \"roads\"\ngeometry_type = \"line\"\nfilter = { \"highway\" = \"*\" }\ntags = []\n"
+    );
+
+    let cfg: DatasourceCfg = toml::from_str(
+        r#"
+        osm_pbf = "extract.osm.pbf"
+        [[osm_layer]]
+        name = "roads"
+        geometry_type = "way"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        OsmPbfDatasource::from_config(&cfg).err(),
+        Some("OSM layer 'roads': geometry_type must be 'point', 'line' or 'polygon'".to_string())
+    );
+
+    let cfg: DatasourceCfg = toml::from_str(r#"osm_pbf = "extract.osm.pbf""#).unwrap();
+    assert!(OsmPbfDatasource::from_config(&cfg).is_err());
+    assert!(OsmPbfDatasource::new("missing.osm.pbf", Vec::new())
+        .connected()
+        .detect_layers(true)
+        .is_empty());
+}
@@ -0,0 +1,291 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Connection pool of asynchronous tokio-postgres clients
+
+use crate::datasource::pg_tls::MakeTlsConnector;
+use crate::datasource::pool::PoolSettings;
+use futures::executor::block_on;
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+use std::ops::{Deref, DerefMut};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::runtime::Runtime;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_postgres::{AsyncMessage, Client, Config, Connection, NoTls};
+
+/// Delay before a lost notification listener connects again
+const LISTEN_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+struct PoolInner {
+    config: Config,
+    tls_connector: Option<MakeTlsConnector>,
+    settings: PoolSettings,
+    max_size: u16,
+    /// Runtime driving the connections, independent of the caller's executor
+    runtime: Runtime,
+    /// Idle clients with the time they were returned to the pool
+    idle: Mutex<Vec<(Client, Instant)>>,
+    slots: Arc<Semaphore>,
+}
+
+/// Pool of tokio-postgres clients. Connections are driven by an own runtime, so
+/// clients can be used from any executor or blocking with `get_blocking`.
+#[derive(Clone)]
+pub struct PgPool {
+    inner: Arc<PoolInner>,
+}
+
+/// Client of a `PgPool`, returned to the pool on drop
+pub struct PooledClient {
+    client: Option<Client>,
+    pool: Arc<PoolInner>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PgPool {
+    pub fn new(
+        config: Config,
+        tls_connector: Option<MakeTlsConnector>,
+        max_size: u16,
+        settings: PoolSettings,
+    ) -> Result<PgPool, String> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("t-rex-postgres")
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let max_size = max_size.max(1);
+        Ok(PgPool {
+            inner: Arc::new(PoolInner {
+                config,
+                tls_connector,
+                settings,
+                max_size,
+                runtime,
+                idle: Mutex::new(Vec::with_capacity(max_size as usize)),
+                slots: Arc::new(Semaphore::new(max_size as usize)),
+            }),
+        })
+    }
+    /// New pool with the same connection parameters and settings, but a different size
+    pub fn with_max_size(&self, max_size: u16) -> Result<PgPool, String> {
+        PgPool::new(
+            self.inner.config.clone(),
+            self.inner.tls_connector.clone(),
+            max_size,
+            self.inner.settings.clone(),
+        )
+    }
+    pub fn max_size(&self) -> u16 {
+        self.inner.max_size
+    }
+    /// Open connections up to the configured number of idle connections (at least one)
+    pub fn fill(&self) -> Result<(), String> {
+        let min_idle = self.inner.settings.min_idle(self.inner.max_size).max(1) as usize;
+        let missing = min_idle.saturating_sub(self.idle());
+        let clients = block_on(join_all((0..missing).map(|_| self.get())));
+        clients
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map(|_| ())
+    }
+    /// Wait for a free connection. Opens a new connection, if no idle connection is available.
+    pub async fn get(&self) -> Result<PooledClient, String> {
+        let inner = self.inner.clone();
+        self.inner
+            .runtime
+            .spawn(async move {
+                let permit = tokio::time::timeout(
+                    inner.settings.acquire_timeout(),
+                    inner.slots.clone().acquire_owned(),
+                )
+                .await
+                .map_err(|_| "Timed out waiting for a database connection".to_string())?
+                .map_err(|e| e.to_string())?;
+                let client = match inner.take_idle() {
+                    Some(client) => client,
+                    None => inner.connect().await?,
+                };
+                Ok(PooledClient {
+                    client: Some(client),
+                    pool: inner,
+                    _permit: permit,
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())?
+    }
+    /// Wait for a free connection, blocking the current thread
+    pub fn get_blocking(&self) -> Result<PooledClient, String> {
+        block_on(self.get())
+    }
+    /// Number of open connections not in use
+    pub fn idle(&self) -> usize {
+        self.inner.idle.lock().unwrap().len()
+    }
+    /// Subscribe to a notification channel with a dedicated connection. Payloads are passed
+    /// to `notify` on an own thread. The subscription is renewed after connection losses.
+    pub fn listen<F>(&self, channel: &str, notify: F) -> Result<(), String>
+    where
+        F: Fn(&str) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<String>();
+        thread::Builder::new()
+            .name("t-rex-notify".to_string())
+            .spawn(move || {
+                for payload in receiver {
+                    notify(&payload);
+                }
+            })
+            .map_err(|e| e.to_string())?;
+        let inner = self.inner.clone();
+        let listen = format!("LISTEN \"{}\"", channel.replace('"', "\"\""));
+        self.inner.runtime.spawn(async move {
+            loop {
+                if let Err(e) = inner.listen(&listen, sender.clone()).await {
+                    warn!("Postgres notification listener: {}", e);
+                }
+                tokio::time::sleep(LISTEN_RECONNECT_DELAY).await;
+            }
+        });
+        Ok(())
+    }
+}
+
+impl PoolInner {
+    /// Most recently used idle client. Drops closed clients and clients idle for longer
+    /// than the idle timeout, keeping `min_idle` clients open.
+    fn take_idle(&self) -> Option<Client> {
+        let mut idle = self.idle.lock().unwrap();
+        idle.retain(|(client, _)| !client.is_closed());
+        let min_idle = self.settings.min_idle(self.max_size) as usize;
+        let timeout = self.settings.idle_timeout();
+        while idle.len() > min_idle && idle[0].1.elapsed() >= timeout {
+            idle.remove(0);
+        }
+        idle.pop().map(|(client, _)| client)
+    }
+    /// Open connection and spawn its connection task
+    async fn connect(&self) -> Result<Client, String> {
+        match self.tls_connector {
+            Some(ref tls_connector) => {
+                let (client, connection) = self
+                    .config
+                    .connect(tls_connector.clone())
+                    .await
+                    .map_err(|e| e.to_string())?;
+                tokio::spawn(async move {
+                    if let Err(e) = connection.await {
+                        warn!("Postgres connection error: {}", e);
+                    }
+                });
+                Ok(client)
+            }
+            None => {
+                let (client, connection) = self
+                    .config
+                    .connect(NoTls)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                tokio::spawn(async move {
+                    if let Err(e) = connection.await {
+                        warn!("Postgres connection error: {}", e);
+                    }
+                });
+                Ok(client)
+            }
+        }
+    }
+}
+
+impl PoolInner {
+    /// Open connection executing `listen` and forward notifications until it is closed
+    async fn listen(&self, listen: &str, sender: mpsc::Sender<String>) -> Result<(), String> {
+        match self.tls_connector {
+            Some(ref tls_connector) => {
+                let (client, connection) = self
+                    .config
+                    .connect(tls_connector.clone())
+                    .await
+                    .map_err(|e| e.to_string())?;
+                forward_notifications(client, connection, listen, sender).await
+            }
+            None => {
+                let (client, connection) = self
+                    .config
+                    .connect(NoTls)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                forward_notifications(client, connection, listen, sender).await
+            }
+        }
+    }
+}
+
+async fn forward_notifications<S, T>(
+    client: Client,
+    mut connection: Connection<S, T>,
+    listen: &str,
+    sender: mpsc::Sender<String>,
+) -> Result<(), String>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    // The connection is driven by polling its messages
+    let messages = tokio::spawn(async move {
+        let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(notification)) => {
+                    if sender.send(notification.payload().to_string()).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        Err("connection closed".to_string())
+    });
+    client
+        .batch_execute(listen)
+        .await
+        .map_err(|e| e.to_string())?;
+    info!("Postgres notification listener: {}", listen);
+    messages.await.map_err(|e| e.to_string())?
+}
+
+impl Deref for PooledClient {
+    type Target = Client;
+    fn deref(&self) -> &Client {
+        self.client.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        self.client.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledClient {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if !client.is_closed() {
+                self.pool
+                    .idle
+                    .lock()
+                    .unwrap()
+                    .push((client, Instant::now()));
+            }
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! TLS settings of PostgreSQL connections (libpq `sslmode`, `sslrootcert`, `sslcert`)
+//!
+//! Connections are encrypted with native-tls (feature `native-tls`) or rustls
+//! (feature `rustls`). Builds without both features only support unencrypted connections.
+
+#[cfg(feature = "native-tls")]
+use native_tls::{Certificate, Identity, TlsConnector};
+#[cfg(feature = "native-tls")]
+pub use postgres_native_tls::MakeTlsConnector;
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+use std::fs;
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+pub use tokio_postgres::NoTls as MakeTlsConnector;
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+pub use tokio_postgres_rustls::MakeRustlsConnect as MakeTlsConnector;
+
+/// libpq SSL modes
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TlsMode {
+    Disable,
+    Allow,
+    Prefer,
+    /// Encrypted without certificate verification (verify-ca with `sslrootcert`)
+    Require,
+    /// Server certificate signed by trusted CA
+    VerifyCa,
+    /// Server certificate signed by trusted CA and matching host name
+    VerifyFull,
+}
+
+/// TLS options of a connection string, which are not handled by tokio-postgres
+#[derive(Clone, PartialEq, Debug)]
+pub struct TlsSettings {
+    pub mode: TlsMode,
+    /// CA certificates (PEM or DER)
+    pub root_cert: Option<String>,
+    /// Client certificate with key as PKCS#12 archive (.p12/.pfx) with native-tls,
+    /// PEM certificate with key in `client_key` with rustls
+    pub client_cert: Option<String>,
+    /// PEM private key of client certificate (rustls)
+    pub client_key: Option<String>,
+    /// Password of PKCS#12 archive (native-tls)
+    pub password: Option<String>,
+}
+
+const TLS_OPTIONS: &[&str] = &["sslmode", "sslrootcert", "sslcert", "sslkey", "sslpassword"];
+
+impl TlsSettings {
+    /// Split TLS options from connection URL or key-value connection string.
+    /// Returns connection string without TLS options and the TLS settings.
+    pub fn from_connection_string(conn: &str) -> Result<(String, TlsSettings), String> {
+        let (conn, options) =
+            if conn.starts_with("postgres://") || conn.starts_with("postgresql://") {
+                split_url_options(conn)
+            } else {
+                split_key_value_options(conn)
+            };
+        let mut settings = TlsSettings {
+            mode: TlsMode::Prefer,
+            root_cert: None,
+            client_cert: None,
+            client_key: None,
+            password: None,
+        };
+        for (key, value) in options {
+            match key.as_str() {
+                "sslmode" => {
+                    settings.mode = match value.as_str() {
+                        "disable" => TlsMode::Disable,
+                        "allow" => TlsMode::Allow,
+                        "prefer" => TlsMode::Prefer,
+                        "require" => TlsMode::Require,
+                        "verify-ca" => TlsMode::VerifyCa,
+                        "verify-full" => TlsMode::VerifyFull,
+                        _ => return Err(format!("Invalid sslmode '{}'", value)),
+                    }
+                }
+                "sslrootcert" => settings.root_cert = Some(value),
+                "sslcert" => settings.client_cert = Some(value),
+                "sslkey" => settings.client_key = Some(value),
+                "sslpassword" => settings.password = Some(value),
+                _ => {}
+            }
+        }
+        Ok((conn, settings))
+    }
+    /// Connections must be encrypted
+    pub fn required(&self) -> bool {
+        matches!(
+            self.mode,
+            TlsMode::Require | TlsMode::VerifyCa | TlsMode::VerifyFull
+        )
+    }
+    /// TLS connector verifying the server certificate according to the SSL mode
+    #[cfg(feature = "native-tls")]
+    pub fn connector(&self) -> Result<MakeTlsConnector, String> {
+        let mut builder = TlsConnector::builder();
+        if let Some(ref path) = self.root_cert {
+            for cert in read_certificates(path)? {
+                builder.add_root_certificate(cert);
+            }
+        }
+        match self.mode {
+            TlsMode::VerifyFull => {}
+            TlsMode::VerifyCa => {
+                builder.danger_accept_invalid_hostnames(true);
+            }
+            _ if self.root_cert.is_some() => {
+                builder.danger_accept_invalid_hostnames(true);
+            }
+            _ => {
+                builder.danger_accept_invalid_certs(true);
+            }
+        }
+        if let Some(ref path) = self.client_cert {
+            builder.identity(self.client_identity(path)?);
+        }
+        let connector = builder.build().map_err(|e| e.to_string())?;
+        Ok(MakeTlsConnector::new(connector))
+    }
+    #[cfg(feature = "native-tls")]
+    fn client_identity(&self, path: &str) -> Result<Identity, String> {
+        let lower = path.to_lowercase();
+        if !(lower.ends_with(".p12") || lower.ends_with(".pfx")) {
+            return Err(format!(
+                "Client certificate {}: expected PKCS#12 archive (.p12), e.g. created with `openssl pkcs12 -export -in {} -inkey {} -out client.p12`",
+                path,
+                path,
+                self.client_key.as_deref().unwrap_or("client.key")
+            ));
+        }
+        let der = fs::read(path).map_err(|e| format!("Client certificate {}: {}", path, e))?;
+        Identity::from_pkcs12(&der, self.password.as_deref().unwrap_or(""))
+            .map_err(|e| format!("Client certificate {}: {}", path, e))
+    }
+    /// TLS connector verifying the server certificate according to the SSL mode
+    #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+    pub fn connector(&self) -> Result<MakeTlsConnector, String> {
+        use rustls::pki_types::pem::PemObject;
+        use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+        use std::sync::Arc;
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut roots = rustls::RootCertStore::empty();
+        match self.root_cert {
+            Some(ref path) => {
+                for cert in read_der_certificates(path)? {
+                    roots
+                        .add(cert)
+                        .map_err(|e| format!("Root certificate {}: {}", path, e))?;
+                }
+            }
+            None if self.mode == TlsMode::VerifyFull || self.mode == TlsMode::VerifyCa => {
+                let native = rustls_native_certs::load_native_certs();
+                roots.add_parsable_certificates(native.certs);
+            }
+            None => {}
+        }
+        let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| e.to_string())?;
+        let builder = match self.mode {
+            TlsMode::VerifyFull => builder.with_root_certificates(roots),
+            _ => {
+                let webpki = if self.mode == TlsMode::VerifyCa || self.root_cert.is_some() {
+                    let verifier = rustls::client::WebPkiServerVerifier::builder_with_provider(
+                        Arc::new(roots),
+                        provider.clone(),
+                    )
+                    .build()
+                    .map_err(|e| e.to_string())?;
+                    Some(verifier)
+                } else {
+                    None
+                };
+                builder
+                    .dangerous()
+                    .with_custom_certificate_verifier(Arc::new(
+                        rustls_verifier::NoHostnameVerifier { webpki, provider },
+                    ))
+            }
+        };
+        let config = match self.client_cert {
+            Some(ref path) => {
+                let certs = CertificateDer::pem_file_iter(path)
+                    .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+                    .map_err(|e| format!("Client certificate {}: {}", path, e))?;
+                let key_path = self.client_key.as_ref().ok_or_else(|| {
+                    format!("Client certificate {}: private key (sslkey) missing", path)
+                })?;
+                let key = PrivateKeyDer::from_pem_file(key_path)
+                    .map_err(|e| format!("Client key {}: {}", key_path, e))?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .map_err(|e| format!("Client certificate {}: {}", path, e))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        Ok(MakeTlsConnector::new(config))
+    }
+    /// Encrypted connections are not supported in this build
+    #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+    pub fn connector(&self) -> Result<MakeTlsConnector, String> {
+        Err(
+            "TLS connections not supported in builds without feature 'native-tls' or 'rustls'"
+                .to_string(),
+        )
+    }
+}
+
+/// Certificates of a PEM bundle or a single DER certificate
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+fn read_der_certificates(
+    path: &str,
+) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, String> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::CertificateDer;
+
+    let data = fs::read(path).map_err(|e| format!("Root certificate {}: {}", path, e))?;
+    if !String::from_utf8_lossy(&data).contains("-----BEGIN CERTIFICATE-----") {
+        return Ok(vec![CertificateDer::from(data)]);
+    }
+    CertificateDer::pem_slice_iter(&data)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Root certificate {}: {}", path, e))
+}
+
+/// Server certificate verification without host name check (libpq `verify-ca`, `require`)
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+mod rustls_verifier {
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::client::WebPkiServerVerifier;
+    use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use rustls::{CertificateError, DigitallySignedStruct, Error, SignatureScheme};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    pub struct NoHostnameVerifier {
+        /// Certificate chain verifier, accepting any certificate if `None`
+        pub webpki: Option<Arc<WebPkiServerVerifier>>,
+        pub provider: Arc<CryptoProvider>,
+    }
+
+    impl ServerCertVerifier for NoHostnameVerifier {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            intermediates: &[CertificateDer<'_>],
+            server_name: &ServerName<'_>,
+            ocsp_response: &[u8],
+            now: UnixTime,
+        ) -> Result<ServerCertVerified, Error> {
+            let webpki = match self.webpki {
+                Some(ref webpki) => webpki,
+                None => return Ok(ServerCertVerified::assertion()),
+            };
+            match webpki.verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                ocsp_response,
+                now,
+            ) {
+                Err(Error::InvalidCertificate(CertificateError::NotValidForName))
+                | Err(Error::InvalidCertificate(CertificateError::NotValidForNameContext {
+                    ..
+                })) => Ok(ServerCertVerified::assertion()),
+                result => result,
+            }
+        }
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            verify_tls12_signature(
+                message,
+                cert,
+                dss,
+                &self.provider.signature_verification_algorithms,
+            )
+        }
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            verify_tls13_signature(
+                message,
+                cert,
+                dss,
+                &self.provider.signature_verification_algorithms,
+            )
+        }
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.provider
+                .signature_verification_algorithms
+                .supported_schemes()
+        }
+    }
+}
+
+/// Certificates of a PEM bundle or a single DER certificate
+#[cfg(feature = "native-tls")]
+fn read_certificates(path: &str) -> Result<Vec<Certificate>, String> {
+    let data = fs::read(path).map_err(|e| format!("Root certificate {}: {}", path, e))?;
+    let text = String::from_utf8_lossy(&data);
+    const END: &str = "-----END CERTIFICATE-----";
+    if !text.contains(END) {
+        return Certificate::from_der(&data)
+            .map(|cert| vec![cert])
+            .map_err(|e| format!("Root certificate {}: {}", path, e));
+    }
+    text.split_inclusive(END)
+        .filter(|block| block.contains("-----BEGIN CERTIFICATE-----"))
+        .map(|block| {
+            Certificate::from_pem(block.trim().as_bytes())
+                .map_err(|e| format!("Root certificate {}: {}", path, e))
+        })
+        .collect()
+}
+
+fn split_url_options(url: &str) -> (String, Vec<(String, String)>) {
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, query),
+        None => return (url.to_string(), Vec::new()),
+    };
+    let mut options = Vec::new();
+    let mut params = Vec::new();
+    for param in query.split('&') {
+        let (key, value) = param.split_once('=').unwrap_or((param, ""));
+        if TLS_OPTIONS.contains(&key) {
+            options.push((key.to_string(), percent_decode(value)));
+        } else {
+            params.push(param);
+        }
+    }
+    let conn = if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, params.join("&"))
+    };
+    (conn, options)
+}
+
+fn split_key_value_options(conn: &str) -> (String, Vec<(String, String)>) {
+    let mut options = Vec::new();
+    let mut params = Vec::new();
+    let mut chars = conn.chars().peekable();
+    let skip_whitespace = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+    };
+    loop {
+        skip_whitespace(&mut chars);
+        if chars.peek().is_none() {
+            break;
+        }
+        let mut key = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '=' || c.is_whitespace() {
+                break;
+            }
+            key.push(c);
+            chars.next();
+        }
+        skip_whitespace(&mut chars);
+        if chars.peek() == Some(&'=') {
+            chars.next();
+        }
+        skip_whitespace(&mut chars);
+        // Unquoted value and raw value text
+        let mut value = String::new();
+        let mut raw = String::new();
+        if chars.peek() == Some(&'\'') {
+            raw.push(chars.next().unwrap());
+            while let Some(c) = chars.next() {
+                raw.push(c);
+                match c {
+                    '\\' => {
+                        if let Some(escaped) = chars.next() {
+                            raw.push(escaped);
+                            value.push(escaped);
+                        }
+                    }
+                    '\'' => break,
+                    _ => value.push(c),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                raw.push(c);
+                value.push(c);
+                chars.next();
+            }
+        }
+        if TLS_OPTIONS.contains(&key.as_str()) {
+            options.push((key, value));
+        } else {
+            params.push(format!("{}={}", key, raw));
+        }
+    }
+    (params.join(" "), options)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match byte {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
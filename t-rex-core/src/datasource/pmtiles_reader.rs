@@ -0,0 +1,414 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Stored tiles of PMTiles v3 archives (https://github.com/protomaps/PMTiles/blob/main/spec/v3/spec.md),
+//! read from local files or with HTTP range requests.
+
+#[cfg(feature = "http")]
+use crate::datasource::db_url::ConnectParams;
+#[cfg(feature = "http")]
+use crate::datasource::http_client::HttpClient;
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "http")]
+use std::time::Duration;
+use tile_grid::Extent;
+
+const URL_SCHEMES: &[&str] = &["https", "http"];
+const HEADER_LEN: usize = 127;
+const COMPRESSION_NONE: u8 = 1;
+const COMPRESSION_GZIP: u8 = 2;
+const COMPRESSION_BROTLI: u8 = 3;
+const TILE_TYPE_MVT: u8 = 1;
+/// Directory levels of valid archives (root and leaf directories)
+const MAX_DIRECTORY_DEPTH: usize = 4;
+/// Number of decoded leaf directories kept in memory
+const LEAF_CACHE_SIZE: usize = 64;
+
+/// Tile ID on the Hilbert curve of zoom level z
+pub fn zxy_to_tile_id(z: u8, x: u32, y: u32) -> u64 {
+    let acc = ((1u64 << (2 * z as u64)) - 1) / 3;
+    let (mut tx, mut ty) = (x as u64, y as u64);
+    let mut d = 0u64;
+    let mut s = (1u64 << z) / 2;
+    while s > 0 {
+        let rx = if tx & s > 0 { 1 } else { 0 };
+        let ry = if ty & s > 0 { 1 } else { 0 };
+        d += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                tx = s - 1 - (tx & (s - 1));
+                ty = s - 1 - (ty & (s - 1));
+            }
+            std::mem::swap(&mut tx, &mut ty);
+        }
+        s /= 2;
+    }
+    acc + d
+}
+
+/// Directory entry. Entries with `run_length` 0 point to leaf directories.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Entry {
+    pub tile_id: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub run_length: u64,
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos).ok_or("Truncated PMTiles directory")?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("Invalid varint in PMTiles directory".to_string())
+}
+
+/// Decode uncompressed directory
+pub fn parse_directory(data: &[u8]) -> Result<Vec<Entry>, String> {
+    let mut pos = 0;
+    let num_entries = read_varint(data, &mut pos)? as usize;
+    if num_entries > data.len() {
+        return Err("Invalid PMTiles directory".to_string());
+    }
+    let mut entries = Vec::with_capacity(num_entries);
+    let mut last_id = 0;
+    for _ in 0..num_entries {
+        last_id += read_varint(data, &mut pos)?;
+        entries.push(Entry {
+            tile_id: last_id,
+            offset: 0,
+            length: 0,
+            run_length: 0,
+        });
+    }
+    for entry in entries.iter_mut() {
+        entry.run_length = read_varint(data, &mut pos)?;
+    }
+    for entry in entries.iter_mut() {
+        entry.length = read_varint(data, &mut pos)?;
+    }
+    for i in 0..num_entries {
+        let offset = read_varint(data, &mut pos)?;
+        entries[i].offset = if offset == 0 && i > 0 {
+            entries[i - 1].offset + entries[i - 1].length
+        } else {
+            offset.saturating_sub(1)
+        };
+    }
+    Ok(entries)
+}
+
+/// Entry containing tile id
+fn find_entry(entries: &[Entry], tile_id: u64) -> Option<&Entry> {
+    let idx = match entries.binary_search_by_key(&tile_id, |e| e.tile_id) {
+        Ok(idx) => idx,
+        Err(0) => return None,
+        Err(idx) => idx - 1,
+    };
+    let entry = &entries[idx];
+    if entry.run_length == 0 || tile_id < entry.tile_id + entry.run_length {
+        Some(entry)
+    } else {
+        None
+    }
+}
+
+/// Remote archive read with HTTP range requests
+#[cfg(feature = "http")]
+struct HttpSource {
+    http: HttpClient,
+    url: String,
+}
+
+#[cfg(feature = "http")]
+impl HttpSource {
+    fn from_url(url: &str) -> Result<HttpSource, String> {
+        let https = url.starts_with("https://");
+        let params = ConnectParams::parse(url, URL_SCHEMES, if https { 443 } else { 80 })?;
+        let after_scheme = &url[url.find("://").unwrap() + 3..];
+        let path = match after_scheme.find('/') {
+            Some(pos) => &after_scheme[pos..],
+            None => "/",
+        };
+        let http = HttpClient::new("PMTiles", &params, https, Duration::from_secs(60));
+        let url = http.url(path);
+        Ok(HttpSource { http, url })
+    }
+    fn read_range(&self, offset: u64, length: u64) -> Result<Vec<u8>, String> {
+        let range = format!("bytes={}-{}", offset, offset + length - 1);
+        let response = self.http.get(&self.url, &[("Range", range)])?;
+        match response.status {
+            206 => Ok(response.body),
+            // Downloading the whole archive for each request is not an option
+            200 => Err("PMTiles server doesn't support range requests".to_string()),
+            status => Err(format!("PMTiles request failed (HTTP {})", status)),
+        }
+    }
+}
+
+/// Remote archives are not supported in builds without HTTP client
+#[cfg(not(feature = "http"))]
+struct HttpSource;
+
+#[cfg(not(feature = "http"))]
+impl HttpSource {
+    fn from_url(url: &str) -> Result<HttpSource, String> {
+        Err(format!(
+            "{}: remote archives not supported in builds without feature 'http'",
+            url
+        ))
+    }
+    fn read_range(&self, _offset: u64, _length: u64) -> Result<Vec<u8>, String> {
+        unreachable!()
+    }
+}
+
+enum Source {
+    File(Mutex<File>),
+    Http(HttpSource),
+}
+
+impl Source {
+    fn read_range(&self, offset: u64, length: u64) -> Result<Vec<u8>, String> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+        match self {
+            Source::File(file) => {
+                let mut file = file.lock().unwrap();
+                let mut data = vec![0u8; length as usize];
+                file.seek(SeekFrom::Start(offset))
+                    .and_then(|_| file.read_exact(&mut data))
+                    .map_err(|e| format!("Error reading PMTiles archive: {}", e))?;
+                Ok(data)
+            }
+            Source::Http(http) => http.read_range(offset, length),
+        }
+    }
+}
+
+/// Archive header fields
+#[derive(Clone, PartialEq, Debug)]
+pub struct Header {
+    pub root_dir_offset: u64,
+    pub root_dir_length: u64,
+    pub metadata_offset: u64,
+    pub metadata_length: u64,
+    pub leaf_dirs_offset: u64,
+    pub tile_data_offset: u64,
+    pub internal_compression: u8,
+    pub tile_compression: u8,
+    pub tile_type: u8,
+    pub minzoom: u8,
+    pub maxzoom: u8,
+    /// Bounds in WGS84
+    pub bounds: Extent,
+    pub center_zoom: u8,
+    pub center: (f64, f64),
+}
+
+impl Header {
+    pub fn parse(data: &[u8]) -> Result<Header, String> {
+        if data.len() < HEADER_LEN || &data[0..7] != b"PMTiles" {
+            return Err("Not a PMTiles archive".to_string());
+        }
+        if data[7] != 3 {
+            return Err(format!("Unsupported PMTiles version {}", data[7]));
+        }
+        let u64_at = |pos: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[pos..pos + 8]);
+            u64::from_le_bytes(bytes)
+        };
+        let e7_at = |pos: usize| {
+            let mut bytes = [0u8; 4];
+            bytes.copy_from_slice(&data[pos..pos + 4]);
+            i32::from_le_bytes(bytes) as f64 / 10_000_000.0
+        };
+        Ok(Header {
+            root_dir_offset: u64_at(8),
+            root_dir_length: u64_at(16),
+            metadata_offset: u64_at(24),
+            metadata_length: u64_at(32),
+            leaf_dirs_offset: u64_at(40),
+            tile_data_offset: u64_at(56),
+            internal_compression: data[97],
+            tile_compression: data[98],
+            tile_type: data[99],
+            minzoom: data[100],
+            maxzoom: data[101],
+            bounds: Extent {
+                minx: e7_at(102),
+                miny: e7_at(106),
+                maxx: e7_at(110),
+                maxy: e7_at(114),
+            },
+            center_zoom: data[118],
+            center: (e7_at(119), e7_at(123)),
+        })
+    }
+}
+
+/// Read-only access to the tiles of a PMTiles archive.
+/// The root directory is read when opening the archive, leaf directories on demand.
+pub struct PmtilesReader {
+    path: String,
+    source: Source,
+    header: Header,
+    root: Vec<Entry>,
+    leaves: Mutex<HashMap<u64, Arc<Vec<Entry>>>>,
+    metadata: serde_json::Value,
+}
+
+impl fmt::Debug for PmtilesReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PmtilesReader")
+            .field("path", &self.path)
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl PmtilesReader {
+    /// Open local file or `http(s)://` URL
+    pub fn open(path: &str) -> Result<PmtilesReader, String> {
+        let source = if URL_SCHEMES
+            .iter()
+            .any(|scheme| path.starts_with(&format!("{}://", scheme)))
+        {
+            Source::Http(HttpSource::from_url(path)?)
+        } else {
+            let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+            Source::File(Mutex::new(file))
+        };
+        let err = |e: String| format!("{}: {}", path, e);
+        // Read header and root directory with one request
+        let initial = source.read_range(0, 16_384).or_else(|_| {
+            // Archive smaller than 16 KB
+            source.read_range(0, HEADER_LEN as u64)
+        });
+        let initial = initial.map_err(err)?;
+        let header = Header::parse(&initial).map_err(err)?;
+        if header.tile_type != TILE_TYPE_MVT {
+            return Err(format!(
+                "{}: unsupported tile type {} (only MVT vector tiles are supported)",
+                path, header.tile_type
+            ));
+        }
+        if ![COMPRESSION_NONE, COMPRESSION_GZIP, COMPRESSION_BROTLI]
+            .contains(&header.tile_compression)
+        {
+            return Err(format!(
+                "{}: unsupported tile compression {}",
+                path, header.tile_compression
+            ));
+        }
+        let mut reader = PmtilesReader {
+            path: path.to_string(),
+            source,
+            header,
+            root: Vec::new(),
+            leaves: Mutex::new(HashMap::new()),
+            metadata: json!({}),
+        };
+        let root_start = reader.header.root_dir_offset as usize;
+        let root_end = root_start + reader.header.root_dir_length as usize;
+        let root = match initial.get(root_start..root_end) {
+            Some(root) => root.to_vec(),
+            None => reader
+                .source
+                .read_range(reader.header.root_dir_offset, reader.header.root_dir_length)
+                .map_err(err)?,
+        };
+        reader.root = parse_directory(&reader.decompress(root).map_err(err)?).map_err(err)?;
+        let metadata = reader
+            .source
+            .read_range(reader.header.metadata_offset, reader.header.metadata_length)
+            .and_then(|data| reader.decompress(data))
+            .map_err(err)?;
+        if !metadata.is_empty() {
+            reader.metadata = serde_json::from_slice(&metadata)
+                .map_err(|e| format!("{}: invalid metadata: {}", path, e))?;
+        }
+        Ok(reader)
+    }
+    fn decompress(&self, data: Vec<u8>) -> Result<Vec<u8>, String> {
+        match self.header.internal_compression {
+            COMPRESSION_NONE => Ok(data),
+            COMPRESSION_GZIP => {
+                let mut decoded = Vec::new();
+                GzDecoder::new(&data[..])
+                    .read_to_end(&mut decoded)
+                    .map_err(|e| format!("Invalid gzip data: {}", e))?;
+                Ok(decoded)
+            }
+            compression => Err(format!("unsupported internal compression {}", compression)),
+        }
+    }
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+    /// JSON metadata
+    pub fn metadata(&self) -> &serde_json::Value {
+        &self.metadata
+    }
+    pub fn vector_layers(&self) -> Option<serde_json::Value> {
+        self.metadata.get("vector_layers").cloned()
+    }
+    fn leaf_directory(&self, entry: &Entry) -> Result<Arc<Vec<Entry>>, String> {
+        let offset = self.header.leaf_dirs_offset + entry.offset;
+        if let Some(entries) = self.leaves.lock().unwrap().get(&offset) {
+            return Ok(entries.clone());
+        }
+        let data = self.source.read_range(offset, entry.length)?;
+        let entries = Arc::new(parse_directory(&self.decompress(data)?)?);
+        let mut leaves = self.leaves.lock().unwrap();
+        if leaves.len() >= LEAF_CACHE_SIZE {
+            leaves.clear();
+        }
+        leaves.insert(offset, entries.clone());
+        Ok(entries)
+    }
+    /// Stored tile data at zoom level, column and row in XYZ scheme
+    pub fn tile(&self, zoom: u8, xtile: u32, ytile: u32) -> Result<Option<Vec<u8>>, String> {
+        if zoom < self.header.minzoom || zoom > self.header.maxzoom {
+            return Ok(None);
+        }
+        let tile_id = zxy_to_tile_id(zoom, xtile, ytile);
+        let err = |e: String| format!("{}: {}", self.path, e);
+        let mut entry = match find_entry(&self.root, tile_id) {
+            Some(entry) => entry.clone(),
+            None => return Ok(None),
+        };
+        for _ in 0..MAX_DIRECTORY_DEPTH {
+            if entry.run_length > 0 {
+                let data = self
+                    .source
+                    .read_range(self.header.tile_data_offset + entry.offset, entry.length)
+                    .map_err(err)?;
+                return Ok(Some(data));
+            }
+            let leaf = self.leaf_directory(&entry).map_err(err)?;
+            entry = match find_entry(&leaf, tile_id) {
+                Some(entry) => entry.clone(),
+                None => return Ok(None),
+            };
+        }
+        Err(err("PMTiles directories too deep".to_string()))
+    }
+}
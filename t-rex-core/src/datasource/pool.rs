@@ -0,0 +1,51 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Connection pool settings of database datasources
+
+use crate::core::config::DatasourceCfg;
+use std::time::Duration;
+
+/// Pool settings besides the maximal pool size
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct PoolSettings {
+    /// Number of idle connections kept open (default: pool size)
+    pub min_idle: Option<u16>,
+    /// Idle connections above `min_idle` are closed after this time (default: 10 min)
+    pub idle_timeout: Option<Duration>,
+    /// Maximal waiting time for a free connection (default: 30s)
+    pub acquire_timeout: Option<Duration>,
+}
+
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+pub const DEFAULT_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl PoolSettings {
+    pub fn from_config(ds_cfg: &DatasourceCfg) -> PoolSettings {
+        PoolSettings {
+            min_idle: ds_cfg.pool_min,
+            idle_timeout: ds_cfg.pool_idle_timeout.map(Duration::from_secs),
+            acquire_timeout: ds_cfg.pool_timeout.map(Duration::from_secs),
+        }
+    }
+    pub fn min_idle(&self, max_size: u16) -> u16 {
+        self.min_idle.unwrap_or(max_size).min(max_size)
+    }
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout.unwrap_or(DEFAULT_IDLE_TIMEOUT)
+    }
+    pub fn acquire_timeout(&self) -> Duration {
+        self.acquire_timeout.unwrap_or(DEFAULT_ACQUIRE_TIMEOUT)
+    }
+    /// r2d2 pool builder with these settings
+    #[cfg(feature = "r2d2")]
+    pub fn r2d2_builder<M: r2d2::ManageConnection>(&self, max_size: u16) -> r2d2::Builder<M> {
+        r2d2::Pool::builder()
+            .max_size(max_size as u32)
+            .min_idle(Some(self.min_idle(max_size) as u32))
+            .idle_timeout(Some(self.idle_timeout()))
+            .connection_timeout(self.acquire_timeout())
+    }
+}
@@ -3,12 +3,16 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::core::feature::{Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::feature::{
+    civil_from_days, fid_from_values, Feature, FeatureAttr, FeatureAttrValType,
+};
 use crate::core::geom::*;
 use crate::core::layer::Layer;
-use postgres::types::{self, FromSql, Type};
-use postgres::Row;
+use serde_json::Value;
 use std;
+use std::collections::HashMap;
+use tokio_postgres::types::{self, FromSql, Type};
+use tokio_postgres::Row;
 
 impl GeometryType {
     /// Convert returned geometry to core::geom::GeometryType based on GeometryType name
@@ -67,7 +71,14 @@ impl<'a> FromSql<'a> for FeatureAttrValType {
             | &types::Type::INT2
             | &types::Type::INT4
             | &types::Type::INT8
-            | &types::Type::BOOL => true,
+            | &types::Type::BOOL
+            | &types::Type::NUMERIC
+            | &types::Type::DATE
+            | &types::Type::TIMESTAMP
+            | &types::Type::TIMESTAMPTZ
+            | &types::Type::UUID
+            | &types::Type::JSON
+            | &types::Type::JSONB => true,
             _ => false,
         }
     }
@@ -96,6 +107,29 @@ impl<'a> FromSql<'a> for FeatureAttrValType {
             &types::Type::BOOL => {
                 <bool>::from_sql(ty, raw).and_then(|v| Ok(FeatureAttrValType::Bool(v)))
             }
+            &types::Type::NUMERIC => numeric_from_sql(raw).map(FeatureAttrValType::Double),
+            &types::Type::DATE => <i32>::from_sql(&types::Type::INT4, raw)
+                .map(|v| FeatureAttrValType::String(pg_date(v))),
+            &types::Type::TIMESTAMP | &types::Type::TIMESTAMPTZ => {
+                <i64>::from_sql(&types::Type::INT8, raw).map(|v| {
+                    let tz = if ty == &types::Type::TIMESTAMPTZ {
+                        "+00"
+                    } else {
+                        ""
+                    };
+                    FeatureAttrValType::String(pg_timestamp(v, tz))
+                })
+            }
+            &types::Type::UUID => uuid_from_sql(raw).map(FeatureAttrValType::String),
+            &types::Type::JSON => {
+                <String>::from_sql(&types::Type::TEXT, raw).map(FeatureAttrValType::String)
+            }
+            &types::Type::JSONB => match raw.split_first() {
+                Some((1, json)) => {
+                    <String>::from_sql(&types::Type::TEXT, json).map(FeatureAttrValType::String)
+                }
+                _ => Err("unsupported JSONB version".into()),
+            },
             _ => {
                 let err: Box<dyn std::error::Error + Sync + Send> =
                     format!("cannot convert {} to FeatureAttrValType", ty).into();
@@ -105,6 +139,333 @@ impl<'a> FromSql<'a> for FeatureAttrValType {
     }
 }
 
+/// One-dimensional array columns (NULL elements as None)
+pub(crate) enum PgArray {
+    Text(Vec<Option<String>>),
+    Int(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+}
+
+impl<'a> FromSql<'a> for PgArray {
+    fn accepts(ty: &Type) -> bool {
+        match ty {
+            &types::Type::TEXT_ARRAY
+            | &types::Type::INT2_ARRAY
+            | &types::Type::INT4_ARRAY
+            | &types::Type::INT8_ARRAY
+            | &types::Type::FLOAT4_ARRAY
+            | &types::Type::FLOAT8_ARRAY
+            | &types::Type::BOOL_ARRAY => true,
+            _ => false,
+        }
+    }
+    fn from_sql(ty: &Type, raw: &[u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        fn convert<T, U>(values: Vec<Option<T>>, f: impl Fn(T) -> U) -> Vec<Option<U>> {
+            values.into_iter().map(|v| v.map(&f)).collect()
+        }
+        match ty {
+            &types::Type::TEXT_ARRAY => <Vec<Option<String>>>::from_sql(ty, raw).map(PgArray::Text),
+            &types::Type::INT2_ARRAY => {
+                <Vec<Option<i16>>>::from_sql(ty, raw).map(|v| PgArray::Int(convert(v, i64::from)))
+            }
+            &types::Type::INT4_ARRAY => {
+                <Vec<Option<i32>>>::from_sql(ty, raw).map(|v| PgArray::Int(convert(v, i64::from)))
+            }
+            &types::Type::INT8_ARRAY => <Vec<Option<i64>>>::from_sql(ty, raw).map(PgArray::Int),
+            &types::Type::FLOAT4_ARRAY => {
+                <Vec<Option<f32>>>::from_sql(ty, raw).map(|v| PgArray::Float(convert(v, f64::from)))
+            }
+            &types::Type::FLOAT8_ARRAY => <Vec<Option<f64>>>::from_sql(ty, raw).map(PgArray::Float),
+            &types::Type::BOOL_ARRAY => <Vec<Option<bool>>>::from_sql(ty, raw).map(PgArray::Bool),
+            _ => Err(format!("cannot convert {} to array", ty).into()),
+        }
+    }
+}
+
+impl PgArray {
+    /// Array as JSON text
+    fn to_json(&self) -> String {
+        match self {
+            PgArray::Text(v) => serde_json::to_string(v),
+            PgArray::Int(v) => serde_json::to_string(v),
+            PgArray::Float(v) => serde_json::to_string(v),
+            PgArray::Bool(v) => serde_json::to_string(v),
+        }
+        .unwrap_or_default()
+    }
+    /// Attributes `key.0`, `key.1`, ... of non-NULL elements
+    fn exploded(&self, key: &str) -> Vec<FeatureAttr> {
+        fn attrs<T: Clone>(
+            key: &str,
+            values: &[Option<T>],
+            f: impl Fn(T) -> FeatureAttrValType,
+        ) -> Vec<FeatureAttr> {
+            values
+                .iter()
+                .enumerate()
+                .filter_map(|(i, v)| {
+                    v.clone().map(|v| FeatureAttr {
+                        key: format!("{}.{}", key, i),
+                        value: f(v),
+                    })
+                })
+                .collect()
+        }
+        match self {
+            PgArray::Text(v) => attrs(key, v, FeatureAttrValType::String),
+            PgArray::Int(v) => attrs(key, v, FeatureAttrValType::Int),
+            PgArray::Float(v) => attrs(key, v, FeatureAttrValType::Double),
+            PgArray::Bool(v) => attrs(key, v, FeatureAttrValType::Bool),
+        }
+    }
+}
+
+#[test]
+fn test_array_attrs() {
+    let tags = PgArray::Text(vec![Some("a".to_string()), None, Some("c".to_string())]);
+    assert_eq!(tags.to_json(), r#"["a",null,"c"]"#);
+    let attrs = tags.exploded("tags");
+    assert_eq!(attrs.len(), 2);
+    assert_eq!(attrs[0].key, "tags.0");
+    assert_eq!(attrs[1].key, "tags.2");
+    assert_eq!(attrs[1].value, FeatureAttrValType::String("c".to_string()));
+
+    assert_eq!(PgArray::Int(vec![Some(1), Some(2)]).to_json(), "[1,2]");
+    assert_eq!(PgArray::Bool(vec![]).to_json(), "[]");
+}
+
+/// Attributes of an hstore or JSON object column, ordered by key (NULL values skipped)
+fn expanded_attrs(row: &Row, idx: usize, ty: &Type) -> Result<Vec<FeatureAttr>, String> {
+    if ty.name() == "hstore" {
+        let hstore = row
+            .try_get::<_, Option<HashMap<String, Option<String>>>>(idx)
+            .map_err(|e| e.to_string())?;
+        let mut attrs: Vec<FeatureAttr> = hstore
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                value.map(|v| FeatureAttr {
+                    key,
+                    value: FeatureAttrValType::String(v),
+                })
+            })
+            .collect();
+        attrs.sort_by(|a, b| a.key.cmp(&b.key));
+        return Ok(attrs);
+    }
+    if !matches!(ty, &types::Type::JSON | &types::Type::JSONB) {
+        return Err(format!("cannot expand column of type {}", ty));
+    }
+    match row
+        .try_get::<_, Option<FeatureAttrValType>>(idx)
+        .map_err(|e| e.to_string())?
+    {
+        Some(FeatureAttrValType::String(json)) => json_object_attrs(&json),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Attributes of JSON object members (nested arrays and objects as JSON text)
+fn json_object_attrs(json: &str) -> Result<Vec<FeatureAttr>, String> {
+    let object: serde_json::Map<String, Value> =
+        serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut attrs: Vec<FeatureAttr> = object
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                Value::Null => return None,
+                Value::Bool(v) => FeatureAttrValType::Bool(v),
+                Value::Number(v) => match v.as_i64() {
+                    Some(i) => FeatureAttrValType::Int(i),
+                    None => FeatureAttrValType::Double(v.as_f64()?),
+                },
+                Value::String(v) => FeatureAttrValType::String(v),
+                v => FeatureAttrValType::String(v.to_string()),
+            };
+            Some(FeatureAttr { key, value })
+        })
+        .collect();
+    attrs.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(attrs)
+}
+
+#[test]
+fn test_json_object_attrs() {
+    let attrs = json_object_attrs(
+        r#"{"name": "Bern", "ele": 540, "area": 51.6, "note": null, "alt": ["a"]}"#,
+    )
+    .unwrap();
+    let values: Vec<(&str, &FeatureAttrValType)> =
+        attrs.iter().map(|a| (a.key.as_str(), &a.value)).collect();
+    assert_eq!(
+        values,
+        vec![
+            ("alt", &FeatureAttrValType::String(r#"["a"]"#.to_string())),
+            ("area", &FeatureAttrValType::Double(51.6)),
+            ("ele", &FeatureAttrValType::Int(540)),
+            ("name", &FeatureAttrValType::String("Bern".to_string())),
+        ]
+    );
+    assert!(json_object_attrs("[1, 2]").is_err());
+}
+
+/// Column type can be read as attribute without cast
+pub(crate) fn supported_attr_type(ty: &Type) -> bool {
+    <FeatureAttrValType as FromSql>::accepts(ty) || <PgArray as FromSql>::accepts(ty)
+}
+
+/// Days between Unix epoch and PostgreSQL epoch (2000-01-01)
+const PG_EPOCH_DAYS: i64 = 10_957;
+
+/// NUMERIC binary format: digit count, weight, sign, scale and base 10000 digits
+fn numeric_from_sql(raw: &[u8]) -> Result<f64, Box<dyn std::error::Error + Sync + Send>> {
+    let word = |i: usize| -> Result<u16, Box<dyn std::error::Error + Sync + Send>> {
+        raw.get(i * 2..i * 2 + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(|| "invalid NUMERIC value".into())
+    };
+    let ndigits = word(0)? as usize;
+    let weight = word(1)? as i16 as i64;
+    let mut text = match word(2)? {
+        0xC000 => return Ok(f64::NAN),
+        0xD000 => return Ok(f64::INFINITY),
+        0xF000 => return Ok(f64::NEG_INFINITY),
+        _ if ndigits == 0 => return Ok(0.0),
+        0x4000 => "-".to_string(),
+        _ => String::new(),
+    };
+    for i in 0..ndigits {
+        text.push_str(&format!("{:04}", word(4 + i)?));
+    }
+    // Parsing the decimal representation gives correctly rounded values
+    text.push_str(&format!("e{}", (weight - ndigits as i64 + 1) * 4));
+    Ok(text.parse()?)
+}
+
+/// Date in ISO format from days since 2000-01-01
+fn pg_date(days: i32) -> String {
+    match days {
+        i32::MAX => "infinity".to_string(),
+        i32::MIN => "-infinity".to_string(),
+        _ => {
+            let (year, month, day) = civil_from_days(days as i64 + PG_EPOCH_DAYS);
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        }
+    }
+}
+
+/// Timestamp in PostgreSQL text format from microseconds since 2000-01-01
+fn pg_timestamp(micros: i64, tz: &str) -> String {
+    match micros {
+        i64::MAX => return "infinity".to_string(),
+        i64::MIN => return "-infinity".to_string(),
+        _ => {}
+    }
+    let secs = micros.div_euclid(1_000_000);
+    let daysecs = secs.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400) + PG_EPOCH_DAYS);
+    let mut text = format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        daysecs / 3600,
+        daysecs / 60 % 60,
+        daysecs % 60
+    );
+    let fraction = micros.rem_euclid(1_000_000);
+    if fraction > 0 {
+        text.push_str(format!(".{:06}", fraction).trim_end_matches('0'));
+    }
+    text.push_str(tz);
+    text
+}
+
+fn uuid_from_sql(raw: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    if raw.len() != 16 {
+        return Err("invalid UUID value".into());
+    }
+    let hex: String = raw.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
+
+#[test]
+fn test_attr_conversions() {
+    let numeric = |words: &[u16]| {
+        let raw: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes()).collect();
+        numeric_from_sql(&raw).unwrap()
+    };
+    // 12345.678: digits 1, 2345, 6780 with weight 1
+    assert_eq!(numeric(&[3, 1, 0, 3, 1, 2345, 6780]), 12345.678);
+    assert_eq!(numeric(&[1, 0xFFFF, 0x4000, 2, 5000]), -0.5);
+    assert_eq!(numeric(&[0, 0, 0, 0]), 0.0);
+    assert!(numeric(&[0, 0, 0xC000, 0]).is_nan());
+    assert!(numeric_from_sql(&[0, 1]).is_err());
+
+    assert_eq!(pg_date(0), "2000-01-01");
+    assert_eq!(pg_date(-1), "1999-12-31");
+    assert_eq!(pg_date(7671), "2021-01-01");
+    assert_eq!(pg_date(i32::MAX), "infinity");
+
+    assert_eq!(pg_timestamp(0, ""), "2000-01-01 00:00:00");
+    assert_eq!(
+        pg_timestamp(662_818_200_123_400, "+00"),
+        "2021-01-01 12:10:00.1234+00"
+    );
+    assert_eq!(pg_timestamp(-1, ""), "1999-12-31 23:59:59.999999");
+
+    let uuid = [
+        0xa0, 0xee, 0xbc, 0x99, 0x9c, 0x0b, 0x4e, 0xf8, 0xbb, 0x6d, 0x6b, 0xb9, 0xbd, 0x38, 0x0a,
+        0x11,
+    ];
+    assert_eq!(
+        uuid_from_sql(&uuid).unwrap(),
+        "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"
+    );
+}
+
+/// Maximal length of logged row content
+const MAX_ROW_LOG_LEN: usize = 200;
+
+fn truncate_log(text: &str, max_len: usize) -> String {
+    match text.char_indices().nth(max_len) {
+        Some((pos, _)) => format!("{}...", &text[..pos]),
+        None => text.to_string(),
+    }
+}
+
+#[test]
+fn test_truncate_log() {
+    assert_eq!(truncate_log("Row { id: 1 }", 200), "Row { id: 1 }");
+    assert_eq!(
+        truncate_log("Row { name: \"Zürich\" }", 15),
+        "Row { name: \"Zü..."
+    );
+}
+
+/// Undecoded EWKB of geometry and geography columns
+struct EwkbBytes<'a>(&'a [u8]);
+
+impl<'a> FromSql<'a> for EwkbBytes<'a> {
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(EwkbBytes(raw))
+    }
+}
+
 pub(crate) struct FeatureRow<'a> {
     pub layer: &'a Layer,
     pub row: &'a Row,
@@ -112,13 +473,18 @@ pub(crate) struct FeatureRow<'a> {
 
 impl<'a> Feature for FeatureRow<'a> {
     fn fid(&self) -> Option<u64> {
-        self.layer.fid_field.as_ref().and_then(|fid| {
-            let val = self.row.try_get::<_, FeatureAttrValType>(fid as &str);
-            match val {
-                Ok(FeatureAttrValType::Int(fid)) => Some(fid as u64),
-                _ => None,
-            }
-        })
+        let values: Vec<Option<FeatureAttrValType>> = self
+            .layer
+            .fid_fields()
+            .iter()
+            .map(|field| {
+                self.row
+                    .try_get::<_, Option<FeatureAttrValType>>(*field)
+                    .ok()
+                    .flatten()
+            })
+            .collect();
+        fid_from_values(&values)
     }
     fn attributes(&self) -> Vec<FeatureAttr> {
         let mut attrs = Vec::new();
@@ -132,6 +498,41 @@ impl<'a> Feature for FeatureRow<'a> {
                     .unwrap_or(&"".to_string())
                 && col.name() != self.layer.fid_field.as_ref().unwrap_or(&"".to_string())
             {
+                if self.layer.expand_fields.iter().any(|f| f == col.name()) {
+                    match expanded_attrs(self.row, i, col.type_()) {
+                        Ok(expanded) => attrs.extend(
+                            expanded
+                                .into_iter()
+                                .filter(|attr| self.layer.expands_key(&attr.key)),
+                        ),
+                        Err(err) => warn!(
+                            "Layer '{}' - skipping field '{}': {}",
+                            self.layer.name,
+                            col.name(),
+                            err
+                        ),
+                    }
+                    continue;
+                }
+                if <PgArray as FromSql>::accepts(col.type_()) {
+                    match self.row.try_get::<_, Option<PgArray>>(i) {
+                        Ok(Some(array)) if self.layer.explode_arrays => {
+                            attrs.extend(array.exploded(col.name()));
+                        }
+                        Ok(Some(array)) => attrs.push(FeatureAttr {
+                            key: col.name().to_string(),
+                            value: FeatureAttrValType::String(array.to_json()),
+                        }),
+                        Ok(None) => {}
+                        Err(err) => warn!(
+                            "Layer '{}' - skipping field '{}': {}",
+                            self.layer.name,
+                            col.name(),
+                            err
+                        ),
+                    }
+                    continue;
+                }
                 let val = self.row.try_get::<_, Option<FeatureAttrValType>>(i);
                 match val {
                     Ok(Some(v)) => {
@@ -173,9 +574,24 @@ impl<'a> Feature for FeatureRow<'a> {
                 .expect("geometry_type undefined"),
         );
         if let Err(ref err) = geom {
-            error!("Layer '{}': {}", self.layer.name, err);
-            error!("{:?}", self.row);
+            match self.fid() {
+                Some(fid) => error!("Layer '{}' feature {}: {}", self.layer.name, fid, err),
+                None => error!("Layer '{}': {}", self.layer.name, err),
+            }
+            // Row values may contain sensitive data
+            debug!(
+                "{}",
+                truncate_log(&format!("{:?}", self.row), MAX_ROW_LOG_LEN)
+            );
         }
         geom
     }
+    fn geometry_wkb(&self) -> Option<&[u8]> {
+        let field = self.layer.geometry_field.as_ref()?;
+        self.row
+            .try_get::<_, Option<EwkbBytes>>(field.as_str())
+            .ok()
+            .flatten()
+            .map(|wkb| wkb.0)
+    }
 }
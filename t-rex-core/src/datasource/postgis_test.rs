@@ -6,8 +6,8 @@
 use crate::core::feature::FeatureAttrValType;
 use crate::core::geom::*;
 use crate::core::layer::{Layer, LayerQuery};
-use crate::datasource::postgis_ds::{PostgisDatasource, QueryParam};
-use crate::datasource::DatasourceType;
+use crate::datasource::postgis_ds::{PostgisDatasource, QueryParam, SqlQuery};
+use crate::datasource::{DatasourceInput, DatasourceType};
 use postgres::{Client, NoTls};
 use std::env;
 use tile_grid::Extent;
@@ -193,6 +193,22 @@ fn test_feature_query() {
     layer.tolerance = "0.5".to_string();
     assert_eq!(pg.build_query(&layer, 3857, 10, None).unwrap().sql,
                "SELECT ST_Multi(ST_SimplifyPreserveTopology(ST_Multi(geometry),0.5)) AS geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857)");
+    layer.simplify_method = Some("visvalingam".to_string());
+    assert_eq!(pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+               "SELECT ST_Multi(ST_SimplifyVW(ST_Multi(geometry),power(0.5,2))) AS geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857)");
+    layer.geometry_type = Some("POLYGON".to_string());
+    layer.simplify_method = Some("douglas_peucker".to_string());
+    assert_eq!(pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+               "SELECT ST_CollectionExtract(ST_MakeValid(ST_Multi(ST_Simplify(ST_Multi(geometry),0.5))),3)::geometry(MULTIPOLYGON,3857) AS geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857)");
+    layer.make_valid = false;
+    assert_eq!(pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+               "SELECT COALESCE(ST_Multi(ST_Simplify(ST_Multi(geometry),0.5)),ST_GeomFromText('MULTIPOLYGON EMPTY',3857))::geometry(MULTIPOLYGON,3857) AS geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857)");
+    layer.simplify_auto = true;
+    assert_eq!(pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+               "SELECT COALESCE(ST_Multi(ST_Simplify(ST_SnapToGrid(ST_Multi(geometry), $5::FLOAT8*256/256),0.5)),ST_GeomFromText('MULTIPOLYGON EMPTY',3857))::geometry(MULTIPOLYGON,3857) AS geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857) AND (ST_Area(geometry) >= power($5::FLOAT8,2))");
+    layer.simplify_auto = false;
+    layer.make_valid = true;
+    layer.simplify_method = None;
     layer.geometry_type = Some("POINT".to_string());
     assert_eq!(
         pg.build_query(&layer, 3857, 10, None).unwrap().sql,
@@ -206,6 +222,34 @@ fn test_feature_query() {
         // No LIMIT clause added - limited when retrieving records
         "SELECT geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857)"
     );
+    layer.query_limit = None;
+
+    // ordering
+    layer.order_by = Some("rank, ST_Area(geometry) DESC".to_string());
+    assert_eq!(
+        pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+        "SELECT geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857) ORDER BY rank, ST_Area(geometry) DESC"
+    );
+    layer.order_by = Some("!zoom! - rank".to_string());
+    let query = pg.build_query(&layer, 3857, 10, None).unwrap();
+    assert_eq!(
+        query.sql,
+        "SELECT geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857) ORDER BY $5 - rank"
+    );
+    layer.order_by = None;
+
+    // row-level filter
+    layer.filter = Some("public = true".to_string());
+    assert_eq!(
+        pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+        "SELECT geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857) AND (public = true)"
+    );
+    let sql = String::from("SELECT geometry FROM osm_place_point WHERE geometry && !bbox!");
+    assert_eq!(
+        pg.build_query(&layer, 3857, 10, Some(&sql)).unwrap().sql,
+        "SELECT * FROM (SELECT geometry FROM osm_place_point WHERE geometry && ST_MakeEnvelope($1,$2,$3,$4,3857)) AS _q WHERE (public = true)"
+    );
+    layer.filter = None;
 
     // user queries
     layer.query = vec![LayerQuery {
@@ -310,6 +354,84 @@ fn test_query_params() {
     assert_eq!(query.params, [QueryParam::Bbox, QueryParam::PixelWidth]);
 }
 
+#[test]
+fn test_changes_query() {
+    let pg = PostgisDatasource::new("postgresql://pi@localhost/osm2vectortiles", Some(1));
+    let mut layer = Layer::new("buildings");
+    layer.geometry_field = Some(String::from("way"));
+    assert_eq!(pg.build_changes_query(&layer, 3857), None);
+
+    layer.changes_sql = Some(String::from(
+        "SELECT way FROM osm_buildings WHERE updated > !since!",
+    ));
+    layer.srid = Some(2056);
+    assert_eq!(pg.build_changes_query(&layer, 3857).unwrap(),
+               "SELECT ST_XMin(_b)::FLOAT8, ST_YMin(_b)::FLOAT8, ST_XMax(_b)::FLOAT8, ST_YMax(_b)::FLOAT8 FROM (SELECT Box2D(ST_Transform(way,3857)) AS _b FROM (SELECT way FROM osm_buildings WHERE updated > $1::TEXT::TIMESTAMPTZ) AS _c) AS _e WHERE _b IS NOT NULL");
+}
+
+#[test]
+fn test_updated_query() {
+    let pg = PostgisDatasource::new("postgresql://pi@localhost/osm2vectortiles", Some(1));
+    let mut layer = Layer::new("buildings");
+    assert_eq!(pg.build_updated_query(&layer), None);
+
+    layer.updated_sql = Some(String::from("SELECT max(updated) FROM osm_buildings"));
+    assert_eq!(pg.build_updated_query(&layer).unwrap(),
+               "SELECT to_char(_t::TIMESTAMPTZ AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"') FROM (SELECT max(updated) FROM osm_buildings) AS _u(_t)");
+}
+
+#[test]
+fn test_srid_override() {
+    let pg = PostgisDatasource::new("postgresql://pi@localhost/osm2vectortiles", Some(1));
+    let mut layer = Layer::new("stations");
+    layer.table_name = Some(String::from("stations"));
+    layer.geometry_field = Some(String::from("geom"));
+    layer.geometry_type = Some(String::from("POINT"));
+    layer.srid = Some(4326);
+    layer.force_srid = true;
+    layer.axis_order = Some(String::from("yx"));
+    assert_eq!(pg.build_query(&layer, 3857, 10, None).unwrap().sql,
+               "SELECT ST_Transform(ST_FlipCoordinates(ST_SetSRID(geom,4326)),3857) AS geom FROM stations WHERE geom && ST_FlipCoordinates(ST_Transform(ST_MakeEnvelope($1,$2,$3,$4,3857),4326))");
+}
+
+#[test]
+fn test_mvt_query() {
+    let pg = PostgisDatasource::new("postgresql://pi@localhost/osm2vectortiles", Some(1));
+    let mut layer = Layer::new("buildings");
+    layer.table_name = Some(String::from("osm_buildings"));
+    layer.geometry_field = Some(String::from("way"));
+    layer.srid = Some(3857);
+    layer.fid_field = Some(String::from("osm_id"));
+    layer.buffer_size = Some(10);
+    layer.query_limit = Some(100);
+    layer.encoding = Some(String::from("st_asmvt"));
+    let query = pg.build_query(&layer, 3857, 10, None).unwrap();
+    let mvt_query = pg.build_mvt_query(&layer, 3857, &query, None).unwrap();
+    assert_eq!(mvt_query.sql,
+               format!("SELECT ST_AsMVT(_t,'buildings',4096,'way','osm_id') FROM (SELECT ST_AsMVTGeom(way,ST_MakeEnvelope($1,$2,$3,$4,3857),4096,160,true) AS way FROM ({} LIMIT 100) AS _f) AS _t", query.sql));
+    assert_eq!(mvt_query.params, query.params);
+
+    let query = SqlQuery {
+        sql: String::from("SELECT * FROM osm_buildings"),
+        params: Vec::new(),
+    };
+    assert!(pg.build_mvt_query(&layer, 3857, &query, None).is_none());
+}
+
+#[test]
+fn test_count_query() {
+    let query = SqlQuery {
+        sql: String::from(
+            "SELECT * FROM osm_buildings WHERE way && ST_MakeEnvelope($1,$2,$3,$4,3857)",
+        ),
+        params: vec![QueryParam::Bbox],
+    };
+    assert_eq!(query.count_sql(None),
+               "SELECT count(*) FROM (SELECT * FROM osm_buildings WHERE way && ST_MakeEnvelope($1,$2,$3,$4,3857)) AS _count");
+    assert_eq!(query.count_sql(Some(100)),
+               "SELECT count(*) FROM (SELECT 1 FROM (SELECT * FROM osm_buildings WHERE way && ST_MakeEnvelope($1,$2,$3,$4,3857)) AS _q LIMIT 100) AS _count");
+}
+
 #[test]
 #[ignore]
 fn test_retrieve_features() {
@@ -373,6 +495,42 @@ fn test_retrieve_features() {
     assert_eq!(cnt, 7321);
 }
 
+#[test]
+#[ignore]
+fn test_fetch_features() {
+    let mut pg: PostgisDatasource = match env::var("DBCONN") {
+        Result::Ok(val) => Some(PostgisDatasource::new(&val, Some(2)).connected()),
+        Result::Err(_) => panic!("DBCONN undefined"),
+    }
+    .unwrap();
+
+    let mut layer = Layer::new("points");
+    layer.table_name = Some(String::from("ne.ne_10m_populated_places"));
+    layer.geometry_field = Some(String::from("wkb_geometry"));
+    layer.geometry_type = Some(String::from("POINT"));
+    let grid = Grid::web_mercator();
+    pg.prepare_queries("ts", &layer, 3857);
+
+    // More concurrent queries than connections
+    let extents: Vec<Extent> = (0..4).map(|x| grid.tile_extent(x, 1, 2)).collect();
+    let fetches = extents
+        .iter()
+        .map(|extent| pg.fetch_features("ts", &layer, extent, 2, &grid, None));
+    let results = futures::executor::block_on(futures::future::join_all(fetches));
+    let mut total = 0;
+    for (extent, result) in extents.iter().zip(results) {
+        let features = result.unwrap();
+        let cnt = pg.read_features(&layer, 2, &features, |_| {});
+        assert_eq!(cnt as usize, features.len());
+        assert_eq!(
+            cnt,
+            pg.retrieve_features("ts", &layer, extent, 2, &grid, |_| {})
+        );
+        total += cnt;
+    }
+    assert!(total > 0);
+}
+
 #[test]
 #[ignore]
 #[should_panic(expected = "geometry_field undefined")]
@@ -392,12 +550,13 @@ fn test_no_geom_field() {
 
 #[test]
 #[ignore]
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
 fn test_tls() {
-    use native_tls::TlsConnector;
-    use postgres_native_tls::MakeTlsConnector;
+    use crate::datasource::TlsSettings;
 
-    let tls_connector = TlsConnector::builder().build().unwrap();
-    let tls_connector = MakeTlsConnector::new(tls_connector);
+    let (_, tls) =
+        TlsSettings::from_connection_string("postgresql://localhost/osm?sslmode=require").unwrap();
+    let tls_connector = tls.connector().unwrap();
     let _conn = match env::var("DBCONN") {
         Result::Ok(val) => Client::connect(&val, tls_connector),
         Result::Err(_) => panic!("DBCONN undefined"),
@@ -409,3 +568,150 @@ fn test_tls() {
     //assert!(conn.unwrap().execute("SELECT 1::VARCHAR", &[]).is_ok());
     // Check pg_stat_ssl? https://www.postgresql.org/docs/9.6/static/monitoring-stats.html#PG-STAT-SSL-VIEW
 }
+
+#[test]
+fn test_session_settings() {
+    let mut pg = PostgisDatasource::new("postgresql://pi@localhost/osm2vectortiles", Some(1));
+    let version = env!("CARGO_PKG_VERSION");
+    assert_eq!(pg.application_name(None), format!("t-rex/{}", version));
+    let config = pg.pg_config();
+    assert_eq!(
+        config.get_application_name(),
+        Some(format!("t-rex/{}", version).as_str())
+    );
+    assert_eq!(config.get_options(), None);
+
+    pg.application_name = Some("t-rex/{version} {tileset}".to_string());
+    assert_eq!(pg.application_name(None), format!("t-rex/{}", version));
+    assert_eq!(
+        pg.application_name(Some("osm")),
+        format!("t-rex/{} osm", version)
+    );
+
+    pg.read_only = true;
+    assert_eq!(
+        pg.pg_config().get_options(),
+        Some("-c default_transaction_read_only=on")
+    );
+
+    // Settings of connection URL
+    let mut pg = PostgisDatasource::new(
+        "postgresql://pi@localhost/osm?application_name=tiles&options=-c%20statement_timeout%3D5000",
+        Some(1),
+    );
+    pg.read_only = true;
+    let config = pg.pg_config();
+    assert_eq!(config.get_application_name(), Some("tiles"));
+    assert_eq!(
+        config.get_options(),
+        Some("-c statement_timeout=5000 -c default_transaction_read_only=on")
+    );
+}
+
+#[test]
+#[ignore]
+fn test_read_only_session() {
+    let mut pg = match env::var("DBCONN") {
+        Result::Ok(val) => PostgisDatasource::new(&val, Some(1)),
+        Result::Err(_) => panic!("DBCONN undefined"),
+    };
+    pg.read_only = true;
+    pg.application_name = Some("t-rex-test".to_string());
+    let mut client = postgres::Config::from(pg.pg_config())
+        .connect(NoTls)
+        .unwrap();
+    let row = client
+        .query_one(
+            "SELECT current_setting('default_transaction_read_only'), current_setting('application_name')",
+            &[],
+        )
+        .unwrap();
+    assert_eq!(row.get::<_, String>(0), "on");
+    assert_eq!(row.get::<_, String>(1), "t-rex-test");
+    assert!(client
+        .batch_execute("CREATE TEMP TABLE t (id int)")
+        .is_err());
+}
+
+#[test]
+fn test_tls_settings() {
+    use crate::datasource::{TlsMode, TlsSettings};
+    use tokio_postgres::config::SslMode;
+
+    let (conn, tls) = TlsSettings::from_connection_string(
+        "postgresql://pi@db.example.com/osm?sslmode=verify-full&sslrootcert=%2Ftmp%2Fca.pem&application_name=t-rex",
+    )
+    .unwrap();
+    assert_eq!(
+        conn,
+        "postgresql://pi@db.example.com/osm?application_name=t-rex"
+    );
+    assert_eq!(tls.mode, TlsMode::VerifyFull);
+    assert_eq!(tls.root_cert, Some("/tmp/ca.pem".to_string()));
+    assert!(tls.required());
+
+    let (conn, tls) = TlsSettings::from_connection_string("postgresql://pi@localhost/osm").unwrap();
+    assert_eq!(conn, "postgresql://pi@localhost/osm");
+    assert_eq!(tls.mode, TlsMode::Prefer);
+    assert!(!tls.required());
+
+    let (conn, tls) = TlsSettings::from_connection_string(
+        "host=localhost user=pi sslmode = require sslrootcert='/tmp/my ca.pem' dbname='osm db'",
+    )
+    .unwrap();
+    assert_eq!(conn, "host=localhost user=pi dbname='osm db'");
+    assert_eq!(tls.mode, TlsMode::Require);
+    assert_eq!(tls.root_cert, Some("/tmp/my ca.pem".to_string()));
+
+    assert!(
+        TlsSettings::from_connection_string("postgresql://localhost/osm?sslmode=always").is_err()
+    );
+
+    // TLS options are not passed to tokio-postgres
+    let pg = PostgisDatasource::new(
+        "postgresql://pi@localhost/osm?sslmode=verify-ca&sslrootcert=/tmp/ca.pem",
+        Some(1),
+    );
+    assert_eq!(pg.pg_config().get_ssl_mode(), SslMode::Require);
+    let pg = PostgisDatasource::new("postgresql://pi@localhost/osm?sslmode=disable", Some(1));
+    assert_eq!(pg.pg_config().get_ssl_mode(), SslMode::Disable);
+}
+
+#[test]
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+fn test_tls_connector() {
+    use crate::datasource::TlsSettings;
+
+    let (_, tls) =
+        TlsSettings::from_connection_string("postgresql://localhost/osm?sslmode=require").unwrap();
+    assert!(tls.connector().is_ok());
+    let (_, tls) = TlsSettings::from_connection_string(
+        "postgresql://localhost/osm?sslmode=verify-full&sslrootcert=/nonexistent/ca.pem",
+    )
+    .unwrap();
+    assert!(tls
+        .connector()
+        .err()
+        .unwrap()
+        .starts_with("Root certificate"));
+    let (_, tls) = TlsSettings::from_connection_string(
+        "postgresql://localhost/osm?sslmode=require&sslcert=client.crt&sslkey=client.key",
+    )
+    .unwrap();
+    let err = tls.connector().err().unwrap();
+    if cfg!(feature = "native-tls") {
+        assert!(err.contains("PKCS#12"));
+    } else {
+        assert!(err.starts_with("Client certificate client.crt"));
+    }
+}
+
+#[test]
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+fn test_tls_connector() {
+    use crate::datasource::TlsSettings;
+
+    let (_, tls) =
+        TlsSettings::from_connection_string("postgresql://localhost/osm?sslmode=require").unwrap();
+    assert!(tls.connector().err().unwrap().contains("'rustls'"));
+}
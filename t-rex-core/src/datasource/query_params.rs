@@ -0,0 +1,39 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Query variables of datasources with queries built as text
+
+use tile_grid::Grid;
+
+/// SQL string literal
+pub fn quote_str(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// SQL string literal or NULL
+pub fn quote_opt(value: Option<&str>, quote: fn(&str) -> String) -> String {
+    value.map(quote).unwrap_or_else(|| "NULL".to_string())
+}
+
+/// Replace variables (!bbox!, !zoom!, etc.) in query with values of tile.
+/// `bbox_expr` and `time_expr` are expressions in the query language of the datasource.
+// https://github.com/mapnik/mapnik/wiki/PostGIS
+pub fn replace_params(
+    query: &str,
+    bbox_expr: &str,
+    zoom: u8,
+    grid: &Grid,
+    time_expr: &str,
+) -> String {
+    query
+        .replace("!bbox!", bbox_expr)
+        .replace("!zoom!", &zoom.to_string())
+        .replace("!pixel_width!", &grid.pixel_width(zoom).to_string())
+        .replace(
+            "!scale_denominator!",
+            &grid.scale_denominator(zoom).to_string(),
+        )
+        .replace("!time!", time_expr)
+}
@@ -0,0 +1,192 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Datasource replaying features recorded with `--record`, e.g. for integration tests or
+//! developing configurations without access to the original database.
+//!
+//! Fixtures are GeoJSON FeatureCollections with coordinates in grid SRS, stored per tile as
+//! `<tileset>/<layer>/<z>/<x>/<y>.geojson` (XYZ scheme).
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::Feature;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::datasource::xyz_tile;
+use crate::datasource::geojson_ds::GeoJsonData;
+use crate::datasource::reproject::{transform_extent, transformation};
+use crate::datasource::DatasourceType;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tile_grid::{Extent, Grid};
+
+#[derive(Clone)]
+pub struct ReplayDatasource {
+    /// Fixture directory
+    pub path: String,
+}
+
+impl ReplayDatasource {
+    pub fn new(path: &str) -> ReplayDatasource {
+        ReplayDatasource {
+            path: path.to_string(),
+        }
+    }
+    /// Fixture file of a layer in the tile with `extent`
+    pub fn fixture_path(
+        dir: &str,
+        tileset: &str,
+        layer: &str,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+    ) -> PathBuf {
+        let (xtile, ytile) = xyz_tile(extent, zoom, grid);
+        Path::new(dir)
+            .join(tileset)
+            .join(layer)
+            .join(zoom.to_string())
+            .join(xtile.to_string())
+            .join(format!("{}.geojson", ytile))
+    }
+    /// Names of subdirectories
+    fn subdirs(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+}
+
+impl DatasourceType for ReplayDatasource {
+    fn connected(&self) -> ReplayDatasource {
+        self.clone()
+    }
+    /// Layers with recorded fixtures in any tileset
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let mut names: Vec<String> = Self::subdirs(Path::new(&self.path))
+            .iter()
+            .flat_map(|tileset| Self::subdirs(&Path::new(&self.path).join(tileset)))
+            .collect();
+        names.sort();
+        names.dedup();
+        names.iter().map(|name| Layer::new(name)).collect()
+    }
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        layer
+            .fields
+            .iter()
+            .map(|col| (col.clone(), String::new()))
+            .collect()
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    fn layer_extent(&self, _layer: &Layer, _grid_srid: i32) -> Option<Extent> {
+        None
+    }
+    fn prepare_queries(&mut self, tileset: &str, layer: &Layer, _grid_srid: i32) {
+        let dir = Path::new(&self.path).join(tileset).join(&layer.name);
+        if !dir.is_dir() {
+            warn!(
+                "Layer '{}': no recorded features in '{}'",
+                layer.name,
+                dir.display()
+            );
+        }
+    }
+    /// Recorded features are already filtered
+    fn supports_filter(&self) -> bool {
+        true
+    }
+    fn retrieve_features<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        match self.try_retrieve_features_at(tileset, layer, extent, zoom, grid, None, read) {
+            Ok(cnt) => cnt,
+            Err(err) => {
+                error!("Layer '{}': {}", layer.name, err);
+                0
+            }
+        }
+    }
+    /// Replay recorded features. Tiles without fixture are empty.
+    fn try_retrieve_features_at<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        _time: Option<&str>,
+        read: F,
+    ) -> Result<u64, String>
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let path = Self::fixture_path(&self.path, tileset, &layer.name, extent, zoom, grid);
+        if !path.exists() {
+            debug!("No recorded features in {}", path.display());
+            return Ok(0);
+        }
+        let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let data = GeoJsonData::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        // Recorded ids are final feature ids
+        let mut replay_layer = layer.clone();
+        replay_layer.fid_field = None;
+        Ok(data.read_all(&replay_layer, Some(grid.srid), read))
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for ReplayDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        Ok(ReplayDatasource::new(ds_cfg.replay.as_ref().unwrap()))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "replay"
+# Features recorded with `t_rex serve --record=<dir>` (or `generate`).
+# Can be added to any datasource to replay it without connecting.
+replay = "<dir>"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+replay = "{}"
+"#,
+            self.path
+        )
+    }
+}
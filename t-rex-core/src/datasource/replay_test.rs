@@ -0,0 +1,87 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::datasource::replay_ds::ReplayDatasource;
+use crate::datasource::DatasourceType;
+use std::fs;
+use std::path::Path;
+use tile_grid::Grid;
+
+const FIXTURE: &str = r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {"type": "Feature", "id": 1, "geometry": {"type": "Point", "coordinates": [829048.4, 5933386.2]},
+     "properties": {"name": "Bern", "population": 133883}},
+    {"type": "Feature", "geometry": {"type": "Point", "coordinates": [950853.1, 6003290.6]},
+     "properties": {"name": "Zürich"}}
+  ]
+}"#;
+
+#[test]
+fn test_fixture_path() {
+    let grid = Grid::web_mercator();
+    let extent = grid.tile_extent_xyz(4, 1, 3);
+    assert_eq!(
+        ReplayDatasource::fixture_path("fixtures", "osm", "places", &extent, 3, &grid),
+        Path::new("fixtures/osm/places/3/4/1.geojson")
+    );
+}
+
+#[test]
+fn test_replay_features() {
+    let dir = std::env::temp_dir().join(format!("t_rex_replay_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let basepath = dir.to_string_lossy().to_string();
+    let grid = Grid::web_mercator();
+    let extent = grid.tile_extent_xyz(33, 22, 6);
+    let path = ReplayDatasource::fixture_path(&basepath, "swiss", "places", &extent, 6, &grid);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, FIXTURE).unwrap();
+
+    let ds = ReplayDatasource::new(&basepath);
+    let layers = ds.detect_layers(false);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+
+    let layer = Layer::new("places");
+    let mut features = Vec::new();
+    let cnt = ds
+        .try_retrieve_features_at("swiss", &layer, &extent, 6, &grid, None, |feat| {
+            let point = match feat.geometry() {
+                Ok(GeometryType::Point(p)) => (p.x, p.y, p.srid),
+                _ => panic!("Point expected"),
+            };
+            features.push((feat.fid(), feat.attributes(), point));
+        })
+        .unwrap();
+    assert_eq!(cnt, 2);
+    assert_eq!(features[0].0, Some(1));
+    assert_eq!(features[0].1[0].key, "name");
+    assert_eq!(
+        features[0].1[0].value,
+        FeatureAttrValType::String("Bern".to_string())
+    );
+    assert_eq!(features[0].1[1].value, FeatureAttrValType::Int(133883));
+    // Coordinates in grid SRS
+    assert_eq!(features[0].2, (829048.4, 5933386.2, Some(3857)));
+    assert_eq!(features[1].0, None);
+
+    // Tile without fixture
+    let extent = grid.tile_extent_xyz(0, 0, 6);
+    assert_eq!(
+        ds.try_retrieve_features_at("swiss", &layer, &extent, 6, &grid, None, |_| {}),
+        Ok(0)
+    );
+    // Invalid fixture
+    fs::write(&path, "{").unwrap();
+    let extent = grid.tile_extent_xyz(33, 22, 6);
+    assert!(ds
+        .try_retrieve_features_at("swiss", &layer, &extent, 6, &grid, None, |_| {})
+        .is_err());
+    let _ = fs::remove_dir_all(&dir);
+}
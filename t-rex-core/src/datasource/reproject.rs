@@ -0,0 +1,52 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Coordinate transformations of native file datasources (without GDAL/PROJ)
+
+use crate::core::layer::Layer;
+use tile_grid::{lonlat_to_merc, merc_to_lonlat, Extent};
+
+/// Coordinate transformation between two supported SRS
+pub type CoordTransform = fn(f64, f64) -> (f64, f64);
+
+/// Transformation from one SRS into another (`None` if not supported)
+pub fn transformation(src_srid: i32, dest_srid: i32) -> Option<Option<CoordTransform>> {
+    match (src_srid, dest_srid) {
+        (src, dest) if src == dest => Some(None),
+        (4326, 3857) => Some(Some(lonlat_to_merc)),
+        (3857, 4326) => Some(Some(merc_to_lonlat)),
+        _ => None,
+    }
+}
+
+/// Source SRS of layer geometries
+pub fn layer_srid(layer: &Layer, grid_srid: i32) -> Option<i32> {
+    if layer.no_transform {
+        Some(grid_srid)
+    } else {
+        layer.srid
+    }
+}
+
+/// Extent with x and y axis swapped
+pub fn swap_extent_axes(extent: &Extent) -> Extent {
+    Extent {
+        minx: extent.miny,
+        miny: extent.minx,
+        maxx: extent.maxy,
+        maxy: extent.maxx,
+    }
+}
+
+pub fn transform_extent(extent: &Extent, transform: CoordTransform) -> Extent {
+    let (minx, miny) = transform(extent.minx, extent.miny);
+    let (maxx, maxy) = transform(extent.maxx, extent.maxy);
+    Extent {
+        minx,
+        miny,
+        maxx,
+        maxy,
+    }
+}
@@ -0,0 +1,155 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Static R-tree for bounding box queries of in-memory features (Sort-Tile-Recursive packing)
+
+use tile_grid::Extent;
+
+const NODE_SIZE: usize = 16;
+
+struct Node {
+    extent: Extent,
+    /// Indices of items (leaf level) or nodes of the level below
+    children: Vec<usize>,
+}
+
+pub struct RTree {
+    items: Vec<Extent>,
+    /// Tree levels from leaves to root
+    levels: Vec<Vec<Node>>,
+}
+
+fn intersects(a: &Extent, b: &Extent) -> bool {
+    a.minx <= b.maxx && a.maxx >= b.minx && a.miny <= b.maxy && a.maxy >= b.miny
+}
+
+fn union(extents: &[&Extent]) -> Extent {
+    let mut extent = extents[0].clone();
+    for e in &extents[1..] {
+        extent.minx = extent.minx.min(e.minx);
+        extent.miny = extent.miny.min(e.miny);
+        extent.maxx = extent.maxx.max(e.maxx);
+        extent.maxy = extent.maxy.max(e.maxy);
+    }
+    extent
+}
+
+/// Group entries into nodes of neighbouring extents
+fn pack(extents: &[&Extent]) -> Vec<Node> {
+    let center = |e: &Extent| ((e.minx + e.maxx) / 2.0, (e.miny + e.maxy) / 2.0);
+    let mut order: Vec<usize> = (0..extents.len()).collect();
+    order.sort_by(|a, b| center(extents[*a]).0.total_cmp(&center(extents[*b]).0));
+    let nodes = extents.len().div_ceil(NODE_SIZE);
+    let slices = (nodes as f64).sqrt().ceil() as usize;
+    let slice_size = NODE_SIZE * slices.max(1);
+    let mut result = Vec::new();
+    for slice in order.chunks_mut(slice_size) {
+        slice.sort_by(|a, b| center(extents[*a]).1.total_cmp(&center(extents[*b]).1));
+        for children in slice.chunks(NODE_SIZE) {
+            let child_extents: Vec<&Extent> = children.iter().map(|i| extents[*i]).collect();
+            result.push(Node {
+                extent: union(&child_extents),
+                children: children.to_vec(),
+            });
+        }
+    }
+    result
+}
+
+impl RTree {
+    pub fn new(extents: &[Extent]) -> RTree {
+        let mut levels: Vec<Vec<Node>> = Vec::new();
+        if !extents.is_empty() {
+            levels.push(pack(&extents.iter().collect::<Vec<_>>()));
+            while levels.last().map(|l| l.len()).unwrap_or(0) > 1 {
+                let level = pack(
+                    &levels
+                        .last()
+                        .unwrap()
+                        .iter()
+                        .map(|node| &node.extent)
+                        .collect::<Vec<_>>(),
+                );
+                levels.push(level);
+            }
+        }
+        RTree {
+            items: extents.to_vec(),
+            levels,
+        }
+    }
+    /// Total extent of all items
+    pub fn extent(&self) -> Option<Extent> {
+        self.levels
+            .last()
+            .and_then(|root| root.first())
+            .map(|node| node.extent.clone())
+    }
+    /// Indices of items intersecting extent, in ascending order
+    pub fn query(&self, extent: &Extent) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut stack: Vec<(usize, usize)> = match self.levels.last() {
+            Some(root) => (0..root.len())
+                .map(|i| (self.levels.len() - 1, i))
+                .collect(),
+            None => Vec::new(),
+        };
+        while let Some((level, idx)) = stack.pop() {
+            let node = &self.levels[level][idx];
+            if !intersects(&node.extent, extent) {
+                continue;
+            }
+            if level == 0 {
+                result.extend(
+                    node.children
+                        .iter()
+                        .filter(|item| intersects(&self.items[**item], extent)),
+                );
+            } else {
+                stack.extend(node.children.iter().map(|child| (level - 1, *child)));
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+}
+
+#[test]
+fn test_rtree() {
+    let extents: Vec<Extent> = (0..1000)
+        .map(|i| {
+            let (x, y) = ((i % 40) as f64, (i / 40) as f64);
+            Extent {
+                minx: x,
+                miny: y,
+                maxx: x + 0.5,
+                maxy: y + 0.5,
+            }
+        })
+        .collect();
+    let tree = RTree::new(&extents);
+    assert_eq!(tree.levels.len(), 3);
+    let query = Extent {
+        minx: 2.2,
+        miny: 3.2,
+        maxx: 4.1,
+        maxy: 4.0,
+    };
+    let expected: Vec<usize> = (0..extents.len())
+        .filter(|i| intersects(&extents[*i], &query))
+        .collect();
+    assert_eq!(expected, vec![122, 123, 124, 162, 163, 164]);
+    assert_eq!(tree.query(&query), expected);
+    assert_eq!(
+        tree.extent(),
+        Some(Extent {
+            minx: 0.0,
+            miny: 0.0,
+            maxx: 39.5,
+            maxy: 24.5
+        })
+    );
+    assert!(RTree::new(&[]).query(&query).is_empty());
+}
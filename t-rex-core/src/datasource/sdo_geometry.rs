@@ -0,0 +1,283 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Conversion of Oracle Spatial `SDO_GEOMETRY` objects (JSON representation of ORDS).
+//! Z and M values are dropped, arcs are linearized.
+
+use crate::core::geom::*;
+use crate::datasource::wkb_reader::linearize_arcs;
+use serde_json::Value;
+
+/// Element of SDO_ELEM_INFO
+#[derive(Clone, Copy)]
+struct Element {
+    /// Position of the first ordinate (starting with 1)
+    offset: usize,
+    etype: u32,
+    interpretation: u32,
+}
+
+struct SdoReader<'a> {
+    ordinates: Vec<f64>,
+    dims: usize,
+    srid: Option<i32>,
+    /// Coordinate transformation applied to every point
+    transform: Option<&'a dyn Fn(f64, f64) -> (f64, f64)>,
+}
+
+/// Attribute of an object with case insensitive name
+fn attr<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    value
+        .as_object()?
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v)
+        .filter(|v| !v.is_null())
+}
+
+fn numbers(value: Option<&Value>) -> Result<Vec<f64>, String> {
+    match value {
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|v| {
+                v.as_f64()
+                    .ok_or(format!("Invalid SDO_GEOMETRY number {}", v))
+            })
+            .collect(),
+        Some(v) => Err(format!("Invalid SDO_GEOMETRY array {}", v)),
+        None => Ok(Vec::new()),
+    }
+}
+
+impl<'a> SdoReader<'a> {
+    fn point(&self, x: f64, y: f64) -> Point {
+        let (x, y) = match self.transform {
+            Some(transform) => transform(x, y),
+            None => (x, y),
+        };
+        Point::new(x, y, self.srid)
+    }
+    fn line(&self, coords: &[(f64, f64)]) -> LineString {
+        LineString {
+            points: coords.iter().map(|&(x, y)| self.point(x, y)).collect(),
+            srid: self.srid,
+        }
+    }
+    /// Coordinates from ordinate position `start` to `end` (exclusive, starting with 1)
+    fn coords(&self, start: usize, end: usize) -> Result<Vec<(f64, f64)>, String> {
+        let ordinates = self
+            .ordinates
+            .get(start.saturating_sub(1)..end.saturating_sub(1))
+            .ok_or("SDO_GEOMETRY ordinates truncated")?;
+        Ok(ordinates
+            .chunks(self.dims)
+            .filter(|c| c.len() >= 2)
+            .map(|c| (c[0], c[1]))
+            .collect())
+    }
+    /// Coordinates of a simple line or ring element
+    fn element_coords(&self, elem: &Element, end: usize) -> Result<Vec<(f64, f64)>, String> {
+        let coords = self.coords(elem.offset, end)?;
+        match (elem.etype % 1000, elem.interpretation) {
+            (_, 1) => Ok(coords),
+            (_, 2) => Ok(linearize_arcs(&coords)),
+            // Rectangle with lower left and upper right corner
+            (3, 3) if coords.len() == 2 => {
+                let (a, b) = (coords[0], coords[1]);
+                Ok(vec![a, (b.0, a.1), b, (a.0, b.1), a])
+            }
+            _ => Err(format!(
+                "SDO_GEOMETRY element type {} with interpretation {} not supported",
+                elem.etype, elem.interpretation
+            )),
+        }
+    }
+    /// Coordinates of a compound line or ring with its sub-elements
+    fn compound_coords(&self, subelems: &[Element], end: usize) -> Result<Vec<(f64, f64)>, String> {
+        let mut coords: Vec<(f64, f64)> = Vec::new();
+        for (i, sub) in subelems.iter().enumerate() {
+            // Sub-elements share their end point with the start of the next sub-element
+            let sub_end = match subelems.get(i + 1) {
+                Some(next) => next.offset + self.dims,
+                None => end,
+            };
+            let part = self.element_coords(sub, sub_end)?;
+            let skip = if coords.last().is_some() && coords.last() == part.first() {
+                1
+            } else {
+                0
+            };
+            coords.extend(part.into_iter().skip(skip));
+        }
+        Ok(coords)
+    }
+    /// Parts of elements in SDO_ELEM_INFO
+    fn parts(&self, elements: &[Element]) -> Result<Vec<Geometry>, String> {
+        let mut parts = Vec::new();
+        let mut i = 0;
+        while i < elements.len() {
+            let elem = elements[i];
+            let nsub = match elem.etype {
+                4 | 1005 | 2005 => elem.interpretation as usize,
+                _ => 0,
+            };
+            let next = i + nsub + 1;
+            let end = elements
+                .get(next)
+                .map(|e| e.offset)
+                .unwrap_or(self.ordinates.len() + 1);
+            let coords = if nsub > 0 {
+                let subelems = elements
+                    .get(i + 1..next)
+                    .ok_or("SDO_GEOMETRY compound element truncated")?;
+                self.compound_coords(subelems, end)?
+            } else if elem.etype <= 1 {
+                // Points and unsupported elements (type 0)
+                Vec::new()
+            } else {
+                self.element_coords(&elem, end)?
+            };
+            match elem.etype {
+                0 => {}
+                1 => {
+                    // Point (interpretation 1), point cluster or orientation (interpretation 0)
+                    if elem.interpretation > 0 {
+                        for (x, y) in self.coords(elem.offset, end)? {
+                            parts.push(Geometry::Point(self.point(x, y)));
+                        }
+                    }
+                }
+                2 | 4 => parts.push(Geometry::LineString(self.line(&coords))),
+                3 | 1003 | 1005 => parts.push(Geometry::Polygon(Polygon {
+                    rings: vec![self.line(&coords)],
+                    srid: self.srid,
+                })),
+                2003 | 2005 => match parts.last_mut() {
+                    Some(Geometry::Polygon(ref mut polygon)) => {
+                        polygon.rings.push(self.line(&coords))
+                    }
+                    _ => return Err("SDO_GEOMETRY interior ring without exterior ring".to_string()),
+                },
+                etype => return Err(format!("SDO_GEOMETRY element type {} not supported", etype)),
+            }
+            i = next;
+        }
+        Ok(parts)
+    }
+}
+
+fn single<T>(parts: Vec<Geometry>, select: fn(Geometry) -> Option<T>) -> Result<Vec<T>, String> {
+    parts
+        .into_iter()
+        .map(|g| {
+            let msg = format!("Unexpected SDO_GEOMETRY part {:?}", g);
+            select(g).ok_or(msg)
+        })
+        .collect()
+}
+
+fn first<T>(mut parts: Vec<T>) -> Result<T, String> {
+    if parts.is_empty() {
+        Err("Empty SDO_GEOMETRY".to_string())
+    } else {
+        Ok(parts.remove(0))
+    }
+}
+
+/// Convert `SDO_GEOMETRY` with optional coordinate transformation
+pub fn read_sdo_geometry(
+    value: &Value,
+    srid: Option<i32>,
+    transform: Option<&dyn Fn(f64, f64) -> (f64, f64)>,
+) -> Result<GeometryType, String> {
+    let gtype = attr(value, "SDO_GTYPE")
+        .and_then(|v| v.as_u64())
+        .ok_or("Invalid SDO_GEOMETRY without SDO_GTYPE")? as u32;
+    let dims = match gtype / 1000 {
+        0 => 2,
+        d => d as usize,
+    };
+    let elem_info = numbers(attr(value, "SDO_ELEM_INFO"))?;
+    let reader = SdoReader {
+        ordinates: numbers(attr(value, "SDO_ORDINATES"))?,
+        dims,
+        srid,
+        transform,
+    };
+    if elem_info.is_empty() {
+        let point = attr(value, "SDO_POINT").ok_or("SDO_GEOMETRY without elements")?;
+        let coord = |name| {
+            attr(point, name)
+                .and_then(|v| v.as_f64())
+                .ok_or(format!("Invalid SDO_POINT {}", point))
+        };
+        return Ok(GeometryType::Point(reader.point(coord("X")?, coord("Y")?)));
+    }
+    let elements: Vec<Element> = elem_info
+        .chunks(3)
+        .filter(|c| c.len() == 3)
+        .map(|c| Element {
+            offset: c[0] as usize,
+            etype: c[1] as u32,
+            interpretation: c[2] as u32,
+        })
+        .collect();
+    let parts = reader.parts(&elements)?;
+    let geom = match gtype % 100 {
+        1 => GeometryType::Point(first(single(parts, |g| match g {
+            Geometry::Point(p) => Some(p),
+            _ => None,
+        })?)?),
+        2 => GeometryType::LineString(first(single(parts, |g| match g {
+            Geometry::LineString(l) => Some(l),
+            _ => None,
+        })?)?),
+        3 => GeometryType::Polygon(first(single(parts, |g| match g {
+            Geometry::Polygon(p) => Some(p),
+            _ => None,
+        })?)?),
+        5 => GeometryType::MultiPoint(MultiPoint {
+            points: single(parts, |g| match g {
+                Geometry::Point(p) => Some(p),
+                _ => None,
+            })?,
+            srid,
+        }),
+        6 => GeometryType::MultiLineString(MultiLineString {
+            lines: single(parts, |g| match g {
+                Geometry::LineString(l) => Some(l),
+                _ => None,
+            })?,
+            srid,
+        }),
+        7 => GeometryType::MultiPolygon(MultiPolygon {
+            polygons: single(parts, |g| match g {
+                Geometry::Polygon(p) => Some(p),
+                _ => None,
+            })?,
+            srid,
+        }),
+        4 => GeometryType::GeometryCollection(GeometryCollection {
+            geometries: parts,
+            srid,
+        }),
+        tt => return Err(format!("SDO_GEOMETRY type {} not supported", tt)),
+    };
+    Ok(geom)
+}
+
+/// Geometry type name of a SDO_GTYPE
+pub fn sdo_geometry_type(gtype: u64) -> Option<&'static str> {
+    match gtype % 100 {
+        1 => Some("POINT"),
+        2 => Some("LINESTRING"),
+        3 => Some("POLYGON"),
+        4 => Some("GEOMETRYCOLLECTION"),
+        5 => Some("MULTIPOINT"),
+        6 => Some("MULTILINESTRING"),
+        7 => Some("MULTIPOLYGON"),
+        _ => None,
+    }
+}
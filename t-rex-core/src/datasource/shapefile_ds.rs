@@ -0,0 +1,405 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Native ESRI Shapefile datasource, without GDAL
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::rtree::RTree;
+use crate::datasource::shapefile_reader::{
+    prj_srid, read_shape, shape_type_name, sibling_file, DbfEncoding, DbfReader, ShapeReader,
+};
+use crate::datasource::DatasourceType;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tile_grid::{Extent, Grid};
+
+/// Spatial index of shape records
+pub struct ShapeIndex {
+    rtree: RTree,
+}
+
+#[derive(Clone)]
+pub struct ShapefileDatasource {
+    pub path: String,
+    index: Option<Arc<ShapeIndex>>,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+struct ShapeFeature<'a> {
+    layer: &'a Layer,
+    fid: u64,
+    fields: &'a [String],
+    values: Vec<Option<FeatureAttrValType>>,
+    shape: Vec<u8>,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> Feature for ShapeFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            return Some(self.fid);
+        }
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                self.fields
+                    .iter()
+                    .position(|name| name == field)
+                    .and_then(|idx| self.values[idx].clone())
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.fields
+            .iter()
+            .zip(self.values.iter())
+            .filter_map(|(key, value)| {
+                value.as_ref().map(|value| FeatureAttr {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        read_shape(&self.shape, self.srid, Some(self.transform))?
+            .ok_or_else(|| "Null shape".to_string())
+    }
+}
+
+impl ShapefileDatasource {
+    pub fn new(path: &str) -> ShapefileDatasource {
+        ShapefileDatasource {
+            path: path.to_string(),
+            index: None,
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn open(&self) -> Result<ShapeReader, String> {
+        ShapeReader::open(&self.path)
+    }
+    /// Attribute table (`None` if .dbf file is missing)
+    fn open_dbf(&self) -> Result<Option<DbfReader>, String> {
+        let dbf = match sibling_file(&self.path, "dbf") {
+            Some(dbf) => dbf,
+            None => return Ok(None),
+        };
+        let encoding = match sibling_file(&self.path, "cpg") {
+            Some(cpg) => DbfEncoding::from_cpg(&fs::read_to_string(cpg).unwrap_or_default()),
+            None => DbfEncoding::Latin1,
+        };
+        DbfReader::open(&dbf, encoding).map(Some)
+    }
+    fn build_index(&self) -> Result<ShapeIndex, String> {
+        let mut reader = self.open()?;
+        let extents = (0..reader.len())
+            .map(|idx| {
+                // Null shapes get an extent outside of valid coordinates
+                reader.record_extent(idx).map(|extent| {
+                    extent.unwrap_or(Extent {
+                        minx: f64::MAX,
+                        miny: f64::MAX,
+                        maxx: f64::MAX,
+                        maxy: f64::MAX,
+                    })
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ShapeIndex {
+            rtree: RTree::new(&extents),
+        })
+    }
+    /// Spatial index (built if not connected)
+    fn index(&self) -> Result<Arc<ShapeIndex>, String> {
+        match self.index {
+            Some(ref index) => Ok(index.clone()),
+            None => self.build_index().map(Arc::new),
+        }
+    }
+    /// Layer name derived from file name
+    fn layer_name(&self) -> String {
+        Path::new(&self.path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "shapefile".to_string())
+    }
+    /// SRS from .prj file
+    fn srid(&self) -> Option<i32> {
+        let prj = sibling_file(&self.path, "prj")?;
+        let srid = prj_srid(&fs::read_to_string(prj).ok()?);
+        if srid.is_none() {
+            warn!("Shapefile '{}': Unknown SRS in .prj file", self.path);
+        }
+        srid
+    }
+}
+
+impl DatasourceType for ShapefileDatasource {
+    /// New instance with spatial index
+    fn connected(&self) -> ShapefileDatasource {
+        let index = match self.index() {
+            Ok(index) => Some(index),
+            Err(e) => {
+                error!("Shapefile '{}': {}", self.path, e);
+                None
+            }
+        };
+        ShapefileDatasource {
+            path: self.path.clone(),
+            index,
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let reader = match self.open() {
+            Ok(reader) => reader,
+            Err(e) => {
+                error!("Shapefile '{}': {}", self.path, e);
+                return Vec::new();
+            }
+        };
+        let mut layer = Layer::new(&self.layer_name());
+        layer.table_name = Some(self.layer_name());
+        layer.geometry_type = shape_type_name(reader.shape_type).map(|t| t.to_string());
+        layer.srid = self.srid();
+        vec![layer]
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        match self.open_dbf() {
+            Ok(Some(dbf)) => dbf
+                .fields
+                .iter()
+                .map(|field| (field.name.clone(), String::new()))
+                .collect(),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let extent = self
+            .open()
+            .map_err(|e| error!("Layer '{}': {}", layer.name, e))
+            .ok()?
+            .extent;
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(0);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if let Err(e) = self.open() {
+            error!("Layer '{}': {}", layer.name, e);
+            return;
+        }
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for Shapefile layers",
+                layer.name
+            );
+        }
+        if !layer.no_transform {
+            match layer.srid {
+                Some(srid) => match transformation(srid, grid_srid) {
+                    Some(Some(transform)) => {
+                        info!(
+                            "Layer '{}': Reprojecting geometry to SRID {}",
+                            layer.name, grid_srid
+                        );
+                        self.geom_transform.insert(layer.name.clone(), transform);
+                    }
+                    Some(None) => {}
+                    None => error!(
+                        "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                        layer.name, srid, grid_srid
+                    ),
+                },
+                None => warn!("Layer '{}': Couldn't detect spatialref", layer.name),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for Shapefile layers",
+                layer.name
+            );
+        }
+        if layer.buffer_size.is_some() && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Clipping with buffer_size not supported for Shapefile layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let opened = self
+            .open()
+            .and_then(|reader| Ok((reader, self.open_dbf()?, self.index()?)));
+        let (mut reader, mut dbf, index) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return 0;
+            }
+        };
+        let fields: Vec<String> = dbf
+            .as_ref()
+            .map(|dbf| dbf.fields.iter().map(|f| f.name.clone()).collect())
+            .unwrap_or_default();
+
+        let mut bbox_extent = if let Some(pixels) = layer.buffer_size {
+            let pixel_width = grid.pixel_width(zoom);
+            let buf = f64::from(pixels) * pixel_width;
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        if layer_transform.is_some() {
+            // Spatial filter must be in layer SRS
+            let inverse = layer
+                .srid
+                .and_then(|srid| transformation(grid.srid, srid))
+                .and_then(|tr| tr);
+            if let Some(inverse) = inverse {
+                bbox_extent = transform_extent(&bbox_extent, inverse);
+            }
+        }
+        let swap_axes = layer.swap_axes();
+        if swap_axes {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        for idx in index.rtree.query(&bbox_extent) {
+            let record = reader.record(idx).and_then(|shape| {
+                let values = match dbf {
+                    Some(ref mut dbf) => dbf.record(idx)?,
+                    None => Some(Vec::new()),
+                };
+                Ok((shape, values))
+            });
+            let (shape, values) = match record {
+                Ok((shape, Some(values))) => (shape, values),
+                // Deleted record
+                Ok((_, None)) => continue,
+                Err(e) => {
+                    error!("Layer '{}': {}", layer.name, e);
+                    break;
+                }
+            };
+            let feat = ShapeFeature {
+                layer,
+                fid: idx as u64,
+                fields: &fields,
+                values,
+                shape,
+                srid: Some(grid.srid),
+                transform: &transform,
+            };
+            read(&feat);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+                break;
+            }
+        }
+        cnt
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for ShapefileDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        Ok(ShapefileDatasource::new(ds_cfg.shapefile.as_ref().unwrap()))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "shapefile"
+# ESRI Shapefile (read without GDAL)
+shapefile = "<filename>.shp"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+shapefile = "{}"
+"#,
+            self.path
+        )
+    }
+}
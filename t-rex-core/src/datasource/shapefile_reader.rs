@@ -0,0 +1,551 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Reader for ESRI Shapefiles (.shp geometries, .shx index, .dbf attributes).
+//! Z and M values are dropped.
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::geom::*;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tile_grid::Extent;
+
+const SHP_FILE_CODE: i32 = 9994;
+const SHP_HEADER_LEN: u64 = 100;
+
+/// File next to `path` with extension `ext` (lower or upper case)
+pub fn sibling_file(path: &str, ext: &str) -> Option<PathBuf> {
+    [ext.to_lowercase(), ext.to_uppercase()]
+        .iter()
+        .map(|ext| Path::new(path).with_extension(ext))
+        .find(|path| path.exists())
+}
+
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, String> {
+    let mut buf = vec![0; len];
+    reader
+        .read_exact(&mut buf)
+        .map_err(|e| format!("Read error: {}", e))?;
+    Ok(buf)
+}
+
+fn i32_be(data: &[u8], pos: usize) -> i32 {
+    i32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+fn i32_le(data: &[u8], pos: usize) -> i32 {
+    i32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+fn f64_le(data: &[u8], pos: usize) -> f64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&data[pos..pos + 8]);
+    f64::from_le_bytes(bytes)
+}
+
+fn read_extent(data: &[u8], pos: usize) -> Extent {
+    Extent {
+        minx: f64_le(data, pos),
+        miny: f64_le(data, pos + 8),
+        maxx: f64_le(data, pos + 16),
+        maxy: f64_le(data, pos + 24),
+    }
+}
+
+/// OGC geometry type name of shape type
+pub fn shape_type_name(shape_type: i32) -> Option<&'static str> {
+    match shape_type {
+        1 | 11 | 21 => Some("POINT"),
+        3 | 13 | 23 => Some("LINESTRING"),
+        5 | 15 | 25 => Some("POLYGON"),
+        8 | 18 | 28 => Some("MULTIPOINT"),
+        _ => None,
+    }
+}
+
+/// Reader for .shp files with record offsets from .shx index
+pub struct ShapeReader {
+    file: BufReader<File>,
+    pub shape_type: i32,
+    pub extent: Extent,
+    /// Offset and content length of records
+    records: Vec<(u64, usize)>,
+}
+
+impl ShapeReader {
+    pub fn open(path: &str) -> Result<ShapeReader, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        let file_len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let mut file = BufReader::new(file);
+        let header = read_bytes(&mut file, SHP_HEADER_LEN as usize)
+            .map_err(|_| format!("{}: Not a shapefile", path))?;
+        if i32_be(&header, 0) != SHP_FILE_CODE {
+            return Err(format!("{}: Not a shapefile", path));
+        }
+        let shape_type = i32_le(&header, 32);
+        let extent = read_extent(&header, 36);
+        let records = match sibling_file(path, "shx") {
+            Some(shx) => Self::read_index(&shx)?,
+            None => {
+                warn!("{}: Index file (.shx) missing", path);
+                Self::scan_records(&mut file, file_len)?
+            }
+        };
+        Ok(ShapeReader {
+            file,
+            shape_type,
+            extent,
+            records,
+        })
+    }
+    fn read_index(shx: &Path) -> Result<Vec<(u64, usize)>, String> {
+        let mut data = Vec::new();
+        File::open(shx)
+            .and_then(|mut f| f.read_to_end(&mut data))
+            .map_err(|e| format!("{}: {}", shx.display(), e))?;
+        if data.len() < SHP_HEADER_LEN as usize || i32_be(&data, 0) != SHP_FILE_CODE {
+            return Err(format!("{}: Invalid index file", shx.display()));
+        }
+        // Offsets and lengths in 16-bit words
+        Ok(data[SHP_HEADER_LEN as usize..]
+            .chunks_exact(8)
+            .map(|entry| {
+                (
+                    i32_be(entry, 0) as u64 * 2 + 8,
+                    i32_be(entry, 4) as usize * 2,
+                )
+            })
+            .collect())
+    }
+    fn scan_records(
+        file: &mut BufReader<File>,
+        file_len: u64,
+    ) -> Result<Vec<(u64, usize)>, String> {
+        let mut records = Vec::new();
+        let mut offset = SHP_HEADER_LEN;
+        while offset + 8 <= file_len {
+            let header = read_bytes(file, 8)?;
+            let len = i32_be(&header, 4) as usize * 2;
+            records.push((offset + 8, len));
+            offset += 8 + len as u64;
+            file.seek(SeekFrom::Start(offset))
+                .map_err(|e| format!("Read error: {}", e))?;
+        }
+        Ok(records)
+    }
+    /// Number of records
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    fn read_at(&mut self, offset: u64, len: usize) -> Result<Vec<u8>, String> {
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .map_err(|e| format!("Read error: {}", e))?;
+        read_bytes(&mut self.file, len)
+    }
+    /// Content of record `idx`
+    pub fn record(&mut self, idx: usize) -> Result<Vec<u8>, String> {
+        let (offset, len) = self.records[idx];
+        self.read_at(offset, len)
+    }
+    /// Bounding box of record `idx` (`None` for null shapes)
+    pub fn record_extent(&mut self, idx: usize) -> Result<Option<Extent>, String> {
+        let (offset, len) = self.records[idx];
+        if len < 4 {
+            return Ok(None);
+        }
+        let data = self.read_at(offset, len.min(36))?;
+        let extent = match i32_le(&data, 0) {
+            0 => None,
+            1 | 11 | 21 if data.len() >= 20 => {
+                let (x, y) = (f64_le(&data, 4), f64_le(&data, 12));
+                Some(Extent {
+                    minx: x,
+                    miny: y,
+                    maxx: x,
+                    maxy: y,
+                })
+            }
+            _ if data.len() >= 36 => Some(read_extent(&data, 4)),
+            _ => return Err(format!("Invalid shape record {}", idx)),
+        };
+        Ok(extent)
+    }
+}
+
+struct ShapeParser<'a> {
+    data: &'a [u8],
+    srid: Option<i32>,
+    transform: Option<&'a dyn Fn(f64, f64) -> (f64, f64)>,
+}
+
+impl<'a> ShapeParser<'a> {
+    fn check_len(&self, len: usize) -> Result<(), String> {
+        if self.data.len() < len {
+            Err("Unexpected end of shape record".to_string())
+        } else {
+            Ok(())
+        }
+    }
+    fn point(&self, pos: usize) -> Point {
+        let (x, y) = (f64_le(self.data, pos), f64_le(self.data, pos + 8));
+        let (x, y) = match self.transform {
+            Some(transform) => transform(x, y),
+            None => (x, y),
+        };
+        Point::new(x, y, self.srid)
+    }
+    /// Parts of PolyLine or Polygon shape
+    fn parts(&self) -> Result<Vec<Vec<Point>>, String> {
+        self.check_len(44)?;
+        let nparts = i32_le(self.data, 36).max(0) as usize;
+        let npoints = i32_le(self.data, 40).max(0) as usize;
+        let points_pos = 44 + 4 * nparts;
+        self.check_len(points_pos + 16 * npoints)?;
+        let mut starts: Vec<usize> = (0..nparts)
+            .map(|i| (i32_le(self.data, 44 + 4 * i).max(0) as usize).min(npoints))
+            .collect();
+        starts.push(npoints);
+        Ok(starts
+            .windows(2)
+            .map(|w| {
+                (w[0]..w[1].max(w[0]))
+                    .map(|i| self.point(points_pos + 16 * i))
+                    .collect()
+            })
+            .collect())
+    }
+    fn geometry(&self) -> Result<Option<GeometryType>, String> {
+        self.check_len(4)?;
+        let shape_type = i32_le(self.data, 0);
+        let srid = self.srid;
+        let geom = match shape_type {
+            0 => return Ok(None),
+            1 | 11 | 21 => {
+                self.check_len(20)?;
+                GeometryType::Point(self.point(4))
+            }
+            8 | 18 | 28 => {
+                self.check_len(40)?;
+                let npoints = i32_le(self.data, 36).max(0) as usize;
+                self.check_len(40 + 16 * npoints)?;
+                GeometryType::MultiPoint(MultiPoint {
+                    points: (0..npoints).map(|i| self.point(40 + 16 * i)).collect(),
+                    srid,
+                })
+            }
+            3 | 13 | 23 => {
+                let mut lines: Vec<LineString> = self
+                    .parts()?
+                    .into_iter()
+                    .map(|points| LineString { points, srid })
+                    .collect();
+                if lines.len() == 1 {
+                    GeometryType::LineString(lines.remove(0))
+                } else {
+                    GeometryType::MultiLineString(MultiLineString { lines, srid })
+                }
+            }
+            5 | 15 | 25 => {
+                let mut polygons = polygons_from_rings(self.parts()?, srid);
+                if polygons.len() == 1 {
+                    GeometryType::Polygon(polygons.remove(0))
+                } else {
+                    GeometryType::MultiPolygon(MultiPolygon { polygons, srid })
+                }
+            }
+            _ => return Err(format!("Unsupported shape type {}", shape_type)),
+        };
+        Ok(Some(geom))
+    }
+}
+
+/// Twice the signed ring area (negative for clockwise rings)
+fn ring_area(points: &[Point]) -> f64 {
+    points
+        .windows(2)
+        .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+        .sum()
+}
+
+/// Group rings into polygons: clockwise rings are exterior rings, followed by their holes
+fn polygons_from_rings(rings: Vec<Vec<Point>>, srid: Option<i32>) -> Vec<Polygon> {
+    let mut polygons: Vec<Polygon> = Vec::new();
+    for points in rings {
+        let exterior = ring_area(&points) <= 0.0;
+        let ring = LineString { points, srid };
+        match polygons.last_mut() {
+            Some(polygon) if !exterior => polygon.rings.push(ring),
+            _ => polygons.push(Polygon {
+                rings: vec![ring],
+                srid,
+            }),
+        }
+    }
+    polygons
+}
+
+/// Read geometry of shape record content. Returns `None` for null shapes.
+pub fn read_shape(
+    data: &[u8],
+    srid: Option<i32>,
+    transform: Option<&dyn Fn(f64, f64) -> (f64, f64)>,
+) -> Result<Option<GeometryType>, String> {
+    ShapeParser {
+        data,
+        srid,
+        transform,
+    }
+    .geometry()
+}
+
+/// EPSG code of SRS in .prj file (ESRI WKT)
+pub fn prj_srid(wkt: &str) -> Option<i32> {
+    // Top-level authority is the last one
+    if let Some(pos) = wkt.rfind("AUTHORITY[\"EPSG\"") {
+        let code: String = wkt[pos + 16..]
+            .chars()
+            .skip_while(|c| !c.is_ascii_digit())
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if wkt[pos..].matches(']').count() <= 2 {
+            return code.parse().ok();
+        }
+    }
+    let name = wkt
+        .split('"')
+        .nth(1)
+        .unwrap_or("")
+        .to_lowercase()
+        .replace(' ', "_");
+    match name.as_str() {
+        "gcs_wgs_1984" | "wgs_84" | "wgs84" => Some(4326),
+        "wgs_84_pseudo_mercator"
+        | "wgs_1984_web_mercator_auxiliary_sphere"
+        | "wgs_1984_web_mercator" => Some(3857),
+        "ch1903+_lv95" => Some(2056),
+        "ch1903_lv03" => Some(21781),
+        "etrs_1989_laea" | "etrs89_laea_europe" => Some(3035),
+        _ => None,
+    }
+}
+
+/// Character encoding of DBF text fields
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DbfEncoding {
+    Utf8,
+    Latin1,
+}
+
+impl DbfEncoding {
+    /// Encoding declared in .cpg file content
+    pub fn from_cpg(cpg: &str) -> DbfEncoding {
+        match cpg.trim().to_uppercase().as_str() {
+            "UTF-8" | "UTF8" | "65001" => DbfEncoding::Utf8,
+            _ => DbfEncoding::Latin1,
+        }
+    }
+    fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            DbfEncoding::Utf8 => String::from_utf8_lossy(bytes).to_string(),
+            DbfEncoding::Latin1 => bytes.iter().map(|b| *b as char).collect(),
+        }
+    }
+}
+
+pub struct DbfField {
+    pub name: String,
+    /// dBASE field type (C, N, F, L, D, ...)
+    pub field_type: char,
+    pub offset: usize,
+    pub length: usize,
+    pub decimals: u8,
+}
+
+/// Reader for .dbf attribute tables
+pub struct DbfReader {
+    file: BufReader<File>,
+    pub fields: Vec<DbfField>,
+    pub record_count: usize,
+    header_len: u64,
+    record_len: usize,
+    encoding: DbfEncoding,
+}
+
+impl DbfReader {
+    pub fn open(path: &Path, encoding: DbfEncoding) -> Result<DbfReader, String> {
+        let mut file =
+            BufReader::new(File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?);
+        let header = read_bytes(&mut file, 32)?;
+        let record_count = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let header_len = u16::from_le_bytes([header[8], header[9]]);
+        let record_len = u16::from_le_bytes([header[10], header[11]]) as usize;
+        if (header_len as usize) < 33 {
+            return Err(format!("{}: Invalid DBF header", path.display()));
+        }
+        let descriptors = read_bytes(&mut file, header_len as usize - 32)?;
+        let mut fields = Vec::new();
+        // Offset 0 is the deletion flag
+        let mut offset = 1;
+        for desc in descriptors.chunks_exact(32) {
+            if desc[0] == 0x0d {
+                break;
+            }
+            let name_len = desc[..11].iter().position(|b| *b == 0).unwrap_or(11);
+            let length = desc[16] as usize;
+            fields.push(DbfField {
+                name: encoding.decode(&desc[..name_len]).trim().to_string(),
+                field_type: desc[11] as char,
+                offset,
+                length,
+                decimals: desc[17],
+            });
+            offset += length;
+        }
+        if offset > record_len {
+            return Err(format!("{}: Invalid DBF field lengths", path.display()));
+        }
+        Ok(DbfReader {
+            file,
+            fields,
+            record_count: record_count as usize,
+            header_len: header_len as u64,
+            record_len,
+            encoding,
+        })
+    }
+    /// Field values of record `idx` (`None` for deleted or missing records)
+    pub fn record(
+        &mut self,
+        idx: usize,
+    ) -> Result<Option<Vec<Option<FeatureAttrValType>>>, String> {
+        if idx >= self.record_count {
+            return Ok(None);
+        }
+        self.file
+            .seek(SeekFrom::Start(
+                self.header_len + (idx * self.record_len) as u64,
+            ))
+            .map_err(|e| format!("Read error: {}", e))?;
+        let data = read_bytes(&mut self.file, self.record_len)?;
+        if data[0] == b'*' {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.fields
+                .iter()
+                .map(|field| self.value(field, &data[field.offset..field.offset + field.length]))
+                .collect(),
+        ))
+    }
+    fn value(&self, field: &DbfField, bytes: &[u8]) -> Option<FeatureAttrValType> {
+        let text = || self.encoding.decode(bytes).trim().to_string();
+        match field.field_type {
+            'C' => Some(FeatureAttrValType::String(
+                self.encoding.decode(bytes).trim_end().to_string(),
+            )),
+            'N' | 'F' => {
+                let text = text();
+                if field.decimals == 0 {
+                    if let Ok(v) = text.parse::<i64>() {
+                        return Some(FeatureAttrValType::Int(v));
+                    }
+                }
+                text.parse::<f64>().ok().map(FeatureAttrValType::Double)
+            }
+            'L' => match bytes.first() {
+                Some(b'T') | Some(b't') | Some(b'Y') | Some(b'y') => {
+                    Some(FeatureAttrValType::Bool(true))
+                }
+                Some(b'F') | Some(b'f') | Some(b'N') | Some(b'n') => {
+                    Some(FeatureAttrValType::Bool(false))
+                }
+                _ => None,
+            },
+            'D' => {
+                let text = text();
+                if text.len() == 8 && text.chars().all(|c| c.is_ascii_digit()) {
+                    Some(FeatureAttrValType::String(format!(
+                        "{}-{}-{}",
+                        &text[0..4],
+                        &text[4..6],
+                        &text[6..8]
+                    )))
+                } else {
+                    None
+                }
+            }
+            'I' if bytes.len() == 4 => Some(FeatureAttrValType::Int(i32_le(bytes, 0) as i64)),
+            'O' if bytes.len() == 8 => Some(FeatureAttrValType::Double(f64_le(bytes, 0))),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_read_shape() {
+    let mut data = Vec::new();
+    data.extend_from_slice(&5i32.to_le_bytes());
+    data.extend_from_slice(&[0; 32]);
+    data.extend_from_slice(&3i32.to_le_bytes()); // parts
+    data.extend_from_slice(&13i32.to_le_bytes()); // points
+    for start in [0i32, 5, 9].iter() {
+        data.extend_from_slice(&start.to_le_bytes());
+    }
+    let points: [(f64, f64); 13] = [
+        // clockwise exterior
+        (0.0, 0.0),
+        (0.0, 10.0),
+        (10.0, 10.0),
+        (10.0, 0.0),
+        (0.0, 0.0),
+        // counter-clockwise hole
+        (2.0, 2.0),
+        (4.0, 2.0),
+        (4.0, 4.0),
+        (2.0, 2.0),
+        // second exterior
+        (20.0, 0.0),
+        (20.0, 5.0),
+        (25.0, 0.0),
+        (20.0, 0.0),
+    ];
+    for (x, y) in points.iter() {
+        data.extend_from_slice(&x.to_le_bytes());
+        data.extend_from_slice(&y.to_le_bytes());
+    }
+    match read_shape(&data, Some(2056), None) {
+        Ok(Some(GeometryType::MultiPolygon(mp))) => {
+            assert_eq!(mp.polygons.len(), 2);
+            assert_eq!(mp.polygons[0].rings.len(), 2);
+            assert_eq!(mp.polygons[1].rings[0].points[2].x, 25.0);
+            assert_eq!(mp.srid, Some(2056));
+        }
+        geom => panic!("Unexpected geometry {:?}", geom),
+    }
+    assert!(read_shape(&data[..60], None, None).is_err());
+    assert!(matches!(
+        read_shape(&0i32.to_le_bytes(), None, None),
+        Ok(None)
+    ));
+}
+
+#[test]
+fn test_prj_srid() {
+    assert_eq!(
+        prj_srid(
+            r#"GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984",SPHEROID["WGS_1984",6378137.0,298.257223563]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]]"#
+        ),
+        Some(4326)
+    );
+    assert_eq!(
+        prj_srid(
+            r#"PROJCS["WGS 84 / Pseudo-Mercator",GEOGCS["WGS 84",AUTHORITY["EPSG","4326"]],PROJECTION["Mercator_1SP"],AUTHORITY["EPSG","3857"]]"#
+        ),
+        Some(3857)
+    );
+    assert_eq!(prj_srid(r#"PROJCS["Unknown",GEOGCS["GCS"]]"#), None);
+}
@@ -0,0 +1,110 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::shapefile_ds::ShapefileDatasource;
+use crate::datasource::shapefile_reader::{sibling_file, DbfEncoding, DbfReader, ShapeReader};
+use crate::datasource::DatasourceType;
+use tile_grid::Extent;
+use tile_grid::Grid;
+
+const SHP: &str = "../data/g1k18.shp";
+
+#[test]
+fn test_shape_reader() {
+    let mut reader = ShapeReader::open(SHP).unwrap();
+    assert_eq!(reader.shape_type, 5);
+    assert_eq!(reader.len(), 26);
+    assert!(reader.record_extent(0).unwrap().is_some());
+
+    let dbf = sibling_file(SHP, "dbf").unwrap();
+    let mut dbf = DbfReader::open(&dbf, DbfEncoding::Latin1).unwrap();
+    assert_eq!(dbf.record_count, 26);
+    assert_eq!(dbf.fields.len(), 20);
+    let values = dbf.record(0).unwrap().unwrap();
+    assert_eq!(values[0], Some(FeatureAttrValType::Int(1)));
+    assert_eq!(
+        values[1],
+        Some(FeatureAttrValType::String("Zürich".to_string()))
+    );
+    assert!(dbf.record(26).unwrap().is_none());
+
+    assert!(ShapeReader::open("../data/missing.shp").is_err());
+    assert!(ShapeReader::open("../data/g1k18.dbf").is_err());
+}
+
+#[test]
+fn test_detect_layers() {
+    let ds = ShapefileDatasource::new(SHP);
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "g1k18");
+    assert_eq!(layers[0].geometry_type, Some("POLYGON".to_string()));
+    assert_eq!(layers[0].srid, Some(2056));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(cols.len(), 20);
+    assert_eq!(cols[1].0, "KTNAME");
+
+    // No reprojection from LV95
+    assert_eq!(ds.layer_extent(&layers[0], 3857), None);
+}
+
+#[test]
+fn test_no_transform() {
+    let mut layer = Layer::new("g1k18");
+    layer.srid = Some(2056);
+    layer.no_transform = true;
+    let ds = ShapefileDatasource::new(SHP);
+    let ext = ds.layer_extent(&layer, 3857);
+    let extent_fake =
+        "Some(Extent { minx: 22.32694, miny: 9.61387, maxx: 25.45679, maxy: 11.56232 })";
+    assert_eq!(format!("{:.5?}", ext), extent_fake);
+}
+
+#[test]
+fn test_retrieve_features() {
+    let mut layer = Layer::new("g1k18");
+    layer.srid = Some(2056);
+    layer.no_transform = true;
+    let grid = Grid::web_mercator();
+    let extent = Extent {
+        minx: 2600000.0,
+        miny: 1199000.0,
+        maxx: 2601000.0,
+        maxy: 1200000.0,
+    };
+
+    let mut ds = ShapefileDatasource::new(SHP).connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut features = Vec::new();
+    ds.retrieve_features("ts", &layer, &extent, 10, &grid, |feat| {
+        assert!(feat.geometry().is_ok());
+        features.push((feat.fid(), feat.attributes()[1].value.clone()));
+    });
+    assert_eq!(
+        features,
+        vec![(
+            Some(1),
+            FeatureAttrValType::String("Bern / Berne".to_string())
+        )]
+    );
+
+    layer.fid_field = Some("KTNR".to_string());
+    ds.retrieve_features("ts", &layer, &extent, 10, &grid, |feat| {
+        assert_eq!(feat.fid(), Some(2));
+    });
+}
+
+#[test]
+fn test_gen_runtime_config() {
+    let ds = ShapefileDatasource::new(SHP);
+    assert_eq!(
+        ds.gen_runtime_config(),
+        "\n[[datasource]]\nshapefile = \"../data/g1k18.shp\"\n"
+    );
+}
@@ -0,0 +1,810 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Native SpatiaLite datasource (https://www.gaia-gis.it/fossil/libspatialite), without GDAL
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::reproject::{
+    layer_srid, swap_extent_axes, transform_extent, transformation, CoordTransform,
+};
+use crate::datasource::sqlite_db::{
+    find_table, quote_ident, read_rows, table_info, SpatialIndex, SqliteDb, TableColumn, TableInfo,
+};
+use crate::datasource::wkb_reader::read_wkb;
+use crate::datasource::DatasourceType;
+use rusqlite::types::Value as SqliteValue;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use tile_grid::{Extent, Grid};
+
+#[derive(Clone)]
+pub struct SpatialiteDatasource {
+    pub path: String,
+    db: SqliteDb,
+    /// Transformation into grid SRS for layers which need reprojection
+    geom_transform: BTreeMap<String, CoordTransform>,
+}
+
+/// Entry of `geometry_columns`
+struct GeomColumn {
+    table_name: String,
+    column_name: String,
+    geometry_type: String,
+    srid: Option<i32>,
+}
+
+/// Geometry type name of a SpatiaLite geometry class (dimension codes are ignored)
+fn geometry_type_name(class: i64) -> &'static str {
+    match class % 1000 {
+        1 => "POINT",
+        2 => "LINESTRING",
+        3 => "POLYGON",
+        4 => "MULTIPOINT",
+        5 => "MULTILINESTRING",
+        6 => "MULTIPOLYGON",
+        7 => "GEOMETRYCOLLECTION",
+        _ => "GEOMETRY",
+    }
+}
+
+impl SpatialiteDatasource {
+    pub fn new(path: &str) -> SpatialiteDatasource {
+        SpatialiteDatasource {
+            path: path.to_string(),
+            db: SqliteDb::new(path),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    /// Geometry columns of SpatiaLite 4 (`geometry_type`) or legacy (`type`) metadata
+    fn geometry_columns(conn: &Connection) -> Result<Vec<GeomColumn>, String> {
+        let geom_cols = table_info(conn, "geometry_columns")?;
+        let geometry_type = if geom_cols.column_index("geometry_type").is_some() {
+            "geometry_type"
+        } else if geom_cols.column_index("type").is_some() {
+            "type"
+        } else {
+            "NULL"
+        };
+        let sql = format!(
+            "SELECT f_table_name, f_geometry_column, srid, {} FROM geometry_columns",
+            geometry_type
+        );
+        let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let columns = stmt
+            .query_map([], |row| {
+                let geometry_type = match row.get::<_, SqliteValue>(3)? {
+                    SqliteValue::Integer(class) => Some(geometry_type_name(class).to_string()),
+                    SqliteValue::Text(name) => Some(name.to_uppercase()),
+                    _ => None,
+                };
+                Ok(GeomColumn {
+                    table_name: row.get(0)?,
+                    column_name: row.get(1)?,
+                    geometry_type: geometry_type.unwrap_or("GEOMETRY".to_string()),
+                    srid: row.get::<_, Option<i32>>(2)?.filter(|srid| *srid > 0),
+                })
+            })
+            .and_then(|rows| rows.collect())
+            .map_err(|e| e.to_string())?;
+        Ok(columns)
+    }
+    /// Table and geometry column of layer
+    fn layer_table(conn: &Connection, layer: &Layer) -> Result<(TableInfo, usize), String> {
+        let table_name = layer.table_name.as_ref().unwrap_or(&layer.name);
+        let table = table_info(conn, table_name)?;
+        let geom_name = match layer.geometry_field {
+            Some(ref field) => field.clone(),
+            None => Self::geometry_columns(conn)?
+                .into_iter()
+                .find(|col| col.table_name.eq_ignore_ascii_case(table_name))
+                .map(|col| col.column_name)
+                .ok_or(format!(
+                    "No geometry column found in table '{}'",
+                    table_name
+                ))?,
+        };
+        let geom_idx = table
+            .column_index(&geom_name)
+            .ok_or(format!("Geometry column '{}' not found", geom_name))?;
+        Ok((table, geom_idx))
+    }
+    /// Layer extent from statistics tables (in layer SRS)
+    fn statistics_extent(
+        conn: &Connection,
+        table_name: &str,
+        geom_column: &str,
+    ) -> Result<Option<Extent>, String> {
+        // SpatiaLite 4 and legacy statistics
+        for (stats_table, table_col, geom_col) in &[
+            (
+                "geometry_columns_statistics",
+                "f_table_name",
+                "f_geometry_column",
+            ),
+            ("layer_statistics", "table_name", "geometry_column"),
+        ] {
+            if find_table(conn, stats_table)?.is_none() {
+                continue;
+            }
+            let sql = format!(
+                "SELECT extent_min_x, extent_min_y, extent_max_x, extent_max_y FROM {} \
+                 WHERE {} = ?1 COLLATE NOCASE AND {} = ?2 COLLATE NOCASE \
+                   AND extent_min_x IS NOT NULL AND extent_min_y IS NOT NULL \
+                   AND extent_max_x IS NOT NULL AND extent_max_y IS NOT NULL",
+                stats_table, table_col, geom_col
+            );
+            let extent = conn
+                .query_row(&sql, params![table_name, geom_column], |row| {
+                    Ok(Extent {
+                        minx: row.get(0)?,
+                        miny: row.get(1)?,
+                        maxx: row.get(2)?,
+                        maxy: row.get(3)?,
+                    })
+                })
+                .optional()
+                .map_err(|e| e.to_string())?;
+            if extent.is_some() {
+                return Ok(extent);
+            }
+        }
+        Ok(None)
+    }
+    /// Layer extent from geometry MBRs (in layer SRS)
+    fn scanned_extent(
+        conn: &Connection,
+        table: &TableInfo,
+        geom_idx: usize,
+    ) -> Result<Option<Extent>, String> {
+        let sql = format!(
+            "SELECT {} FROM {}",
+            quote_ident(&table.columns[geom_idx].name),
+            quote_ident(&table.name)
+        );
+        let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+        let mut extent: Option<Extent> = None;
+        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+            if let Ok(Some(blob)) = row.get::<_, Option<Vec<u8>>>(0) {
+                if let Ok([minx, miny, maxx, maxy]) =
+                    SpatialiteBlob::parse(&blob).map(|blob| blob.mbr)
+                {
+                    extent = Some(match extent {
+                        Some(ref ext) => Extent {
+                            minx: ext.minx.min(minx),
+                            miny: ext.miny.min(miny),
+                            maxx: ext.maxx.max(maxx),
+                            maxy: ext.maxy.max(maxy),
+                        },
+                        None => Extent {
+                            minx,
+                            miny,
+                            maxx,
+                            maxy,
+                        },
+                    });
+                }
+            }
+        }
+        Ok(extent)
+    }
+}
+
+/// SpatiaLite geometry blob (https://www.gaia-gis.it/gaia-sins/BLOB-Geometry.html)
+struct SpatialiteBlob<'a> {
+    little_endian: bool,
+    /// Bounding box (minx, miny, maxx, maxy)
+    mbr: [f64; 4],
+    /// Class type followed by geometry
+    geometry: &'a [u8],
+    /// Compact point format of SpatiaLite 4.3 without MBR
+    tiny_point: bool,
+}
+
+const BLOB_START: u8 = 0x00;
+const BLOB_MBR_END: u8 = 0x7C;
+const BLOB_ENTITY: u8 = 0x69;
+const BLOB_END: u8 = 0xFE;
+
+impl<'a> SpatialiteBlob<'a> {
+    fn parse(blob: &'a [u8]) -> Result<SpatialiteBlob<'a>, String> {
+        if blob.len() < 2 || blob[0] != BLOB_START || blob.last() != Some(&BLOB_END) {
+            return Err("Invalid SpatiaLite geometry".to_string());
+        }
+        let little_endian = blob[1] & 0x01 != 0;
+        let tiny_point = blob[1] & 0x80 != 0;
+        let f64_at = |pos: usize| -> Result<f64, String> {
+            let mut b = [0u8; 8];
+            b.copy_from_slice(
+                blob.get(pos..pos + 8)
+                    .ok_or("SpatiaLite geometry truncated")?,
+            );
+            Ok(if little_endian {
+                f64::from_le_bytes(b)
+            } else {
+                f64::from_be_bytes(b)
+            })
+        };
+        if tiny_point {
+            // Start, endian, srid, class type, coordinates, end
+            let (x, y) = (f64_at(10)?, f64_at(18)?);
+            return Ok(SpatialiteBlob {
+                little_endian,
+                mbr: [x, y, x, y],
+                geometry: &blob[6..blob.len() - 1],
+                tiny_point,
+            });
+        }
+        if blob.len() < 44 || blob[38] != BLOB_MBR_END {
+            return Err("Invalid SpatiaLite geometry".to_string());
+        }
+        Ok(SpatialiteBlob {
+            little_endian,
+            mbr: [f64_at(6)?, f64_at(14)?, f64_at(22)?, f64_at(30)?],
+            geometry: &blob[39..blob.len() - 1],
+            tiny_point,
+        })
+    }
+    fn intersects(&self, extent: &Extent) -> bool {
+        let [minx, miny, maxx, maxy] = self.mbr;
+        minx <= extent.maxx && maxx >= extent.minx && miny <= extent.maxy && maxy >= extent.miny
+    }
+    /// Geometry converted to ISO WKB
+    fn to_wkb(&self) -> Result<Vec<u8>, String> {
+        let mut conv = BlobConverter {
+            data: self.geometry,
+            pos: 0,
+            little_endian: self.little_endian,
+            wkb: Vec::with_capacity(self.geometry.len() + 16),
+        };
+        let class = conv.read_u32()?;
+        if self.tiny_point && class % 1000 != 1 {
+            return Err(format!("Invalid SpatiaLite point class {}", class));
+        }
+        conv.geometry(class)?;
+        Ok(conv.wkb)
+    }
+}
+
+/// Converter of SpatiaLite geometry entities into little endian ISO WKB
+struct BlobConverter<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+    wkb: Vec<u8>,
+}
+
+impl<'a> BlobConverter<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or("SpatiaLite geometry truncated")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+    fn read_u32(&mut self) -> Result<u32, String> {
+        let mut b = [0u8; 4];
+        b.copy_from_slice(self.bytes(4)?);
+        Ok(if self.little_endian {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    }
+    fn read_f32(&mut self) -> Result<f32, String> {
+        let mut b = [0u8; 4];
+        b.copy_from_slice(self.bytes(4)?);
+        Ok(if self.little_endian {
+            f32::from_le_bytes(b)
+        } else {
+            f32::from_be_bytes(b)
+        })
+    }
+    fn read_f64(&mut self) -> Result<f64, String> {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(self.bytes(8)?);
+        Ok(if self.little_endian {
+            f64::from_le_bytes(b)
+        } else {
+            f64::from_be_bytes(b)
+        })
+    }
+    fn put_u32(&mut self, v: u32) {
+        self.wkb.extend_from_slice(&v.to_le_bytes());
+    }
+    fn put_f64(&mut self, v: f64) {
+        self.wkb.extend_from_slice(&v.to_le_bytes());
+    }
+    /// Geometry of class type (1-7, +1000 Z, +2000 M, +3000 ZM, +1000000 compressed)
+    fn geometry(&mut self, class: u32) -> Result<(), String> {
+        let compressed = class > 1_000_000;
+        let class = class % 1_000_000;
+        let (base, dim) = (class % 1000, class / 1000);
+        if dim > 3 {
+            return Err(format!("Invalid SpatiaLite geometry class {}", class));
+        }
+        let dims = match dim {
+            0 => 2,
+            3 => 4,
+            _ => 3,
+        };
+        let has_m = dim >= 2;
+        self.wkb.push(1);
+        self.put_u32(class);
+        match base {
+            1 => {
+                for _ in 0..dims {
+                    let v = self.read_f64()?;
+                    self.put_f64(v);
+                }
+            }
+            2 => self.points(dims, has_m, compressed)?,
+            3 => {
+                let nrings = self.read_u32()?;
+                self.put_u32(nrings);
+                for _ in 0..nrings {
+                    self.points(dims, has_m, compressed)?;
+                }
+            }
+            4..=7 => {
+                let nentities = self.read_u32()?;
+                self.put_u32(nentities);
+                for _ in 0..nentities {
+                    if self.bytes(1)?[0] != BLOB_ENTITY {
+                        return Err("Invalid SpatiaLite collection entity".to_string());
+                    }
+                    let class = self.read_u32()?;
+                    self.geometry(class)?;
+                }
+            }
+            _ => return Err(format!("Unsupported SpatiaLite geometry class {}", class)),
+        }
+        Ok(())
+    }
+    /// Point sequence. Compressed sequences store the first and last point as doubles
+    /// and intermediate points as float offsets to the previous point (except M values).
+    fn points(&mut self, dims: usize, has_m: bool, compressed: bool) -> Result<(), String> {
+        let npoints = self.read_u32()?;
+        self.put_u32(npoints);
+        let mut last = [0f64; 4];
+        for i in 0..npoints {
+            let full = !compressed || i == 0 || i == npoints - 1;
+            for (d, v) in last.iter_mut().enumerate().take(dims) {
+                if full || (has_m && d == dims - 1) {
+                    *v = self.read_f64()?;
+                } else {
+                    *v += f64::from(self.read_f32()?);
+                }
+            }
+            for v in last.iter().take(dims) {
+                self.put_f64(*v);
+            }
+        }
+        Ok(())
+    }
+}
+
+struct SpatialiteFeature<'a> {
+    layer: &'a Layer,
+    columns: &'a [TableColumn],
+    geom_idx: usize,
+    rowid: i64,
+    values: Vec<SqliteValue>,
+    srid: Option<i32>,
+    transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> SpatialiteFeature<'a> {
+    fn attr_value(&self, idx: usize) -> Option<FeatureAttrValType> {
+        match self.values[idx] {
+            SqliteValue::Null | SqliteValue::Blob(_) => None,
+            SqliteValue::Integer(v) if self.columns[idx].decl_type == "BOOLEAN" => {
+                Some(FeatureAttrValType::Bool(v != 0))
+            }
+            SqliteValue::Integer(v) => Some(FeatureAttrValType::Int(v)),
+            SqliteValue::Real(v) => Some(FeatureAttrValType::Double(v)),
+            SqliteValue::Text(ref v) => Some(FeatureAttrValType::String(v.clone())),
+        }
+    }
+}
+
+impl<'a> Feature for SpatialiteFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            return if self.rowid >= 0 {
+                Some(self.rowid as u64)
+            } else {
+                None
+            };
+        }
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                self.columns
+                    .iter()
+                    .position(|col| col.name == *field)
+                    .and_then(|idx| self.attr_value(idx))
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        let mut attrs = Vec::new();
+        for (idx, col) in self.columns.iter().enumerate() {
+            // The INTEGER PRIMARY KEY is the feature id
+            if idx == self.geom_idx || col.rowid_alias {
+                continue;
+            }
+            match self.attr_value(idx) {
+                Some(value) => attrs.push(FeatureAttr {
+                    key: col.name.clone(),
+                    value,
+                }),
+                None => {
+                    if let SqliteValue::Blob(_) = self.values[idx] {
+                        warn!(
+                            "Layer '{}' - skipping unsupported blob field '{}'",
+                            self.layer.name, col.name
+                        );
+                    }
+                }
+            }
+        }
+        attrs
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        match self.values[self.geom_idx] {
+            SqliteValue::Blob(ref blob) => {
+                let wkb = SpatialiteBlob::parse(blob)?.to_wkb()?;
+                read_wkb(&wkb, self.srid, Some(self.transform))
+            }
+            _ => Err("Geometry is NULL".to_string()),
+        }
+    }
+}
+
+impl DatasourceType for SpatialiteDatasource {
+    /// New instance with connected pool
+    fn connected(&self) -> SpatialiteDatasource {
+        SpatialiteDatasource {
+            path: self.path.clone(),
+            db: self.db.connected(),
+            geom_transform: BTreeMap::new(),
+        }
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let columns = self.db.with_conn(Self::geometry_columns);
+        let columns = match columns {
+            Ok(columns) => columns,
+            Err(e) => {
+                error!("SpatiaLite '{}': {}", self.path, e);
+                return Vec::new();
+            }
+        };
+        let mut layers: Vec<Layer> = Vec::new();
+        let ntables = |table: &str| columns.iter().filter(|c| c.table_name == table).count();
+        for col in &columns {
+            // Name layers of tables with multiple geometry columns like GDAL
+            let name = if ntables(&col.table_name) > 1 {
+                format!("{}_{}", col.table_name, col.column_name)
+            } else {
+                col.table_name.clone()
+            };
+            let mut layer = Layer::new(&name);
+            layer.table_name = Some(col.table_name.clone());
+            layer.geometry_field = Some(col.column_name.clone());
+            layer.geometry_type = Some(col.geometry_type.clone());
+            layer.srid = col.srid;
+            layers.push(layer);
+        }
+        layers
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        let table = self.db.with_conn(|conn| Self::layer_table(conn, layer));
+        match table {
+            Ok((table, geom_idx)) => table
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|&(idx, col)| idx != geom_idx && !col.rowid_alias)
+                .map(|(_, col)| (col.name.clone(), String::new()))
+                .collect(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Detect extent of layer (in WGS84)
+    fn layer_extent(&self, layer: &Layer, grid_srid: i32) -> Option<Extent> {
+        let extent = self.db.with_conn(|conn| {
+            let (table, geom_idx) = Self::layer_table(conn, layer)?;
+            let geom_column = &table.columns[geom_idx].name;
+            match Self::statistics_extent(conn, &table.name, geom_column)? {
+                Some(extent) => Ok(Some(extent)),
+                None => Self::scanned_extent(conn, &table, geom_idx),
+            }
+        });
+        let extent = match extent {
+            Ok(Some(extent)) => extent,
+            Ok(None) => {
+                warn!("Layer '{}': Unable to get extent", layer.name);
+                return None;
+            }
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                return None;
+            }
+        };
+        let extent = if layer.swap_axes() {
+            swap_extent_axes(&extent)
+        } else {
+            extent
+        };
+        let src_srid = layer_srid(layer, grid_srid).unwrap_or(0);
+        match transformation(src_srid, 4326) {
+            Some(Some(transform)) => Some(transform_extent(&extent, transform)),
+            Some(None) => Some(extent),
+            None => {
+                info!(
+                    "Couldn't detect extent of layer {}, because reprojection from SRID {} is not supported",
+                    layer.name, src_srid
+                );
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if let Err(e) = self.db.with_conn(|conn| Self::layer_table(conn, layer)) {
+            error!("Layer '{}': {}", layer.name, e);
+            return;
+        }
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for SpatiaLite layers",
+                layer.name
+            );
+        }
+        if !layer.no_transform {
+            match layer.srid {
+                Some(srid) => match transformation(srid, grid_srid) {
+                    Some(Some(transform)) => {
+                        info!(
+                            "Layer '{}': Reprojecting geometry to SRID {}",
+                            layer.name, grid_srid
+                        );
+                        self.geom_transform.insert(layer.name.clone(), transform);
+                    }
+                    Some(None) => {}
+                    None => error!(
+                        "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                        layer.name, srid, grid_srid
+                    ),
+                },
+                None => warn!("Layer '{}': Couldn't detect spatialref", layer.name),
+            }
+        }
+        if layer.simplify && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Simplification not supported for SpatiaLite layers",
+                layer.name
+            );
+        }
+        if layer.buffer_size.is_some() && layer.geometry_type != Some("POINT".to_string()) {
+            warn!(
+                "Layer '{}': Clipping with buffer_size not supported for SpatiaLite layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let mut bbox_extent = if let Some(pixels) = layer.buffer_size {
+            let pixel_width = grid.pixel_width(zoom);
+            let buf = f64::from(pixels) * pixel_width;
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        let layer_transform = self.geom_transform.get(&layer.name).cloned();
+        if layer_transform.is_some() {
+            // Spatial filter must be in layer SRS
+            let inverse = layer
+                .srid
+                .and_then(|srid| transformation(grid.srid, srid))
+                .and_then(|tr| tr);
+            if let Some(inverse) = inverse {
+                bbox_extent = transform_extent(&bbox_extent, inverse);
+            }
+        }
+        let swap_axes = layer.swap_axes();
+        if swap_axes {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+
+        let result = self.db.with_conn(|conn| {
+            let (table, geom_idx) = Self::layer_table(conn, layer)?;
+            debug!("retrieve_features layer: {}", table.name);
+            let mut cnt = 0;
+            let query_limit = layer.query_limit.unwrap_or(0);
+            let read_row = |rowid: i64, values: Vec<SqliteValue>| {
+                let intersects = match values[geom_idx] {
+                    SqliteValue::Blob(ref blob) => SpatialiteBlob::parse(blob)
+                        .map(|blob| blob.intersects(&bbox_extent))
+                        .unwrap_or(true),
+                    _ => false,
+                };
+                if !intersects {
+                    return true;
+                }
+                let feat = SpatialiteFeature {
+                    layer,
+                    columns: &table.columns,
+                    geom_idx,
+                    rowid,
+                    values,
+                    srid: Some(grid.srid),
+                    transform: &transform,
+                };
+                read(&feat);
+                cnt += 1;
+                if cnt == query_limit as u64 {
+                    info!(
+                        "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                        layer.name, cnt, zoom
+                    );
+                    return false;
+                }
+                true
+            };
+
+            let index = SpatialIndex {
+                table: format!("idx_{}_{}", table.name, table.columns[geom_idx].name),
+                columns: ["pkid", "xmin", "xmax", "ymin", "ymax"],
+            };
+            read_rows(
+                conn,
+                &table,
+                Some(&index),
+                &bbox_extent,
+                layer.filter.as_deref(),
+                read_row,
+            )?;
+            Ok(cnt)
+        });
+        match result {
+            Ok(cnt) => cnt,
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                0
+            }
+        }
+    }
+    fn supports_filter(&self) -> bool {
+        true
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for SpatialiteDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        Ok(SpatialiteDatasource::new(
+            ds_cfg.spatialite.as_ref().unwrap(),
+        ))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "spatialite"
+# SpatiaLite database (read without GDAL)
+spatialite = "<filename>.sqlite"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+spatialite = "{}"
+"#,
+            self.path
+        )
+    }
+}
+
+#[test]
+fn test_blob_to_wkb() {
+    // Compressed LINESTRING (0 0, 1 2, 3 3), big endian
+    let mut blob = vec![BLOB_START, 0];
+    blob.extend_from_slice(&4326i32.to_be_bytes());
+    for v in &[0.0f64, 0.0, 3.0, 3.0] {
+        blob.extend_from_slice(&v.to_be_bytes());
+    }
+    blob.push(BLOB_MBR_END);
+    blob.extend_from_slice(&1_000_002u32.to_be_bytes());
+    blob.extend_from_slice(&3u32.to_be_bytes());
+    blob.extend_from_slice(&0.0f64.to_be_bytes());
+    blob.extend_from_slice(&0.0f64.to_be_bytes());
+    blob.extend_from_slice(&1.0f32.to_be_bytes());
+    blob.extend_from_slice(&2.0f32.to_be_bytes());
+    blob.extend_from_slice(&3.0f64.to_be_bytes());
+    blob.extend_from_slice(&3.0f64.to_be_bytes());
+    blob.push(BLOB_END);
+
+    let blob = SpatialiteBlob::parse(&blob).unwrap();
+    assert_eq!(blob.mbr, [0.0, 0.0, 3.0, 3.0]);
+    assert!(blob.intersects(&Extent {
+        minx: 2.0,
+        miny: 2.0,
+        maxx: 5.0,
+        maxy: 5.0
+    }));
+    match read_wkb(&blob.to_wkb().unwrap(), None, None).unwrap() {
+        GeometryType::LineString(line) => {
+            let coords: Vec<(f64, f64)> = line.points.iter().map(|p| (p.x, p.y)).collect();
+            assert_eq!(coords, vec![(0.0, 0.0), (1.0, 2.0), (3.0, 3.0)]);
+        }
+        g => panic!("unexpected {:?}", g),
+    }
+
+    // TinyPoint Z (7 46 500), little endian
+    let mut blob = vec![BLOB_START, 0x81];
+    blob.extend_from_slice(&4326i32.to_le_bytes());
+    blob.extend_from_slice(&1001u32.to_le_bytes());
+    for v in &[7.0f64, 46.0, 500.0] {
+        blob.extend_from_slice(&v.to_le_bytes());
+    }
+    blob.push(BLOB_END);
+    let blob = SpatialiteBlob::parse(&blob).unwrap();
+    assert_eq!(blob.mbr, [7.0, 46.0, 7.0, 46.0]);
+    match read_wkb(&blob.to_wkb().unwrap(), None, None).unwrap() {
+        GeometryType::Point(p) => assert_eq!((p.x, p.y), (7.0, 46.0)),
+        g => panic!("unexpected {:?}", g),
+    }
+
+    assert!(SpatialiteBlob::parse(b"GP\0\x01").is_err());
+}
@@ -0,0 +1,94 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::spatialite_ds::SpatialiteDatasource;
+use crate::datasource::test_helpers::{assert_point, bern_extent, BERN};
+use crate::datasource::DatasourceType;
+use tile_grid::Grid;
+
+const SPATIALITE: &str = "../data/places.sqlite";
+
+#[test]
+fn test_detect_layers() {
+    let ds = SpatialiteDatasource::new(SPATIALITE);
+    let layers = ds.detect_layers(true);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+    assert_eq!(layers[0].geometry_field, Some("geometry".to_string()));
+    assert_eq!(layers[0].geometry_type, Some("GEOMETRY".to_string()));
+    assert_eq!(layers[0].srid, Some(4326));
+
+    let cols = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        cols.iter().map(|c| c.0.as_str()).collect::<Vec<_>>(),
+        vec!["name", "population", "capital", "area"]
+    );
+
+    let extent = ds.layer_extent(&layers[0], 3857);
+    assert_eq!(
+        format!("{:.4?}", extent),
+        "Some(Extent { minx: 6.1432, miny: 46.2044, maxx: 8.5417, maxy: 47.3769 })"
+    );
+}
+
+#[test]
+fn test_retrieve_features() {
+    let mut layer = Layer::new("places");
+    layer.srid = Some(4326);
+    let grid = Grid::web_mercator();
+
+    let mut ds = SpatialiteDatasource::new(SPATIALITE);
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut features = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        if feat.fid() == Some(1) {
+            assert_point(feat.geometry(), BERN);
+            let attrs = feat.attributes();
+            assert_eq!(attrs.len(), 3);
+            assert_eq!(attrs[1].key, "population");
+            assert_eq!(attrs[1].value, FeatureAttrValType::Int(133883));
+        } else {
+            assert!(feat.geometry().is_ok());
+        }
+        features.push((feat.fid(), feat.attributes()[0].value.clone()));
+    });
+    assert_eq!(
+        features,
+        vec![
+            (Some(1), FeatureAttrValType::String("Bern".to_string())),
+            (Some(4), FeatureAttrValType::String("Aare".to_string())),
+            (
+                Some(5),
+                FeatureAttrValType::String("Bremgartenwald".to_string())
+            ),
+        ]
+    );
+
+    layer.query_limit = Some(2);
+    let cnt = ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |_| {});
+    assert_eq!(cnt, 2);
+
+    layer.query_limit = None;
+    layer.filter = Some("population > 100000".to_string());
+    let mut ds = ds.connected();
+    ds.prepare_queries("ts", &layer, grid.srid);
+    let mut fids = Vec::new();
+    ds.retrieve_features("ts", &layer, &bern_extent(), 10, &grid, |feat| {
+        fids.push(feat.fid());
+    });
+    assert_eq!(fids, vec![Some(1)]);
+}
+
+#[test]
+fn test_gen_runtime_config() {
+    let ds = SpatialiteDatasource::new(SPATIALITE);
+    assert_eq!(
+        ds.gen_runtime_config(),
+        "\n[[datasource]]\nspatialite = \"../data/places.sqlite\"\n"
+    );
+}
@@ -0,0 +1,219 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Read-only access to SQLite database files (GeoPackage, SpatiaLite, MBTiles) with rusqlite
+
+use crate::datasource::pool::PoolSettings;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use tile_grid::Extent;
+
+/// Number of connections kept open per database file
+const POOL_SIZE: u16 = 8;
+
+fn open_flags() -> OpenFlags {
+    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX | OpenFlags::SQLITE_OPEN_URI
+}
+
+/// Pool of read-only connections to a database file
+#[derive(Clone)]
+pub struct SqliteDb {
+    pub path: String,
+    pool: Option<r2d2::Pool<SqliteConnectionManager>>,
+}
+
+impl SqliteDb {
+    pub fn new(path: &str) -> SqliteDb {
+        SqliteDb {
+            path: path.to_string(),
+            pool: None,
+        }
+    }
+    /// New instance with connected pool
+    pub fn connected(&self) -> SqliteDb {
+        // Fail early on missing or invalid files
+        if let Err(e) = SqliteDb::open(&self.path) {
+            error!("{}", e);
+            return SqliteDb::new(&self.path);
+        }
+        let manager = SqliteConnectionManager::file(&self.path).with_flags(open_flags());
+        let pool = PoolSettings::default()
+            .r2d2_builder(POOL_SIZE)
+            .build_unchecked(manager);
+        SqliteDb {
+            path: self.path.clone(),
+            pool: Some(pool),
+        }
+    }
+    /// Open a single connection and check that the file is an SQLite database
+    pub fn open(path: &str) -> Result<Connection, String> {
+        let err = |e: rusqlite::Error| format!("{}: {}", path, e);
+        let conn = Connection::open_with_flags(path, open_flags()).map_err(err)?;
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(err)?;
+        Ok(conn)
+    }
+    /// Run `f` with a pooled connection (or a new connection, if not connected)
+    pub fn with_conn<T, F>(&self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&Connection) -> Result<T, String>,
+    {
+        match self.pool {
+            Some(ref pool) => {
+                let conn = pool.get().map_err(|e| format!("{}: {}", self.path, e))?;
+                f(&conn)
+            }
+            None => f(&SqliteDb::open(&self.path)?),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableColumn {
+    pub name: String,
+    /// Declared type in upper case
+    pub decl_type: String,
+    /// INTEGER PRIMARY KEY column, i.e. the rowid
+    pub rowid_alias: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct TableInfo {
+    pub name: String,
+    pub columns: Vec<TableColumn>,
+}
+
+impl TableInfo {
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|col| col.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// R*Tree spatial index of a geometry column
+pub struct SpatialIndex {
+    pub table: String,
+    /// Columns containing the row id and minx, maxx, miny, maxy
+    pub columns: [&'static str; 5],
+}
+
+/// Quoted SQL identifier
+pub fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Name of an existing table or view (case-insensitive lookup)
+pub fn find_table(conn: &Connection, name: &str) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT name FROM sqlite_master WHERE type IN ('table', 'view') AND name = ?1 COLLATE NOCASE",
+        params![name],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Columns of a table or view
+pub fn table_info(conn: &Connection, name: &str) -> Result<TableInfo, String> {
+    let name = find_table(conn, name)?.ok_or(format!("Table '{}' not found", name))?;
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", quote_ident(&name)))
+        .map_err(|e| e.to_string())?;
+    let columns = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                row.get::<_, i64>(5)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    // Only a single INTEGER PRIMARY KEY column is an alias for the rowid
+    let npk = columns.iter().filter(|col| col.2 > 0).count();
+    let columns = columns
+        .into_iter()
+        .map(|(name, decl_type, pk)| {
+            let decl_type = decl_type.to_uppercase();
+            TableColumn {
+                rowid_alias: npk == 1 && pk > 0 && decl_type == "INTEGER",
+                name,
+                decl_type,
+            }
+        })
+        .collect();
+    Ok(TableInfo { name, columns })
+}
+
+/// Read rows (rowid and values of all table columns) in rowid order, until `read` returns false.
+/// Rows are selected with the spatial index, if available, and with an optional SQL condition.
+pub fn read_rows<F>(
+    conn: &Connection,
+    table: &TableInfo,
+    index: Option<&SpatialIndex>,
+    extent: &Extent,
+    filter: Option<&str>,
+    mut read: F,
+) -> Result<(), String>
+where
+    F: FnMut(i64, Vec<Value>) -> bool,
+{
+    let mut conditions = Vec::new();
+    let mut bbox = Vec::new();
+    match index {
+        Some(index) if find_table(conn, &index.table)?.is_some() => {
+            let [id, minx, maxx, miny, maxy] = index.columns;
+            conditions.push(format!(
+                "rowid IN (SELECT {} FROM {} WHERE {} <= ?1 AND {} >= ?2 AND {} <= ?3 AND {} >= ?4)",
+                id,
+                quote_ident(&index.table),
+                minx,
+                maxx,
+                miny,
+                maxy
+            ));
+            bbox = vec![extent.maxx, extent.minx, extent.maxy, extent.miny];
+        }
+        _ => debug!("Table '{}': no spatial index found", table.name),
+    }
+    if let Some(filter) = filter {
+        conditions.push(format!("({})", filter));
+    }
+    let columns: Vec<String> = table
+        .columns
+        .iter()
+        .map(|col| quote_ident(&col.name))
+        .collect();
+    let mut sql = format!(
+        "SELECT rowid, {} FROM {}",
+        columns.join(", "),
+        quote_ident(&table.name)
+    );
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+    sql.push_str(" ORDER BY rowid");
+    trace!("Query: {}", sql);
+    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(bbox))
+        .map_err(|e| e.to_string())?;
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        let rowid = row.get(0).map_err(|e| e.to_string())?;
+        let values = (1..=columns.len())
+            .map(|idx| row.get(idx))
+            .collect::<Result<Vec<Value>, _>>()
+            .map_err(|e| e.to_string())?;
+        if !read(rowid, values) {
+            break;
+        }
+    }
+    Ok(())
+}
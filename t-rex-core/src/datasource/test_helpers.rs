@@ -0,0 +1,33 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Fixtures shared by datasource tests
+
+use crate::core::geom::GeometryType;
+use tile_grid::Extent;
+
+/// Web Mercator extent around Bern (tile 10/532/360 with neighbours)
+pub fn bern_extent() -> Extent {
+    Extent {
+        minx: 821850.9,
+        miny: 5909499.5,
+        maxx: 860986.7,
+        maxy: 5948635.3,
+    }
+}
+
+/// Web Mercator position of Bern in the `places` test data
+pub const BERN: (f64, f64) = (829040.78, 5933590.48);
+
+/// Assert Web Mercator point geometry with a precision of 2 decimals
+pub fn assert_point(geom: Result<GeometryType, String>, (x, y): (f64, f64)) {
+    assert_eq!(
+        format!("{:.2?}", geom),
+        format!(
+            "Ok(Point(Point {{ x: {:.2}, y: {:.2}, srid: Some(3857) }}))",
+            x, y
+        )
+    );
+}
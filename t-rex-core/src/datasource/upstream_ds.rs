@@ -0,0 +1,364 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Datasource with features of vector tiles fetched from an upstream XYZ tile service.
+//! Layers of the upstream tiles can be combined with locally generated layers.
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::datasource::xyz_tile;
+use crate::datasource::db_url::ConnectParams;
+use crate::datasource::http_client::HttpClient;
+use crate::datasource::reproject::{transform_extent, transformation};
+use crate::datasource::wfs_client::URL_SCHEMES;
+use crate::datasource::DatasourceType;
+use crate::mvt::geom_decoder::decode_geometry;
+use crate::mvt::tile::{Tile, TileCompression};
+use crate::mvt::vector_tile;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tile_grid::{Extent, Grid};
+
+/// Number of decoded upstream tiles kept in memory, shared by the layers of a tile
+const TILE_CACHE_SIZE: usize = 32;
+
+type CachedTiles = VecDeque<((u8, u32, u32), Arc<vector_tile::Tile>)>;
+
+/// Attribute value of a vector tile
+fn tile_value(value: &vector_tile::Tile_Value) -> Option<FeatureAttrValType> {
+    if value.has_string_value() {
+        Some(FeatureAttrValType::String(
+            value.get_string_value().to_string(),
+        ))
+    } else if value.has_float_value() {
+        Some(FeatureAttrValType::Float(value.get_float_value()))
+    } else if value.has_double_value() {
+        Some(FeatureAttrValType::Double(value.get_double_value()))
+    } else if value.has_int_value() {
+        Some(FeatureAttrValType::Int(value.get_int_value()))
+    } else if value.has_uint_value() {
+        Some(FeatureAttrValType::UInt(value.get_uint_value()))
+    } else if value.has_sint_value() {
+        Some(FeatureAttrValType::SInt(value.get_sint_value()))
+    } else if value.has_bool_value() {
+        Some(FeatureAttrValType::Bool(value.get_bool_value()))
+    } else {
+        None
+    }
+}
+
+/// Geometry type of the first feature of an upstream layer
+fn layer_geometry_type(mvt_layer: &vector_tile::Tile_Layer) -> Option<&'static str> {
+    match mvt_layer.get_features().first()?.get_field_type() {
+        vector_tile::Tile_GeomType::POINT => Some("POINT"),
+        vector_tile::Tile_GeomType::LINESTRING => Some("LINESTRING"),
+        vector_tile::Tile_GeomType::POLYGON => Some("POLYGON"),
+        vector_tile::Tile_GeomType::UNKNOWN => None,
+    }
+}
+
+pub struct UpstreamFeature<'a> {
+    mvt_layer: &'a vector_tile::Tile_Layer,
+    feature: &'a vector_tile::Tile_Feature,
+    extent: &'a Extent,
+    srid: Option<i32>,
+    /// Selected attributes (empty: all)
+    fields: &'a [String],
+}
+
+impl<'a> Feature for UpstreamFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        if self.feature.has_id() {
+            Some(self.feature.get_id())
+        } else {
+            None
+        }
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        let keys = self.mvt_layer.get_keys();
+        let values = self.mvt_layer.get_values();
+        self.feature
+            .get_tags()
+            .chunks(2)
+            .filter_map(|tag| {
+                let key = keys.get(*tag.first()? as usize)?;
+                if !self.fields.is_empty() && !self.fields.contains(key) {
+                    return None;
+                }
+                let value = tile_value(values.get(*tag.get(1)? as usize)?)?;
+                Some(FeatureAttr {
+                    key: key.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        decode_geometry(
+            self.feature.get_field_type(),
+            self.feature.get_geometry(),
+            self.extent,
+            self.mvt_layer.get_extent(),
+            self.srid,
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct UpstreamDatasource {
+    /// Tile URL with `{z}`, `{x}` and `{y}` (or `{-y}` for TMS rows) placeholders
+    pub url: String,
+    http: HttpClient,
+    /// Path and query string with placeholders
+    path: String,
+    /// Recently fetched tiles, shared by connected instances
+    tiles: Arc<Mutex<CachedTiles>>,
+}
+
+impl UpstreamDatasource {
+    pub fn new(url: &str) -> Result<UpstreamDatasource, String> {
+        if !url.contains("{z}") || !url.contains("{x}") || !url.contains("y}") {
+            return Err(format!(
+                "Upstream tile URL '{}' must contain {{z}}, {{x}} and {{y}} placeholders",
+                url
+            ));
+        }
+        let https = url.starts_with("https://");
+        let params = ConnectParams::parse(url, URL_SCHEMES, if https { 443 } else { 80 })?;
+        let after_scheme = &url[url.find("://").unwrap() + 3..];
+        let path = match after_scheme.find('/') {
+            Some(pos) => after_scheme[pos..].to_string(),
+            None => "/".to_string(),
+        };
+        Ok(UpstreamDatasource {
+            url: url.to_string(),
+            http: HttpClient::new("Upstream", &params, https, Duration::from_secs(60)),
+            path,
+            tiles: Arc::new(Mutex::new(VecDeque::new())),
+        })
+    }
+    /// Request path of tile in XYZ scheme
+    pub fn tile_path(&self, zoom: u8, xtile: u32, ytile: u32) -> String {
+        let tms_y = (1u32 << zoom).saturating_sub(1).saturating_sub(ytile);
+        self.path
+            .replace("{z}", &zoom.to_string())
+            .replace("{x}", &xtile.to_string())
+            .replace("{-y}", &tms_y.to_string())
+            .replace("{y}", &ytile.to_string())
+    }
+    /// Fetch and decode tile. Missing tiles (HTTP 204 and 404) are empty.
+    fn fetch(&self, zoom: u8, xtile: u32, ytile: u32) -> Result<vector_tile::Tile, String> {
+        let path = self.tile_path(zoom, xtile, ytile);
+        debug!("Upstream tile request {}", path);
+        let response = self.http.get(&self.http.url(&path), &[])?;
+        let (status, body) = (response.status, response.body);
+        match status {
+            200 => {
+                // Tiles may be delivered compressed without Content-Encoding
+                let data = TileCompression::default().tile_content(body, false);
+                Tile::read_from(&mut data.as_slice())
+                    .map_err(|e| format!("Upstream tile {}: {}", path, e))
+            }
+            204 | 404 => Ok(vector_tile::Tile::new()),
+            _ => Err(format!("Upstream tile {}: HTTP {}", path, status)),
+        }
+    }
+    /// Decoded tile in XYZ scheme, from the in-memory cache if recently fetched
+    pub fn tile(&self, zoom: u8, xtile: u32, ytile: u32) -> Result<Arc<vector_tile::Tile>, String> {
+        let key = (zoom, xtile, ytile);
+        if let Some((_, tile)) = self.tiles.lock().unwrap().iter().find(|(k, _)| *k == key) {
+            return Ok(tile.clone());
+        }
+        let tile = Arc::new(self.fetch(zoom, xtile, ytile)?);
+        let mut tiles = self.tiles.lock().unwrap();
+        tiles.push_back((key, tile.clone()));
+        if tiles.len() > TILE_CACHE_SIZE {
+            tiles.pop_front();
+        }
+        Ok(tile)
+    }
+    /// Name of upstream layer
+    fn upstream_layer<'a>(&self, layer: &'a Layer) -> &'a str {
+        layer.table_name.as_deref().unwrap_or(&layer.name)
+    }
+}
+
+impl DatasourceType for UpstreamDatasource {
+    /// New instance sharing the tile cache
+    fn connected(&self) -> UpstreamDatasource {
+        self.clone()
+    }
+    fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        info!("Detecting layers from upstream tile 0/0/0");
+        let tile = match self.tile(0, 0, 0) {
+            Ok(tile) => tile,
+            Err(e) => {
+                error!("{}", e);
+                return Vec::new();
+            }
+        };
+        tile.get_layers()
+            .iter()
+            .map(|mvt_layer| {
+                let mut layer = Layer::new(mvt_layer.get_name());
+                layer.geometry_type = layer_geometry_type(mvt_layer).map(|t| t.to_string());
+                layer
+            })
+            .collect()
+    }
+    /// Attribute names of the upstream layer in tile 0/0/0
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        if !layer.fields.is_empty() {
+            return layer
+                .fields
+                .iter()
+                .map(|col| (col.clone(), String::new()))
+                .collect();
+        }
+        match self.tile(0, 0, 0) {
+            Ok(tile) => tile
+                .get_layers()
+                .iter()
+                .find(|l| l.get_name() == self.upstream_layer(layer))
+                .map(|l| {
+                    l.get_keys()
+                        .iter()
+                        .map(|key| (key.clone(), String::new()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    fn layer_extent(&self, _layer: &Layer, _grid_srid: i32) -> Option<Extent> {
+        None
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for upstream layers",
+                layer.name
+            );
+        }
+        if grid_srid != 3857 {
+            error!(
+                "Layer '{}': upstream tiles require a Web Mercator grid",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        match self.try_retrieve_features_at(tileset, layer, extent, zoom, grid, None, read) {
+            Ok(cnt) => cnt,
+            Err(err) => {
+                error!("Layer '{}': {}", layer.name, err);
+                0
+            }
+        }
+    }
+    fn try_retrieve_features_at<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        _time: Option<&str>,
+        mut read: F,
+    ) -> Result<u64, String>
+    where
+        F: FnMut(&dyn Feature),
+    {
+        if grid.srid != 3857 {
+            return Err("Upstream tiles require a Web Mercator grid".to_string());
+        }
+        let (xtile, ytile) = xyz_tile(extent, zoom, grid);
+        let tile = self.tile(zoom, xtile, ytile)?;
+        let name = self.upstream_layer(layer);
+        let mvt_layer = match tile.get_layers().iter().find(|l| l.get_name() == name) {
+            Some(mvt_layer) => mvt_layer,
+            None => return Ok(0),
+        };
+        let mut cnt = 0;
+        let query_limit = layer.query_limit.unwrap_or(0);
+        for feature in mvt_layer.get_features() {
+            let feat = UpstreamFeature {
+                mvt_layer,
+                feature,
+                extent,
+                srid: Some(grid.srid),
+                fields: &layer.fields,
+            };
+            read(&feat);
+            cnt += 1;
+            if cnt == query_limit as u64 {
+                info!(
+                    "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                    layer.name, cnt, zoom
+                );
+                break;
+            }
+        }
+        Ok(cnt)
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for UpstreamDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        UpstreamDatasource::new(ds_cfg.upstream.as_ref().unwrap())
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "upstream"
+# Vector tile service with {z}, {x} and {y} (or {-y} for TMS rows) placeholders.
+# Layers select the upstream layer with table_name (default: layer name) and
+# attributes with fields (default: all).
+upstream = "https://example.com/tiles/{z}/{x}/{y}.pbf"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+upstream = "{}"
+"#,
+            self.url
+        )
+    }
+}
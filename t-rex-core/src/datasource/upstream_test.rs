@@ -0,0 +1,168 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::FeatureAttrValType;
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::datasource::datasource::xyz_tile;
+use crate::datasource::upstream_ds::UpstreamDatasource;
+use crate::datasource::DatasourceType;
+use crate::mvt::tile::Tile;
+use crate::mvt::vector_tile;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use tile_grid::Grid;
+
+/// Upstream tile with a `places` layer containing one point
+fn upstream_tile() -> vector_tile::Tile {
+    let mut layer = vector_tile::Tile_Layer::new();
+    layer.set_version(2);
+    layer.set_name("places".to_string());
+    layer.set_extent(4096);
+    layer.mut_keys().push("name".to_string());
+    layer.mut_keys().push("population".to_string());
+    let mut value = vector_tile::Tile_Value::new();
+    value.set_string_value("Bern".to_string());
+    layer.mut_values().push(value);
+    let mut value = vector_tile::Tile_Value::new();
+    value.set_int_value(134_000);
+    layer.mut_values().push(value);
+    let mut feature = vector_tile::Tile_Feature::new();
+    feature.set_id(7);
+    feature.set_tags(vec![0, 0, 1, 1]);
+    feature.set_field_type(vector_tile::Tile_GeomType::POINT);
+    feature.set_geometry(vec![9, 50, 34]);
+    layer.mut_features().push(feature);
+    let mut tile = vector_tile::Tile::new();
+    tile.mut_layers().push(layer);
+    tile
+}
+
+/// HTTP server delivering the gzip compressed upstream tile for /tiles/1/1/0.pbf and /tiles/0/0/0.pbf.
+/// Returns base URL and requested paths.
+fn serve_tiles() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let logged = requests.clone();
+    let tile = Tile::tile_bytevec_gz(&upstream_tile());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut path = String::new();
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(request) = line.strip_prefix("GET ") {
+                    path = request.split(' ').next().unwrap().to_string();
+                }
+            }
+            logged.lock().unwrap().push(path.clone());
+            let _ = match path.as_str() {
+                "/tiles/1/1/0.pbf" | "/tiles/0/0/0.pbf" => write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                    tile.len()
+                )
+                .and_then(|_| stream.write_all(&tile)),
+                "/tiles/1/0/0.pbf" => {
+                    write!(stream, "HTTP/1.1 500 Error\r\nContent-Length: 0\r\n\r\n")
+                }
+                _ => write!(
+                    stream,
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"
+                ),
+            };
+        }
+    });
+    (format!("http://127.0.0.1:{}/tiles", port), requests)
+}
+
+#[test]
+fn test_tile_path() {
+    let ds =
+        UpstreamDatasource::new("https://user:pw@example.com/{z}/{x}/{-y}.pbf?key=abc").unwrap();
+    assert_eq!(ds.tile_path(3, 4, 1), "/3/4/6.pbf?key=abc");
+    let ds = UpstreamDatasource::new("http://example.com:8080/tiles/{z}/{x}/{y}.mvt").unwrap();
+    assert_eq!(ds.tile_path(3, 4, 1), "/tiles/3/4/1.mvt");
+    assert!(UpstreamDatasource::new("http://example.com/tiles.pbf").is_err());
+
+    let grid = Grid::web_mercator();
+    let extent = grid.tile_extent_xyz(4, 1, 3);
+    assert_eq!(xyz_tile(&extent, 3, &grid), (4, 1));
+}
+
+#[test]
+fn test_retrieve_features() {
+    let (url, requests) = serve_tiles();
+    let ds = UpstreamDatasource::new(&format!("{}/{{z}}/{{x}}/{{y}}.pbf", url)).unwrap();
+    let grid = Grid::web_mercator();
+
+    let layers = ds.detect_layers(false);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+    assert_eq!(layers[0].geometry_type, Some("POINT".to_string()));
+    let columns = ds.detect_data_columns(&layers[0], None);
+    assert_eq!(
+        columns,
+        vec![
+            ("name".to_string(), String::new()),
+            ("population".to_string(), String::new())
+        ]
+    );
+
+    let mut layer = Layer::new("cities");
+    layer.table_name = Some("places".to_string());
+    layer.fields = vec!["name".to_string()];
+    let extent = grid.tile_extent_xyz(1, 0, 1);
+    let mut features = Vec::new();
+    let cnt = ds
+        .try_retrieve_features_at("base", &layer, &extent, 1, &grid, None, |feat| {
+            let point = match feat.geometry() {
+                Ok(GeometryType::Point(p)) => (p.x, p.y),
+                _ => panic!("Point expected"),
+            };
+            features.push((feat.fid(), feat.attributes(), point));
+        })
+        .unwrap();
+    assert_eq!(cnt, 1);
+    let (fid, attributes, (x, y)) = &features[0];
+    assert_eq!(*fid, Some(7));
+    assert_eq!(attributes.len(), 1);
+    assert_eq!(attributes[0].key, "name");
+    assert_eq!(
+        attributes[0].value,
+        FeatureAttrValType::String("Bern".to_string())
+    );
+    assert!(x > &extent.minx && x < &extent.maxx);
+    assert!(y > &extent.miny && y < &extent.maxy);
+
+    // Same tile is served from memory for other layers
+    let count = requests.lock().unwrap().len();
+    assert_eq!(
+        ds.retrieve_features("base", &Layer::new("places"), &extent, 1, &grid, |_| {}),
+        1
+    );
+    assert_eq!(requests.lock().unwrap().len(), count);
+
+    // Missing tile
+    let extent = grid.tile_extent_xyz(0, 1, 1);
+    assert_eq!(
+        ds.try_retrieve_features_at("base", &layer, &extent, 1, &grid, None, |_| {}),
+        Ok(0)
+    );
+    // Server error
+    let extent = grid.tile_extent_xyz(0, 0, 1);
+    assert!(ds
+        .try_retrieve_features_at("base", &layer, &extent, 1, &grid, None, |_| {})
+        .is_err());
+    assert!(requests
+        .lock()
+        .unwrap()
+        .contains(&"/tiles/1/0/0.pbf".to_string()));
+}
@@ -0,0 +1,648 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Remote WFS 2.0 datasource. Layers are feature types (`table_name`) requested with
+//! GetFeature and the tile bbox. Responses are read as GML (2/3.1/3.2) or GeoJSON.
+
+use crate::core::config::DatasourceCfg;
+use crate::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
+use crate::core::geom::GeometryType;
+use crate::core::layer::Layer;
+use crate::core::Config;
+use crate::datasource::csv_ds::text_value;
+use crate::datasource::geojson_ds::{attr_value, read_geojson_geometry};
+use crate::datasource::reproject::{swap_extent_axes, transform_extent, transformation};
+use crate::datasource::wfs_client::{child, gml_geometry_type, local_name, parse_xml, WfsClient};
+use crate::datasource::DatasourceType;
+use elementtree::Element;
+use serde_json::Value;
+use tile_grid::{Extent, Grid};
+
+/// Number of features requested per GetFeature page
+const PAGE_SIZE: u64 = 1000;
+
+/// Feature of a GetFeature response with GeoJSON geometry
+#[derive(Debug)]
+pub struct WfsFeature {
+    /// Feature id (`gml:id` or GeoJSON `id`)
+    pub id: Option<String>,
+    pub properties: Vec<(String, FeatureAttrValType)>,
+    pub geometry: Option<Value>,
+}
+
+#[derive(Clone)]
+pub struct WfsDatasource {
+    pub url: String,
+    client: WfsClient,
+}
+
+fn is_gml(elem: &Element) -> bool {
+    elem.tag()
+        .ns()
+        .is_some_and(|ns| ns.starts_with("http://www.opengis.net/gml"))
+}
+
+/// Coordinates of `gml:pos`, `gml:posList` or GML 2 `gml:coordinates`
+fn gml_positions(elem: &Element) -> Result<Vec<Value>, String> {
+    let mut coords = Vec::new();
+    for c in elem.children() {
+        match local_name(c) {
+            "pos" | "posList" => {
+                let dim = c
+                    .get_attr("srsDimension")
+                    .or_else(|| elem.get_attr("srsDimension"))
+                    .and_then(|d| d.parse::<usize>().ok())
+                    .unwrap_or(2)
+                    .max(2);
+                let values = c
+                    .text()
+                    .split_whitespace()
+                    .map(|v| v.parse::<f64>())
+                    .collect::<Result<Vec<f64>, _>>()
+                    .map_err(|_| format!("Invalid GML coordinates '{}'", c.text().trim()))?;
+                for pos in values.chunks(dim) {
+                    if pos.len() < 2 {
+                        return Err(format!("Invalid GML coordinates '{}'", c.text().trim()));
+                    }
+                    coords.push(json!([pos[0], pos[1]]));
+                }
+            }
+            "coordinates" => {
+                let cs = c.get_attr("cs").unwrap_or(",");
+                for tuple in c.text().split_whitespace() {
+                    let pos: Vec<f64> = tuple.split(cs).filter_map(|v| v.parse().ok()).collect();
+                    if pos.len() < 2 {
+                        return Err(format!("Invalid GML coordinates '{}'", tuple));
+                    }
+                    coords.push(json!([pos[0], pos[1]]));
+                }
+            }
+            "Point" => coords.extend(gml_positions(c)?),
+            _ => {}
+        }
+    }
+    Ok(coords)
+}
+
+/// Coordinates of a line like `gml:LineString` or `gml:Curve` (concatenated segments)
+fn gml_line(elem: &Element) -> Result<Vec<Value>, String> {
+    match child(elem, "segments") {
+        Some(segments) => {
+            let mut coords = Vec::new();
+            for segment in segments.children() {
+                coords.extend(gml_positions(segment)?);
+            }
+            Ok(coords)
+        }
+        None => gml_positions(elem),
+    }
+}
+
+/// Rings of a `gml:Polygon`, `gml:PolygonPatch` or `gml:Surface`
+fn gml_polygon(elem: &Element) -> Result<Vec<Value>, String> {
+    if let Some(patches) = child(elem, "patches") {
+        return match patches.children().next() {
+            Some(patch) => gml_polygon(patch),
+            None => Err("Empty GML surface".to_string()),
+        };
+    }
+    let mut rings = Vec::new();
+    for boundary in elem.children() {
+        match local_name(boundary) {
+            "exterior" | "interior" | "outerBoundaryIs" | "innerBoundaryIs" => {
+                if let Some(ring) = boundary.children().next() {
+                    rings.push(Value::Array(gml_line(ring)?));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(rings)
+}
+
+/// Geometries of members of a GML multi geometry
+fn gml_members(elem: &Element) -> Vec<&Element> {
+    elem.children()
+        .filter(|c| local_name(c).ends_with("Member") || local_name(c).ends_with("Members"))
+        .flat_map(|member| member.children())
+        .collect()
+}
+
+/// Convert GML geometry element into a GeoJSON geometry
+pub fn gml_geometry(elem: &Element) -> Result<Value, String> {
+    let geometry = match local_name(elem) {
+        "Point" => {
+            let coords = gml_positions(elem)?;
+            let pos = coords.into_iter().next().ok_or("Empty GML point")?;
+            json!({"type": "Point", "coordinates": pos})
+        }
+        "LineString" | "Curve" | "LinearRing" => {
+            json!({"type": "LineString", "coordinates": gml_line(elem)?})
+        }
+        "Polygon" | "Surface" => json!({"type": "Polygon", "coordinates": gml_polygon(elem)?}),
+        "MultiPoint" => {
+            let mut coords = Vec::new();
+            for member in gml_members(elem) {
+                coords.extend(gml_positions(member)?);
+            }
+            json!({"type": "MultiPoint", "coordinates": coords})
+        }
+        "MultiLineString" | "MultiCurve" => {
+            let lines = gml_members(elem)
+                .into_iter()
+                .map(|member| gml_line(member).map(Value::Array))
+                .collect::<Result<Vec<Value>, String>>()?;
+            json!({"type": "MultiLineString", "coordinates": lines})
+        }
+        "MultiPolygon" | "MultiSurface" => {
+            let polygons = gml_members(elem)
+                .into_iter()
+                .map(|member| gml_polygon(member).map(Value::Array))
+                .collect::<Result<Vec<Value>, String>>()?;
+            json!({"type": "MultiPolygon", "coordinates": polygons})
+        }
+        "MultiGeometry" => {
+            let geometries = gml_members(elem)
+                .into_iter()
+                .map(gml_geometry)
+                .collect::<Result<Vec<Value>, String>>()?;
+            json!({"type": "GeometryCollection", "geometries": geometries})
+        }
+        name => return Err(format!("Unsupported GML geometry '{}'", name)),
+    };
+    Ok(geometry)
+}
+
+fn gml_feature(elem: &Element) -> WfsFeature {
+    let id = elem
+        .attrs()
+        .find(|(name, _)| name.name() == "id" || name.name() == "fid")
+        .map(|(_, value)| value.to_string());
+    let mut properties = Vec::new();
+    let mut geometry = None;
+    for prop in elem.children() {
+        if is_gml(prop) {
+            // gml:boundedBy, gml:name, ...
+            continue;
+        }
+        match prop.children().next() {
+            Some(value) if is_gml(value) => {
+                if geometry.is_none() {
+                    match gml_geometry(value) {
+                        Ok(geom) => geometry = Some(geom),
+                        Err(e) => warn!("Feature {:?}: {}", id, e),
+                    }
+                }
+            }
+            Some(_) => {}
+            None => {
+                if let Some(value) = text_value(prop.text().trim()) {
+                    properties.push((local_name(prop).to_string(), value));
+                }
+            }
+        }
+    }
+    WfsFeature {
+        id,
+        properties,
+        geometry,
+    }
+}
+
+/// Features of a GML feature collection (WFS 2.0 `wfs:member` or GML `featureMember(s)`)
+pub fn parse_gml_features(root: &Element) -> Vec<WfsFeature> {
+    root.children()
+        .filter(|c| matches!(local_name(c), "member" | "featureMember" | "featureMembers"))
+        .flat_map(|member| member.children())
+        .map(gml_feature)
+        .collect()
+}
+
+/// Features of a GeoJSON feature collection
+pub fn parse_geojson_features(json: &Value) -> Result<Vec<WfsFeature>, String> {
+    let features = json["features"]
+        .as_array()
+        .ok_or("Invalid GeoJSON feature collection")?;
+    Ok(features
+        .iter()
+        .map(|feature| WfsFeature {
+            id: match &feature["id"] {
+                Value::String(id) => Some(id.clone()),
+                Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            },
+            properties: feature["properties"]
+                .as_object()
+                .map(|props| {
+                    props
+                        .iter()
+                        .filter_map(|(key, value)| attr_value(value).map(|v| (key.clone(), v)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            geometry: Some(feature["geometry"].clone()).filter(|geom| geom.is_object()),
+        })
+        .collect())
+}
+
+/// Features of a GetFeature response in GeoJSON or GML format
+pub fn parse_features(body: &[u8]) -> Result<Vec<WfsFeature>, String> {
+    let start = body.iter().find(|b| !b.is_ascii_whitespace());
+    if start == Some(&b'{') {
+        let json: Value =
+            serde_json::from_slice(body).map_err(|e| format!("Invalid WFS response: {}", e))?;
+        parse_geojson_features(&json)
+    } else {
+        Ok(parse_gml_features(&parse_xml(body)?))
+    }
+}
+
+/// Number of matched features of a `RESULTTYPE=hits` response
+pub fn parse_hits(body: &[u8]) -> Result<Option<u64>, String> {
+    let start = body.iter().find(|b| !b.is_ascii_whitespace());
+    if start == Some(&b'{') {
+        let json: Value =
+            serde_json::from_slice(body).map_err(|e| format!("Invalid WFS response: {}", e))?;
+        Ok(json["numberMatched"]
+            .as_u64()
+            .or_else(|| json["totalFeatures"].as_u64()))
+    } else {
+        let root = parse_xml(body)?;
+        Ok(root
+            .get_attr("numberMatched")
+            .or_else(|| root.get_attr("numberOfFeatures"))
+            .and_then(|n| n.parse().ok()))
+    }
+}
+
+/// CRS URN of an EPSG code
+fn crs_urn(srid: i32) -> String {
+    format!("urn:ogc:def:crs:EPSG::{}", srid)
+}
+
+/// GetFeature parameters for a bbox in `srid` and axis order of the layer
+pub fn get_feature_params(
+    layer: &Layer,
+    bbox: Option<&Extent>,
+    srid: i32,
+) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("REQUEST", "GetFeature".to_string()),
+        ("TYPENAMES", layer.table_name.clone().unwrap_or_default()),
+        ("SRSNAME", crs_urn(srid)),
+    ];
+    if let Some(bbox) = bbox {
+        params.push((
+            "BBOX",
+            format!(
+                "{},{},{},{},{}",
+                bbox.minx,
+                bbox.miny,
+                bbox.maxx,
+                bbox.maxy,
+                crs_urn(srid)
+            ),
+        ));
+    }
+    if let Some(ref order_by) = layer.order_by {
+        params.push(("SORTBY", order_by.clone()));
+    }
+    params
+}
+
+impl WfsDatasource {
+    pub fn new(url: &str) -> WfsDatasource {
+        WfsDatasource {
+            url: url.to_string(),
+            client: WfsClient::from_url(url).unwrap(),
+        }
+    }
+    /// Request features page by page and call `read` for each feature
+    fn get_features<F>(
+        &self,
+        params: &[(&str, String)],
+        limit: Option<u64>,
+        mut read: F,
+    ) -> Result<u64, String>
+    where
+        F: FnMut(&WfsFeature),
+    {
+        let page_size = limit.map(|l| l.min(PAGE_SIZE)).unwrap_or(PAGE_SIZE);
+        let mut cnt = 0;
+        loop {
+            let mut page_params = params.to_vec();
+            page_params.push(("COUNT", page_size.to_string()));
+            page_params.push(("STARTINDEX", cnt.to_string()));
+            let features = parse_features(&self.client.get(&page_params)?)?;
+            for feature in &features {
+                read(feature);
+                cnt += 1;
+                if Some(cnt) == limit {
+                    return Ok(cnt);
+                }
+            }
+            if (features.len() as u64) < page_size {
+                return Ok(cnt);
+            }
+        }
+    }
+    /// Tile bbox in request SRS and layer axis order
+    fn tile_bbox(&self, layer: &Layer, extent: &Extent, zoom: u8, grid: &Grid) -> Extent {
+        let mut bbox = if let Some(pixels) = layer.buffer_size {
+            let buf = f64::from(pixels) * grid.pixel_width(zoom);
+            Extent {
+                minx: extent.minx - buf,
+                miny: extent.miny - buf,
+                maxx: extent.maxx + buf,
+                maxy: extent.maxy + buf,
+            }
+        } else {
+            extent.clone()
+        };
+        if let Some(srid) = layer.srid.filter(|srid| *srid != grid.srid) {
+            if let Some(Some(inverse)) = transformation(grid.srid, srid) {
+                bbox = transform_extent(&bbox, inverse);
+            }
+        }
+        if layer.swap_axes() {
+            bbox = swap_extent_axes(&bbox);
+        }
+        bbox
+    }
+}
+
+pub struct WfsTileFeature<'a> {
+    pub layer: &'a Layer,
+    pub feature: &'a WfsFeature,
+    pub srid: Option<i32>,
+    pub transform: &'a dyn Fn(f64, f64) -> (f64, f64),
+}
+
+impl<'a> Feature for WfsTileFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        let fid_fields = self.layer.fid_fields();
+        if fid_fields.is_empty() {
+            // Numeric part of ids like `places.42`
+            let id = self.feature.id.as_ref()?;
+            let value = match id.rsplit('.').next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) => FeatureAttrValType::UInt(n),
+                None => FeatureAttrValType::String(id.clone()),
+            };
+            return fid_from_values(&[Some(value)]);
+        }
+        let values: Vec<Option<FeatureAttrValType>> = fid_fields
+            .iter()
+            .map(|field| {
+                self.feature
+                    .properties
+                    .iter()
+                    .find(|(key, _)| key == field)
+                    .map(|(_, value)| value.clone())
+            })
+            .collect();
+        fid_from_values(&values)
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.feature
+            .properties
+            .iter()
+            .map(|(key, value)| FeatureAttr {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        match self.feature.geometry {
+            Some(ref geom) => read_geojson_geometry(geom, self.srid, self.transform),
+            None => Err("Geometry is NULL".to_string()),
+        }
+    }
+}
+
+impl DatasourceType for WfsDatasource {
+    fn connected(&self) -> WfsDatasource {
+        self.clone()
+    }
+    fn detect_layers(&self, detect_geometry_types: bool) -> Vec<Layer> {
+        info!("Detecting layers from WFS capabilities");
+        let feature_types = match self.client.feature_types() {
+            Ok(feature_types) => feature_types,
+            Err(e) => {
+                error!("{}", e);
+                return Vec::new();
+            }
+        };
+        feature_types
+            .iter()
+            .map(|ft| {
+                let mut layer = Layer::new(&ft.name.replace(':', "_"));
+                layer.table_name = Some(ft.name.clone());
+                // Features are requested in grid SRS, reprojected by the WFS
+                layer.srid = None;
+                if detect_geometry_types {
+                    match self.client.describe_feature_type(&ft.name) {
+                        Ok(properties) => {
+                            let geom = properties.iter().find_map(|(name, prop_type)| {
+                                gml_geometry_type(prop_type).map(|t| (name, t))
+                            });
+                            if let Some((name, geometry_type)) = geom {
+                                layer.geometry_field = Some(name.clone());
+                                layer.geometry_type = Some(geometry_type.to_string());
+                            }
+                        }
+                        Err(e) => warn!("Layer '{}': {}", layer.name, e),
+                    }
+                }
+                layer
+            })
+            .collect()
+    }
+    /// Return column field names and Rust compatible type conversion - without geometry column
+    fn detect_data_columns(&self, layer: &Layer, _sql: Option<&String>) -> Vec<(String, String)> {
+        let type_name = match layer.table_name {
+            Some(ref type_name) => type_name,
+            None => return Vec::new(),
+        };
+        match self.client.describe_feature_type(type_name) {
+            Ok(properties) => properties
+                .into_iter()
+                .filter(|(_, prop_type)| gml_geometry_type(prop_type).is_none())
+                .map(|(name, _)| (name, String::new()))
+                .collect(),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                Vec::new()
+            }
+        }
+    }
+    /// Projected extent
+    fn reproject_extent(
+        &self,
+        extent: &Extent,
+        dest_srid: i32,
+        src_srid: Option<i32>,
+    ) -> Option<Extent> {
+        let ext_srid = src_srid.unwrap_or(4326);
+        match transformation(ext_srid, dest_srid)? {
+            Some(transform) => Some(transform_extent(extent, transform)),
+            None => Some(extent.clone()),
+        }
+    }
+    /// Extent of layer (in WGS84) from the capabilities
+    fn layer_extent(&self, layer: &Layer, _grid_srid: i32) -> Option<Extent> {
+        let type_name = layer.table_name.as_ref()?;
+        match self.client.feature_types() {
+            Ok(feature_types) => feature_types
+                .into_iter()
+                .find(|ft| &ft.name == type_name)
+                .and_then(|ft| ft.extent),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                None
+            }
+        }
+    }
+    fn prepare_queries(&mut self, _tileset: &str, layer: &Layer, grid_srid: i32) {
+        if layer.table_name.is_none() {
+            error!(
+                "Layer '{}': table_name (feature type) undefined",
+                layer.name
+            );
+        }
+        if let Some(srid) = layer.srid.filter(|srid| *srid != grid_srid) {
+            if transformation(srid, grid_srid).is_none() {
+                error!(
+                    "Layer '{}': Reprojecting geometry from SRID {} to SRID {} not supported",
+                    layer.name, srid, grid_srid
+                );
+            } else {
+                info!(
+                    "Layer '{}': Reprojecting geometry to SRID {}",
+                    layer.name, grid_srid
+                );
+            }
+        }
+        if !layer.query.is_empty() {
+            warn!(
+                "Layer '{}': SQL queries not supported for WFS layers",
+                layer.name
+            );
+        }
+    }
+    fn retrieve_features<F>(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        mut read: F,
+    ) -> u64
+    where
+        F: FnMut(&dyn Feature),
+    {
+        let srid = layer.srid.unwrap_or(grid.srid);
+        let bbox = self.tile_bbox(layer, extent, zoom, grid);
+        let params = get_feature_params(layer, Some(&bbox), srid);
+        let layer_transform = if srid != grid.srid && !layer.no_transform {
+            transformation(srid, grid.srid).and_then(|tr| tr)
+        } else {
+            None
+        };
+        let swap_axes = layer.swap_axes();
+        let transform = move |x: f64, y: f64| {
+            let (x, y) = if swap_axes { (y, x) } else { (x, y) };
+            match layer_transform {
+                Some(transform) => transform(x, y),
+                None => (x, y),
+            }
+        };
+        debug!("Reading features in layer {}", layer.name);
+        let limit = layer.query_limit.map(u64::from);
+        let result = self.get_features(&params, limit, |feature| {
+            read(&WfsTileFeature {
+                layer,
+                feature,
+                srid: Some(grid.srid),
+                transform: &transform,
+            });
+        });
+        match result {
+            Ok(cnt) => {
+                if Some(cnt) == limit {
+                    info!(
+                        "Features of layer {} limited to {} (tile query_limit reached, zoom level {})",
+                        layer.name, cnt, zoom
+                    );
+                }
+                cnt
+            }
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                0
+            }
+        }
+    }
+    fn estimated_row_count(&self, layer: &Layer) -> Option<u64> {
+        let mut params = get_feature_params(layer, None, layer.srid.unwrap_or(4326));
+        params.push(("RESULTTYPE", "hits".to_string()));
+        match self.client.get(&params).and_then(|body| parse_hits(&body)) {
+            Ok(hits) => hits,
+            Err(e) => {
+                warn!("Layer '{}': {}", layer.name, e);
+                None
+            }
+        }
+    }
+    fn count_features(
+        &self,
+        _tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+    ) -> u64 {
+        let srid = layer.srid.unwrap_or(grid.srid);
+        let bbox = self.tile_bbox(layer, extent, zoom, grid);
+        let mut params = get_feature_params(layer, Some(&bbox), srid);
+        params.push(("RESULTTYPE", "hits".to_string()));
+        match self.client.get(&params).and_then(|body| parse_hits(&body)) {
+            Ok(hits) => hits.unwrap_or(0),
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                0
+            }
+        }
+    }
+}
+
+impl<'a> Config<'a, DatasourceCfg> for WfsDatasource {
+    fn from_config(ds_cfg: &DatasourceCfg) -> Result<Self, String> {
+        let url = ds_cfg.wfs.as_ref().unwrap();
+        WfsClient::from_url(url)?;
+        Ok(WfsDatasource::new(url))
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[[datasource]]
+name = "wfs"
+# WFS 2.0 service URL. Layers are feature types (table_name). Append
+# outputFormat=application/json for GeoJSON responses, GML otherwise.
+wfs = "https://example.com/geoserver/wfs"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(
+            r#"
+[[datasource]]
+wfs = "{}"
+"#,
+            self.url
+        )
+    }
+}
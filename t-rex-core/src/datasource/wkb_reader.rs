@@ -0,0 +1,335 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Reader for OGC WKB geometries (ISO and extended Z/M variants).
+//! Z and M values are dropped, curves are linearized.
+
+use crate::core::geom::*;
+use std::f64::consts::PI;
+
+/// Maximal angle between linearized arc vertices
+const ARC_STEP: f64 = PI / 36.0;
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+pub(crate) struct WkbReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    srid: Option<i32>,
+    /// Coordinate transformation applied to every point
+    transform: Option<&'a dyn Fn(f64, f64) -> (f64, f64)>,
+}
+
+impl<'a> WkbReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> WkbReader<'a> {
+        WkbReader {
+            data,
+            pos: 0,
+            srid: None,
+            transform: None,
+        }
+    }
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or("WKB geometry truncated")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+    pub(crate) fn read_u32(&mut self, little_endian: bool) -> Result<u32, String> {
+        let mut b = [0u8; 4];
+        b.copy_from_slice(self.bytes(4)?);
+        Ok(if little_endian {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    }
+    fn read_f64(&mut self, little_endian: bool) -> Result<f64, String> {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(self.bytes(8)?);
+        Ok(if little_endian {
+            f64::from_le_bytes(b)
+        } else {
+            f64::from_be_bytes(b)
+        })
+    }
+    fn point(&self, x: f64, y: f64) -> Point {
+        let (x, y) = match self.transform {
+            Some(transform) => transform(x, y),
+            None => (x, y),
+        };
+        Point::new(x, y, self.srid)
+    }
+    fn line(&self, coords: &[(f64, f64)]) -> LineString {
+        LineString {
+            points: coords.iter().map(|&(x, y)| self.point(x, y)).collect(),
+            srid: self.srid,
+        }
+    }
+    /// Geometry header: byte order, base type and number of ordinates
+    pub(crate) fn header(&mut self) -> Result<(bool, u32, usize), String> {
+        let little_endian = self.bytes(1)?[0] == 1;
+        let code = self.read_u32(little_endian)?;
+        let mut dims = 2;
+        if code & EWKB_Z != 0 {
+            dims += 1;
+        }
+        if code & EWKB_M != 0 {
+            dims += 1;
+        }
+        if code & EWKB_SRID != 0 {
+            self.read_u32(little_endian)?;
+        }
+        let code = code & 0x0fff_ffff;
+        // ISO types: 1000 Z, 2000 M, 3000 ZM
+        dims += match code / 1000 {
+            1 | 2 => 1,
+            3 => 2,
+            _ => 0,
+        };
+        Ok((little_endian, code % 1000, dims))
+    }
+    fn coords(&mut self, little_endian: bool, dims: usize) -> Result<Vec<(f64, f64)>, String> {
+        let npoints = self.read_u32(little_endian)? as usize;
+        let mut coords = Vec::with_capacity(npoints.min(self.data.len() / 16));
+        for _ in 0..npoints {
+            coords.push(self.coord(little_endian, dims)?);
+        }
+        Ok(coords)
+    }
+    pub(crate) fn coord(&mut self, little_endian: bool, dims: usize) -> Result<(f64, f64), String> {
+        let x = self.read_f64(little_endian)?;
+        let y = self.read_f64(little_endian)?;
+        for _ in 2..dims {
+            self.read_f64(little_endian)?;
+        }
+        Ok((x, y))
+    }
+    /// Coordinates of a curve (LineString, CircularString or CompoundCurve)
+    fn curve_coords(&mut self) -> Result<Vec<(f64, f64)>, String> {
+        let (little_endian, geom_type, dims) = self.header()?;
+        self.curve_coords_body(little_endian, geom_type, dims)
+    }
+    fn curve_coords_body(
+        &mut self,
+        little_endian: bool,
+        geom_type: u32,
+        dims: usize,
+    ) -> Result<Vec<(f64, f64)>, String> {
+        match geom_type {
+            2 => self.coords(little_endian, dims),
+            8 => Ok(linearize_arcs(&self.coords(little_endian, dims)?)),
+            9 => {
+                let nparts = self.read_u32(little_endian)?;
+                let mut coords: Vec<(f64, f64)> = Vec::new();
+                for _ in 0..nparts {
+                    let part = self.curve_coords()?;
+                    // Parts share their start point with the end point of the previous part
+                    let skip = if coords.last().is_some() && coords.last() == part.first() {
+                        1
+                    } else {
+                        0
+                    };
+                    coords.extend(part.into_iter().skip(skip));
+                }
+                Ok(coords)
+            }
+            _ => Err(format!("Unexpected WKB curve type {}", geom_type)),
+        }
+    }
+    fn polygon_body(
+        &mut self,
+        little_endian: bool,
+        geom_type: u32,
+        dims: usize,
+    ) -> Result<Polygon, String> {
+        let nrings = self.read_u32(little_endian)?;
+        let mut rings = Vec::new();
+        for _ in 0..nrings {
+            let coords = if geom_type == 3 {
+                self.coords(little_endian, dims)?
+            } else {
+                self.curve_coords()?
+            };
+            rings.push(self.line(&coords));
+        }
+        Ok(Polygon {
+            rings,
+            srid: self.srid,
+        })
+    }
+    fn geometry(&mut self) -> Result<Geometry, String> {
+        let (little_endian, geom_type, dims) = self.header()?;
+        let geom = match geom_type {
+            1 => {
+                let (x, y) = self.coord(little_endian, dims)?;
+                Geometry::Point(self.point(x, y))
+            }
+            2 | 8 | 9 => {
+                let coords = self.curve_coords_body(little_endian, geom_type, dims)?;
+                Geometry::LineString(self.line(&coords))
+            }
+            3 | 10 => Geometry::Polygon(self.polygon_body(little_endian, geom_type, dims)?),
+            4..=7 | 11 | 12 => {
+                let nparts = self.read_u32(little_endian)?;
+                let mut parts = Vec::new();
+                for _ in 0..nparts {
+                    parts.push(self.geometry()?);
+                }
+                self.collection(geom_type, parts)?
+            }
+            _ => return Err(format!("Unsupported WKB geometry type {}", geom_type)),
+        };
+        Ok(geom)
+    }
+    fn collection(&self, geom_type: u32, parts: Vec<Geometry>) -> Result<Geometry, String> {
+        let srid = self.srid;
+        let unexpected =
+            |part: &Geometry| format!("Unexpected part {:?} of WKB type {}", part, geom_type);
+        let geom = match geom_type {
+            4 => {
+                let mut points = Vec::new();
+                for part in parts {
+                    match part {
+                        Geometry::Point(p) => points.push(p),
+                        part => return Err(unexpected(&part)),
+                    }
+                }
+                Geometry::MultiPoint(MultiPoint { points, srid })
+            }
+            5 | 11 => {
+                let mut lines = Vec::new();
+                for part in parts {
+                    match part {
+                        Geometry::LineString(l) => lines.push(l),
+                        part => return Err(unexpected(&part)),
+                    }
+                }
+                Geometry::MultiLineString(MultiLineString { lines, srid })
+            }
+            6 | 12 => {
+                let mut polygons = Vec::new();
+                for part in parts {
+                    match part {
+                        Geometry::Polygon(p) => polygons.push(p),
+                        part => return Err(unexpected(&part)),
+                    }
+                }
+                Geometry::MultiPolygon(MultiPolygon { polygons, srid })
+            }
+            _ => Geometry::GeometryCollection(GeometryCollection {
+                geometries: parts,
+                srid,
+            }),
+        };
+        Ok(geom)
+    }
+}
+
+/// Approximate circular arcs (sequences of start, mid and end points) with line segments
+pub(crate) fn linearize_arcs(coords: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut result = Vec::new();
+    if let Some(first) = coords.first() {
+        result.push(*first);
+    }
+    let mut i = 0;
+    while i + 2 < coords.len() {
+        let (p0, p1, p2) = (coords[i], coords[i + 1], coords[i + 2]);
+        let d = 2.0 * (p0.0 * (p1.1 - p2.1) + p1.0 * (p2.1 - p0.1) + p2.0 * (p0.1 - p1.1));
+        if d.abs() < 1e-12 {
+            // Collinear points
+            result.push(p1);
+            result.push(p2);
+        } else {
+            let sq = |p: (f64, f64)| p.0 * p.0 + p.1 * p.1;
+            let cx = (sq(p0) * (p1.1 - p2.1) + sq(p1) * (p2.1 - p0.1) + sq(p2) * (p0.1 - p1.1)) / d;
+            let cy = (sq(p0) * (p2.0 - p1.0) + sq(p1) * (p0.0 - p2.0) + sq(p2) * (p1.0 - p0.0)) / d;
+            let radius = ((p0.0 - cx).powi(2) + (p0.1 - cy).powi(2)).sqrt();
+            let angle = |p: (f64, f64)| (p.1 - cy).atan2(p.0 - cx);
+            let (a0, a1, a2) = (angle(p0), angle(p1), angle(p2));
+            // Counterclockwise sweep from a0 to a2, reversed if the mid point is not on it
+            let ccw = |a: f64| (a - a0).rem_euclid(2.0 * PI);
+            let mut sweep = ccw(a2);
+            if ccw(a1) > sweep {
+                sweep -= 2.0 * PI;
+            }
+            let steps = (sweep.abs() / ARC_STEP).ceil().max(1.0) as usize;
+            for step in 1..steps {
+                let a = a0 + sweep * step as f64 / steps as f64;
+                result.push((cx + radius * a.cos(), cy + radius * a.sin()));
+            }
+            result.push(p2);
+        }
+        i += 2;
+    }
+    result
+}
+
+/// Read WKB geometry with optional coordinate transformation
+pub fn read_wkb(
+    data: &[u8],
+    srid: Option<i32>,
+    transform: Option<&dyn Fn(f64, f64) -> (f64, f64)>,
+) -> Result<GeometryType, String> {
+    let mut reader = WkbReader {
+        data,
+        pos: 0,
+        srid,
+        transform,
+    };
+    let geom = match reader.geometry()? {
+        Geometry::Point(g) => GeometryType::Point(g),
+        Geometry::LineString(g) => GeometryType::LineString(g),
+        Geometry::Polygon(g) => GeometryType::Polygon(g),
+        Geometry::MultiPoint(g) => GeometryType::MultiPoint(g),
+        Geometry::MultiLineString(g) => GeometryType::MultiLineString(g),
+        Geometry::MultiPolygon(g) => GeometryType::MultiPolygon(g),
+        Geometry::GeometryCollection(g) => GeometryType::GeometryCollection(g),
+    };
+    Ok(geom)
+}
+
+#[test]
+fn test_read_wkb() {
+    // POINT(1 2), little endian
+    let wkb = [
+        1u8, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 240, 63, 0, 0, 0, 0, 0, 0, 0, 64,
+    ];
+    match read_wkb(&wkb, Some(3857), None).unwrap() {
+        GeometryType::Point(p) => assert_eq!((p.x, p.y, p.srid), (1.0, 2.0, Some(3857))),
+        g => panic!("unexpected {:?}", g),
+    }
+
+    // POINT Z (1 2 3), big endian ISO
+    let mut wkb = vec![0u8, 0, 0, 0x03, 0xe9];
+    for v in &[1.0f64, 2.0, 3.0] {
+        wkb.extend_from_slice(&v.to_be_bytes());
+    }
+    match read_wkb(&wkb, None, Some(&|x, y| (y, x))).unwrap() {
+        GeometryType::Point(p) => assert_eq!((p.x, p.y), (2.0, 1.0)),
+        g => panic!("unexpected {:?}", g),
+    }
+
+    assert!(read_wkb(&wkb[0..12], None, None).is_err());
+}
+
+#[test]
+fn test_linearize_arcs() {
+    // Half circle counterclockwise
+    let coords = linearize_arcs(&[(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]);
+    assert_eq!(coords.len(), 37);
+    assert!(coords.iter().all(|p| p.1 >= -1e-9));
+    assert_eq!(coords.last(), Some(&(-1.0, 0.0)));
+    // Half circle clockwise
+    let coords = linearize_arcs(&[(1.0, 0.0), (0.0, -1.0), (-1.0, 0.0)]);
+    assert!(coords.iter().all(|p| p.1 <= 1e-9));
+    // Collinear
+    let coords = linearize_arcs(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+    assert_eq!(coords.len(), 3);
+}
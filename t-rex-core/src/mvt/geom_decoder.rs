@@ -0,0 +1,171 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Decoding of vector tile geometries
+
+use crate::core::geom::{self, GeometryType};
+use crate::mvt::vector_tile;
+use tile_grid::Extent;
+
+pub(crate) const MOVE_TO: u32 = 1;
+pub(crate) const LINE_TO: u32 = 2;
+pub(crate) const CLOSE_PATH: u32 = 7;
+
+fn zigzag_decode(value: u32) -> i32 {
+    ((value >> 1) as i32) ^ -((value & 1) as i32)
+}
+
+/// Decoded geometry command
+pub(crate) struct Command {
+    pub id: u32,
+    pub points: Vec<(i64, i64)>,
+}
+
+/// Decode command integers into commands with absolute coordinates
+pub(crate) fn decode_commands(geometry: &[u32]) -> Result<Vec<Command>, String> {
+    let mut commands = Vec::new();
+    let (mut x, mut y) = (0i64, 0i64);
+    let mut pos = 0;
+    while pos < geometry.len() {
+        let id = geometry[pos] & 0x7;
+        let count = (geometry[pos] >> 3) as usize;
+        pos += 1;
+        let mut points = Vec::new();
+        match id {
+            MOVE_TO | LINE_TO => {
+                if count == 0 {
+                    return Err(format!("command {} with count 0", id));
+                }
+                if pos + 2 * count > geometry.len() {
+                    return Err("truncated command parameters".to_string());
+                }
+                for _ in 0..count {
+                    x += zigzag_decode(geometry[pos]) as i64;
+                    y += zigzag_decode(geometry[pos + 1]) as i64;
+                    points.push((x, y));
+                    pos += 2;
+                }
+            }
+            CLOSE_PATH => {
+                if count != 1 {
+                    return Err(format!("ClosePath with count {}", count));
+                }
+            }
+            _ => return Err(format!("unknown command id {}", id)),
+        }
+        commands.push(Command { id, points });
+    }
+    Ok(commands)
+}
+
+/// Area of ring calculated with the surveyor's formula (positive for clockwise rings in screen coordinates)
+pub(crate) fn ring_area(ring: &[(i64, i64)]) -> i64 {
+    let mut area = 0;
+    for i in 0..ring.len() {
+        let (x1, y1) = ring[i];
+        let (x2, y2) = ring[(i + 1) % ring.len()];
+        area += x1 * y2 - x2 * y1;
+    }
+    area
+}
+
+/// Decode feature geometry into coordinates of the tile extent.
+/// Coordinates are placed within their pixel, so that encoding the geometry again
+/// with the same tile size reproduces the original coordinates.
+pub fn decode_geometry(
+    geom_type: vector_tile::Tile_GeomType,
+    geometry: &[u32],
+    extent: &Extent,
+    tile_size: u32,
+    srid: Option<i32>,
+) -> Result<GeometryType, String> {
+    let pixel_size_x = (extent.maxx - extent.minx) / tile_size as f64;
+    let pixel_size_y = (extent.maxy - extent.miny) / tile_size as f64;
+    let point = |(x, y): &(i64, i64)| {
+        geom::Point::new(
+            extent.minx + (*x as f64 + 0.5) * pixel_size_x,
+            extent.maxy - (*y as f64 - 0.5) * pixel_size_y,
+            srid,
+        )
+    };
+    let commands = decode_commands(geometry)?;
+    match geom_type {
+        vector_tile::Tile_GeomType::POINT => {
+            let mut points: Vec<geom::Point> = commands
+                .iter()
+                .filter(|c| c.id == MOVE_TO)
+                .flat_map(|c| c.points.iter().map(point))
+                .collect();
+            match points.len() {
+                0 => Err("Point geometry without coordinates".to_string()),
+                1 => Ok(GeometryType::Point(points.remove(0))),
+                _ => Ok(GeometryType::MultiPoint(geom::MultiPoint { points, srid })),
+            }
+        }
+        vector_tile::Tile_GeomType::LINESTRING => {
+            let mut lines = Vec::new();
+            for command in &commands {
+                match command.id {
+                    MOVE_TO => lines.push(geom::LineString {
+                        points: command.points.iter().map(point).collect(),
+                        srid,
+                    }),
+                    LINE_TO => match lines.last_mut() {
+                        Some(line) => line.points.extend(command.points.iter().map(point)),
+                        None => return Err("LineTo without MoveTo".to_string()),
+                    },
+                    _ => return Err("ClosePath in LineString".to_string()),
+                }
+            }
+            match lines.len() {
+                0 => Err("LineString geometry without coordinates".to_string()),
+                1 => Ok(GeometryType::LineString(lines.remove(0))),
+                _ => Ok(GeometryType::MultiLineString(geom::MultiLineString {
+                    lines,
+                    srid,
+                })),
+            }
+        }
+        vector_tile::Tile_GeomType::POLYGON => {
+            let mut polygons: Vec<geom::Polygon> = Vec::new();
+            let mut ring: Vec<(i64, i64)> = Vec::new();
+            for command in &commands {
+                match command.id {
+                    MOVE_TO => {
+                        ring.clear();
+                        ring.extend(command.points.iter().cloned());
+                    }
+                    LINE_TO => ring.extend(command.points.iter().cloned()),
+                    _ => {
+                        let area = ring_area(&ring);
+                        if ring.len() < 3 || area == 0 {
+                            continue;
+                        }
+                        let mut points: Vec<geom::Point> = ring.iter().map(point).collect();
+                        points.push(point(&ring[0]));
+                        let linestring = geom::LineString { points, srid };
+                        // Exterior rings are clockwise, interior rings belong to the last polygon
+                        match polygons.last_mut() {
+                            Some(polygon) if area < 0 => polygon.rings.push(linestring),
+                            _ => polygons.push(geom::Polygon {
+                                rings: vec![linestring],
+                                srid,
+                            }),
+                        }
+                    }
+                }
+            }
+            match polygons.len() {
+                0 => Err("Polygon geometry without valid rings".to_string()),
+                1 => Ok(GeometryType::Polygon(polygons.remove(0))),
+                _ => Ok(GeometryType::MultiPolygon(geom::MultiPolygon {
+                    polygons,
+                    srid,
+                })),
+            }
+        }
+        vector_tile::Tile_GeomType::UNKNOWN => Err("Unknown geometry type".to_string()),
+    }
+}
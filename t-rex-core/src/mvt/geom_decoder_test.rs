@@ -0,0 +1,104 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::geom::GeometryType;
+use crate::mvt::geom_decoder::decode_geometry;
+use crate::mvt::tile::Tile;
+use crate::mvt::vector_tile::Tile_GeomType;
+use tile_grid::Extent;
+
+/// Decode geometry and encode it again into a tile with the same extent
+fn roundtrip(geom_type: Tile_GeomType, geometry: &[u32]) -> (GeometryType, Vec<u32>) {
+    let extent = Extent {
+        minx: 958826.08,
+        miny: 5987771.04,
+        maxx: 978393.96,
+        maxy: 6007338.92,
+    };
+    let tile = Tile::new(&extent, true);
+    let decode = || decode_geometry(geom_type, geometry, &extent, 4096, Some(3857)).unwrap();
+    (decode(), tile.encode_geom(decode(), 4096).vec())
+}
+
+#[test]
+fn test_decode_points() {
+    let (geom, encoded) = roundtrip(Tile_GeomType::POINT, &[9, 50, 34]);
+    assert!(matches!(geom, GeometryType::Point(_)));
+    assert_eq!(encoded, &[9, 50, 34]);
+    let (geom, encoded) = roundtrip(Tile_GeomType::POINT, &[17, 10, 14, 3, 9]);
+    match geom {
+        GeometryType::MultiPoint(mp) => assert_eq!(mp.points.len(), 2),
+        _ => panic!("MultiPoint expected"),
+    }
+    assert_eq!(encoded, &[17, 10, 14, 3, 9]);
+}
+
+#[test]
+fn test_decode_lines() {
+    let (geom, encoded) = roundtrip(Tile_GeomType::LINESTRING, &[9, 4, 4, 18, 0, 16, 16, 0]);
+    match geom {
+        GeometryType::LineString(line) => {
+            assert_eq!(line.points.len(), 3);
+            assert_eq!(line.srid, Some(3857));
+        }
+        _ => panic!("LineString expected"),
+    }
+    assert_eq!(encoded, &[9, 4, 4, 18, 0, 16, 16, 0]);
+    let multiline = [9, 4, 4, 18, 0, 16, 16, 0, 9, 17, 17, 10, 4, 8];
+    let (geom, encoded) = roundtrip(Tile_GeomType::LINESTRING, &multiline);
+    assert!(matches!(geom, GeometryType::MultiLineString(_)));
+    assert_eq!(encoded, &multiline);
+}
+
+#[test]
+fn test_decode_polygons() {
+    let polygon = [9, 6, 12, 18, 10, 12, 24, 44, 15];
+    let (geom, encoded) = roundtrip(Tile_GeomType::POLYGON, &polygon);
+    match geom {
+        GeometryType::Polygon(p) => {
+            assert_eq!(p.rings.len(), 1);
+            // Closed ring
+            assert_eq!(p.rings[0].points.len(), 4);
+            assert_eq!(p.rings[0].points[0], p.rings[0].points[3]);
+        }
+        _ => panic!("Polygon expected"),
+    }
+    assert_eq!(encoded, &polygon);
+
+    // Two polygons, the second with a hole
+    let multipolygon = [
+        9, 0, 0, 26, 20, 0, 0, 20, 19, 0, 15, 9, 22, 2, 26, 18, 0, 0, 18, 17, 0, 15, 9, 4, 13, 26,
+        0, 8, 8, 0, 0, 7, 15,
+    ];
+    let (geom, encoded) = roundtrip(Tile_GeomType::POLYGON, &multipolygon);
+    match geom {
+        GeometryType::MultiPolygon(mp) => {
+            assert_eq!(mp.polygons.len(), 2);
+            assert_eq!(mp.polygons[1].rings.len(), 2);
+        }
+        _ => panic!("MultiPolygon expected"),
+    }
+    assert_eq!(encoded, &multipolygon);
+}
+
+#[test]
+fn test_decode_errors() {
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 4096.0,
+        maxy: 4096.0,
+    };
+    assert!(decode_geometry(Tile_GeomType::POINT, &[9, 50], &extent, 4096, None).is_err());
+    assert!(decode_geometry(Tile_GeomType::UNKNOWN, &[9, 50, 34], &extent, 4096, None).is_err());
+    assert!(decode_geometry(
+        Tile_GeomType::LINESTRING,
+        &[18, 0, 16, 16, 0],
+        &extent,
+        4096,
+        None
+    )
+    .is_err());
+}
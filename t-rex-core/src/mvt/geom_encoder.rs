@@ -140,7 +140,7 @@ impl EncodableGeom for screen::LineString {
     }
 }
 impl screen::LineString {
-    fn encode_ring_from(&self, startpos: &screen::Point, seq: &mut CommandSequence) {
+    pub(crate) fn encode_ring_from(&self, startpos: &screen::Point, seq: &mut CommandSequence) {
         // almost same as LineString.encode_from, with ClosePath instead of last point
         if self.points.len() > 3 {
             self.points[0].encode_from(startpos, seq);
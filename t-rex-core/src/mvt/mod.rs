@@ -3,10 +3,20 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+pub mod geom_decoder;
+#[cfg(test)]
+mod geom_decoder_test;
 pub mod geom_encoder;
 #[cfg(test)]
 mod geom_encoder_test;
 pub mod tile;
 #[cfg(test)]
 mod tile_test;
+pub mod validator;
+#[cfg(test)]
+mod validator_test;
 pub mod vector_tile;
+#[cfg(feature = "server")]
+pub mod wkb_encoder;
+#[cfg(all(test, feature = "server"))]
+mod wkb_encoder_test;
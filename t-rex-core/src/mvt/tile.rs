@@ -3,22 +3,152 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+use crate::core::config::ServiceMvtCfg;
 use crate::core::feature::{Feature, FeatureAttrValType};
 use crate::core::layer::Layer;
 use crate::core::screen;
+use crate::core::Config;
 use crate::core::{geom, geom::GeometryType};
 use crate::mvt::geom_encoder::{CommandSequence, EncodableGeom};
 use crate::mvt::vector_tile;
+#[cfg(feature = "server")]
+use crate::mvt::wkb_encoder::WkbEncoder;
+#[cfg(feature = "server")]
+use brotli2::read::BrotliDecoder;
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use protobuf::{error::ProtobufError, CodedOutputStream, Message};
 use std::fs::File;
 use std::io::{BufReader, Read, Write};
 use tile_grid::Extent;
 
+/// Compression of delivered and cached tiles
+#[derive(Clone, Debug)]
+pub struct TileCompression {
+    /// Gzip compression level (0-9)
+    pub gzip_level: u32,
+    /// Store gzip compressed tiles in cache
+    pub cache_compressed: bool,
+}
+
+impl Default for TileCompression {
+    fn default() -> Self {
+        TileCompression {
+            gzip_level: 6,
+            cache_compressed: true,
+        }
+    }
+}
+
+impl TileCompression {
+    /// Encoded tile for storing in cache
+    pub fn cache_bytevec(&self, mvt_tile: &vector_tile::Tile) -> Vec<u8> {
+        if self.cache_compressed {
+            Tile::tile_bytevec_gz_level(mvt_tile, self.gzip_level)
+        } else {
+            Tile::tile_bytevec(mvt_tile)
+        }
+    }
+    /// Tile content from cached data, gzip compressed if requested.
+    /// The encoding of cached data is detected, since caches may be produced by other tools.
+    pub fn tile_content(&self, cached: Vec<u8>, gzip: bool) -> Vec<u8> {
+        let data = match TileEncoding::detect(&cached) {
+            TileEncoding::Gzip => return Tile::tile_content(cached, gzip),
+            TileEncoding::Brotli => TileEncoding::brotli_decode(&cached).unwrap_or_default(),
+            TileEncoding::Identity => cached,
+        };
+        if gzip {
+            Tile::gzip_bytevec(&data, self.gzip_level)
+        } else {
+            data
+        }
+    }
+}
+
+/// Content encoding of stored tile data
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TileEncoding {
+    Identity,
+    Gzip,
+    Brotli,
+}
+
+/// First byte of an encoded tile (field 3 `layers`, length delimited)
+const MVT_LAYERS_TAG: u8 = 0x1a;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+impl TileEncoding {
+    /// Encoding recognizable from the first bytes of tile data
+    pub fn sniff(prefix: &[u8]) -> Option<TileEncoding> {
+        if prefix.is_empty() || prefix[0] == MVT_LAYERS_TAG {
+            Some(TileEncoding::Identity)
+        } else if prefix.starts_with(&GZIP_MAGIC) {
+            Some(TileEncoding::Gzip)
+        } else {
+            None
+        }
+    }
+    /// Detect encoding of tile data. Brotli has no magic bytes and is recognized by decoding.
+    pub fn detect(data: &[u8]) -> TileEncoding {
+        match TileEncoding::sniff(data) {
+            Some(encoding) => encoding,
+            None if TileEncoding::brotli_decode(data).is_some() => TileEncoding::Brotli,
+            None => TileEncoding::Identity,
+        }
+    }
+    #[cfg(feature = "server")]
+    fn brotli_decode(data: &[u8]) -> Option<Vec<u8>> {
+        let mut decoded = Vec::new();
+        BrotliDecoder::new(data).read_to_end(&mut decoded).ok()?;
+        Some(decoded)
+    }
+    #[cfg(not(feature = "server"))]
+    fn brotli_decode(_data: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+    /// HTTP Content-Encoding value
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            TileEncoding::Identity => "identity",
+            TileEncoding::Gzip => "gzip",
+            TileEncoding::Brotli => "br",
+        }
+    }
+}
+
+impl<'a> Config<'a, ServiceMvtCfg> for TileCompression {
+    fn from_config(cfg: &ServiceMvtCfg) -> Result<Self, String> {
+        let default = TileCompression::default();
+        let gzip_level = cfg.gzip_level.unwrap_or(default.gzip_level);
+        if gzip_level > 9 {
+            return Err(format!("Invalid gzip_level {} (expected 0-9)", gzip_level));
+        }
+        Ok(TileCompression {
+            gzip_level,
+            cache_compressed: cfg.cache_compressed.unwrap_or(default.cache_compressed),
+        })
+    }
+    fn gen_config() -> String {
+        "".to_string()
+    }
+}
+
+/// Appended to truncated string attributes
+pub const TRUNCATION_MARKER: &str = "…";
+
 pub struct Tile<'a> {
     pub mvt_tile: vector_tile::Tile,
     extent: &'a Extent,
     reverse_y: bool,
+    /// Remove collinear points of lines and polygons in current layer
+    remove_collinear: bool,
+    /// Minimal area of polygon holes in current layer
+    min_hole_area: Option<f64>,
+    /// Maximal length of string attributes in current layer
+    max_string_length: Option<usize>,
+    /// Attributes removed from current layer
+    protected_fields: Vec<String>,
+    /// Publish protected attributes
+    pub authenticated: bool,
 }
 
 impl GeometryType {
@@ -154,6 +284,11 @@ impl<'a> Tile<'a> {
             mvt_tile: mvt_tile,
             extent: extent,
             reverse_y: reverse_y,
+            remove_collinear: false,
+            min_hole_area: None,
+            max_string_length: None,
+            protected_fields: Vec::new(),
+            authenticated: false,
         }
     }
 
@@ -162,6 +297,14 @@ impl<'a> Tile<'a> {
         mvt_layer.set_version(2);
         mvt_layer.set_name(layer.name.clone());
         mvt_layer.set_extent(layer.tile_size);
+        self.remove_collinear = layer.remove_collinear;
+        self.min_hole_area = layer.min_hole_area;
+        self.max_string_length = layer.max_string_length;
+        self.protected_fields = if self.authenticated {
+            Vec::new()
+        } else {
+            layer.protected_fields.clone()
+        };
         mvt_layer
     }
 
@@ -174,17 +317,42 @@ impl<'a> Tile<'a> {
                 screen::MultiPoint::from_geom(&self.extent, self.reverse_y, tile_size, g).encode()
             }
             GeometryType::LineString(ref g) => {
-                screen::LineString::from_geom(&self.extent, self.reverse_y, tile_size, g).encode()
+                let mut line =
+                    screen::LineString::from_geom(&self.extent, self.reverse_y, tile_size, g);
+                if self.remove_collinear {
+                    line.remove_collinear();
+                }
+                line.encode()
             }
             GeometryType::MultiLineString(ref g) => {
-                screen::MultiLineString::from_geom(&self.extent, self.reverse_y, tile_size, g)
-                    .encode()
+                let mut lines =
+                    screen::MultiLineString::from_geom(&self.extent, self.reverse_y, tile_size, g);
+                if self.remove_collinear {
+                    lines.remove_collinear();
+                }
+                lines.encode()
             }
             GeometryType::Polygon(ref g) => {
-                screen::Polygon::from_geom(&self.extent, self.reverse_y, tile_size, g).encode()
+                let mut polygon =
+                    screen::Polygon::from_geom(&self.extent, self.reverse_y, tile_size, g);
+                if self.remove_collinear {
+                    polygon.remove_collinear();
+                }
+                if let Some(min_area) = self.min_hole_area {
+                    polygon.drop_holes(min_area);
+                }
+                polygon.encode()
             }
             GeometryType::MultiPolygon(ref g) => {
-                screen::MultiPolygon::from_geom(&self.extent, self.reverse_y, tile_size, g).encode()
+                let mut polygons =
+                    screen::MultiPolygon::from_geom(&self.extent, self.reverse_y, tile_size, g);
+                if self.remove_collinear {
+                    polygons.remove_collinear();
+                }
+                if let Some(min_area) = self.min_hole_area {
+                    polygons.drop_holes(min_area);
+                }
+                polygons.encode()
             }
             GeometryType::GeometryCollection(_) => panic!("GeometryCollection not supported"),
             GeometryType::Geometry(_) => panic!("Geometry not supported"),
@@ -220,16 +388,40 @@ impl<'a> Tile<'a> {
         mvt_feature.mut_tags().push(validx as u32);
     }
 
-    pub fn add_feature(&self, mut mvt_layer: &mut vector_tile::Tile_Layer, feature: &dyn Feature) {
+    /// Truncate string attribute value to `max_string_length` characters
+    fn truncate_string(&self, value: &str) -> String {
+        match self
+            .max_string_length
+            .and_then(|max_len| value.char_indices().nth(max_len))
+        {
+            Some((pos, _)) => format!("{}{}", &value[..pos], TRUNCATION_MARKER),
+            None => value.to_string(),
+        }
+    }
+
+    /// Deterministic replacement for the n-th duplicate of a feature id within a layer
+    pub fn disambiguated_fid(fid: u64, occurrence: u64) -> u64 {
+        (occurrence << 48) | (fid & 0xFFFF_FFFF_FFFF)
+    }
+
+    /// Encode feature into layer. Returns error of invalid geometries.
+    pub fn add_feature(
+        &self,
+        mut mvt_layer: &mut vector_tile::Tile_Layer,
+        feature: &dyn Feature,
+    ) -> Result<(), String> {
         let mut mvt_feature = vector_tile::Tile_Feature::new();
         if let Some(fid) = feature.fid() {
             mvt_feature.set_id(fid);
         }
         'attr: for attr in feature.attributes() {
+            if self.protected_fields.contains(&attr.key) {
+                continue;
+            }
             let mut mvt_value = vector_tile::Tile_Value::new();
             match attr.value {
                 FeatureAttrValType::String(ref v) => {
-                    mvt_value.set_string_value(v.clone());
+                    mvt_value.set_string_value(self.truncate_string(v));
                 }
                 FeatureAttrValType::Double(v) => {
                     mvt_value.set_double_value(v);
@@ -268,14 +460,102 @@ impl<'a> Tile<'a> {
                 mvt_value,
             );
         }
-        if let Ok(geom) = feature.geometry() {
-            let g_type = geom.mvt_field_type();
-            let enc_geom = self.encode_geom(geom, mvt_layer.get_extent()).vec();
-            if !enc_geom.is_empty() {
-                mvt_feature.set_field_type(g_type);
-                mvt_feature.set_geometry(enc_geom);
-                mvt_layer.mut_features().push(mvt_feature);
+        let tile_size = mvt_layer.get_extent();
+        // Geometries not supported by the WKB encoder are decoded with `geometry()`,
+        // which also reports invalid geometries
+        let (g_type, enc_geom) = match self.encode_wkb(feature, tile_size) {
+            Some((g_type, seq)) => (g_type, seq.0),
+            None => {
+                let geom = feature.geometry()?;
+                let g_type = geom.mvt_field_type();
+                (g_type, self.encode_geom(geom, tile_size).vec())
             }
+        };
+        if !enc_geom.is_empty() {
+            mvt_feature.set_field_type(g_type);
+            mvt_feature.set_geometry(enc_geom);
+            mvt_layer.mut_features().push(mvt_feature);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "server")]
+    fn encode_wkb(
+        &self,
+        feature: &dyn Feature,
+        tile_size: u32,
+    ) -> Option<(vector_tile::Tile_GeomType, CommandSequence)> {
+        let wkb_encoder = WkbEncoder {
+            extent: self.extent,
+            reverse_y: self.reverse_y,
+            tile_size,
+            remove_collinear: self.remove_collinear,
+            min_hole_area: self.min_hole_area,
+        };
+        feature
+            .geometry_wkb()
+            .and_then(|wkb| wkb_encoder.encode(wkb).ok().flatten())
+    }
+    #[cfg(not(feature = "server"))]
+    fn encode_wkb(
+        &self,
+        _feature: &dyn Feature,
+        _tile_size: u32,
+    ) -> Option<(vector_tile::Tile_GeomType, CommandSequence)> {
+        None
+    }
+
+    /// Sort keys and values of layer and feature attributes for byte-identical encoding of
+    /// identical content. The order of features (e.g. from `order_by`) is kept.
+    pub fn canonicalize_layer(mvt_layer: &mut vector_tile::Tile_Layer) {
+        let keys = mvt_layer.take_keys().into_vec();
+        let values: Vec<Vec<u8>> = mvt_layer
+            .get_values()
+            .iter()
+            .map(|v| v.write_to_bytes().unwrap_or_default())
+            .collect();
+        let mvt_values = mvt_layer.take_values().into_vec();
+        // Feature attributes as sorted (key, encoded value) pairs
+        let features: Vec<_> = mvt_layer
+            .take_features()
+            .into_iter()
+            .map(|feature| {
+                let mut attrs: Vec<(&String, &Vec<u8>)> = feature
+                    .get_tags()
+                    .chunks(2)
+                    .filter(|tag| tag.len() == 2)
+                    .map(|tag| (&keys[tag[0] as usize], &values[tag[1] as usize]))
+                    .collect();
+                attrs.sort();
+                (attrs, feature)
+            })
+            .collect();
+        let mut sorted_keys: Vec<&String> = keys.iter().collect();
+        sorted_keys.sort();
+        sorted_keys.dedup();
+        let mut sorted_values: Vec<(&Vec<u8>, usize)> =
+            values.iter().enumerate().map(|(idx, v)| (v, idx)).collect();
+        sorted_values.sort();
+        sorted_values.dedup_by(|a, b| a.0 == b.0);
+        for key in &sorted_keys {
+            mvt_layer.mut_keys().push((*key).clone());
+        }
+        for (_, idx) in &sorted_values {
+            mvt_layer.mut_values().push(mvt_values[*idx].clone());
+        }
+        for (attrs, mut feature) in features {
+            let tags = attrs
+                .iter()
+                .flat_map(|(key, value)| {
+                    let keyidx = sorted_keys.binary_search(key).unwrap_or(0) as u32;
+                    let validx = sorted_values
+                        .binary_search_by(|(v, _)| v.cmp(value))
+                        .unwrap_or(0) as u32;
+                    vec![keyidx, validx]
+                })
+                .collect();
+            feature.set_tags(tags);
+            mvt_layer.mut_features().push(feature);
         }
     }
 
@@ -290,7 +570,11 @@ impl<'a> Tile<'a> {
     }
 
     pub fn write_gz_to(out: &mut dyn Write, mvt_tile: &vector_tile::Tile) {
-        let mut gz = GzEncoder::new(out, Compression::default());
+        Self::write_gz_level_to(out, mvt_tile, Compression::default().level());
+    }
+
+    pub fn write_gz_level_to(out: &mut dyn Write, mvt_tile: &vector_tile::Tile, level: u32) {
+        let mut gz = GzEncoder::new(out, Compression::new(level));
         {
             let mut os = CodedOutputStream::new(&mut gz);
             let _ = mvt_tile.write_to(&mut os);
@@ -322,6 +606,19 @@ impl<'a> Tile<'a> {
         v
     }
 
+    pub fn tile_bytevec_gz_level(mvt_tile: &vector_tile::Tile, level: u32) -> Vec<u8> {
+        let mut v = Vec::with_capacity(mvt_tile.compute_size() as usize);
+        Self::write_gz_level_to(&mut v, &mvt_tile, level);
+        v
+    }
+
+    /// Gzip compress encoded tile
+    pub fn gzip_bytevec(data: &[u8], level: u32) -> Vec<u8> {
+        let mut gz = GzEncoder::new(Vec::with_capacity(data.len()), Compression::new(level));
+        let _ = gz.write_all(data);
+        gz.finish().unwrap_or_default()
+    }
+
     pub fn tile_content(tilegz: Vec<u8>, gzip: bool) -> Vec<u8> {
         if gzip {
             tilegz
@@ -341,4 +638,12 @@ impl<'a> Tile<'a> {
     pub fn size(mvt_tile: &vector_tile::Tile) -> u32 {
         mvt_tile.compute_size()
     }
+
+    pub fn layer_size(mvt_layer: &vector_tile::Tile_Layer) -> u32 {
+        mvt_layer.compute_size()
+    }
+
+    pub fn feature_size(mvt_feature: &vector_tile::Tile_Feature) -> u32 {
+        mvt_feature.compute_size()
+    }
 }
@@ -10,7 +10,7 @@ use crate::core::geom::GeometryType;
 use crate::core::layer::Layer;
 use crate::core::screen;
 use crate::mvt::geom_encoder::EncodableGeom;
-use crate::mvt::tile::{ScreenGeom, Tile};
+use crate::mvt::tile::{ScreenGeom, Tile, TileCompression};
 use crate::mvt::vector_tile;
 use std::fs::File;
 use tile_grid::Extent;
@@ -148,6 +148,95 @@ fn test_clipped_polygon() {
     assert_eq!(screen_geom.polygons[0].rings[0].points.len(), 197);
 }
 
+#[test]
+fn test_remove_collinear() {
+    fn pts(coords: &[(i32, i32)]) -> Vec<screen::Point> {
+        coords
+            .iter()
+            .map(|&(x, y)| screen::Point { x, y })
+            .collect()
+    }
+    // Duplicates are removed when converting to screen coordinates
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 4096.0,
+        maxy: 4096.0,
+    };
+    let line = geom::LineString {
+        points: vec![
+            geom::Point::new(0.0, 0.0, None),
+            geom::Point::new(0.4, 0.2, None),
+            geom::Point::new(10.0, 0.0, None),
+        ],
+        srid: None,
+    };
+    let screen_line = screen::LineString::from_geom(&extent, false, 4096, &line);
+    assert_eq!(screen_line.points, pts(&[(0, 0), (10, 0)]));
+
+    let mut line = screen::LineString {
+        points: pts(&[(0, 0), (1, 1), (2, 2), (4, 4), (4, 5), (4, 3)]),
+    };
+    line.remove_collinear();
+    // Spike (4,5) is kept
+    assert_eq!(line.points, pts(&[(0, 0), (4, 4), (4, 5), (4, 3)]));
+
+    let mut ring = screen::LineString {
+        points: pts(&[(0, 0), (5, 0), (10, 0), (10, 10), (0, 10), (0, 5), (0, 0)]),
+    };
+    ring.remove_collinear();
+    assert_eq!(
+        ring.points,
+        pts(&[(0, 0), (10, 0), (10, 10), (0, 10), (0, 0)])
+    );
+    assert_eq!(ring.encode().vec().len(), 12);
+
+    let mut tile = Tile::new(&extent, false);
+    let mut layer = Layer::new("lines");
+    let line = geom::LineString {
+        points: vec![
+            geom::Point::new(0.0, 0.0, None),
+            geom::Point::new(5.0, 5.0, None),
+            geom::Point::new(10.0, 10.0, None),
+        ],
+        srid: None,
+    };
+    layer.tile_size = 4096;
+    tile.new_layer(&layer);
+    let geom = GeometryType::LineString(line.clone());
+    assert_eq!(tile.encode_geom(geom, 4096).vec().len(), 8);
+    layer.remove_collinear = true;
+    tile.new_layer(&layer);
+    let geom = GeometryType::LineString(line);
+    assert_eq!(tile.encode_geom(geom, 4096).vec().len(), 6);
+}
+
+#[test]
+fn test_drop_holes() {
+    fn ring(coords: &[(i32, i32)]) -> screen::LineString {
+        screen::LineString {
+            points: coords
+                .iter()
+                .map(|&(x, y)| screen::Point { x, y })
+                .collect(),
+        }
+    }
+    let exterior = ring(&[(0, 0), (100, 0), (100, 100), (0, 100), (0, 0)]);
+    assert_eq!(exterior.ring_area(), 10000.0);
+    let small_hole = ring(&[(10, 10), (10, 12), (12, 12), (12, 10), (10, 10)]);
+    assert_eq!(small_hole.ring_area(), 4.0);
+    let large_hole = ring(&[(20, 20), (20, 40), (40, 40), (40, 20), (20, 20)]);
+    let mut polygon = screen::Polygon {
+        rings: vec![exterior, small_hole, large_hole],
+    };
+    polygon.drop_holes(10.0);
+    assert_eq!(polygon.rings.len(), 2);
+    assert_eq!(polygon.rings[1].ring_area(), 400.0);
+    // Exterior ring is always kept
+    polygon.drop_holes(1000000.0);
+    assert_eq!(polygon.rings.len(), 1);
+}
+
 #[test]
 fn test_tile_values() {
     let mut value = vector_tile::Tile_Value::new();
@@ -441,7 +530,7 @@ fn test_build_mvt_with_helpers() {
         ],
         geometry: geom,
     };
-    tile.add_feature(&mut mvt_layer, &feature);
+    tile.add_feature(&mut mvt_layer, &feature).unwrap();
 
     let geom: GeometryType = GeometryType::Point(geom::Point::new(960000.0, 6002729.0, Some(3857)));
     let feature = FeatureStruct {
@@ -458,7 +547,7 @@ fn test_build_mvt_with_helpers() {
         ],
         geometry: geom,
     };
-    tile.add_feature(&mut mvt_layer, &feature);
+    tile.add_feature(&mut mvt_layer, &feature).unwrap();
 
     tile.add_layer(mvt_layer);
     println!("{:#?}", tile.mvt_tile);
@@ -471,3 +560,204 @@ fn test_build_mvt_with_helpers() {
     path.push("out.pbf");
     tile.to_file(&format!("{}", &path.display()));
 }
+
+#[test]
+fn test_attribute_truncation() {
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 4096.0,
+        maxy: 4096.0,
+    };
+    let mut tile = Tile::new(&extent, false);
+    let mut layer = Layer::new("points");
+    layer.tile_size = 4096;
+    layer.max_string_length = Some(5);
+    let mut mvt_layer = tile.new_layer(&layer);
+    for text in &["short", "<p>Grüezi mitenand</p>"] {
+        let feature = FeatureStruct {
+            fid: None,
+            attributes: vec![FeatureAttr {
+                key: String::from("descr"),
+                value: FeatureAttrValType::String(text.to_string()),
+            }],
+            geometry: GeometryType::Point(geom::Point::new(10.0, 10.0, None)),
+        };
+        tile.add_feature(&mut mvt_layer, &feature).unwrap();
+    }
+    let values: Vec<&str> = mvt_layer
+        .get_values()
+        .iter()
+        .map(|v| v.get_string_value())
+        .collect();
+    assert_eq!(values, vec!["short", "<p>Gr…"]);
+}
+
+#[test]
+fn test_protected_fields() {
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 4096.0,
+        maxy: 4096.0,
+    };
+    let mut layer = Layer::new("parcels");
+    layer.protected_fields = vec!["owner".to_string()];
+    let feature = FeatureStruct {
+        fid: None,
+        attributes: vec![
+            FeatureAttr {
+                key: String::from("area"),
+                value: FeatureAttrValType::Int(250),
+            },
+            FeatureAttr {
+                key: String::from("owner"),
+                value: FeatureAttrValType::String("Jane Doe".to_string()),
+            },
+        ],
+        geometry: GeometryType::Point(geom::Point::new(10.0, 10.0, None)),
+    };
+
+    let mut tile = Tile::new(&extent, false);
+    let mut mvt_layer = tile.new_layer(&layer);
+    tile.add_feature(&mut mvt_layer, &feature).unwrap();
+    assert_eq!(mvt_layer.get_keys(), &["area".to_string()]);
+
+    tile.authenticated = true;
+    let mut mvt_layer = tile.new_layer(&layer);
+    tile.add_feature(&mut mvt_layer, &feature).unwrap();
+    assert_eq!(
+        mvt_layer.get_keys(),
+        &["area".to_string(), "owner".to_string()]
+    );
+}
+
+#[test]
+fn test_disambiguated_fid() {
+    assert_eq!(Tile::disambiguated_fid(42, 1), (1 << 48) + 42);
+    assert_ne!(
+        Tile::disambiguated_fid(42, 1),
+        Tile::disambiguated_fid(42, 2)
+    );
+    assert_eq!(
+        Tile::disambiguated_fid(42, 2),
+        Tile::disambiguated_fid(42, 2)
+    );
+}
+
+#[test]
+fn test_tile_compression() {
+    let mut f = File::open("../t-rex-service/src/test/tile.pbf").unwrap();
+    let tile = Tile::read_from(&mut f).unwrap();
+    let raw = Tile::tile_bytevec(&tile);
+
+    let compression = TileCompression::default();
+    let cached = compression.cache_bytevec(&tile);
+    assert!(cached.len() < raw.len());
+    assert_eq!(compression.tile_content(cached.clone(), false), raw);
+    assert_eq!(compression.tile_content(cached, true)[0..2], [0x1f, 0x8b]);
+
+    let compression = TileCompression {
+        gzip_level: 9,
+        cache_compressed: false,
+    };
+    let cached = compression.cache_bytevec(&tile);
+    assert_eq!(cached, raw);
+    assert_eq!(compression.tile_content(cached.clone(), false), raw);
+    let tilegz = compression.tile_content(cached, true);
+    assert_eq!(Tile::tile_content(tilegz, false), raw);
+}
+
+#[test]
+#[cfg(feature = "server")]
+fn test_mixed_cache_encodings() {
+    use crate::mvt::tile::TileEncoding;
+    use brotli2::write::BrotliEncoder;
+    use std::io::Write;
+
+    let mut f = File::open("../t-rex-service/src/test/tile.pbf").unwrap();
+    let tile = Tile::read_from(&mut f).unwrap();
+    let raw = Tile::tile_bytevec(&tile);
+    let tilegz = Tile::tile_bytevec_gz(&tile);
+    let mut br = BrotliEncoder::new(Vec::new(), 6);
+    br.write_all(&raw).unwrap();
+    let tilebr = br.finish().unwrap();
+
+    assert_eq!(TileEncoding::detect(&raw), TileEncoding::Identity);
+    assert_eq!(TileEncoding::detect(&tilegz), TileEncoding::Gzip);
+    assert_eq!(TileEncoding::detect(&tilebr), TileEncoding::Brotli);
+    assert_eq!(TileEncoding::sniff(&tilebr), None);
+    assert_eq!(TileEncoding::Brotli.content_encoding(), "br");
+
+    // Cache content produced with other settings or tools
+    for compression in &[
+        TileCompression::default(),
+        TileCompression {
+            gzip_level: 6,
+            cache_compressed: false,
+        },
+    ] {
+        for cached in &[&raw, &tilegz, &tilebr] {
+            assert_eq!(compression.tile_content(cached.to_vec(), false), raw);
+            let served = compression.tile_content(cached.to_vec(), true);
+            assert_eq!(Tile::tile_content(served, false), raw);
+        }
+    }
+}
+
+#[test]
+fn test_canonicalize_layer() {
+    fn value(s: &str) -> vector_tile::Tile_Value {
+        let mut value = vector_tile::Tile_Value::new();
+        value.set_string_value(s.to_string());
+        value
+    }
+    fn feature(id: u64, geometry: Vec<u32>) -> vector_tile::Tile_Feature {
+        let mut feature = vector_tile::Tile_Feature::new();
+        feature.set_id(id);
+        feature.set_field_type(vector_tile::Tile_GeomType::POINT);
+        feature.set_geometry(geometry);
+        feature
+    }
+    // Same content, different order of attributes
+    let mut layer1 = vector_tile::Tile_Layer::new();
+    layer1.set_name("points".to_string());
+    layer1.set_version(2);
+    let mut f = feature(2, vec![9, 2, 2]);
+    Tile::add_feature_attribute(&mut layer1, &mut f, "type".to_string(), value("b"));
+    layer1.mut_features().push(f);
+    let mut f = feature(1, vec![9, 4, 4]);
+    Tile::add_feature_attribute(&mut layer1, &mut f, "name".to_string(), value("x"));
+    Tile::add_feature_attribute(&mut layer1, &mut f, "type".to_string(), value("a"));
+    layer1.mut_features().push(f);
+
+    let mut layer2 = layer1.clone();
+    layer2.clear_features();
+    layer2.clear_keys();
+    layer2.clear_values();
+    let mut f = feature(2, vec![9, 2, 2]);
+    Tile::add_feature_attribute(&mut layer2, &mut f, "type".to_string(), value("b"));
+    layer2.mut_features().push(f);
+    let mut f = feature(1, vec![9, 4, 4]);
+    Tile::add_feature_attribute(&mut layer2, &mut f, "type".to_string(), value("a"));
+    Tile::add_feature_attribute(&mut layer2, &mut f, "name".to_string(), value("x"));
+    layer2.mut_features().push(f);
+    assert_ne!(layer1, layer2);
+
+    Tile::canonicalize_layer(&mut layer1);
+    Tile::canonicalize_layer(&mut layer2);
+    assert_eq!(layer1, layer2);
+    assert_eq!(layer1.get_keys(), &["name".to_string(), "type".to_string()]);
+    // Feature order is kept
+    assert_eq!(layer1.get_features()[0].get_id(), 2);
+    assert_eq!(layer1.get_features()[0].get_tags(), &[1, 1]);
+    assert_eq!(layer1.get_features()[1].get_tags(), &[0, 2, 1, 0]);
+    assert_eq!(layer1.get_values()[0].get_string_value(), "a");
+
+    // gzip encoding without timestamp
+    let mut tile = vector_tile::Tile::new();
+    tile.mut_layers().push(layer1);
+    let gz = Tile::tile_bytevec_gz_level(&tile, 6);
+    assert_eq!(&gz[4..8], &[0, 0, 0, 0]);
+    assert_eq!(gz, Tile::tile_bytevec_gz_level(&tile, 6));
+}
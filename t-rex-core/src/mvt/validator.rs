@@ -0,0 +1,178 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Conformance checks for Mapbox Vector Tile specification 2.1
+
+use crate::mvt::geom_decoder::{decode_commands, ring_area, CLOSE_PATH, LINE_TO, MOVE_TO};
+use crate::mvt::vector_tile;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Violation of a vector tile specification rule
+#[derive(PartialEq, Debug)]
+pub struct Violation {
+    pub layer: String,
+    /// Index of feature within layer
+    pub feature: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.feature {
+            Some(idx) => write!(
+                f,
+                "Layer '{}' feature {}: {}",
+                self.layer, idx, self.message
+            ),
+            None => write!(f, "Layer '{}': {}", self.layer, self.message),
+        }
+    }
+}
+
+/// Check command sequence of feature geometry
+fn check_geometry(
+    geom_type: vector_tile::Tile_GeomType,
+    geometry: &[u32],
+    extent: u32,
+) -> Vec<String> {
+    let commands = match decode_commands(geometry) {
+        Ok(commands) => commands,
+        Err(e) => return vec![format!("Invalid geometry encoding: {}", e)],
+    };
+    let ids: Vec<u32> = commands.iter().map(|c| c.id).collect();
+    let mut errors = Vec::new();
+    match geom_type {
+        vector_tile::Tile_GeomType::UNKNOWN => {}
+        vector_tile::Tile_GeomType::POINT => {
+            if ids != [MOVE_TO] {
+                errors.push("Point geometry must consist of a single MoveTo command".to_string());
+            }
+        }
+        vector_tile::Tile_GeomType::LINESTRING => {
+            let valid = !ids.is_empty()
+                && ids.chunks(2).all(|c| c == [MOVE_TO, LINE_TO])
+                && commands
+                    .iter()
+                    .all(|c| c.id != MOVE_TO || c.points.len() == 1);
+            if !valid {
+                errors
+                    .push("LineString must be a sequence of MoveTo(1) LineTo commands".to_string());
+            }
+        }
+        vector_tile::Tile_GeomType::POLYGON => {
+            let valid =
+                !ids.is_empty() && ids.chunks(3).all(|c| c == [MOVE_TO, LINE_TO, CLOSE_PATH]);
+            if !valid {
+                errors.push(
+                    "Polygon must be a sequence of MoveTo(1) LineTo ClosePath commands".to_string(),
+                );
+                return errors;
+            }
+            for (ringno, ring_cmds) in commands.chunks(3).enumerate() {
+                if ring_cmds[0].points.len() != 1 || ring_cmds[1].points.len() < 2 {
+                    errors.push(format!("Ring {} has less than 3 points", ringno));
+                    continue;
+                }
+                let ring: Vec<(i64, i64)> = ring_cmds[0]
+                    .points
+                    .iter()
+                    .chain(ring_cmds[1].points.iter())
+                    .cloned()
+                    .collect();
+                let area = ring_area(&ring);
+                if area == 0 {
+                    errors.push(format!("Ring {} has zero area", ringno));
+                } else if ringno == 0 && area < 0 {
+                    errors.push("First ring must be an exterior ring (clockwise)".to_string());
+                }
+            }
+        }
+    }
+    // Coordinates may be outside of extent (buffer), but not further than one tile
+    let extent = extent as i64;
+    let outside = commands
+        .iter()
+        .flat_map(|c| c.points.iter())
+        .filter(|(x, y)| *x < -extent || *x > 2 * extent || *y < -extent || *y > 2 * extent)
+        .count();
+    if extent > 0 && outside > 0 {
+        errors.push(format!(
+            "{} coordinates more than one tile outside of extent",
+            outside
+        ));
+    }
+    errors
+}
+
+/// Check tile against the rules of the vector tile specification 2.1
+pub fn validate(tile: &vector_tile::Tile) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut layer_names = HashSet::new();
+    for layer in tile.get_layers() {
+        let name = layer.get_name().to_string();
+        let violation = |feature, message: String| Violation {
+            layer: name.clone(),
+            feature,
+            message,
+        };
+        if name.is_empty() {
+            violations.push(violation(None, "Layer name must not be empty".to_string()));
+        }
+        if !layer_names.insert(name.clone()) {
+            violations.push(violation(None, "Duplicate layer name".to_string()));
+        }
+        if layer.get_version() != 2 {
+            violations.push(violation(
+                None,
+                format!("Unsupported version {}", layer.get_version()),
+            ));
+        }
+        let extent = layer.get_extent();
+        if extent == 0 {
+            violations.push(violation(None, "Extent must be greater than 0".to_string()));
+        }
+        let mut keys = HashSet::new();
+        for key in layer.get_keys() {
+            if !keys.insert(key) {
+                violations.push(violation(None, format!("Duplicate key '{}'", key)));
+            }
+        }
+        let num_keys = layer.get_keys().len() as u32;
+        let num_values = layer.get_values().len() as u32;
+        let mut ids = HashSet::new();
+        for (idx, feature) in layer.get_features().iter().enumerate() {
+            let tags = feature.get_tags();
+            if tags.len() % 2 != 0 {
+                violations.push(violation(Some(idx), "Odd number of tags".to_string()));
+            }
+            for tag in tags.chunks(2) {
+                if tag[0] >= num_keys {
+                    violations.push(violation(
+                        Some(idx),
+                        format!("Key index {} out of range", tag[0]),
+                    ));
+                }
+                if tag.len() == 2 && tag[1] >= num_values {
+                    violations.push(violation(
+                        Some(idx),
+                        format!("Value index {} out of range", tag[1]),
+                    ));
+                }
+            }
+            if feature.has_id() && !ids.insert(feature.get_id()) {
+                violations.push(violation(
+                    Some(idx),
+                    format!("Duplicate feature id {}", feature.get_id()),
+                ));
+            }
+            for message in check_geometry(feature.get_field_type(), feature.get_geometry(), extent)
+            {
+                violations.push(violation(Some(idx), message));
+            }
+        }
+    }
+    violations
+}
@@ -0,0 +1,165 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::mvt::tile::Tile;
+use crate::mvt::validator::{validate, Violation};
+use crate::mvt::vector_tile;
+use std::fs::{self, File};
+use std::path::Path;
+
+fn layer(name: &str) -> vector_tile::Tile_Layer {
+    let mut layer = vector_tile::Tile_Layer::new();
+    layer.set_name(name.to_string());
+    layer.set_version(2);
+    layer.set_extent(4096);
+    layer
+}
+
+fn feature(geom_type: vector_tile::Tile_GeomType, geometry: Vec<u32>) -> vector_tile::Tile_Feature {
+    let mut feature = vector_tile::Tile_Feature::new();
+    feature.set_field_type(geom_type);
+    feature.set_geometry(geometry);
+    feature
+}
+
+fn messages(tile: &vector_tile::Tile) -> Vec<String> {
+    validate(tile).iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_valid_tiles() {
+    let mut f = File::open("../t-rex-service/src/test/tile.pbf").unwrap();
+    let tile = Tile::read_from(&mut f).unwrap();
+    assert_eq!(validate(&tile), Vec::<Violation>::new());
+
+    // Polygon with exterior and interior ring (spec example 4.3.5.3)
+    let mut poly_layer = layer("polygons");
+    poly_layer.mut_features().push(feature(
+        vector_tile::Tile_GeomType::POLYGON,
+        vec![
+            9, 0, 0, 26, 20, 0, 0, 20, 19, 0, 15, // exterior
+            9, 22, 2, 26, 18, 0, 0, 18, 17, 0, 15, // exterior
+            9, 4, 13, 26, 0, 8, 8, 0, 0, 7, 15, // interior
+        ],
+    ));
+    let mut tile = vector_tile::Tile::new();
+    tile.mut_layers().push(poly_layer);
+    assert_eq!(messages(&tile), Vec::<String>::new());
+}
+
+#[test]
+fn test_layer_violations() {
+    let mut tile = vector_tile::Tile::new();
+    let mut points = layer("points");
+    points.mut_keys().push("name".to_string());
+    points.mut_keys().push("name".to_string());
+    points.set_version(1);
+    tile.mut_layers().push(points);
+    let mut dup = layer("points");
+    dup.set_extent(0);
+    tile.mut_layers().push(dup);
+    assert_eq!(
+        messages(&tile),
+        vec![
+            "Layer 'points': Unsupported version 1",
+            "Layer 'points': Duplicate key 'name'",
+            "Layer 'points': Duplicate layer name",
+            "Layer 'points': Extent must be greater than 0",
+        ]
+    );
+}
+
+#[test]
+fn test_feature_violations() {
+    let mut points = layer("points");
+    points.mut_keys().push("name".to_string());
+    let mut pt = feature(vector_tile::Tile_GeomType::POINT, vec![9, 50, 34]);
+    pt.set_tags(vec![0, 0, 1]);
+    pt.set_id(1);
+    points.mut_features().push(pt.clone());
+    pt.set_tags(vec![]);
+    points.mut_features().push(pt);
+    // MoveTo with count 0
+    points
+        .mut_features()
+        .push(feature(vector_tile::Tile_GeomType::POINT, vec![1]));
+    // Point outside buffer
+    points.mut_features().push(feature(
+        vector_tile::Tile_GeomType::POINT,
+        vec![9, 20000, 0],
+    ));
+    let mut lines = layer("lines");
+    lines.mut_features().push(feature(
+        vector_tile::Tile_GeomType::LINESTRING,
+        vec![9, 4, 4],
+    ));
+    let mut polygons = layer("polygons");
+    // Counter-clockwise exterior ring
+    polygons.mut_features().push(feature(
+        vector_tile::Tile_GeomType::POLYGON,
+        vec![9, 0, 0, 26, 0, 20, 20, 0, 0, 19, 15],
+    ));
+    // Missing ClosePath
+    polygons.mut_features().push(feature(
+        vector_tile::Tile_GeomType::POLYGON,
+        vec![9, 0, 0, 26, 20, 0, 0, 20, 19, 0],
+    ));
+    let mut tile = vector_tile::Tile::new();
+    tile.mut_layers().push(points);
+    tile.mut_layers().push(lines);
+    tile.mut_layers().push(polygons);
+    assert_eq!(
+        messages(&tile),
+        vec![
+            "Layer 'points' feature 0: Odd number of tags",
+            "Layer 'points' feature 0: Value index 0 out of range",
+            "Layer 'points' feature 0: Key index 1 out of range",
+            "Layer 'points' feature 1: Duplicate feature id 1",
+            "Layer 'points' feature 2: Invalid geometry encoding: command 1 with count 0",
+            "Layer 'points' feature 3: 1 coordinates more than one tile outside of extent",
+            "Layer 'lines' feature 0: LineString must be a sequence of MoveTo(1) LineTo commands",
+            "Layer 'polygons' feature 0: First ring must be an exterior ring (clockwise)",
+            "Layer 'polygons' feature 1: Polygon must be a sequence of MoveTo(1) LineTo ClosePath commands",
+        ]
+    );
+}
+
+/// Conformance with the official mvt-fixtures suite (https://github.com/mapbox/mvt-fixtures).
+/// Fixtures are fetched with `make mvt-fixtures` in the data directory.
+#[test]
+fn test_mvt_fixtures() {
+    let dir = Path::new("../data/mvt-fixtures/fixtures");
+    if !dir.exists() {
+        println!("Skipping mvt-fixtures - run `make mvt-fixtures` in data directory");
+        return;
+    }
+    let mut fixtures: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join("info.json").exists())
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty());
+    let mut mismatches = Vec::new();
+    for fixture in fixtures {
+        let info: serde_json::Value =
+            serde_json::from_reader(File::open(fixture.join("info.json")).unwrap()).unwrap();
+        let expected_valid = info["validity"]["v2"].as_bool().unwrap();
+        let violations = File::open(fixture.join("tile.mvt"))
+            .map_err(|e| e.to_string())
+            .and_then(|mut f| Tile::read_from(&mut f).map_err(|e| e.to_string()))
+            .map(|tile| messages(&tile))
+            .unwrap_or_else(|e| vec![format!("Decoding failed: {}", e)]);
+        if violations.is_empty() != expected_valid {
+            mismatches.push(format!(
+                "{}: expected valid={} - {:?}",
+                fixture.file_name().unwrap().to_string_lossy(),
+                expected_valid,
+                violations
+            ));
+        }
+    }
+    assert_eq!(mismatches, Vec::<String>::new());
+}
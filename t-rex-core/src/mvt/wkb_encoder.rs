@@ -0,0 +1,176 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Encode (E)WKB geometries into MVT commands without intermediate geometry structs
+
+use crate::core::screen;
+use crate::datasource::wkb_reader::WkbReader;
+use crate::mvt::geom_encoder::{CommandSequence, EncodableGeom};
+use crate::mvt::vector_tile::Tile_GeomType;
+use tile_grid::Extent;
+
+const WKB_POINT: u32 = 1;
+const WKB_LINESTRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOINT: u32 = 4;
+const WKB_MULTILINESTRING: u32 = 5;
+const WKB_MULTIPOLYGON: u32 = 6;
+
+/// Base geometry type and number of ordinates of WKB geometry header
+fn read_typed_header(reader: &mut WkbReader, geom_type: u32) -> Result<(bool, usize), String> {
+    let (little_endian, header_type, dims) = reader.header()?;
+    if header_type != geom_type {
+        return Err(format!(
+            "Invalid WKB: expected geometry type {}, found {}",
+            geom_type, header_type
+        ));
+    }
+    Ok((little_endian, dims))
+}
+
+/// WKB geometry encoder producing the same commands as `ScreenGeom` with `EncodableGeom`
+pub struct WkbEncoder<'a> {
+    pub extent: &'a Extent,
+    pub reverse_y: bool,
+    pub tile_size: u32,
+    /// Remove collinear points of lines and polygons
+    pub remove_collinear: bool,
+    /// Minimal area of polygon holes
+    pub min_hole_area: Option<f64>,
+}
+
+impl<'a> WkbEncoder<'a> {
+    /// Encode geometry into MVT commands. Returns None for geometry types without
+    /// MVT representation like collections or curves.
+    pub fn encode(&self, wkb: &[u8]) -> Result<Option<(Tile_GeomType, CommandSequence)>, String> {
+        let mut reader = WkbReader::new(wkb);
+        let (le, geom_type, dims) = reader.header()?;
+        let mut state = EncoderState {
+            encoder: self,
+            reader,
+            pixel_size_x: (self.extent.maxx - self.extent.minx) / self.tile_size as f64,
+            pixel_size_y: (self.extent.maxy - self.extent.miny) / self.tile_size as f64,
+            line: screen::LineString { points: Vec::new() },
+            seq: CommandSequence(Vec::new()),
+        };
+        let geom_type = match geom_type {
+            WKB_POINT => {
+                let point = state.read_point(le, dims)?;
+                point.encode_from(&screen::Point::origin(), &mut state.seq);
+                Tile_GeomType::POINT
+            }
+            WKB_MULTIPOINT => {
+                let mut multipoint = screen::MultiPoint { points: Vec::new() };
+                for _ in 0..state.reader.read_u32(le)? {
+                    let (le, dims) = read_typed_header(&mut state.reader, WKB_POINT)?;
+                    multipoint.points.push(state.read_point(le, dims)?);
+                }
+                multipoint.encode_from(&screen::Point::origin(), &mut state.seq);
+                Tile_GeomType::POINT
+            }
+            WKB_LINESTRING => {
+                state.read_line(le, dims)?;
+                state
+                    .line
+                    .encode_from(&screen::Point::origin(), &mut state.seq);
+                Tile_GeomType::LINESTRING
+            }
+            WKB_MULTILINESTRING => {
+                let mut pos = screen::Point::origin();
+                for _ in 0..state.reader.read_u32(le)? {
+                    let (le, dims) = read_typed_header(&mut state.reader, WKB_LINESTRING)?;
+                    state.read_line(le, dims)?;
+                    if let Some(last) = state.line.points.last() {
+                        state.line.encode_from(&pos, &mut state.seq);
+                        pos = screen::Point {
+                            x: last.x,
+                            y: last.y,
+                        };
+                    }
+                }
+                Tile_GeomType::LINESTRING
+            }
+            WKB_POLYGON => {
+                state.encode_polygon(le, dims, &mut screen::Point::origin())?;
+                Tile_GeomType::POLYGON
+            }
+            WKB_MULTIPOLYGON => {
+                let mut pos = screen::Point::origin();
+                for _ in 0..state.reader.read_u32(le)? {
+                    let (le, dims) = read_typed_header(&mut state.reader, WKB_POLYGON)?;
+                    state.encode_polygon(le, dims, &mut pos)?;
+                }
+                Tile_GeomType::POLYGON
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some((geom_type, state.seq)))
+    }
+}
+
+struct EncoderState<'a, 'e> {
+    encoder: &'e WkbEncoder<'e>,
+    reader: WkbReader<'a>,
+    pixel_size_x: f64,
+    pixel_size_y: f64,
+    /// Points of current line or ring, reused for all parts of a geometry
+    line: screen::LineString,
+    seq: CommandSequence,
+}
+
+impl<'a, 'e> EncoderState<'a, 'e> {
+    fn read_point(&mut self, le: bool, dims: usize) -> Result<screen::Point, String> {
+        let (x, y) = self.reader.coord(le, dims)?;
+        let extent = self.encoder.extent;
+        let mut point = screen::Point {
+            x: ((x - extent.minx) / self.pixel_size_x) as i32,
+            y: ((y - extent.miny) / self.pixel_size_y) as i32,
+        };
+        if self.encoder.reverse_y {
+            point.y = (self.encoder.tile_size as i32).saturating_sub(point.y)
+        };
+        Ok(point)
+    }
+    /// Read points into `line`, skipping repeated screen points
+    fn read_line(&mut self, le: bool, dims: usize) -> Result<(), String> {
+        let num_points = self.reader.read_u32(le)?;
+        self.line.points.clear();
+        for _ in 0..num_points {
+            let point = self.read_point(le, dims)?;
+            if self.line.points.last() != Some(&point) {
+                self.line.points.push(point);
+            }
+        }
+        if self.encoder.remove_collinear {
+            self.line.remove_collinear();
+        }
+        Ok(())
+    }
+    fn encode_polygon(
+        &mut self,
+        le: bool,
+        dims: usize,
+        pos: &mut screen::Point,
+    ) -> Result<(), String> {
+        for ring_no in 0..self.reader.read_u32(le)? {
+            self.read_line(le, dims)?;
+            if let Some(min_area) = self.encoder.min_hole_area {
+                if ring_no > 0 && self.line.ring_area() < min_area {
+                    continue;
+                }
+            }
+            let len = self.line.points.len();
+            if len > 1 {
+                self.line.encode_ring_from(pos, &mut self.seq);
+                let last = &self.line.points[len - 2];
+                *pos = screen::Point {
+                    x: last.x,
+                    y: last.y,
+                };
+            }
+        }
+        Ok(())
+    }
+}
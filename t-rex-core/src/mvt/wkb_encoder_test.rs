@@ -0,0 +1,287 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::core::feature::{Feature, FeatureAttr};
+use crate::core::geom::{self, GeometryType};
+use crate::core::layer::Layer;
+use crate::mvt::tile::Tile;
+use crate::mvt::vector_tile;
+use crate::mvt::wkb_encoder::WkbEncoder;
+use postgis::ewkb::EwkbRead;
+use tile_grid::Extent;
+
+type Coords<'a> = &'a [(f64, f64)];
+
+/// Minimal (E)WKB writer
+struct WkbWriter {
+    little_endian: bool,
+    srid: Option<u32>,
+    has_z: bool,
+    buf: Vec<u8>,
+}
+
+impl WkbWriter {
+    fn new(little_endian: bool, srid: Option<u32>, has_z: bool) -> WkbWriter {
+        WkbWriter {
+            little_endian,
+            srid,
+            has_z,
+            buf: Vec::new(),
+        }
+    }
+    fn u32(&mut self, v: u32) {
+        let bytes = if self.little_endian {
+            v.to_le_bytes()
+        } else {
+            v.to_be_bytes()
+        };
+        self.buf.extend_from_slice(&bytes);
+    }
+    fn f64(&mut self, v: f64) {
+        let bytes = if self.little_endian {
+            v.to_le_bytes()
+        } else {
+            v.to_be_bytes()
+        };
+        self.buf.extend_from_slice(&bytes);
+    }
+    fn header(&mut self, geom_type: u32, top_level: bool) {
+        self.buf.push(self.little_endian as u8);
+        let mut type_id = geom_type;
+        if self.has_z {
+            type_id |= 0x8000_0000;
+        }
+        match self.srid {
+            Some(srid) if top_level => {
+                self.u32(type_id | 0x2000_0000);
+                self.u32(srid);
+            }
+            _ => self.u32(type_id),
+        }
+    }
+    fn coords(&mut self, coords: Coords) {
+        for &(x, y) in coords {
+            self.f64(x);
+            self.f64(y);
+            if self.has_z {
+                self.f64(99.0);
+            }
+        }
+    }
+    fn points(&mut self, coords: Coords) {
+        self.u32(coords.len() as u32);
+        self.coords(coords);
+    }
+    fn rings(&mut self, rings: &[Coords]) {
+        self.u32(rings.len() as u32);
+        for ring in rings {
+            self.points(ring);
+        }
+    }
+    fn point(mut self, x: f64, y: f64) -> Vec<u8> {
+        self.header(1, true);
+        self.coords(&[(x, y)]);
+        self.buf
+    }
+    fn linestring(mut self, coords: Coords) -> Vec<u8> {
+        self.header(2, true);
+        self.points(coords);
+        self.buf
+    }
+    fn polygon(mut self, rings: &[Coords]) -> Vec<u8> {
+        self.header(3, true);
+        self.rings(rings);
+        self.buf
+    }
+    fn multipoint(mut self, coords: Coords) -> Vec<u8> {
+        self.header(4, true);
+        self.u32(coords.len() as u32);
+        for &(x, y) in coords {
+            self.header(1, false);
+            self.coords(&[(x, y)]);
+        }
+        self.buf
+    }
+    fn multilinestring(mut self, lines: &[Coords]) -> Vec<u8> {
+        self.header(5, true);
+        self.u32(lines.len() as u32);
+        for line in lines {
+            self.header(2, false);
+            self.points(line);
+        }
+        self.buf
+    }
+    fn multipolygon(mut self, polygons: &[&[Coords]]) -> Vec<u8> {
+        self.header(6, true);
+        self.u32(polygons.len() as u32);
+        for rings in polygons {
+            self.header(3, false);
+            self.rings(rings);
+        }
+        self.buf
+    }
+}
+
+/// Feature with EWKB geometry decoded by rust-postgis and optionally available as raw WKB
+struct WkbFeature {
+    wkb: Vec<u8>,
+    raw: bool,
+}
+
+impl Feature for WkbFeature {
+    fn fid(&self) -> Option<u64> {
+        None
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        Vec::new()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        let geom =
+            geom::Geometry::read_ewkb(&mut self.wkb.as_slice()).map_err(|e| e.to_string())?;
+        Ok(match geom {
+            geom::Geometry::Point(g) => GeometryType::Point(g),
+            geom::Geometry::LineString(g) => GeometryType::LineString(g),
+            geom::Geometry::Polygon(g) => GeometryType::Polygon(g),
+            geom::Geometry::MultiPoint(g) => GeometryType::MultiPoint(g),
+            geom::Geometry::MultiLineString(g) => GeometryType::MultiLineString(g),
+            geom::Geometry::MultiPolygon(g) => GeometryType::MultiPolygon(g),
+            geom::Geometry::GeometryCollection(g) => GeometryType::GeometryCollection(g),
+        })
+    }
+    fn geometry_wkb(&self) -> Option<&[u8]> {
+        if self.raw {
+            Some(&self.wkb)
+        } else {
+            None
+        }
+    }
+}
+
+const EXTENT: Extent = Extent {
+    minx: 0.0,
+    miny: 0.0,
+    maxx: 2048.0,
+    maxy: 2048.0,
+};
+
+const RING: &[(f64, f64)] = &[
+    (100.0, 100.0),
+    (100.4, 100.2),
+    (500.0, 100.0),
+    (1000.0, 100.0),
+    (1000.0, 1000.0),
+    (100.0, 1000.0),
+    (100.0, 100.0),
+];
+const HOLE: &[(f64, f64)] = &[
+    (200.0, 200.0),
+    (200.0, 800.0),
+    (800.0, 800.0),
+    (800.0, 200.0),
+    (200.0, 200.0),
+];
+const SMALL_HOLE: &[(f64, f64)] = &[
+    (900.0, 900.0),
+    (900.0, 905.0),
+    (905.0, 905.0),
+    (900.0, 900.0),
+];
+const DEGENERATED: &[(f64, f64)] = &[(300.0, 300.0), (300.2, 300.7), (300.0, 300.0)];
+const LINE: &[(f64, f64)] = &[
+    (10.0, 10.0),
+    (10.2, 10.7),
+    (20.0, 10.0),
+    (30.0, 10.0),
+    (35.5, 50.5),
+];
+
+fn sample_geometries(little_endian: bool, srid: Option<u32>, has_z: bool) -> Vec<Vec<u8>> {
+    let w = || WkbWriter::new(little_endian, srid, has_z);
+    vec![
+        w().point(960.5, 1024.7),
+        w().point(-10.0, 3000.0),
+        w().multipoint(&[(10.0, 10.0), (10.0, 10.0), (20.0, 30.0)]),
+        w().multipoint(&[]),
+        w().linestring(LINE),
+        w().linestring(&[(10.0, 10.0), (10.3, 10.4)]),
+        w().multilinestring(&[LINE, &[(40.0, 40.0)], &[(50.0, 50.0), (60.0, 50.0)]]),
+        w().polygon(&[RING, HOLE, SMALL_HOLE]),
+        w().polygon(&[DEGENERATED]),
+        w().multipolygon(&[&[RING, SMALL_HOLE], &[DEGENERATED], &[HOLE]]),
+    ]
+}
+
+fn encoded_features(layer: &Layer, raw: bool, reverse_y: bool) -> Vec<vector_tile::Tile_Feature> {
+    let mut tile = Tile::new(&EXTENT, reverse_y);
+    let mut mvt_layer = tile.new_layer(layer);
+    for (little_endian, srid, has_z) in [
+        (true, None, false),
+        (false, None, false),
+        (true, Some(3857), false),
+        (false, Some(3857), false),
+    ] {
+        for wkb in sample_geometries(little_endian, srid, has_z) {
+            let feature = WkbFeature { wkb, raw };
+            tile.add_feature(&mut mvt_layer, &feature).unwrap();
+        }
+    }
+    mvt_layer.take_features().into_vec()
+}
+
+#[test]
+fn test_wkb_encoding() {
+    let encoder = WkbEncoder {
+        extent: &EXTENT,
+        reverse_y: false,
+        tile_size: 4096,
+        remove_collinear: false,
+        min_hole_area: None,
+    };
+    let wkb = WkbWriter::new(true, Some(3857), false).point(960.5, 1024.7);
+    let (geom_type, seq) = encoder.encode(&wkb).unwrap().unwrap();
+    assert_eq!(geom_type, vector_tile::Tile_GeomType::POINT);
+    assert_eq!(seq.0, &[9, 3842, 4098]);
+
+    let wkb = WkbWriter::new(false, None, true).linestring(&[(1.0, 1.0), (2.0, 1.0)]);
+    let (geom_type, seq) = encoder.encode(&wkb).unwrap().unwrap();
+    assert_eq!(geom_type, vector_tile::Tile_GeomType::LINESTRING);
+    assert_eq!(seq.0, &[9, 4, 4, 10, 4, 0]);
+
+    // Z coordinates are ignored
+    for (wkb, wkb_z) in sample_geometries(true, None, false)
+        .into_iter()
+        .zip(sample_geometries(false, Some(3857), true))
+    {
+        assert_eq!(
+            encoder.encode(&wkb_z).unwrap().map(|(_, seq)| seq.0),
+            encoder.encode(&wkb).unwrap().map(|(_, seq)| seq.0)
+        );
+    }
+
+    // GeometryCollection
+    let mut wkb = WkbWriter::new(true, None, false);
+    wkb.header(7, true);
+    wkb.u32(0);
+    assert!(encoder.encode(&wkb.buf).unwrap().is_none());
+
+    // Truncated
+    let wkb = WkbWriter::new(true, None, false).linestring(LINE);
+    assert!(encoder.encode(&wkb[..wkb.len() - 4]).is_err());
+}
+
+#[test]
+fn test_wkb_encoding_matches_geometry_encoding() {
+    let mut layer = Layer::new("wkb");
+    for reverse_y in [false, true] {
+        let expected = encoded_features(&layer, false, reverse_y);
+        assert_eq!(expected.len(), 32);
+        assert_eq!(encoded_features(&layer, true, reverse_y), expected);
+    }
+
+    layer.remove_collinear = true;
+    layer.min_hole_area = Some(100.0);
+    let expected = encoded_features(&layer, false, true);
+    assert_eq!(encoded_features(&layer, true, true), expected);
+}
@@ -3,9 +3,14 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+#[cfg(feature = "server")]
+use crate::cache::Tilecache;
 use crate::core::config::Config;
-use crate::core::config::{TilesetCacheCfg, TilesetCfg};
-use crate::core::layer::Layer;
+use crate::core::config::{
+    TilesetAccessCfg, TilesetCacheCfg, TilesetCfg, TilesetCrawlCfg, TimeDimensionCfg,
+};
+use crate::core::layer::{Layer, Volatility};
+use std::cmp;
 use tile_grid::Extent;
 
 #[derive(Clone, Debug)]
@@ -28,6 +33,256 @@ impl<'a> Config<'a, TilesetCacheCfg> for CacheLimits {
     }
 }
 
+/// Referrer restriction for tile requests
+#[derive(Clone, Debug)]
+pub struct AccessRestriction {
+    pub allowed_referrers: Vec<String>,
+    pub allow_missing: bool,
+    pub token: Option<String>,
+}
+
+/// Origin or Referer header of a tile request
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Referrer<'a> {
+    /// Origin header of cross-origin requests (scheme, host and port)
+    Origin(&'a str),
+    /// Referer header (URL of requesting page)
+    Referer(&'a str),
+}
+
+/// Allowed referrer `scheme://host[:port][/path]`. The host may start with `*.` for any
+/// subdomain, the path may contain `*` wildcards. Patterns without path allow any page.
+#[derive(Debug, PartialEq)]
+struct ReferrerPattern<'a> {
+    scheme: &'a str,
+    host: &'a str,
+    port: Option<u16>,
+    path: &'a str,
+}
+
+impl<'a> ReferrerPattern<'a> {
+    fn parse(pattern: &'a str) -> Result<ReferrerPattern<'a>, String> {
+        let invalid = || {
+            format!(
+                "Invalid referrer pattern '{}': expected `scheme://host[:port][/path]`",
+                pattern
+            )
+        };
+        let (scheme, rest) = pattern.split_once("://").ok_or_else(invalid)?;
+        let (authority, path) = match rest.find('/') {
+            Some(pos) => rest.split_at(pos),
+            None => (rest, ""),
+        };
+        // IPv6 addresses are enclosed in brackets
+        let (host, port) = match authority.rfind(':') {
+            Some(pos) if !authority[pos..].contains(']') => {
+                let port = authority[pos + 1..].parse().map_err(|_| invalid())?;
+                (&authority[..pos], Some(port))
+            }
+            _ => (authority, None),
+        };
+        let wildcard_host = host.strip_prefix("*.").unwrap_or(host);
+        if scheme.is_empty() || wildcard_host.is_empty() || wildcard_host.contains('*') {
+            return Err(invalid());
+        }
+        Ok(ReferrerPattern {
+            scheme,
+            host,
+            port,
+            path,
+        })
+    }
+    /// Match scheme, host and port of URL, and path if `with_path` is set
+    fn matches(&self, url: &url::Url, with_path: bool) -> bool {
+        let host = match url.host_str() {
+            Some(host) => host,
+            None => return false,
+        };
+        let host_matches = match self.host.strip_prefix('*') {
+            // Subdomain on label boundary, e.g. `*.example.org` matches `maps.example.org`
+            Some(suffix) => host.len() > suffix.len() && ends_with_ignore_case(host, suffix),
+            None => host.eq_ignore_ascii_case(self.host),
+        };
+        let port = self.port.or_else(|| default_port(self.scheme));
+        url.scheme().eq_ignore_ascii_case(self.scheme)
+            && host_matches
+            && url.port_or_known_default() == port
+            && (!with_path || self.path.is_empty() || wildcard_match(self.path, url.path()))
+    }
+}
+
+fn ends_with_ignore_case(text: &str, suffix: &str) -> bool {
+    text.len() >= suffix.len()
+        && text.is_char_boundary(text.len() - suffix.len())
+        && text[text.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        _ => None,
+    }
+}
+
+/// Match text against pattern with `*` wildcards
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    if !text.ends_with(last) {
+        return false;
+    }
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+impl AccessRestriction {
+    /// Check Origin or Referer header value and bypass token.
+    /// Origin headers contain no path and are matched against scheme, host and port of the patterns.
+    pub fn is_allowed(&self, referrer: Option<Referrer>, token: Option<&str>) -> bool {
+        if let (Some(expected), Some(token)) = (&self.token, token) {
+            if expected == token {
+                return true;
+            }
+        }
+        let (url, with_path) = match referrer {
+            Some(Referrer::Origin(origin)) => (origin, false),
+            Some(Referrer::Referer(referer)) => (referer, true),
+            None => return self.allow_missing,
+        };
+        let url = match url::Url::parse(url) {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+        self.allowed_referrers.iter().any(|pattern| {
+            pattern == "*"
+                || ReferrerPattern::parse(pattern)
+                    .map_or(false, |pattern| pattern.matches(&url, with_path))
+        })
+    }
+}
+
+impl<'a> Config<'a, TilesetAccessCfg> for AccessRestriction {
+    fn from_config(cfg: &TilesetAccessCfg) -> Result<Self, String> {
+        for pattern in cfg.allowed_referrers.iter().filter(|p| *p != "*") {
+            ReferrerPattern::parse(pattern)?;
+        }
+        Ok(AccessRestriction {
+            allowed_referrers: cfg.allowed_referrers.clone(),
+            allow_missing: cfg.allow_missing,
+            token: cfg.token.clone(),
+        })
+    }
+    fn gen_config() -> String {
+        "".to_string()
+    }
+}
+
+/// Protection against bulk enumeration of tiles by crawlers
+#[derive(Clone, Debug)]
+pub struct CrawlProtection {
+    /// Exclude tileset in generated robots.txt
+    pub disallow: bool,
+    /// Maximal number of sequentially enumerated tiles per client within `window`
+    pub max_sequential: u32,
+    /// Detection time window in seconds
+    pub window: u64,
+    /// Token allowing bulk requests
+    pub token: Option<String>,
+}
+
+impl CrawlProtection {
+    /// Check crawl token
+    pub fn token_valid(&self, token: Option<&str>) -> bool {
+        match (&self.token, token) {
+            (Some(expected), Some(token)) => expected == token,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Config<'a, TilesetCrawlCfg> for CrawlProtection {
+    fn from_config(cfg: &TilesetCrawlCfg) -> Result<Self, String> {
+        let protection = CrawlProtection {
+            disallow: cfg.disallow.unwrap_or(true),
+            max_sequential: cfg.max_sequential.unwrap_or(200),
+            window: cfg.window.unwrap_or(60),
+            token: cfg.token.clone(),
+        };
+        if protection.max_sequential == 0 || protection.window == 0 {
+            return Err("Crawl protection requires max_sequential and window > 0".to_string());
+        }
+        Ok(protection)
+    }
+    fn gen_config() -> String {
+        "".to_string()
+    }
+}
+
+/// Time steps of a tileset, substituted for `!time!` in layer queries
+#[derive(Clone, Debug)]
+pub struct TimeDimension {
+    pub values: Vec<String>,
+    pub range: Option<(String, String)>,
+    pub default: Option<String>,
+}
+
+impl TimeDimension {
+    /// Check time against configured values or range
+    pub fn is_valid(&self, time: &str) -> bool {
+        if self.values.iter().any(|v| v == time) {
+            return true;
+        }
+        match self.range {
+            // ISO 8601 values of equal length are ordered lexicographically
+            Some((ref min, ref max)) => {
+                time.len() == min.len()
+                    && time.len() == max.len()
+                    && time
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || "-:.TZ".contains(c))
+                    && min.as_str() <= time
+                    && time <= max.as_str()
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a> Config<'a, TimeDimensionCfg> for TimeDimension {
+    fn from_config(cfg: &TimeDimensionCfg) -> Result<Self, String> {
+        let time = TimeDimension {
+            values: cfg.values.clone(),
+            range: cfg.range.clone(),
+            default: cfg.default.clone(),
+        };
+        if time.values.is_empty() && time.range.is_none() {
+            return Err("Time dimension requires values or range".to_string());
+        }
+        if let Some(ref default) = time.default {
+            if !time.is_valid(default) {
+                return Err(format!("Invalid default time '{}'", default));
+            }
+        }
+        Ok(time)
+    }
+    fn gen_config() -> String {
+        "".to_string()
+    }
+}
+
 /// Collection of layers in one MVT
 #[derive(Clone)]
 pub struct Tileset {
@@ -38,8 +293,25 @@ pub struct Tileset {
     pub extent: Option<Extent>,
     pub center: Option<(f64, f64)>,
     pub start_zoom: Option<u8>,
+    /// Tags for catalog discovery
+    pub tags: Vec<String>,
     pub layers: Vec<Layer>,
     pub cache_limits: Option<CacheLimits>,
+    /// Cache of this tileset instead of the service cache
+    #[cfg(feature = "server")]
+    pub cache: Option<Tilecache>,
+    /// Restriction of tile requests to allowed referrers
+    pub access: Option<AccessRestriction>,
+    /// Time dimension (`/{tileset}/{time}/{z}/{x}/{y}.pbf`)
+    pub time: Option<TimeDimension>,
+    /// Content type of vector tiles (default: negotiated from extension and Accept header)
+    pub content_type: Option<String>,
+    /// Detection of tile enumeration by crawlers
+    pub crawl_protection: Option<CrawlProtection>,
+    /// MBTiles file with pre-rendered tiles
+    pub mbtiles: Option<String>,
+    /// PMTiles archive (file or URL) with pre-rendered tiles
+    pub pmtiles: Option<String>,
 }
 
 pub static WORLD_EXTENT: Extent = Extent {
@@ -50,18 +322,20 @@ pub static WORLD_EXTENT: Extent = Extent {
 };
 
 impl Tileset {
+    /// Effective minimum zoom level (tileset setting limited to zoom range of layers)
     pub fn minzoom(&self) -> u8 {
-        self.minzoom
-            .unwrap_or(self.layers.iter().map(|l| l.minzoom()).min().unwrap_or(0))
+        let layers_minzoom = self.layers.iter().map(|l| l.minzoom()).min().unwrap_or(0);
+        cmp::max(self.minzoom.unwrap_or(0), layers_minzoom)
     }
+    /// Effective maximum zoom level (tileset setting limited to zoom range of layers)
     pub fn maxzoom(&self) -> u8 {
-        self.maxzoom.unwrap_or(
-            self.layers
-                .iter()
-                .map(|l| l.maxzoom(22))
-                .max()
-                .unwrap_or(22),
-        )
+        let layers_maxzoom = self
+            .layers
+            .iter()
+            .map(|l| l.maxzoom(22))
+            .max()
+            .unwrap_or(22);
+        cmp::min(self.maxzoom.unwrap_or(22), layers_maxzoom)
     }
     pub fn attribution(&self) -> String {
         self.attribution.clone().unwrap_or("".to_string())
@@ -83,7 +357,34 @@ impl Tileset {
     pub fn get_start_zoom(&self) -> u8 {
         self.start_zoom.unwrap_or(2)
     }
+    /// Check whether tileset or one of its layers is tagged with `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+            || self.layers.iter().any(|l| l.tags.iter().any(|t| t == tag))
+    }
+    /// Check referrer restriction of tile request
+    pub fn access_allowed(&self, referrer: Option<Referrer>, token: Option<&str>) -> bool {
+        match self.access {
+            Some(ref access) => access.is_allowed(referrer, token),
+            None => true,
+        }
+    }
+    /// Volatility of the most volatile layer
+    pub fn volatility(&self) -> Option<Volatility> {
+        self.layers.iter().filter_map(|l| l.volatility).max()
+    }
+    /// Cache-Control max-age derived from layer volatility
+    pub fn cache_max_age(&self, default: u32) -> u32 {
+        self.volatility().map(|v| v.max_age()).unwrap_or(default)
+    }
+    /// Lifetime of cached tiles in seconds (None: unlimited)
+    pub fn cache_ttl(&self) -> Option<u64> {
+        self.volatility().and_then(|v| v.cache_ttl())
+    }
     pub fn is_cachable_at(&self, zoom: u8) -> bool {
+        if self.cache_ttl() == Some(0) {
+            return false;
+        }
         match self.cache_limits {
             Some(ref cl) => !cl.no_cache && cl.minzoom <= zoom && cl.maxzoom.unwrap_or(99) >= zoom,
             None => true,
@@ -96,8 +397,8 @@ impl<'a> Config<'a, TilesetCfg> for Tileset {
         let layers = tileset_cfg
             .layers
             .iter()
-            .map(|layer| Layer::from_config(layer).unwrap())
-            .collect();
+            .map(Layer::from_config)
+            .collect::<Result<Vec<_>, _>>()?;
         let cache_limits: Option<CacheLimits> = match tileset_cfg.cache_limits {
             Some(ref cfg) => match CacheLimits::from_config(&cfg) {
                 Ok(cl) => Some(cl),
@@ -105,11 +406,68 @@ impl<'a> Config<'a, TilesetCfg> for Tileset {
             },
             None => None,
         };
+        #[cfg(feature = "server")]
+        let cache = match tileset_cfg.cache {
+            Some(ref cfg) => Some(
+                Tilecache::from_cache_cfg(cfg)
+                    .map_err(|e| format!("Tileset '{}': {}", tileset_cfg.name, e))?,
+            ),
+            None => None,
+        };
+        let access = match tileset_cfg.access {
+            Some(ref cfg) => Some(AccessRestriction::from_config(cfg)?),
+            None => None,
+        };
+        let time = match tileset_cfg.time {
+            Some(ref cfg) => Some(
+                TimeDimension::from_config(cfg)
+                    .map_err(|e| format!("Tileset '{}': {}", tileset_cfg.name, e))?,
+            ),
+            None => None,
+        };
+        let crawl_protection = match tileset_cfg.crawl_protection {
+            Some(ref cfg) => Some(
+                CrawlProtection::from_config(cfg)
+                    .map_err(|e| format!("Tileset '{}': {}", tileset_cfg.name, e))?,
+            ),
+            None => None,
+        };
+        if let Some(ref content_type) = tileset_cfg.content_type {
+            if content_type.is_empty()
+                || !content_type
+                    .chars()
+                    .all(|c| c.is_ascii_graphic() || c == ' ')
+            {
+                return Err(format!(
+                    "Tileset '{}': invalid content_type '{}'",
+                    tileset_cfg.name, content_type
+                ));
+            }
+        }
+        let archive = match (&tileset_cfg.mbtiles, &tileset_cfg.pmtiles) {
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "Tileset '{}': mbtiles can't be combined with pmtiles",
+                    tileset_cfg.name
+                ))
+            }
+            (Some(_), None) => Some("mbtiles"),
+            (None, Some(_)) => Some("pmtiles"),
+            (None, None) => None,
+        };
+        if let Some(archive) = archive {
+            if !tileset_cfg.layers.is_empty() {
+                return Err(format!(
+                    "Tileset '{}': layers can't be combined with {}",
+                    tileset_cfg.name, archive
+                ));
+            }
+        }
         let extent = match &tileset_cfg.extent {
             Some(cfg) => Some(Extent::from(cfg)),
             None => None,
         };
-        Ok(Tileset {
+        let tileset = Tileset {
             name: tileset_cfg.name.clone(),
             minzoom: tileset_cfg.minzoom.clone(),
             maxzoom: tileset_cfg.maxzoom.clone(),
@@ -117,9 +475,34 @@ impl<'a> Config<'a, TilesetCfg> for Tileset {
             extent,
             center: tileset_cfg.center.clone(),
             start_zoom: tileset_cfg.start_zoom.clone(),
+            tags: tileset_cfg.tags.clone(),
             layers: layers,
             cache_limits: cache_limits,
-        })
+            #[cfg(feature = "server")]
+            cache,
+            access,
+            time,
+            content_type: tileset_cfg.content_type.clone(),
+            crawl_protection,
+            mbtiles: tileset_cfg.mbtiles.clone(),
+            pmtiles: tileset_cfg.pmtiles.clone(),
+        };
+        if tileset.minzoom() > tileset.maxzoom() {
+            warn!(
+                "Tileset '{}': zoom range of tileset doesn't overlap with zoom range of layers",
+                tileset.name
+            );
+        } else if tileset.minzoom.is_some() && tileset.minzoom != Some(tileset.minzoom())
+            || tileset.maxzoom.is_some() && tileset.maxzoom != Some(tileset.maxzoom())
+        {
+            info!(
+                "Tileset '{}': zoom range limited to zoom range of layers ({}-{})",
+                tileset.name,
+                tileset.minzoom(),
+                tileset.maxzoom()
+            );
+        }
+        Ok(tileset)
     }
     fn gen_config() -> String {
         let mut config = String::new();
@@ -148,6 +531,7 @@ fn test_zoom() {
         center: None,
         start_zoom: Some(3),
         attribution: None,
+        tags: Vec::new(),
         extent: Some(Extent {
             minx: -179.58998,
             miny: -90.00000,
@@ -156,6 +540,14 @@ fn test_zoom() {
         }),
         layers: vec![layer],
         cache_limits: None,
+        #[cfg(feature = "server")]
+        cache: None,
+        access: None,
+        time: None,
+        content_type: None,
+        crawl_protection: None,
+        mbtiles: None,
+        pmtiles: None,
     };
 
     assert_eq!(tileset.minzoom(), 0);
@@ -168,5 +560,162 @@ fn test_zoom() {
     assert_eq!(tileset.minzoom(), 3);
 
     tileset.minzoom = Some(2);
-    assert_eq!(tileset.minzoom(), 2);
+    assert_eq!(tileset.minzoom(), 3);
+
+    tileset.minzoom = Some(5);
+    assert_eq!(tileset.minzoom(), 5);
+
+    tileset.maxzoom = Some(12);
+    assert_eq!(tileset.maxzoom(), 8);
+
+    tileset.maxzoom = Some(6);
+    assert_eq!(tileset.maxzoom(), 6);
+}
+
+#[test]
+fn test_volatility() {
+    let mut tileset = Tileset {
+        name: "mixed".to_string(),
+        minzoom: None,
+        maxzoom: None,
+        center: None,
+        start_zoom: None,
+        attribution: None,
+        tags: Vec::new(),
+        extent: None,
+        layers: vec![Layer::new("countries"), Layer::new("roads")],
+        cache_limits: None,
+        #[cfg(feature = "server")]
+        cache: None,
+        access: None,
+        time: None,
+        content_type: None,
+        crawl_protection: None,
+        mbtiles: None,
+        pmtiles: None,
+    };
+    assert_eq!(tileset.volatility(), None);
+    assert_eq!(tileset.cache_max_age(300), 300);
+    assert_eq!(tileset.cache_ttl(), None);
+
+    tileset.layers[0].volatility = Some(Volatility::Static);
+    assert_eq!(tileset.cache_max_age(300), 86400);
+    assert_eq!(tileset.cache_ttl(), None);
+
+    tileset.layers[1].volatility = Some(Volatility::Daily);
+    assert_eq!(tileset.volatility(), Some(Volatility::Daily));
+    assert_eq!(tileset.cache_max_age(300), 3600);
+    assert_eq!(tileset.cache_ttl(), Some(86400));
+    assert!(tileset.is_cachable_at(10));
+
+    tileset.layers[0].volatility = Some(Volatility::Live);
+    assert_eq!(tileset.cache_max_age(300), 0);
+    assert!(!tileset.is_cachable_at(10));
+}
+
+#[test]
+fn test_access_restriction() {
+    let access = AccessRestriction {
+        allowed_referrers: vec![
+            "https://example.com/maps/*".to_string(),
+            "https://*.example.org".to_string(),
+            "http://localhost:8080".to_string(),
+        ],
+        allow_missing: false,
+        token: Some("secret".to_string()),
+    };
+    let referer = |url| Some(Referrer::Referer(url));
+    assert!(access.is_allowed(referer("https://example.com/maps/index.html"), None));
+    assert!(access.is_allowed(referer("https://example.com:443/maps/"), None));
+    assert!(!access.is_allowed(referer("https://example.com/other.html"), None));
+    // Patterns without path allow any page
+    assert!(access.is_allowed(referer("https://maps.example.org/"), None));
+    assert!(access.is_allowed(referer("https://a.b.example.org/map.html?x=1#8/46/7"), None));
+    assert!(access.is_allowed(referer("https://MAPS.Example.org/viewer/"), None));
+    assert!(access.is_allowed(referer("http://localhost:8080/index.html"), None));
+    assert!(!access.is_allowed(referer("http://localhost:8081/index.html"), None));
+    assert!(!access.is_allowed(referer("http://localhost/index.html"), None));
+    // Wildcards only match subdomains
+    assert!(!access.is_allowed(referer("https://example.org/"), None));
+    assert!(!access.is_allowed(referer("https://evilexample.org/"), None));
+    assert!(!access.is_allowed(referer("https://evil.com/x.example.org"), None));
+    assert!(!access.is_allowed(referer("https://evil.com/.example.org"), None));
+    assert!(!access.is_allowed(referer("https://evil.com?.example.org"), None));
+    assert!(!access.is_allowed(referer("https://maps.example.org.evil.net/"), None));
+    assert!(!access.is_allowed(referer("https://maps.example.org:8443/"), None));
+    assert!(!access.is_allowed(referer("http://maps.example.org/"), None));
+    assert!(!access.is_allowed(referer("https://example.com.evil.net/maps/"), None));
+    assert!(!access.is_allowed(referer("https://user@evil.com/maps/"), None));
+    assert!(!access.is_allowed(referer("not a url"), None));
+    assert!(!access.is_allowed(None, None));
+    assert!(access.is_allowed(None, Some("secret")));
+    assert!(!access.is_allowed(referer("https://other.net/"), Some("wrong")));
+    // Cross-origin requests
+    let origin = |url| Some(Referrer::Origin(url));
+    assert!(access.is_allowed(origin("https://example.com"), None));
+    assert!(access.is_allowed(origin("https://maps.example.org"), None));
+    assert!(access.is_allowed(origin("http://localhost:8080"), None));
+    assert!(!access.is_allowed(origin("https://example.com.evil.net"), None));
+    assert!(!access.is_allowed(origin("https://evil.com"), None));
+    assert!(!access.is_allowed(origin("http://example.com"), None));
+    assert!(!access.is_allowed(origin("null"), None));
+
+    let any = AccessRestriction {
+        allowed_referrers: vec!["*".to_string()],
+        allow_missing: false,
+        token: None,
+    };
+    assert!(any.is_allowed(referer("https://other.net/"), None));
+
+    assert_eq!(
+        ReferrerPattern::parse("https://[::1]:8443/map/*"),
+        Ok(ReferrerPattern {
+            scheme: "https",
+            host: "[::1]",
+            port: Some(8443),
+            path: "/map/*",
+        })
+    );
+    assert!(ReferrerPattern::parse("*.example.org").is_err());
+    assert!(ReferrerPattern::parse("https://*").is_err());
+    assert!(ReferrerPattern::parse("https://maps.*.org").is_err());
+    assert!(ReferrerPattern::parse("https://example.org:*").is_err());
+    let cfg = TilesetAccessCfg {
+        allowed_referrers: vec!["example.org".to_string()],
+        allow_missing: false,
+        token: None,
+    };
+    assert!(AccessRestriction::from_config(&cfg).is_err());
+
+    assert!(wildcard_match("*", "anything"));
+    assert!(wildcard_match("a*b*c", "a--b--c"));
+    assert!(!wildcard_match("a*b*c", "a--c--b"));
+    assert!(!wildcard_match("ab*ba", "aba"));
+}
+
+#[test]
+fn test_time_dimension() {
+    let time = TimeDimension {
+        values: vec!["latest".to_string()],
+        range: Some(("2021-01-01".to_string(), "2021-12-31".to_string())),
+        default: None,
+    };
+    assert!(time.is_valid("latest"));
+    assert!(time.is_valid("2021-01-01"));
+    assert!(time.is_valid("2021-06-15"));
+    assert!(time.is_valid("2021-12-31"));
+    assert!(!time.is_valid("2022-01-01"));
+    assert!(!time.is_valid("2021-6-15"));
+    assert!(!time.is_valid("2021-06-1'"));
+    assert!(!time.is_valid("other"));
+
+    let cfg = TimeDimensionCfg {
+        values: vec!["2021".to_string()],
+        range: None,
+        default: Some("2020".to_string()),
+    };
+    assert_eq!(
+        TimeDimension::from_config(&cfg).err(),
+        Some("Invalid default time '2020'".to_string())
+    );
 }
@@ -0,0 +1,295 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! C interface for rendering vector tiles in-process (declarations in `t_rex.h`).
+//!
+//! Functions returning a null pointer or an empty buffer on failure set an error message
+//! retrievable with `t_rex_last_error`.
+
+#[macro_use]
+extern crate log;
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use t_rex_core::core::{read_config, ApplicationCfg, Config};
+use t_rex_service::mvt_service::MvtService;
+
+/// Tile service initialized from a configuration file
+pub struct TRexService(MvtService);
+
+/// Tile data owned by the library. Release with `t_rex_buffer_free`.
+#[repr(C)]
+pub struct TRexBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(msg: String) {
+    error!("{}", msg);
+    let msg = CString::new(msg.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|e| *e.borrow_mut() = None);
+}
+
+unsafe fn str_arg<'a>(arg: *const c_char, name: &str) -> Result<&'a str, String> {
+    if arg.is_null() {
+        return Err(format!("Argument '{}' is null", name));
+    }
+    CStr::from_ptr(arg)
+        .to_str()
+        .map_err(|_| format!("Argument '{}' is not valid UTF-8", name))
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    match panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+    {
+        Some(msg) => format!("Internal error: {}", msg),
+        None => "Internal error".to_string(),
+    }
+}
+
+impl TRexBuffer {
+    fn empty() -> TRexBuffer {
+        TRexBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+    fn from_vec(data: Vec<u8>) -> TRexBuffer {
+        let len = data.len();
+        let data = Box::into_raw(data.into_boxed_slice()) as *mut u8;
+        TRexBuffer { data, len }
+    }
+}
+
+fn service_from_config(path: &str) -> Result<MvtService, String> {
+    let config: ApplicationCfg = read_config(path)?;
+    let mut service = MvtService::from_config(&config)?;
+    service.connect();
+    service.prepare_feature_queries();
+    service.init_cache();
+    Ok(service)
+}
+
+fn render_tile(
+    service: &MvtService,
+    tileset: &str,
+    z: u8,
+    x: u32,
+    y: u32,
+) -> Result<Option<Vec<u8>>, String> {
+    if service.get_tileset(tileset).is_none() {
+        return Err(format!("Tileset '{}' not found", tileset));
+    }
+    if z > service.grid.maxzoom() {
+        return Err(format!("Zoom level {} out of grid range", z));
+    }
+    Ok(service.tile_cached(tileset, x, y, z, false, None))
+}
+
+/// Load configuration file and connect datasources.
+/// Returns null on error.
+///
+/// # Safety
+///
+/// `config_path` must be a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn t_rex_service_new(config_path: *const c_char) -> *mut TRexService {
+    clear_last_error();
+    let result = catch_unwind(|| service_from_config(str_arg(config_path, "config_path")?));
+    match result {
+        Ok(Ok(service)) => Box::into_raw(Box::new(TRexService(service))),
+        Ok(Err(e)) => {
+            set_last_error(e);
+            ptr::null_mut()
+        }
+        Err(panic) => {
+            set_last_error(panic_message(panic));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Render uncompressed MVT tile at z/x/y (XYZ adressing scheme), using the tile cache
+/// if configured. Returns an empty buffer for empty tiles, tiles outside of the
+/// tileset coverage and on error.
+///
+/// # Safety
+///
+/// `service` must be a pointer returned by `t_rex_service_new`, `tileset` a valid
+/// null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn t_rex_render_tile(
+    service: *const TRexService,
+    tileset: *const c_char,
+    z: u8,
+    x: u32,
+    y: u32,
+) -> TRexBuffer {
+    clear_last_error();
+    if service.is_null() {
+        set_last_error("Argument 'service' is null".to_string());
+        return TRexBuffer::empty();
+    }
+    let service = &(*service).0;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        render_tile(service, str_arg(tileset, "tileset")?, z, x, y)
+    }));
+    match result {
+        Ok(Ok(Some(data))) => TRexBuffer::from_vec(data),
+        Ok(Ok(None)) => TRexBuffer::empty(),
+        Ok(Err(e)) => {
+            set_last_error(e);
+            TRexBuffer::empty()
+        }
+        Err(panic) => {
+            set_last_error(panic_message(panic));
+            TRexBuffer::empty()
+        }
+    }
+}
+
+/// Release tile data returned by `t_rex_render_tile`.
+///
+/// # Safety
+///
+/// `buffer` must be returned by `t_rex_render_tile` and not be released before.
+#[no_mangle]
+pub unsafe extern "C" fn t_rex_buffer_free(buffer: TRexBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+/// Release service created with `t_rex_service_new`.
+///
+/// # Safety
+///
+/// `service` must be returned by `t_rex_service_new` and not be released before.
+#[no_mangle]
+pub unsafe extern "C" fn t_rex_service_free(service: *mut TRexService) {
+    if !service.is_null() {
+        drop(Box::from_raw(service));
+    }
+}
+
+/// Error message of the last failed call in the current thread or null.
+/// The string is valid until the next call into the library.
+#[no_mangle]
+pub extern "C" fn t_rex_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| match *e.borrow() {
+        Some(ref msg) => msg.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod test {
+    use super::*;
+    use std::fs;
+
+    const CONFIG: &str = r#"
+[service.mvt]
+viewer = false
+
+[[datasource]]
+name = "natural_earth"
+gpkg = "../data/natural_earth.gpkg"
+
+[grid]
+predefined = "web_mercator"
+
+[[tileset]]
+name = "places"
+
+[[tileset.layer]]
+name = "points"
+datasource = "natural_earth"
+table_name = "ne_10m_populated_places"
+geometry_field = "geom"
+geometry_type = "POINT"
+srid = 3857
+
+[webserver]
+bind = "127.0.0.1"
+port = 6767
+"#;
+
+    fn last_error() -> Option<String> {
+        let msg = t_rex_last_error();
+        if msg.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(msg) }.to_string_lossy().to_string())
+        }
+    }
+
+    #[test]
+    fn test_render_tile() {
+        let path = std::env::temp_dir().join("t_rex_ffi_test.toml");
+        fs::write(&path, CONFIG).unwrap();
+        let path = CString::new(path.to_str().unwrap()).unwrap();
+        let tileset = CString::new("places").unwrap();
+        unsafe {
+            let service = t_rex_service_new(path.as_ptr());
+            assert!(!service.is_null(), "{:?}", last_error());
+
+            let tile = t_rex_render_tile(service, tileset.as_ptr(), 10, 533, 360);
+            assert!(!tile.data.is_null());
+            assert!(tile.len > 0);
+            assert_eq!(last_error(), None);
+            t_rex_buffer_free(tile);
+
+            // Empty tile in the Pacific
+            let tile = t_rex_render_tile(service, tileset.as_ptr(), 10, 0, 512);
+            assert!(tile.data.is_null());
+            assert_eq!(last_error(), None);
+
+            let unknown = CString::new("unknown").unwrap();
+            let tile = t_rex_render_tile(service, unknown.as_ptr(), 10, 533, 360);
+            assert!(tile.data.is_null());
+            assert_eq!(
+                last_error(),
+                Some("Tileset 'unknown' not found".to_string())
+            );
+
+            let tile = t_rex_render_tile(service, ptr::null(), 10, 533, 360);
+            assert!(tile.data.is_null());
+            assert_eq!(last_error(), Some("Argument 'tileset' is null".to_string()));
+
+            t_rex_service_free(service);
+        }
+    }
+
+    #[test]
+    fn test_service_errors() {
+        let path = CString::new("missing.toml").unwrap();
+        unsafe {
+            assert!(t_rex_service_new(path.as_ptr()).is_null());
+            assert_eq!(
+                last_error(),
+                Some("Could not find config file!".to_string())
+            );
+            assert!(t_rex_service_new(ptr::null()).is_null());
+            t_rex_service_free(ptr::null_mut());
+        }
+    }
+}
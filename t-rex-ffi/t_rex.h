@@ -0,0 +1,45 @@
+/*
+ * Copyright (c) Pirmin Kalberer. All rights reserved.
+ * Licensed under the MIT License. See LICENSE file in the project root for full license information.
+ */
+
+/* C interface for rendering vector tiles with t-rex in-process */
+
+#ifndef T_REX_H
+#define T_REX_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct TRexService TRexService;
+
+/* Tile data owned by the library. Release with t_rex_buffer_free. */
+typedef struct {
+    uint8_t *data;
+    size_t len;
+} TRexBuffer;
+
+/* Load configuration file and connect datasources. Returns NULL on error. */
+TRexService *t_rex_service_new(const char *config_path);
+
+/* Render uncompressed MVT tile at z/x/y (XYZ adressing scheme).
+ * Returns an empty buffer (data == NULL) for empty tiles and on error. */
+TRexBuffer t_rex_render_tile(const TRexService *service, const char *tileset,
+                             uint8_t z, uint32_t x, uint32_t y);
+
+void t_rex_buffer_free(TRexBuffer buffer);
+
+void t_rex_service_free(TRexService *service);
+
+/* Error message of the last failed call in the current thread or NULL */
+const char *t_rex_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
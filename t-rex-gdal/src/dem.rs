@@ -0,0 +1,66 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use gdal::Dataset;
+use std::path::Path;
+use tile_grid::Extent;
+
+/// Read elevation values of the first band within extent, resampled to width x height.
+/// The elevation model has to be in the SRS of the extent. Areas without data are set to 0.
+pub fn read_dem(
+    path: &str,
+    extent: &Extent,
+    width: usize,
+    height: usize,
+) -> Result<Vec<f32>, String> {
+    let dataset = Dataset::open(Path::new(path)).map_err(|e| e.to_string())?;
+    let gt = dataset.geo_transform().map_err(|e| e.to_string())?;
+    if gt[2] != 0.0 || gt[4] != 0.0 {
+        return Err(format!(
+            "{}: rotated elevation models are not supported",
+            path
+        ));
+    }
+    let (size_x, size_y) = dataset.raster_size();
+    let band = dataset.rasterband(1).map_err(|e| e.to_string())?;
+    let nodata = band.no_data_value();
+
+    let mut dem = vec![0.0f32; width * height];
+    // Requested extent in pixel coordinates of the elevation model
+    let (col0, col1) = ((extent.minx - gt[0]) / gt[1], (extent.maxx - gt[0]) / gt[1]);
+    let (row0, row1) = ((extent.maxy - gt[3]) / gt[5], (extent.miny - gt[3]) / gt[5]);
+    let (win_col0, win_col1) = (col0.max(0.0).floor(), col1.min(size_x as f64).ceil());
+    let (win_row0, win_row1) = (row0.max(0.0).floor(), row1.min(size_y as f64).ceil());
+    if win_col1 <= win_col0 || win_row1 <= win_row0 {
+        return Ok(dem);
+    }
+    // Part of the output covered by the dataset
+    let out_x = |col: f64| ((col - col0) / (col1 - col0) * width as f64).round() as usize;
+    let out_y = |row: f64| ((row - row0) / (row1 - row0) * height as f64).round() as usize;
+    let (out_x0, out_x1) = (out_x(win_col0), out_x(win_col1).min(width));
+    let (out_y0, out_y1) = (out_y(win_row0), out_y(win_row1).min(height));
+    if out_x1 <= out_x0 || out_y1 <= out_y0 {
+        return Ok(dem);
+    }
+    let buffer = band
+        .read_as::<f32>(
+            (win_col0 as isize, win_row0 as isize),
+            (
+                (win_col1 - win_col0) as usize,
+                (win_row1 - win_row0) as usize,
+            ),
+            (out_x1 - out_x0, out_y1 - out_y0),
+            None,
+        )
+        .map_err(|e| e.to_string())?;
+    for (row, line) in buffer.data.chunks(out_x1 - out_x0).enumerate() {
+        for (col, z) in line.iter().enumerate() {
+            if nodata != Some(*z as f64) {
+                dem[(out_y0 + row) * width + out_x0 + col] = *z;
+            }
+        }
+    }
+    Ok(dem)
+}
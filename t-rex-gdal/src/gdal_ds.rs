@@ -8,6 +8,7 @@ use gdal::spatial_ref::{CoordTransform, SpatialRef};
 use gdal::vector::Geometry;
 use gdal::Dataset;
 use std::collections::BTreeMap;
+use std::ffi::CString;
 use std::path::Path;
 use t_rex_core::core::config::DatasourceCfg;
 use t_rex_core::core::feature::Feature;
@@ -105,7 +106,7 @@ impl DatasourceType for GdalDatasource {
             Ok(sref) => sref,
         };
 
-        let layer_sref = geom_spatialref(&ogr_layer, layer.geometry_field.as_ref());
+        let layer_sref = layer_spatialref(&ogr_layer, layer);
         let src_sref = match layer_sref {
             Some(ref sref) if !layer.no_transform => sref,
             _ => &grid_sref,
@@ -119,6 +120,14 @@ impl DatasourceType for GdalDatasource {
             Ok(sref) => sref,
         };
 
+        let extent = extent.map(|extent| {
+            if layer.swap_axes() {
+                swap_extent_axes(&extent)
+            } else {
+                extent
+            }
+        });
+
         match extent {
             Some(extent) => match transform_extent_sref(&extent, src_sref, &wgs84_sref) {
                 Ok(extent) => Some(extent),
@@ -158,7 +167,7 @@ impl DatasourceType for GdalDatasource {
             Ok(sref) => sref,
         };
         if !layer.no_transform {
-            let layer_sref = geom_spatialref(&ogr_layer, layer.geometry_field.as_ref());
+            let layer_sref = layer_spatialref(&ogr_layer, layer);
             if let Some(ref sref) = layer_sref {
                 info!(
                     "Layer '{}': Reprojecting geometry to SRID {}",
@@ -197,6 +206,9 @@ impl DatasourceType for GdalDatasource {
             }
         }
     }
+    fn supports_filter(&self) -> bool {
+        true
+    }
     fn retrieve_features<F>(
         &self,
         _tileset: &str,
@@ -243,6 +255,9 @@ impl DatasourceType for GdalDatasource {
             }
             transformation = CoordTransform::new(&layer_sref, &grid_sref).ok();
         }
+        if layer.swap_axes() {
+            bbox_extent = swap_extent_axes(&bbox_extent);
+        }
         let bbox = Geometry::bbox(
             bbox_extent.minx,
             bbox_extent.miny,
@@ -251,6 +266,15 @@ impl DatasourceType for GdalDatasource {
         )
         .unwrap();
         ogr_layer.set_spatial_filter(&bbox);
+        if let Some(ref filter) = layer.filter {
+            let filter = CString::new(filter.as_str()).unwrap();
+            let rv =
+                unsafe { gdal_sys::OGR_L_SetAttributeFilter(ogr_layer.c_layer(), filter.as_ptr()) };
+            if rv != gdal_sys::OGRErr::OGRERR_NONE {
+                error!("Layer '{}': invalid filter (OGR error {})", layer.name, rv);
+                return 0;
+            }
+        }
 
         let ogr_layer_for_defn = dataset.layer_by_name(layer_name).unwrap();
         let fields_defn = ogr_layer_for_defn.defn().fields().collect::<Vec<_>>();
@@ -278,6 +302,26 @@ impl DatasourceType for GdalDatasource {
     }
 }
 
+/// Spatial reference of layer geometries, configured with `force_srid` or reported by GDAL
+fn layer_spatialref(ogr_layer: &gdal::vector::Layer, layer: &Layer) -> Option<SpatialRef> {
+    match layer.srid {
+        Some(srid) if layer.force_srid => sref(srid as u32)
+            .map_err(|e| error!("Layer '{}': Invalid srid {}: {:?}", layer.name, srid, e))
+            .ok(),
+        _ => geom_spatialref(ogr_layer, layer.geometry_field.as_ref()),
+    }
+}
+
+/// Extent with x and y axis swapped
+fn swap_extent_axes(extent: &Extent) -> Extent {
+    Extent {
+        minx: extent.miny,
+        miny: extent.minx,
+        maxx: extent.maxy,
+        maxy: extent.maxx,
+    }
+}
+
 /// Projected extent
 fn transform_extent(
     extent: &Extent,
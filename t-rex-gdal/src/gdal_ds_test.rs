@@ -49,18 +49,20 @@ fn test_detect_layers() {
     let layers = ds.detect_layers(true);
     println!("{:?}", layers);
     assert_eq!(layers.len(), 3);
-    assert_eq!(
-        format!("{:?}", layers[0]),
-        r#"Layer { name: "ne_10m_populated_places", datasource: None, geometry_field: Some("geom"), geometry_type: Some("POINT"), srid: Some(3857), no_transform: false, fid_field: None, table_name: Some("ne_10m_populated_places"), query_limit: None, query: [], minzoom: None, maxzoom: None, tile_size: 4096, simplify: false, tolerance: "", buffer_size: None, make_valid: false, shift_longitude: false, style: None }"#
-    );
-    assert_eq!(
-        format!("{:?}", layers[1]),
-        r#"Layer { name: "ne_10m_rivers_lake_centerlines", datasource: None, geometry_field: Some("geom"), geometry_type: Some("LINE"), srid: Some(3857), no_transform: false, fid_field: None, table_name: Some("ne_10m_rivers_lake_centerlines"), query_limit: None, query: [], minzoom: None, maxzoom: None, tile_size: 4096, simplify: false, tolerance: "", buffer_size: None, make_valid: false, shift_longitude: false, style: None }"#
-    );
-    assert_eq!(
-        format!("{:?}", layers[2]),
-        r#"Layer { name: "ne_110m_admin_0_countries", datasource: None, geometry_field: Some("geom"), geometry_type: Some("POLYGON"), srid: Some(3857), no_transform: false, fid_field: None, table_name: Some("ne_110m_admin_0_countries"), query_limit: None, query: [], minzoom: None, maxzoom: None, tile_size: 4096, simplify: false, tolerance: "", buffer_size: None, make_valid: false, shift_longitude: false, style: None }"#
-    );
+    let expected = [
+        ("ne_10m_populated_places", "POINT"),
+        ("ne_10m_rivers_lake_centerlines", "LINE"),
+        ("ne_110m_admin_0_countries", "POLYGON"),
+    ];
+    for (layer, (name, geometry_type)) in layers.iter().zip(expected.iter()) {
+        assert_eq!(layer.name, *name);
+        assert_eq!(layer.table_name, Some(name.to_string()));
+        assert_eq!(layer.geometry_field, Some("geom".to_string()));
+        assert_eq!(layer.geometry_type, Some(geometry_type.to_string()));
+        assert_eq!(layer.srid, Some(3857));
+        assert_eq!(layer.fid_field, None);
+        assert!(layer.query.is_empty());
+    }
 }
 
 #[test]
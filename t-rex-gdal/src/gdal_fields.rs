@@ -8,7 +8,7 @@ use gdal::vector::{FieldValue, Geometry};
 use gdal::Dataset;
 use gdal_sys;
 use std::path::Path;
-use t_rex_core::core::feature::{Feature, FeatureAttr, FeatureAttrValType};
+use t_rex_core::core::feature::{fid_from_values, Feature, FeatureAttr, FeatureAttrValType};
 use t_rex_core::core::geom::{self, GeometryType};
 use t_rex_core::core::layer::Layer;
 
@@ -362,13 +362,19 @@ pub(crate) struct VectorFeature<'a> {
 
 impl<'a> Feature for VectorFeature<'a> {
     fn fid(&self) -> Option<u64> {
-        self.layer.fid_field.as_ref().and_then(|fid| {
-            let field_value = self.feature.field(&fid);
-            match field_value {
-                Ok(Some(FieldValue::IntegerValue(v))) => Some(v as u64),
+        let values: Vec<Option<FeatureAttrValType>> = self
+            .layer
+            .fid_fields()
+            .iter()
+            .map(|field| match self.feature.field(field) {
+                Ok(Some(FieldValue::IntegerValue(v))) => Some(FeatureAttrValType::Int(v as i64)),
+                Ok(Some(FieldValue::Integer64Value(v))) => Some(FeatureAttrValType::Int(v)),
+                Ok(Some(FieldValue::RealValue(v))) => Some(FeatureAttrValType::Double(v)),
+                Ok(Some(FieldValue::StringValue(v))) => Some(FeatureAttrValType::String(v)),
                 _ => None,
-            }
-        })
+            })
+            .collect();
+        fid_from_values(&values)
     }
     fn attributes(&self) -> Vec<FeatureAttr> {
         let mut attrs = Vec::new();
@@ -428,6 +434,9 @@ impl<'a> Feature for VectorFeature<'a> {
             self.feature.geometry()
         };
         let mut ogrgeom = ogrgeom.clone();
+        if self.layer.swap_axes() {
+            unsafe { gdal_sys::OGR_G_SwapXY(ogrgeom.c_geometry()) };
+        }
         if let Some(ref transform) = self.transform {
             ogrgeom.transform_inplace(transform).unwrap();
         };
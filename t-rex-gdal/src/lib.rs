@@ -1,11 +1,13 @@
 #[macro_use]
 extern crate log;
 
+mod dem;
 mod gdal_ds;
 #[cfg(test)]
 mod gdal_ds_test;
 mod gdal_fields;
 
+pub use self::dem::read_dem;
 pub use self::gdal_ds::GdalDatasource;
 pub use self::gdal_fields::ogr_layer_name;
 
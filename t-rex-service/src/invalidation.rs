@@ -0,0 +1,237 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Invalidation of cached tiles after data changes, e.g. announced by PostgreSQL notifications
+
+use crate::datasources::Datasource;
+use crate::mvt_service::{remove_cached_tile, tile_cache_path, MvtService};
+use std::cmp;
+use t_rex_core::cache::{Cache, Tilecache};
+use t_rex_core::core::config::CacheNotifyCfg;
+use t_rex_core::datasource::DatasourceType;
+use t_rex_core::service::tileset::{Tileset, WORLD_EXTENT};
+use tile_grid::{extent_wgs84_to_merc, Extent};
+
+/// Default maximal number of tiles removed per invalidation request
+const DEFAULT_MAX_TILES: u64 = 100_000;
+
+/// Tiles to remove from the cache. Undefined properties match all tiles.
+#[derive(Deserialize, Default, PartialEq, Debug)]
+pub struct Invalidation {
+    pub tileset: Option<String>,
+    /// Layer with changed data, invalidating all tilesets containing it
+    pub layer: Option<String>,
+    /// Changed extent (minx, miny, maxx, maxy)
+    pub bbox: Option<[f64; 4]>,
+    /// SRID of bbox (default 4326)
+    pub srid: Option<i32>,
+    pub minzoom: Option<u8>,
+    pub maxzoom: Option<u8>,
+}
+
+impl Invalidation {
+    /// Invalidation request from a JSON object or a plain layer name
+    pub fn parse(payload: &str) -> Result<Invalidation, String> {
+        let payload = payload.trim();
+        if payload.starts_with('{') {
+            serde_json::from_str(payload).map_err(|e| e.to_string())
+        } else if payload.is_empty() {
+            Err("empty payload".to_string())
+        } else {
+            Ok(Invalidation {
+                layer: Some(payload.to_string()),
+                ..Default::default()
+            })
+        }
+    }
+}
+
+impl MvtService {
+    /// Extent of invalidation request in grid SRS
+    fn invalidation_extent(&self, request: &Invalidation, ts: &Tileset) -> Result<Extent, String> {
+        let (extent, srid) = match request.bbox {
+            Some([minx, miny, maxx, maxy]) => (
+                Extent {
+                    minx,
+                    miny,
+                    maxx,
+                    maxy,
+                },
+                request.srid.unwrap_or(4326),
+            ),
+            None => match ts.extent {
+                Some(ref extent) if *extent != WORLD_EXTENT => (extent.clone(), 4326),
+                _ => return Ok(self.grid.tile_extent(0, 0, 0)),
+            },
+        };
+        if srid == self.grid.srid {
+            Ok(extent)
+        } else if srid == 4326 && self.grid.srid == 3857 {
+            Ok(extent_wgs84_to_merc(&extent))
+        } else {
+            self.datasources
+                .default()
+                .and_then(|ds| ds.reproject_extent(&extent, self.grid.srid, Some(srid)))
+                .ok_or_else(|| {
+                    format!("Error transforming {:?} to SRID {}", extent, self.grid.srid)
+                })
+        }
+    }
+    /// Cache paths of tiles matching invalidation request, by tileset
+    pub fn invalidated_tiles(
+        &self,
+        request: &Invalidation,
+        max_tiles: u64,
+    ) -> Result<Vec<(&Tileset, Vec<String>)>, String> {
+        if let Some(ref name) = request.tileset {
+            if self.get_tileset(name).is_none() {
+                return Err(format!("Tileset '{}' not found", name));
+            }
+        }
+        let mut invalidated = Vec::new();
+        let mut count = 0;
+        for ts in &self.tilesets {
+            if request
+                .tileset
+                .as_ref()
+                .is_some_and(|name| *name != ts.name)
+                || self.archives.contains_key(&ts.name)
+                || matches!(self.tileset_cache(ts), Tilecache::Nocache(_))
+            {
+                continue;
+            }
+            // Zoom range of changed layer or all layers
+            let layers: Vec<_> = ts
+                .layers
+                .iter()
+                .filter(|l| request.layer.as_ref().is_none_or(|name| *name == l.name))
+                .collect();
+            if layers.is_empty() {
+                continue;
+            }
+            let layer_minzoom = layers.iter().map(|l| l.minzoom()).min().unwrap_or(0);
+            let layer_maxzoom = layers
+                .iter()
+                .map(|l| l.maxzoom(self.grid.maxzoom()))
+                .max()
+                .unwrap_or(0);
+            let minzoom = *[ts.minzoom(), layer_minzoom, request.minzoom.unwrap_or(0)]
+                .iter()
+                .max()
+                .unwrap();
+            let maxzoom = *[
+                ts.maxzoom(),
+                layer_maxzoom,
+                request.maxzoom.unwrap_or(99),
+                self.grid.maxzoom(),
+            ]
+            .iter()
+            .min()
+            .unwrap();
+            let limits = self
+                .grid
+                .tile_limits(self.invalidation_extent(request, ts)?, 0);
+            let time = self.tile_time(ts, None);
+            let mut paths = Vec::new();
+            for zoom in minzoom..=maxzoom {
+                let limit = &limits[zoom as usize];
+                // Extent of points and axis-parallel lines may have no area
+                let maxx = cmp::max(limit.maxx, limit.minx + 1);
+                let maxy = cmp::max(limit.maxy, limit.miny + 1);
+                count += (maxx - limit.minx) as u64 * (maxy - limit.miny) as u64;
+                if count > max_tiles {
+                    return Err(format!(
+                        "More than {} tiles to invalidate - skipping",
+                        max_tiles
+                    ));
+                }
+                for x in limit.minx..maxx {
+                    for y in limit.miny..maxy {
+                        // Mercator tiles are stored in xyz scheme
+                        let y = if self.grid.srid == 3857 {
+                            self.grid.ytile_from_xyz(y, zoom)
+                        } else {
+                            y
+                        };
+                        paths.push(tile_cache_path(&ts.name, time.as_deref(), zoom, x, y));
+                    }
+                }
+            }
+            invalidated.push((ts, paths));
+        }
+        Ok(invalidated)
+    }
+    /// Remove cached tiles matching invalidation request. Returns the number of removed tiles.
+    pub fn invalidate_cache(&self, request: &Invalidation, max_tiles: u64) -> Result<u64, String> {
+        let mut removed = 0;
+        for (ts, paths) in self.invalidated_tiles(request, max_tiles)? {
+            let cache = self.tileset_cache(ts);
+            for path in paths {
+                if !cache.exists(&path) {
+                    continue;
+                }
+                remove_cached_tile(cache, &path)
+                    .map_err(|e| format!("Error removing {}: {}", path, e))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+    /// Invalidate cached tiles on notifications of a PostGIS datasource
+    pub fn start_cache_invalidation(&self, config: &CacheNotifyCfg) -> Result<(), String> {
+        let ds = match config.datasource {
+            Some(_) => self.datasources.datasource(&config.datasource),
+            None => self.datasources.default(),
+        };
+        let pg = match ds {
+            Some(Datasource::Postgis(pg)) => pg,
+            _ => return Err("Cache notifications require a PostGIS datasource".to_string()),
+        };
+        let max_tiles = config.max_tiles.unwrap_or(DEFAULT_MAX_TILES);
+        let service = self.clone();
+        info!(
+            "Invalidating cached tiles on notifications of channel '{}'",
+            config.channel
+        );
+        pg.listen(&config.channel, move |payload| {
+            let request = match Invalidation::parse(payload) {
+                Ok(request) => request,
+                Err(e) => {
+                    warn!("Invalid cache notification '{}': {}", payload, e);
+                    return;
+                }
+            };
+            match service.invalidate_cache(&request, max_tiles) {
+                Ok(removed) => info!(
+                    "Cache notification '{}': {} tiles removed",
+                    payload, removed
+                ),
+                Err(e) => warn!("Cache notification '{}': {}", payload, e),
+            }
+        })
+    }
+}
+
+#[test]
+fn test_parse_invalidation() {
+    assert_eq!(
+        Invalidation::parse("roads"),
+        Ok(Invalidation {
+            layer: Some("roads".to_string()),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        Invalidation::parse(r#"{"layer": "roads", "bbox": [7.4, 46.9, 7.5, 47.0], "minzoom": 10}"#),
+        Ok(Invalidation {
+            layer: Some("roads".to_string()),
+            bbox: Some([7.4, 46.9, 7.5, 47.0]),
+            minzoom: Some(10),
+            ..Default::default()
+        })
+    );
+    assert!(Invalidation::parse(" ").is_err());
+    assert!(Invalidation::parse(r#"{"bbox": [7.4]}"#).is_err());
+}
@@ -14,9 +14,21 @@ extern crate serde_json;
 extern crate t_rex_gdal;
 
 pub mod datasources;
+pub mod invalidation;
 pub mod metadata;
 pub mod mvt_service;
 #[cfg(test)]
 mod mvt_service_test;
+pub mod openapi;
+pub mod pmtiles;
+pub mod prerender;
+pub mod preview;
 mod qgs_reader;
+pub mod raster_service;
+pub mod record;
+pub mod sample;
+pub mod seed_coordination;
+pub mod tile_archive;
+pub mod tile_batch;
+pub mod tune;
 pub use qgs_reader::read_qgs;
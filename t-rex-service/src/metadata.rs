@@ -6,7 +6,9 @@
 use crate::mvt_service::MvtService;
 use serde_json;
 use std::cmp;
+use t_rex_core::core::config::ViewerCfg;
 use t_rex_core::datasource::DatasourceType;
+use tile_grid::{Origin, Unit};
 
 type JsonResult = Result<serde_json::Value, serde_json::error::Error>;
 
@@ -23,6 +25,8 @@ impl MvtService {
             tilejson: String,
             tileurl: String,
             bounds: [f64; 4],
+            minzoom: u8,
+            maxzoom: u8,
             layers: Vec<LayerInfo>,
             supported: bool,
         }
@@ -44,16 +48,19 @@ impl MvtService {
                         geometry_type: l.geometry_type.clone(),
                     })
                     .collect();
-                let supported = set.layers.iter().any(|l| {
-                    let geom_type = l.geometry_type.clone().unwrap_or("UNKNOWN".to_string());
-                    ["POINT", "LINESTRING", "POLYGON"].contains(&(&geom_type as &str))
-                });
+                let supported = self.archives.contains_key(&set.name)
+                    || set.layers.iter().any(|l| {
+                        let geom_type = l.geometry_type.clone().unwrap_or("UNKNOWN".to_string());
+                        ["POINT", "LINESTRING", "POLYGON"].contains(&(&geom_type as &str))
+                    });
                 let ext = set.get_extent();
                 TilesetInfo {
                     name: set.name.clone(),
                     tilejson: format!("{}.json", set.name),
                     tileurl: format!("/{}/{{z}}/{{x}}/{{y}}.pbf", set.name),
                     bounds: [ext.minx, ext.miny, ext.maxx, ext.maxy],
+                    minzoom: set.minzoom(),
+                    maxzoom: set.maxzoom(),
                     layers: layerinfos,
                     supported: supported,
                 }
@@ -65,6 +72,100 @@ impl MvtService {
         };
         serde_json::to_value(mvt_info)
     }
+    /// Tileset catalog for service discovery, optionally filtered by tag.
+    /// `tile_paths` are additional tile URL templates with a `{tileset}` placeholder.
+    pub fn get_catalog(
+        &self,
+        baseurl: &str,
+        tag: Option<&str>,
+        tile_paths: &[String],
+    ) -> JsonResult {
+        let scheme = if self.grid.srid == 3857 { "xyz" } else { "tms" };
+        let mut tilesets: Vec<_> = self
+            .tilesets
+            .iter()
+            .filter(|set| match tag {
+                Some(tag) => set.has_tag(tag),
+                None => true,
+            })
+            .collect();
+        tilesets.sort_by_key(|set| set.name.clone());
+        let entries: Vec<serde_json::Value> = tilesets
+            .iter()
+            .map(|set| {
+                let ext = set.get_extent();
+                let layers: Vec<serde_json::Value> = set
+                    .layers
+                    .iter()
+                    .map(|l| {
+                        json!({
+                            "name": l.name,
+                            "geometry_type": l.geometry_type,
+                            "tags": l.tags,
+                        })
+                    })
+                    .collect();
+                let tileset_url = format!("{}/{}", baseurl, set.name);
+                let mut templates: Vec<serde_json::Value> = ["pbf", "mvt"]
+                    .iter()
+                    .map(|format| {
+                        json!({
+                            "url": format!("{}/{{z}}/{{x}}/{{y}}.{}", tileset_url, format),
+                            "format": format,
+                        })
+                    })
+                    .collect();
+                if let Some(ref time) = set.time {
+                    templates.push(json!({
+                        "url": format!("{}/{{time}}/{{z}}/{{x}}/{{y}}.pbf", tileset_url),
+                        "format": "pbf",
+                        "time": {
+                            "values": time.values,
+                            "range": time.range.as_ref().map(|(min, max)| [min, max]),
+                            "default": time.default,
+                        },
+                    }));
+                }
+                for path in tile_paths {
+                    let path = path.replace("{tileset}", &set.name);
+                    if set.time.is_none() && path.contains("{time}") {
+                        continue;
+                    }
+                    let format = path.rsplit('.').next().filter(|ext| !ext.contains('}'));
+                    templates.push(json!({
+                        "url": format!("{}{}", baseurl, path),
+                        "format": format.unwrap_or("pbf"),
+                    }));
+                }
+                json!({
+                    "name": set.name,
+                    "tags": set.tags,
+                    "tilejson": format!("{}.json", tileset_url),
+                    "tiles": [format!("{}/{{z}}/{{x}}/{{y}}.pbf", tileset_url)],
+                    "tile_templates": templates,
+                    "scheme": scheme,
+                    "bounds": [ext.minx, ext.miny, ext.maxx, ext.maxy],
+                    "minzoom": set.minzoom(),
+                    "maxzoom": set.maxzoom(),
+                    "attribution": set.attribution(),
+                    "layers": layers,
+                    "links": [
+                        {"rel": "tilejson", "type": "application/json", "href": format!("{}.json", tileset_url)},
+                        {"rel": "style", "type": "application/json", "href": format!("{}.style.json", tileset_url)},
+                        {"rel": "metadata", "type": "application/json", "href": format!("{}/metadata.json", tileset_url)},
+                    ],
+                })
+            })
+            .collect();
+        Ok(json!({
+            "tilesets": entries,
+            "links": [
+                {"rel": "self", "type": "application/json", "href": format!("{}/catalog", baseurl)},
+                {"rel": "service-desc", "type": "application/vnd.oai.openapi+json;version=3.0", "href": format!("{}/openapi.json", baseurl)},
+                {"rel": "grid", "type": "application/json", "href": format!("{}/grids/{}.json", baseurl, self.grid_name)},
+            ],
+        }))
+    }
     fn get_tilejson_metadata(&self, tileset: &str) -> JsonResult {
         let ts = self
             .get_tileset(tileset)
@@ -136,6 +237,9 @@ impl MvtService {
         let ts = self
             .get_tileset(tileset)
             .expect(&format!("Tileset '{}' not found", tileset));
+        if let Some(archive) = self.archives.get(&ts.name) {
+            return Ok(archive.vector_layers().unwrap_or(json!([])));
+        }
         let layers = self.get_tileset_layers(tileset);
         let vector_layers: Vec<serde_json::Value> = layers
             .iter()
@@ -156,6 +260,9 @@ impl MvtService {
                 if let Some(srid) = layer.srid {
                     layer_json["projection"] = json!(format!("EPSG:{}", srid));
                 }
+                if let Some(updated) = self.layer_updated(&ts.name, layer) {
+                    layer_json["updated"] = json!(updated);
+                }
                 //insert fields
                 let fields = self.ds(&layer).unwrap().detect_data_columns(&layer, query);
                 for (ref field, _) in fields {
@@ -177,6 +284,9 @@ impl MvtService {
         let obj = metadata.as_object_mut().unwrap();
         obj.insert("tiles".to_string(), url);
         obj.insert("vector_layers".to_string(), vector_layers);
+        if let Some(updated) = self.tileset_updated(tileset) {
+            obj.insert("updated".to_string(), json!(updated));
+        }
         Ok(json!(obj))
     }
     /// MapboxGL Style JSON (https://www.mapbox.com/mapbox-gl-style-spec/)
@@ -261,6 +371,68 @@ impl MvtService {
         obj.insert("layers".to_string(), json!(layer_styles));
         Ok(json!(obj))
     }
+    /// Style JSON of the built-in viewer with background map and overlay tilesets.
+    /// A background style URL is passed in the metadata and merged by the viewer.
+    pub fn get_viewer_stylejson(
+        &self,
+        baseurl: &str,
+        tileset: &str,
+        viewer: &ViewerCfg,
+    ) -> JsonResult {
+        let mut stylejson = self.get_stylejson(baseurl, tileset)?;
+        let mut layers = stylejson["layers"].as_array().cloned().unwrap_or_default();
+        if let Some(ref url) = viewer.background_tiles {
+            let mut source = json!({
+                "type": "raster",
+                "tiles": [url],
+                "tileSize": 256
+            });
+            if let Some(ref attribution) = viewer.background_attribution {
+                source["attribution"] = json!(attribution);
+            }
+            stylejson["sources"]["background_"] = source;
+            layers.insert(
+                1,
+                json!({
+                    "id": "background_tiles_",
+                    "type": "raster",
+                    "source": "background_"
+                }),
+            );
+        }
+        if let Some(ref url) = viewer.background_style {
+            stylejson["metadata"]["t-rex:background_style"] = json!(url);
+        }
+        for overlay in &viewer.overlays {
+            if overlay == tileset {
+                continue;
+            }
+            if self.get_tileset(overlay).is_none() {
+                warn!("Viewer overlay '{}': unknown tileset", overlay);
+                continue;
+            }
+            let overlay_style = self.get_stylejson(baseurl, overlay)?;
+            stylejson["sources"][overlay] = overlay_style["sources"][overlay].clone();
+            let overlay_layers = overlay_style["layers"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            for mut layer in overlay_layers {
+                if layer["type"] == "background" {
+                    continue;
+                }
+                // Layer ids must be unique within style
+                layer["id"] = json!(format!(
+                    "{}.{}",
+                    overlay,
+                    layer["id"].as_str().unwrap_or("")
+                ));
+                layers.push(layer);
+            }
+        }
+        stylejson["layers"] = json!(layers);
+        Ok(stylejson)
+    }
 
     /// MBTiles metadata.json (https://github.com/mapbox/mbtiles-spec/blob/master/1.3/spec.md)
     pub fn get_mbtiles_metadata(&self, tileset: &str) -> JsonResult {
@@ -280,11 +452,178 @@ impl MvtService {
         );
         Ok(json!(obj))
     }
+
+    /// Layer introspection with resolved queries and live statistics
+    pub fn get_layer_metadata(&self, tileset: &str, layer_name: &str) -> Option<serde_json::Value> {
+        let ts = self.get_tileset(tileset)?;
+        let layer = ts.layers.iter().find(|l| l.name == layer_name)?;
+        let ds = self.ds(layer)?;
+        let minzoom = cmp::max(ts.minzoom(), layer.minzoom());
+        let maxzoom = cmp::min(ts.maxzoom(), layer.maxzoom(self.grid.maxzoom()));
+        // Zoom ranges with identical queries
+        let mut queries: Vec<serde_json::Value> = Vec::new();
+        let mut current: Option<(u8, u8, String)> = None;
+        for zoom in minzoom..=maxzoom {
+            let sql = ds.query_sql(&ts.name, layer, zoom);
+            match (current.as_mut(), sql) {
+                (Some((_, max, cur_sql)), Some(sql)) if *cur_sql == sql => *max = zoom,
+                (_, sql) => {
+                    if let Some((min, max, cur_sql)) = current.take() {
+                        queries.push(json!({"minzoom": min, "maxzoom": max, "sql": cur_sql}));
+                    }
+                    current = sql.map(|sql| (zoom, zoom, sql));
+                }
+            }
+        }
+        if let Some((min, max, cur_sql)) = current {
+            queries.push(json!({"minzoom": min, "maxzoom": max, "sql": cur_sql}));
+        }
+        let extent = ds
+            .layer_extent(layer, self.grid.srid)
+            .map(|ext| json!([ext.minx, ext.miny, ext.maxx, ext.maxy]));
+        Some(json!({
+            "name": layer.name,
+            "tileset": ts.name,
+            "datasource": layer.datasource,
+            "geometry_type": layer.geometry_type,
+            "srid": layer.srid,
+            "extent": extent,
+            "minzoom": minzoom,
+            "maxzoom": maxzoom,
+            "row_count": ds.estimated_row_count(layer),
+            "queries": queries
+        }))
+    }
+
+    /// Description of the tile grid for aligning clients with the tiling scheme
+    pub fn get_grid_metadata(&self, name: &str) -> Option<serde_json::Value> {
+        if name != self.grid_name {
+            return None;
+        }
+        let grid = &self.grid;
+        let units = match grid.units {
+            Unit::Meters => "m",
+            Unit::Degrees => "dd",
+            Unit::Feet => "ft",
+        };
+        let origin = match grid.origin {
+            Origin::TopLeft => "TopLeft",
+            Origin::BottomLeft => "BottomLeft",
+        };
+        let (width, height) = grid.tile_size();
+        let levels: Vec<serde_json::Value> = (0..grid.nlevels())
+            .map(|zoom| {
+                let (matrix_width, matrix_height) = grid.matrix_size(zoom);
+                json!({
+                    "zoom": zoom,
+                    "resolution": grid.resolution(zoom),
+                    "scale_denominator": grid.scale_denominator(zoom),
+                    "matrix_width": matrix_width,
+                    "matrix_height": matrix_height
+                })
+            })
+            .collect();
+        Some(json!({
+            "name": self.grid_name,
+            "srid": grid.srid,
+            "units": units,
+            "extent": [grid.extent.minx, grid.extent.miny, grid.extent.maxx, grid.extent.maxy],
+            "origin": origin,
+            "tile_width": width,
+            "tile_height": height,
+            "minzoom": 0,
+            "maxzoom": grid.maxzoom(),
+            "levels": levels
+        }))
+    }
+
+    /// PMTiles metadata (https://github.com/protomaps/PMTiles/blob/main/spec/v3/spec.md)
+    pub fn get_pmtiles_metadata(&self, tileset: &str) -> JsonResult {
+        let ts = self
+            .get_tileset(tileset)
+            .expect(&format!("Tileset '{}' not found", tileset));
+        Ok(json!({
+            "name": tileset,
+            "attribution": ts.attribution(),
+            "vector_layers": self.get_tilejson_vector_layers(tileset)?
+        }))
+    }
 }
 
 #[cfg(test)]
 use t_rex_core::core::Config;
 
+#[test]
+fn test_catalog() {
+    use t_rex_core::core::read_config;
+    use t_rex_core::service::tileset::TimeDimension;
+
+    let config = read_config("src/test/example.toml").unwrap();
+    let mut service = MvtService::from_config(&config).unwrap();
+    service.tilesets[0].layers[1].tags = vec!["basemap".to_string()];
+
+    let tile_paths = vec![
+        "/services/{tileset}/tiles/{z}/{x}/{y}.mvt".to_string(),
+        "/{tileset}/{time}/{z}/{x}/{y}".to_string(),
+    ];
+    let catalog = service
+        .get_catalog("http://127.0.0.1", None, &tile_paths)
+        .unwrap();
+    assert_eq!(catalog["tilesets"][0]["name"], "osm");
+    assert_eq!(
+        catalog["tilesets"][0]["tilejson"],
+        "http://127.0.0.1/osm.json"
+    );
+    assert_eq!(catalog["tilesets"][0]["layers"][1]["tags"][0], "basemap");
+    assert_eq!(catalog["tilesets"][0]["scheme"], "xyz");
+    assert_eq!(
+        catalog["tilesets"][0]["tile_templates"],
+        json!([
+            {"url": "http://127.0.0.1/osm/{z}/{x}/{y}.pbf", "format": "pbf"},
+            {"url": "http://127.0.0.1/osm/{z}/{x}/{y}.mvt", "format": "mvt"},
+            {"url": "http://127.0.0.1/services/osm/tiles/{z}/{x}/{y}.mvt", "format": "mvt"},
+        ])
+    );
+    assert_eq!(
+        catalog["tilesets"][0]["links"][1]["href"],
+        "http://127.0.0.1/osm.style.json"
+    );
+    assert_eq!(
+        catalog["links"][2]["href"],
+        "http://127.0.0.1/grids/web_mercator.json"
+    );
+
+    service.tilesets[0].time = Some(TimeDimension {
+        values: vec!["2020".to_string(), "2021".to_string()],
+        range: None,
+        default: Some("2021".to_string()),
+    });
+    let catalog = service
+        .get_catalog("http://127.0.0.1", None, &tile_paths)
+        .unwrap();
+    let templates = catalog["tilesets"][0]["tile_templates"].as_array().unwrap();
+    assert_eq!(templates.len(), 5);
+    assert_eq!(
+        templates[2]["url"],
+        "http://127.0.0.1/osm/{time}/{z}/{x}/{y}.pbf"
+    );
+    assert_eq!(templates[2]["time"]["values"], json!(["2020", "2021"]));
+    assert_eq!(
+        templates[4]["url"],
+        "http://127.0.0.1/osm/{time}/{z}/{x}/{y}"
+    );
+    assert_eq!(templates[4]["format"], "pbf");
+
+    let catalog = service
+        .get_catalog("http://127.0.0.1", Some("basemap"), &[])
+        .unwrap();
+    assert_eq!(catalog["tilesets"].as_array().unwrap().len(), 1);
+    let catalog = service
+        .get_catalog("http://127.0.0.1", Some("internal"), &[])
+        .unwrap();
+    assert_eq!(catalog["tilesets"].as_array().unwrap().len(), 0);
+}
+
 #[test]
 fn test_mvt_metadata() {
     use t_rex_core::core::read_config;
@@ -316,6 +655,8 @@ fn test_mvt_metadata() {
           "name": "admin_0_countries"
         }
       ],
+      "maxzoom": 22,
+      "minzoom": 0,
       "name": "osm",
       "supported": true,
       "tilejson": "osm.json",
@@ -443,6 +784,80 @@ fn test_stylejson() {
     assert!(json.contains(expected));
 }
 
+#[test]
+fn test_viewer_stylejson() {
+    use t_rex_core::core::config::ViewerCfg;
+    use t_rex_core::core::read_config;
+
+    let config = read_config("src/test/example.toml").unwrap();
+    let mut service = MvtService::from_config(&config).unwrap();
+    let mut overlay = service.tilesets[0].clone();
+    overlay.name = "roads".to_string();
+    overlay.layers.truncate(1);
+    service.tilesets.push(overlay);
+
+    let viewer = ViewerCfg::default();
+    assert_eq!(
+        service
+            .get_viewer_stylejson("http://127.0.0.1", "osm", &viewer)
+            .unwrap(),
+        service.get_stylejson("http://127.0.0.1", "osm").unwrap()
+    );
+
+    let viewer = ViewerCfg {
+        background_tiles: Some("https://tile.openstreetmap.org/{z}/{x}/{y}.png".to_string()),
+        background_attribution: Some("© OpenStreetMap contributors".to_string()),
+        background_style: Some("https://example.com/style.json".to_string()),
+        overlays: vec![
+            "roads".to_string(),
+            "osm".to_string(),
+            "unknown".to_string(),
+        ],
+    };
+    let json = service
+        .get_viewer_stylejson("http://127.0.0.1", "osm", &viewer)
+        .unwrap();
+    assert_eq!(
+        json["sources"]["background_"],
+        json!({
+            "type": "raster",
+            "tiles": ["https://tile.openstreetmap.org/{z}/{x}/{y}.png"],
+            "tileSize": 256,
+            "attribution": "© OpenStreetMap contributors"
+        })
+    );
+    assert_eq!(
+        json["sources"]["roads"]["url"],
+        "http://127.0.0.1/roads.json"
+    );
+    assert_eq!(
+        json["metadata"]["t-rex:background_style"],
+        "https://example.com/style.json"
+    );
+    let layers: Vec<(&str, &str)> = json["layers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| {
+            (
+                l["id"].as_str().unwrap(),
+                l["source"].as_str().unwrap_or(""),
+            )
+        })
+        .collect();
+    assert_eq!(
+        layers,
+        vec![
+            ("background_", ""),
+            ("background_tiles_", "background_"),
+            ("points", "osm"),
+            ("buildings", "osm"),
+            ("admin_0_countries", "osm"),
+            ("roads.points", "roads"),
+        ]
+    );
+}
+
 #[test]
 #[ignore]
 fn test_mbtiles_metadata() {
@@ -472,3 +887,50 @@ fn test_mbtiles_metadata() {
 }"#;
     assert_eq!(metadata, expected);
 }
+
+#[test]
+fn test_layer_metadata_unknown() {
+    use t_rex_core::core::read_config;
+
+    let config = read_config("src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+    assert!(service.get_layer_metadata("osm", "unknown").is_none());
+    assert!(service.get_layer_metadata("unknown", "points").is_none());
+}
+
+#[test]
+#[ignore]
+fn test_layer_metadata() {
+    use std::env;
+    use t_rex_core::core::read_config;
+
+    env::var("DBCONN").expect("DBCONN undefined");
+    let config = read_config("src/test/example.toml").unwrap();
+    let mut service = MvtService::from_config(&config).unwrap();
+    service.connect();
+    service.prepare_feature_queries();
+    let metadata = service.get_layer_metadata("osm", "points").unwrap();
+    assert_eq!(metadata["geometry_type"], "POINT");
+    assert_eq!(metadata["queries"][0]["minzoom"], 0);
+    assert_eq!(metadata["queries"][0]["maxzoom"], 22);
+    assert!(metadata["row_count"].as_u64().is_some());
+}
+
+#[test]
+fn test_grid_metadata() {
+    use t_rex_core::core::read_config;
+
+    let config = read_config("src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+    assert_eq!(service.get_grid_metadata("wgs84"), None);
+    let grid = service.get_grid_metadata("web_mercator").unwrap();
+    assert_eq!(grid["srid"], 3857);
+    assert_eq!(grid["origin"], "BottomLeft");
+    assert_eq!(grid["tile_width"], 256);
+    assert_eq!(
+        grid["levels"].as_array().unwrap().len(),
+        service.grid.nlevels() as usize
+    );
+    assert_eq!(grid["levels"][1]["matrix_width"], 2);
+    assert_eq!(grid["levels"][0]["resolution"], service.grid.resolution(0));
+}
@@ -3,31 +3,810 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::datasources::{Datasource, Datasources};
+use crate::datasources::{Datasource, Datasources, FetchedFeatures};
+use crate::prerender::{PrerenderQueue, PrerenderTile};
+use crate::record::FixtureRecorder;
+use crate::seed_coordination::{WorkPartition, WorkSelector};
+use crate::tile_archive::TileArchive;
 use pbr::ProgressBar;
 use percent_encoding::percent_decode;
 use serde_json;
+use sha2::{Digest, Sha256};
 use std::cmp;
-use std::io::{stderr, Stderr, Stdout};
-use std::time::Instant;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, stderr, Read, Stderr, Stdout};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::{Duration, Instant};
 use t_rex_core::cache::{Cache, Tilecache};
+use t_rex_core::core::attr_stats::AttributeStatistics;
+use t_rex_core::core::config::{CacheLeaseCfg, TilesetCfg};
+use t_rex_core::core::feature::Feature;
 use t_rex_core::core::layer::Layer;
 use t_rex_core::core::stats::Statistics;
 use t_rex_core::core::{ApplicationCfg, Config};
 use t_rex_core::datasource::DatasourceType;
-use t_rex_core::mvt::tile::Tile;
+use t_rex_core::mvt::tile::{Tile, TileCompression, TileEncoding};
 use t_rex_core::mvt::vector_tile;
-use t_rex_core::service::tileset::{Tileset, WORLD_EXTENT};
+use t_rex_core::service::tileset::{CrawlProtection, Referrer, Tileset, WORLD_EXTENT};
 use tile_grid::{extent_wgs84_to_merc, Extent, ExtentInt, Grid, GridIterator};
 use tokio::task;
 
+/// Tile coordinates (z, x, y) in grid scheme
+type TileIter = Box<dyn Iterator<Item = (u8, u32, u32)>>;
+
 /// Mapbox Vector Tile Service
 #[derive(Clone)]
 pub struct MvtService {
     pub datasources: Datasources,
     pub grid: Grid,
+    /// Name of predefined grid or "user"
+    pub grid_name: String,
     pub tilesets: Vec<Tileset>,
     pub cache: Tilecache,
+    pub compression: TileCompression,
+    pub size_budget: TileSizeBudget,
+    pub memory: MemoryUsage,
+    pub geometry_errors: GeometryErrors,
+    pub layer_circuits: LayerCircuits,
+    /// Tile requests per tileset and zoom level
+    pub layer_access: LayerAccessStats,
+    /// Cached times of last data updates
+    pub data_updated: DataUpdated,
+    pub crawl_detector: CrawlDetector,
+    pub seeding: SeedingStats,
+    /// Store and serve SHA-256 digests of tiles
+    pub content_digest: bool,
+    /// Sort features and attributes for reproducible tiles
+    pub deterministic: bool,
+    /// Tile limits of tilesets with configured extent (set in `prepare_feature_queries`)
+    pub coverage: HashMap<String, Vec<ExtentInt>>,
+    pub aliases: TilesetAliases,
+    /// Tilesets and layers disabled because of configuration errors
+    pub disabled: Vec<DisabledConfig>,
+    /// Tiles currently rendered, shared by concurrent requests
+    pub renderings: TileRenderings,
+    /// Tilesets served from MBTiles or PMTiles archives
+    pub archives: HashMap<String, Arc<TileArchive>>,
+    /// Coordination of tile rendering with other instances sharing the cache
+    pub render_lease: Option<RenderLease>,
+    /// Queue of tiles rendered in the background around cache misses
+    pub prerender: Option<PrerenderQueue>,
+    /// Record retrieved features as fixtures for the replay datasource
+    pub record: Option<FixtureRecorder>,
+}
+
+/// Tileset or layer skipped when loading the configuration
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct DisabledConfig {
+    pub tileset: String,
+    /// Disabled layer (None: whole tileset)
+    pub layer: Option<String>,
+    pub error: String,
+}
+
+/// Tileset aliases shared between all service instances
+#[derive(Clone, Default)]
+pub struct TilesetAliases(Arc<RwLock<BTreeMap<String, String>>>);
+
+impl TilesetAliases {
+    /// Tileset name of alias
+    pub fn get(&self, alias: &str) -> Option<String> {
+        self.0.read().unwrap().get(alias).cloned()
+    }
+    /// All aliases with their tileset names
+    pub fn list(&self) -> BTreeMap<String, String> {
+        self.0.read().unwrap().clone()
+    }
+    fn insert(&self, alias: &str, tileset: &str) -> Option<String> {
+        self.0
+            .write()
+            .unwrap()
+            .insert(alias.to_string(), tileset.to_string())
+    }
+    fn remove(&self, alias: &str) -> Option<String> {
+        self.0.write().unwrap().remove(alias)
+    }
+}
+
+/// Limit for encoded tile size with counter of oversized tiles
+#[derive(Clone, Default)]
+pub struct TileSizeBudget {
+    /// Maximal tile size in bytes
+    pub limit: Option<u64>,
+    pub oversized_tiles: Arc<AtomicU64>,
+}
+
+impl TileSizeBudget {
+    pub fn new(limit_kb: Option<u32>) -> TileSizeBudget {
+        TileSizeBudget {
+            limit: limit_kb.map(|kb| kb as u64 * 1024),
+            oversized_tiles: Arc::new(AtomicU64::new(0)),
+        }
+    }
+    /// Check size of encoded tile and log oversized tiles. Returns false if tile is too large.
+    /// `xtile` and `ytile` are logged as in request URLs and cache paths (XYZ for Web Mercator).
+    pub fn check(
+        &self,
+        mvt_tile: &vector_tile::Tile,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+    ) -> bool {
+        let limit = match self.limit {
+            Some(limit) => limit,
+            None => return true,
+        };
+        let size = Tile::size(mvt_tile) as u64;
+        if size <= limit {
+            return true;
+        }
+        self.oversized_tiles.fetch_add(1, Ordering::Relaxed);
+        let largest = mvt_tile
+            .get_layers()
+            .iter()
+            .max_by_key(|l| Tile::layer_size(l))
+            .map(|l| format!("{} ({} KB)", l.get_name(), Tile::layer_size(l) / 1024))
+            .unwrap_or_default();
+        warn!(
+            "{}/{}/{}/{}: tile size {} KB exceeds {} KB - largest layer: {}",
+            tileset,
+            zoom,
+            xtile,
+            ytile,
+            size / 1024,
+            limit / 1024,
+            largest
+        );
+        false
+    }
+    /// Number of tiles exceeding the size limit
+    pub fn oversized_count(&self) -> u64 {
+        self.oversized_tiles.load(Ordering::Relaxed)
+    }
+}
+
+/// Approximate bytes buffered per feature besides its encoded size (row, attribute values)
+const FEATURE_OVERHEAD_BYTES: u64 = 64;
+
+/// Approximate memory of tiles in rendering with optional global cap
+#[derive(Clone, Default)]
+pub struct MemoryUsage {
+    /// Maximal memory of all tiles in rendering in bytes
+    pub limit: Option<u64>,
+    in_use: Arc<AtomicU64>,
+    peak: Arc<AtomicU64>,
+    tiles: Arc<AtomicU64>,
+    largest_tile: Arc<AtomicU64>,
+    exceeded: Arc<AtomicU64>,
+}
+
+/// Memory accounted to a tile in rendering, released on drop
+pub struct TileAllocation<'a> {
+    usage: &'a MemoryUsage,
+    bytes: u64,
+    exceeded: bool,
+}
+
+impl MemoryUsage {
+    pub fn new(limit_mb: Option<u32>) -> MemoryUsage {
+        MemoryUsage {
+            limit: limit_mb.map(|mb| mb as u64 * 1024 * 1024),
+            ..Default::default()
+        }
+    }
+    /// Start accounting of a tile
+    pub fn tile(&self) -> TileAllocation<'_> {
+        self.tiles.fetch_add(1, Ordering::Relaxed);
+        TileAllocation {
+            usage: self,
+            bytes: 0,
+            exceeded: false,
+        }
+    }
+    /// Bytes of all tiles in rendering
+    pub fn in_use(&self) -> u64 {
+        self.in_use.load(Ordering::Relaxed)
+    }
+    /// Highest value of `in_use`
+    pub fn peak(&self) -> u64 {
+        self.peak.load(Ordering::Relaxed)
+    }
+    /// Number of tiles in rendering
+    pub fn tiles_in_flight(&self) -> u64 {
+        self.tiles.load(Ordering::Relaxed)
+    }
+    /// Bytes of the largest rendered tile
+    pub fn largest_tile(&self) -> u64 {
+        self.largest_tile.load(Ordering::Relaxed)
+    }
+    /// Number of tiles truncated because of the memory limit
+    pub fn exceeded_count(&self) -> u64 {
+        self.exceeded.load(Ordering::Relaxed)
+    }
+}
+
+impl<'a> TileAllocation<'a> {
+    /// Account bytes of tile. Returns false if the memory limit is exceeded.
+    pub fn add(&mut self, bytes: u64) -> bool {
+        if self.exceeded {
+            return false;
+        }
+        let total = self.usage.in_use.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.bytes += bytes;
+        self.usage.peak.fetch_max(total, Ordering::Relaxed);
+        if self.usage.limit.is_some_and(|limit| total > limit) {
+            self.exceeded = true;
+            self.usage.exceeded.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+    /// Accounted bytes
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+    pub fn exceeded(&self) -> bool {
+        self.exceeded
+    }
+}
+
+impl<'a> Drop for TileAllocation<'a> {
+    fn drop(&mut self) {
+        self.usage.in_use.fetch_sub(self.bytes, Ordering::Relaxed);
+        self.usage.tiles.fetch_sub(1, Ordering::Relaxed);
+        self.usage
+            .largest_tile
+            .fetch_max(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Counter of invalid or unparseable geometries per tileset and layer
+#[derive(Clone, Default)]
+pub struct GeometryErrors(Arc<Mutex<BTreeMap<(String, String), u64>>>);
+
+impl GeometryErrors {
+    pub fn add(&self, tileset: &str, layer: &str, count: u64) {
+        let mut counts = self.0.lock().unwrap();
+        *counts
+            .entry((tileset.to_string(), layer.to_string()))
+            .or_insert(0) += count;
+    }
+    /// Number of invalid geometries per (tileset, layer)
+    pub fn counts(&self) -> BTreeMap<(String, String), u64> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Failure state of a layer
+#[derive(Clone, Default, Debug)]
+pub struct LayerCircuit {
+    /// Consecutive failed queries
+    pub failures: u32,
+    /// Layer is skipped until this time
+    pub open_until: Option<Instant>,
+    /// Number of skipped layer queries
+    pub skipped: u64,
+}
+
+impl LayerCircuit {
+    pub fn is_open(&self) -> bool {
+        self.open_until.is_some_and(|until| Instant::now() < until)
+    }
+}
+
+/// Circuit breaker skipping layers with repeatedly failing queries for a cooldown period
+#[derive(Clone)]
+pub struct LayerCircuits {
+    /// Consecutive failures opening the circuit (0: disabled)
+    pub threshold: u32,
+    pub cooldown: Duration,
+    circuits: Arc<Mutex<BTreeMap<(String, String), LayerCircuit>>>,
+}
+
+impl Default for LayerCircuits {
+    fn default() -> Self {
+        LayerCircuits::new(None, None)
+    }
+}
+
+impl LayerCircuits {
+    pub fn new(threshold: Option<u32>, cooldown_secs: Option<u32>) -> LayerCircuits {
+        LayerCircuits {
+            threshold: threshold.unwrap_or(5),
+            cooldown: Duration::from_secs(cooldown_secs.unwrap_or(60) as u64),
+            circuits: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+    /// Check whether the layer should be queried. After the cooldown a single query is let
+    /// through, while concurrent requests keep skipping the layer until it succeeds.
+    pub fn allow(&self, tileset: &str, layer: &str) -> bool {
+        if self.threshold == 0 {
+            return true;
+        }
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = match circuits.get_mut(&(tileset.to_string(), layer.to_string())) {
+            Some(circuit) => circuit,
+            None => return true,
+        };
+        match circuit.open_until {
+            Some(_) if circuit.is_open() => {
+                circuit.skipped += 1;
+                false
+            }
+            Some(_) => {
+                circuit.open_until = Some(Instant::now() + self.cooldown);
+                true
+            }
+            None => true,
+        }
+    }
+    /// Circuit of layer is open and its cooldown period not elapsed
+    pub fn is_open(&self, tileset: &str, layer: &str) -> bool {
+        self.threshold > 0
+            && self
+                .circuits
+                .lock()
+                .unwrap()
+                .get(&(tileset.to_string(), layer.to_string()))
+                .is_some_and(|circuit| circuit.is_open())
+    }
+    pub fn success(&self, tileset: &str, layer: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        if let Some(circuit) = circuits.get_mut(&(tileset.to_string(), layer.to_string())) {
+            if circuit.open_until.take().is_some() {
+                info!(
+                    "Tileset '{}': layer '{}' recovered - querying again",
+                    tileset, layer
+                );
+            }
+            circuit.failures = 0;
+        }
+    }
+    pub fn failure(&self, tileset: &str, layer: &str) {
+        if self.threshold == 0 {
+            return;
+        }
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits
+            .entry((tileset.to_string(), layer.to_string()))
+            .or_default();
+        circuit.failures += 1;
+        if circuit.failures >= self.threshold {
+            circuit.open_until = Some(Instant::now() + self.cooldown);
+            warn!(
+                "Tileset '{}': layer '{}' failed {} times in a row - skipping it for {}s",
+                tileset,
+                layer,
+                circuit.failures,
+                self.cooldown.as_secs()
+            );
+        }
+    }
+    /// Failure state per (tileset, layer) of layers with failed queries
+    pub fn circuits(&self) -> BTreeMap<(String, String), LayerCircuit> {
+        self.circuits.lock().unwrap().clone()
+    }
+}
+
+/// Counter of tile requests per tileset and zoom level
+#[derive(Clone, Default)]
+pub struct LayerAccessStats(Arc<Mutex<BTreeMap<(String, u8), u64>>>);
+
+impl LayerAccessStats {
+    pub fn add(&self, tileset: &str, zoom: u8) {
+        let mut counts = self.0.lock().unwrap();
+        *counts.entry((tileset.to_string(), zoom)).or_insert(0) += 1;
+    }
+    /// Number of tile requests per (tileset, zoom)
+    pub fn counts(&self) -> BTreeMap<(String, u8), u64> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Seconds until the time of the last data update is queried again
+const DATA_UPDATED_TTL: u64 = 60;
+
+/// Time of the last data update per tileset and layer, cached for `DATA_UPDATED_TTL` seconds
+#[derive(Clone, Default)]
+pub struct DataUpdated(Arc<Mutex<HashMap<(String, String), (Instant, Option<String>)>>>);
+
+impl DataUpdated {
+    /// Cached update time or result of `lookup`
+    pub fn get<F>(&self, tileset: &str, layer: &str, lookup: F) -> Option<String>
+    where
+        F: FnOnce() -> Option<String>,
+    {
+        let key = (tileset.to_string(), layer.to_string());
+        if let Some((checked, updated)) = self.0.lock().unwrap().get(&key) {
+            if checked.elapsed() < Duration::from_secs(DATA_UPDATED_TTL) {
+                return updated.clone();
+            }
+        }
+        // Lookup without holding the lock, concurrent lookups are harmless
+        let updated = lookup();
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key, (Instant::now(), updated.clone()));
+        updated
+    }
+}
+
+/// Number of tracked clients before expired entries are removed
+const CRAWL_DETECTOR_MAX_CLIENTS: usize = 10000;
+
+/// Recent tile requests of a client
+struct ClientTileRequests {
+    /// Last requested tile per zoom level
+    last_tiles: HashMap<u8, (u32, u32)>,
+    /// Times of requests continuing a row or column enumeration
+    sequential: VecDeque<Instant>,
+    last_request: Instant,
+}
+
+/// Heuristic detection of clients enumerating tiles zoom level by zoom level
+#[derive(Clone, Default)]
+pub struct CrawlDetector(Arc<Mutex<HashMap<(String, String), ClientTileRequests>>>);
+
+impl CrawlDetector {
+    /// Register tile request and return whether the client exceeds the number
+    /// of sequentially enumerated tiles
+    #[allow(clippy::too_many_arguments)]
+    pub fn is_crawling(
+        &self,
+        client: &str,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        protection: &CrawlProtection,
+        now: Instant,
+    ) -> bool {
+        let window = Duration::from_secs(protection.window);
+        let mut clients = self.0.lock().unwrap();
+        if clients.len() >= CRAWL_DETECTOR_MAX_CLIENTS {
+            clients.retain(|_, requests| now.duration_since(requests.last_request) < window);
+        }
+        let requests = clients
+            .entry((client.to_string(), tileset.to_string()))
+            .or_insert_with(|| ClientTileRequests {
+                last_tiles: HashMap::new(),
+                sequential: VecDeque::new(),
+                last_request: now,
+            });
+        requests.last_request = now;
+        let sequential = match requests.last_tiles.insert(zoom, (xtile, ytile)) {
+            // Next tile in row or column, or first tile of next row or column
+            Some((x, y)) => {
+                (xtile == x + 1 && ytile == y)
+                    || (xtile == x && ytile == y + 1)
+                    || (xtile == 0 && ytile == y + 1)
+                    || (xtile == x + 1 && ytile == 0)
+            }
+            None => false,
+        };
+        while let Some(time) = requests.sequential.front() {
+            if now.duration_since(*time) < window {
+                break;
+            }
+            requests.sequential.pop_front();
+        }
+        if sequential {
+            requests.sequential.push_back(now);
+        }
+        requests.sequential.len() > protection.max_sequential as usize
+    }
+}
+
+/// Requests of layer data since server start
+#[derive(Clone, Serialize, PartialEq, Debug)]
+pub struct LayerAccess {
+    pub tileset: String,
+    pub layer: String,
+    /// Zoom range of layer within tileset
+    pub minzoom: u8,
+    pub maxzoom: u8,
+    /// Tile requests per zoom level
+    pub requests: BTreeMap<u8, u64>,
+    pub total: u64,
+    /// Zoom range with tile requests (None: layer never requested)
+    pub requested_zoom: Option<(u8, u8)>,
+    /// Time of the last data update (`updated_sql` or `updated_file`)
+    pub updated: Option<String>,
+}
+
+/// Result of a tile rendering, set when finished
+#[derive(Default)]
+struct Rendering {
+    result: Mutex<Option<Option<Vec<u8>>>>,
+    finished: Condvar,
+}
+
+/// Tile renderings in progress. Concurrent requests of the same tile (e.g. with different
+/// `Accept-Encoding`) wait for the first rendering instead of running the whole pipeline again.
+#[derive(Clone, Default)]
+pub struct TileRenderings(Arc<Mutex<HashMap<String, Arc<Rendering>>>>);
+
+/// Publishes the rendering result to waiting requests, also if rendering panicked
+struct RenderingGuard<'a> {
+    renderings: &'a TileRenderings,
+    key: &'a str,
+    rendering: Arc<Rendering>,
+    result: Option<Option<Vec<u8>>>,
+}
+
+impl<'a> Drop for RenderingGuard<'a> {
+    fn drop(&mut self) {
+        self.renderings.0.lock().unwrap().remove(self.key);
+        let mut result = self.rendering.result.lock().unwrap();
+        *result = Some(self.result.take().unwrap_or(None));
+        self.rendering.finished.notify_all();
+    }
+}
+
+impl TileRenderings {
+    /// Render tile data once for all concurrent calls with the same key
+    pub fn coalesce<F>(&self, key: &str, render: F) -> Option<Vec<u8>>
+    where
+        F: FnOnce() -> Option<Vec<u8>>,
+    {
+        let (rendering, first) = {
+            let mut renderings = self.0.lock().unwrap();
+            match renderings.get(key) {
+                Some(rendering) => (rendering.clone(), false),
+                None => {
+                    let rendering = Arc::new(Rendering::default());
+                    renderings.insert(key.to_string(), rendering.clone());
+                    (rendering, true)
+                }
+            }
+        };
+        if first {
+            let mut guard = RenderingGuard {
+                renderings: self,
+                key,
+                rendering,
+                result: None,
+            };
+            let data = render();
+            guard.result = Some(data.clone());
+            data
+        } else {
+            debug!("{} - Waiting for rendering in progress", key);
+            let mut result = rendering.result.lock().unwrap();
+            while result.is_none() {
+                result = rendering.finished.wait(result).unwrap();
+            }
+            result.clone().unwrap_or(None)
+        }
+    }
+    /// Number of tiles currently rendered
+    pub fn in_progress(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+}
+
+/// Interval for checking the cache while another instance renders a tile
+const LEASE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Outcome of waiting for a render lease
+pub enum LeaseWait {
+    /// Lease acquired, tile has to be rendered
+    Acquired,
+    /// Tile written by the lease holder
+    Rendered(Vec<u8>),
+    /// Lease still held by another instance after waiting
+    TimedOut,
+}
+
+/// Render leases in a shared cache, so only one of several instances renders a missing tile
+#[derive(Clone, Debug)]
+pub struct RenderLease {
+    /// Identification of this instance
+    pub owner: String,
+    pub ttl: Duration,
+    pub wait: Duration,
+    /// Serve an empty placeholder instead of rendering after waiting
+    pub placeholder: bool,
+}
+
+impl RenderLease {
+    pub fn from_config(config: &CacheLeaseCfg) -> Result<RenderLease, String> {
+        let placeholder = match config.fallback.as_deref() {
+            None | Some("render") => false,
+            Some("empty") => true,
+            Some(other) => {
+                return Err(format!(
+                    "Invalid lease fallback '{}' (expected 'render' or 'empty')",
+                    other
+                ))
+            }
+        };
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "t-rex".to_string());
+        let started = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        Ok(RenderLease {
+            owner: format!("{}:{}:{}", host, std::process::id(), started),
+            ttl: Duration::from_secs(config.ttl.unwrap_or(30)),
+            wait: Duration::from_secs(config.wait.unwrap_or(5)),
+            placeholder,
+        })
+    }
+    /// Acquire lease for tile at cache path, or wait for the tile rendered by the lease holder
+    pub fn acquire(&self, cache: &Tilecache, path: &str) -> LeaseWait {
+        let start = Instant::now();
+        loop {
+            if cache.try_lease(path, &self.owner, self.ttl) {
+                // The previous lease holder may have finished in the meantime
+                return match read_cached(cache, path) {
+                    Some(data) => {
+                        cache.release_lease(path, &self.owner);
+                        LeaseWait::Rendered(data)
+                    }
+                    None => LeaseWait::Acquired,
+                };
+            }
+            if start.elapsed() >= self.wait {
+                return LeaseWait::TimedOut;
+            }
+            debug!("{} - Waiting for rendering by other instance", path);
+            std::thread::sleep(LEASE_POLL_INTERVAL);
+            if let Some(data) = read_cached(cache, path) {
+                return LeaseWait::Rendered(data);
+            }
+        }
+    }
+    pub fn release(&self, cache: &Tilecache, path: &str) {
+        cache.release_lease(path, &self.owner);
+    }
+}
+
+fn read_cached(cache: &Tilecache, path: &str) -> Option<Vec<u8>> {
+    let mut data = None;
+    cache.read(path, |f| {
+        let mut buf = Vec::new();
+        if f.read_to_end(&mut buf).is_ok() {
+            data = Some(buf);
+        }
+    });
+    data
+}
+
+/// Content-Digest header value (RFC 9530) of tile data
+pub fn content_digest(data: &[u8]) -> String {
+    format!("sha-256=:{}:", base64::encode(Sha256::digest(data)))
+}
+
+/// Cache path of the digest stored with a tile
+pub(crate) fn digest_path(path: &str) -> String {
+    format!("{}.sha256", path)
+}
+
+/// Write tile into cache, with its digest if requested.
+/// A digest stored with a previous version of the tile is removed.
+pub(crate) fn write_cached_tile(
+    cache: &Tilecache,
+    path: &str,
+    data: &[u8],
+    digest: bool,
+) -> Result<(), io::Error> {
+    cache.write(path, data)?;
+    if digest {
+        cache.write(&digest_path(path), content_digest(data).as_bytes())
+    } else {
+        cache.remove(&digest_path(path))
+    }
+}
+
+/// Remove tile and its content digest from cache
+pub(crate) fn remove_cached_tile(cache: &Tilecache, path: &str) -> Result<(), io::Error> {
+    cache.remove(path)?;
+    cache.remove(&digest_path(path))
+}
+
+/// Encoding of tile data delivered with or without gzip compression
+fn served_encoding(gzip: bool) -> TileEncoding {
+    if gzip {
+        TileEncoding::Gzip
+    } else {
+        TileEncoding::Identity
+    }
+}
+
+/// Encoding of a local cache file, recognized from its first bytes
+fn local_tile_encoding(file: &str) -> Option<TileEncoding> {
+    let mut prefix = [0u8; 2];
+    let len = File::open(file)
+        .and_then(|mut f| f.read(&mut prefix))
+        .ok()?;
+    TileEncoding::sniff(&prefix[..len])
+}
+
+/// Request dependent options for creating a tile
+#[derive(Clone, Default, Debug)]
+pub struct TileOptions {
+    /// Include protected attributes
+    pub authenticated: bool,
+    /// Time value of a time dimension tileset
+    pub time: Option<String>,
+    /// Render tile ignoring and overwriting the cached tile
+    pub refresh: bool,
+    /// Tile requested by an interactive client. Cache misses feed the pre-render queue.
+    pub interactive: bool,
+}
+
+/// Features of tile layers fetched with `MvtService::prefetch_features`, by layer name
+pub type PrefetchedFeatures = HashMap<String, Result<FetchedFeatures, String>>;
+
+/// Cache path of tile, partitioned by time value
+pub(crate) fn tile_cache_path(
+    tileset: &str,
+    time: Option<&str>,
+    zoom: u8,
+    xtile: u32,
+    ytile: u32,
+) -> String {
+    match time {
+        Some(time) => format!("{}/time={}/{}/{}/{}.pbf", tileset, time, zoom, xtile, ytile),
+        None => format!("{}/{}/{}/{}.pbf", tileset, zoom, xtile, ytile),
+    }
+}
+
+/// Progress counters of cache seeding
+#[derive(Clone, Default)]
+pub struct SeedingStats {
+    pub tiles_rendered: Arc<AtomicU64>,
+    pub tiles_failed: Arc<AtomicU64>,
+    pub current_zoom: Arc<AtomicU64>,
+    started: Arc<Mutex<Option<Instant>>>,
+}
+
+impl SeedingStats {
+    pub fn start(&self) {
+        let mut started = self.started.lock().unwrap();
+        if started.is_none() {
+            *started = Some(Instant::now());
+        }
+    }
+    pub fn is_started(&self) -> bool {
+        self.started.lock().unwrap().is_some()
+    }
+    /// Rendered tiles per second since start
+    pub fn throughput(&self) -> f64 {
+        match *self.started.lock().unwrap() {
+            Some(started) => {
+                let secs = started.elapsed().as_secs_f64();
+                if secs > 0.0 {
+                    self.tiles_rendered.load(Ordering::Relaxed) as f64 / secs
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        }
+    }
+}
+
+/// Reason for rejecting a tile request
+#[derive(PartialEq, Debug)]
+pub enum TileRequestError {
+    /// Tileset not configured
+    UnknownTileset,
+    /// Tile coordinates outside of grid
+    InvalidTile,
+    /// Tile outside of tileset zoom range or extent
+    OutsideCoverage,
+    /// Referrer not allowed for tileset
+    Forbidden,
+    /// Time not valid for time dimension of tileset
+    InvalidTime,
+    /// Tile enumeration detected for crawl protected tileset
+    Crawling,
 }
 
 impl MvtService {
@@ -37,6 +816,7 @@ impl MvtService {
     pub fn connect(&mut self) {
         let mut datasources = Datasources::new();
         datasources.default = self.datasources.default.clone();
+        datasources.query_limits = self.datasources.query_limits.clone();
         for (name, ds) in &self.datasources.datasources {
             datasources.add(&name, ds.connected());
         }
@@ -46,10 +826,32 @@ impl MvtService {
     pub(crate) fn ds(&self, layer: &Layer) -> Option<&Datasource> {
         self.datasources.datasource(&layer.datasource)
     }
-    pub(crate) fn get_tileset(&self, name: &str) -> Option<&Tileset> {
+    pub fn get_tileset(&self, name: &str) -> Option<&Tileset> {
         // URL decode tileset names from http requests
         let dec_name = percent_decode(name.as_bytes()).decode_utf8().unwrap();
-        self.tilesets.iter().find(|t| t.name == dec_name)
+        self.tilesets
+            .iter()
+            .find(|t| t.name == dec_name)
+            .or_else(|| {
+                let target = self.aliases.get(&dec_name)?;
+                self.tilesets.iter().find(|t| t.name == target)
+            })
+    }
+    /// Point alias to tileset. Returns the previous tileset name of the alias.
+    pub fn set_alias(&self, alias: &str, tileset: &str) -> Result<Option<String>, String> {
+        if self.tilesets.iter().any(|t| t.name == alias) {
+            return Err(format!("Alias '{}' conflicts with tileset name", alias));
+        }
+        if !self.tilesets.iter().any(|t| t.name == tileset) {
+            return Err(format!("Unknown tileset '{}'", tileset));
+        }
+        let previous = self.aliases.insert(alias, tileset);
+        info!("Alias '{}' points to tileset '{}'", alias, tileset);
+        Ok(previous)
+    }
+    /// Remove alias. Returns the tileset name of the removed alias.
+    pub fn remove_alias(&self, alias: &str) -> Option<String> {
+        self.aliases.remove(alias)
     }
     /// Get layers (as reference) of given tileset
     pub(crate) fn get_tileset_layers(&self, name: &str) -> Vec<&Layer> {
@@ -69,15 +871,211 @@ impl MvtService {
                 ds.prepare_queries(&tileset.name, &layer, self.grid.srid);
             }
         }
+        self.coverage = self
+            .tilesets
+            .iter()
+            .filter_map(|ts| {
+                let ext = ts.extent.as_ref()?;
+                let ext_proj = if self.grid.srid == 3857 {
+                    extent_wgs84_to_merc(ext)
+                } else if self.grid.srid == 4326 {
+                    ext.clone()
+                } else {
+                    let ext_proj = self
+                        .datasources
+                        .default()
+                        .and_then(|ds| ds.reproject_extent(ext, self.grid.srid, None));
+                    if ext_proj.is_none() {
+                        warn!(
+                            "Tileset '{}': Error transforming extent to SRID {} - coverage not checked",
+                            ts.name, self.grid.srid
+                        );
+                    }
+                    ext_proj?
+                };
+                Some((ts.name.clone(), self.grid.tile_limits(ext_proj, 0)))
+            })
+            .collect();
+    }
+    /// Check whether tile at x, y, z in TMS adressing scheme is within tileset zoom range and extent
+    pub fn tile_in_coverage(&self, tileset: &Tileset, xtile: u32, ytile: u32, zoom: u8) -> bool {
+        if zoom < tileset.minzoom() || zoom > tileset.maxzoom() {
+            return false;
+        }
+        match self.coverage.get(&tileset.name) {
+            Some(limits) => match limits.get(zoom as usize) {
+                Some(limit) => {
+                    xtile >= limit.minx
+                        && xtile < limit.maxx
+                        && ytile >= limit.miny
+                        && ytile < limit.maxy
+                }
+                None => false,
+            },
+            None => true,
+        }
+    }
+    /// Check referrer restriction of tileset (Origin or Referer header value and bypass token)
+    pub fn check_tile_access(
+        &self,
+        tileset: &str,
+        referrer: Option<Referrer>,
+        token: Option<&str>,
+    ) -> Result<(), TileRequestError> {
+        let ts = self
+            .get_tileset(tileset)
+            .ok_or(TileRequestError::UnknownTileset)?;
+        if ts.access_allowed(referrer, token) {
+            Ok(())
+        } else {
+            Err(TileRequestError::Forbidden)
+        }
+    }
+    /// Check crawl protection of tileset for a client request (token from `X-Crawl-Token` header or query)
+    pub fn check_crawling(
+        &self,
+        tileset: &str,
+        client: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        token: Option<&str>,
+    ) -> Result<(), TileRequestError> {
+        let ts = self
+            .get_tileset(tileset)
+            .ok_or(TileRequestError::UnknownTileset)?;
+        let protection = match ts.crawl_protection {
+            Some(ref protection) => protection,
+            None => return Ok(()),
+        };
+        if protection.token_valid(token) {
+            return Ok(());
+        }
+        if self.crawl_detector.is_crawling(
+            client,
+            &ts.name,
+            xtile,
+            ytile,
+            zoom,
+            protection,
+            Instant::now(),
+        ) {
+            Err(TileRequestError::Crawling)
+        } else {
+            Ok(())
+        }
+    }
+    /// Generated robots.txt excluding crawl protected tilesets
+    pub fn robots_txt(&self) -> String {
+        let mut robots = "User-agent: *\n".to_string();
+        let disallowed: Vec<&Tileset> = self
+            .tilesets
+            .iter()
+            .filter(|ts| ts.crawl_protection.as_ref().is_some_and(|p| p.disallow))
+            .collect();
+        if disallowed.is_empty() {
+            robots.push_str("Disallow:\n");
+        }
+        for ts in disallowed {
+            robots.push_str(&format!("Disallow: /{}/\n", ts.name));
+        }
+        robots
+    }
+    /// Validate tile request at x, y, z (XYZ adressing scheme for Web Mercator, TMS otherwise)
+    pub fn check_tile_request(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+    ) -> Result<(), TileRequestError> {
+        let ts = self
+            .get_tileset(tileset)
+            .ok_or(TileRequestError::UnknownTileset)?;
+        if zoom > self.grid.maxzoom() {
+            return Err(TileRequestError::InvalidTile);
+        }
+        let (maxx, maxy) = self.grid.matrix_size(zoom);
+        if xtile >= maxx || ytile >= maxy {
+            return Err(TileRequestError::InvalidTile);
+        }
+        let y = if self.grid.srid == 3857 {
+            self.grid.ytile_from_xyz(ytile, zoom)
+        } else {
+            ytile
+        };
+        if !self.tile_in_coverage(ts, xtile, y, zoom) {
+            return Err(TileRequestError::OutsideCoverage);
+        }
+        Ok(())
+    }
+    /// Check whether token unlocks protected attributes of tileset
+    pub fn unlocks_protected_fields(&self, tileset: &str, token: Option<&str>) -> bool {
+        match (self.get_tileset(tileset), token) {
+            (Some(ts), Some(token)) => {
+                ts.layers.iter().any(|l| !l.protected_fields.is_empty())
+                    && ts
+                        .access
+                        .as_ref()
+                        .and_then(|access| access.token.as_ref())
+                        .map(|t| t == token)
+                        .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+    /// Requested time or default time of tileset with time dimension
+    pub(crate) fn tile_time(&self, tileset: &Tileset, time: Option<&str>) -> Option<String> {
+        let dimension = tileset.time.as_ref()?;
+        time.map(|t| t.to_string())
+            .or_else(|| dimension.default.clone())
+    }
+    /// Check time value of tileset with time dimension
+    pub fn check_tile_time(&self, tileset: &str, time: &str) -> Result<(), TileRequestError> {
+        let ts = self
+            .get_tileset(tileset)
+            .ok_or(TileRequestError::UnknownTileset)?;
+        match ts.time {
+            Some(ref dimension) if dimension.is_valid(time) => Ok(()),
+            _ => Err(TileRequestError::InvalidTime),
+        }
     }
     /// Create vector tile from input at x, y, z in TMS adressing scheme
     pub fn tile(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        stats: Option<&mut Statistics>,
+    ) -> vector_tile::Tile {
+        self.tile_with_options(tileset, xtile, ytile, zoom, stats, &TileOptions::default())
+    }
+    /// Create vector tile with request dependent options
+    pub fn tile_with_options(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        stats: Option<&mut Statistics>,
+        options: &TileOptions,
+    ) -> vector_tile::Tile {
+        let mut prefetched = PrefetchedFeatures::new();
+        self.tile_with_prefetched(tileset, xtile, ytile, zoom, stats, options, &mut prefetched)
+    }
+    /// Create vector tile using features fetched asynchronously in advance.
+    /// Layers without prefetched features are retrieved blocking.
+    #[allow(clippy::too_many_arguments)]
+    fn tile_with_prefetched(
         &self,
         tileset: &str,
         xtile: u32,
         ytile: u32,
         zoom: u8,
         mut stats: Option<&mut Statistics>,
+        options: &TileOptions,
+        prefetched: &mut PrefetchedFeatures,
     ) -> vector_tile::Tile {
         let extent = self.grid.tile_extent(xtile, ytile, zoom);
         debug!(
@@ -85,22 +1083,156 @@ impl MvtService {
             tileset, zoom, xtile, ytile, extent
         );
         let mut tile = Tile::new(&extent, true);
+        tile.authenticated = options.authenticated;
+        let mut allocation = self.memory.tile();
+        let ts = self.get_tileset(tileset);
+        let time = ts.and_then(|ts| self.tile_time(ts, options.time.as_deref()));
+        // Resolve alias
+        let tileset = ts.map(|ts| ts.name.as_str()).unwrap_or(tileset);
         for layer in self.get_tileset_layers(tileset) {
             if zoom >= layer.minzoom() && zoom <= layer.maxzoom(self.grid.maxzoom()) {
                 let mut mvt_layer = tile.new_layer(layer);
+                let mut fids: HashMap<u64, u64> = HashMap::new();
+                let track_fids = layer.dedup_fid || layer.fid_check.is_some();
+                let mut duplicate_fids = 0;
+                let mut invalid_geometries = 0;
+                if !self.layer_circuits.allow(tileset, &layer.name) {
+                    debug!(
+                        "{}/{}/{}/{} layer {}: skipped after repeated failures",
+                        tileset, zoom, xtile, ytile, layer.name
+                    );
+                    continue;
+                }
+                let ds = self.ds(&layer).unwrap();
+                let fetched = prefetched.remove(&layer.name);
+                let permit = if fetched.is_none() {
+                    self.datasources.query_permit(&layer.datasource)
+                } else {
+                    None
+                };
                 let now = Instant::now();
-                let num_features = self.ds(&layer).unwrap().retrieve_features(
-                    tileset,
-                    &layer,
-                    &extent,
-                    zoom,
-                    &self.grid,
-                    |feat| {
-                        tile.add_feature(&mut mvt_layer, feat);
-                    },
-                );
+                let mut recorded = Vec::new();
+                let result = if layer.st_asmvt() {
+                    // Layer blob built by the datasource
+                    ds.retrieve_mvt_layer(
+                        tileset,
+                        &layer,
+                        &extent,
+                        zoom,
+                        &self.grid,
+                        time.as_deref(),
+                    )
+                    .and_then(|data| {
+                        allocation.add(data.len() as u64);
+                        Tile::read_from(&mut &data[..])
+                            .map_err(|e| format!("Layer '{}': {}", layer.name, e))
+                    })
+                    .map(|mut encoded| {
+                        if let Some(encoded_layer) = encoded.take_layers().into_iter().next() {
+                            mvt_layer = encoded_layer;
+                        }
+                        mvt_layer.get_features().len() as u64
+                    })
+                } else {
+                    let mut read = |feat: &dyn Feature| {
+                        if let Some(ref recorder) = self.record {
+                            recorded.push(recorder.feature(feat, layer));
+                        }
+                        if allocation.exceeded() {
+                            return;
+                        }
+                        let mut new_fid = None;
+                        if track_fids {
+                            if let Some(fid) = feat.fid() {
+                                let occurrence = fids.entry(fid).or_insert(0);
+                                *occurrence += 1;
+                                if *occurrence > 1 {
+                                    if layer.dedup_fid {
+                                        trace!(
+                                            "Layer '{}': skipping duplicate fid {}",
+                                            layer.name,
+                                            fid
+                                        );
+                                        return;
+                                    }
+                                    duplicate_fids += 1;
+                                    if layer.fid_check.as_deref() == Some("renumber") {
+                                        new_fid =
+                                            Some(Tile::disambiguated_fid(fid, *occurrence - 1));
+                                    }
+                                }
+                            }
+                        }
+                        let feature_count = mvt_layer.get_features().len();
+                        if tile.add_feature(&mut mvt_layer, feat).is_err() {
+                            invalid_geometries += 1;
+                        }
+                        if mvt_layer.get_features().len() > feature_count {
+                            let mvt_feature = mvt_layer.mut_features().last_mut().unwrap();
+                            if let Some(fid) = new_fid {
+                                mvt_feature.set_id(fid);
+                            }
+                            let size = Tile::feature_size(mvt_feature) as u64;
+                            if !allocation.add(FEATURE_OVERHEAD_BYTES + size) {
+                                warn!(
+                                        "{}/{}/{}/{} layer {}: memory_limit exceeded - skipping remaining features",
+                                        tileset, zoom, xtile, ytile, layer.name
+                                    );
+                            }
+                        }
+                    };
+                    match fetched {
+                        Some(features) => features
+                            .map(|features| ds.read_fetched(layer, zoom, &features, &mut read)),
+                        None => ds.try_retrieve_features_at(
+                            tileset,
+                            &layer,
+                            &extent,
+                            zoom,
+                            &self.grid,
+                            time.as_deref(),
+                            &mut read,
+                        ),
+                    }
+                };
+                drop(permit);
+                if let (Some(recorder), Ok(_), false) = (&self.record, &result, layer.st_asmvt()) {
+                    if let Err(e) =
+                        recorder.write(tileset, layer, &extent, zoom, &self.grid, recorded)
+                    {
+                        warn!("Recording features of layer '{}': {}", layer.name, e);
+                    }
+                }
+                // Features in layer, without skipped duplicates and invalid geometries
+                let num_features = match result {
+                    Ok(_) => {
+                        self.layer_circuits.success(tileset, &layer.name);
+                        mvt_layer.get_features().len() as u64
+                    }
+                    Err(err) => {
+                        error!("{}/{}/{}/{}: {}", tileset, zoom, xtile, ytile, err);
+                        self.layer_circuits.failure(tileset, &layer.name);
+                        0
+                    }
+                };
+                if duplicate_fids > 0 && layer.fid_check.as_deref() == Some("warn") {
+                    warn!(
+                        "{}/{}/{}/{} layer {}: {} features with duplicate fid",
+                        tileset, zoom, xtile, ytile, layer.name, duplicate_fids
+                    );
+                }
                 let elapsed = now.elapsed();
+                if invalid_geometries > 0 {
+                    self.geometry_errors
+                        .add(tileset, &layer.name, invalid_geometries);
+                }
                 if let Some(ref mut stats) = stats {
+                    if invalid_geometries > 0 {
+                        stats.add(
+                            format!("invalid_geometries.{}.{}.{}", tileset, layer.name, zoom),
+                            invalid_geometries,
+                        );
+                    }
                     stats.add(
                         format!("tile_ms.{}.{}.{}", tileset, layer.name, zoom),
                         elapsed.as_secs() * 1000 + elapsed.subsec_millis() as u64,
@@ -115,12 +1247,406 @@ impl MvtService {
                     tileset, zoom, xtile, ytile, layer.name, num_features
                 );
                 if num_features > 0 {
+                    if self.deterministic {
+                        Tile::canonicalize_layer(&mut mvt_layer);
+                    }
                     tile.add_layer(mvt_layer);
                 }
             }
         }
+        // Encoded tile
+        allocation.add(Tile::size(&tile.mvt_tile) as u64);
+        debug!(
+            "{}/{}/{}/{}: ~{} KB memory",
+            tileset,
+            zoom,
+            xtile,
+            ytile,
+            allocation.bytes() / 1024
+        );
+        if let Some(ref mut stats) = stats {
+            stats.add(
+                format!("memory_kb.{}.{}", tileset, zoom),
+                allocation.bytes() / 1024,
+            );
+        }
         tile.mvt_tile
     }
+    /// Tiles (z, x, y in grid scheme) touched by features changed since timestamp
+    pub fn changed_tiles(
+        &self,
+        tileset: &Tileset,
+        since: &str,
+        minzoom: u8,
+        maxzoom: u8,
+    ) -> Result<BTreeSet<(u8, u32, u32)>, String> {
+        let mut tiles = BTreeSet::new();
+        for layer in tileset.layers.iter().filter(|l| l.changes_sql.is_some()) {
+            let ds = self
+                .ds(layer)
+                .ok_or_else(|| format!("Datasource of layer `{}` not found", layer.name))?;
+            let layer_minzoom = cmp::max(minzoom, layer.minzoom());
+            let layer_maxzoom = cmp::min(maxzoom, layer.maxzoom(self.grid.maxzoom()));
+            for extent in ds.changed_extents(layer, since, self.grid.srid)? {
+                let limits = self.grid.tile_limits(extent, 0);
+                for zoom in layer_minzoom..=layer_maxzoom {
+                    let limit = &limits[zoom as usize];
+                    // Extent of points and axis-parallel lines may have no area
+                    for x in limit.minx..cmp::max(limit.maxx, limit.minx + 1) {
+                        for y in limit.miny..cmp::max(limit.maxy, limit.miny + 1) {
+                            tiles.insert((zoom, x, y));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(tiles)
+    }
+    /// Count features of all layers in tile without retrieving geometries.
+    /// Records `feature_count` and `count_ms` statistics. Returns total feature count.
+    pub fn count_tile_features(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        stats: &mut Statistics,
+    ) -> u64 {
+        let extent = self.grid.tile_extent(xtile, ytile, zoom);
+        let tileset = self
+            .get_tileset(tileset)
+            .map(|ts| ts.name.as_str())
+            .unwrap_or(tileset);
+        let mut total = 0;
+        for layer in self.get_tileset_layers(tileset) {
+            if zoom >= layer.minzoom() && zoom <= layer.maxzoom(self.grid.maxzoom()) {
+                let _permit = self.datasources.query_permit(&layer.datasource);
+                let now = Instant::now();
+                let num_features = self
+                    .ds(&layer)
+                    .unwrap()
+                    .count_features(tileset, &layer, &extent, zoom, &self.grid);
+                let elapsed = now.elapsed();
+                stats.add(
+                    format!("count_ms.{}.{}.{}", tileset, layer.name, zoom),
+                    elapsed.as_secs() * 1000 + elapsed.subsec_millis() as u64,
+                );
+                stats.add(
+                    format!("feature_count.{}.{}.{}", tileset, layer.name, zoom),
+                    num_features,
+                );
+                total += num_features;
+            }
+        }
+        total
+    }
+    /// Count tile request for layer access statistics
+    pub fn record_access(&self, tileset: &str, zoom: u8) {
+        if let Some(ts) = self.get_tileset(tileset) {
+            self.layer_access.add(&ts.name, zoom);
+        }
+    }
+    /// Time of the last data update of a layer as UTC timestamp (`updated_file` or `updated_sql`)
+    pub fn layer_updated(&self, tileset: &str, layer: &Layer) -> Option<String> {
+        if layer.updated_file.is_none() && layer.updated_sql.is_none() {
+            return None;
+        }
+        self.data_updated.get(tileset, &layer.name, || {
+            layer
+                .file_updated()
+                .or_else(|| self.ds(layer).and_then(|ds| ds.data_updated(layer)))
+        })
+    }
+    /// Most recent data update of all tileset layers
+    pub fn tileset_updated(&self, tileset: &str) -> Option<String> {
+        let ts = self.get_tileset(tileset)?;
+        ts.layers
+            .iter()
+            .filter_map(|layer| self.layer_updated(&ts.name, layer))
+            .max()
+    }
+    /// Tile requests of all layers within their zoom range
+    pub fn layer_access_report(&self) -> Vec<LayerAccess> {
+        let counts = self.layer_access.counts();
+        let mut report = Vec::new();
+        for ts in &self.tilesets {
+            for layer in &ts.layers {
+                let minzoom = cmp::max(ts.minzoom(), layer.minzoom());
+                let maxzoom = cmp::min(ts.maxzoom(), layer.maxzoom(22));
+                let requests: BTreeMap<u8, u64> = (minzoom..=maxzoom)
+                    .filter_map(|z| counts.get(&(ts.name.clone(), z)).map(|cnt| (z, *cnt)))
+                    .collect();
+                let requested_zoom = match (requests.keys().next(), requests.keys().last()) {
+                    (Some(min), Some(max)) => Some((*min, *max)),
+                    _ => None,
+                };
+                report.push(LayerAccess {
+                    tileset: ts.name.clone(),
+                    layer: layer.name.clone(),
+                    minzoom,
+                    maxzoom,
+                    total: requests.values().sum(),
+                    requests,
+                    requested_zoom,
+                    updated: self.layer_updated(&ts.name, layer),
+                });
+            }
+        }
+        report
+    }
+    /// Service metrics in Prometheus text format
+    pub fn prometheus_metrics(&self) -> String {
+        let mut lines = Vec::new();
+        lines.push("# HELP trex_oversized_tiles_total Number of tiles exceeding tile_size_warning");
+        lines.push("# TYPE trex_oversized_tiles_total counter");
+        let oversized = format!(
+            "trex_oversized_tiles_total {}",
+            self.size_budget.oversized_count()
+        );
+        lines.push(&oversized);
+        lines.push(
+            "# HELP trex_invalid_geometries_total Number of invalid or unparseable geometries",
+        );
+        lines.push("# TYPE trex_invalid_geometries_total counter");
+        let invalid: Vec<String> = self
+            .geometry_errors
+            .counts()
+            .iter()
+            .map(|((tileset, layer), count)| {
+                format!(
+                    "trex_invalid_geometries_total{{tileset=\"{}\",layer=\"{}\"}} {}",
+                    tileset, layer, count
+                )
+            })
+            .collect();
+        lines.extend(invalid.iter().map(|l| l.as_str()));
+        let circuits = self.layer_circuits.circuits();
+        lines.push("# HELP trex_layer_circuit_open Layer skipped after repeated query failures");
+        lines.push("# TYPE trex_layer_circuit_open gauge");
+        let open: Vec<String> = circuits
+            .iter()
+            .map(|((tileset, layer), circuit)| {
+                format!(
+                    "trex_layer_circuit_open{{tileset=\"{}\",layer=\"{}\"}} {}",
+                    tileset,
+                    layer,
+                    circuit.is_open() as u8
+                )
+            })
+            .collect();
+        lines.extend(open.iter().map(|l| l.as_str()));
+        lines.push(
+            "# HELP trex_layer_circuit_skipped_total Number of layer queries skipped by an open circuit",
+        );
+        lines.push("# TYPE trex_layer_circuit_skipped_total counter");
+        let skipped: Vec<String> = circuits
+            .iter()
+            .map(|((tileset, layer), circuit)| {
+                format!(
+                    "trex_layer_circuit_skipped_total{{tileset=\"{}\",layer=\"{}\"}} {}",
+                    tileset, layer, circuit.skipped
+                )
+            })
+            .collect();
+        lines.extend(skipped.iter().map(|l| l.as_str()));
+        let memory = format!(
+            "# HELP trex_memory_in_flight_bytes Approximate memory of tiles in rendering
+# TYPE trex_memory_in_flight_bytes gauge
+trex_memory_in_flight_bytes {}
+# HELP trex_memory_peak_bytes Highest approximate memory of tiles in rendering
+# TYPE trex_memory_peak_bytes gauge
+trex_memory_peak_bytes {}
+# HELP trex_tiles_in_flight Number of tiles in rendering
+# TYPE trex_tiles_in_flight gauge
+trex_tiles_in_flight {}
+# HELP trex_memory_tile_max_bytes Approximate memory of the largest rendered tile
+# TYPE trex_memory_tile_max_bytes gauge
+trex_memory_tile_max_bytes {}
+# HELP trex_memory_limit_bytes Cap of memory of tiles in rendering (memory_limit)
+# TYPE trex_memory_limit_bytes gauge
+trex_memory_limit_bytes {}
+# HELP trex_memory_limit_exceeded_total Number of tiles truncated because of memory_limit
+# TYPE trex_memory_limit_exceeded_total counter
+trex_memory_limit_exceeded_total {}",
+            self.memory.in_use(),
+            self.memory.peak(),
+            self.memory.tiles_in_flight(),
+            self.memory.largest_tile(),
+            self.memory.limit.unwrap_or(0),
+            self.memory.exceeded_count()
+        );
+        lines.push(&memory);
+        let seeding;
+        if self.seeding.is_started() {
+            seeding = format!(
+                "# HELP trex_seeding_tiles_total Number of tiles rendered while seeding
+# TYPE trex_seeding_tiles_total counter
+trex_seeding_tiles_total {}
+# HELP trex_seeding_failures_total Number of tiles which couldn't be written to the cache
+# TYPE trex_seeding_failures_total counter
+trex_seeding_failures_total {}
+# HELP trex_seeding_zoom Zoom level currently seeded
+# TYPE trex_seeding_zoom gauge
+trex_seeding_zoom {}
+# HELP trex_seeding_tiles_per_second Average number of rendered tiles per second
+# TYPE trex_seeding_tiles_per_second gauge
+trex_seeding_tiles_per_second {:.3}",
+                self.seeding.tiles_rendered.load(Ordering::Relaxed),
+                self.seeding.tiles_failed.load(Ordering::Relaxed),
+                self.seeding.current_zoom.load(Ordering::Relaxed),
+                self.seeding.throughput()
+            );
+            lines.push(&seeding);
+        }
+        lines.join("\n") + "\n"
+    }
+    /// Cache of tileset, if configured, or service cache
+    pub fn tileset_cache<'s>(&'s self, ts: &'s Tileset) -> &'s Tilecache {
+        ts.cache.as_ref().unwrap_or(&self.cache)
+    }
+    /// Check whether a cached tile exceeds the cache lifetime of the tileset volatility
+    fn cached_tile_expired(&self, ts: &Tileset, path: &str) -> bool {
+        match ts.cache_ttl() {
+            Some(ttl) => self
+                .tileset_cache(ts)
+                .age(path)
+                .is_some_and(|age| age.as_secs() >= ttl),
+            None => false,
+        }
+    }
+    /// Local cache file of tile at x, y, z, if it can be delivered without decoding
+    pub fn tile_cache_file(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        gzip: bool,
+    ) -> Option<String> {
+        let ts = self.get_tileset(tileset)?;
+        if !ts.is_cachable_at(zoom) {
+            return None;
+        }
+        let time = self.tile_time(ts, None);
+        let path = tile_cache_path(&ts.name, time.as_deref(), zoom, xtile, ytile);
+        if self.cached_tile_expired(ts, &path) {
+            return None;
+        }
+        let file = self.tileset_cache(ts).local_path(&path)?;
+        if local_tile_encoding(&file) == Some(served_encoding(gzip)) {
+            Some(file)
+        } else {
+            None
+        }
+    }
+    /// Digest stored with cached tile, if it matches the served encoding
+    pub fn cached_tile_digest(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        gzip: bool,
+    ) -> Option<String> {
+        if !self.content_digest {
+            return None;
+        }
+        let ts = self.get_tileset(tileset)?;
+        if !ts.is_cachable_at(zoom) {
+            return None;
+        }
+        let time = self.tile_time(ts, None);
+        let path = tile_cache_path(&ts.name, time.as_deref(), zoom, xtile, ytile);
+        if self.cached_tile_expired(ts, &path) {
+            return None;
+        }
+        // Digest is calculated from the stored tile data
+        let stored_encoding = match self.tileset_cache(ts).local_path(&path) {
+            Some(file) => local_tile_encoding(&file),
+            None if self.compression.cache_compressed => Some(TileEncoding::Gzip),
+            None => Some(TileEncoding::Identity),
+        };
+        if stored_encoding != Some(served_encoding(gzip)) {
+            return None;
+        }
+        // Digest is written after the tile, an older one belongs to a previous version
+        let cache = self.tileset_cache(ts);
+        if let (Some(tile_age), Some(digest_age)) =
+            (cache.age(&path), cache.age(&digest_path(&path)))
+        {
+            if digest_age > tile_age {
+                return None;
+            }
+        }
+        let mut digest = None;
+        cache.read(&digest_path(&path), |f| {
+            let mut data = String::new();
+            if f.read_to_string(&mut data).is_ok() {
+                digest = Some(data.trim().to_string());
+            }
+        });
+        digest
+    }
+    /// Fetch features of tile layers concurrently without blocking, for rendering with
+    /// `tile_cached_prefetched`. Only datasources with asynchronous retrieval (PostGIS) are
+    /// queried. Tiles served from cache or archive, layers of datasources with `max_queries`
+    /// and layers with an open circuit are not fetched.
+    pub async fn prefetch_features(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        options: &TileOptions,
+    ) -> PrefetchedFeatures {
+        let ts = match self.get_tileset(tileset) {
+            Some(ts) => ts,
+            None => return PrefetchedFeatures::new(),
+        };
+        // Reverse y for XYZ scheme
+        let y = if self.grid.srid == 3857 {
+            self.grid.ytile_from_xyz(ytile, zoom)
+        } else {
+            ytile
+        };
+        let time = self.tile_time(ts, options.time.as_deref());
+        let path = tile_cache_path(&ts.name, time.as_deref(), zoom, xtile, ytile);
+        let cachable = ts.is_cachable_at(zoom) && !options.authenticated;
+        let cached = cachable
+            && !options.refresh
+            && !self.cached_tile_expired(ts, &path)
+            && self.tileset_cache(ts).exists(&path);
+        if cached
+            || self.archives.contains_key(&ts.name)
+            || !self.tile_in_coverage(ts, xtile, y, zoom)
+        {
+            return PrefetchedFeatures::new();
+        }
+        let extent = self.grid.tile_extent(xtile, y, zoom);
+        let extent = &extent;
+        let time = time.as_deref();
+        let fetches = ts
+            .layers
+            .iter()
+            .filter(|layer| zoom >= layer.minzoom() && zoom <= layer.maxzoom(self.grid.maxzoom()))
+            .filter(|layer| {
+                !self.datasources.has_query_limit(&layer.datasource)
+                    && !layer.st_asmvt()
+                    && !self.layer_circuits.is_open(&ts.name, &layer.name)
+            })
+            .map(|layer| async move {
+                let features = self
+                    .ds(layer)?
+                    .fetch_features(&ts.name, layer, extent, zoom, &self.grid, time)
+                    .await?;
+                Some((layer.name.clone(), features))
+            });
+        futures_util::future::join_all(fetches)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
     /// Fetch or create vector tile from input at x, y, z
     pub fn tile_cached(
         &self,
@@ -130,6 +1656,41 @@ impl MvtService {
         zoom: u8,
         gzip: bool,
         stats: Option<&mut Statistics>,
+    ) -> Option<Vec<u8>> {
+        let options = TileOptions::default();
+        self.tile_cached_with_options(tileset, xtile, ytile, zoom, gzip, stats, &options)
+    }
+    /// Fetch or create vector tile with request dependent options.
+    /// Tiles with protected attributes bypass the cache.
+    #[allow(clippy::too_many_arguments)]
+    pub fn tile_cached_with_options(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        gzip: bool,
+        stats: Option<&mut Statistics>,
+        options: &TileOptions,
+    ) -> Option<Vec<u8>> {
+        let prefetched = PrefetchedFeatures::new();
+        self.tile_cached_prefetched(
+            tileset, xtile, ytile, zoom, gzip, stats, options, prefetched,
+        )
+    }
+    /// Fetch or create vector tile like `tile_cached_with_options`, using features
+    /// of `prefetch_features` for rendering.
+    #[allow(clippy::too_many_arguments)]
+    pub fn tile_cached_prefetched(
+        &self,
+        tileset: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        gzip: bool,
+        stats: Option<&mut Statistics>,
+        options: &TileOptions,
+        mut prefetched: PrefetchedFeatures,
     ) -> Option<Vec<u8>> {
         // Reverse y for XYZ scheme (TODO: protocol instead of CRS dependent?)
         let y = if self.grid.srid == 3857 {
@@ -137,19 +1698,22 @@ impl MvtService {
         } else {
             ytile
         };
-        let path = format!("{}/{}/{}/{}.pbf", tileset, zoom, xtile, ytile);
-
         let ts = self
             .get_tileset(tileset)
             .expect(&format!("Tileset '{}' not found", tileset));
+        let time = self.tile_time(ts, options.time.as_deref());
+        let path = tile_cache_path(&ts.name, time.as_deref(), zoom, xtile, ytile);
 
-        if zoom < ts.minzoom() || zoom > ts.maxzoom() {
+        if !self.tile_in_coverage(ts, xtile, y, zoom) {
+            debug!("{} - Skipping tile outside of tileset coverage", path);
             return None;
         }
 
+        // Tiles with protected attributes must not end up in the public cache
+        let cachable = ts.is_cachable_at(zoom) && !options.authenticated;
         let mut tile: Option<Vec<u8>> = None;
-        if ts.is_cachable_at(zoom) {
-            self.cache.read(&path, |f| {
+        if cachable && !options.refresh && !self.cached_tile_expired(ts, &path) {
+            self.tileset_cache(ts).read(&path, |f| {
                 let mut data = Vec::new();
                 let _ = f.read_to_end(&mut data);
                 tile = Some(data);
@@ -162,17 +1726,98 @@ impl MvtService {
         }
 
         // Return tile from cache
-        if let Some(tilegz) = tile {
-            return Some(Tile::tile_content(tilegz, gzip));
+        if let Some(data) = tile {
+            return Some(self.compression.tile_content(data, gzip));
+        }
+        if let Some(ref queue) = self.prerender {
+            if cachable
+                && options.interactive
+                && !options.refresh
+                && !self.archives.contains_key(&ts.name)
+            {
+                queue.record_miss(
+                    &ts.name,
+                    options.time.as_deref(),
+                    xtile,
+                    ytile,
+                    zoom,
+                    ts.maxzoom(),
+                );
+            }
         }
 
-        // Request tile and write into cache
-        let mvt_tile = self.tile(tileset, xtile, y, zoom, stats);
+        // Request tile and write into cache. Concurrent requests share the encoded tile
+        // and only compress it for their encoding.
+        let key = format!(
+            "{}{}{}",
+            path,
+            if options.authenticated { "?auth" } else { "" },
+            if options.refresh { "?refresh" } else { "" }
+        );
+        let data = self.renderings.coalesce(&key, || {
+            if let Some(archive) = self.archives.get(&ts.name) {
+                return self.archive_tile(ts, archive, &path, xtile, y, zoom, cachable);
+            }
+            let lease = self.render_lease.as_ref().filter(|_| cachable);
+            let leased = match lease.map(|lease| lease.acquire(self.tileset_cache(ts), &path)) {
+                Some(LeaseWait::Acquired) => true,
+                Some(LeaseWait::Rendered(data)) => return Some(data),
+                Some(LeaseWait::TimedOut) if lease.is_some_and(|l| l.placeholder) => {
+                    debug!(
+                        "{} - Serving empty tile while rendered by other instance",
+                        path
+                    );
+                    return None;
+                }
+                Some(LeaseWait::TimedOut) | None => false,
+            };
+            let data = self.render_cached_tile(
+                ts,
+                tileset,
+                &path,
+                xtile,
+                ytile,
+                y,
+                zoom,
+                cachable,
+                stats,
+                options,
+                &mut prefetched,
+            );
+            if leased {
+                lease.unwrap().release(self.tileset_cache(ts), &path);
+            }
+            data
+        });
+        data.map(|data| self.compression.tile_content(data, gzip))
+    }
+    /// Render tile and write it into cache
+    #[allow(clippy::too_many_arguments)]
+    fn render_cached_tile(
+        &self,
+        ts: &Tileset,
+        tileset: &str,
+        path: &str,
+        xtile: u32,
+        ytile: u32,
+        y: u32,
+        zoom: u8,
+        cachable: bool,
+        stats: Option<&mut Statistics>,
+        options: &TileOptions,
+        prefetched: &mut PrefetchedFeatures,
+    ) -> Option<Vec<u8>> {
+        let mvt_tile =
+            self.tile_with_prefetched(tileset, xtile, y, zoom, stats, options, prefetched);
+        self.size_budget
+            .check(&mvt_tile, tileset, xtile, ytile, zoom);
         // Spec: A Vector Tile SHOULD contain at least one layer.
         if mvt_tile.get_layers().len() > 0 {
-            let tilegz = Tile::tile_bytevec_gz(&mvt_tile);
-            if ts.is_cachable_at(zoom) {
-                if let Err(ioerr) = self.cache.write(&path, &tilegz) {
+            let data = self.compression.cache_bytevec(&mvt_tile);
+            if cachable {
+                if let Err(ioerr) =
+                    write_cached_tile(self.tileset_cache(ts), path, &data, self.content_digest)
+                {
                     error!("Error writing {}: {}", path, ioerr);
                 }
             } else {
@@ -181,18 +1826,54 @@ impl MvtService {
                     ts.name, zoom
                 );
             }
-            Some(Tile::tile_content(tilegz, gzip))
+            Some(data)
         } else {
             // We don't save empty tiles
             // When serving from file cache return 204 No Content
             // Nginx: try_files $uri = 204;
             debug!("{} - Skipping empty tile", path);
+            if options.refresh && cachable && self.tileset_cache(ts).exists(path) {
+                // Remove stale tile
+                if let Err(ioerr) = remove_cached_tile(self.tileset_cache(ts), path) {
+                    error!("Error removing {}: {}", path, ioerr);
+                }
+            }
             None
         }
     }
-    fn progress_bar(&self, msg: &str, limits: &ExtentInt) -> ProgressBar<Stdout> {
-        let tiles =
-            (limits.maxx as u64 - limits.minx as u64) * (limits.maxy as u64 - limits.miny as u64);
+    /// Stored tile of archive at x, y, z in TMS adressing scheme
+    #[allow(clippy::too_many_arguments)]
+    fn archive_tile(
+        &self,
+        ts: &Tileset,
+        archive: &TileArchive,
+        path: &str,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        cachable: bool,
+    ) -> Option<Vec<u8>> {
+        let data = match archive.tile(xtile, ytile, zoom) {
+            Ok(Some(data)) => data,
+            Ok(None) => {
+                debug!("{} - Tile not found in {}", path, archive.path());
+                return None;
+            }
+            Err(e) => {
+                error!("{} - {}", path, e);
+                return None;
+            }
+        };
+        if cachable {
+            if let Err(ioerr) =
+                write_cached_tile(self.tileset_cache(ts), path, &data, self.content_digest)
+            {
+                error!("Error writing {}: {}", path, ioerr);
+            }
+        }
+        Some(data)
+    }
+    fn progress_bar(&self, msg: &str, tiles: u64) -> ProgressBar<Stdout> {
         let mut pb = ProgressBar::new(tiles);
         pb.message(msg);
         //pb.set_max_refresh_rate(Some(Duration::from_millis(200)));
@@ -222,28 +1903,36 @@ impl MvtService {
             }
         }
     }
-    /// Seed tile cache
+    /// Seed tile cache. With `since`, only tiles affected by changes since this timestamp are re-rendered.
+    #[allow(clippy::too_many_arguments)]
     pub fn generate(
         &self,
         tileset_name: Option<&str>,
         minzoom: Option<u8>,
         maxzoom: Option<u8>,
         extent: Option<Extent>,
-        nodes: Option<u8>,
-        nodeno: Option<u8>,
+        partition: &WorkPartition,
         progress: bool,
         overwrite: bool,
         extent_srid: Option<i32>,
+        attr_stats: bool,
+        since: Option<&str>,
     ) {
         let rt = tokio::runtime::Runtime::new().expect("Couldn't initialize tokio runtime");
         self.init_cache();
-        let nodes = nodes.unwrap_or(1) as u64;
-        let nodeno = nodeno.unwrap_or(0) as u64;
+        self.seeding.start();
 
         for tileset in &self.tilesets {
             if tileset_name.is_some() && tileset_name.unwrap() != &tileset.name {
                 continue;
             }
+            if self.archives.contains_key(&tileset.name) {
+                info!(
+                    "Tileset '{}' is served from a tile archive - skipping",
+                    tileset.name
+                );
+                continue;
+            }
             if progress {
                 println!("Generating tileset '{}'...", tileset.name);
             }
@@ -281,54 +1970,131 @@ impl MvtService {
             if maxzoom.is_some() && maxzoom.unwrap() > ts_maxzoom {
                 warn!("Skipping zoom levels >{}", ts_maxzoom);
             }
+            let tileset_attr_stats = if attr_stats {
+                Some(Arc::new(Mutex::new(AttributeStatistics::new())))
+            } else {
+                None
+            };
+            let (tiles, level_tiles): (TileIter, Vec<u64>) = if let Some(since) = since {
+                let mut changed = match self.changed_tiles(tileset, since, ts_minzoom, ts_maxzoom) {
+                    Ok(changed) => changed,
+                    Err(e) => {
+                        error!("{}", e);
+                        continue;
+                    }
+                };
+                // Restrict to generation extent
+                changed.retain(|(z, x, y)| {
+                    let limit = &limits[*z as usize];
+                    *x >= limit.minx && *x < limit.maxx && *y >= limit.miny && *y < limit.maxy
+                });
+                info!(
+                    "Tileset '{}': {} tiles affected by changes since {}",
+                    tileset.name,
+                    changed.len(),
+                    since
+                );
+                let mut level_tiles = vec![0; limits.len()];
+                for (zoom, _, _) in &changed {
+                    level_tiles[*zoom as usize] += 1;
+                }
+                (Box::new(changed.into_iter()), level_tiles)
+            } else {
+                let level_tiles = limits
+                    .iter()
+                    .map(|l| (l.maxx as u64 - l.minx as u64) * (l.maxy as u64 - l.miny as u64))
+                    .collect();
+                (
+                    Box::new(GridIterator::new(ts_minzoom, ts_maxzoom, limits)),
+                    level_tiles,
+                )
+            };
             rt.block_on(self.generate_tileset(
-                limits,
+                tiles,
+                level_tiles,
                 &tileset.name,
-                ts_minzoom,
-                ts_maxzoom,
-                nodes,
-                nodeno,
+                partition,
                 progress,
-                overwrite,
+                overwrite || since.is_some(),
+                since.is_some(),
+                tileset_attr_stats.clone(),
             ));
+            if let Some(attr_stats) = tileset_attr_stats {
+                let attr_stats = attr_stats.lock().unwrap();
+                if progress {
+                    println!("");
+                }
+                info!("Attribute statistics of tileset '{}':", tileset.name);
+                for line in attr_stats.report() {
+                    info!("{}", line);
+                }
+                for (layer, attr) in attr_stats.high_cardinality_attributes() {
+                    warn!(
+                        "Layer '{}': attribute '{}' has a high cardinality and increases tile sizes",
+                        layer, attr
+                    );
+                }
+            }
         }
         if progress {
             println!("");
         }
+        if self.size_budget.oversized_count() > 0 {
+            warn!(
+                "{} tiles exceeded the tile size limit",
+                self.size_budget.oversized_count()
+            );
+        }
     }
     /// Seed tile cache for tileset
-    async fn generate_tileset(
+    #[allow(clippy::too_many_arguments)]
+    async fn generate_tileset<I>(
         &self,
-        limits: Vec<ExtentInt>,
-        tileset_name: &String,
-        ts_minzoom: u8,
-        ts_maxzoom: u8,
-        nodes: u64,
-        nodeno: u64,
+        tiles: I,
+        level_tiles: Vec<u64>,
+        tileset_name: &str,
+        partition: &WorkPartition,
         progress: bool,
         overwrite: bool,
-    ) {
+        incremental: bool,
+        attr_stats: Option<Arc<Mutex<AttributeStatistics>>>,
+    ) where
+        I: Iterator<Item = (u8, u32, u32)>,
+    {
         // Keep a queue of tasks waiting for parallel async execution (size >= #cores).
         // libspatialite has a max connection limit of 64 for now. libspatialite (4.4.0) when
         // compiled on top of GEOS 3.5.0 is able to support an arbitrary number of threads
         let task_queue_size = cmp::min(num_cpus::get() * 2, 64);
         let mut tasks = Vec::with_capacity(task_queue_size);
-        let griditer = GridIterator::new(ts_minzoom, ts_maxzoom, limits.clone());
-        let mut tileno: u64 = 0;
+        let mut selector = WorkSelector::new(partition, tileset_name);
         let mut pb = ProgressBar::new(0);
-        let mut pb_z = !ts_minzoom;
-        for (zoom, xtile, ytile) in griditer {
-            if progress && zoom != pb_z {
-                pb_z = zoom;
-                let ref limit = limits[zoom as usize];
-                debug!("level {}: {:?}", zoom, limit);
-                pb = self.progress_bar(&format!("Level {}: ", zoom), &limit);
+        let mut pb_z = None;
+        let tileset = self.get_tileset(tileset_name).unwrap();
+        let tileset_cache = self.tileset_cache(tileset);
+        let time = self.tile_time(tileset, None);
+        for (zoom, xtile, ytile) in tiles {
+            self.seeding
+                .current_zoom
+                .store(zoom as u64, Ordering::Relaxed);
+            if progress && pb_z != Some(zoom) {
+                pb_z = Some(zoom);
+                pb = self.progress_bar(&format!("Level {}: ", zoom), level_tiles[zoom as usize]);
                 pb.tick();
             }
 
-            let skip = tileno % nodes != nodeno;
-            tileno += 1;
-            if skip {
+            let selected = selector.selected(zoom);
+            if selector.has_pending() {
+                // Tiles of finished work queue blocks are written before recording their completion
+                futures_util::future::join_all(tasks.drain(..)).await;
+                selector.complete_pending();
+            }
+            if !selected {
+                continue;
+            }
+            if !self.tile_in_coverage(tileset, xtile, ytile, zoom) {
+                if progress {
+                    pb.inc();
+                }
                 continue;
             }
 
@@ -338,24 +2104,40 @@ impl MvtService {
             } else {
                 ytile
             };
-            let path = format!("{}/{}/{}/{}.pbf", tileset_name, zoom, xtile, y);
+            let path = tile_cache_path(tileset_name, time.as_deref(), zoom, xtile, y);
 
-            if overwrite || !self.cache.exists(&path) {
+            if overwrite || !tileset_cache.exists(&path) {
+                // Stale tiles of changed features have to be replaced even if empty now
+                let replace_empty = incremental && tileset_cache.exists(&path);
                 // Entry doesn't exist, or overwrite is forced, so generate it
                 let svc = self.clone();
-                let cache = self.cache.clone();
-                let tileset_name = tileset_name.clone();
+                let cache = tileset_cache.clone();
+                let compression = self.compression.clone();
+                let attr_stats = attr_stats.clone();
+                let seeding = self.seeding.clone();
+                let content_digest = self.content_digest;
+                let tileset_name = tileset_name.to_string();
                 tasks.push(task::spawn(async move {
                     // rust-postgres starts its own Tokio runtime
                     // without spawn_blocking or block_in_place we get 'Cannot start a runtime from within a runtime'
                     let mvt_tile = task::spawn_blocking(move || {
-                        svc.tile(&tileset_name, xtile as u32, ytile as u32, zoom, None)
+                        let mvt_tile =
+                            svc.tile(&tileset_name, xtile as u32, ytile as u32, zoom, None);
+                        svc.size_budget
+                            .check(&mvt_tile, &tileset_name, xtile, y, zoom);
+                        mvt_tile
                     })
                     .await
                     .unwrap();
-                    if mvt_tile.get_layers().len() > 0 {
-                        let tilegz = Tile::tile_bytevec_gz(&mvt_tile);
-                        if let Err(ioerr) = cache.write(&path, &tilegz) {
+                    if let Some(attr_stats) = attr_stats {
+                        attr_stats.lock().unwrap().add_tile(&mvt_tile);
+                    }
+                    seeding.tiles_rendered.fetch_add(1, Ordering::Relaxed);
+                    if mvt_tile.get_layers().len() > 0 || replace_empty {
+                        let data = compression.cache_bytevec(&mvt_tile);
+                        if let Err(ioerr) = write_cached_tile(&cache, &path, &data, content_digest)
+                        {
+                            seeding.tiles_failed.fetch_add(1, Ordering::Relaxed);
                             error!("Error writing {}: {}", path, ioerr);
                         }
                     }
@@ -371,31 +2153,83 @@ impl MvtService {
         }
         // Finish remaining tasks
         futures_util::future::join_all(tasks).await;
+        selector.finish();
+    }
+    /// Start background threads rendering tiles around cache misses of interactive requests
+    pub fn start_prerender(&mut self, queue: PrerenderQueue) {
+        if self
+            .tilesets
+            .iter()
+            .all(|ts| matches!(self.tileset_cache(ts), Tilecache::Nocache(_)))
+        {
+            warn!("Pre-rendering disabled without tile cache");
+            return;
+        }
+        info!(
+            "Pre-rendering tiles around cache misses (radius {}, zoom depth {}) with {} threads",
+            queue.radius, queue.zoom_depth, queue.threads
+        );
+        self.prerender = Some(queue.clone());
+        for _ in 0..queue.threads {
+            let service = self.clone();
+            let queue = queue.clone();
+            let _ = std::thread::Builder::new()
+                .name("t-rex-prerender".to_string())
+                .spawn(move || loop {
+                    let tile = queue.pop();
+                    service.prerender_tile(&tile);
+                });
+        }
+    }
+    /// Render queued tile into cache, if it isn't cached yet
+    pub fn prerender_tile(&self, tile: &PrerenderTile) -> bool {
+        if self
+            .check_tile_request(&tile.tileset, tile.x, tile.y, tile.z)
+            .is_err()
+        {
+            return false;
+        }
+        let cachable = self
+            .get_tileset(&tile.tileset)
+            .is_some_and(|ts| ts.is_cachable_at(tile.z));
+        if !cachable {
+            return false;
+        }
+        debug!(
+            "Pre-rendering tile {}/{}/{}/{}",
+            tile.tileset, tile.z, tile.x, tile.y
+        );
+        let options = TileOptions {
+            time: tile.time.clone(),
+            ..Default::default()
+        };
+        self.tile_cached_with_options(&tile.tileset, tile.x, tile.y, tile.z, false, None, &options);
+        true
     }
     pub fn init_cache(&self) {
         info!("{}", &self.cache.info());
         for tileset in &self.tilesets {
+            let cache = self.tileset_cache(tileset);
+            if tileset.cache.is_some() {
+                info!("Tileset '{}': {}", tileset.name, cache.info());
+            }
             // :tileset.json
-            let json = self
-                .get_tilejson(&self.cache.baseurl(), &tileset.name)
-                .unwrap();
-            let _ = self.cache.write(
+            let json = self.get_tilejson(&cache.baseurl(), &tileset.name).unwrap();
+            let _ = cache.write(
                 &format!("{}.json", &tileset.name),
                 &serde_json::to_vec(&json).unwrap(),
             );
 
             // :tileset.style.json
-            let json = self
-                .get_stylejson(&self.cache.baseurl(), &tileset.name)
-                .unwrap();
-            let _ = self.cache.write(
+            let json = self.get_stylejson(&cache.baseurl(), &tileset.name).unwrap();
+            let _ = cache.write(
                 &format!("{}.style.json", &tileset.name),
                 &serde_json::to_vec(&json).unwrap(),
             );
 
             // :tileset/metadata.json
             let json = self.get_mbtiles_metadata(&tileset.name).unwrap();
-            let _ = self.cache.write(
+            let _ = cache.write(
                 &format!("{}/metadata.json", &tileset.name),
                 &serde_json::to_vec(&json).unwrap(),
             );
@@ -418,6 +2252,7 @@ impl MvtService {
         maxzoom: Option<u8>,
         points: Vec<f64>,
         progress: bool,
+        count_only: bool,
     ) -> Statistics {
         let mut stats = Statistics::new();
         for tileset in &self.tilesets {
@@ -456,17 +2291,31 @@ impl MvtService {
                     debug!("level {}: {:?}", zoom, limit);
                     let xtile = limit.minx;
                     let ytile = limit.miny;
-                    let mvt_tile = self.tile(
-                        &tileset.name,
-                        xtile as u32,
-                        ytile as u32,
-                        zoom,
-                        Some(&mut stats),
-                    );
-                    stats.add(
-                        format!("tile_bytes.{}.total.{}", &tileset.name, zoom),
-                        Tile::size(&mvt_tile) as u64,
-                    );
+                    if count_only {
+                        let count = self.count_tile_features(
+                            &tileset.name,
+                            xtile as u32,
+                            ytile as u32,
+                            zoom,
+                            &mut stats,
+                        );
+                        stats.add(
+                            format!("feature_count.{}.total.{}", &tileset.name, zoom),
+                            count,
+                        );
+                    } else {
+                        let mvt_tile = self.tile(
+                            &tileset.name,
+                            xtile as u32,
+                            ytile as u32,
+                            zoom,
+                            Some(&mut stats),
+                        );
+                        stats.add(
+                            format!("tile_bytes.{}.total.{}", &tileset.name, zoom),
+                            Tile::size(&mvt_tile) as u64,
+                        );
+                    }
                     if progress {
                         pb.inc();
                     }
@@ -478,6 +2327,20 @@ impl MvtService {
         }
         stats
     }
+    /// Lowest zoom level with less than MAX_FEATURES_PER_TILE features per tile on average
+    pub fn suggested_minzoom(&self, extent: &Extent, feature_count: u64) -> u8 {
+        const MAX_FEATURES_PER_TILE: u64 = 1000;
+        let limits = self
+            .grid
+            .tile_limits(self.extent_from_input_extent(extent, None), 0);
+        limits
+            .iter()
+            .position(|limit| {
+                let tiles = (limit.maxx - limit.minx) as u64 * (limit.maxy - limit.miny) as u64;
+                feature_count <= tiles.max(1) * MAX_FEATURES_PER_TILE
+            })
+            .unwrap_or(limits.len().saturating_sub(1)) as u8
+    }
     fn gen_layer_runtime_config(&self, layer: &Layer, grid_srid: i32) -> String {
         let ds = self.ds(layer).unwrap();
         let mut lines = vec!["\n[[tileset]]".to_string()];
@@ -497,6 +2360,15 @@ impl MvtService {
                 r#"extent = [{:.5}, {:.5}, {:.5}, {:.5}]"#,
                 ext.minx, ext.miny, ext.maxx, ext.maxy
             ));
+            if let &Datasource::Postgis(ref pg) = ds {
+                if let Some(count) = pg.estimate_feature_count(layer) {
+                    let minzoom = self.suggested_minzoom(&ext, count as u64);
+                    lines.push(format!(
+                        "#minzoom = {} # suggested for about {} features",
+                        minzoom, count
+                    ));
+                }
+            }
         } else {
             lines.push("#extent = [-180.0,-90.0,180.0,90.0]".to_string());
         }
@@ -524,18 +2396,83 @@ impl<'a> Config<'a, ApplicationCfg> for MvtService {
     fn from_config(config: &ApplicationCfg) -> Result<Self, String> {
         let datasources = Datasources::from_config(config)?;
         let grid = Grid::from_config(&config.grid)?;
-        let tilesets = config
-            .tilesets
-            .iter()
-            .map(|ts_cfg| Tileset::from_config(ts_cfg).unwrap())
-            .collect();
+        let fail_on_invalid = config.service.mvt.fail_on_invalid.unwrap_or(false);
+        let mut tilesets = Vec::new();
+        let mut disabled = Vec::new();
+        let mut archives = HashMap::new();
+        for ts_cfg in &config.tilesets {
+            let loaded = load_tileset(ts_cfg, config, &datasources, &mut disabled).and_then(
+                |mut tileset| {
+                    if let Some(archive) = load_archive(&mut tileset, &grid)? {
+                        archives.insert(tileset.name.clone(), Arc::new(archive));
+                    }
+                    Ok(tileset)
+                },
+            );
+            match loaded {
+                Ok(tileset) => tilesets.push(tileset),
+                Err(error) => disabled.push(DisabledConfig {
+                    tileset: ts_cfg.name.clone(),
+                    layer: None,
+                    error,
+                }),
+            }
+        }
+        if let Some(first) = disabled.first() {
+            if fail_on_invalid {
+                return Err(first.error.clone());
+            }
+        }
+        for item in &disabled {
+            match item.layer {
+                Some(ref layer) => error!(
+                    "Tileset '{}': layer '{}' disabled - {}",
+                    item.tileset, layer, item.error
+                ),
+                None => error!("Tileset '{}' disabled - {}", item.tileset, item.error),
+            }
+        }
         let cache = Tilecache::from_config(&config)?;
-        Ok(MvtService {
+        let compression = TileCompression::from_config(&config.service.mvt)?;
+        let size_budget = TileSizeBudget::new(config.service.mvt.tile_size_warning);
+        let memory = MemoryUsage::new(config.service.mvt.memory_limit);
+        let render_lease = match config.cache.as_ref().and_then(|c| c.lease.as_ref()) {
+            Some(lease_cfg) => Some(RenderLease::from_config(lease_cfg)?),
+            None => None,
+        };
+        let service = MvtService {
             datasources,
             grid,
+            grid_name: config.grid.name().to_string(),
             tilesets,
             cache,
-        })
+            compression,
+            size_budget,
+            memory,
+            geometry_errors: GeometryErrors::default(),
+            layer_circuits: LayerCircuits::new(
+                config.service.mvt.layer_failure_threshold,
+                config.service.mvt.layer_failure_cooldown,
+            ),
+            layer_access: LayerAccessStats::default(),
+            data_updated: DataUpdated::default(),
+            crawl_detector: CrawlDetector::default(),
+            seeding: SeedingStats::default(),
+            content_digest: config.service.mvt.content_digest.unwrap_or(false),
+            deterministic: config.service.mvt.deterministic.unwrap_or(false),
+            coverage: HashMap::new(),
+            aliases: TilesetAliases::default(),
+            disabled,
+            renderings: TileRenderings::default(),
+            archives,
+            render_lease,
+            prerender: None,
+            record: None,
+        };
+        for (alias, tileset) in &config.service.mvt.aliases {
+            service.set_alias(alias, tileset)?;
+        }
+        Ok(service)
     }
     fn gen_config() -> String {
         let mut config = String::new();
@@ -561,6 +2498,61 @@ impl<'a> Config<'a, ApplicationCfg> for MvtService {
     }
 }
 
+/// Tileset with valid layers. Invalid layers are added to `disabled`.
+fn load_tileset(
+    ts_cfg: &TilesetCfg,
+    config: &ApplicationCfg,
+    datasources: &Datasources,
+    disabled: &mut Vec<DisabledConfig>,
+) -> Result<Tileset, String> {
+    let mut ts_cfg = ts_cfg.with_sql_snippets(&config.sql_snippets)?;
+    let tileset_name = ts_cfg.name.clone();
+    ts_cfg.layers.retain(|layer_cfg| {
+        let result = Layer::from_config(layer_cfg).and_then(|layer| {
+            let ds = if layer.datasource.is_some() || datasources.default().is_some() {
+                datasources.datasource(&layer.datasource)
+            } else {
+                None
+            };
+            match ds {
+                None => Err(format!("Datasource of layer `{}` not found", layer.name)),
+                // Ignoring a filter would publish rows which are meant to be hidden
+                Some(ds) if layer.filter.is_some() && !ds.supports_filter() => Err(format!(
+                    "Layer `{}`: filter not supported by datasource",
+                    layer.name
+                )),
+                Some(_) => Ok(()),
+            }
+        });
+        match result {
+            Ok(()) => true,
+            Err(error) => {
+                disabled.push(DisabledConfig {
+                    tileset: tileset_name.clone(),
+                    layer: Some(layer_cfg.name.clone()),
+                    error,
+                });
+                false
+            }
+        }
+    });
+    Tileset::from_config(&ts_cfg)
+}
+
+/// Open MBTiles or PMTiles archive of tileset. Zoom range, extent, center and attribution
+/// default to the archive metadata.
+fn load_archive(tileset: &mut Tileset, grid: &Grid) -> Result<Option<TileArchive>, String> {
+    let archive = match TileArchive::open(tileset)? {
+        Some(archive) => archive,
+        None => return Ok(None),
+    };
+    if grid.srid != 3857 {
+        return Err("Tile archives require a Web Mercator grid".to_string());
+    }
+    archive.apply_metadata(tileset);
+    Ok(Some(archive))
+}
+
 const TOML_SERVICES: &'static str = r#"# t-rex configuration
 
 [service.mvt]
@@ -0,0 +1,280 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use crate::mvt_service::MvtService;
+use serde_json;
+
+type JsonResult = Result<serde_json::Value, serde_json::error::Error>;
+
+fn get_json(summary: &str, params: serde_json::Value) -> serde_json::Value {
+    json!({
+        "get": {
+            "summary": summary,
+            "parameters": params,
+            "responses": {
+                "200": {
+                    "description": "JSON document",
+                    "content": { "application/json": { "schema": { "type": "object" } } }
+                }
+            }
+        }
+    })
+}
+
+impl MvtService {
+    /// OpenAPI 3 description of the HTTP API
+    pub fn get_openapi(&self, baseurl: &str, viewer: bool) -> JsonResult {
+        let mut tileset_names: Vec<&String> = self.tilesets.iter().map(|ts| &ts.name).collect();
+        tileset_names.sort();
+        let tileset_param = json!({
+            "name": "tileset",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string", "enum": tileset_names }
+        });
+        let zxy_params: Vec<serde_json::Value> = ["z", "x", "y"]
+            .iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer", "minimum": 0 }
+                })
+            })
+            .collect();
+        let mut tile_params = vec![tileset_param.clone()];
+        tile_params.extend(zxy_params.clone());
+        let mut time_tile_params = vec![
+            tileset_param.clone(),
+            json!({ "name": "time", "in": "path", "required": true, "schema": { "type": "string" } }),
+        ];
+        time_tile_params.extend(zxy_params);
+
+        let mut paths = json!({
+            "/index.json": get_json("Service metadata", json!([])),
+            "/catalog": get_json("Tileset catalog with tile URL templates and endpoint links", json!([{
+                "name": "tag",
+                "in": "query",
+                "required": false,
+                "description": "Only list tilesets with this tag",
+                "schema": { "type": "string" }
+            }])),
+            "/fontstacks.json": get_json("Available font stacks", json!([])),
+            "/grids/{name}.json": get_json(
+                "Tile grid with SRID, extent, origin and resolutions per zoom level",
+                json!([{ "name": "name", "in": "path", "required": true,
+                         "schema": { "type": "string" } }])
+            ),
+            "/metrics": {
+                "get": {
+                    "summary": "Service metrics in Prometheus text format",
+                    "responses": {
+                        "200": {
+                            "description": "Metrics",
+                            "content": { "text/plain": { "schema": { "type": "string" } } }
+                        }
+                    }
+                }
+            },
+            "/{tileset}.json": get_json("TileJSON 2.0 description", json!([tileset_param])),
+            "/{tileset}.style.json": get_json("Mapbox GL style", json!([tileset_param])),
+            "/{tileset}/metadata.json": get_json("MBTiles metadata", json!([tileset_param])),
+            "/{tileset}/{layer}/metadata.json": get_json(
+                "Layer queries, extent and approximate row count",
+                json!([tileset_param, { "name": "layer", "in": "path", "required": true,
+                                        "schema": { "type": "string" } }])
+            ),
+            "/{tileset}/{layer}/sample.json": {
+                "get": {
+                    "summary": "Raw features of layer as GeoJSON for exploring attribute values",
+                    "parameters": [
+                        tileset_param,
+                        { "name": "layer", "in": "path", "required": true,
+                          "schema": { "type": "string" } },
+                        { "name": "bbox", "in": "query",
+                          "description": "WGS84 extent minx,miny,maxx,maxy (default: tileset extent)",
+                          "schema": { "type": "string" } },
+                        { "name": "limit", "in": "query",
+                          "description": "Number of features (default 10, max 100)",
+                          "schema": { "type": "integer" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "GeoJSON FeatureCollection",
+                            "content": { "application/geo+json": {} }
+                        },
+                        "400": { "description": "Invalid extent" },
+                        "404": { "description": "Unknown tileset or layer" }
+                    }
+                }
+            },
+            "/{tileset}/package.pmtiles": {
+                "get": {
+                    "summary": "PMTiles package for offline use",
+                    "description": "Requires the admin API token as bearer token",
+                    "parameters": [
+                        tileset_param,
+                        { "name": "bbox", "in": "query", "required": true,
+                          "description": "WGS84 extent minx,miny,maxx,maxy",
+                          "schema": { "type": "string" } },
+                        { "name": "minzoom", "in": "query", "schema": { "type": "integer" } },
+                        { "name": "maxzoom", "in": "query", "schema": { "type": "integer" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "PMTiles v3 archive",
+                            "content": {
+                                "application/vnd.pmtiles": {
+                                    "schema": { "type": "string", "format": "binary" }
+                                }
+                            }
+                        },
+                        "400": { "description": "Invalid extent or package too large" },
+                        "401": { "description": "Missing or invalid admin token" },
+                        "404": { "description": "Unknown tileset or admin API disabled" }
+                    }
+                }
+            },
+            "/{tileset}/tiles": {
+                "post": {
+                    "summary": "Multiple vector tiles as tar archive",
+                    "parameters": [tileset_param],
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": {
+                            "type": "object",
+                            "properties": { "tiles": {
+                                "type": "array",
+                                "description": "List of [z, x, y] coordinates",
+                                "items": { "type": "array", "items": { "type": "integer" } }
+                            } }
+                        } } }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Tar archive with entries {z}/{x}/{y}.pbf",
+                            "content": {
+                                "application/x-tar": {
+                                    "schema": { "type": "string", "format": "binary" }
+                                }
+                            }
+                        },
+                        "413": { "description": "Too many tiles requested" }
+                    }
+                }
+            },
+            "/{tileset}/{z}/{x}/{y}.pbf": {
+                "get": {
+                    "summary": "Mapbox Vector Tile",
+                    "parameters": tile_params,
+                    "responses": {
+                        "200": {
+                            "description": "Vector tile",
+                            "content": {
+                                "application/x-protobuf": {
+                                    "schema": { "type": "string", "format": "binary" }
+                                }
+                            }
+                        },
+                        "204": { "description": "Empty tile" },
+                        "400": { "description": "Tile coordinates outside of grid" },
+                        "403": { "description": "Referrer not allowed for tileset" },
+                        "404": { "description": "Tile outside of tileset extent or zoom range" }
+                    }
+                }
+            },
+            "/{tileset}/{z}/{x}/{y}.mvt": {
+                "get": {
+                    "summary": "Mapbox Vector Tile with .mvt extension",
+                    "parameters": tile_params,
+                    "responses": {
+                        "200": {
+                            "description": "Vector tile",
+                            "content": {
+                                "application/vnd.mapbox-vector-tile": {
+                                    "schema": { "type": "string", "format": "binary" }
+                                }
+                            }
+                        },
+                        "204": { "description": "Empty tile" },
+                        "400": { "description": "Tile coordinates outside of grid" },
+                        "403": { "description": "Referrer not allowed for tileset" },
+                        "404": { "description": "Tile outside of tileset extent or zoom range" }
+                    }
+                }
+            },
+            "/{tileset}/{time}/{z}/{x}/{y}.pbf": {
+                "get": {
+                    "summary": "Mapbox Vector Tile of time dimension tileset",
+                    "parameters": time_tile_params,
+                    "responses": {
+                        "200": {
+                            "description": "Vector tile",
+                            "content": {
+                                "application/x-protobuf": {
+                                    "schema": { "type": "string", "format": "binary" }
+                                }
+                            }
+                        },
+                        "204": { "description": "Empty tile" },
+                        "404": { "description": "Invalid time or tile outside of tileset" }
+                    }
+                }
+            }
+        });
+        if viewer {
+            paths["/drilldown"] = get_json(
+                "Tile layer statistics",
+                json!([
+                    { "name": "points", "in": "query", "required": true,
+                      "description": "Comma separated list of coordinates x1,y1,x2,y2,..",
+                      "schema": { "type": "string" } },
+                    { "name": "minzoom", "in": "query", "schema": { "type": "integer" } },
+                    { "name": "maxzoom", "in": "query", "schema": { "type": "integer" } },
+                    { "name": "count_only", "in": "query",
+                      "description": "Count features instead of generating tiles",
+                      "schema": { "type": "boolean" } }
+                ]),
+            );
+        }
+        Ok(json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "t-rex vector tile service",
+                "version": env!("CARGO_PKG_VERSION")
+            },
+            "servers": [{ "url": baseurl }],
+            "paths": paths
+        }))
+    }
+}
+
+#[cfg(test)]
+use t_rex_core::core::Config;
+
+#[test]
+fn test_openapi() {
+    use t_rex_core::core::read_config;
+
+    let config = read_config("src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+
+    let openapi = service.get_openapi("http://127.0.0.1:6767", false).unwrap();
+    assert_eq!(openapi["openapi"], "3.0.3");
+    assert_eq!(openapi["servers"][0]["url"], "http://127.0.0.1:6767");
+    let tile_path = &openapi["paths"]["/{tileset}/{z}/{x}/{y}.pbf"]["get"];
+    assert_eq!(tile_path["parameters"][0]["schema"]["enum"], json!(["osm"]));
+    assert_eq!(tile_path["parameters"].as_array().unwrap().len(), 4);
+    assert!(
+        openapi["paths"]["/{tileset}/{z}/{x}/{y}.mvt"]["get"]["responses"]["200"]["content"]
+            ["application/vnd.mapbox-vector-tile"]
+            .is_object()
+    );
+    assert!(openapi["paths"]["/drilldown"].is_null());
+
+    let openapi = service.get_openapi("http://127.0.0.1:6767", true).unwrap();
+    assert!(openapi["paths"]["/drilldown"].is_object());
+}
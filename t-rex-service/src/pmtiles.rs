@@ -0,0 +1,423 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! PMTiles v3 packages for offline use (https://github.com/protomaps/PMTiles/blob/main/spec/v3/spec.md)
+
+use crate::mvt_service::MvtService;
+pub use t_rex_core::datasource::zxy_to_tile_id;
+use tile_grid::{Extent, GridIterator};
+
+/// Maximal number of tiles in an offline package
+pub const MAX_PACKAGE_TILES: u64 = 100_000;
+
+const HEADER_LEN: usize = 127;
+/// Header and root directory have to fit into the first 16 KB
+const MAX_ROOT_LEN: usize = 16_384 - HEADER_LEN;
+
+const COMPRESSION_NONE: u8 = 1;
+const COMPRESSION_GZIP: u8 = 2;
+const TILE_TYPE_MVT: u8 = 1;
+
+struct Entry {
+    tile_id: u64,
+    offset: u64,
+    length: u64,
+    run_length: u64,
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn serialize_directory(entries: &[Entry]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_varint(&mut buf, entries.len() as u64);
+    let mut last_id = 0;
+    for entry in entries {
+        write_varint(&mut buf, entry.tile_id - last_id);
+        last_id = entry.tile_id;
+    }
+    for entry in entries {
+        write_varint(&mut buf, entry.run_length);
+    }
+    for entry in entries {
+        write_varint(&mut buf, entry.length);
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 && entry.offset == entries[i - 1].offset + entries[i - 1].length {
+            write_varint(&mut buf, 0);
+        } else {
+            write_varint(&mut buf, entry.offset + 1);
+        }
+    }
+    buf
+}
+
+/// Root directory and leaf directories
+fn build_directories(entries: &[Entry]) -> (Vec<u8>, Vec<u8>) {
+    let root = serialize_directory(entries);
+    if root.len() <= MAX_ROOT_LEN {
+        return (root, Vec::new());
+    }
+    let mut leaf_size = 4096;
+    loop {
+        let mut leaves = Vec::new();
+        let mut root_entries = Vec::new();
+        for chunk in entries.chunks(leaf_size) {
+            let leaf = serialize_directory(chunk);
+            root_entries.push(Entry {
+                tile_id: chunk[0].tile_id,
+                offset: leaves.len() as u64,
+                length: leaf.len() as u64,
+                run_length: 0,
+            });
+            leaves.extend(leaf);
+        }
+        let root = serialize_directory(&root_entries);
+        if root.len() <= MAX_ROOT_LEN {
+            return (root, leaves);
+        }
+        leaf_size *= 2;
+    }
+}
+
+fn e7(coord: f64) -> [u8; 4] {
+    ((coord * 10_000_000.0) as i32).to_le_bytes()
+}
+
+/// PMTiles archive of gzip compressed MVT tiles, given as (z, x, y, data) in XYZ scheme
+pub fn pmtiles_archive(
+    tiles: Vec<(u8, u32, u32, Vec<u8>)>,
+    metadata: &serde_json::Value,
+    bounds: &Extent,
+    minzoom: u8,
+    maxzoom: u8,
+) -> Vec<u8> {
+    let mut tiles: Vec<(u64, Vec<u8>)> = tiles
+        .into_iter()
+        .map(|(z, x, y, data)| (zxy_to_tile_id(z, x, y), data))
+        .collect();
+    tiles.sort_by_key(|(id, _)| *id);
+    let mut entries = Vec::with_capacity(tiles.len());
+    let mut tile_data = Vec::new();
+    for (tile_id, data) in &tiles {
+        entries.push(Entry {
+            tile_id: *tile_id,
+            offset: tile_data.len() as u64,
+            length: data.len() as u64,
+            run_length: 1,
+        });
+        tile_data.extend_from_slice(data);
+    }
+    let (root, leaves) = build_directories(&entries);
+    let metadata = metadata.to_string().into_bytes();
+
+    let root_offset = HEADER_LEN as u64;
+    let metadata_offset = root_offset + root.len() as u64;
+    let leaves_offset = metadata_offset + metadata.len() as u64;
+    let data_offset = leaves_offset + leaves.len() as u64;
+    let mut out = Vec::with_capacity(data_offset as usize + tile_data.len());
+    out.extend_from_slice(b"PMTiles");
+    out.push(3);
+    for value in &[
+        root_offset,
+        root.len() as u64,
+        metadata_offset,
+        metadata.len() as u64,
+        leaves_offset,
+        leaves.len() as u64,
+        data_offset,
+        tile_data.len() as u64,
+        entries.len() as u64, // addressed tiles
+        entries.len() as u64, // tile entries
+        entries.len() as u64, // tile contents
+    ] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out.push(1); // clustered
+    out.push(COMPRESSION_NONE); // internal compression
+    out.push(COMPRESSION_GZIP); // tile compression
+    out.push(TILE_TYPE_MVT);
+    out.push(minzoom);
+    out.push(maxzoom);
+    out.extend_from_slice(&e7(bounds.minx));
+    out.extend_from_slice(&e7(bounds.miny));
+    out.extend_from_slice(&e7(bounds.maxx));
+    out.extend_from_slice(&e7(bounds.maxy));
+    out.push(minzoom); // center zoom
+    out.extend_from_slice(&e7((bounds.minx + bounds.maxx) / 2.0));
+    out.extend_from_slice(&e7((bounds.miny + bounds.maxy) / 2.0));
+    debug_assert_eq!(out.len(), HEADER_LEN);
+    out.extend(root);
+    out.extend(metadata);
+    out.extend(leaves);
+    out.extend(tile_data);
+    out
+}
+
+impl MvtService {
+    /// PMTiles package of tileset within WGS84 extent, served from cache where possible
+    pub fn offline_package(
+        &self,
+        tileset: &str,
+        extent: &Extent,
+        minzoom: u8,
+        maxzoom: u8,
+    ) -> Result<Vec<u8>, String> {
+        if self.grid.srid != 3857 {
+            return Err("Offline packages require a Web Mercator grid".to_string());
+        }
+        let ts = self
+            .get_tileset(tileset)
+            .ok_or(format!("Tileset '{}' not found", tileset))?;
+        let minzoom = minzoom.max(ts.minzoom());
+        let maxzoom = maxzoom.min(ts.maxzoom()).min(self.grid.maxzoom());
+        if minzoom > maxzoom {
+            return Err(format!("Invalid zoom range {}-{}", minzoom, maxzoom));
+        }
+        let limits = self
+            .grid
+            .tile_limits(self.extent_from_input_extent(extent, Some(4326)), 0);
+        let count: u64 = limits[minzoom as usize..=maxzoom as usize]
+            .iter()
+            .map(|l| (l.maxx - l.minx) as u64 * (l.maxy - l.miny) as u64)
+            .sum();
+        if count > MAX_PACKAGE_TILES {
+            return Err(format!(
+                "Package with {} tiles exceeds limit of {} tiles",
+                count, MAX_PACKAGE_TILES
+            ));
+        }
+        let mut tiles = Vec::new();
+        for (zoom, xtile, ytile) in GridIterator::new(minzoom, maxzoom, limits) {
+            let y = self.grid.ytile_from_xyz(ytile, zoom);
+            let gzip = true;
+            if let Some(data) = self.tile_cached(tileset, xtile, y, zoom, gzip, None) {
+                tiles.push((zoom, xtile, y, data));
+            }
+        }
+        let metadata = self
+            .get_pmtiles_metadata(tileset)
+            .map_err(|e| e.to_string())?;
+        Ok(pmtiles_archive(tiles, &metadata, extent, minzoom, maxzoom))
+    }
+}
+
+#[test]
+fn test_tile_id() {
+    assert_eq!(zxy_to_tile_id(0, 0, 0), 0);
+    assert_eq!(zxy_to_tile_id(1, 0, 0), 1);
+    assert_eq!(zxy_to_tile_id(1, 0, 1), 2);
+    assert_eq!(zxy_to_tile_id(1, 1, 1), 3);
+    assert_eq!(zxy_to_tile_id(1, 1, 0), 4);
+    assert_eq!(zxy_to_tile_id(2, 0, 0), 5);
+    assert_eq!(zxy_to_tile_id(12, 3423, 1763), 19078479);
+}
+
+#[test]
+fn test_directory() {
+    let mut buf = Vec::new();
+    write_varint(&mut buf, 300);
+    assert_eq!(buf, vec![0xac, 0x02]);
+
+    let entries = vec![
+        Entry {
+            tile_id: 1,
+            offset: 0,
+            length: 10,
+            run_length: 1,
+        },
+        Entry {
+            tile_id: 3,
+            offset: 10,
+            length: 5,
+            run_length: 1,
+        },
+    ];
+    assert_eq!(
+        serialize_directory(&entries),
+        vec![2, 1, 2, 1, 1, 10, 5, 1, 0]
+    );
+
+    let entries: Vec<Entry> = (0..5000)
+        .map(|i| Entry {
+            tile_id: i * 1000,
+            offset: i * 100,
+            length: 100,
+            run_length: 1,
+        })
+        .collect();
+    let (root, leaves) = build_directories(&entries);
+    assert!(root.len() <= MAX_ROOT_LEN);
+    assert!(!leaves.is_empty());
+}
+
+#[test]
+fn test_pmtiles_archive() {
+    let bounds = Extent {
+        minx: 5.9,
+        miny: 45.8,
+        maxx: 10.5,
+        maxy: 47.8,
+    };
+    let tiles = vec![(1, 1, 0, vec![1, 2, 3]), (0, 0, 0, vec![4, 5])];
+    let archive = pmtiles_archive(tiles, &json!({"name": "ch"}), &bounds, 0, 1);
+    assert_eq!(&archive[0..7], b"PMTiles");
+    assert_eq!(archive[7], 3);
+    let u64_at = |pos: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&archive[pos..pos + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    assert_eq!(u64_at(8), HEADER_LEN as u64);
+    let data_offset = u64_at(56) as usize;
+    assert_eq!(u64_at(64), 5);
+    assert_eq!(u64_at(72), 2);
+    // Tiles are ordered by tile id
+    assert_eq!(&archive[data_offset..], &[4, 5, 1, 2, 3]);
+    let metadata_offset = u64_at(24) as usize;
+    let metadata_len = u64_at(32) as usize;
+    assert_eq!(
+        &archive[metadata_offset..metadata_offset + metadata_len],
+        br#"{"name":"ch"}"#
+    );
+    assert_eq!((archive[100], archive[101]), (0, 1));
+    assert_eq!(&archive[102..106], &59_000_000i32.to_le_bytes());
+}
+
+#[cfg(test)]
+fn write_test_archive(name: &str, archive: &[u8]) -> String {
+    let mut path = std::env::temp_dir();
+    path.push(name);
+    std::fs::write(&path, archive).unwrap();
+    format!("{}", path.display())
+}
+
+#[test]
+fn test_pmtiles_reader() {
+    use t_rex_core::datasource::PmtilesReader;
+
+    let bounds = Extent {
+        minx: 5.9,
+        miny: 45.8,
+        maxx: 10.5,
+        maxy: 47.8,
+    };
+    let tiles = vec![(1, 1, 0, vec![1, 2, 3]), (0, 0, 0, vec![4, 5])];
+    let metadata = json!({"name": "ch", "vector_layers": [{"id": "places"}]});
+    let archive = pmtiles_archive(tiles, &metadata, &bounds, 0, 1);
+    let path = write_test_archive("t_rex_test_reader.pmtiles", &archive);
+    let reader = PmtilesReader::open(&path).unwrap();
+    let header = reader.header();
+    assert_eq!((header.minzoom, header.maxzoom), (0, 1));
+    assert_eq!(header.bounds.minx, 5.9);
+    assert_eq!(reader.metadata()["name"], "ch");
+    assert_eq!(reader.vector_layers(), Some(json!([{"id": "places"}])));
+    assert_eq!(reader.tile(1, 1, 0).unwrap(), Some(vec![1, 2, 3]));
+    assert_eq!(reader.tile(0, 0, 0).unwrap(), Some(vec![4, 5]));
+    assert_eq!(reader.tile(1, 0, 0).unwrap(), None);
+    assert_eq!(reader.tile(2, 0, 0).unwrap(), None);
+
+    // Leaf directories
+    let tiles: Vec<_> = (0..5000u32)
+        .map(|i| (13, i, 100, i.to_le_bytes().to_vec()))
+        .collect();
+    let archive = pmtiles_archive(tiles, &metadata, &bounds, 13, 13);
+    let path = write_test_archive("t_rex_test_leaves.pmtiles", &archive);
+    let reader = PmtilesReader::open(&path).unwrap();
+    for i in &[0u32, 2047, 4999] {
+        assert_eq!(
+            reader.tile(13, *i, 100).unwrap(),
+            Some(i.to_le_bytes().to_vec())
+        );
+    }
+    assert_eq!(reader.tile(13, 5000, 100).unwrap(), None);
+
+    assert!(PmtilesReader::open("../data/places.mbtiles").is_err());
+}
+
+#[test]
+fn test_pmtiles_http_range_requests() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use t_rex_core::datasource::PmtilesReader;
+
+    let bounds = Extent {
+        minx: 5.9,
+        miny: 45.8,
+        maxx: 10.5,
+        maxy: 47.8,
+    };
+    let tiles = vec![(1, 1, 0, vec![1, 2, 3]), (0, 0, 0, vec![4, 5])];
+    let archive = pmtiles_archive(tiles, &json!({"name": "ch"}), &bounds, 0, 1);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    // Server ignoring the Range header
+    let full_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let full_port = full_listener.local_addr().unwrap().port();
+    let full_archive = archive.clone();
+    std::thread::spawn(move || {
+        for stream in full_listener.incoming() {
+            let mut stream = stream.unwrap();
+            for line in BufReader::new(&stream).lines() {
+                if line.unwrap().is_empty() {
+                    break;
+                }
+            }
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                full_archive.len()
+            );
+            let _ = stream.write_all(&full_archive);
+        }
+    });
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut range = None;
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(bytes) = line.strip_prefix("Range: bytes=") {
+                    let (start, end) = bytes.split_once('-').unwrap();
+                    range = Some((
+                        start.parse::<usize>().unwrap(),
+                        end.parse::<usize>().unwrap(),
+                    ));
+                }
+            }
+            let (start, end) = range.unwrap();
+            let body = &archive[start.min(archive.len())..(end + 1).min(archive.len())];
+            let _ = write!(
+                stream,
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(body);
+        }
+    });
+    let url = format!("http://127.0.0.1:{}/tiles/ch.pmtiles", port);
+    let reader = PmtilesReader::open(&url).unwrap();
+    assert_eq!(reader.path(), url);
+    assert_eq!(reader.metadata()["name"], "ch");
+    assert_eq!(reader.tile(1, 1, 0).unwrap(), Some(vec![1, 2, 3]));
+    assert_eq!(reader.tile(0, 0, 0).unwrap(), Some(vec![4, 5]));
+
+    let url = format!("http://127.0.0.1:{}/tiles/ch.pmtiles", full_port);
+    assert_eq!(
+        PmtilesReader::open(&url).err(),
+        Some(format!(
+            "{}: PMTiles server doesn't support range requests",
+            url
+        ))
+    );
+}
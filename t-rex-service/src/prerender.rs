@@ -0,0 +1,177 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Background pre-rendering of tiles around cache misses
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Condvar, Mutex};
+use t_rex_core::core::config::CachePrerenderCfg;
+
+/// Upper limit of configured neighbors in each direction
+const MAX_RADIUS: u8 = 4;
+/// Upper limit of configured zoom levels of child tiles (256 tiles at the deepest level)
+const MAX_ZOOM_DEPTH: u8 = 4;
+
+/// Tile queued for pre-rendering (XYZ adressing scheme)
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PrerenderTile {
+    pub tileset: String,
+    pub time: Option<String>,
+    pub x: u32,
+    pub y: u32,
+    pub z: u8,
+}
+
+#[derive(Default)]
+struct QueueState {
+    tiles: VecDeque<PrerenderTile>,
+    queued: HashSet<PrerenderTile>,
+    dropped: u64,
+}
+
+/// Bounded queue of tiles around recently missed tiles, shared by all service instances
+#[derive(Clone)]
+pub struct PrerenderQueue {
+    state: Arc<(Mutex<QueueState>, Condvar)>,
+    /// Neighboring tiles in each direction
+    pub radius: u32,
+    /// Zoom levels of child tiles
+    pub zoom_depth: u8,
+    pub max_size: usize,
+    /// Number of rendering threads
+    pub threads: u8,
+}
+
+impl PrerenderQueue {
+    pub fn new(radius: u32, zoom_depth: u8, max_size: usize, threads: u8) -> PrerenderQueue {
+        PrerenderQueue {
+            state: Arc::new((Mutex::new(QueueState::default()), Condvar::new())),
+            radius,
+            zoom_depth,
+            max_size: max_size.max(1),
+            threads: threads.max(1),
+        }
+    }
+    pub fn from_config(config: &CachePrerenderCfg) -> Result<PrerenderQueue, String> {
+        let radius = config.radius.unwrap_or(1);
+        if radius > MAX_RADIUS {
+            return Err(format!(
+                "Prerender radius {} exceeds maximum of {}",
+                radius, MAX_RADIUS
+            ));
+        }
+        let zoom_depth = config.zoom_depth.unwrap_or(1);
+        if zoom_depth > MAX_ZOOM_DEPTH {
+            return Err(format!(
+                "Prerender zoom_depth {} exceeds maximum of {}",
+                zoom_depth, MAX_ZOOM_DEPTH
+            ));
+        }
+        Ok(PrerenderQueue::new(
+            radius as u32,
+            zoom_depth,
+            config.queue_size.unwrap_or(1000),
+            config.threads.unwrap_or(1),
+        ))
+    }
+    /// Queue neighbors and children of a missed tile. Tiles outside of the grid are
+    /// filtered when rendering. No more tiles than fit into the queue are generated.
+    pub fn record_miss(
+        &self,
+        tileset: &str,
+        time: Option<&str>,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        maxzoom: u8,
+    ) {
+        let tile = |x, y, z| PrerenderTile {
+            tileset: tileset.to_string(),
+            time: time.map(|t| t.to_string()),
+            x,
+            y,
+            z,
+        };
+        let r = self.radius;
+        let limit = self.max_size;
+        let mut tiles = Vec::new();
+        'neighbors: for y in ytile.saturating_sub(r)..=ytile.saturating_add(r) {
+            for x in xtile.saturating_sub(r)..=xtile.saturating_add(r) {
+                if tiles.len() >= limit {
+                    break 'neighbors;
+                }
+                if (x, y) != (xtile, ytile) {
+                    tiles.push(tile(x, y, zoom));
+                }
+            }
+        }
+        let maxdepth = self.zoom_depth.min(maxzoom.saturating_sub(zoom)).min(31);
+        'children: for depth in 1..=maxdepth {
+            let size = 1u32 << depth;
+            for y in 0..size {
+                for x in 0..size {
+                    if tiles.len() >= limit {
+                        break 'children;
+                    }
+                    tiles.push(tile(
+                        (xtile << depth) | x,
+                        (ytile << depth) | y,
+                        zoom + depth,
+                    ));
+                }
+            }
+        }
+        self.push(tiles);
+    }
+    fn push(&self, tiles: Vec<PrerenderTile>) {
+        let (lock, available) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        for tile in tiles {
+            if state.queued.contains(&tile) {
+                continue;
+            }
+            if state.tiles.len() >= self.max_size {
+                if let Some(oldest) = state.tiles.pop_front() {
+                    state.queued.remove(&oldest);
+                    state.dropped += 1;
+                }
+            }
+            state.queued.insert(tile.clone());
+            state.tiles.push_back(tile);
+        }
+        available.notify_all();
+    }
+    /// Next tile, waiting until a tile is queued
+    pub fn pop(&self) -> PrerenderTile {
+        let (lock, available) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        loop {
+            if let Some(tile) = state.tiles.pop_front() {
+                state.queued.remove(&tile);
+                return tile;
+            }
+            state = available.wait(state).unwrap();
+        }
+    }
+    /// Next tile without waiting
+    pub fn try_pop(&self) -> Option<PrerenderTile> {
+        let mut state = self.state.0.lock().unwrap();
+        let tile = state.tiles.pop_front();
+        if let Some(ref tile) = tile {
+            state.queued.remove(tile);
+        }
+        tile
+    }
+    pub fn len(&self) -> usize {
+        self.state.0.lock().unwrap().tiles.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Number of tiles dropped because of a full queue
+    pub fn dropped(&self) -> u64 {
+        self.state.0.lock().unwrap().dropped
+    }
+}
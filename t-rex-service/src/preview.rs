@@ -0,0 +1,97 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Rendering of ad-hoc layer definitions
+
+use crate::mvt_service::{MvtService, TileOptions};
+use t_rex_core::cache::{Nocache, Tilecache};
+use t_rex_core::core::config::LayerCfg;
+use t_rex_core::core::layer::Layer;
+use t_rex_core::core::Config;
+use t_rex_core::datasource::DatasourceType;
+use t_rex_core::service::tileset::Tileset;
+
+/// Tileset name used for rendering preview layers
+pub const PREVIEW_TILESET: &str = "_preview";
+
+impl MvtService {
+    /// Render tile at x, y, z (XYZ scheme for Web Mercator) with a layer not in the configuration
+    pub fn preview_tile(
+        &self,
+        layer_cfg: &LayerCfg,
+        xtile: u32,
+        ytile: u32,
+        zoom: u8,
+        gzip: bool,
+    ) -> Result<Option<Vec<u8>>, String> {
+        if zoom > self.grid.maxzoom() {
+            return Err(format!("Zoom level {} outside of grid", zoom));
+        }
+        let layer = Layer::from_config(layer_cfg)?;
+        let mut service = self.clone();
+        service.cache = Tilecache::Nocache(Nocache);
+        service.aliases = Default::default();
+        service
+            .datasources
+            .datasource_mut(&layer.datasource)
+            .ok_or_else(|| format!("Datasource of layer `{}` not found", layer.name))?
+            .prepare_queries(PREVIEW_TILESET, &layer, self.grid.srid);
+        service.tilesets = vec![Tileset {
+            name: PREVIEW_TILESET.to_string(),
+            minzoom: None,
+            maxzoom: None,
+            attribution: None,
+            extent: None,
+            center: None,
+            start_zoom: None,
+            tags: Vec::new(),
+            layers: vec![layer],
+            cache_limits: None,
+            cache: None,
+            access: None,
+            time: None,
+            content_type: None,
+            crawl_protection: None,
+            mbtiles: None,
+            pmtiles: None,
+        }];
+        let y = if self.grid.srid == 3857 {
+            self.grid.ytile_from_xyz(ytile, zoom)
+        } else {
+            ytile
+        };
+        let options = TileOptions {
+            authenticated: true,
+            time: None,
+            refresh: false,
+            interactive: false,
+        };
+        let mvt_tile = service.tile_with_options(PREVIEW_TILESET, xtile, y, zoom, None, &options);
+        if mvt_tile.get_layers().is_empty() {
+            return Ok(None);
+        }
+        let data = service.compression.cache_bytevec(&mvt_tile);
+        Ok(Some(service.compression.tile_content(data, gzip)))
+    }
+}
+
+#[test]
+fn test_preview_errors() {
+    use t_rex_core::core::read_config;
+
+    let config = read_config("src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+    let mut layer_cfg = config.tilesets[0].layers[0].clone();
+    assert_eq!(
+        service.preview_tile(&layer_cfg, 0, 0, 30, false),
+        Err("Zoom level 30 outside of grid".to_string())
+    );
+    layer_cfg.name = "preview".to_string();
+    layer_cfg.datasource = Some("unknown".to_string());
+    assert_eq!(
+        service.preview_tile(&layer_cfg, 0, 0, 0, false),
+        Err("Datasource of layer `preview` not found".to_string())
+    );
+}
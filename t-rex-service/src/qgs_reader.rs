@@ -161,8 +161,16 @@ pub fn read_qgs(fname: &str) -> (Datasources, Tileset) {
         extent: None,
         center: None,
         start_zoom: None,
+        tags: Vec::new(),
         layers: Vec::new(),
         cache_limits: None,
+        cache: None,
+        access: None,
+        time: None,
+        content_type: None,
+        crawl_protection: None,
+        mbtiles: None,
+        pmtiles: None,
     };
     for qgslayer in projectlayers.find_all("maplayer") {
         let layertype = qgslayer.get_attr("type").expect("Missing attribute 'type'");
@@ -309,6 +317,7 @@ srid = 3857
 #buffer_size = 10
 #make_valid = true
 simplify = true
+#tolerance = "!pixel_width!/2"
 #query_limit = 1000
 #[[tileset.layer.query]]
 "#;
@@ -327,6 +336,7 @@ table_name = "ne_110m_admin_0_countries"
 #buffer_size = 10
 #make_valid = true
 simplify = false
+#tolerance = "!pixel_width!/2"
 #query_limit = 1000
 #[[tileset.layer.query]]
 "#;
@@ -0,0 +1,347 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Passthrough of pre-rendered raster tiles and hillshade rendering
+
+use std::path::Path;
+use t_rex_core::core::config::RasterTilesetCfg;
+use t_rex_core::core::hillshade::Hillshade;
+use t_rex_core::core::png::encode_grey_png;
+use t_rex_core::core::{ApplicationCfg, Config};
+use tile_grid::{Extent, Grid};
+
+/// Raster tiles stored in a directory
+#[derive(Clone, Debug)]
+pub struct RasterTileset {
+    pub name: String,
+    pub path: String,
+    /// File extensions of available formats (png, jpg or webp), default format first
+    pub formats: Vec<String>,
+    pub minzoom: u8,
+    pub maxzoom: u8,
+    /// Render hillshade from elevation model in `path`
+    pub hillshade: Option<Hillshade>,
+}
+
+/// MIME type of format
+pub fn content_type(format: &str) -> &'static str {
+    match format {
+        "jpg" => "image/jpeg",
+        "webp" => "image/webp",
+        _ => "image/png",
+    }
+}
+
+#[cfg(feature = "with-gdal")]
+fn read_dem(path: &str, extent: &Extent, width: usize, height: usize) -> Result<Vec<f32>, String> {
+    t_rex_gdal::read_dem(path, extent, width, height)
+}
+
+#[cfg(not(feature = "with-gdal"))]
+fn read_dem(
+    _path: &str,
+    _extent: &Extent,
+    _width: usize,
+    _height: usize,
+) -> Result<Vec<f32>, String> {
+    Err("Reading elevation models requires GDAL support".to_string())
+}
+
+fn normalized_format(tileset: &str, format: &str) -> Result<String, String> {
+    match format {
+        "png" | "webp" => Ok(format.to_string()),
+        "jpg" | "jpeg" => Ok("jpg".to_string()),
+        other => Err(format!(
+            "Raster tileset '{}': unsupported format '{}' (expected png, jpg or webp)",
+            tileset, other
+        )),
+    }
+}
+
+impl RasterTileset {
+    /// Default format
+    pub fn format(&self) -> &str {
+        &self.formats[0]
+    }
+    /// Select format by requested extension or by Accept header, preferring WebP for capable clients
+    pub fn negotiate_format(&self, extension: Option<&str>, accept: Option<&str>) -> Option<&str> {
+        if let Some(ext) = extension {
+            let ext = if ext == "jpeg" { "jpg" } else { ext };
+            return self
+                .formats
+                .iter()
+                .find(|f| f.as_str() == ext)
+                .map(|f| f.as_str());
+        }
+        let accept = accept.unwrap_or("");
+        if accept.contains("image/webp") && self.formats.iter().any(|f| f == "webp") {
+            return Some("webp");
+        }
+        // Serve default format, if client doesn't accept WebP
+        self.formats
+            .iter()
+            .find(|f| f.as_str() != "webp")
+            .or_else(|| self.formats.first())
+            .map(|f| f.as_str())
+    }
+    /// Render hillshade PNG tile from elevation model
+    pub fn render_hillshade(
+        &self,
+        grid: &Grid,
+        zoom: u8,
+        xtile: u32,
+        ytile: u32,
+    ) -> Result<Option<Vec<u8>>, String> {
+        let hillshade = match self.hillshade {
+            Some(ref hillshade) if zoom >= self.minzoom && zoom <= self.maxzoom => hillshade,
+            _ => return Ok(None),
+        };
+        let (width, height) = grid.tile_size();
+        let (width, height) = (width as usize, height as usize);
+        let extent = grid.tile_extent_xyz(xtile, ytile, zoom);
+        let cell_size = (extent.maxx - extent.minx) / width as f64;
+        // Elevation model with a buffer of one pixel for shading border pixels
+        let cell_height = (extent.maxy - extent.miny) / height as f64;
+        let buffered = Extent {
+            minx: extent.minx - cell_size,
+            miny: extent.miny - cell_height,
+            maxx: extent.maxx + cell_size,
+            maxy: extent.maxy + cell_height,
+        };
+        let dem = read_dem(&self.path, &buffered, width + 2, height + 2)?;
+        let pixels = hillshade.render(&dem, width, height, cell_size);
+        Ok(Some(encode_grey_png(&pixels, width as u32, height as u32)))
+    }
+    /// Path of existing tile file
+    pub fn tile_path(&self, zoom: u8, xtile: u32, ytile: u32, format: &str) -> Option<String> {
+        if zoom < self.minzoom || zoom > self.maxzoom {
+            return None;
+        }
+        let path = format!("{}/{}/{}/{}.{}", self.path, zoom, xtile, ytile, format);
+        if Path::new(&path).is_file() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Config<'a, RasterTilesetCfg> for RasterTileset {
+    fn from_config(cfg: &RasterTilesetCfg) -> Result<Self, String> {
+        if cfg.path.ends_with(".mbtiles") {
+            return Err(format!(
+                "Raster tileset '{}': MBTiles sources are not supported",
+                cfg.name
+            ));
+        }
+        let mut formats = vec![normalized_format(
+            &cfg.name,
+            cfg.format.as_deref().unwrap_or("png"),
+        )?];
+        for format in &cfg.formats {
+            let format = normalized_format(&cfg.name, format)?;
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        let hillshade = match cfg.hillshade {
+            Some(ref hillshade_cfg) => {
+                if formats != vec!["png"] {
+                    return Err(format!(
+                        "Raster tileset '{}': hillshade tiles are rendered as png",
+                        cfg.name
+                    ));
+                }
+                if !cfg!(feature = "with-gdal") {
+                    warn!(
+                        "Raster tileset '{}': hillshade rendering requires GDAL support",
+                        cfg.name
+                    );
+                }
+                Some(Hillshade::from_config(hillshade_cfg)?)
+            }
+            None => None,
+        };
+        if hillshade.is_some() {
+            if !Path::new(&cfg.path).is_file() {
+                warn!(
+                    "Raster tileset '{}': elevation model '{}' not found",
+                    cfg.name, cfg.path
+                );
+            }
+        } else if !Path::new(&cfg.path).is_dir() {
+            warn!(
+                "Raster tileset '{}': directory '{}' not found",
+                cfg.name, cfg.path
+            );
+        }
+        Ok(RasterTileset {
+            name: cfg.name.clone(),
+            path: cfg.path.trim_end_matches('/').to_string(),
+            formats,
+            minzoom: cfg.minzoom.unwrap_or(0),
+            maxzoom: cfg.maxzoom.unwrap_or(22),
+            hillshade,
+        })
+    }
+    fn gen_config() -> String {
+        let toml = r#"
+#[[raster]]
+#name = "orthophoto"
+#path = "/var/tiles/orthophoto" # {z}/{x}/{y}.{format}
+#format = "jpg"
+#formats = ["webp"] # Additional formats, WebP is preferred for capable clients
+
+#[[raster]]
+#name = "hillshade"
+#path = "/var/data/dem.tif" # GDAL elevation model in grid SRS
+#hillshade = { azimuth = 315, altitude = 45, z_factor = 1.0 }
+"#;
+        toml.to_string()
+    }
+}
+
+/// Raster tile passthrough service
+#[derive(Clone)]
+pub struct RasterService {
+    pub tilesets: Vec<RasterTileset>,
+    pub grid: Grid,
+}
+
+impl Default for RasterService {
+    fn default() -> Self {
+        RasterService {
+            tilesets: Vec::new(),
+            grid: Grid::web_mercator(),
+        }
+    }
+}
+
+impl RasterService {
+    pub fn tileset(&self, name: &str) -> Option<&RasterTileset> {
+        self.tilesets.iter().find(|ts| ts.name == name)
+    }
+}
+
+impl<'a> Config<'a, ApplicationCfg> for RasterService {
+    fn from_config(config: &ApplicationCfg) -> Result<Self, String> {
+        let tilesets = config
+            .rasters
+            .iter()
+            .map(RasterTileset::from_config)
+            .collect::<Result<Vec<_>, _>>()?;
+        let grid = Grid::from_config(&config.grid)?;
+        Ok(RasterService { tilesets, grid })
+    }
+    fn gen_config() -> String {
+        RasterTileset::gen_config()
+    }
+}
+
+#[test]
+fn test_raster_tileset() {
+    use std::env;
+    use std::fs;
+    use t_rex_core::core::parse_config;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_raster_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("3/4")).unwrap();
+    fs::write(dir.join("3/4/2.jpg"), b"JFIF").unwrap();
+
+    let toml = format!(
+        r#"
+        name = "ortho"
+        path = "{}/"
+        format = "jpeg"
+        maxzoom = 18
+        "#,
+        dir.display()
+    );
+    let cfg: RasterTilesetCfg = parse_config(toml, "").unwrap();
+    let tileset = RasterTileset::from_config(&cfg).unwrap();
+    assert_eq!(tileset.format(), "jpg");
+    assert_eq!(content_type(tileset.format()), "image/jpeg");
+    assert_eq!(
+        tileset.tile_path(3, 4, 2, "jpg"),
+        Some(format!("{}/3/4/2.jpg", dir.display()))
+    );
+    assert_eq!(tileset.tile_path(3, 4, 3, "jpg"), None);
+    assert_eq!(tileset.tile_path(3, 4, 2, "webp"), None);
+
+    let toml = r#"
+        name = "ortho"
+        path = "/tiles/ortho"
+        formats = ["webp", "png", "webp"]
+        "#;
+    let cfg: RasterTilesetCfg = parse_config(toml.to_string(), "").unwrap();
+    let tileset = RasterTileset::from_config(&cfg).unwrap();
+    assert_eq!(tileset.formats, vec!["png", "webp"]);
+    let webp_client = Some("image/avif,image/webp,*/*");
+    assert_eq!(tileset.negotiate_format(None, webp_client), Some("webp"));
+    assert_eq!(
+        tileset.negotiate_format(None, Some("image/png")),
+        Some("png")
+    );
+    assert_eq!(tileset.negotiate_format(None, None), Some("png"));
+    assert_eq!(
+        tileset.negotiate_format(Some("png"), webp_client),
+        Some("png")
+    );
+    assert_eq!(tileset.negotiate_format(Some("jpg"), webp_client), None);
+
+    let toml = r#"
+        name = "ortho"
+        path = "/tiles/ortho.mbtiles"
+        "#;
+    let cfg: RasterTilesetCfg = parse_config(toml.to_string(), "").unwrap();
+    assert!(RasterTileset::from_config(&cfg).is_err());
+    let toml = r#"
+        name = "ortho"
+        path = "/tiles/ortho"
+        format = "tiff"
+        "#;
+    let cfg: RasterTilesetCfg = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(
+        RasterTileset::from_config(&cfg).err(),
+        Some(
+            "Raster tileset 'ortho': unsupported format 'tiff' (expected png, jpg or webp)"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_hillshade_tileset() {
+    use t_rex_core::core::parse_config;
+
+    let toml = r#"
+        name = "hillshade"
+        path = "/data/dem.tif"
+        hillshade = { azimuth = 270, z_factor = 2.0 }
+        "#;
+    let cfg: RasterTilesetCfg = parse_config(toml.to_string(), "").unwrap();
+    let tileset = RasterTileset::from_config(&cfg).unwrap();
+    assert_eq!(
+        tileset.hillshade,
+        Some(Hillshade {
+            azimuth: 270.0,
+            altitude: 45.0,
+            z_factor: 2.0
+        })
+    );
+    let grid = Grid::web_mercator();
+    assert_eq!(tileset.render_hillshade(&grid, 23, 0, 0), Ok(None));
+
+    let toml = r#"
+        name = "hillshade"
+        path = "/data/dem.tif"
+        format = "jpg"
+        hillshade = {}
+        "#;
+    let cfg: RasterTilesetCfg = parse_config(toml.to_string(), "").unwrap();
+    assert!(RasterTileset::from_config(&cfg).is_err());
+}
@@ -0,0 +1,50 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Recording of retrieved features as fixtures for the replay datasource (`--record`)
+
+use crate::sample::GeoJsonWriter;
+use serde_json::Value;
+use std::fs;
+use t_rex_core::core::feature::Feature;
+use t_rex_core::core::layer::Layer;
+use t_rex_core::datasource::ReplayDatasource;
+use tile_grid::{Extent, Grid};
+
+#[derive(Clone)]
+pub struct FixtureRecorder {
+    /// Fixture directory
+    pub dir: String,
+}
+
+impl FixtureRecorder {
+    pub fn new(dir: &str) -> FixtureRecorder {
+        FixtureRecorder {
+            dir: dir.to_string(),
+        }
+    }
+    /// GeoJSON feature with all attributes and coordinates in grid SRS
+    pub fn feature(&self, feat: &dyn Feature, layer: &Layer) -> Value {
+        GeoJsonWriter { to_wgs84: false }.feature(feat, &layer.name, &[])
+    }
+    /// Write features of a layer in the tile with `extent`
+    pub fn write(
+        &self,
+        tileset: &str,
+        layer: &Layer,
+        extent: &Extent,
+        zoom: u8,
+        grid: &Grid,
+        features: Vec<Value>,
+    ) -> Result<(), String> {
+        let path =
+            ReplayDatasource::fixture_path(&self.dir, tileset, &layer.name, extent, zoom, grid);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let collection = GeoJsonWriter { to_wgs84: false }.feature_collection(features, grid.srid);
+        fs::write(&path, collection.to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
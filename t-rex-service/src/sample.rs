@@ -0,0 +1,199 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Raw feature samples of layers for attribute exploration
+
+use crate::mvt_service::MvtService;
+use serde_json::{Map, Value};
+use std::cmp;
+use t_rex_core::core::feature::{Feature, FeatureAttrValType};
+use t_rex_core::core::geom::{self, GeometryType};
+use t_rex_core::datasource::DatasourceType;
+use tile_grid::{merc_to_lonlat, Extent};
+
+/// Number of features returned without `limit`
+pub const DEFAULT_SAMPLE_LIMIT: u32 = 10;
+/// Maximal number of features of a sample
+pub const MAX_SAMPLE_LIMIT: u32 = 100;
+
+/// Coordinates of geometries in grid SRS
+pub(crate) struct GeoJsonWriter {
+    /// Transform Web Mercator coordinates to WGS84
+    pub to_wgs84: bool,
+}
+
+impl GeoJsonWriter {
+    fn coord(&self, x: f64, y: f64) -> Value {
+        let (x, y) = if self.to_wgs84 {
+            merc_to_lonlat(x, y)
+        } else {
+            (x, y)
+        };
+        json!([x, y])
+    }
+    fn points(&self, points: &[geom::Point]) -> Value {
+        Value::Array(points.iter().map(|p| self.coord(p.x, p.y)).collect())
+    }
+    fn polygon(&self, polygon: &geom::Polygon) -> Value {
+        Value::Array(
+            polygon
+                .rings
+                .iter()
+                .map(|ring| self.points(&ring.points))
+                .collect(),
+        )
+    }
+    fn geometry(&self, geom: &GeometryType) -> Value {
+        match geom {
+            GeometryType::Point(p) => {
+                json!({"type": "Point", "coordinates": self.coord(p.x, p.y)})
+            }
+            GeometryType::LineString(l) => {
+                json!({"type": "LineString", "coordinates": self.points(&l.points)})
+            }
+            GeometryType::Polygon(p) => {
+                json!({"type": "Polygon", "coordinates": self.polygon(p)})
+            }
+            GeometryType::MultiPoint(mp) => {
+                json!({"type": "MultiPoint", "coordinates": self.points(&mp.points)})
+            }
+            GeometryType::MultiLineString(ml) => json!({
+                "type": "MultiLineString",
+                "coordinates": ml.lines.iter().map(|l| self.points(&l.points)).collect::<Vec<_>>()
+            }),
+            GeometryType::MultiPolygon(mp) => json!({
+                "type": "MultiPolygon",
+                "coordinates": mp.polygons.iter().map(|p| self.polygon(p)).collect::<Vec<_>>()
+            }),
+            GeometryType::GeometryCollection(gc) => self.collection(gc),
+            GeometryType::Geometry(g) => self.ewkb_geometry(g),
+        }
+    }
+    fn collection(&self, gc: &geom::GeometryCollection) -> Value {
+        json!({
+            "type": "GeometryCollection",
+            "geometries": gc.geometries.iter().map(|g| self.ewkb_geometry(g)).collect::<Vec<_>>()
+        })
+    }
+    fn ewkb_geometry(&self, geom: &geom::Geometry) -> Value {
+        use geom::Geometry as GeometryT;
+        let geom = match geom {
+            GeometryT::Point(p) => GeometryType::Point(*p),
+            GeometryT::LineString(l) => GeometryType::LineString(l.clone()),
+            GeometryT::Polygon(p) => GeometryType::Polygon(p.clone()),
+            GeometryT::MultiPoint(mp) => GeometryType::MultiPoint(mp.clone()),
+            GeometryT::MultiLineString(ml) => GeometryType::MultiLineString(ml.clone()),
+            GeometryT::MultiPolygon(mp) => GeometryType::MultiPolygon(mp.clone()),
+            GeometryT::GeometryCollection(gc) => return self.collection(gc),
+        };
+        self.geometry(&geom)
+    }
+    /// GeoJSON feature without `excluded_fields`
+    pub(crate) fn feature(
+        &self,
+        feat: &dyn Feature,
+        layer_name: &str,
+        excluded_fields: &[String],
+    ) -> Value {
+        let mut properties = Map::new();
+        for attr in feat.attributes() {
+            if !excluded_fields.contains(&attr.key) {
+                properties.insert(attr.key.clone(), attr_value(&attr.value));
+            }
+        }
+        let geometry = match feat.geometry() {
+            Ok(geom) => self.geometry(&geom),
+            Err(e) => {
+                warn!("Layer '{}': {}", layer_name, e);
+                Value::Null
+            }
+        };
+        let mut feature = json!({
+            "type": "Feature",
+            "geometry": geometry,
+            "properties": properties
+        });
+        if let Some(fid) = feat.fid() {
+            feature["id"] = json!(fid);
+        }
+        feature
+    }
+    /// FeatureCollection with CRS member for projected coordinates other than Web Mercator
+    pub(crate) fn feature_collection(&self, features: Vec<Value>, srid: i32) -> Value {
+        let mut collection = json!({
+            "type": "FeatureCollection",
+            "features": features
+        });
+        if !self.to_wgs84 && srid != 4326 {
+            collection["crs"] = json!({
+                "type": "name",
+                "properties": { "name": format!("urn:ogc:def:crs:EPSG::{}", srid) }
+            });
+        }
+        collection
+    }
+}
+
+fn attr_value(value: &FeatureAttrValType) -> Value {
+    match value {
+        FeatureAttrValType::String(v) => json!(v),
+        FeatureAttrValType::Float(v) => json!(v),
+        FeatureAttrValType::Double(v) => json!(v),
+        FeatureAttrValType::Int(v) | FeatureAttrValType::SInt(v) => json!(v),
+        FeatureAttrValType::UInt(v) => json!(v),
+        FeatureAttrValType::Bool(v) => json!(v),
+        FeatureAttrValType::VarcharArray(v) => json!(v),
+    }
+}
+
+impl MvtService {
+    /// Features of a layer as GeoJSON FeatureCollection, queried with the layer query of its
+    /// maximal zoom level. `bbox` is a WGS84 extent (default: tileset extent).
+    /// Returns None for unknown tilesets or layers.
+    pub fn get_layer_sample(
+        &self,
+        tileset: &str,
+        layer_name: &str,
+        bbox: Option<&Extent>,
+        limit: Option<u32>,
+        authenticated: bool,
+    ) -> Option<Result<Value, String>> {
+        let ts = self.get_tileset(tileset)?;
+        let layer = ts.layers.iter().find(|l| l.name == layer_name)?;
+        let ds = self.ds(layer)?;
+        let zoom = cmp::min(ts.maxzoom(), layer.maxzoom(self.grid.maxzoom()));
+        let extent = self.extent_from_input_extent(bbox.unwrap_or(ts.get_extent()), Some(4326));
+        let limit = limit
+            .unwrap_or(DEFAULT_SAMPLE_LIMIT)
+            .clamp(1, MAX_SAMPLE_LIMIT);
+        let mut sample_layer = layer.clone();
+        sample_layer.query_limit = Some(cmp::min(limit, layer.query_limit.unwrap_or(limit)));
+        let writer = GeoJsonWriter {
+            to_wgs84: self.grid.srid == 3857,
+        };
+        let excluded_fields: &[String] = if authenticated {
+            &[]
+        } else {
+            &layer.protected_fields
+        };
+        let mut features = Vec::new();
+        let _permit = self.datasources.query_permit(&layer.datasource);
+        let result = ds.try_retrieve_features_at(
+            &ts.name,
+            &sample_layer,
+            &extent,
+            zoom,
+            &self.grid,
+            None,
+            |feat: &dyn Feature| {
+                if features.len() >= limit as usize {
+                    return;
+                }
+                features.push(writer.feature(feat, &layer.name, excluded_fields));
+            },
+        );
+        Some(result.map(|_| writer.feature_collection(features, self.grid.srid)))
+    }
+}
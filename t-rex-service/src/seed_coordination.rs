@@ -0,0 +1,409 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Distribution of seeding work between cooperating generator instances
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Number of consecutive tiles claimed at once from a work queue
+pub const QUEUE_BLOCK_SIZE: u64 = 256;
+
+/// Default time after which claims without heartbeat can be taken over by other instances
+const DEFAULT_CLAIM_TTL: Duration = Duration::from_secs(600);
+
+/// Marker of a tileset whose seeding run has been completed by an instance
+const FINISHED_MARKER: &str = "finished";
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Work queue in a directory shared between generator instances (e.g. on NFS).
+/// A block of tiles is claimed by atomically creating a marker file with owner and
+/// expiration time, which is renewed while the block is generated. Claims of crashed
+/// instances expire and are taken over by other instances, e.g. when re-running the command.
+/// Generated blocks get a completion record. The run is marked as finished, when all blocks
+/// are completed, and the markers of a tileset are cleared by the first instance starting
+/// after a finished run, so that re-running the same command generates the tiles again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkQueue {
+    pub dir: PathBuf,
+    /// Claims not renewed within this time can be taken over
+    pub claim_ttl: Duration,
+    /// Unique owner of claims of this instance
+    owner: String,
+}
+
+impl WorkQueue {
+    pub fn new(dir: &str) -> WorkQueue {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        WorkQueue {
+            dir: PathBuf::from(dir),
+            claim_ttl: DEFAULT_CLAIM_TTL,
+            owner: format!("{}-{}-{}", std::process::id(), unix_time(), nanos),
+        }
+    }
+    /// Start seeding a tileset. Clears the claims of a completed previous run.
+    pub fn start(&self, tileset: &str) -> Result<(), String> {
+        let dir = self.dir.join(tileset);
+        if !dir.join(FINISHED_MARKER).exists() {
+            return Ok(());
+        }
+        // Only one of the concurrently starting instances succeeds in renaming
+        let old = self
+            .dir
+            .join(format!("{}.{}.old", tileset, std::process::id()));
+        match fs::rename(&dir, &old) {
+            Ok(()) => {
+                info!("Work queue {:?}: starting new run", dir);
+                fs::remove_dir_all(&old).map_err(|e| format!("Work queue {:?}: {}", old, e))
+            }
+            Err(ref e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Work queue {:?}: {}", dir, e)),
+        }
+    }
+    /// Mark seeding run of tileset as completed, if all blocks (number of blocks per zoom level)
+    /// have a completion record. Returns false, if blocks of other instances are not completed yet.
+    pub fn finish(&self, tileset: &str, blocks: &BTreeMap<u8, u64>) -> Result<bool, String> {
+        let dir = self.dir.join(tileset);
+        let incomplete = blocks
+            .iter()
+            .flat_map(|(zoom, count)| (0..*count).map(move |block| (*zoom, block)))
+            .filter(|(zoom, block)| !done_path(&dir, *zoom, *block).exists())
+            .count();
+        if incomplete > 0 {
+            info!(
+                "Work queue {:?}: {} blocks not completed yet by other instances",
+                dir, incomplete
+            );
+            return Ok(false);
+        }
+        let path = dir.join(FINISHED_MARKER);
+        fs::write(&path, format!("{}\n", self.owner))
+            .map_err(|e| format!("Work queue {:?}: {}", path, e))?;
+        Ok(true)
+    }
+    /// Claim block of tiles. Returns false, if the block is completed or claimed by
+    /// another instance with an unexpired claim.
+    pub fn claim(&self, tileset: &str, zoom: u8, block: u64) -> Result<bool, String> {
+        let dir = self.dir.join(tileset).join(zoom.to_string());
+        fs::create_dir_all(&dir).map_err(|e| format!("Work queue {:?}: {}", dir, e))?;
+        if done_path(&self.dir.join(tileset), zoom, block).exists() {
+            return Ok(false);
+        }
+        let path = dir.join(format!("{}.claim", block));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                let _ = file.write_all(self.claim_content().as_bytes());
+                return Ok(true);
+            }
+            Err(ref e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(format!("Work queue {:?}: {}", path, e)),
+        }
+        // Take over expired claim. Instances seeing the same expired claim compete
+        // for creating its takeover marker.
+        let stale = fs::read_to_string(&path).unwrap_or_default();
+        let expires = match claim_expiration(&stale) {
+            Some(expires) if expires > unix_time() => return Ok(false),
+            Some(expires) => expires,
+            // Claim being written by another instance
+            None => return Ok(false),
+        };
+        let takeover = dir.join(format!("{}.{}.takeover", block, expires));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&takeover)
+        {
+            Ok(_) => {
+                warn!("Work queue {:?}: taking over expired claim", path);
+                fs::write(&path, self.claim_content())
+                    .map_err(|e| format!("Work queue {:?}: {}", path, e))?;
+                Ok(true)
+            }
+            Err(ref e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(format!("Work queue {:?}: {}", takeover, e)),
+        }
+    }
+    /// Extend claim of a block in generation (heartbeat).
+    /// Returns false, if the claim has been taken over by another instance.
+    pub fn renew(&self, tileset: &str, zoom: u8, block: u64) -> Result<bool, String> {
+        let path = self
+            .dir
+            .join(tileset)
+            .join(zoom.to_string())
+            .join(format!("{}.claim", block));
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        if claim_owner(&current) != Some(self.owner.as_str()) {
+            return Ok(false);
+        }
+        fs::write(&path, self.claim_content())
+            .map_err(|e| format!("Work queue {:?}: {}", path, e))?;
+        Ok(true)
+    }
+    /// Record completion of a block, after all its tiles have been written
+    pub fn complete(&self, tileset: &str, zoom: u8, block: u64) -> Result<(), String> {
+        let path = done_path(&self.dir.join(tileset), zoom, block);
+        fs::write(&path, format!("{}\n", self.owner))
+            .map_err(|e| format!("Work queue {:?}: {}", path, e))
+    }
+    /// Claim file content with owner and expiration time
+    fn claim_content(&self) -> String {
+        format!(
+            "{} {}\n",
+            self.owner,
+            unix_time() + self.claim_ttl.as_secs()
+        )
+    }
+}
+
+/// Completion record of a block
+fn done_path(dir: &Path, zoom: u8, block: u64) -> PathBuf {
+    dir.join(zoom.to_string()).join(format!("{}.done", block))
+}
+
+fn claim_owner(content: &str) -> Option<&str> {
+    content.trim().rsplit_once(' ').map(|(owner, _)| owner)
+}
+
+fn claim_expiration(content: &str) -> Option<u64> {
+    content.trim().rsplit_once(' ')?.1.parse().ok()
+}
+
+/// Selection of tiles generated by this instance
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkPartition {
+    /// All tiles
+    All,
+    /// Tiles with sequence number modulo `nodes` equal to `nodeno`
+    Shard { nodes: u64, nodeno: u64 },
+    /// Blocks of tiles claimed from a shared work queue
+    Queue(WorkQueue),
+}
+
+impl WorkPartition {
+    /// Static partition from `--nodes`/`--nodeno`
+    pub fn from_nodes(nodes: Option<u8>, nodeno: Option<u8>) -> WorkPartition {
+        match nodes {
+            Some(nodes) if nodes > 1 => WorkPartition::Shard {
+                nodes: nodes as u64,
+                nodeno: nodeno.unwrap_or(0) as u64,
+            },
+            _ => WorkPartition::All,
+        }
+    }
+    /// Parse shard specification `n/count` (1 <= n <= count)
+    pub fn from_shard(shard: &str) -> Result<WorkPartition, String> {
+        let err = || format!("Invalid shard '{}' (expected n/count)", shard);
+        let mut parts = shard.splitn(2, '/');
+        let no: u64 = parts
+            .next()
+            .and_then(|s| s.trim().parse().ok())
+            .ok_or_else(err)?;
+        let count: u64 = parts
+            .next()
+            .and_then(|s| s.trim().parse().ok())
+            .ok_or_else(err)?;
+        if no == 0 || no > count {
+            return Err(err());
+        }
+        Ok(WorkPartition::Shard {
+            nodes: count,
+            nodeno: no - 1,
+        })
+    }
+}
+
+/// Decides per tile whether this instance generates it
+pub struct WorkSelector<'a> {
+    partition: &'a WorkPartition,
+    tileset: String,
+    tileno: u64,
+    zoom: Option<u8>,
+    claimed_block: Option<(u8, u64, bool)>,
+    /// Number of queue blocks per zoom level
+    blocks: BTreeMap<u8, u64>,
+    /// Claimed blocks, whose tiles have all been selected
+    pending: Vec<(u8, u64)>,
+    renewed: Instant,
+}
+
+impl<'a> WorkSelector<'a> {
+    pub fn new(partition: &'a WorkPartition, tileset: &str) -> WorkSelector<'a> {
+        if let WorkPartition::Queue(queue) = partition {
+            if let Err(e) = queue.start(tileset) {
+                error!("{}", e);
+            }
+        }
+        WorkSelector {
+            partition,
+            tileset: tileset.to_string(),
+            tileno: 0,
+            zoom: None,
+            claimed_block: None,
+            blocks: BTreeMap::new(),
+            pending: Vec::new(),
+            renewed: Instant::now(),
+        }
+    }
+    /// Called after all selected tiles have been generated
+    pub fn finish(&mut self) {
+        if let WorkPartition::Queue(queue) = self.partition {
+            if let Some((zoom, block, true)) = self.claimed_block.take() {
+                self.pending.push((zoom, block));
+            }
+            self.complete_pending();
+            if let Err(e) = queue.finish(&self.tileset, &self.blocks) {
+                error!("{}", e);
+            }
+        }
+    }
+    /// Claimed blocks with all tiles selected are waiting for completion
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+    /// Record completion of pending blocks. To be called after their tiles have been written.
+    pub fn complete_pending(&mut self) {
+        if let WorkPartition::Queue(queue) = self.partition {
+            for (zoom, block) in self.pending.drain(..) {
+                if let Err(e) = queue.complete(&self.tileset, zoom, block) {
+                    error!("{}", e);
+                }
+            }
+        }
+    }
+    /// Called for each tile in grid iteration order
+    pub fn selected(&mut self, zoom: u8) -> bool {
+        // Queue blocks are numbered per zoom level
+        if self.zoom != Some(zoom) {
+            self.zoom = Some(zoom);
+            if let WorkPartition::Queue(_) = self.partition {
+                self.tileno = 0;
+            }
+        }
+        let tileno = self.tileno;
+        self.tileno += 1;
+        match self.partition {
+            WorkPartition::All => true,
+            WorkPartition::Shard { nodes, nodeno } => tileno % nodes == *nodeno,
+            WorkPartition::Queue(queue) => {
+                let block = tileno / QUEUE_BLOCK_SIZE;
+                self.blocks.insert(zoom, block + 1);
+                match self.claimed_block {
+                    Some((z, b, claimed)) if z == zoom && b == block => {
+                        if claimed && self.renewed.elapsed() > queue.claim_ttl / 4 {
+                            self.renewed = Instant::now();
+                            if let Ok(false) = queue.renew(&self.tileset, zoom, block) {
+                                warn!(
+                                    "Work queue: claim of block {} at level {} taken over by another instance",
+                                    block, zoom
+                                );
+                            }
+                        }
+                        claimed
+                    }
+                    previous => {
+                        if let Some((z, b, true)) = previous {
+                            self.pending.push((z, b));
+                        }
+                        let claimed = queue.claim(&self.tileset, zoom, block).unwrap_or_else(|e| {
+                            error!("{}", e);
+                            false
+                        });
+                        self.claimed_block = Some((zoom, block, claimed));
+                        self.renewed = Instant::now();
+                        claimed
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_shard_spec() {
+    assert_eq!(
+        WorkPartition::from_shard("2/8"),
+        Ok(WorkPartition::Shard {
+            nodes: 8,
+            nodeno: 1
+        })
+    );
+    assert!(WorkPartition::from_shard("0/8").is_err());
+    assert!(WorkPartition::from_shard("9/8").is_err());
+    assert!(WorkPartition::from_shard("2").is_err());
+    assert_eq!(WorkPartition::from_nodes(None, None), WorkPartition::All);
+}
+
+#[test]
+fn test_work_selection() {
+    let shard = WorkPartition::from_shard("2/3").unwrap();
+    let mut selector = WorkSelector::new(&shard, "osm");
+    let selected: Vec<bool> = (0..6).map(|_| selector.selected(1)).collect();
+    assert_eq!(selected, [false, true, false, false, true, false]);
+
+    let dir = std::env::temp_dir().join(format!("t_rex_queue_{}", std::process::id()));
+    let queue = WorkPartition::Queue(WorkQueue::new(dir.to_str().unwrap()));
+    let mut first = WorkSelector::new(&queue, "osm");
+    let mut second = WorkSelector::new(&queue, "osm");
+    assert!(first.selected(3));
+    // Block claimed by first instance
+    assert!(!second.selected(3));
+    // Next zoom level starts with a new block
+    assert!(second.selected(4));
+    assert!(!first.selected(4));
+    // Block of zoom level 3 is waiting for its tiles being written
+    assert!(first.has_pending());
+    first.complete_pending();
+    assert!(!first.has_pending());
+    // Block of second instance only claimed
+    first.finish();
+    assert!(!dir.join("osm").join(FINISHED_MARKER).exists());
+    second.finish();
+    assert!(dir.join("osm").join(FINISHED_MARKER).exists());
+    // Re-run after completed run
+    let mut rerun = WorkSelector::new(&queue, "osm");
+    assert!(rerun.selected(3));
+    let mut second = WorkSelector::new(&queue, "osm");
+    assert!(!second.selected(3));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_work_queue_claims() {
+    let dir = std::env::temp_dir().join(format!("t_rex_claims_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut crashed = WorkQueue::new(dir.to_str().unwrap());
+    crashed.claim_ttl = Duration::from_secs(0);
+    let first = WorkQueue::new(dir.to_str().unwrap());
+    let second = WorkQueue::new(dir.to_str().unwrap());
+    assert!(crashed.claim("osm", 3, 0).unwrap());
+    assert!(first.claim("osm", 3, 1).unwrap());
+    assert!(!second.claim("osm", 3, 1).unwrap());
+    assert!(first.renew("osm", 3, 1).unwrap());
+    assert!(!second.renew("osm", 3, 1).unwrap());
+    // Expired claim is taken over by one instance
+    assert!(first.claim("osm", 3, 0).unwrap());
+    assert!(!second.claim("osm", 3, 0).unwrap());
+    assert!(!crashed.renew("osm", 3, 0).unwrap());
+    // Completed blocks are not claimed again
+    first.complete("osm", 3, 0).unwrap();
+    let blocks: BTreeMap<u8, u64> = [(3, 2)].iter().cloned().collect();
+    assert_eq!(first.finish("osm", &blocks), Ok(false));
+    first.complete("osm", 3, 1).unwrap();
+    assert!(!second.claim("osm", 3, 1).unwrap());
+    assert_eq!(second.finish("osm", &blocks), Ok(true));
+    let _ = fs::remove_dir_all(&dir);
+}
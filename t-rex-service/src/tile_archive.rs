@@ -0,0 +1,119 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Tilesets served from pre-rendered MBTiles and PMTiles archives
+
+#[cfg(feature = "sqlite")]
+use t_rex_core::datasource::MbtilesReader;
+use t_rex_core::datasource::PmtilesReader;
+use t_rex_core::service::tileset::Tileset;
+use tile_grid::Extent;
+
+#[derive(Debug)]
+pub enum TileArchive {
+    #[cfg(feature = "sqlite")]
+    Mbtiles(MbtilesReader),
+    Pmtiles(PmtilesReader),
+}
+
+impl TileArchive {
+    /// Open archive of tileset, if configured
+    pub fn open(tileset: &Tileset) -> Result<Option<TileArchive>, String> {
+        if let Some(ref path) = tileset.mbtiles {
+            #[cfg(feature = "sqlite")]
+            return MbtilesReader::open(path).map(|r| Some(TileArchive::Mbtiles(r)));
+            #[cfg(not(feature = "sqlite"))]
+            return Err(format!(
+                "{}: MBTiles archives not supported in builds without feature 'sqlite'",
+                path
+            ));
+        }
+        if let Some(ref path) = tileset.pmtiles {
+            return PmtilesReader::open(path).map(|r| Some(TileArchive::Pmtiles(r)));
+        }
+        Ok(None)
+    }
+    pub fn path(&self) -> &str {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.path(),
+            TileArchive::Pmtiles(r) => r.path(),
+        }
+    }
+    /// Stored tile data at x, y, z in TMS adressing scheme
+    pub fn tile(&self, xtile: u32, ytile: u32, zoom: u8) -> Result<Option<Vec<u8>>, String> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.tile(zoom, xtile, ytile),
+            TileArchive::Pmtiles(r) => {
+                let y = (1u32 << zoom) - 1 - ytile;
+                r.tile(zoom, xtile, y)
+            }
+        }
+    }
+    pub fn minzoom(&self) -> Option<u8> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.minzoom(),
+            TileArchive::Pmtiles(r) => Some(r.header().minzoom),
+        }
+    }
+    pub fn maxzoom(&self) -> Option<u8> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.maxzoom(),
+            TileArchive::Pmtiles(r) => Some(r.header().maxzoom),
+        }
+    }
+    /// Bounds in WGS84
+    pub fn bounds(&self) -> Option<Extent> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.bounds(),
+            TileArchive::Pmtiles(r) => Some(r.header().bounds.clone()),
+        }
+    }
+    /// Center longitude, latitude and zoom level
+    pub fn center(&self) -> Option<(f64, f64, u8)> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.center(),
+            TileArchive::Pmtiles(r) => {
+                let header = r.header();
+                Some((header.center.0, header.center.1, header.center_zoom))
+            }
+        }
+    }
+    pub fn attribution(&self) -> Option<String> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.metadata().get("attribution").cloned(),
+            TileArchive::Pmtiles(r) => r.metadata()["attribution"].as_str().map(|s| s.to_string()),
+        }
+    }
+    /// TileJSON `vector_layers` of archive metadata
+    pub fn vector_layers(&self) -> Option<serde_json::Value> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            TileArchive::Mbtiles(r) => r.vector_layers(),
+            TileArchive::Pmtiles(r) => r.vector_layers(),
+        }
+    }
+    /// Use archive metadata for zoom range, extent, center and attribution not set in tileset
+    pub fn apply_metadata(&self, tileset: &mut Tileset) {
+        tileset.minzoom = tileset.minzoom.or(self.minzoom());
+        tileset.maxzoom = tileset.maxzoom.or(self.maxzoom());
+        if tileset.extent.is_none() {
+            tileset.extent = self.bounds();
+        }
+        if let Some((lon, lat, zoom)) = self.center() {
+            tileset.center = tileset.center.or(Some((lon, lat)));
+            tileset.start_zoom = tileset.start_zoom.or(Some(zoom));
+        }
+        if tileset.attribution.is_none() {
+            tileset.attribution = self.attribution();
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Multiple tiles in one tar archive
+
+use crate::mvt_service::{MvtService, TileOptions};
+
+/// Maximal number of tiles in a batch request
+pub const MAX_BATCH_TILES: usize = 1000;
+
+/// Uncompressed ustar archive of (path, content) entries
+pub fn tar_archive(entries: &[(String, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (name, data) in entries {
+        let mut header = tar::Header::new_ustar();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_entry_type(tar::EntryType::Regular);
+        builder
+            .append_data(&mut header, name, &data[..])
+            .map_err(|e| format!("Tar entry {}: {}", name, e))?;
+    }
+    builder.into_inner().map_err(|e| e.to_string())
+}
+
+impl MvtService {
+    /// Uncompressed tiles at (z, x, y) with their path `{z}/{x}/{y}.pbf`.
+    /// Invalid and empty tiles are skipped.
+    pub fn tile_batch(
+        &self,
+        tileset: &str,
+        tiles: &[(u8, u32, u32)],
+        options: &TileOptions,
+    ) -> Vec<(String, Vec<u8>)> {
+        tiles
+            .iter()
+            .filter(|(z, x, y)| self.check_tile_request(tileset, *x, *y, *z).is_ok())
+            .filter_map(|(z, x, y)| {
+                self.tile_cached_with_options(tileset, *x, *y, *z, false, None, options)
+                    .map(|data| (format!("{}/{}/{}.pbf", z, x, y), data))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_tar_archive() {
+    use std::io::Read;
+
+    let long_name = format!("{}/0/0.pbf", "x".repeat(101));
+    let entries = vec![
+        ("0/0/0.pbf".to_string(), vec![1u8; 10]),
+        ("1/0/1.pbf".to_string(), vec![2u8; 512]),
+        (long_name.clone(), vec![3u8; 3]),
+    ];
+    let tar = tar_archive(&entries).unwrap();
+    assert_eq!(&tar[0..9], b"0/0/0.pbf");
+    assert_eq!(&tar[257..262], b"ustar");
+
+    let mut archive = tar::Archive::new(&tar[..]);
+    let mut read = Vec::new();
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let path = entry.path().unwrap().to_string_lossy().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).unwrap();
+        read.push((path, data));
+    }
+    assert_eq!(read, entries);
+}
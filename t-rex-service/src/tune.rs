@@ -0,0 +1,522 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Tile size sampling with suggestions for layer settings (`t_rex tune`)
+
+use crate::mvt_service::MvtService;
+use std::cmp;
+use std::collections::BTreeMap;
+use t_rex_core::core::attr_stats::AttributeStatistics;
+use t_rex_core::core::config_upgrade::{key_name, section_name};
+use t_rex_core::core::layer::Layer;
+use t_rex_core::mvt::tile::Tile;
+use t_rex_core::mvt::vector_tile;
+use tile_grid::ExtentInt;
+
+const NOTE: &str = "# t_rex tune:";
+/// Mean string length above which `max_string_length` is suggested
+const LONG_STRING_LEN: f64 = 100.0;
+
+pub struct TuneOptions {
+    pub tileset: Option<String>,
+    pub minzoom: Option<u8>,
+    pub maxzoom: Option<u8>,
+    /// Number of sampled tiles per zoom level
+    pub samples: u32,
+    /// Layer size budget per tile in bytes
+    pub max_layer_size: u64,
+    /// Number of features per tile and layer
+    pub max_features: u64,
+}
+
+impl Default for TuneOptions {
+    fn default() -> TuneOptions {
+        TuneOptions {
+            tileset: None,
+            minzoom: None,
+            maxzoom: Some(14),
+            samples: 16,
+            max_layer_size: 250 * 1024,
+            max_features: 5000,
+        }
+    }
+}
+
+/// Sizes of a layer in sampled tiles of a zoom level
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct ZoomSample {
+    pub tiles: u64,
+    /// Tiles containing features of the layer
+    pub nonempty: u64,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    /// Encoded geometry bytes
+    pub geometry_bytes: u64,
+    pub features: u64,
+    pub max_features: u64,
+}
+
+impl ZoomSample {
+    fn add_layer(&mut self, mvt_layer: &vector_tile::Tile_Layer) {
+        let bytes = Tile::layer_size(mvt_layer) as u64;
+        let features = mvt_layer.get_features().len() as u64;
+        self.nonempty += 1;
+        self.bytes += bytes;
+        self.max_bytes = cmp::max(self.max_bytes, bytes);
+        self.features += features;
+        self.max_features = cmp::max(self.max_features, features);
+        self.geometry_bytes += mvt_layer
+            .get_features()
+            .iter()
+            .flat_map(|f| f.get_geometry())
+            .map(|cmd| varint_len(*cmd))
+            .sum::<u64>();
+    }
+    fn exceeds(&self, options: &TuneOptions) -> bool {
+        self.max_bytes > options.max_layer_size || self.max_features > options.max_features
+    }
+}
+
+/// Sampled sizes of a layer
+pub struct LayerSamples {
+    pub tileset: String,
+    pub layer: Layer,
+    pub zooms: BTreeMap<u8, ZoomSample>,
+}
+
+/// Suggested layer setting or note (`value` None)
+#[derive(Clone, PartialEq, Debug)]
+pub struct Suggestion {
+    pub tileset: String,
+    pub layer: String,
+    pub key: String,
+    pub value: Option<String>,
+    pub reason: String,
+}
+
+fn varint_len(v: u32) -> u64 {
+    match v {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        0x4000..=0x1f_ffff => 3,
+        0x20_0000..=0xfff_ffff => 4,
+        _ => 5,
+    }
+}
+
+/// Evenly distributed tiles within limit (at most `samples`)
+pub fn sample_tiles(limit: &ExtentInt, samples: u32) -> Vec<(u32, u32)> {
+    let nx = limit.maxx.saturating_sub(limit.minx).max(1);
+    let ny = limit.maxy.saturating_sub(limit.miny).max(1);
+    let k = (samples as f64).sqrt().ceil().max(1.0) as u32;
+    let (kx, ky) = (cmp::min(k, nx), cmp::min(k, ny));
+    let mut tiles = Vec::new();
+    for i in 0..kx {
+        for j in 0..ky {
+            let x = limit.minx + ((2 * i + 1) as u64 * nx as u64 / (2 * kx) as u64) as u32;
+            let y = limit.miny + ((2 * j + 1) as u64 * ny as u64 / (2 * ky) as u64) as u32;
+            tiles.push((x, y));
+        }
+    }
+    tiles
+}
+
+/// Render sample tiles of all layers and collect their sizes and attribute statistics
+pub fn sample_layers(
+    service: &MvtService,
+    options: &TuneOptions,
+) -> (Vec<LayerSamples>, AttributeStatistics) {
+    let mut results = Vec::new();
+    let mut attr_stats = AttributeStatistics::new();
+    for tileset in &service.tilesets {
+        if options.tileset.as_ref().is_some_and(|n| n != &tileset.name) {
+            continue;
+        }
+        let minzoom = cmp::max(tileset.minzoom(), options.minzoom.unwrap_or(0));
+        let maxzoom = *[
+            tileset.maxzoom(),
+            options.maxzoom.unwrap_or(22),
+            service.grid.maxzoom(),
+        ]
+        .iter()
+        .min()
+        .unwrap();
+        let extent = service.extent_from_input_extent(tileset.get_extent(), None);
+        let limits = service.grid.tile_limits(extent, 0);
+        let mut samples: Vec<LayerSamples> = tileset
+            .layers
+            .iter()
+            .map(|layer| LayerSamples {
+                tileset: tileset.name.clone(),
+                layer: layer.clone(),
+                zooms: BTreeMap::new(),
+            })
+            .collect();
+        for zoom in minzoom..=maxzoom {
+            let tiles = sample_tiles(&limits[zoom as usize], options.samples);
+            info!(
+                "Tileset '{}' zoom {}: sampling {} tiles",
+                tileset.name,
+                zoom,
+                tiles.len()
+            );
+            for (x, y) in tiles {
+                let mvt_tile = service.tile(&tileset.name, x, y, zoom, None);
+                attr_stats.add_tile(&mvt_tile);
+                for sample in samples.iter_mut() {
+                    let layer = &sample.layer;
+                    if zoom < layer.minzoom() || zoom > layer.maxzoom(service.grid.maxzoom()) {
+                        continue;
+                    }
+                    let zoom_sample = sample.zooms.entry(zoom).or_default();
+                    zoom_sample.tiles += 1;
+                    if let Some(mvt_layer) = mvt_tile
+                        .get_layers()
+                        .iter()
+                        .find(|l| l.get_name() == layer.name)
+                    {
+                        zoom_sample.add_layer(mvt_layer);
+                    }
+                }
+            }
+        }
+        results.extend(samples);
+    }
+    (results, attr_stats)
+}
+
+/// Report lines with one layer and zoom level per line
+pub fn report(samples: &[LayerSamples]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for sample in samples {
+        for (zoom, zs) in &sample.zooms {
+            let avg = |total: u64| total / zs.nonempty.max(1);
+            lines.push(format!(
+                "Layer '{}/{}' zoom {}: {} of {} tiles with features, avg {} KB, max {} KB, avg {} features, max {} features, geometry {}%",
+                sample.tileset,
+                sample.layer.name,
+                zoom,
+                zs.nonempty,
+                zs.tiles,
+                avg(zs.bytes) / 1024,
+                zs.max_bytes / 1024,
+                avg(zs.features),
+                zs.max_features,
+                zs.geometry_bytes * 100 / zs.bytes.max(1)
+            ));
+        }
+    }
+    lines
+}
+
+/// Suggested layer settings for sampled sizes
+pub fn suggestions(
+    samples: &[LayerSamples],
+    attr_stats: &AttributeStatistics,
+    options: &TuneOptions,
+) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    for sample in samples {
+        let suggest = |key: &str, value: Option<String>, reason: String| Suggestion {
+            tileset: sample.tileset.clone(),
+            layer: sample.layer.name.clone(),
+            key: key.to_string(),
+            value,
+            reason,
+        };
+        let layer = &sample.layer;
+        let exceeding: Vec<(&u8, &ZoomSample)> = sample
+            .zooms
+            .iter()
+            .filter(|(_, zs)| zs.exceeds(options))
+            .collect();
+        // Lowest zoom level without exceeding tiles on this and higher levels
+        let last_exceeding = exceeding.last().map(|(z, _)| **z);
+        let sampled_maxzoom = sample.zooms.keys().last().copied();
+        if let (Some(last), Some(maxzoom)) = (last_exceeding, sampled_maxzoom) {
+            if last < maxzoom && last + 1 > layer.minzoom() {
+                let max_kb = exceeding.iter().map(|(_, zs)| zs.max_bytes).max().unwrap() / 1024;
+                let max_features = exceeding
+                    .iter()
+                    .map(|(_, zs)| zs.max_features)
+                    .max()
+                    .unwrap();
+                suggestions.push(suggest(
+                    "minzoom",
+                    Some((last + 1).to_string()),
+                    format!(
+                        "tiles up to zoom {} with up to {} KB and {} features (limits {} KB, {} features)",
+                        last,
+                        max_kb,
+                        max_features,
+                        options.max_layer_size / 1024,
+                        options.max_features
+                    ),
+                ));
+            }
+        }
+        let is_point = layer.geometry_type.as_deref() == Some("POINT");
+        if !exceeding.is_empty() && !is_point && !layer.simplify {
+            let (bytes, geometry_bytes) = exceeding.iter().fold((0, 0), |(b, g), (_, zs)| {
+                (b + zs.bytes, g + zs.geometry_bytes)
+            });
+            if geometry_bytes * 2 >= bytes {
+                suggestions.push(suggest(
+                    "simplify",
+                    Some("\"auto\"".to_string()),
+                    format!(
+                        "geometries are {}% of oversized tiles",
+                        geometry_bytes * 100 / bytes.max(1)
+                    ),
+                ));
+            }
+        }
+        let mut long_strings = Vec::new();
+        for attr in &attr_stats.attributes(&layer.name) {
+            let res = match attr_stats.results(&layer.name, attr) {
+                Some(res) => res,
+                None => continue,
+            };
+            if res.mean_string_len > LONG_STRING_LEN {
+                long_strings.push(format!("'{}' ({:.0})", attr, res.mean_string_len));
+            }
+            if res.high_cardinality {
+                suggestions.push(suggest(
+                    "",
+                    None,
+                    format!(
+                        "attribute '{}' has {}{} distinct values - consider removing it from the layer query",
+                        attr,
+                        if res.distinct_overflow { ">" } else { "" },
+                        res.distinct
+                    ),
+                ));
+            }
+        }
+        if !long_strings.is_empty() && layer.max_string_length.is_none() {
+            suggestions.push(suggest(
+                "max_string_length",
+                Some((LONG_STRING_LEN as usize).to_string()),
+                format!("mean string length of {}", long_strings.join(", ")),
+            ));
+        }
+    }
+    suggestions
+}
+
+/// TOML string value
+fn string_value(line: &str) -> Option<String> {
+    let value = line.split_once('=')?.1.trim();
+    Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+/// Config lines of a section with tileset and layer name of layer sections
+type Section = (Vec<String>, Option<(String, String)>);
+
+/// Apply suggestions to configuration with comments for review
+pub fn patch_config(config: &str, suggestions: &[Suggestion]) -> String {
+    let mut sections: Vec<Section> = vec![(Vec::new(), None)];
+    let mut tileset = String::new();
+    for line in config.lines() {
+        if let Some(name) = section_name(line) {
+            sections.push((Vec::new(), None));
+            if name == "tileset.layer" {
+                sections.last_mut().unwrap().1 = Some((tileset.clone(), String::new()));
+            }
+        } else if let Some("name") = key_name(line) {
+            let value = string_value(line).unwrap_or_default();
+            match sections.last_mut().unwrap() {
+                (_, Some((_, ref mut layer))) => *layer = value,
+                (ref lines, None) => {
+                    if lines.first().and_then(|l| section_name(l)) == Some("tileset") {
+                        tileset = value;
+                    }
+                }
+            }
+        }
+        sections.last_mut().unwrap().0.push(line.to_string());
+    }
+    let mut out: Vec<String> = Vec::new();
+    for (mut lines, layer) in sections {
+        if let Some((tileset, layer)) = layer {
+            let layer_suggestions = suggestions
+                .iter()
+                .filter(|s| s.tileset == tileset && s.layer == layer);
+            // Insert new keys before trailing empty lines
+            let mut end = lines.len();
+            while end > 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            let mut appended = Vec::new();
+            for s in layer_suggestions {
+                let note = format!("{} {}", NOTE, s.reason);
+                let value = match s.value {
+                    Some(ref value) => value,
+                    None => {
+                        appended.push(note);
+                        continue;
+                    }
+                };
+                let new_line = format!("{} = {}", s.key, value);
+                match lines[..end]
+                    .iter()
+                    .position(|l| key_name(l) == Some(s.key.as_str()))
+                {
+                    Some(pos) => {
+                        let old = format!("#{}", lines[pos].trim());
+                        lines.splice(pos..=pos, vec![note, old, new_line]);
+                        end += 2;
+                    }
+                    None => {
+                        appended.push(note);
+                        appended.push(new_line);
+                    }
+                }
+            }
+            lines.splice(end..end, appended);
+        }
+        out.extend(lines);
+    }
+    out.join("\n") + "\n"
+}
+
+#[cfg(test)]
+fn layer_samples(zooms: &[(u8, u64, u64)]) -> LayerSamples {
+    let mut layer = Layer::new("roads");
+    layer.geometry_type = Some("LINESTRING".to_string());
+    LayerSamples {
+        tileset: "osm".to_string(),
+        layer,
+        zooms: zooms
+            .iter()
+            .map(|(z, max_bytes, geometry_bytes)| {
+                let zs = ZoomSample {
+                    tiles: 4,
+                    nonempty: 1,
+                    bytes: *max_bytes,
+                    max_bytes: *max_bytes,
+                    geometry_bytes: *geometry_bytes,
+                    features: 10,
+                    max_features: 10,
+                };
+                (*z, zs)
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_sample_tiles() {
+    let limit = ExtentInt {
+        minx: 0,
+        miny: 0,
+        maxx: 1,
+        maxy: 1,
+    };
+    assert_eq!(sample_tiles(&limit, 16), vec![(0, 0)]);
+    let limit = ExtentInt {
+        minx: 10,
+        miny: 20,
+        maxx: 18,
+        maxy: 22,
+    };
+    assert_eq!(
+        sample_tiles(&limit, 4),
+        vec![(12, 20), (12, 21), (16, 20), (16, 21)]
+    );
+    assert_eq!(sample_tiles(&limit, 100).len(), 16);
+}
+
+#[test]
+fn test_suggestions() {
+    let options = TuneOptions {
+        max_layer_size: 1000,
+        ..Default::default()
+    };
+    let attr_stats = AttributeStatistics::new();
+    let samples = vec![layer_samples(&[
+        (4, 5000, 4000),
+        (5, 2000, 1500),
+        (6, 900, 700),
+    ])];
+    let result = suggestions(&samples, &attr_stats, &options);
+    assert_eq!(
+        result
+            .iter()
+            .map(|s| (s.key.as_str(), s.value.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("minzoom", Some("6")), ("simplify", Some("\"auto\""))]
+    );
+    assert_eq!(
+        result[0].reason,
+        "tiles up to zoom 5 with up to 4 KB and 10 features (limits 0 KB, 5000 features)"
+    );
+
+    // Exceeding maxzoom: no minzoom suggestion hiding the layer
+    let samples = vec![layer_samples(&[(4, 5000, 100), (5, 2000, 100)])];
+    assert!(suggestions(&samples, &attr_stats, &options).is_empty());
+}
+
+#[test]
+fn test_patch_config() {
+    let config = r#"[[tileset]]
+name = "osm"
+
+[[tileset.layer]]
+name = "roads"
+minzoom = 2
+
+[[tileset.layer.query]]
+minzoom = 10
+sql = "SELECT 1"
+
+[[tileset.layer]]
+name = "places"
+
+[cache.file]
+base = "/tmp"
+"#;
+    let suggestion = |layer: &str, key: &str, value: Option<&str>| Suggestion {
+        tileset: "osm".to_string(),
+        layer: layer.to_string(),
+        key: key.to_string(),
+        value: value.map(|v| v.to_string()),
+        reason: "test".to_string(),
+    };
+    let patched = patch_config(
+        config,
+        &[
+            suggestion("roads", "minzoom", Some("6")),
+            suggestion("roads", "simplify", Some("\"auto\"")),
+            suggestion("places", "", None),
+            suggestion("other", "minzoom", Some("1")),
+        ],
+    );
+    assert_eq!(
+        patched,
+        r#"[[tileset]]
+name = "osm"
+
+[[tileset.layer]]
+name = "roads"
+# t_rex tune: test
+#minzoom = 2
+minzoom = 6
+# t_rex tune: test
+simplify = "auto"
+
+[[tileset.layer.query]]
+minzoom = 10
+sql = "SELECT 1"
+
+[[tileset.layer]]
+name = "places"
+# t_rex tune: test
+
+[cache.file]
+base = "/tmp"
+"#
+    );
+}
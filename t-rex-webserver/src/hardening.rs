@@ -0,0 +1,265 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Safe defaults for public internet exposure (`t_rex serve --hardened`)
+
+use crate::core::config::ApplicationCfg;
+use crate::mvt_service::MvtService;
+use crate::server::client_addr;
+use actix_web::{web, HttpRequest, HttpResponse};
+use num_cpus;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_RATE_LIMIT: u32 = 50;
+/// Default of concurrently rendered tiles per core
+const DEFAULT_CONCURRENCY_PER_CORE: u32 = 4;
+/// Default maximal size of rendered tiles in KB
+const DEFAULT_MAX_TILE_SIZE: u32 = 2048;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+/// Number of tracked clients before expired windows are removed
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Requests per client in fixed windows of one second
+pub struct RateLimiter {
+    limit: u32,
+    clients: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32) -> RateLimiter {
+        RateLimiter {
+            limit,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+    /// Count `count` tile requests of client. Returns false if the client exceeded the limit.
+    pub fn allow(&self, client: &str, count: u32, now: Instant) -> bool {
+        let mut clients = self.clients.lock().unwrap();
+        if clients.len() >= MAX_TRACKED_CLIENTS {
+            clients.retain(|_, (start, _)| now.duration_since(*start) < RATE_LIMIT_WINDOW);
+        }
+        let window = clients.entry(client.to_string()).or_insert((now, 0));
+        if now.duration_since(window.0) >= RATE_LIMIT_WINDOW {
+            *window = (now, 0);
+        }
+        window.1 = window.1.saturating_add(count);
+        window.1 <= self.limit
+    }
+}
+
+/// Upper limit of tiles in rendering
+pub struct ConcurrencyLimit {
+    max: usize,
+    active: Arc<AtomicUsize>,
+}
+
+/// Tile in rendering, released on drop
+pub struct RenderSlot(Arc<AtomicUsize>);
+
+impl Drop for RenderSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ConcurrencyLimit {
+    pub fn new(max: usize) -> ConcurrencyLimit {
+        ConcurrencyLimit {
+            max,
+            active: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+    /// Reserve rendering of a tile. Returns None if the limit is reached.
+    pub fn acquire(&self) -> Option<RenderSlot> {
+        let active = self.active.fetch_add(1, Ordering::SeqCst);
+        let slot = RenderSlot(self.active.clone());
+        if active < self.max {
+            Some(slot)
+        } else {
+            None
+        }
+    }
+}
+
+/// Limits of the hardened serve profile
+pub struct Hardening {
+    rate_limiter: Option<RateLimiter>,
+    concurrency: Option<ConcurrencyLimit>,
+    /// Maximal size of rendered tiles in bytes
+    max_tile_size: Option<usize>,
+    /// Viewer, drilldown, layer samples and metrics enabled
+    pub debug_endpoints: bool,
+    unbounded_tilesets: bool,
+}
+
+impl Hardening {
+    /// Apply profile defaults to configuration
+    pub fn apply(config: &mut ApplicationCfg) -> Hardening {
+        let cfg = config.webserver.hardened.clone().unwrap_or_default();
+        if !cfg.admin_api && config.webserver.admin_token.take().is_some() {
+            warn!("Hardened profile: admin API disabled (enable with `admin_api = true`)");
+        }
+        let rate_limit = cfg.rate_limit.unwrap_or(DEFAULT_RATE_LIMIT);
+        let max_concurrency = cfg
+            .max_concurrency
+            .unwrap_or(num_cpus::get() as u32 * DEFAULT_CONCURRENCY_PER_CORE);
+        let max_tile_size = cfg.max_tile_size.unwrap_or(DEFAULT_MAX_TILE_SIZE);
+        if max_tile_size > 0 && config.service.mvt.tile_size_warning.is_none() {
+            // Log largest layer of refused tiles
+            config.service.mvt.tile_size_warning = Some(max_tile_size);
+        }
+        info!(
+            "Hardened profile: {} tile requests/s per client, {} concurrent renderings, {} KB maximal tile size",
+            rate_limit, max_concurrency, max_tile_size
+        );
+        Hardening {
+            rate_limiter: Some(rate_limit)
+                .filter(|&limit| limit > 0)
+                .map(RateLimiter::new),
+            concurrency: Some(max_concurrency as usize)
+                .filter(|&max| max > 0)
+                .map(ConcurrencyLimit::new),
+            max_tile_size: Some(max_tile_size as usize * 1024).filter(|&size| size > 0),
+            debug_endpoints: cfg.debug_endpoints,
+            unbounded_tilesets: cfg.unbounded_tilesets,
+        }
+    }
+    /// Check that requests are restricted to tileset extents
+    pub fn check_tilesets(&self, service: &MvtService) -> Result<(), String> {
+        if self.unbounded_tilesets {
+            return Ok(());
+        }
+        let unbounded: Vec<&str> = service
+            .tilesets
+            .iter()
+            .filter(|ts| ts.extent.is_none())
+            .map(|ts| ts.name.as_str())
+            .collect();
+        if unbounded.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Hardened profile: tilesets without extent: {} (allow with `unbounded_tilesets = true`)",
+                unbounded.join(", ")
+            ))
+        }
+    }
+}
+
+/// Hardened profile of server, if enabled
+fn hardening(req: &HttpRequest) -> Option<&Hardening> {
+    req.app_data::<web::Data<Hardening>>()
+        .map(|hardening| hardening.get_ref())
+}
+
+/// Count tile request of client
+pub fn check_rate_limit(req: &HttpRequest) -> Result<(), HttpResponse> {
+    check_rate_limit_tiles(req, 1)
+}
+
+/// Count each tile of a batch request against the rate limit of the client
+pub fn check_rate_limit_tiles(req: &HttpRequest, tiles: usize) -> Result<(), HttpResponse> {
+    let count = u32::try_from(tiles).unwrap_or(u32::MAX);
+    match hardening(req).and_then(|h| h.rate_limiter.as_ref()) {
+        Some(limiter) if !limiter.allow(&client_addr(req), count, Instant::now()) => {
+            Err(HttpResponse::TooManyRequests()
+                .content_type("text/plain")
+                .body("Rate limit exceeded"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reserve rendering of a tile
+pub fn acquire_render_slot(req: &HttpRequest) -> Result<Option<RenderSlot>, HttpResponse> {
+    match hardening(req).and_then(|h| h.concurrency.as_ref()) {
+        Some(limit) => limit
+            .acquire()
+            .map(Some)
+            .ok_or_else(|| HttpResponse::ServiceUnavailable().finish()),
+        None => Ok(None),
+    }
+}
+
+/// Maximal size of rendered tiles in bytes
+pub fn max_tile_size(req: &HttpRequest) -> Option<usize> {
+    hardening(req).and_then(|h| h.max_tile_size)
+}
+
+/// Check size of rendered tile
+pub fn check_tile_size(req: &HttpRequest, size: usize) -> Result<(), HttpResponse> {
+    match max_tile_size(req) {
+        Some(max) if size > max => {
+            warn!(
+                "{}: tile size {} KB exceeds max_tile_size - refused",
+                req.path(),
+                size / 1024
+            );
+            Err(HttpResponse::InternalServerError().finish())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_rate_limiter() {
+    let limiter = RateLimiter::new(2);
+    let now = Instant::now();
+    assert!(limiter.allow("10.0.0.1", 1, now));
+    assert!(limiter.allow("10.0.0.1", 1, now));
+    assert!(!limiter.allow("10.0.0.1", 1, now));
+    assert!(limiter.allow("10.0.0.2", 1, now));
+    // New window
+    assert!(limiter.allow("10.0.0.1", 1, now + Duration::from_millis(1000)));
+    // Tiles of batch requests
+    let later = now + Duration::from_millis(2000);
+    assert!(!limiter.allow("10.0.0.3", 3, later));
+    assert!(!limiter.allow("10.0.0.3", 1, later));
+    assert!(limiter.allow("10.0.0.4", 2, later));
+    assert!(!limiter.allow("10.0.0.4", 1, later));
+}
+
+#[test]
+fn test_concurrency_limit() {
+    let limit = ConcurrencyLimit::new(2);
+    let first = limit.acquire();
+    assert!(first.is_some());
+    let second = limit.acquire();
+    assert!(second.is_some());
+    assert!(limit.acquire().is_none());
+    drop(first);
+    assert!(limit.acquire().is_some());
+}
+
+#[test]
+fn test_apply_hardening() {
+    use crate::core::config::DEFAULT_CONFIG;
+    use crate::core::parse_config;
+
+    let mut config: ApplicationCfg = parse_config(DEFAULT_CONFIG.to_string(), "").unwrap();
+    config.webserver.admin_token = Some("secret".to_string());
+    let hardening = Hardening::apply(&mut config);
+    assert_eq!(config.webserver.admin_token, None);
+    assert_eq!(config.service.mvt.tile_size_warning, Some(2048));
+    assert_eq!(hardening.max_tile_size, Some(2048 * 1024));
+    assert!(hardening.rate_limiter.is_some());
+    assert!(!hardening.debug_endpoints);
+
+    let toml = format!(
+        "{}\n[webserver.hardened]\nrate_limit = 0\nmax_tile_size = 0\nadmin_api = true\n",
+        DEFAULT_CONFIG
+    );
+    let mut config: ApplicationCfg = parse_config(toml, "").unwrap();
+    config.webserver.admin_token = Some("secret".to_string());
+    let hardening = Hardening::apply(&mut config);
+    assert_eq!(config.webserver.admin_token, Some("secret".to_string()));
+    assert_eq!(config.service.mvt.tile_size_warning, None);
+    assert!(hardening.rate_limiter.is_none());
+    assert_eq!(hardening.max_tile_size, None);
+}
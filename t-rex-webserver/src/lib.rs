@@ -11,12 +11,17 @@ extern crate lazy_static;
 extern crate serde_derive;
 extern crate tile_grid;
 
-use t_rex_core::{cache, core, datasource, service};
-use t_rex_service::{datasources, mvt_service, read_qgs};
+use t_rex_core::{cache, core, datasource, mvt, service};
+use t_rex_service::{
+    datasources, mvt_service, prerender, raster_service, read_qgs, record, tile_batch,
+};
 
+mod hardening;
+mod metrics_server;
 mod runtime_config;
 mod server;
 mod static_files;
 
+pub use crate::metrics_server::serve_metrics;
 pub use crate::runtime_config::*;
 pub use crate::server::webserver;
@@ -0,0 +1,63 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! HTTP endpoint for service metrics outside of the web server (e.g. while seeding)
+
+use crate::mvt_service::MvtService;
+use crate::server::metrics;
+use actix_web::{rt, web, App, HttpServer};
+use std::net::{SocketAddr, TcpListener};
+use std::thread;
+
+/// Serve Prometheus metrics of `service` on `addr` in a background thread.
+/// Returns the bound address.
+pub fn serve_metrics(service: MvtService, addr: &str) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    info!("Serving metrics on http://{}/metrics", local_addr);
+    thread::spawn(move || {
+        let mut sys = rt::System::new("metrics");
+        let server = HttpServer::new(move || {
+            App::new()
+                .data(service.clone())
+                .route("/metrics", web::get().to(metrics))
+        })
+        .workers(1)
+        .listen(listener);
+        match server {
+            Ok(server) => {
+                if let Err(e) = sys.block_on(server.run()) {
+                    error!("Metrics server failed: {}", e);
+                }
+            }
+            Err(e) => error!("Metrics server failed: {}", e),
+        }
+    });
+    Ok(local_addr)
+}
+
+#[test]
+fn test_metrics_server() {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::sync::atomic::Ordering;
+    use t_rex_core::core::{read_config, Config};
+
+    let config = read_config("../t-rex-service/src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+    service.seeding.start();
+    service.seeding.tiles_rendered.store(42, Ordering::Relaxed);
+    let addr = serve_metrics(service, "127.0.0.1:0").unwrap();
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("\ntrex_seeding_tiles_total 42\n"));
+    assert!(response.contains("\ntrex_seeding_zoom 0\n"));
+}
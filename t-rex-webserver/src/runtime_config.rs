@@ -6,14 +6,20 @@
 use crate::cache::{Filecache, Nocache, Tilecache};
 use crate::core::config::{ApplicationCfg, DEFAULT_CONFIG};
 use crate::core::layer::Layer;
-use crate::core::{parse_config, read_config, Config};
+use crate::core::{parse_config, read_config_checked, Config};
 use crate::datasource::DatasourceType;
 use crate::datasources::Datasources;
-use crate::mvt_service::MvtService;
+use crate::mvt::tile::TileCompression;
+use crate::mvt_service::{
+    CrawlDetector, DataUpdated, GeometryErrors, LayerAccessStats, LayerCircuits, MemoryUsage,
+    MvtService, SeedingStats, TileRenderings, TileSizeBudget, TilesetAliases,
+};
 use crate::read_qgs;
+use crate::record::FixtureRecorder;
 use crate::service::tileset::Tileset;
 use crate::tile_grid::Grid;
 use clap::ArgMatches;
+use std::collections::HashMap;
 use std::process;
 use std::str::FromStr;
 
@@ -49,7 +55,8 @@ pub fn config_from_args(args: &ArgMatches) -> ApplicationCfg {
                 warn!("Ignoring argument `{}`", argname);
             }
         }
-        let config = read_config(cfgpath).unwrap_or_else(|err| {
+        let strict = args.is_present("strict-config");
+        let config = read_config_checked(cfgpath, strict).unwrap_or_else(|err| {
             println!("Error reading configuration - {} ", err);
             process::exit(1)
         });
@@ -66,6 +73,15 @@ pub fn config_from_args(args: &ArgMatches) -> ApplicationCfg {
 }
 
 pub fn service_from_args(config: &ApplicationCfg, args: &ArgMatches) -> MvtService {
+    let mut svc = service_from_config_or_args(config, args);
+    if let Some(dir) = args.value_of("record") {
+        info!("Recording retrieved features in '{}'", dir);
+        svc.record = Some(FixtureRecorder::new(dir));
+    }
+    svc
+}
+
+fn service_from_config_or_args(config: &ApplicationCfg, args: &ArgMatches) -> MvtService {
     if args.value_of("config").is_some() {
         let mut svc = MvtService::from_config(&config).unwrap_or_else(|err| {
             println!("Error reading configuration - {} ", err);
@@ -119,19 +135,54 @@ pub fn service_from_args(config: &ApplicationCfg, args: &ArgMatches) -> MvtServi
                         extent: extent,
                         center: None,
                         start_zoom: None,
+                        tags: Vec::new(),
                         layers: vec![l],
                         cache_limits: None,
+                        cache: None,
+                        access: None,
+                        time: None,
+                        content_type: None,
+                        crawl_protection: None,
+                        mbtiles: None,
+                        pmtiles: None,
                     };
                     tilesets.push(tileset);
                 }
             }
             datasources
         };
+        let compression = TileCompression::from_config(&config.service.mvt).unwrap_or_else(|err| {
+            println!("Error reading configuration - {} ", err);
+            process::exit(1)
+        });
         let mut svc = MvtService {
             datasources: datasources,
             grid: grid,
+            grid_name: config.grid.name().to_string(),
             tilesets: tilesets,
             cache: cache,
+            compression: compression,
+            size_budget: TileSizeBudget::new(config.service.mvt.tile_size_warning),
+            memory: MemoryUsage::new(config.service.mvt.memory_limit),
+            geometry_errors: GeometryErrors::default(),
+            layer_circuits: LayerCircuits::new(
+                config.service.mvt.layer_failure_threshold,
+                config.service.mvt.layer_failure_cooldown,
+            ),
+            layer_access: LayerAccessStats::default(),
+            data_updated: DataUpdated::default(),
+            crawl_detector: CrawlDetector::default(),
+            seeding: SeedingStats::default(),
+            content_digest: config.service.mvt.content_digest.unwrap_or(false),
+            deterministic: config.service.mvt.deterministic.unwrap_or(false),
+            coverage: HashMap::new(),
+            aliases: TilesetAliases::default(),
+            disabled: Vec::new(),
+            renderings: TileRenderings::default(),
+            archives: HashMap::new(),
+            render_lease: None,
+            prerender: None,
+            record: None,
         };
         svc.connect(); //TODO: ugly - we connect twice
         svc
@@ -144,10 +195,37 @@ pub fn gen_config(args: &ArgMatches) -> String {
 # Bind address. Use 0.0.0.0 to listen on all adresses.
 bind = "127.0.0.1"
 port = 6767
+# Number of HTTP workers (default: number of cores)
+#threads = 4
+# Threads rendering and encoding tiles (default: 5 x number of cores)
+#render_threads = 8
+# Additional tile routes with {tileset}, {z}, {x}, {y} and optional {time} placeholders
+#tile_paths = ["/services/{tileset}/tiles/{z}/{x}/{y}.mvt"]
+# Content of /robots.txt (default: disallow tilesets with crawl_protection)
+#robots_txt = "User-agent: *\nDisallow: /"
+
+# Background map and overlay tilesets of the built-in viewer
+#[webserver.viewer]
+#background_tiles = "https://tile.openstreetmap.org/{z}/{x}/{y}.png"
+#background_attribution = "© OpenStreetMap contributors"
+#background_style = "https://demotiles.maplibre.org/style.json"
+#overlays = ["tileset"]
+
+# Directory served under /static/ (custom viewers, landing pages, sprites)
+#static_dir = "./public/"
 
 #[[webserver.static]]
 #path = "/static"
 #dir = "./public/"
+
+# Safe defaults for public internet exposure (also enabled with `t_rex serve --hardened`)
+#[webserver.hardened]
+#rate_limit = 50 # Tile requests per second and client (0: unlimited)
+#max_concurrency = 32 # Tiles rendered concurrently (0: unlimited)
+#max_tile_size = 2048 # Maximal size of rendered tiles in KB (0: unlimited)
+#admin_api = true # Keep admin API enabled
+#debug_endpoints = true # Keep viewer, drilldown, layer samples and metrics enabled
+#unbounded_tilesets = true # Serve tilesets without configured extent
 "#;
     let mut config;
     if args.value_of("dbconn").is_some()
@@ -187,7 +265,7 @@ fn test_runtime_config() {
 
     env::var("DBCONN").expect("DBCONN undefined");
     let args = App::new("test")
-        .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME'")
+        .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME, MySQL connection mysql://USER@HOST/DBNAME, SQL Server connection mssql://USER@HOST/DBNAME, Oracle ORDS connection ords://USER@HOST/ords/SCHEMA, MongoDB connection mongodb://USER@HOST/DBNAME or Elasticsearch connection elasticsearch://USER@HOST'")
         .get_matches_from(vec!["", "--dbconn", &env::var("DBCONN").unwrap()]);
     let toml = gen_config(&args);
     println!("{}", toml);
@@ -3,10 +3,16 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::core::config::ApplicationCfg;
-use crate::mvt_service::MvtService;
+use crate::core::config::{ApplicationCfg, LayerCfg};
+use crate::core::Config;
+use crate::hardening::{self, Hardening};
+use crate::mvt_service::{content_digest, MvtService, TileOptions, TileRequestError};
+use crate::prerender::PrerenderQueue;
+use crate::raster_service::{self, RasterService};
 use crate::runtime_config::{config_from_args, service_from_args};
+use crate::service::tileset::Referrer;
 use crate::static_files::StaticFiles;
+use crate::tile_batch::{tar_archive, MAX_BATCH_TILES};
 use actix_cors::Cors;
 use actix_files as fs;
 use actix_web::dev::BodyEncoding;
@@ -19,8 +25,11 @@ use num_cpus;
 use open;
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::str;
 use std::str::FromStr;
+use tile_grid::Extent;
 
 static DINO: &'static str = "             xxxxxxxxx
         xxxxxxxxxxxxxxxxxxxxxxxx
@@ -52,6 +61,59 @@ async fn mvt_metadata(service: web::Data<MvtService>) -> Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(json))
 }
 
+#[derive(Deserialize)]
+struct CatalogParams {
+    tag: Option<String>,
+}
+
+async fn catalog(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    params: web::Query<CatalogParams>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let tile_paths: Vec<String> = config
+        .webserver
+        .tile_paths
+        .iter()
+        .filter(|path| check_tile_path(path).is_ok())
+        .cloned()
+        .collect();
+    let json = service
+        .get_catalog(&req_baseurl(&req), params.tag.as_deref(), &tile_paths)
+        .unwrap();
+    Ok(HttpResponse::Ok().json(json))
+}
+
+async fn openapi(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let json = service
+        .get_openapi(&req_baseurl(&req), config.service.mvt.viewer)
+        .unwrap();
+    Ok(HttpResponse::Ok().json(json))
+}
+
+pub(crate) async fn metrics(service: web::Data<MvtService>) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(service.prometheus_metrics()))
+}
+
+async fn robots_txt(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+) -> Result<HttpResponse> {
+    let robots = config
+        .webserver
+        .robots_txt
+        .clone()
+        .unwrap_or_else(|| service.robots_txt());
+    Ok(HttpResponse::Ok().content_type("text/plain").body(robots))
+}
+
 /// Font list for Maputnik
 async fn fontstacks() -> Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(["Roboto Medium", "Roboto Regular"]))
@@ -104,11 +166,20 @@ async fn tileset_tilejson(
 }
 
 async fn tileset_style_json(
+    config: web::Data<ApplicationCfg>,
     service: web::Data<MvtService>,
     tileset: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
     req: HttpRequest,
 ) -> Result<HttpResponse> {
-    let json = service.get_stylejson(&req_baseurl(&req), &tileset).unwrap();
+    let baseurl = req_baseurl(&req);
+    // Style of built-in viewer with configured background and overlays
+    let json = match (&config.webserver.viewer, query.get("viewer")) {
+        (Some(viewer), Some(_)) => service
+            .get_viewer_stylejson(&baseurl, &tileset, viewer)
+            .unwrap(),
+        _ => service.get_stylejson(&baseurl, &tileset).unwrap(),
+    };
     Ok(HttpResponse::Ok().json(json))
 }
 
@@ -123,17 +194,326 @@ async fn tileset_metadata_json(
     Ok(HttpResponse::Ok().json(json))
 }
 
+async fn layer_metadata_json(
+    service: web::Data<MvtService>,
+    params: web::Path<(String, String)>,
+) -> Result<HttpResponse> {
+    let (tileset, layer) = params.into_inner();
+    let json =
+        web::block::<_, _, Infallible>(move || Ok(service.get_layer_metadata(&tileset, &layer)))
+            .await
+            .unwrap();
+    match json {
+        Some(json) => Ok(HttpResponse::Ok().json(json)),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+#[derive(Deserialize)]
+struct SampleParams {
+    /// minx,miny,maxx,maxy in WGS84
+    bbox: Option<String>,
+    limit: Option<u32>,
+    token: Option<String>,
+}
+
+async fn layer_sample_json(
+    service: web::Data<MvtService>,
+    params: web::Path<(String, String)>,
+    query: web::Query<SampleParams>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let (tileset, layer) = params.into_inner();
+    let query = query.into_inner();
+    let referrer = request_referrer(&req);
+    match service.check_tile_access(&tileset, referrer, query.token.as_deref()) {
+        Err(TileRequestError::Forbidden) => return Ok(access_denied()),
+        Err(_) => return Ok(HttpResponse::NotFound().finish()),
+        Ok(()) => {}
+    }
+    let bbox = match query.bbox.as_deref().map(parse_bbox) {
+        Some(None) => {
+            return Ok(HttpResponse::BadRequest().body("Expected bbox=minx,miny,maxx,maxy"))
+        }
+        Some(extent) => extent,
+        None => None,
+    };
+    let authenticated = service.unlocks_protected_fields(&tileset, query.token.as_deref());
+    let sample = web::block::<_, _, Infallible>(move || {
+        Ok(service.get_layer_sample(&tileset, &layer, bbox.as_ref(), query.limit, authenticated))
+    })
+    .await
+    .unwrap();
+    match sample {
+        Some(Ok(json)) => Ok(HttpResponse::Ok()
+            .content_type("application/geo+json")
+            .body(json.to_string())),
+        Some(Err(e)) => {
+            warn!("{}", e);
+            Ok(HttpResponse::InternalServerError().finish())
+        }
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+async fn grid_json(
+    service: web::Data<MvtService>,
+    name: web::Path<String>,
+) -> Result<HttpResponse> {
+    match service.get_grid_metadata(&name) {
+        Some(json) => Ok(HttpResponse::Ok().json(json)),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+/// Reverse proxies whose `Forwarded` and `X-Forwarded-For` headers identify clients
+pub(crate) struct TrustedProxies(Vec<IpAddr>);
+
+impl TrustedProxies {
+    pub fn from_config(addrs: &[String]) -> Result<TrustedProxies, String> {
+        addrs
+            .iter()
+            .map(|addr| {
+                parse_ip(addr).ok_or_else(|| format!("Invalid trusted proxy address '{}'", addr))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(TrustedProxies)
+    }
+    fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.contains(ip)
+    }
+}
+
+/// IP address of client (without port).
+/// Proxy headers are only used for connections from trusted proxies, since clients can set them.
+pub(crate) fn client_addr(req: &HttpRequest) -> String {
+    let peer = match req.peer_addr() {
+        Some(addr) => addr.ip(),
+        None => return String::new(),
+    };
+    match req.app_data::<web::Data<TrustedProxies>>() {
+        Some(proxies) if proxies.contains(&peer) => {
+            forwarded_client(req, proxies).unwrap_or(peer).to_string()
+        }
+        _ => peer.to_string(),
+    }
+}
+
+/// Client address in forwarding chain: the last address not belonging to a trusted proxy
+fn forwarded_client(req: &HttpRequest, proxies: &TrustedProxies) -> Option<IpAddr> {
+    let headers = req.headers();
+    let values = |name| {
+        headers
+            .get_all(name)
+            .filter_map(|headerval| headerval.to_str().ok())
+    };
+    // RFC 7239: `Forwarded: for=192.0.2.60;proto=http, for="[2001:db8::1]:4711"`
+    let mut chain: Vec<&str> = values(header::FORWARDED)
+        .flat_map(|value| value.split(','))
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                Some(value).filter(|_| key.trim().eq_ignore_ascii_case("for"))
+            })
+        })
+        .collect();
+    if chain.is_empty() {
+        chain = values(header::HeaderName::from_static("x-forwarded-for"))
+            .flat_map(|value| value.split(','))
+            .collect();
+    }
+    let mut client = None;
+    for addr in chain.iter().rev() {
+        let ip = parse_ip(addr)?;
+        client = Some(ip);
+        if !proxies.contains(&ip) {
+            break;
+        }
+    }
+    client
+}
+
+/// IP address with optional port (`192.0.2.1:80`, `[2001:db8::1]:80`)
+fn parse_ip(addr: &str) -> Option<IpAddr> {
+    let addr = addr.trim().trim_matches('"');
+    addr.parse::<IpAddr>()
+        .ok()
+        .or_else(|| addr.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| addr.strip_prefix('[')?.strip_suffix(']')?.parse().ok())
+}
+
+/// Detect tile enumeration of client, identified like in rate limiting
+fn check_crawling(
+    service: &MvtService,
+    tileset: &str,
+    x: u32,
+    y: u32,
+    z: u8,
+    query: &HashMap<String, String>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let crawl_token = req
+        .headers()
+        .get("X-Crawl-Token")
+        .and_then(|headerval| headerval.to_str().ok())
+        .or_else(|| query.get("crawl_token").map(|t| t.as_str()));
+    match service.check_crawling(tileset, &client_addr(req), x, y, z, crawl_token) {
+        Err(TileRequestError::Crawling) => Err(HttpResponse::TooManyRequests()
+            .content_type("text/plain")
+            .body("Tile enumeration detected, crawl token required")),
+        _ => Ok(()),
+    }
+}
+
+/// Origin or Referer header of request
+fn request_referrer(req: &HttpRequest) -> Option<Referrer<'_>> {
+    let header_value = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|headerval| headerval.to_str().ok())
+    };
+    header_value(header::ORIGIN)
+        .map(Referrer::Origin)
+        .or_else(|| header_value(header::REFERER).map(Referrer::Referer))
+}
+
+/// Response to requests rejected by the referrer restriction of a tileset
+fn access_denied() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .header(header::VARY, "Origin, Referer")
+        .finish()
+}
+
+/// Vary header of tile responses negotiated from the Accept header or
+/// depending on the referrer restriction of the tileset
+fn tile_vary(vary_accept: bool, restricted: bool) -> Option<&'static str> {
+    match (vary_accept, restricted) {
+        (true, true) => Some("Accept, Origin, Referer"),
+        (true, false) => Some("Accept"),
+        (false, true) => Some("Origin, Referer"),
+        (false, false) => None,
+    }
+}
+
+/// Tile coordinates of tile routes
+#[derive(Deserialize)]
+struct TilePath {
+    tileset: String,
+    time: Option<String>,
+    z: u8,
+    x: u32,
+    y: u32,
+}
+
 async fn tile_pbf(
     config: web::Data<ApplicationCfg>,
     service: web::Data<MvtService>,
-    params: web::Path<(String, u8, u32, u32)>,
+    params: web::Path<TilePath>,
+    query: web::Query<HashMap<String, String>>,
     req: HttpRequest,
 ) -> Result<HttpResponse> {
     let params = params.into_inner();
-    let tileset = params.0;
-    let z = params.1;
-    let x = params.2;
-    let y = params.3;
+    if let Some(ref time) = params.time {
+        if service.check_tile_time(&params.tileset, time).is_err() {
+            return Ok(HttpResponse::NotFound().finish());
+        }
+    }
+    tile_response(
+        config,
+        service,
+        params.tileset,
+        params.time,
+        params.z,
+        params.x,
+        params.y,
+        query,
+        req,
+    )
+    .await
+}
+
+/// Raster tile or vector tile without extension
+async fn tile_without_extension(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    rasters: web::Data<RasterService>,
+    params: web::Path<TilePath>,
+    query: web::Query<HashMap<String, String>>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if rasters.tileset(&params.tileset).is_some() {
+        raster_tile(config, rasters, req).await
+    } else {
+        tile_pbf(config, service, params, query, req).await
+    }
+}
+
+/// Check URL template of a configured tile route
+fn check_tile_path(path: &str) -> Result<(), String> {
+    if !path.starts_with('/') {
+        return Err(format!("Tile path '{}' must start with '/'", path));
+    }
+    let mut placeholders = Vec::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or(format!("Unclosed placeholder in tile path '{}'", path))?;
+        let name = &rest[start + 1..start + end];
+        if !["tileset", "time", "z", "x", "y"].contains(&name) {
+            return Err(format!(
+                "Unknown placeholder {{{}}} in tile path '{}'",
+                name, path
+            ));
+        }
+        placeholders.push(name);
+        rest = &rest[start + end + 1..];
+    }
+    for name in &["tileset", "z", "x", "y"] {
+        if !placeholders.contains(name) {
+            return Err(format!(
+                "Placeholder {{{}}} missing in tile path '{}'",
+                name, path
+            ));
+        }
+    }
+    Ok(())
+}
+
+const PBF_CONTENT_TYPE: &str = "application/x-protobuf";
+const MVT_CONTENT_TYPE: &str = "application/vnd.mapbox-vector-tile";
+
+/// Content type of vector tiles from tileset configuration, extension or Accept header.
+/// Returns whether the content type was negotiated from the Accept header.
+fn tile_content_type(configured: Option<&str>, path: &str, accept: Option<&str>) -> (String, bool) {
+    if let Some(content_type) = configured {
+        (content_type.to_string(), false)
+    } else if path.ends_with(".mvt") {
+        (MVT_CONTENT_TYPE.to_string(), false)
+    } else if path.ends_with(".pbf") {
+        (PBF_CONTENT_TYPE.to_string(), false)
+    } else if accept.is_some_and(|accept| accept.contains(MVT_CONTENT_TYPE)) {
+        (MVT_CONTENT_TYPE.to_string(), true)
+    } else {
+        (PBF_CONTENT_TYPE.to_string(), true)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn tile_response(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    tileset: String,
+    time: Option<String>,
+    z: u8,
+    x: u32,
+    y: u32,
+    query: web::Query<HashMap<String, String>>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = hardening::check_rate_limit(&req) {
+        return Ok(resp);
+    }
     let gzip = req
         .headers()
         .get(header::ACCEPT_ENCODING)
@@ -144,25 +524,303 @@ async fn tile_pbf(
                 .and_then(|headerstr| Some(headerstr.contains("gzip")))
         })
         .unwrap_or(false);
+    match service.check_tile_request(&tileset, x, y, z) {
+        Err(TileRequestError::InvalidTile) => return Ok(HttpResponse::BadRequest().finish()),
+        Err(_) => return Ok(HttpResponse::NotFound().finish()),
+        Ok(()) => {}
+    }
+    let referrer = request_referrer(&req);
+    let token = query.get("token").map(|t| t.as_str());
+    if service
+        .check_tile_access(&tileset, referrer, token)
+        .is_err()
+    {
+        return Ok(access_denied());
+    }
+    if let Err(resp) = check_crawling(&service, &tileset, x, y, z, &query, &req) {
+        return Ok(resp);
+    }
+    service.record_access(&tileset, z);
+    // Forced re-rendering is restricted to the admin API token
+    let refresh = query.get("refresh").map(|v| v.as_str()) == Some("true");
+    if refresh {
+        if let Err(resp) = admin_authorized(&config, &req) {
+            return Ok(resp);
+        }
+    }
+    let cache_max_age = if refresh {
+        0
+    } else {
+        let default_max_age = config.webserver.cache_control_max_age.unwrap_or(300);
+        service
+            .get_tileset(&tileset)
+            .map(|ts| ts.cache_max_age(default_max_age))
+            .unwrap_or(default_max_age)
+    };
+    let configured_type = service
+        .get_tileset(&tileset)
+        .and_then(|ts| ts.content_type.clone());
+    let data_updated = service.tileset_updated(&tileset);
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|headerval| headerval.to_str().ok());
+    let (content_type, vary_accept) =
+        tile_content_type(configured_type.as_deref(), req.path(), accept);
+    let restricted = service
+        .get_tileset(&tileset)
+        .map_or(false, |ts| ts.access.is_some());
+    let vary = tile_vary(vary_accept, restricted);
+    let authenticated = service.unlocks_protected_fields(&tileset, token);
+    // Cache files of explicit times are not streamed
+    let streamable = !authenticated && time.is_none() && !refresh;
+    let digest = if !streamable {
+        None
+    } else {
+        service.cached_tile_digest(&tileset, x, y, z, gzip)
+    };
+    let cache_file = if !streamable {
+        None
+    } else {
+        service.tile_cache_file(&tileset, x, y, z, gzip)
+    };
+    if let Some(path) = cache_file {
+        // Stream cache file without reading it into memory
+        let file = fs::NamedFile::open(&path)?
+            .disable_content_disposition()
+            .set_content_encoding(ContentEncoding::Identity);
+        let mut resp = file.into_response(&req)?;
+        let headers = resp.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_str(&content_type).unwrap(),
+        );
+        if let Some(vary) = vary {
+            headers.insert(header::VARY, header::HeaderValue::from_static(vary));
+        }
+        if gzip {
+            headers.insert(
+                header::CONTENT_ENCODING,
+                header::HeaderValue::from_static("gzip"),
+            );
+        }
+        headers.insert(
+            header::CACHE_CONTROL,
+            header::HeaderValue::from_str(&format!("max-age={}", cache_max_age)).unwrap(),
+        );
+        if let Some(ref updated) = data_updated {
+            headers.insert(
+                header::HeaderName::from_static("x-data-updated"),
+                header::HeaderValue::from_str(updated).unwrap(),
+            );
+        }
+        // Only digests stored with the tile, without reading the file
+        if let Some(digest) = digest {
+            headers.insert(
+                header::HeaderName::from_static("content-digest"),
+                header::HeaderValue::from_str(&digest).unwrap(),
+            );
+        }
+        return Ok(resp);
+    }
+    let render_slot = match hardening::acquire_render_slot(&req) {
+        Ok(slot) => slot,
+        Err(resp) => return Ok(resp),
+    };
+    let with_digest = service.content_digest;
+    let options = TileOptions {
+        authenticated,
+        time,
+        refresh,
+        interactive: true,
+    };
+    // Database IO of layers with asynchronous datasources doesn't block a worker thread
+    let prefetched = service.prefetch_features(&tileset, x, y, z, &options).await;
     let tile = web::block::<_, _, Infallible>(move || {
-        Ok(service.tile_cached(&tileset, x, y, z, gzip, None))
+        Ok(service.tile_cached_prefetched(&tileset, x, y, z, gzip, None, &options, prefetched))
     })
     .await;
+    drop(render_slot);
 
     let resp = match tile {
         Ok(Some(tile)) => {
+            if let Err(resp) = hardening::check_tile_size(&req, tile.len()) {
+                return Ok(resp);
+            }
             let mut r = HttpResponse::Ok();
-            r.content_type("application/x-protobuf");
+            r.content_type(content_type);
+            if let Some(vary) = vary {
+                r.header(header::VARY, vary);
+            }
             if gzip {
                 // data is already gzip compressed
                 r.encoding(ContentEncoding::Identity)
                     .header(header::CONTENT_ENCODING, "gzip");
             }
-            let cache_max_age = config.webserver.cache_control_max_age.unwrap_or(300);
-            r.header(header::CACHE_CONTROL, format!("max-age={}", cache_max_age));
+            if authenticated {
+                // Don't share tiles with protected attributes in public caches
+                r.header(
+                    header::CACHE_CONTROL,
+                    format!("private, max-age={}", cache_max_age),
+                );
+            } else {
+                r.header(header::CACHE_CONTROL, format!("max-age={}", cache_max_age));
+            }
+            if let Some(updated) = data_updated {
+                r.header("X-Data-Updated", updated);
+            }
+            if with_digest {
+                let digest = digest.unwrap_or_else(|| content_digest(&tile));
+                r.header("Content-Digest", digest);
+            }
             r.body(tile) // TODO: chunked response
         }
+        Ok(None) => {
+            let mut r = HttpResponse::NoContent();
+            if let Some(vary) = vary {
+                r.header(header::VARY, vary);
+            }
+            r.finish()
+        }
+        Err(e) => {
+            error!("{}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    };
+    Ok(resp)
+}
+
+/// Check bearer token of admin API request. Admin API is disabled without configured token.
+fn admin_authorized(config: &ApplicationCfg, req: &HttpRequest) -> Result<(), HttpResponse> {
+    let admin_token = match config.webserver.admin_token {
+        Some(ref token) => token,
+        None => return Err(HttpResponse::NotFound().finish()),
+    };
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|headerval| headerval.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "));
+    if bearer == Some(admin_token.as_str()) {
+        Ok(())
+    } else {
+        Err(HttpResponse::Unauthorized().finish())
+    }
+}
+
+async fn admin_aliases(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    Ok(HttpResponse::Ok().json(service.aliases.list()))
+}
+
+async fn admin_disabled(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    Ok(HttpResponse::Ok().json(&service.disabled))
+}
+
+async fn admin_layer_stats(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    Ok(HttpResponse::Ok().json(service.layer_access_report()))
+}
+
+#[derive(Deserialize)]
+struct AliasRequest {
+    tileset: String,
+}
+
+#[derive(Serialize)]
+struct AliasResponse {
+    alias: String,
+    tileset: String,
+    /// Tileset of alias before switching
+    previous: Option<String>,
+}
+
+async fn admin_set_alias(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    alias: web::Path<String>,
+    body: web::Json<AliasRequest>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    match service.set_alias(&alias, &body.tileset) {
+        Ok(previous) => Ok(HttpResponse::Ok().json(AliasResponse {
+            alias: alias.into_inner(),
+            tileset: body.into_inner().tileset,
+            previous,
+        })),
+        Err(e) => Ok(HttpResponse::BadRequest().body(e)),
+    }
+}
+
+async fn admin_remove_alias(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    alias: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    match service.remove_alias(&alias) {
+        Some(_) => Ok(HttpResponse::NoContent().finish()),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+async fn admin_preview_tile(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    params: web::Path<(u8, u32, u32)>,
+    layer: web::Json<LayerCfg>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    let (z, x, y) = params.into_inner();
+    let gzip = req
+        .headers()
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|headerval| headerval.to_str().ok())
+        .map(|headerstr| headerstr.contains("gzip"))
+        .unwrap_or(false);
+    let layer = layer.into_inner();
+    let tile = web::block(move || service.preview_tile(&layer, x, y, z, gzip)).await;
+    let resp = match tile {
+        Ok(Some(tile)) => {
+            let mut r = HttpResponse::Ok();
+            r.content_type("application/x-protobuf");
+            if gzip {
+                r.encoding(ContentEncoding::Identity)
+                    .header(header::CONTENT_ENCODING, "gzip");
+            }
+            r.header(header::CACHE_CONTROL, "no-store");
+            r.body(tile)
+        }
         Ok(None) => HttpResponse::NoContent().finish(),
+        Err(actix_web::error::BlockingError::Error(e)) => HttpResponse::BadRequest().body(e),
         Err(e) => {
             error!("{}", e);
             HttpResponse::InternalServerError().finish()
@@ -171,6 +829,244 @@ async fn tile_pbf(
     Ok(resp)
 }
 
+#[derive(Deserialize)]
+struct TileBatchRequest {
+    /// List of [z, x, y] coordinates
+    tiles: Vec<(u8, u32, u32)>,
+}
+
+/// Tiles of a batch request with the checks of single tile requests.
+/// Each tile counts against the rate limit and crawl detection.
+async fn tile_batch(
+    service: web::Data<MvtService>,
+    tileset: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+    batch: web::Json<TileBatchRequest>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let tileset = tileset.into_inner();
+    if batch.tiles.len() > MAX_BATCH_TILES {
+        return Ok(HttpResponse::PayloadTooLarge()
+            .body(format!("Maximal number of tiles is {}", MAX_BATCH_TILES)));
+    }
+    if let Err(resp) = hardening::check_rate_limit_tiles(&req, batch.tiles.len()) {
+        return Ok(resp);
+    }
+    let referrer = request_referrer(&req);
+    let token = query.get("token").map(|t| t.as_str());
+    match service.check_tile_access(&tileset, referrer, token) {
+        Err(TileRequestError::Forbidden) => return Ok(access_denied()),
+        Err(_) => return Ok(HttpResponse::NotFound().finish()),
+        Ok(()) => {}
+    }
+    for &(z, x, y) in &batch.tiles {
+        if service.check_tile_request(&tileset, x, y, z).is_err() {
+            continue;
+        }
+        if let Err(resp) = check_crawling(&service, &tileset, x, y, z, &query, &req) {
+            return Ok(resp);
+        }
+        service.record_access(&tileset, z);
+    }
+    let options = TileOptions {
+        authenticated: service.unlocks_protected_fields(&tileset, token),
+        time: query.get("time").cloned(),
+        ..TileOptions::default()
+    };
+    let max_tile_size = hardening::max_tile_size(&req);
+    // Tiles are rendered one after another
+    let render_slot = match hardening::acquire_render_slot(&req) {
+        Ok(slot) => slot,
+        Err(resp) => return Ok(resp),
+    };
+    let tar = web::block(move || {
+        let tiles: Vec<(String, Vec<u8>)> = service
+            .tile_batch(&tileset, &batch.tiles, &options)
+            .into_iter()
+            .filter(|(path, tile)| match max_tile_size {
+                Some(max) if tile.len() > max => {
+                    warn!(
+                        "{}/{}: tile size {} KB exceeds max_tile_size - skipped",
+                        tileset,
+                        path,
+                        tile.len() / 1024
+                    );
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+        tar_archive(&tiles)
+    })
+    .await;
+    drop(render_slot);
+    let resp = match tar {
+        Ok(tar) => HttpResponse::Ok()
+            .content_type("application/x-tar")
+            .body(tar),
+        Err(e) => {
+            error!("{}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    };
+    Ok(resp)
+}
+
+/// Extent from `minx,miny,maxx,maxy`
+fn parse_bbox(bbox: &str) -> Option<Extent> {
+    let coords: Vec<f64> = bbox.split(',').filter_map(|v| v.parse().ok()).collect();
+    if coords.len() != 4 {
+        return None;
+    }
+    Some(Extent {
+        minx: coords[0],
+        miny: coords[1],
+        maxx: coords[2],
+        maxy: coords[3],
+    })
+}
+
+#[derive(Deserialize)]
+struct PackageParams {
+    /// minx,miny,maxx,maxy in WGS84
+    bbox: String,
+    minzoom: Option<u8>,
+    maxzoom: Option<u8>,
+    token: Option<String>,
+}
+
+/// Offline package of a tileset. Restricted to the admin API token, since tiles
+/// missing in the cache are rendered on request.
+async fn offline_package(
+    config: web::Data<ApplicationCfg>,
+    service: web::Data<MvtService>,
+    tileset: web::Path<String>,
+    params: web::Query<PackageParams>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = hardening::check_rate_limit(&req) {
+        return Ok(resp);
+    }
+    if let Err(resp) = admin_authorized(&config, &req) {
+        return Ok(resp);
+    }
+    let tileset = tileset.into_inner();
+    let params = params.into_inner();
+    let referrer = request_referrer(&req);
+    match service.check_tile_access(&tileset, referrer, params.token.as_deref()) {
+        Err(TileRequestError::Forbidden) => return Ok(access_denied()),
+        Err(_) => return Ok(HttpResponse::NotFound().finish()),
+        Ok(()) => {}
+    }
+    let extent = match parse_bbox(&params.bbox) {
+        Some(extent) => extent,
+        None => return Ok(HttpResponse::BadRequest().body("Expected bbox=minx,miny,maxx,maxy")),
+    };
+    let filename = format!("{}.pmtiles", tileset);
+    let package = web::block(move || {
+        service.offline_package(
+            &tileset,
+            &extent,
+            params.minzoom.unwrap_or(0),
+            params.maxzoom.unwrap_or(14),
+        )
+    })
+    .await;
+    let resp = match package {
+        Ok(package) => HttpResponse::Ok()
+            .content_type("application/vnd.pmtiles")
+            .header(
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            )
+            .body(package),
+        Err(e) => {
+            warn!("{}", e);
+            HttpResponse::BadRequest().body(e.to_string())
+        }
+    };
+    Ok(resp)
+}
+
+async fn raster_tile(
+    config: web::Data<ApplicationCfg>,
+    rasters: web::Data<RasterService>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    if let Err(resp) = hardening::check_rate_limit(&req) {
+        return Ok(resp);
+    }
+    let params = req.match_info();
+    let tileset = params.get("tileset").unwrap_or_default();
+    let zxy = (
+        params.get("z").and_then(|v| v.parse::<u8>().ok()),
+        params.get("x").and_then(|v| v.parse::<u32>().ok()),
+        params.get("y").and_then(|v| v.parse::<u32>().ok()),
+    );
+    let (z, x, y) = match zxy {
+        (Some(z), Some(x), Some(y)) => (z, x, y),
+        _ => return Ok(HttpResponse::BadRequest().finish()),
+    };
+    let extension = params.get("format");
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|headerval| headerval.to_str().ok());
+    let tileset = match rasters.tileset(tileset) {
+        Some(tileset) => tileset,
+        None => return Ok(HttpResponse::NotFound().finish()),
+    };
+    let format = match tileset.negotiate_format(extension, accept) {
+        Some(format) => format,
+        None => return Ok(HttpResponse::NotFound().finish()),
+    };
+    match tileset.tile_path(z, x, y, format) {
+        Some(path) => {
+            let file = fs::NamedFile::open(&path)?
+                .disable_content_disposition()
+                .set_content_encoding(ContentEncoding::Identity);
+            let mut resp = file.into_response(&req)?;
+            let headers = resp.headers_mut();
+            headers.insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static(raster_service::content_type(format)),
+            );
+            if extension.is_none() {
+                headers.insert(header::VARY, header::HeaderValue::from_static("Accept"));
+            }
+            let cache_max_age = config.webserver.cache_control_max_age.unwrap_or(300);
+            headers.insert(
+                header::CACHE_CONTROL,
+                header::HeaderValue::from_str(&format!("max-age={}", cache_max_age)).unwrap(),
+            );
+            Ok(resp)
+        }
+        None if tileset.hillshade.is_some() => {
+            let tileset = tileset.clone();
+            let rasters = rasters.clone();
+            let tile = web::block(move || tileset.render_hillshade(&rasters.grid, z, x, y)).await;
+            match tile {
+                Ok(Some(png)) => Ok(HttpResponse::Ok()
+                    .content_type("image/png")
+                    .header(
+                        header::CACHE_CONTROL,
+                        format!(
+                            "max-age={}",
+                            config.webserver.cache_control_max_age.unwrap_or(300)
+                        ),
+                    )
+                    .body(png)),
+                Ok(None) => Ok(HttpResponse::NotFound().finish()),
+                Err(e) => {
+                    error!("{}", e);
+                    Ok(HttpResponse::InternalServerError().finish())
+                }
+            }
+        }
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
 lazy_static! {
     static ref STATIC_FILES: StaticFiles = StaticFiles::init();
 }
@@ -188,11 +1084,70 @@ async fn static_file_handler(req: HttpRequest) -> Result<HttpResponse> {
     Ok(resp)
 }
 
+/// Path of a file in the static directory (`index.html` for directories).
+/// Parent and hidden path components are rejected.
+fn static_dir_path(dir: &str, tail: &str) -> Option<PathBuf> {
+    let mut path = PathBuf::from(dir);
+    for part in tail.split('/') {
+        if part.is_empty() {
+            continue;
+        }
+        if part.starts_with('.') || part.contains('\\') {
+            return None;
+        }
+        path.push(part);
+    }
+    if path.is_dir() {
+        path.push("index.html");
+    }
+    Some(path)
+}
+
+/// Content type of map specific file types unknown to the default mime type detection
+fn static_content_type(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "pbf" => Some("application/x-protobuf"),
+        "mvt" => Some("application/vnd.mapbox-vector-tile"),
+        "pmtiles" => Some("application/vnd.pmtiles"),
+        "geojson" => Some("application/geo+json"),
+        _ => None,
+    }
+}
+
+async fn static_dir_file(
+    config: web::Data<ApplicationCfg>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let dir = config.webserver.static_dir.as_deref().unwrap_or(".");
+    let path = match static_dir_path(dir, req.match_info().query("tail")) {
+        Some(path) if path.is_file() => path,
+        _ => return Ok(HttpResponse::NotFound().finish()),
+    };
+    // NamedFile handles ETag and Last-Modified conditional requests
+    let file = fs::NamedFile::open(&path)?.disable_content_disposition();
+    let mut resp = file.into_response(&req)?;
+    let headers = resp.headers_mut();
+    if let Some(content_type) = static_content_type(&path) {
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static(content_type),
+        );
+    }
+    let max_age = config.webserver.cache_control_max_age.unwrap_or(300);
+    headers.insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_str(&format!("max-age={}", max_age)).unwrap(),
+    );
+    Ok(resp)
+}
+
 #[derive(Deserialize)]
 struct DrilldownParams {
     minzoom: Option<u8>,
     maxzoom: Option<u8>,
     points: String, //x1,y1,x2,y2,..
+    /// Count features instead of generating tiles
+    count_only: Option<bool>,
 }
 
 async fn drilldown_handler(
@@ -210,14 +1165,33 @@ async fn drilldown_handler(
             //FIXME: map_err(|_| error::ErrorInternalServerError("...")
         })
         .collect();
-    let stats = service.drilldown(tileset, params.minzoom, params.maxzoom, points, progress);
+    let stats = service.drilldown(
+        tileset,
+        params.minzoom,
+        params.maxzoom,
+        points,
+        progress,
+        params.count_only.unwrap_or(false),
+    );
     let json = stats.as_json().unwrap();
     Ok(HttpResponse::Ok().json(json))
 }
 
 #[actix_web::main]
 pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
-    let config = config_from_args(&args);
+    let mut config = config_from_args(&args);
+    let hardening = if args.is_present("hardened") || config.webserver.hardened.is_some() {
+        Some(web::Data::new(Hardening::apply(&mut config)))
+    } else {
+        None
+    };
+    let debug_endpoints = hardening.as_ref().map_or(true, |h| h.debug_endpoints);
+    let trusted_proxies = TrustedProxies::from_config(&config.webserver.trusted_proxies)
+        .map(web::Data::new)
+        .map_err(|e| {
+            error!("{}", e);
+            std::io::Error::new(std::io::ErrorKind::Other, e)
+        })?;
     let host = config
         .webserver
         .bind
@@ -226,28 +1200,101 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
     let port = config.webserver.port.unwrap_or(6767);
     let bind_addr = format!("{}:{}", host, port);
     let workers = config.webserver.threads.unwrap_or(num_cpus::get() as u8);
-    let mvt_viewer = config.service.mvt.viewer;
+    if let Some(render_threads) = config.webserver.render_threads {
+        // Read by actix before its first blocking call
+        info!("Rendering tiles with {} threads", render_threads);
+        std::env::set_var("ACTIX_THREADPOOL", render_threads.to_string());
+    }
+    let mvt_viewer = config.service.mvt.viewer && debug_endpoints;
     let openbrowser =
         bool::from_str(args.value_of("openbrowser").unwrap_or("true")).unwrap_or(false);
     let static_dirs = config.webserver.static_.clone();
+    let static_dir = config.webserver.static_dir.clone().filter(|dir| {
+        let exists = Path::new(dir).is_dir();
+        if exists {
+            info!(
+                "Serving static files from directory '{}' under /static/",
+                dir
+            );
+        } else {
+            warn!("Static file directory '{}' not found", dir);
+        }
+        exists
+    });
+    let tile_paths: Vec<String> = config
+        .webserver
+        .tile_paths
+        .iter()
+        .filter(|path| match check_tile_path(path) {
+            Ok(_) => {
+                info!("Serving tiles from '{}'", path);
+                true
+            }
+            Err(e) => {
+                error!("{}", e);
+                false
+            }
+        })
+        .cloned()
+        .collect();
 
+    let prerender = match config.cache.as_ref().and_then(|c| c.prerender.as_ref()) {
+        Some(cfg) => Some(PrerenderQueue::from_config(cfg).map_err(|e| {
+            error!("{}", e);
+            std::io::Error::new(std::io::ErrorKind::Other, e)
+        })?),
+        None => None,
+    };
     let svc_config = config.clone();
     let service = web::block::<_, _, Infallible>(move || {
         let mut service = service_from_args(&svc_config, &args);
         service.prepare_feature_queries();
         service.init_cache();
+        if let Some(prerender) = prerender {
+            service.start_prerender(prerender);
+        }
+        if let Some(cfg) = svc_config.cache.as_ref().and_then(|c| c.notify.as_ref()) {
+            if let Err(e) = service.start_cache_invalidation(cfg) {
+                error!("{}", e);
+            }
+        }
         Ok(service)
     })
     .await
     .unwrap();
+    if let Some(ref hardening) = hardening {
+        if let Err(e) = hardening.check_tilesets(&service) {
+            error!("{}", e);
+            return Err(std::io::Error::new(std::io::ErrorKind::Other, e));
+        }
+    }
+
+    let rasters = RasterService::from_config(&config).unwrap_or_else(|err| {
+        error!("{}", err);
+        RasterService::default()
+    });
+    for raster in &rasters.tilesets {
+        info!(
+            "Serving raster tiles '{}' from directory '{}'",
+            raster.name, raster.path
+        );
+    }
 
     let server = HttpServer::new(move || {
         let mut app = App::new()
             .data(config.clone())
             .data(service.clone())
+            .data(rasters.clone())
+            .app_data(trusted_proxies.clone())
             .wrap(middleware::Logger::new("%r %s %b %Dms %a"))
             .wrap(Compress::default())
-            .wrap(Cors::default().send_wildcard().allowed_methods(vec!["GET"]))
+            .wrap(
+                Cors::default()
+                    .send_wildcard()
+                    .allowed_methods(vec!["GET", "POST"])
+                    .allowed_header(header::CONTENT_TYPE)
+                    .expose_headers(vec!["X-Data-Updated"]),
+            )
             .service(
                 web::resource("/index.json").route(
                     web::route()
@@ -255,6 +1302,47 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
                         .to(mvt_metadata),
                 ),
             )
+            .service(
+                web::resource("/catalog").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(catalog),
+                ),
+            )
+            .service(
+                web::resource("/openapi.json").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(openapi),
+                ),
+            )
+            .service(
+                web::resource("/robots.txt").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(robots_txt),
+                ),
+            );
+        if let Some(ref hardening) = hardening {
+            app = app.app_data(hardening.clone());
+        }
+        if debug_endpoints {
+            app = app.service(
+                web::resource("/metrics").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(metrics),
+                ),
+            );
+        }
+        app = app
+            .service(
+                web::resource("/grids/{name}.json").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(grid_json),
+                ),
+            )
             .service(
                 web::resource("/fontstacks.json").route(
                     web::route()
@@ -275,7 +1363,37 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
                         .guard(guard::Any(guard::Get()).or(guard::Head()))
                         .to(fonts_pbf),
                 ),
+            )
+            .service(
+                web::resource("/admin/aliases")
+                    .route(web::route().guard(guard::Get()).to(admin_aliases)),
+            )
+            .service(
+                web::resource("/admin/disabled")
+                    .route(web::route().guard(guard::Get()).to(admin_disabled)),
+            )
+            .service(
+                web::resource("/admin/layer-stats")
+                    .route(web::route().guard(guard::Get()).to(admin_layer_stats)),
+            )
+            .service(
+                web::resource("/admin/aliases/{alias}")
+                    .route(web::route().guard(guard::Put()).to(admin_set_alias))
+                    .route(web::route().guard(guard::Delete()).to(admin_remove_alias)),
+            )
+            .service(
+                web::resource("/admin/preview/{z}/{x}/{y}.pbf")
+                    .route(web::route().guard(guard::Post()).to(admin_preview_tile)),
+            );
+        if static_dir.is_some() {
+            app = app.service(
+                web::resource("/static/{tail:.*}").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(static_dir_file),
+                ),
             );
+        }
         for static_dir in &static_dirs {
             let dir = &static_dir.dir;
             if std::path::Path::new(dir).is_dir() {
@@ -285,6 +1403,15 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
                 warn!("Static file directory '{}' not found", dir);
             }
         }
+        for path in &tile_paths {
+            app = app.service(
+                web::resource(path).route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(tile_pbf),
+                ),
+            );
+        }
         app = app
             .service(
                 web::resource("/{tileset}.style.json").route(
@@ -300,6 +1427,23 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
                         .to(tileset_metadata_json),
                 ),
             )
+            .service(
+                web::resource("/{tileset}/{layer}/metadata.json").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(layer_metadata_json),
+                ),
+            );
+        if debug_endpoints {
+            app = app.service(
+                web::resource("/{tileset}/{layer}/sample.json").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(layer_sample_json),
+                ),
+            );
+        }
+        app = app
             .service(
                 web::resource("/{tileset}.json").route(
                     web::route()
@@ -308,11 +1452,43 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
                 ),
             )
             .service(
-                web::resource("/{tileset}/{z}/{x}/{y}.pbf").route(
+                web::resource("/{tileset}/package.pmtiles").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(offline_package),
+                ),
+            )
+            .service(
+                web::resource("/{tileset}/tiles")
+                    .route(web::route().guard(guard::Post()).to(tile_batch)),
+            )
+            .service(
+                web::resource("/{tileset}/{z}/{x}/{y}.{ext:(pbf|mvt)}").route(
                     web::route()
                         .guard(guard::Any(guard::Get()).or(guard::Head()))
                         .to(tile_pbf),
                 ),
+            )
+            .service(
+                web::resource("/{tileset}/{time}/{z}/{x}/{y}.{ext:(pbf|mvt)}").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(tile_pbf),
+                ),
+            )
+            .service(
+                web::resource("/{tileset}/{z}/{x}/{y}.{format:(png|jpg|jpeg|webp)}").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(raster_tile),
+                ),
+            )
+            .service(
+                web::resource("/{tileset}/{z}/{x}/{y:\\d+}").route(
+                    web::route()
+                        .guard(guard::Any(guard::Get()).or(guard::Head()))
+                        .to(tile_without_extension),
+                ),
             );
         if mvt_viewer {
             app = app.service(
@@ -342,3 +1518,299 @@ pub async fn webserver(args: ArgMatches<'static>) -> std::io::Result<()> {
 
     server.await
 }
+
+#[test]
+fn test_check_tile_path() {
+    assert_eq!(
+        check_tile_path("/services/{tileset}/tiles/{z}/{x}/{y}.mvt"),
+        Ok(())
+    );
+    assert_eq!(
+        check_tile_path("/arcgis/rest/services/{tileset}/VectorTileServer/tile/{z}/{y}/{x}.pbf"),
+        Ok(())
+    );
+    assert_eq!(check_tile_path("/{tileset}/{time}/{z}/{x}/{y}.mvt"), Ok(()));
+    assert_eq!(
+        check_tile_path("{tileset}/{z}/{x}/{y}"),
+        Err("Tile path '{tileset}/{z}/{x}/{y}' must start with '/'".to_string())
+    );
+    assert_eq!(
+        check_tile_path("/{tileset}/{z}/{x}.mvt"),
+        Err("Placeholder {y} missing in tile path '/{tileset}/{z}/{x}.mvt'".to_string())
+    );
+    assert_eq!(
+        check_tile_path("/{tileset}/{zoom}/{x}/{y}.mvt"),
+        Err("Unknown placeholder {zoom} in tile path '/{tileset}/{zoom}/{x}/{y}.mvt'".to_string())
+    );
+    assert!(check_tile_path("/{tileset}/{z}/{x}/{y").is_err());
+}
+
+#[test]
+fn test_tile_content_type() {
+    assert_eq!(
+        tile_content_type(None, "/osm/0/0/0.pbf", Some(MVT_CONTENT_TYPE)),
+        (PBF_CONTENT_TYPE.to_string(), false)
+    );
+    assert_eq!(
+        tile_content_type(None, "/osm/0/0/0.mvt", None),
+        (MVT_CONTENT_TYPE.to_string(), false)
+    );
+    assert_eq!(
+        tile_content_type(
+            None,
+            "/osm/0/0/0",
+            Some("application/vnd.mapbox-vector-tile,*/*")
+        ),
+        (MVT_CONTENT_TYPE.to_string(), true)
+    );
+    assert_eq!(
+        tile_content_type(None, "/osm/0/0/0", None),
+        (PBF_CONTENT_TYPE.to_string(), true)
+    );
+    assert_eq!(
+        tile_content_type(Some(MVT_CONTENT_TYPE), "/osm/0/0/0.pbf", None),
+        (MVT_CONTENT_TYPE.to_string(), false)
+    );
+}
+
+#[test]
+fn test_static_dir_path() {
+    assert_eq!(
+        static_dir_path("src", "static/viewer.js"),
+        Some(PathBuf::from("src/static/viewer.js"))
+    );
+    assert_eq!(
+        static_dir_path("src", "static/"),
+        Some(PathBuf::from("src/static/index.html"))
+    );
+    assert_eq!(
+        static_dir_path("src", ""),
+        Some(PathBuf::from("src/index.html"))
+    );
+    assert_eq!(
+        static_dir_path("src", "static//img/logo-color.svg"),
+        Some(PathBuf::from("src/static/img/logo-color.svg"))
+    );
+    assert_eq!(static_dir_path("src", "../Cargo.toml"), None);
+    assert_eq!(static_dir_path("src", "static/../../Cargo.toml"), None);
+    assert_eq!(static_dir_path("src", ".git/config"), None);
+
+    assert_eq!(
+        static_content_type(Path::new("fonts/Roboto/0-255.pbf")),
+        Some("application/x-protobuf")
+    );
+    assert_eq!(
+        static_content_type(Path::new("tiles/0/0/0.mvt")),
+        Some("application/vnd.mapbox-vector-tile")
+    );
+    assert_eq!(static_content_type(Path::new("index.html")), None);
+    assert_eq!(static_content_type(Path::new("README")), None);
+}
+
+#[test]
+fn test_request_referrer() {
+    use actix_web::test::TestRequest;
+
+    // Origin takes precedence in cross-origin requests
+    let req = TestRequest::default()
+        .header(header::ORIGIN, "https://example.com")
+        .header(header::REFERER, "https://example.com/map.html")
+        .to_http_request();
+    assert_eq!(
+        request_referrer(&req),
+        Some(Referrer::Origin("https://example.com"))
+    );
+    let req = TestRequest::default()
+        .header(header::REFERER, "https://example.com/map.html")
+        .to_http_request();
+    assert_eq!(
+        request_referrer(&req),
+        Some(Referrer::Referer("https://example.com/map.html"))
+    );
+    assert_eq!(
+        request_referrer(&TestRequest::default().to_http_request()),
+        None
+    );
+
+    assert_eq!(tile_vary(false, false), None);
+    assert_eq!(tile_vary(false, true), Some("Origin, Referer"));
+    assert_eq!(tile_vary(true, true), Some("Accept, Origin, Referer"));
+    assert_eq!(
+        access_denied().headers().get(header::VARY).unwrap(),
+        "Origin, Referer"
+    );
+}
+
+#[test]
+fn test_client_addr() {
+    use actix_web::test::TestRequest;
+
+    let proxies = TrustedProxies::from_config(&["10.0.0.1".to_string(), "::1".to_string()]);
+    let proxies = web::Data::new(proxies.unwrap());
+    let request = |peer: &str, headers: &[(header::HeaderName, &str)], trusted: bool| {
+        let mut req = TestRequest::default().peer_addr(peer.parse().unwrap());
+        for (name, value) in headers {
+            req = req.header(name.clone(), *value);
+        }
+        if trusted {
+            req = req.app_data(proxies.clone());
+        }
+        client_addr(&req.to_http_request())
+    };
+
+    assert_eq!(request("192.0.2.1:5123", &[], true), "192.0.2.1");
+    assert_eq!(request("[2001:db8::1]:5123", &[], true), "2001:db8::1");
+    // Spoofed headers of untrusted clients
+    let x_forwarded_for = header::HeaderName::from_static("x-forwarded-for");
+    let spoofed = [
+        (x_forwarded_for.clone(), "198.51.100.7"),
+        (header::FORWARDED, "for=198.51.100.7"),
+    ];
+    assert_eq!(request("192.0.2.1:5123", &spoofed, true), "192.0.2.1");
+    assert_eq!(request("10.0.0.1:5123", &spoofed, false), "10.0.0.1");
+    // Forwarded by trusted proxy
+    assert_eq!(
+        request(
+            "10.0.0.1:5123",
+            &[(x_forwarded_for.clone(), "198.51.100.7, 192.0.2.1")],
+            true
+        ),
+        "192.0.2.1"
+    );
+    assert_eq!(
+        request(
+            "[::1]:5123",
+            &[(
+                header::FORWARDED,
+                "for=198.51.100.7, for=\"[2001:db8::2]:4711\";proto=https"
+            )],
+            true
+        ),
+        "2001:db8::2"
+    );
+    assert_eq!(
+        request(
+            "10.0.0.1:5123",
+            &[(x_forwarded_for.clone(), "2001:db8::3, 10.0.0.1")],
+            true
+        ),
+        "2001:db8::3"
+    );
+    assert_eq!(
+        request(
+            "10.0.0.1:5123",
+            &[(x_forwarded_for.clone(), "unknown")],
+            true
+        ),
+        "10.0.0.1"
+    );
+    assert!(TrustedProxies::from_config(&["proxy.example.org".to_string()]).is_err());
+}
+
+#[test]
+fn test_crawling_client() {
+    use crate::core::read_config;
+    use crate::service::tileset::CrawlProtection;
+    use actix_web::test::TestRequest;
+
+    let config: ApplicationCfg = read_config("../t-rex-service/src/test/example.toml").unwrap();
+    let mut service = MvtService::from_config(&config).unwrap();
+    service.tilesets[0].crawl_protection = Some(CrawlProtection {
+        disallow: true,
+        max_sequential: 3,
+        window: 60,
+        token: None,
+    });
+    let query = HashMap::new();
+    // Row by row enumeration with a different spoofed address per request
+    let crawling: Vec<bool> = (0..5)
+        .map(|x| {
+            let req = TestRequest::default()
+                .peer_addr("192.0.2.1:5123".parse().unwrap())
+                .header("X-Forwarded-For", format!("198.51.100.{}", x))
+                .to_http_request();
+            check_crawling(&service, "osm", x, 0, 3, &query, &req).is_err()
+        })
+        .collect();
+    assert_eq!(crawling, vec![false, false, false, false, true]);
+}
+
+#[test]
+fn test_tile_batch_rate_limit() {
+    use crate::core::config::HardenedCfg;
+    use crate::core::read_config;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    let mut config: ApplicationCfg = read_config("../t-rex-service/src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+    config.webserver.hardened = Some(HardenedCfg {
+        rate_limit: Some(2),
+        ..HardenedCfg::default()
+    });
+    let hardening = web::Data::new(Hardening::apply(&mut config));
+    let request = |tiles: Vec<(u8, u32, u32)>| {
+        let req = TestRequest::default()
+            .peer_addr("192.0.2.1:5123".parse().unwrap())
+            .app_data(hardening.clone())
+            .to_http_request();
+        let resp = tile_batch(
+            web::Data::new(service.clone()),
+            web::Path::from("osm".to_string()),
+            web::Query(HashMap::new()),
+            web::Json(TileBatchRequest { tiles }),
+            req,
+        );
+        actix_web::rt::System::new("test")
+            .block_on(resp)
+            .unwrap()
+            .status()
+    };
+    // Each tile counts against the limit
+    assert_eq!(
+        request(vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[test]
+fn test_offline_package_admin_only() {
+    use crate::core::read_config;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    let mut config: ApplicationCfg = read_config("../t-rex-service/src/test/example.toml").unwrap();
+    let service = MvtService::from_config(&config).unwrap();
+    let request = |config: &ApplicationCfg, auth: Option<&str>| {
+        let mut req = TestRequest::default();
+        if let Some(auth) = auth {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+        let params = PackageParams {
+            bbox: "-180,-85,180,85".to_string(),
+            minzoom: Some(0),
+            maxzoom: Some(0),
+            token: None,
+        };
+        let resp = offline_package(
+            web::Data::new(config.clone()),
+            web::Data::new(service.clone()),
+            web::Path::from("osm".to_string()),
+            web::Query(params),
+            req.to_http_request(),
+        );
+        actix_web::rt::System::new("test")
+            .block_on(resp)
+            .unwrap()
+            .status()
+    };
+
+    // Disabled without admin token
+    assert_eq!(request(&config, None), StatusCode::NOT_FOUND);
+    config.webserver.admin_token = Some("secret".to_string());
+    assert_eq!(request(&config, None), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        request(&config, Some("Bearer wrong")),
+        StatusCode::UNAUTHORIZED
+    );
+}
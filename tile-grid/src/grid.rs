@@ -154,6 +154,47 @@ impl Grid {
         )
     }
 
+    /// OGC WorldCRS84Quad grid (two tiles at zoom level 0, origin top left)
+    pub fn world_crs84_quad() -> Grid {
+        Grid::new(
+            256,
+            256,
+            Extent {
+                minx: -180.0,
+                miny: -90.0,
+                maxx: 180.0,
+                maxy: 90.0,
+            },
+            4326,
+            Unit::Degrees,
+            // 180° / 256 pixels, halved for each level (exact in binary representation)
+            (0..24).map(|z| 0.703125 / (z as f64).exp2()).collect(),
+            Origin::TopLeft,
+        )
+    }
+
+    /// Swiss LV95 grid (EPSG:2056) matching the swisstopo WMTS tile matrix set
+    pub fn lv95() -> Grid {
+        Grid::new(
+            256,
+            256,
+            Extent {
+                minx: 2420000.0,
+                miny: 1030000.0,
+                maxx: 2900000.0,
+                maxy: 1350000.0,
+            },
+            2056,
+            Unit::Meters,
+            vec![
+                4000.0, 3750.0, 3500.0, 3250.0, 3000.0, 2750.0, 2500.0, 2250.0, 2000.0, 1750.0,
+                1500.0, 1250.0, 1000.0, 750.0, 650.0, 500.0, 250.0, 100.0, 50.0, 20.0, 10.0, 5.0,
+                2.5, 2.0, 1.5, 1.0, 0.5, 0.25, 0.1,
+            ],
+            Origin::TopLeft,
+        )
+    }
+
     pub fn new(
         width: u16,
         height: u16,
@@ -176,12 +217,29 @@ impl Grid {
         grid.level_max = grid.level_max();
         grid
     }
+    /// Set number of tiles per level explicitly, e.g. for WMTS tile matrix sets
+    /// with matrix sizes not derived from the grid extent.
+    pub fn set_matrix_sizes(&mut self, sizes: Vec<(u32, u32)>) {
+        self.level_max = sizes;
+    }
+    /// Tile width and height in pixels
+    pub fn tile_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+    /// Number of tiles (matrix width, matrix height) of grid level
+    pub fn matrix_size(&self, zoom: u8) -> (u32, u32) {
+        self.level_max[zoom as usize]
+    }
     pub fn nlevels(&self) -> u8 {
         self.resolutions.len() as u8
     }
     pub fn maxzoom(&self) -> u8 {
         self.nlevels() - 1
     }
+    /// Resolution of grid level in grid units per pixel
+    pub fn resolution(&self, zoom: u8) -> f64 {
+        self.resolutions[zoom as usize]
+    }
     /// Pixel width for 256x256 tile
     pub fn pixel_width(&self, zoom: u8) -> f64 {
         const METERS_PER_DEGREE: f64 = 6378137.0 * 2.0 * consts::PI / 360.0;
@@ -316,6 +374,13 @@ pub fn lonlat_to_merc(lon: f64, lat: f64) -> (f64, f64) {
     (x, y)
 }
 
+/// Returns the longitude and latitude of a Spherical Mercator (x, y) position
+pub fn merc_to_lonlat(x: f64, y: f64) -> (f64, f64) {
+    let lon = (x / 6378137.0).to_degrees();
+    let lat = (2.0 * (y / 6378137.0).exp().atan() - consts::PI * 0.5).to_degrees();
+    (lon, lat)
+}
+
 /// Projected extent
 pub fn extent_wgs84_to_merc(extent: &Extent) -> Extent {
     let (minx, miny) = lonlat_to_merc(extent.minx, extent.miny);
@@ -3,7 +3,7 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use crate::grid::{extent_wgs84_to_merc, lonlat_to_merc, Extent, ExtentInt, Grid};
+use crate::grid::{extent_wgs84_to_merc, lonlat_to_merc, merc_to_lonlat, Extent, ExtentInt, Grid};
 
 #[test]
 fn test_bbox() {
@@ -223,6 +223,8 @@ fn test_projected_extent() {
         lonlat_to_merc(extent_wgs84.minx, extent_wgs84.miny),
         (projected.minx, projected.miny)
     );
+    let (lon, lat) = merc_to_lonlat(projected.maxx, projected.maxy);
+    assert!((lon - 5.0).abs() < 1e-9 && (lat - 53.0).abs() < 1e-9);
 }
 
 mod web_mercator {
@@ -358,3 +360,97 @@ mod web_mercator {
         );
     }
 }
+
+#[test]
+fn test_matrix_sizes() {
+    use crate::grid::{Origin, Unit};
+
+    let mut grid = Grid::new(
+        512,
+        256,
+        Extent {
+            minx: 0.0,
+            miny: 0.0,
+            maxx: 2048.0,
+            maxy: 1024.0,
+        },
+        0,
+        Unit::Meters,
+        vec![2.0, 1.0],
+        Origin::BottomLeft,
+    );
+    assert_eq!(grid.matrix_size(0), (2, 2));
+    assert_eq!(grid.matrix_size(1), (4, 4));
+    assert_eq!(
+        grid.tile_extent(1, 1, 0),
+        Extent {
+            minx: 1024.0,
+            miny: 512.0,
+            maxx: 2048.0,
+            maxy: 1024.0,
+        }
+    );
+
+    grid.set_matrix_sizes(vec![(2, 1), (3, 3)]);
+    assert_eq!(grid.matrix_size(0), (2, 1));
+    let limits = grid.tile_limits(grid.extent.clone(), 0);
+    assert_eq!(
+        limits[1],
+        ExtentInt {
+            minx: 0,
+            miny: 0,
+            maxx: 3,
+            maxy: 3,
+        }
+    );
+}
+
+#[test]
+fn test_lv95_grid() {
+    let grid = Grid::lv95();
+
+    assert_eq!(grid.srid, 2056);
+    assert_eq!(grid.nlevels(), 29);
+    assert_eq!(grid.matrix_size(0), (1, 1));
+    assert_eq!(grid.matrix_size(20), (188, 125));
+    // swisstopo tile matrix 17, tile row 4, column 10
+    assert_eq!(
+        grid.tile_extent(10, 4, 17),
+        Extent {
+            minx: 2676000.,
+            miny: 1222000.,
+            maxx: 2701600.,
+            maxy: 1247600.,
+        }
+    );
+}
+
+#[test]
+fn test_world_crs84_quad_grid() {
+    let grid = Grid::world_crs84_quad();
+
+    assert_eq!(grid.nlevels(), 24);
+    assert_eq!(grid.tile_size(), (256, 256));
+    assert_eq!(grid.matrix_size(0), (2, 1));
+    assert_eq!(grid.matrix_size(1), (4, 2));
+    assert_eq!(
+        grid.tile_extent(1, 0, 0),
+        Extent {
+            minx: 0.0,
+            miny: -90.0,
+            maxx: 180.0,
+            maxy: 90.0,
+        }
+    );
+    // Row 0 is the northern row
+    assert_eq!(
+        grid.tile_extent(0, 0, 1),
+        Extent {
+            minx: -180.0,
+            miny: 0.0,
+            maxx: -90.0,
+            maxy: 90.0,
+        }
+    );
+    assert_eq!(grid.pixel_width(0), Grid::wgs84().pixel_width(0));
+}
@@ -70,5 +70,7 @@ mod grid_iterator;
 #[cfg(test)]
 mod grid_test;
 
-pub use grid::{extent_wgs84_to_merc, Extent, ExtentInt, Grid, Origin, Unit};
+pub use grid::{
+    extent_wgs84_to_merc, lonlat_to_merc, merc_to_lonlat, Extent, ExtentInt, Grid, Origin, Unit,
+};
 pub use grid_iterator::GridIterator;